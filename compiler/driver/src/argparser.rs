@@ -203,6 +203,11 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .long("no-warn")
                 .conflicts_with("warnings-as-errors"),
         )
+        .arg(
+            Arg::with_name("no-cache")
+                .help("Do not reuse or update the incremental compilation cache")
+                .long("no-cache"),
+        )
         .arg(
             Arg::with_name("verbose")
                 .help("Set verbosity level")
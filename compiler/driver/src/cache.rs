@@ -0,0 +1,629 @@
+//! A persistent, content-addressed cache of compiled modules
+//!
+//! The query database only memoizes within a single invocation of the compiler, so this
+//! cache is what allows `lumen compile` to skip inputs which have not changed since the
+//! last build. Each entry is keyed on a hash of the input source (and any headers it may
+//! include), the options which affect code generation, the target, and the commit hash of
+//! the compiler itself.
+//!
+//! Generated code refers to atoms by the id they were assigned by the symbol interner, so
+//! an object file can only be reused if every atom it references interns to the same id in
+//! the current session. To make that the common case, the atoms of every session are
+//! persisted alongside the cache, in the order of their ids, and replayed by name before
+//! compilation begins; entries are still validated when loaded, and any mismatch is treated
+//! as a cache miss.
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+
+use log::debug;
+
+use parking_lot::Mutex;
+
+use sha2::{Digest, Sha256};
+
+use libeir_intern::Symbol;
+
+use liblumen_core::symbols::FunctionSymbol;
+use liblumen_session::{Input, InputType, Options, OutputType};
+
+const CACHE_VERSION: &'static str = "lumen-cache v1";
+const MANIFEST_FILE: &'static str = "manifest";
+const OBJECT_FILE: &'static str = "module.o";
+const BITCODE_FILE: &'static str = "module.bc";
+const SYMBOLS_FILE: &'static str = "symbols";
+
+/// The key of a single entry in the cache, a hex-encoded SHA-256 digest
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+/// The contents of a cache entry which are needed to reconstitute a compiled module
+#[derive(Debug)]
+pub struct CacheEntry {
+    pub atoms: Vec<Symbol>,
+    pub symbols: Vec<FunctionSymbol>,
+    pub object: Option<PathBuf>,
+    pub bitcode: Option<PathBuf>,
+}
+
+pub struct CompilationCache {
+    dir: PathBuf,
+    options_hash: Vec<u8>,
+    /// The symbols replayed from previous sessions by `load_symbols`
+    loaded_symbols: Mutex<Vec<Symbol>>,
+}
+impl CompilationCache {
+    /// Returns the cache for the given options, or `None` if caching is disabled or
+    /// not applicable to the requested outputs
+    pub fn new(options: &Options) -> Option<Self> {
+        if options.no_cache {
+            return None;
+        }
+        // If the user has requested any intermediate outputs, those must be regenerated,
+        // which requires running the full pipeline anyway
+        let only_final_outputs = options.output_types.keys().all(|t| match t {
+            OutputType::Object | OutputType::LLVMBitcode | OutputType::Link => true,
+            _ => false,
+        });
+        if !only_final_outputs || !options.output_types.contains_key(&OutputType::Object) {
+            return None;
+        }
+
+        let dir = options.output_dir().join("incremental");
+
+        Some(Self::with_dir(dir, hash_options(options)))
+    }
+
+    fn with_dir(dir: PathBuf, options_hash: Vec<u8>) -> Self {
+        Self {
+            dir,
+            options_hash,
+            loaded_symbols: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Replays the symbols recorded by previous sessions so that they are assigned the same
+    /// ids as when the cached objects were generated.
+    ///
+    /// This must be called before anything else is interned in this session.
+    pub fn load_symbols(&self) -> anyhow::Result<()> {
+        let path = self.dir.join(SYMBOLS_FILE);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut loaded_symbols = self.loaded_symbols.lock();
+        for line in BufReader::new(file).lines() {
+            let s = decode_hex_str(&line?)?;
+            loaded_symbols.push(Symbol::intern(&s));
+        }
+        Ok(())
+    }
+
+    /// Persists the replayed symbols and the atoms of this session, in the order of their ids,
+    /// for use by future sessions
+    pub fn save_symbols<'a, A>(&self, atoms: A) -> anyhow::Result<()>
+    where
+        A: IntoIterator<Item = &'a Symbol>,
+    {
+        let mut symbols: Vec<Symbol> = self.loaded_symbols.lock().clone();
+        symbols.extend(atoms.into_iter().copied());
+        symbols.sort_by_key(|symbol| symbol.as_usize());
+        symbols.dedup();
+
+        let mut contents = String::new();
+        for symbol in symbols {
+            contents.push_str(&encode_hex_str(&symbol.as_str().get()));
+            contents.push('\n');
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        write_atomically(&self.dir.join(SYMBOLS_FILE), contents.as_bytes())
+    }
+
+    /// Computes the cache key for the given input, if it can be cached
+    pub fn key(&self, options: &Options, input: &Input) -> anyhow::Result<Option<CacheKey>> {
+        self.key_with_include_path(options.include_path.iter().cloned().collect(), input)
+    }
+
+    fn key_with_include_path(
+        &self,
+        include_path: Vec<PathBuf>,
+        input: &Input,
+    ) -> anyhow::Result<Option<CacheKey>> {
+        let path = match input {
+            Input::File(ref path) => path,
+            // Inputs from stdin are never cached
+            Input::Str { .. } => return Ok(None),
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.input(&self.options_hash);
+        hasher.input(input.get_type().to_string().as_bytes());
+        let source = fs::read(path)?;
+        hasher.input(&source);
+
+        // Changes to header files must invalidate the modules which include them, but the
+        // set of included files is not known until the module is parsed, so we conservatively
+        // hash every header which could possibly be included, plus those named by
+        // `-include_lib`, which are usually in subdirectories of the include path
+        if input.get_type() == InputType::Erlang {
+            let mut dirs = Vec::with_capacity(include_path.len() + 1);
+            if let Some(parent) = path.parent() {
+                dirs.push(parent.to_path_buf());
+            }
+            dirs.extend(include_path);
+            let mut headers: BTreeSet<PathBuf> = find_headers(&dirs)?.into_iter().collect();
+            headers.extend(find_included_headers(&source, &dirs)?);
+            for header in headers {
+                hasher.input(header.to_string_lossy().as_bytes());
+                hasher.input(&fs::read(&header)?);
+            }
+        }
+
+        Ok(Some(CacheKey(format!("{:x}", hasher.result()))))
+    }
+
+    /// Fetches an entry from the cache, if present and valid for this session
+    pub fn get(&self, key: &CacheKey) -> anyhow::Result<Option<CacheEntry>> {
+        let entry_dir = self.dir.join(&key.0);
+        let manifest = match File::open(entry_dir.join(MANIFEST_FILE)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut entry = CacheEntry {
+            atoms: Vec::new(),
+            symbols: Vec::new(),
+            object: None,
+            bitcode: None,
+        };
+
+        let mut lines = BufReader::new(manifest).lines();
+        // Names are only interned once the whole manifest has been read, and then in the
+        // order of their ids, stopping at the first mismatch, so that a stale entry interns
+        // as few names as possible
+        let mut atoms = Vec::new();
+        let mut symbols = Vec::new();
+        match lines.next() {
+            Some(Ok(ref version)) if version == CACHE_VERSION => (),
+            _ => return Ok(None),
+        }
+        for line in lines {
+            let line = line?;
+            let mut parts = line.split(' ');
+            match parts.next() {
+                Some("atom") => atoms.push(parse_symbol(&mut parts)?),
+                Some("symbol") => {
+                    let module = parse_symbol(&mut parts)?;
+                    let function = parse_symbol(&mut parts)?;
+                    let arity = parts
+                        .next()
+                        .ok_or_else(|| anyhow!("missing arity"))?
+                        .parse::<u8>()?;
+                    symbols.push((module, function, arity));
+                }
+                Some("object") => entry.object = Some(entry_dir.join(OBJECT_FILE)),
+                Some("bitcode") => entry.bitcode = Some(entry_dir.join(BITCODE_FILE)),
+                Some(other) => return Err(anyhow!("invalid manifest entry '{}'", other)),
+                None => continue,
+            }
+        }
+
+        // Functions are named by atoms of the same module, so checking the atoms is
+        // enough to know that the function symbols match too
+        atoms.sort_by_key(|(id, _)| *id);
+        let mut interned = HashMap::with_capacity(atoms.len());
+        for (id, name) in atoms.iter() {
+            match lookup_symbol(*id, name) {
+                Some(atom) => {
+                    interned.insert(*id, atom);
+                    entry.atoms.push(atom);
+                }
+                None => return Ok(None),
+            }
+        }
+        for (module, function, arity) in symbols {
+            match (interned.get(&module.0), interned.get(&function.0)) {
+                (Some(module), Some(function)) => entry.symbols.push(FunctionSymbol {
+                    module: module.as_usize(),
+                    function: function.as_usize(),
+                    arity,
+                    ptr: std::ptr::null(),
+                }),
+                _ => return Ok(None),
+            }
+        }
+
+        Ok(Some(entry))
+    }
+
+    /// Stores the outputs of a successful compilation in the cache
+    pub fn put<'a, A, S>(
+        &self,
+        key: &CacheKey,
+        atoms: A,
+        symbols: S,
+        object: Option<&Path>,
+        bitcode: Option<&Path>,
+    ) -> anyhow::Result<()>
+    where
+        A: Iterator<Item = &'a Symbol>,
+        S: Iterator<Item = &'a FunctionSymbol>,
+    {
+        use std::fmt::Write;
+
+        let entry_dir = self.dir.join(&key.0);
+        let staging_dir = self
+            .dir
+            .join(format!("{}.{}.tmp", &key.0, std::process::id()));
+        fs::create_dir_all(&staging_dir)
+            .with_context(|| format!("could not create {}", staging_dir.display()))?;
+
+        let mut manifest = String::new();
+        writeln!(&mut manifest, "{}", CACHE_VERSION)?;
+        // Function symbols only record the ids of their names, which are atoms of the module
+        let mut atoms_by_id = HashMap::new();
+        for atom in atoms {
+            writeln!(&mut manifest, "atom {}", format_symbol(*atom))?;
+            atoms_by_id.insert(atom.as_usize(), *atom);
+        }
+        for symbol in symbols {
+            let module = atom_by_id(&atoms_by_id, symbol.module)?;
+            let function = atom_by_id(&atoms_by_id, symbol.function)?;
+            writeln!(
+                &mut manifest,
+                "symbol {} {} {}",
+                format_symbol(module),
+                format_symbol(function),
+                symbol.arity
+            )?;
+        }
+        if let Some(object) = object {
+            fs::copy(object, staging_dir.join(OBJECT_FILE))?;
+            writeln!(&mut manifest, "object")?;
+        }
+        if let Some(bitcode) = bitcode {
+            fs::copy(bitcode, staging_dir.join(BITCODE_FILE))?;
+            writeln!(&mut manifest, "bitcode")?;
+        }
+        File::create(staging_dir.join(MANIFEST_FILE))?.write_all(manifest.as_bytes())?;
+
+        // Another session may have raced us to populate this entry, in which
+        // case we can keep theirs, since the contents are equivalent
+        if entry_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+            return Ok(());
+        }
+        if let Err(err) = fs::rename(&staging_dir, &entry_dir) {
+            fs::remove_dir_all(&staging_dir)?;
+            if !entry_dir.exists() {
+                return Err(err.into());
+            }
+        }
+
+        debug!("stored cache entry {}", &key.0);
+        Ok(())
+    }
+}
+
+/// Hashes the parts of the compiler configuration which affect generated code
+fn hash_options(options: &Options) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.input(CACHE_VERSION.as_bytes());
+    hasher.input(crate::LUMEN_COMMIT_HASH.as_bytes());
+    hasher.input(options.target.triple().as_bytes());
+    hasher.input(format!("{:?}", options.opt_level).as_bytes());
+    hasher.input(format!("{:?}", options.debug_info).as_bytes());
+    hasher.input(format!("{:?}", options.debug_assertions).as_bytes());
+    hasher.input(format!("{:?}", options.warnings_as_errors).as_bytes());
+    hasher.input(format!("{:?}", options.codegen_opts).as_bytes());
+    hasher.input(format!("{:?}", options.include_path).as_bytes());
+    hasher.input(format!("{:?}", options.source_path_prefix).as_bytes());
+
+    let mut defines = options.defines.iter().collect::<Vec<_>>();
+    defines.sort();
+    hasher.input(format!("{:?}", defines).as_bytes());
+
+    hasher.result().to_vec()
+}
+
+fn find_headers(dirs: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut headers = Vec::new();
+    for dir in dirs.iter() {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("hrl") {
+                headers.push(path);
+            }
+        }
+    }
+    headers.sort();
+    headers.dedup();
+    Ok(headers)
+}
+
+/// Finds the headers named by `-include` and `-include_lib` attributes in `source`, and in the
+/// headers they include, resolved against `dirs`
+fn find_included_headers(source: &[u8], dirs: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut found = HashSet::new();
+    let mut headers = Vec::new();
+    let mut pending = vec![source.to_vec()];
+
+    while let Some(source) = pending.pop() {
+        for name in included_header_names(&String::from_utf8_lossy(&source)) {
+            let resolved = dirs
+                .iter()
+                .map(|dir| dir.join(&name))
+                .find(|path| path.is_file());
+            if let Some(path) = resolved {
+                if found.insert(path.clone()) {
+                    pending.push(fs::read(&path)?);
+                    headers.push(path);
+                }
+            }
+        }
+    }
+
+    Ok(headers)
+}
+
+/// The file names of the `-include` and `-include_lib` attributes of `source`
+fn included_header_names(source: &str) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for line in source.lines() {
+        let line = line.trim_start();
+        let rest = if line.starts_with("-include_lib") {
+            &line["-include_lib".len()..]
+        } else if line.starts_with("-include") {
+            &line["-include".len()..]
+        } else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        if !rest.starts_with('(') {
+            continue;
+        }
+        let rest = rest[1..].trim_start();
+        if !rest.starts_with('"') {
+            continue;
+        }
+        if let Some(end) = rest[1..].find('"') {
+            names.push(rest[1..(end + 1)].to_string());
+        }
+    }
+
+    names
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    File::create(&tmp)?.write_all(contents)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn format_symbol(symbol: Symbol) -> String {
+    format!(
+        "{} {}",
+        symbol.as_usize(),
+        encode_hex_str(&symbol.as_str().get())
+    )
+}
+
+/// Parses the id and name of a symbol written by `format_symbol`
+fn parse_symbol<'a, I>(parts: &mut I) -> anyhow::Result<(usize, String)>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let id = parts
+        .next()
+        .ok_or_else(|| anyhow!("missing symbol id"))?
+        .parse::<usize>()?;
+    let name = decode_hex_str(parts.next().ok_or_else(|| anyhow!("missing symbol"))?)?;
+    Ok((id, name))
+}
+
+/// Interns `name`, returning `None` if it is not assigned `id` in this session
+fn lookup_symbol(id: usize, name: &str) -> Option<Symbol> {
+    let symbol = Symbol::intern(name);
+    if symbol.as_usize() == id {
+        Some(symbol)
+    } else {
+        debug!(
+            "cached symbol '{}' has id {}, but is {} in this session",
+            name,
+            id,
+            symbol.as_usize()
+        );
+        None
+    }
+}
+
+fn atom_by_id(atoms_by_id: &HashMap<usize, Symbol>, id: usize) -> anyhow::Result<Symbol> {
+    atoms_by_id
+        .get(&id)
+        .copied()
+        .ok_or_else(|| anyhow!("function symbol name {} is not an atom of the module", id))
+}
+
+fn encode_hex_str(s: &str) -> String {
+    use std::fmt::Write;
+
+    // Empty strings are encoded specially so that every line is non-empty
+    if s.is_empty() {
+        return "-".to_string();
+    }
+    let mut encoded = String::with_capacity(s.len() * 2);
+    for byte in s.bytes() {
+        write!(&mut encoded, "{:02x}", byte).unwrap();
+    }
+    encoded
+}
+
+fn decode_hex_str(s: &str) -> anyhow::Result<String> {
+    if s == "-" {
+        return Ok(String::new());
+    }
+    if s.len() % 2 != 0 {
+        return Err(anyhow!("invalid hex string '{}'", s));
+    }
+    let mut bytes = Vec::with_capacity(s.len() / 2);
+    for i in (0..s.len()).step_by(2) {
+        bytes.push(u8::from_str_radix(&s[i..(i + 2)], 16)?);
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Copies a cached file to the location the rest of the pipeline expects it to be
+pub fn restore_file(cached: &Path, dest: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(cached, dest)
+        .with_context(|| format!("could not restore {} from cache", dest.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("lumen_cache_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn put_then_get_round_trips_entry() {
+        let dir = temp_dir("round_trip");
+        let cache = CompilationCache::with_dir(dir.join("incremental"), vec![]);
+        let object = dir.join("foo.o");
+        fs::write(&object, b"object").unwrap();
+
+        let module = Symbol::intern("cache_round_trip_module");
+        let function = Symbol::intern("cache_round_trip_function");
+        let atoms = vec![module, function];
+        let symbols = vec![FunctionSymbol {
+            module: module.as_usize(),
+            function: function.as_usize(),
+            arity: 2,
+            ptr: std::ptr::null(),
+        }];
+        let key = CacheKey("round_trip".to_string());
+
+        cache
+            .put(&key, atoms.iter(), symbols.iter(), Some(&object), None)
+            .unwrap();
+        let entry = cache.get(&key).unwrap().unwrap();
+
+        assert_eq!(entry.atoms, atoms);
+        assert_eq!(entry.symbols.len(), 1);
+        assert_eq!(entry.symbols[0].module, module.as_usize());
+        assert_eq!(entry.symbols[0].function, function.as_usize());
+        assert_eq!(entry.symbols[0].arity, 2);
+        assert_eq!(fs::read(entry.object.unwrap()).unwrap(), b"object");
+        assert!(entry.bitcode.is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn get_with_mismatched_atom_id_misses() {
+        let dir = temp_dir("mismatch");
+        let cache = CompilationCache::with_dir(dir.clone(), vec![]);
+        let key = CacheKey("mismatch".to_string());
+        let atom = Symbol::intern("cache_mismatch_atom");
+        let entry_dir = dir.join(&key.0);
+        fs::create_dir_all(&entry_dir).unwrap();
+        fs::write(
+            entry_dir.join(MANIFEST_FILE),
+            format!(
+                "{}\natom {} {}\n",
+                CACHE_VERSION,
+                atom.as_usize() + 1,
+                encode_hex_str("cache_mismatch_atom")
+            ),
+        )
+        .unwrap();
+
+        assert!(cache.get(&key).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn save_then_load_symbols_replays_atoms_in_id_order() {
+        let dir = temp_dir("symbols");
+        let cache = CompilationCache::with_dir(dir.clone(), vec![]);
+        let first = Symbol::intern("cache_symbols_first");
+        let second = Symbol::intern("cache_symbols_second");
+
+        cache.save_symbols(vec![second, first].iter()).unwrap();
+
+        let contents = fs::read_to_string(dir.join(SYMBOLS_FILE)).unwrap();
+        let names: Vec<String> = contents
+            .lines()
+            .map(|line| decode_hex_str(line).unwrap())
+            .collect();
+        assert_eq!(names, vec!["cache_symbols_first", "cache_symbols_second"]);
+
+        let reloaded = CompilationCache::with_dir(dir.clone(), vec![]);
+        reloaded.load_symbols().unwrap();
+        assert_eq!(*reloaded.loaded_symbols.lock(), vec![first, second]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn key_changes_when_include_lib_header_changes() {
+        let dir = temp_dir("include_lib");
+        let source = dir.join("foo.erl");
+        fs::write(
+            &source,
+            "-module(foo).\n-include_lib(\"app/include/app.hrl\").\n",
+        )
+        .unwrap();
+        let lib = dir.join("lib");
+        let header = lib.join("app/include/app.hrl");
+        fs::create_dir_all(header.parent().unwrap()).unwrap();
+        fs::write(&header, "-define(VALUE, 1).\n").unwrap();
+
+        let cache = CompilationCache::with_dir(dir.join("incremental"), vec![]);
+        let input = Input::File(source);
+        let before = cache
+            .key_with_include_path(vec![lib.clone()], &input)
+            .unwrap();
+        fs::write(&header, "-define(VALUE, 2).\n").unwrap();
+        let after = cache.key_with_include_path(vec![lib], &input).unwrap();
+
+        assert!(before.is_some());
+        assert_ne!(before, after);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn included_header_names_finds_include_and_include_lib() {
+        let source = "-module(foo).\n-include(\"foo.hrl\").\n  -include_lib( \"app/include/app.hrl\").\n-define(INCLUDE, \"not.hrl\").\n";
+
+        assert_eq!(
+            included_header_names(source),
+            vec!["foo.hrl".to_string(), "app/include/app.hrl".to_string()]
+        );
+    }
+}
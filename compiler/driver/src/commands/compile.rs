@@ -17,6 +17,7 @@ use liblumen_session::{CodegenOptions, DebuggingOptions, Options};
 use liblumen_util::diagnostics::{CodeMap, Emitter};
 use liblumen_util::time::HumanDuration;
 
+use crate::cache::CompilationCache;
 use crate::commands::*;
 use crate::compiler::prelude::{Compiler as CompilerQueryGroup, *};
 use crate::compiler::Compiler;
@@ -41,8 +42,20 @@ pub fn handle_command<'a>(
     // Initialize codegen backend
     codegen::init(&options)?;
 
+    // Set up the incremental compilation cache, this must happen before anything
+    // else is interned so that symbols are assigned the same ids as in previous sessions
+    let cache = CompilationCache::new(&options).map(Arc::new);
+    if let Some(ref cache) = cache {
+        if let Err(err) = cache.load_symbols() {
+            diagnostics.warn(format!(
+                "unable to load incremental compilation cache, ignoring: {}",
+                err
+            ));
+        }
+    }
+
     // Build query database
-    let mut db = Compiler::new(codemap, diagnostics);
+    let mut db = Compiler::new(codemap, diagnostics, cache);
 
     // The core of the query system is the initial set of options provided to the compiler
     //
@@ -107,6 +120,17 @@ pub fn handle_command<'a>(
     let target_machine = db.get_target_machine(thread_id);
     let atoms = db.take_atoms();
     let symbols = db.take_symbols();
+
+    // Persist the atoms of this session for use by future sessions
+    if let Some(cache) = db.cache() {
+        if let Err(err) = cache.save_symbols(atoms.iter()) {
+            diagnostics.warn(format!(
+                "unable to update incremental compilation cache: {}",
+                err
+            ));
+        }
+    }
+
     codegen::generators::run(
         &options,
         &mut codegen_results,
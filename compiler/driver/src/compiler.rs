@@ -1,7 +1,7 @@
 mod queries;
 mod query_groups;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -17,6 +17,7 @@ use liblumen_core::symbols::FunctionSymbol;
 use liblumen_session::{Emit, Options, OutputType};
 use liblumen_util::diagnostics::{CodeMap, DiagnosticsHandler};
 
+use crate::cache::CompilationCache;
 use crate::diagnostics::*;
 use crate::interner::{InternedInput, Interner, InternerStorage};
 use crate::output::CompilerOutput;
@@ -40,9 +41,16 @@ pub struct Compiler {
     codemap: Arc<CodeMap>,
     atoms: Arc<Mutex<HashSet<Symbol>>>,
     symbols: Arc<Mutex<HashSet<FunctionSymbol>>>,
+    module_atoms: Arc<Mutex<HashMap<InternedInput, HashSet<Symbol>>>>,
+    module_symbols: Arc<Mutex<HashMap<InternedInput, HashSet<FunctionSymbol>>>>,
+    cache: Option<Arc<CompilationCache>>,
 }
 impl Compiler {
-    pub fn new(codemap: Arc<CodeMap>, diagnostics: Arc<DiagnosticsHandler>) -> Self {
+    pub fn new(
+        codemap: Arc<CodeMap>,
+        diagnostics: Arc<DiagnosticsHandler>,
+        cache: Option<Arc<CompilationCache>>,
+    ) -> Self {
         let mut atoms = HashSet::default();
         atoms.insert(Symbol::intern("false"));
         atoms.insert(Symbol::intern("true"));
@@ -52,6 +60,9 @@ impl Compiler {
             codemap,
             atoms: Arc::new(Mutex::new(atoms)),
             symbols: Arc::new(Mutex::new(HashSet::default())),
+            module_atoms: Arc::new(Mutex::new(HashMap::default())),
+            module_symbols: Arc::new(Mutex::new(HashMap::default())),
+            cache,
         }
     }
 }
@@ -72,6 +83,9 @@ impl salsa::ParallelDatabase for Compiler {
             codemap: self.codemap.clone(),
             atoms: self.atoms.clone(),
             symbols: self.symbols.clone(),
+            module_atoms: self.module_atoms.clone(),
+            module_symbols: self.module_symbols.clone(),
+            cache: self.cache.clone(),
         })
     }
 }
@@ -155,29 +169,55 @@ impl CompilerExt for Compiler {
         core::mem::replace(atoms, empty)
     }
 
-    fn add_atoms<'a, I>(&self, atoms: I)
+    fn add_atoms<'a, I>(&self, input: InternedInput, atoms: I)
     where
         I: Iterator<Item = &'a Symbol>,
     {
         let mut locked = self.atoms.lock();
+        let mut module_locked = self.module_atoms.lock();
+        let module_atoms = module_locked.entry(input).or_default();
         for i in atoms {
             locked.insert(*i);
+            module_atoms.insert(*i);
         }
     }
 
+    fn module_atoms(&self, input: InternedInput) -> HashSet<Symbol> {
+        self.module_atoms
+            .lock()
+            .get(&input)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn take_symbols(&mut self) -> HashSet<FunctionSymbol> {
         let symbols = Arc::get_mut(&mut self.symbols).unwrap().get_mut();
         let empty = HashSet::default();
         core::mem::replace(symbols, empty)
     }
 
-    fn add_symbols<'a, I>(&self, symbols: I)
+    fn add_symbols<'a, I>(&self, input: InternedInput, symbols: I)
     where
         I: Iterator<Item = &'a FunctionSymbol>,
     {
         let mut locked = self.symbols.lock();
+        let mut module_locked = self.module_symbols.lock();
+        let module_symbols = module_locked.entry(input).or_default();
         for i in symbols {
             locked.insert(*i);
+            module_symbols.insert(*i);
         }
     }
+
+    fn module_symbols(&self, input: InternedInput) -> HashSet<FunctionSymbol> {
+        self.module_symbols
+            .lock()
+            .get(&input)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn cache(&self) -> Option<&Arc<CompilationCache>> {
+        self.cache.as_ref()
+    }
 }
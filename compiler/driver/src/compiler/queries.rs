@@ -12,6 +12,8 @@ use liblumen_llvm::{self as llvm, target::TargetMachineConfig};
 use liblumen_mlir as mlir;
 use liblumen_session::{Input, InputType, OutputType};
 
+use crate::cache::{self, CacheKey};
+
 use super::prelude::*;
 
 /// Create context for LLVM
@@ -118,8 +120,8 @@ where
             )))
         }
         Ok(generated_module) => {
-            db.add_atoms(generated_module.atoms.iter());
-            db.add_symbols(generated_module.symbols.iter());
+            db.add_atoms(input, generated_module.atoms.iter());
+            db.add_symbols(input, generated_module.symbols.iter());
             db.maybe_emit_file_with_opts(&options, input, &generated_module.module)?;
             Ok(Arc::new(generated_module.module))
         }
@@ -136,7 +138,7 @@ where
     C: Compiler,
 {
     match db.input_type(input) {
-        InputType::Erlang | InputType::AbstractErlang | InputType::EIR => {
+        InputType::Erlang | InputType::AbstractErlang | InputType::Beam | InputType::EIR => {
             debug!("input {:?} is erlang", input);
             db.generate_mlir(thread_id, input)
         }
@@ -146,7 +148,7 @@ where
         }
         InputType::Unknown(None) => {
             debug!("unknown input type for {:?} on {:?}", input, thread_id);
-            db.report_error("invalid input, expected .erl or .mlir");
+            db.report_error("invalid input, expected .erl, .beam or .mlir");
             Err(ErrorReported)
         }
         InputType::Unknown(Some(ref ext)) => {
@@ -155,7 +157,7 @@ where
                 ext, input, thread_id
             );
             db.report_error(format!(
-                "invalid input extension ({}), expected .erl or .mlir",
+                "invalid input extension ({}), expected .erl, .beam or .mlir",
                 ext
             ));
             Err(ErrorReported)
@@ -302,6 +304,24 @@ where
     let source_name = input_info.source_name();
     let diagnostics = db.diagnostics();
 
+    // Reuse the outputs of a previous session if this input is unchanged
+    let cache_key = match db.cache() {
+        None => None,
+        Some(cache) => match cache.key(&options, &input_info) {
+            Ok(key) => key,
+            Err(err) => {
+                debug!("unable to compute cache key for {:?}: {}", input, err);
+                None
+            }
+        },
+    };
+    if let Some(ref key) = cache_key {
+        if let Some(compiled) = restore_from_cache(db, input, key)? {
+            diagnostics.success("Cached", format!("{}", &source_name));
+            return Ok(compiled);
+        }
+    }
+
     diagnostics.success("Compiling", format!("{}", &source_name));
     debug!(
         "compiling {:?} ({:?}) on thread {:?}",
@@ -337,6 +357,25 @@ where
         .maybe_emit(&input_info, OutputType::LLVMBitcode)
         .map(|filename| db.output_dir().join(filename));
 
+    // Store the outputs for reuse by future sessions, failure to do so is not fatal
+    if let Some(ref key) = cache_key {
+        let cache = db.cache().unwrap();
+        let atoms = db.module_atoms(input);
+        let symbols = db.module_symbols(input);
+        if let Err(err) = cache.put(
+            key,
+            atoms.iter(),
+            symbols.iter(),
+            obj_path.as_deref(),
+            bc_path.as_deref(),
+        ) {
+            diagnostics.warn(format!(
+                "unable to cache compilation results for {}: {}",
+                &source_name, err
+            ));
+        }
+    }
+
     let compiled = Arc::new(CompiledModule::new(
         input_info.file_stem().to_string_lossy().into_owned(),
         obj_path,
@@ -348,6 +387,69 @@ where
     Ok(compiled)
 }
 
+/// Attempts to satisfy a compilation request using the incremental cache, restoring
+/// the cached outputs to the locations they would have been emitted to
+fn restore_from_cache<C>(
+    db: &C,
+    input: InternedInput,
+    key: &CacheKey,
+) -> QueryResult<Option<Arc<CompiledModule>>>
+where
+    C: Compiler,
+{
+    let cache = db.cache().unwrap();
+    let entry = match cache.get(key) {
+        Ok(Some(entry)) => entry,
+        Ok(None) => {
+            debug!("cache miss for {:?}", input);
+            return Ok(None);
+        }
+        Err(err) => {
+            debug!("unable to read cache entry for {:?}: {}", input, err);
+            return Ok(None);
+        }
+    };
+
+    let options = db.options();
+    let input_info = db.lookup_intern_input(input);
+
+    let obj_path = match (
+        entry.object.as_ref(),
+        options.maybe_emit(&input_info, OutputType::Object),
+    ) {
+        (Some(cached), Some(dest)) => {
+            db.to_query_result(cache::restore_file(cached, &dest))?;
+            Some(dest)
+        }
+        (None, Some(_)) => return Ok(None),
+        (_, None) => None,
+    };
+    let bc_path = match (
+        entry.bitcode.as_ref(),
+        options
+            .output_types
+            .maybe_emit(&input_info, OutputType::LLVMBitcode)
+            .map(|filename| db.output_dir().join(filename)),
+    ) {
+        (Some(cached), Some(dest)) => {
+            db.to_query_result(cache::restore_file(cached, &dest))?;
+            Some(dest)
+        }
+        (None, Some(_)) => return Ok(None),
+        (_, None) => None,
+    };
+
+    debug!("cache hit for {:?}", input);
+    db.add_atoms(input, entry.atoms.iter());
+    db.add_symbols(input, entry.symbols.iter());
+
+    Ok(Some(Arc::new(CompiledModule::new(
+        input_info.file_stem().to_string_lossy().into_owned(),
+        obj_path,
+        bc_path,
+    ))))
+}
+
 fn get_input_source_name<C>(db: &C, input: InternedInput) -> Option<String>
 where
     C: Compiler,
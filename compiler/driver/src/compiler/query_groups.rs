@@ -7,6 +7,7 @@ use liblumen_core::symbols::FunctionSymbol;
 use liblumen_llvm as llvm;
 use liblumen_mlir as mlir;
 
+use crate::cache::CompilationCache;
 use crate::compiler::queries;
 use crate::diagnostics::QueryResult;
 use crate::interner::InternedInput;
@@ -71,11 +72,14 @@ pub trait Compiler: CompilerExt + Parser {
 
 pub trait CompilerExt: CompilerOutput {
     fn take_atoms(&mut self) -> HashSet<libeir_intern::Symbol>;
-    fn add_atoms<'a, I>(&self, atoms: I)
+    fn add_atoms<'a, I>(&self, input: InternedInput, atoms: I)
     where
         I: Iterator<Item = &'a libeir_intern::Symbol>;
+    fn module_atoms(&self, input: InternedInput) -> HashSet<libeir_intern::Symbol>;
     fn take_symbols(&mut self) -> HashSet<FunctionSymbol>;
-    fn add_symbols<'a, I>(&self, symbols: I)
+    fn add_symbols<'a, I>(&self, input: InternedInput, symbols: I)
     where
         I: Iterator<Item = &'a FunctionSymbol>;
+    fn module_symbols(&self, input: InternedInput) -> HashSet<FunctionSymbol>;
+    fn cache(&self) -> Option<&Arc<CompilationCache>>;
 }
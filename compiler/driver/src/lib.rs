@@ -1,4 +1,5 @@
 pub mod argparser;
+mod cache;
 mod commands;
 mod compiler;
 mod diagnostics;
@@ -38,7 +38,7 @@ where
         // We can get three types of input:
         //
         // 1. `-` for standard input
-        // 2. `path/to/file.erl` for a single file
+        // 2. `path/to/file.erl` (or `.abstr`, `.beam`, etc.) for a single file
         // 3. `path/to/dir` for a directory of files
         match input_file {
             // Read from standard input
@@ -113,9 +113,10 @@ where
     use libeir_frontend::erlang::ErlangFrontend;
 
     let codemap = db.codemap().clone();
-    let frontend: AnyFrontend = match db.input_type(input) {
+    let input_type = db.input_type(input);
+    let frontend: AnyFrontend = match input_type {
         InputType::Erlang => ErlangFrontend::new(db.parse_config(), codemap).into(),
-        InputType::AbstractErlang => AbstrErlangFrontend::new(codemap).into(),
+        InputType::AbstractErlang | InputType::Beam => AbstrErlangFrontend::new(codemap).into(),
         InputType::EIR => EirFrontend::new(codemap).into(),
         ty => {
             db.report_error(format!("invalid input type: {}", ty));
@@ -124,7 +125,16 @@ where
     };
 
     let (result, diags) = match db.lookup_intern_input(input) {
+        // BEAM files are lowered via the abstract code embedded in their debug info
+        Input::File(ref path) if input_type == InputType::Beam => {
+            let source = beam_abstract_source(db, path)?;
+            frontend.parse_string_dyn(&source)
+        }
         Input::File(ref path) => frontend.parse_file_dyn(path),
+        Input::Str { .. } if input_type == InputType::Beam => {
+            db.report_error("invalid input, .beam files must be read from disk");
+            return Err(ErrorReported);
+        }
         Input::Str { ref input, .. } => frontend.parse_string_dyn(input),
     };
 
@@ -145,6 +155,26 @@ where
     }
 }
 
+/// Extracts the abstract code from the `Abst` or `Dbgi` chunk of a BEAM file, rendered in the
+/// same format as `.abstr` files
+fn beam_abstract_source<P>(db: &P, path: &Path) -> QueryResult<String>
+where
+    P: Parser,
+{
+    use liblumen_beam::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    AbstractCode::from_beam_file(path)
+        .and_then(|code| code.to_source())
+        .map_err(|err| {
+            db.report_error(format!(
+                "unable to read abstract code from {}: {}",
+                path.to_string_lossy(),
+                err
+            ));
+            ErrorReported
+        })
+}
+
 pub(crate) fn input_eir<P>(db: &P, input: InternedInput) -> QueryResult<IRModule>
 where
     P: Parser,
@@ -191,16 +221,71 @@ where
 
     let walker = WalkDir::new(dir.as_ref()).follow_links(false).into_iter();
 
-    let mut inputs = Vec::new();
+    let mut paths = Vec::new();
 
     for maybe_entry in walker.filter_entry(is_valid_entry) {
         let entry = maybe_entry?;
         if entry.path().is_file() {
-            let input = Input::from(entry.path());
-            let interned = db.intern_input(input);
-            inputs.push(interned);
+            paths.push(entry.into_path());
         }
     }
 
+    let inputs = without_shadowed_beams(paths)
+        .into_iter()
+        .map(|path| db.intern_input(Input::from(path)))
+        .collect();
+
     Ok(inputs)
 }
+
+/// Removes `.beam` files whose module is also found as another kind of input, such as the
+/// `ebin/foo.beam` compiled from `src/foo.erl`, so that the module is not compiled twice.
+fn without_shadowed_beams(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    use std::collections::HashSet;
+    use std::ffi::OsStr;
+
+    fn is_beam(path: &Path) -> bool {
+        path.extension() == Some(OsStr::new("beam"))
+    }
+
+    let source_modules: HashSet<PathBuf> = paths
+        .iter()
+        .filter(|path| !is_beam(path))
+        .filter_map(|path| path.file_stem().map(PathBuf::from))
+        .collect();
+
+    paths
+        .into_iter()
+        .filter(|path| {
+            !is_beam(path)
+                || path
+                    .file_stem()
+                    .map(|stem| !source_modules.contains(Path::new(stem)))
+                    .unwrap_or(true)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::without_shadowed_beams;
+
+    #[test]
+    fn beam_with_source_is_skipped() {
+        let paths = vec![
+            PathBuf::from("app/ebin/foo.beam"),
+            PathBuf::from("app/src/foo.erl"),
+            PathBuf::from("app/ebin/bar.beam"),
+        ];
+
+        assert_eq!(
+            without_shadowed_beams(paths),
+            vec![
+                PathBuf::from("app/src/foo.erl"),
+                PathBuf::from("app/ebin/bar.beam"),
+            ]
+        );
+    }
+}
@@ -50,6 +50,7 @@ pub struct Options {
     pub color: ColorChoice,
     pub warnings_as_errors: bool,
     pub no_warn: bool,
+    pub no_cache: bool,
     pub verbosity: Verbosity,
 
     pub host: Target,
@@ -221,6 +222,7 @@ impl Options {
         }
         let warnings_as_errors = args.is_present("warnings-as-errors");
         let no_warn = args.is_present("no-warn");
+        let no_cache = args.is_present("no-cache") || debugging_opts.no_cache;
         let verbosity = Verbosity::from_level(args.occurrences_of("verbose") as isize);
         let mut include_path = VecDeque::new();
         if let Some(values) = args.values_of_os("include-paths") {
@@ -236,6 +238,7 @@ impl Options {
             color: color_arg.into(),
             warnings_as_errors,
             no_warn,
+            no_cache,
             verbosity,
             host,
             target,
@@ -313,6 +316,7 @@ impl Options {
             color: ColorChoice::Auto,
             warnings_as_errors: false,
             no_warn: false,
+            no_cache: debugging_opts.no_cache,
             verbosity: Verbosity::from_level(0),
             host,
             target,
@@ -32,6 +32,9 @@ pub struct DebuggingOptions {
     /// the same values as the target option of the same name
     pub merge_functions: Option<MergeFunctions>,
     #[option]
+    /// Disable the persistent incremental compilation cache
+    pub no_cache: bool,
+    #[option]
     /// Run all passes except codegen; no output
     pub no_codegen: bool,
     #[option]
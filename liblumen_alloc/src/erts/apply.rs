@@ -84,6 +84,24 @@ pub fn module_loaded(module: Atom) -> bool {
     symbols.contains_module(module)
 }
 
+/// The number of functions in the symbol table for which `predicate` is true.  No functions match
+/// when the symbol table was not initialized, such as when running tests.
+pub fn count_symbols<P>(predicate: P) -> usize
+where
+    P: Fn(&ModuleFunctionArity) -> bool,
+{
+    SYMBOLS
+        .get()
+        .map(|symbols| {
+            symbols
+                .functions
+                .keys()
+                .filter(|module_function_arity| predicate(module_function_arity))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
 /// The symbol table used by the runtime system
 static SYMBOLS: OnceCell<SymbolTable> = OnceCell::new();
 
@@ -3,6 +3,8 @@ use core::mem;
 use core::ptr::{self, NonNull};
 use core::str::Chars;
 
+use alloc::vec::Vec;
+
 use hashbrown::HashMap;
 
 use intrusive_collections::intrusive_adapter;
@@ -12,6 +14,7 @@ use liblumen_core::alloc::prelude::*;
 use liblumen_core::alloc::utils::{align_up_to, is_aligned, is_aligned_at};
 use liblumen_core::sys::sysconf::MIN_ALIGN;
 
+use crate::erts;
 use crate::erts::exception::AllocResult;
 use crate::erts::module_function_arity::Arity;
 use crate::erts::process::alloc::{Heap, HeapAlloc, TermAlloc};
@@ -19,7 +22,6 @@ use crate::erts::term::closure::{ClosureLayout, Creator, Index, OldUnique, Uniqu
 use crate::erts::term::prelude::*;
 use crate::scheduler;
 use crate::std_alloc;
-use crate::{erts, CloneToProcess};
 
 // This adapter is used to track a list of heap fragments, attached to a process
 intrusive_adapter!(pub HeapFragmentAdapter = UnsafeRef<HeapFragment>: HeapFragment { link: LinkedListLink });
@@ -305,25 +307,77 @@ impl HeapFragment {
     pub fn new_map_from_hash_map(
         hash_map: HashMap<Term, Term>,
     ) -> AllocResult<(Boxed<Map>, NonNull<Self>)> {
-        let map = Map::from_hash_map(hash_map);
-        let layout = Layout::for_value(&map);
-        let mut non_null_heap_fragment = Self::new(layout)?;
+        let entries: Vec<(Term, Term)> = hash_map.into_iter().collect();
+
+        Self::new_map_from_slice(&entries)
+    }
+
+    pub fn new_map_from_slice(slice: &[(Term, Term)]) -> AllocResult<(Boxed<Map>, NonNull<Self>)> {
+        let mut non_null_heap_fragment =
+            Self::new_from_word_size(Map::need_in_words_from_slice(slice))?;
         let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
 
-        let term = map.clone_to_heap(heap_fragment)?;
-        let boxed_map: Boxed<Map> = term.dyn_cast();
+        let boxed_map = Map::from_slice(heap_fragment, slice)?;
 
         Ok((boxed_map, non_null_heap_fragment))
     }
 
-    pub fn new_map_from_slice(slice: &[(Term, Term)]) -> AllocResult<(Boxed<Map>, NonNull<Self>)> {
-        let mut hash_map: HashMap<Term, Term> = HashMap::with_capacity(slice.len());
+    /// Returns `None` for the map if `key` is already associated with `value` in `map`
+    pub fn new_map_put(
+        map: Boxed<Map>,
+        key: Term,
+        value: Term,
+    ) -> AllocResult<(Option<Boxed<Map>>, NonNull<Self>)> {
+        let mut non_null_heap_fragment =
+            Self::new_from_word_size(map.need_in_words_to_modify(key, value))?;
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
 
-        for (entry_key, entry_value) in slice {
-            hash_map.insert(*entry_key, *entry_value);
-        }
+        let option_map = map.put(heap_fragment, key, value)?;
+
+        Ok((option_map, non_null_heap_fragment))
+    }
+
+    /// Returns `None` for the map if `key` is not in `map`
+    pub fn new_map_update(
+        map: Boxed<Map>,
+        key: Term,
+        value: Term,
+    ) -> AllocResult<(Option<Boxed<Map>>, NonNull<Self>)> {
+        let mut non_null_heap_fragment =
+            Self::new_from_word_size(map.need_in_words_to_modify(key, value))?;
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+        let option_map = map.update(heap_fragment, key, value)?;
+
+        Ok((option_map, non_null_heap_fragment))
+    }
+
+    /// Returns `None` for the map if `key` is not in `map`
+    pub fn new_map_remove(
+        map: Boxed<Map>,
+        key: Term,
+    ) -> AllocResult<(Option<Boxed<Map>>, NonNull<Self>)> {
+        let mut non_null_heap_fragment =
+            Self::new_from_word_size(map.need_in_words_to_modify(key, Term::NIL))?;
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+        let option_map = map.remove(heap_fragment, key)?;
+
+        Ok((option_map, non_null_heap_fragment))
+    }
+
+    /// Returns `None` for the value and map if `key` is not in `map`
+    pub fn new_map_take(
+        map: Boxed<Map>,
+        key: Term,
+    ) -> AllocResult<(Option<(Term, Boxed<Map>)>, NonNull<Self>)> {
+        let mut non_null_heap_fragment =
+            Self::new_from_word_size(map.need_in_words_to_modify(key, Term::NIL))?;
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+        let option_value_map = map.take(heap_fragment, key)?;
 
-        Self::new_map_from_hash_map(hash_map)
+        Ok((option_value_map, non_null_heap_fragment))
     }
 
     pub fn new_reference(
@@ -1,4 +1,5 @@
 use crate::erts::fragment;
+use crate::erts::process::SeqTraceToken;
 use crate::erts::term::prelude::Term;
 
 use intrusive_collections::UnsafeRef;
@@ -21,19 +22,33 @@ pub enum Message {
 impl Message {
     pub fn data(&self) -> &Term {
         match self {
-            Self::Process(Process { data }) => data,
+            Self::Process(Process { data, .. }) => data,
             Self::HeapFragment(HeapFragment { data, .. }) => data,
         }
     }
+
+    /// The sequential trace token of the sender when the message was sent
+    pub fn seq_trace_token(&self) -> Option<SeqTraceToken> {
+        match self {
+            Self::Process(Process {
+                seq_trace_token, ..
+            }) => *seq_trace_token,
+            Self::HeapFragment(HeapFragment {
+                seq_trace_token, ..
+            }) => *seq_trace_token,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Process {
     pub data: Term,
+    pub seq_trace_token: Option<SeqTraceToken>,
 }
 
 #[derive(Debug)]
 pub struct HeapFragment {
     pub unsafe_ref_heap_fragment: UnsafeRef<fragment::HeapFragment>,
     pub data: Term,
+    pub seq_trace_token: Option<SeqTraceToken>,
 }
@@ -8,9 +8,14 @@ mod frames;
 pub mod gc;
 mod heap;
 mod mailbox;
+mod max_heap_size;
+mod message_queue_data;
 mod monitor;
 pub mod priority;
+mod saved_calls;
+mod seq_trace;
 pub mod trace;
+mod tracing;
 
 use core::cell::RefCell;
 use core::convert::TryInto;
@@ -56,12 +61,20 @@ pub use self::frame::{Frame, Native};
 pub use self::frame_with_arguments::FrameWithArguments;
 pub use self::frames::{Frames, StackTrace};
 use self::gc::{GcError, RootSet};
+use self::priority::AtomicPriority;
 
 pub use self::flags::*;
-pub use self::heap::ProcessHeap;
+pub use self::heap::{GarbageCollectionInfo, ProcessHeap};
 pub use self::mailbox::*;
+pub use self::max_heap_size::MaxHeapSize;
+pub use self::message_queue_data::MessageQueueData;
 pub use self::monitor::Monitor;
 pub use self::priority::Priority;
+pub use self::saved_calls::SavedCalls;
+pub use self::seq_trace::{SeqTraceFlags, SeqTraceSerial, SeqTraceToken};
+pub use self::tracing::{
+    set_trace_event_handler, TraceEvent, TraceEventHandler, TraceFlags, Tracing,
+};
 use crate::erts::process::ffi::process_error;
 
 // 4000 in [BEAM](https://github.com/erlang/otp/blob/61ebe71042fce734a06382054690d240ab027409/erts/emulator/beam/erl_vm.h#L39)
@@ -102,15 +115,15 @@ pub struct Process {
     /// ID of the scheduler that is running the process
     scheduler_id: Mutex<Option<scheduler::ID>>,
     /// The priority of the process in `scheduler`.
-    pub priority: Priority,
+    priority: AtomicPriority,
     /// Process flags, e.g. `Process.flag/1`
     flags: AtomicProcessFlags,
     /// Minimum size of the heap that this process will start with
-    min_heap_size: usize,
+    min_heap_size: AtomicUsize,
     /// The maximum size of the heap allowed for this process
-    max_heap_size: usize,
+    max_heap_size: Mutex<MaxHeapSize>,
     /// Minimum virtual heap size for this process
-    min_vheap_size: usize,
+    min_vheap_size: AtomicUsize,
     /// The percentage of used to unused space at which a collection is triggered
     gc_threshold: f64,
     /// The maximum number of minor collections before a full sweep occurs
@@ -141,13 +154,31 @@ pub struct Process {
     pub monitor_by_reference: DashMap<Reference, Monitor>,
     /// Maps monitor references to the PID of the process being monitored by this process.
     pub monitored_pid_by_reference: DashMap<Reference, Pid>,
-    pub mailbox: Mutex<RefCell<Mailbox>>,
+    mailbox: Mutex<RefCell<Mailbox>>,
+    /// Messages sent while `message_queue_data` is `off_heap` that have not been moved into
+    /// `mailbox` yet.
+    off_heap_messages: OffHeapQueue,
+    /// The module whose `undefined_function/3` is called for undefined functions
+    error_handler: Mutex<Atom>,
+    /// The most recent calls, as enabled with `process_flag(save_calls, N)`
+    saved_calls: Mutex<SavedCalls>,
+    /// The sequential trace token passed along with sent messages, as set with
+    /// `seq_trace:set_token/1` or received with a message
+    seq_trace_token: Mutex<Option<SeqTraceToken>>,
+    /// The tracer and flags set with `erlang:trace/3`
+    tracing: Mutex<Option<Tracing>>,
+    /// The number of times each process suspended this process with `erlang:suspend_process/1,2`
+    /// without resuming it with `erlang:resume_process/1`
+    suspend_count_by_suspender_pid: DashMap<Pid, usize>,
     pub registers: CalleeSavedRegisters,
     pub stack: Mutex<alloc::Stack>,
     // process heap, cache line aligned to avoid false sharing with rest of struct
     heap: Mutex<ProcessHeap>,
 }
 impl Process {
+    /// The default maximum number of minor collections before a full sweep
+    pub const DEFAULT_FULLSWEEP_AFTER: usize = 65535;
+
     /// Creates a new PCB with a heap defined by the given pointer, and
     /// `heap_size`, which is the size of the heap in words.
     pub fn new(
@@ -172,23 +203,29 @@ impl Process {
 
         Self {
             flags: AtomicProcessFlags::new(ProcessFlags::Default),
-            min_heap_size: heap_size,
-            max_heap_size: 0,
-            min_vheap_size: 0,
+            min_heap_size: AtomicUsize::new(heap_size),
+            max_heap_size: Default::default(),
+            min_vheap_size: AtomicUsize::new(0),
             gc_threshold: 0.75,
-            max_gen_gcs: 65535,
+            max_gen_gcs: Self::DEFAULT_FULLSWEEP_AFTER,
             off_heap,
             off_heap_size: AtomicUsize::new(0),
             dictionary: Default::default(),
             pid,
             status: Default::default(),
             mailbox: Default::default(),
+            off_heap_messages: Default::default(),
+            error_handler: Mutex::new(Atom::from_str("error_handler")),
+            saved_calls: Default::default(),
+            seq_trace_token: Default::default(),
+            tracing: Default::default(),
+            suspend_count_by_suspender_pid: Default::default(),
             heap: Mutex::new(heap),
             stack: Default::default(),
             registers: Default::default(),
             frames: Default::default(),
             scheduler_id: Mutex::new(None),
-            priority,
+            priority: AtomicPriority::new(priority),
             parent_pid,
             group_leader_pid: Mutex::new(group_leader_pid),
             initial_module_function_arity,
@@ -229,6 +266,21 @@ impl Process {
         *self.scheduler_id.lock() = Some(scheduler_id);
     }
 
+    // Priority
+
+    pub fn priority(&self) -> Priority {
+        self.priority.load()
+    }
+
+    /// Returns the old priority.
+    ///
+    /// The process is only moved to the run queue for `priority` when it is next enqueued, so
+    /// this should only be called by the process itself while it is running, as
+    /// `process_flag(priority, Priority)` does.
+    pub fn set_priority(&self, priority: Priority) -> Priority {
+        self.priority.swap(priority)
+    }
+
     // Flags
 
     pub fn are_flags_set(&self, flags: ProcessFlags) -> bool {
@@ -263,6 +315,158 @@ impl Process {
         self.are_flags_set(ProcessFlags::TrapExit)
     }
 
+    /// Whether features that inspect the data of the process, such as tracing and reading the
+    /// mailbox or dictionary with `process_info`, are disabled.
+    pub fn is_sensitive(&self) -> bool {
+        self.are_flags_set(ProcessFlags::Sensitive)
+    }
+
+    /// Returns the old value
+    pub fn set_sensitive(&self, value: bool) -> bool {
+        let flag = ProcessFlags::Sensitive;
+
+        let old_flags = if value {
+            self.set_flags(flag)
+        } else {
+            self.clear_flags(flag)
+        };
+
+        old_flags.are_set(flag)
+    }
+
+    pub fn message_queue_data(&self) -> MessageQueueData {
+        if self.are_flags_set(ProcessFlags::OffHeapMessageQueue) {
+            MessageQueueData::OffHeap
+        } else {
+            MessageQueueData::OnHeap
+        }
+    }
+
+    /// Returns the old `MessageQueueData`.  Messages already in the mailbox stay where they are,
+    /// only messages sent afterwards are affected.
+    pub fn set_message_queue_data(&self, message_queue_data: MessageQueueData) -> MessageQueueData {
+        let flag = ProcessFlags::OffHeapMessageQueue;
+
+        let old_flags = match message_queue_data {
+            MessageQueueData::OffHeap => self.set_flags(flag),
+            MessageQueueData::OnHeap => self.clear_flags(flag),
+        };
+
+        if old_flags.are_set(flag) {
+            MessageQueueData::OffHeap
+        } else {
+            MessageQueueData::OnHeap
+        }
+    }
+
+    // Error Handler
+
+    pub fn error_handler(&self) -> Atom {
+        *self.error_handler.lock()
+    }
+
+    /// Returns the old error handler module
+    pub fn set_error_handler(&self, module: Atom) -> Atom {
+        mem::replace(&mut *self.error_handler.lock(), module)
+    }
+
+    // Saved Calls
+
+    /// Returns the old number of calls that were saved
+    pub fn save_calls(&self, n: usize) -> usize {
+        let mut saved_calls = self.saved_calls.lock();
+        let old_n = saved_calls.set_capacity(n);
+
+        if saved_calls.is_enabled() {
+            self.set_flags(ProcessFlags::SaveCalls);
+        } else {
+            self.clear_flags(ProcessFlags::SaveCalls);
+        }
+
+        old_n
+    }
+
+    /// `None` when calls are not being saved; otherwise, the calls from oldest to most recent.
+    pub fn saved_calls(&self) -> Option<Vec<ModuleFunctionArity>> {
+        let saved_calls = self.saved_calls.lock();
+
+        if saved_calls.is_enabled() {
+            Some(saved_calls.iter().copied().collect())
+        } else {
+            None
+        }
+    }
+
+    // Sequential Trace
+
+    pub fn seq_trace_token(&self) -> Option<SeqTraceToken> {
+        *self.seq_trace_token.lock()
+    }
+
+    /// Returns the old token
+    pub fn set_seq_trace_token(
+        &self,
+        seq_trace_token: Option<SeqTraceToken>,
+    ) -> Option<SeqTraceToken> {
+        mem::replace(&mut *self.seq_trace_token.lock(), seq_trace_token)
+    }
+
+    /// Increments the serial of the token, if any, and returns the token to pass along with a
+    /// message being sent by this process.
+    pub fn send_seq_trace_token(&self) -> Option<SeqTraceToken> {
+        self.seq_trace_token.lock().as_mut().map(|seq_trace_token| {
+            seq_trace_token.send(self.pid);
+
+            *seq_trace_token
+        })
+    }
+
+    /// Replaces the token with the token that was passed along with a received message, so that
+    /// the token is passed along with the messages this process sends next.  A message without a
+    /// token clears the token, as in the BEAM.
+    pub fn receive_seq_trace_token(
+        &self,
+        message_seq_trace_token: Option<SeqTraceToken>,
+    ) -> Option<SeqTraceToken> {
+        let mut seq_trace_token = self.seq_trace_token.lock();
+
+        *seq_trace_token = message_seq_trace_token.map(|message_seq_trace_token| {
+            SeqTraceToken::receive(*seq_trace_token, message_seq_trace_token)
+        });
+
+        *seq_trace_token
+    }
+
+    // Tracing
+
+    pub fn tracing(&self) -> Option<Tracing> {
+        *self.tracing.lock()
+    }
+
+    /// Returns the old tracing
+    pub fn set_tracing(&self, tracing: Option<Tracing>) -> Option<Tracing> {
+        mem::replace(&mut *self.tracing.lock(), tracing)
+    }
+
+    /// Returns the tracing if any of `flags` are traced and the process is not sensitive
+    pub fn tracing_with_flags(&self, flags: TraceFlags) -> Option<Tracing> {
+        self.tracing()
+            .filter(|tracing| tracing.flags.are_set(flags) && !self.is_sensitive())
+    }
+
+    /// Passes the event returned by `event` to the trace event handler, only constructing the
+    /// event if `flags` are traced.
+    fn trace_event<'a, F>(&self, flags: TraceFlags, event: F)
+    where
+        F: FnOnce() -> TraceEvent<'a>,
+    {
+        if let Some(tracing) = self.tracing_with_flags(flags) {
+            if let Some(trace_event_handler) = tracing::trace_event_handler() {
+                trace_event_handler(self, tracing, &event());
+            }
+        }
+    }
+
     // Alloc
 
     /// Acquires exclusive access to the process heap, blocking the current thread until it is able
@@ -454,22 +658,36 @@ impl Process {
     // Links
 
     pub fn link(&self, other: &Process) {
+        self.link_in_order(other);
+
+        self.trace_event(TraceFlags::Procs, || TraceEvent::Link(other.pid));
+        other.trace_event(TraceFlags::Procs, || TraceEvent::GettingLinked(self.pid));
+    }
+
+    pub fn unlink(&self, other: &Process) {
+        self.unlink_in_order(other);
+
+        self.trace_event(TraceFlags::Procs, || TraceEvent::Unlink(other.pid));
+        other.trace_event(TraceFlags::Procs, || TraceEvent::GettingUnlinked(self.pid));
+    }
+
+    fn link_in_order(&self, other: &Process) {
         // link in order so that locks are always taken in the same order to prevent deadlocks
         if self.pid < other.pid {
             self.linked_pid_set.insert(other.pid);
             other.linked_pid_set.insert(self.pid);
         } else {
-            other.link(self)
+            other.link_in_order(self)
         }
     }
 
-    pub fn unlink(&self, other: &Process) {
+    fn unlink_in_order(&self, other: &Process) {
         // unlink in order so that locks are always taken in the same order to prevent deadlocks
         if self.pid < other.pid {
             self.linked_pid_set.remove(&other.pid);
             other.linked_pid_set.remove(&self.pid);
         } else {
-            other.unlink(self)
+            other.unlink_in_order(self)
         }
     }
 
@@ -524,50 +742,131 @@ impl Process {
     // Send
 
     pub fn send_heap_message(&self, heap_fragment: NonNull<HeapFragment>, data: Term) {
-        let heap_fragment_ptr = heap_fragment.as_ptr();
-
-        let off_heap_unsafe_ref_heap_fragment = unsafe { UnsafeRef::from_raw(heap_fragment_ptr) };
-        self.off_heap
-            .lock()
-            .push_back(off_heap_unsafe_ref_heap_fragment);
+        self.send_heap_message_with_seq_trace_token(heap_fragment, data, None)
+    }
 
+    fn send_heap_message_with_seq_trace_token(
+        &self,
+        heap_fragment: NonNull<HeapFragment>,
+        data: Term,
+        seq_trace_token: Option<SeqTraceToken>,
+    ) {
+        let heap_fragment_ptr = heap_fragment.as_ptr();
         let message_unsafe_ref_heap_fragment = unsafe { UnsafeRef::from_raw(heap_fragment_ptr) };
-
-        self.send_message(Message::HeapFragment(message::HeapFragment {
+        let message = Message::HeapFragment(message::HeapFragment {
             unsafe_ref_heap_fragment: message_unsafe_ref_heap_fragment,
             data,
-        }));
+            seq_trace_token,
+        });
+
+        match self.message_queue_data() {
+            MessageQueueData::OnHeap => {
+                let off_heap_unsafe_ref_heap_fragment =
+                    unsafe { UnsafeRef::from_raw(heap_fragment_ptr) };
+                self.off_heap
+                    .lock()
+                    .push_back(off_heap_unsafe_ref_heap_fragment);
+
+                self.send_message(message);
+            }
+            // The fragment is only added to `off_heap` when the receiver moves the message into
+            // its mailbox, so that the sender does not need to take any of the receiver's locks.
+            MessageQueueData::OffHeap => self.off_heap_messages.push(message),
+        }
     }
 
     pub fn send_from_self(&self, data: Term) {
-        self.send_message(Message::Process(message::Process { data }));
+        self.send_from_self_with_seq_trace_token(data, None)
+    }
+
+    pub fn send_from_self_with_seq_trace_token(
+        &self,
+        data: Term,
+        seq_trace_token: Option<SeqTraceToken>,
+    ) {
+        self.send_message(Message::Process(message::Process {
+            data,
+            seq_trace_token,
+        }));
     }
 
     /// Returns `true` if the process should stop waiting and be rescheduled as runnable.
     pub fn send_from_other(&self, data: Term) {
-        match self.heap.try_lock() {
+        self.send_from_other_with_seq_trace_token(data, None)
+    }
+
+    pub fn send_from_other_with_seq_trace_token(
+        &self,
+        data: Term,
+        seq_trace_token: Option<SeqTraceToken>,
+    ) {
+        let mut option_destination_heap = match self.message_queue_data() {
+            MessageQueueData::OnHeap => self.heap.try_lock(),
+            MessageQueueData::OffHeap => None,
+        };
+
+        match option_destination_heap {
             Some(ref mut destination_heap) => match data.clone_to_heap(destination_heap) {
                 Ok(destination_data) => {
                     self.send_message(Message::Process(message::Process {
                         data: destination_data,
+                        seq_trace_token,
                     }));
                 }
                 Err(_) => {
                     let (heap_fragment_data, heap_fragment) = data.clone_to_fragment().unwrap();
 
-                    self.send_heap_message(heap_fragment, heap_fragment_data);
+                    self.send_heap_message_with_seq_trace_token(
+                        heap_fragment,
+                        heap_fragment_data,
+                        seq_trace_token,
+                    );
                 }
             },
             None => {
                 let (heap_fragment_data, heap_fragment) = data.clone_to_fragment().unwrap();
 
-                self.send_heap_message(heap_fragment, heap_fragment_data);
+                self.send_heap_message_with_seq_trace_token(
+                    heap_fragment,
+                    heap_fragment_data,
+                    seq_trace_token,
+                );
             }
         }
     }
 
     fn send_message(&self, message: Message) {
-        self.mailbox.lock().borrow_mut().push(message)
+        self.acquire_mailbox().borrow_mut().push(message)
+    }
+
+    // Mailbox
+
+    /// Acquires exclusive access to the process mailbox, blocking the current thread until it is
+    /// able to do so.
+    ///
+    /// Any messages sent with `off_heap` `message_queue_data` since the mailbox was last acquired
+    /// are moved into the mailbox first, so they are received in the order they were sent.
+    pub fn acquire_mailbox<'a>(&'a self) -> MutexGuard<'a, RefCell<Mailbox>> {
+        let mailbox_guard = self.mailbox.lock();
+
+        if !self.off_heap_messages.is_empty() {
+            let mut mailbox = mailbox_guard.borrow_mut();
+            let mut off_heap = self.off_heap.lock();
+
+            for message in self.off_heap_messages.take() {
+                if let Message::HeapFragment(message::HeapFragment {
+                    ref unsafe_ref_heap_fragment,
+                    ..
+                }) = message
+                {
+                    off_heap.push_back(unsafe_ref_heap_fragment.clone());
+                }
+
+                mailbox.push(message);
+            }
+        }
+
+        mailbox_guard
     }
 
     // Terms
@@ -765,7 +1064,6 @@ impl Process {
     pub fn map_from_hash_map(&self, hash_map: HashMap<Term, Term>) -> Term {
         self.acquire_heap()
             .map_from_hash_map(hash_map.clone())
-            .map(|boxed_map| boxed_map.into())
             .unwrap_or_else(|_| {
                 self.attach_fragment_or_panic(HeapFragment::new_map_from_hash_map(hash_map))
             })
@@ -781,6 +1079,51 @@ impl Process {
             .into()
     }
 
+    /// `map` with `key` associated with `value`.  Returns `map` itself if `key` is already
+    /// associated with `value`.
+    pub fn map_put(&self, map: Boxed<Map>, key: Term, value: Term) -> Term {
+        let result = map.put(&mut *self.acquire_heap(), key, value);
+
+        result
+            .unwrap_or_else(|_| {
+                self.attach_fragment_or_panic(HeapFragment::new_map_put(map, key, value))
+            })
+            .map(|new_map| new_map.into())
+            .unwrap_or_else(|| map.into())
+    }
+
+    /// `map` with `key` associated with `value`, or `None` if `key` is not in `map`.
+    pub fn map_update(&self, map: Boxed<Map>, key: Term, value: Term) -> Option<Term> {
+        let result = map.update(&mut *self.acquire_heap(), key, value);
+
+        result
+            .unwrap_or_else(|_| {
+                self.attach_fragment_or_panic(HeapFragment::new_map_update(map, key, value))
+            })
+            .map(|new_map| new_map.into())
+    }
+
+    /// `map` without `key`.  Returns `map` itself if `key` is not in `map`.
+    pub fn map_remove(&self, map: Boxed<Map>, key: Term) -> Term {
+        let result = map.remove(&mut *self.acquire_heap(), key);
+
+        result
+            .unwrap_or_else(|_| {
+                self.attach_fragment_or_panic(HeapFragment::new_map_remove(map, key))
+            })
+            .map(|new_map| new_map.into())
+            .unwrap_or_else(|| map.into())
+    }
+
+    /// The value of `key` in `map` and `map` without `key`, or `None` if `key` is not in `map`.
+    pub fn map_take(&self, map: Boxed<Map>, key: Term) -> Option<(Term, Term)> {
+        let result = map.take(&mut *self.acquire_heap(), key);
+
+        result
+            .unwrap_or_else(|_| self.attach_fragment_or_panic(HeapFragment::new_map_take(map, key)))
+            .map(|(value, new_map)| (value, new_map.into()))
+    }
+
     pub fn reference(&self, number: ReferenceNumber) -> Term {
         self.reference_from_scheduler(self.scheduler_id.lock().unwrap(), number)
     }
@@ -889,6 +1232,22 @@ impl Process {
         self.list_from_slice(&entry_vec).into()
     }
 
+    /// Returns all key/value pairs from process dictionary copied to `process`, such as when
+    /// `process` is inspecting this process.
+    pub fn get_entries_in(&self, process: &Process) -> Term {
+        let entry_vec: Vec<Term> = self
+            .dictionary
+            .iter()
+            .map(|entry| {
+                let key = entry.key().clone_to_process(process);
+                let value = entry.value().clone_to_process(process);
+                process.tuple_from_slice(&[key, value])
+            })
+            .collect();
+
+        process.list_from_slice(&entry_vec)
+    }
+
     /// Returns list of all keys from the process dictionary.
     pub fn get_keys(&self) -> Term {
         let entry_vec: Vec<Term> = self.dictionary.iter().map(|entry| *entry.key()).collect();
@@ -980,6 +1339,159 @@ impl Process {
         self.flags.are_set(ProcessFlags::NeedFullSweep)
     }
 
+    /// Forces a collection of the heap the next time the scheduler checks for one, as with
+    /// `erlang:garbage_collect/0,1,2`.  A `major` collection also sweeps the old generation.
+    pub fn force_garbage_collection(&self, major: bool) {
+        let flags = if major {
+            ProcessFlags::ForceGC | ProcessFlags::NeedFullSweep
+        } else {
+            ProcessFlags::ForceGC
+        };
+
+        self.set_flags(flags);
+    }
+
+    /// Collects the heap if a collection was forced with `force_garbage_collection`.  Returns the
+    /// reductions used by the collection, which are `0` if no collection was forced.
+    ///
+    /// Only call this between runs of the process, when all roots are on the stack.
+    pub fn garbage_collect_if_forced(&self) -> Result<usize, GcError> {
+        if self.is_gc_forced() {
+            self.clear_flags(ProcessFlags::ForceGC);
+
+            self.garbage_collect(0, &mut [][..])
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Discards the frames between the current frame and the bottom frame along with the terms
+    /// they left on the stack, then does a full sweep, so that the heap shrinks to the data
+    /// reachable from the remaining frames and `roots`, as with `erlang:hibernate/3`.
+    ///
+    /// `roots` are updated in place, as they may move during the collection.
+    pub fn hibernate(&self, roots: &mut [Term]) -> Result<usize, GcError> {
+        let option_arities = {
+            let mut frames = self.frames.lock();
+
+            if 0 < frames.discard_callers() {
+                let arity = frames.current().unwrap().native().arity() as usize;
+                let bottom_arity = frames.bottom().unwrap().native().arity() as usize;
+
+                Some((arity, bottom_arity))
+            } else {
+                None
+            }
+        };
+
+        if let Some((arity, bottom_arity)) = option_arities {
+            let stack_used = self.stack_used();
+            let arguments: Vec<Term> = (1..=arity)
+                .map(|one_based_index| self.stack_peek(one_based_index).unwrap())
+                .collect();
+            self.stack_popn(stack_used - bottom_arity);
+
+            for argument in arguments.into_iter().rev() {
+                self.stack_push(argument).unwrap();
+            }
+        }
+
+        self.set_flags(ProcessFlags::NeedFullSweep);
+
+        self.garbage_collect(0, roots)
+    }
+
+    /// The number of words in the young generation of the heap
+    pub fn heap_size(&self) -> usize {
+        self.heap.lock().heap_size()
+    }
+
+    /// The number of words in the old generation of the heap
+    pub fn old_heap_size(&self) -> usize {
+        self.heap.lock().old_heap_size()
+    }
+
+    /// The number of words used in the young and old generations of the heap
+    pub fn heap_used(&self) -> usize {
+        let heap = self.heap.lock();
+
+        heap.heap_used() + heap.old_heap_used()
+    }
+
+    /// The number of words in the young and old generations of the heap and its heap fragments
+    pub fn total_heap_size(&self) -> usize {
+        let heap = self.heap.lock();
+
+        heap.heap_size() + heap.old_heap_size() + self.off_heap_size()
+    }
+
+    /// The sizes of the generations of the heap and its heap fragments
+    pub fn garbage_collection_info(&self) -> GarbageCollectionInfo {
+        self.heap
+            .lock()
+            .garbage_collection_info(self.off_heap_size())
+    }
+
+    /// The `(id, size, reference_count)` of each reference-counted binary referenced from the
+    /// heap
+    pub fn binaries(&self) -> Vec<(usize, usize, usize)> {
+        self.heap
+            .lock()
+            .proc_bins()
+            .map(|proc_bin| {
+                (
+                    proc_bin.id(),
+                    proc_bin.full_byte_len(),
+                    proc_bin.reference_count(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn min_heap_size(&self) -> usize {
+        self.min_heap_size.load(Ordering::Acquire)
+    }
+
+    /// Returns the old value.  The heap is not shrunk below `min_heap_size` by the next
+    /// collection.
+    pub fn set_min_heap_size(&self, min_heap_size: usize) -> usize {
+        self.min_heap_size.swap(min_heap_size, Ordering::AcqRel)
+    }
+
+    pub fn max_heap_size(&self) -> MaxHeapSize {
+        *self.max_heap_size.lock()
+    }
+
+    /// Returns the old value.  The new value is checked by the next collection.
+    pub fn set_max_heap_size(&self, max_heap_size: MaxHeapSize) -> MaxHeapSize {
+        mem::replace(&mut *self.max_heap_size.lock(), max_heap_size)
+    }
+
+    pub fn min_bin_vheap_size(&self) -> usize {
+        self.min_vheap_size.load(Ordering::Acquire)
+    }
+
+    /// Returns the old value
+    pub fn set_min_bin_vheap_size(&self, min_bin_vheap_size: usize) -> usize {
+        self.min_vheap_size
+            .swap(min_bin_vheap_size, Ordering::AcqRel)
+    }
+
+    /// The maximum number of minor collections before a full sweep
+    pub fn fullsweep_after(&self) -> usize {
+        self.max_gen_gcs
+    }
+
+    /// Only valid before the process is first run, such as for spawn options
+    pub fn set_fullsweep_after(&mut self, fullsweep_after: usize) {
+        self.max_gen_gcs = fullsweep_after;
+    }
+
+    /// The number of minor collections since the last full sweep
+    pub fn minor_gcs(&self) -> usize {
+        self.heap.lock().gen_gc_count
+    }
+
     /// Inserts roots from the process into the given root set.
     /// This includes all process dictionary entries.
     #[inline]
@@ -1006,13 +1518,37 @@ impl Process {
         need: usize,
         roots: impl Into<RootSet>,
     ) -> Result<usize, GcError> {
+        let major = self.heap.lock().needs_full_collection(self);
+        let used_before = self.heap_used() + self.off_heap_size();
+        self.trace_event(TraceFlags::GarbageCollection, || {
+            TraceEvent::GarbageCollectionStart {
+                major,
+                heap_size: self.heap_size(),
+            }
+        });
+
         let mut heap = self.heap.lock();
         // The roots passed in here are pointers to the native stack, all other roots
         // we are able to pick up from the current process context
         let mut rootset = roots.into();
         self.base_root_set(&mut rootset);
         // Initialize the collector with the given root set
-        heap.garbage_collect(self, need, rootset)
+        let result = heap.garbage_collect(self, need, rootset);
+        drop(heap);
+
+        if result.is_ok() {
+            let used_after = self.heap_used() + self.off_heap_size();
+            gc::count_collection(used_before.saturating_sub(used_after));
+        }
+
+        self.trace_event(TraceFlags::GarbageCollection, || {
+            TraceEvent::GarbageCollectionEnd {
+                major,
+                heap_size: self.heap_size(),
+            }
+        });
+
+        result
     }
 
     /// Cleans up any linked HeapFragments which should have had any live
@@ -1071,6 +1607,17 @@ impl Process {
         MAX_REDUCTIONS_PER_RUN <= self.run_reductions.load(Ordering::SeqCst)
     }
 
+    /// Uses up the remaining reductions of the current run, so that the scheduler runs other
+    /// processes before this one continues, as with `erlang:yield/0`.
+    pub fn exhaust_reductions(&self) {
+        let run_reductions = self.run_reductions.load(Ordering::SeqCst);
+
+        if run_reductions < MAX_REDUCTIONS_PER_RUN {
+            self.run_reductions
+                .fetch_add(MAX_REDUCTIONS_PER_RUN - run_reductions, Ordering::SeqCst);
+        }
+    }
+
     pub fn runnable<F>(&self, before_runnable: F)
     where
         F: FnOnce(),
@@ -1137,6 +1684,64 @@ impl Process {
         }
     }
 
+    // Suspending
+
+    /// Suspends the process on behalf of `suspender_pid`, as with `erlang:suspend_process/1`.
+    /// Returns how many times `suspender_pid` has now suspended the process.
+    pub fn suspend(&self, suspender_pid: Pid) -> usize {
+        let mut suspend_count = self
+            .suspend_count_by_suspender_pid
+            .entry(suspender_pid)
+            .or_insert(0);
+        *suspend_count += 1;
+
+        *suspend_count
+    }
+
+    /// Undoes one suspend by `suspender_pid`, as with `erlang:resume_process/1`.  Returns how many
+    /// suspends by `suspender_pid` remain, or `None` if `suspender_pid` had not suspended the
+    /// process.
+    pub fn resume(&self, suspender_pid: Pid) -> Option<usize> {
+        let remaining = match self.suspend_count_by_suspender_pid.get_mut(&suspender_pid) {
+            Some(mut suspend_count) => {
+                *suspend_count -= 1;
+
+                *suspend_count
+            }
+            None => return None,
+        };
+
+        if remaining == 0 {
+            self.suspend_count_by_suspender_pid.remove(&suspender_pid);
+        }
+
+        Some(remaining)
+    }
+
+    /// Undoes all suspends by `suspender_pid`, such as when it exits.  Returns whether
+    /// `suspender_pid` had suspended the process.
+    pub fn resume_all(&self, suspender_pid: Pid) -> bool {
+        self.suspend_count_by_suspender_pid
+            .remove(&suspender_pid)
+            .is_some()
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        !self.suspend_count_by_suspender_pid.is_empty()
+    }
+
+    pub fn is_suspended_by(&self, suspender_pid: Pid) -> bool {
+        self.suspend_count_by_suspender_pid
+            .contains_key(&suspender_pid)
+    }
+
+    /// The number of suspends of the process by `suspender_pid` that have not been resumed
+    pub fn suspend_count_by(&self, suspender_pid: Pid) -> usize {
+        self.suspend_count_by_suspender_pid
+            .get(&suspender_pid)
+            .map_or(0, |suspend_count| *suspend_count)
+    }
+
     pub fn erlang_exit(&self, exception: Box<ErlangException>) {
         self.reduce();
         let mut heap = self.acquire_heap();
@@ -1157,6 +1762,11 @@ impl Process {
         self.exception(exception);
     }
 
+    /// Exits with the `killed` reason, as when the process exceeds its `max_heap_size`
+    pub fn exit_killed(&self) {
+        self.exit(atom!("killed"), trace::Trace::capture(), None);
+    }
+
     pub fn exit_normal(&self) {
         *self.status.write() = Status::Exited;
     }
@@ -1207,13 +1817,17 @@ impl Process {
     fn call_current_native(&self) -> CalledCurrentNative {
         // not done inline in `match` argument, so that lock isn't held for `native.apply`, when
         // `native` may want to manipulate `frame_stack`.
-        let native = self
+        let (module_function_arity, native) = self
             .frames
             .lock()
             .current()
-            .map(|frame| frame.native())
+            .map(|frame| (frame.module_function_arity(), frame.native()))
             .unwrap_or_else(|| panic!("Process ({:?}) ran out of frames without exiting", self));
 
+        if self.are_flags_set(ProcessFlags::SaveCalls) {
+            self.saved_calls.lock().push(module_function_arity);
+        }
+
         let arity = native.arity() as usize;
         let mut arguments = Vec::with_capacity(arity);
 
@@ -1232,6 +1846,11 @@ impl Process {
             arguments.push(argument);
         }
 
+        self.trace_event(TraceFlags::Call, || TraceEvent::Call {
+            module_function_arity,
+            arguments: &arguments,
+        });
+
         let result = catch_unwind(|| native.apply(&arguments));
 
         match result {
@@ -1270,6 +1889,8 @@ impl Process {
                     // remove completed frame now that it isn't needed for backtrace
                     self.frames.lock().pop().unwrap();
                     self.stack_popn(arity);
+                    self.trace_return_from(module_function_arity, returned);
+                    self.trace_return_to();
 
                     self.stack_queued_frames_with_arguments();
 
@@ -1281,10 +1902,25 @@ impl Process {
                     }
                 };
 
+                if let CalledCurrentNative::RuntimeException = called_current_native {
+                    // not done inside the `match`, so that the status lock isn't held while tracing
+                    let option_runtime_exception = match *self.status.read() {
+                        Status::RuntimeException(ref runtime_exception) => {
+                            Some(runtime_exception.clone())
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(runtime_exception) = option_runtime_exception {
+                        self.trace_exception_from(module_function_arity, &runtime_exception);
+                    }
+                }
+
                 called_current_native
             }
             Err(_) => {
                 let runtime_exception = process_error().unwrap();
+                self.trace_exception_from(module_function_arity, &runtime_exception);
                 *self.status.write() = Status::RuntimeException(runtime_exception);
 
                 CalledCurrentNative::RuntimeException
@@ -1292,6 +1928,47 @@ impl Process {
         }
     }
 
+    /// Traces `module_function_arity` returning `returned`, for `return_trace` match
+    /// specifications.
+    fn trace_return_from(&self, module_function_arity: ModuleFunctionArity, returned: Term) {
+        self.trace_event(TraceFlags::Call, || TraceEvent::ReturnFrom {
+            module_function_arity,
+            returned,
+        });
+    }
+
+    /// Traces `module_function_arity` raising `runtime_exception`, for `exception_trace` match
+    /// specifications.
+    fn trace_exception_from(
+        &self,
+        module_function_arity: ModuleFunctionArity,
+        runtime_exception: &RuntimeException,
+    ) {
+        self.trace_event(TraceFlags::Call, || TraceEvent::ExceptionFrom {
+            module_function_arity,
+            class: runtime_exception.class(),
+            reason: runtime_exception.reason(),
+        });
+    }
+
+    /// Traces returning to the function of the current frame, if any, after a frame is popped.
+    fn trace_return_to(&self) {
+        // not done inline in `if let`, so that lock isn't held while tracing
+        let option_module_function_arity = self
+            .frames
+            .lock()
+            .current()
+            .map(|frame| frame.module_function_arity());
+
+        if let Some(module_function_arity) = option_module_function_arity {
+            self.trace_event(TraceFlags::Call | TraceFlags::ReturnTo, || {
+                TraceEvent::ReturnTo {
+                    module_function_arity,
+                }
+            });
+        }
+    }
+
     pub fn stack_queued_frames_with_arguments(&self) {
         let mut frames = self.frames.lock();
         let mut frames_with_arguments = frames.drain_queue();
@@ -1338,6 +2015,11 @@ impl Process {
                                 .expect("Could not push arguments to stack after successful GC");
                             frames.push(frame);
                         }
+                        Err(GcError::MaxHeapSizeExceeded) => {
+                            self.exit_killed();
+
+                            return;
+                        }
                         Err(_) => {
                             unimplemented!(
                                 "Could not GC while stacking queued frames with arguments"
@@ -145,6 +145,11 @@ pub fn default_heap_size() -> usize {
     ProcessHeapAlloc::HEAP_SIZES[ProcessHeapAlloc::MIN_HEAP_SIZE_INDEX]
 }
 
+/// Returns the heap sizes, in words, that process heaps grow through
+pub fn heap_sizes() -> &'static [usize] {
+    &ProcessHeapAlloc::HEAP_SIZES
+}
+
 /// Allocate a new process heap of the given size
 #[inline]
 pub fn heap(size: usize) -> AllocResult<*mut Term> {
@@ -80,8 +80,8 @@ where
                     return Some(term);
                 } else if term.is_header() {
                     // For certain terms, we need to walk their elements
-                    if term.is_tuple() {
-                        // Tuple header is word-sized, followed by elements
+                    if term.is_tuple() || term.is_map() {
+                        // Tuple and map headers are word-sized, followed by elements
                         self.pos = unsafe { pos.add(1) };
                         // Shift to first element
                         return Some(term);
@@ -264,9 +264,9 @@ pub trait TermAlloc: Heap {
     where
         Self: Sized,
     {
-        let boxed = Map::from_hash_map(hash_map).clone_to_heap(self)?;
-        let ptr: Boxed<Map> = boxed.dyn_cast();
-        Ok(ptr)
+        let entries: Vec<(Term, Term)> = hash_map.into_iter().collect();
+
+        Map::from_slice(self, &entries)
     }
 
     /// Constructs a map and associated with the given process.
@@ -274,9 +274,7 @@ pub trait TermAlloc: Heap {
     where
         Self: Sized,
     {
-        let boxed = Map::from_slice(slice).clone_to_heap(self)?;
-        let ptr: Boxed<Map> = boxed.dyn_cast();
-        Ok(ptr)
+        Map::from_slice(self, slice)
     }
 
     #[inline]
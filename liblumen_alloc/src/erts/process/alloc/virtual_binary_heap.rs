@@ -121,6 +121,11 @@ impl VirtualBinaryHeap {
         }
     }
 
+    /// The `ProcBin`s allocated on this virtual heap
+    pub fn iter(&self) -> impl Iterator<Item = &ProcBin> {
+        self.bins.iter()
+    }
+
     #[inline]
     unsafe fn unlink_raw(&mut self, raw: *mut ProcBin) {
         // Remove from the list
@@ -26,6 +26,16 @@ impl ProcessFlags {
     /// This flag indicates the processes linked to this process should send exit messages instead
     /// of causing this process to exit when they exit
     pub const TrapExit: Self = Self(1 << 6);
+    /// This flag indicates that messages sent by other processes are stored in heap fragments
+    /// outside of the process heap until they are received, i.e. `message_queue_data` is
+    /// `off_heap`
+    pub const OffHeapMessageQueue: Self = Self(1 << 7);
+    /// This flag indicates that the data of the process cannot be inspected, such as by tracing
+    /// or reading its mailbox or dictionary with `process_info`
+    pub const Sensitive: Self = Self(1 << 8);
+    /// This flag indicates that the most recent calls are saved, i.e. `save_calls` is more than
+    /// `0`, so that calls only lock the saved calls when they are saved
+    pub const SaveCalls: Self = Self(1 << 9);
 
     pub fn are_set(&self, flags: ProcessFlags) -> bool {
         (*self & flags) == flags
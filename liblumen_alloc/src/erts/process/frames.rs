@@ -40,6 +40,18 @@ impl Frames {
         self.queue.push(frame_with_arguments);
     }
 
+    /// The frame that runs when all other frames have returned
+    pub fn bottom(&self) -> Option<&Frame> {
+        self.stack.bottom()
+    }
+
+    /// Discards the frames between the current frame and the bottom frame, as when the process
+    /// hibernates, so that the process still ends as usual once the current frame returns.
+    /// Returns the number of discarded frames.
+    pub fn discard_callers(&mut self) -> usize {
+        self.stack.remove_middle()
+    }
+
     pub fn drain_queue(&mut self) -> Vec<FrameWithArguments> {
         self.queue.drain().collect()
     }
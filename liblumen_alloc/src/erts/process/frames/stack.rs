@@ -28,6 +28,22 @@ impl Stack {
         self.0.push_front(frame);
     }
 
+    pub fn bottom(&self) -> Option<&Frame> {
+        self.0.back()
+    }
+
+    /// Removes the frames between the top and bottom frames.  Returns the number of removed
+    /// frames.
+    pub fn remove_middle(&mut self) -> usize {
+        let len = self.0.len();
+
+        if len <= 2 {
+            0
+        } else {
+            self.0.drain(1..len - 1).count()
+        }
+    }
+
     pub fn top(&self) -> Option<&Frame> {
         self.0.get(0)
     }
@@ -60,6 +76,13 @@ impl Debug for Stack {
 
 pub struct Trace(Vec<ModuleFunctionArity>);
 
+impl Trace {
+    /// From the current function to the initial call
+    pub fn iter(&self) -> impl Iterator<Item = &ModuleFunctionArity> {
+        self.0.iter()
+    }
+}
+
 impl Debug for Trace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for module_function_arity in self.0.iter() {
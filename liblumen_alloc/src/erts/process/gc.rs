@@ -17,10 +17,17 @@ pub use self::rootset::RootSet;
 pub use self::sweep::{Sweep, Sweepable, Sweeper};
 pub use self::young_heap::YoungHeap;
 
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use super::alloc::SemispaceHeap;
 use crate::erts::exception;
 use thiserror::Error;
 
+/// The number of collections completed across all processes
+static COLLECTIONS: AtomicUsize = AtomicUsize::new(0);
+/// The number of words reclaimed by the collections in `COLLECTIONS`
+static WORDS_RECLAIMED: AtomicUsize = AtomicUsize::new(0);
+
 /// Represents the types of errors that can occur during garbage collection.
 ///
 /// See the documentation for each variant to get general advice for how to
@@ -31,10 +38,10 @@ pub enum GcError {
     /// but panic, however this choice is left up to the caller
     #[error("unable to allocate memory for garbage collection")]
     Alloc(#[from] exception::Alloc),
-    /// Occurs when a process is configured with a maximum heap size,
-    /// and a projected heap growth is found to exceed the limit. In
-    /// this situation the only meaningful thing to do is to kill the
-    /// process
+    /// Occurs when a process is configured with a maximum heap size
+    /// that kills the process, and a projected heap growth is found to
+    /// exceed the limit. In this situation the process must be killed
+    /// with the `killed` exit reason, as in the BEAM
     #[error("maximum heap size exceeded")]
     MaxHeapSizeExceeded,
     /// Indicates that an allocation could not be filled without first
@@ -77,3 +84,18 @@ pub fn estimate_cost(moved_live_words: usize, resize_moved_words: usize) -> usiz
         reds
     }
 }
+
+/// Counts a completed collection of any process that reclaimed `words_reclaimed`
+pub(super) fn count_collection(words_reclaimed: usize) {
+    COLLECTIONS.fetch_add(1, Ordering::Relaxed);
+    WORDS_RECLAIMED.fetch_add(words_reclaimed, Ordering::Relaxed);
+}
+
+/// The number of collections completed across all processes and the number of words they
+/// reclaimed, as for `erlang:statistics(garbage_collection)`
+pub fn statistics() -> (usize, usize) {
+    (
+        COLLECTIONS.load(Ordering::Relaxed),
+        WORDS_RECLAIMED.load(Ordering::Relaxed),
+    )
+}
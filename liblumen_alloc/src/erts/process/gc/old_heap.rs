@@ -65,6 +65,11 @@ impl OldHeap {
     pub fn active(&self) -> bool {
         !self.start.is_null()
     }
+
+    /// The `ProcBin`s referenced from this heap
+    pub fn proc_bins(&self) -> impl Iterator<Item = &ProcBin> {
+        self.vheap.iter()
+    }
 }
 impl Heap for OldHeap {
     fn is_corrupted(&self) -> bool {
@@ -64,6 +64,8 @@ where
             let closure = Closure::from_raw_term(self);
             mem::size_of_val(closure.as_ref())
         } else {
+            // Maps are like tuples: a header followed by terms, so that their keys, values and
+            // hash map nodes are swept when the moved map is scanned
             header.sizeof()
         };
 
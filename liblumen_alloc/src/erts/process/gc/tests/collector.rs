@@ -5,6 +5,7 @@ use core::ops::Deref;
 use core::ptr::NonNull;
 
 use crate::erts::process::alloc::TermAlloc;
+use crate::erts::process::gc::GcError;
 use crate::erts::process::test::process;
 use crate::erts::term::closure::*;
 use crate::erts::term::prelude::*;
@@ -28,6 +29,51 @@ fn gc_simple_minor_test() {
     simple_gc_test(process);
 }
 
+// This test ensures that a collection that would grow the heap beyond `max_heap_size` fails, so
+// that the process can be killed
+#[test]
+fn gc_max_heap_size_kill_test() {
+    let process = process();
+    process.set_max_heap_size(MaxHeapSize {
+        size: 1,
+        kill: true,
+        error_logger: false,
+    });
+    process.set_flags(ProcessFlags::NeedFullSweep);
+
+    assert_eq!(
+        process.garbage_collect(0, &mut [][..]),
+        Err(GcError::MaxHeapSizeExceeded)
+    );
+}
+
+// This test ensures that exceeding `max_heap_size` without `kill` lets the collection continue
+#[test]
+fn gc_max_heap_size_without_kill_test() {
+    let process = process();
+    process.set_max_heap_size(MaxHeapSize {
+        size: 1,
+        kill: false,
+        error_logger: false,
+    });
+    process.set_flags(ProcessFlags::NeedFullSweep);
+
+    assert!(process.garbage_collect(0, &mut [][..]).is_ok());
+}
+
+// This test ensures that `fullsweep_after` of `0` makes every collection a full sweep
+#[test]
+fn gc_fullsweep_after_zero_test() {
+    let mut process = process();
+    process.set_fullsweep_after(0);
+
+    process.garbage_collect(0, &mut [][..]).unwrap();
+    assert_eq!(process.minor_gcs(), 0);
+
+    process.garbage_collect(0, &mut [][..]).unwrap();
+    assert_eq!(process.minor_gcs(), 0);
+}
+
 // This test is like `gc_simple_minor_test`, but also validates that after two collections,
 // that objects which have survived (tenured objects), have been moved to the old
 // generation heap
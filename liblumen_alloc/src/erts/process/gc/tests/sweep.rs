@@ -39,6 +39,63 @@ fn sweep_tuple() {
     assert_eq!(new_tuple_ref.get_element(1), Ok(atom!("world")));
 }
 
+#[test]
+fn sweep_map() {
+    let mut fromspace = RegionHeap::new(default_heap_layout());
+    let young = RegionHeap::new(default_heap_layout());
+    let old = RegionHeap::new(default_heap_layout());
+    let mut tospace = SemispaceHeap::new(young, old);
+    // Allocate term in fromspace, small enough to be a flatmap with keys and values tuples
+    let entries: Vec<(Term, Term)> = (0..16isize).map(|i| (fixnum!(i), atom!("value"))).collect();
+    let map = fromspace.map_from_slice(&entries).unwrap();
+    // Sanity check
+    assert_eq!(map.len(), 16);
+
+    // Get raw Term pointer
+    let map_ptr: *mut Term = map.as_ptr() as *mut Term;
+
+    // Sweep map into new young heap
+    let mut sweeper = MinorCollection::new(&mut fromspace, &mut tospace);
+    let result = unsafe { sweeper.sweep(map_ptr) };
+    assert!(result.is_some());
+
+    // We should have a new pointer
+    let (new_ptr, bytes_moved) = result.unwrap();
+    assert_ne!(map_ptr, new_ptr);
+    // Should have moved only the map itself, as its entries are separate terms
+    assert_eq!(bytes_moved, mem::size_of::<Map>());
+
+    // Sweep the keys and values tuples, as scanning the moved map would: the map is a header
+    // followed by its length, keys and values
+    for field in 2..4 {
+        unsafe {
+            let field_ptr = new_ptr.add(field);
+            let field_term = *field_ptr;
+            assert!(field_term.is_boxed());
+
+            let tuple_ptr: *mut Term = field_term.dyn_cast();
+            let (new_tuple_ptr, tuple_bytes_moved) = sweeper.sweep(tuple_ptr).unwrap();
+            assert_ne!(tuple_ptr, new_tuple_ptr);
+            // Should have moved the tuple header and its 16 elements
+            assert_eq!(tuple_bytes_moved, mem::size_of::<Term>() * 17);
+            // The old tuple header is now a move marker to the new tuple
+            assert!((*tuple_ptr).is_boxed());
+            assert_eq!((*tuple_ptr).dyn_cast::<Term>(), new_tuple_ptr);
+
+            field_ptr.write(new_tuple_ptr.into());
+        }
+    }
+
+    // Make sure moved term is still consistent
+    let new_map: Boxed<Map> = unsafe { Boxed::new_unchecked(new_ptr as *mut Map) };
+    assert_eq!(new_map.len(), 16);
+    assert_eq!(new_map.get(fixnum!(15)), Some(atom!("value")));
+    assert_eq!(
+        new_map.keys(),
+        entries.iter().map(|(key, _)| *key).collect::<Vec<Term>>()
+    );
+}
+
 #[test]
 fn sweep_heapbin() {
     use crate::erts::string::Encoding;
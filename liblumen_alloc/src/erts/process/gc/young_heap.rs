@@ -117,6 +117,11 @@ impl YoungHeap {
         distance_absolute(self.high_water_mark, self.start)
     }
 
+    /// The `ProcBin`s referenced from this heap
+    pub fn proc_bins(&self) -> impl Iterator<Item = &ProcBin> {
+        self.vheap.iter()
+    }
+
     /// Sets the high water mark to the current top of the heap
     #[inline]
     pub fn set_high_water_mark(&mut self) {
@@ -1,17 +1,35 @@
 use core::alloc::Layout;
 use core::ptr::NonNull;
 
-use log::trace;
+use log::{error, trace};
 
 use liblumen_core::util::pointer::distance_absolute;
 
 use crate::erts::exception::AllocResult;
 use crate::erts::term::prelude::{Boxed, ProcBin, Term};
+use crate::erts::to_word_size;
 
 use super::alloc::{self, *};
 use super::gc::{self, *};
 use super::{Process, ProcessFlags};
 
+/// The sizes of the heap reported by `erlang:process_info(Pid, garbage_collection_info)`, in
+/// words.  The `*_block_size`s are the allocated sizes while the others are the used sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GarbageCollectionInfo {
+    pub old_heap_block_size: usize,
+    pub heap_block_size: usize,
+    pub mbuf_size: usize,
+    pub recent_size: usize,
+    pub stack_size: usize,
+    pub old_heap_size: usize,
+    pub heap_size: usize,
+    pub bin_vheap_size: usize,
+    pub bin_vheap_block_size: usize,
+    pub bin_old_vheap_size: usize,
+    pub bin_old_vheap_block_size: usize,
+}
+
 /// This struct contains the actual semi-space heap that stack/heap allocations
 /// are delegated to, and provides coordination for garbage collection of the
 /// heap given the current process context.
@@ -40,6 +58,46 @@ impl ProcessHeap {
         self.heap.should_collect(gc_threshold)
     }
 
+    /// The size of the old generation, which is not included in `heap_size`
+    #[inline]
+    pub fn old_heap_size(&self) -> usize {
+        self.heap.old_generation().heap_size()
+    }
+
+    /// The number of words used in the old generation, which is not included in `heap_used`
+    #[inline]
+    pub fn old_heap_used(&self) -> usize {
+        self.heap.old_generation().heap_used()
+    }
+
+    /// The `ProcBin`s referenced from the young and old generations
+    pub fn proc_bins(&self) -> impl Iterator<Item = &ProcBin> {
+        self.heap
+            .young_generation()
+            .proc_bins()
+            .chain(self.heap.old_generation().proc_bins())
+    }
+
+    /// The sizes of the generations, with `mbuf_size` words in heap fragments
+    pub fn garbage_collection_info(&self, mbuf_size: usize) -> GarbageCollectionInfo {
+        let young = self.heap.young_generation();
+        let old = self.heap.old_generation();
+
+        GarbageCollectionInfo {
+            old_heap_block_size: old.heap_size(),
+            heap_block_size: young.heap_size(),
+            mbuf_size,
+            recent_size: young.mature_size(),
+            stack_size: young.stack_used(),
+            old_heap_size: old.heap_used(),
+            heap_size: young.heap_used(),
+            bin_vheap_size: to_word_size(young.virtual_heap_used()),
+            bin_vheap_block_size: to_word_size(young.virtual_size()),
+            bin_old_vheap_size: to_word_size(old.virtual_heap_used()),
+            bin_old_vheap_block_size: to_word_size(old.virtual_size()),
+        }
+    }
+
     #[cfg(test)]
     pub(super) fn heap(&self) -> &SemispaceProcessHeap {
         &self.heap
@@ -78,13 +136,20 @@ impl ProcessHeap {
 
         // Initialize the collector
         // Determine if the current collection requires a full sweep or not
-        if process.needs_fullsweep() || self.gen_gc_count >= process.max_gen_gcs {
+        if self.needs_full_collection(process) {
             self.collect_full(process, needed, roots)
         } else {
             self.collect_minor(process, needed, roots)
         }
     }
 
+    /// Whether the next collection is a full sweep instead of a minor collection
+    pub(super) fn needs_full_collection(&self, process: &Process) -> bool {
+        process.needs_fullsweep()
+            || self.gen_gc_count >= process.max_gen_gcs
+            || self.old_generation_is_full()
+    }
+
     /// Handles the specific details required to initialize and execute a full sweep garbage
     /// collection
     fn collect_full(
@@ -117,9 +182,8 @@ impl ProcessHeap {
             };
 
         // Verify that our projected heap size is not going to blow the max heap size, if set
-        // NOTE: When this happens, we will be left with no choice but to kill the process
-        if process.max_heap_size > 0 && process.max_heap_size < new_heap_size {
-            return Err(GcError::MaxHeapSizeExceeded);
+        if process.max_heap_size().is_exceeded_by(new_heap_size) {
+            reached_max_heap_size(process, new_heap_size)?;
         }
 
         // Unset heap_grow and need_fullsweep flags, because we are doing both
@@ -198,13 +262,13 @@ impl ProcessHeap {
 
         // Check if the needed space consumes less than 25% of the new heap,
         // and if so, shrink the new heap immediately to free the unused space
-        if total_size > needed_after * 4 && process.min_heap_size < total_size {
+        if total_size > needed_after * 4 && process.min_heap_size() < total_size {
             // Shrink to double our estimated need
             let mut estimate = needed_after * 2;
             // If our estimated need is too low, round up to the min heap size;
             // otherwise, calculate the next heap size bucket our need falls in
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -248,7 +312,7 @@ impl ProcessHeap {
         // the max heap size, if one was configured.
         //
         // If a max heap size is set, make sure we're not going to exceed it
-        if process.max_heap_size > 0 {
+        if process.max_heap_size().is_enabled() {
             // First, check if we have exceeded the max heap size
             let mut heap_size = size_before;
             // In this estimate, our stack size includes unused area between stack and heap
@@ -268,9 +332,8 @@ impl ProcessHeap {
             let baseline_size = stack_size + size_before + needed;
             heap_size += alloc::next_heap_size(baseline_size);
 
-            // When this error type is returned, a full sweep will be triggered
-            if heap_size > process.max_heap_size {
-                return Err(GcError::MaxHeapSizeExceeded);
+            if process.max_heap_size().is_exceeded_by(heap_size) {
+                reached_max_heap_size(process, heap_size)?;
             }
         }
 
@@ -356,8 +419,8 @@ impl ProcessHeap {
 
             // If the new estimate is less than the min heap size, then round up;
             // otherwise, round the estimate up to the nearest heap size bucket
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -380,6 +443,14 @@ impl ProcessHeap {
         }
     }
 
+    /// As in the BEAM, when the old generation can't hold the mature objects of the young
+    /// generation, a full sweep is done instead of a minor collection
+    fn old_generation_is_full(&self) -> bool {
+        let old = self.heap.old_generation();
+
+        old.active() && self.heap.young_generation().mature_size() > old.heap_available()
+    }
+
     /// In some cases, after a minor collection we may find that we have over-allocated for the
     /// new young heap, this is because we make a conservative estimate as to how much space will
     /// be needed, and if our collections are effective, that may leave a lot of unused space.
@@ -397,6 +468,34 @@ impl ProcessHeap {
         unsafe { self.heap.young_generation_mut().shrink(new_size) }
     }
 }
+
+/// Called when a collection would grow the heap of `process` to `total_heap_size` words, past its
+/// `max_heap_size`.
+///
+/// As in the BEAM, an error report is logged if `error_logger` is set, and the collection is
+/// abandoned with `GcError::MaxHeapSizeExceeded` if `kill` is set, so that the caller can kill the
+/// process.  Otherwise, the collection continues and the heap grows past the limit.
+fn reached_max_heap_size(process: &Process, total_heap_size: usize) -> Result<(), GcError> {
+    let max_heap_size = process.max_heap_size();
+
+    if max_heap_size.error_logger {
+        error!(
+            "\n     Process:          {}\n     Context:          maximum heap size reached\n     Max Heap Size:    {}\n     Total Heap Size:  {}\n     Kill:             {}\n     Error Logger:     {}",
+            process.pid(),
+            max_heap_size.size,
+            total_heap_size,
+            max_heap_size.kill,
+            max_heap_size.error_logger
+        );
+    }
+
+    if max_heap_size.kill {
+        Err(GcError::MaxHeapSizeExceeded)
+    } else {
+        Ok(())
+    }
+}
+
 impl HeapAlloc for ProcessHeap {
     #[inline]
     unsafe fn alloc_layout(&mut self, layout: Layout) -> AllocResult<NonNull<Term>> {
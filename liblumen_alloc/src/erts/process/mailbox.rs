@@ -1,3 +1,5 @@
+mod off_heap_queue;
+
 use core::default::Default;
 
 use alloc::collections::vec_deque::Iter;
@@ -10,6 +12,8 @@ use crate::erts::process::ffi::{set_process_signal, ProcessSignal};
 use crate::erts::process::Process;
 use crate::erts::term::prelude::Term;
 
+pub use self::off_heap_queue::OffHeapQueue;
+
 #[derive(Debug)]
 pub struct Mailbox {
     messages: VecDeque<Message>,
@@ -30,7 +34,7 @@ impl Mailbox {
     pub fn recv_peek(&self) -> Option<Term> {
         match self.messages.get(self.cursor) {
             None => None,
-            Some(Message::Process(message::Process { data })) => Some(*data),
+            Some(Message::Process(message::Process { data, .. })) => Some(*data),
             Some(Message::HeapFragment(message::HeapFragment { data, .. })) => Some(*data),
         }
     }
@@ -44,7 +48,8 @@ impl Mailbox {
     pub fn recv_increment(&mut self) {
         self.cursor += 1;
     }
-    pub fn recv_received(&mut self) {
+    /// Returns the received message, so that its sequential trace token can be received too.
+    pub fn recv_received(&mut self) -> Message {
         let message = self.messages.remove(self.cursor - 1).unwrap();
 
         if let Message::HeapFragment(_) = message {
@@ -52,6 +57,8 @@ impl Mailbox {
         }
 
         self.cursor = 0;
+
+        message
     }
     pub fn recv_timeout(&mut self) {
         self.cursor = 0;
@@ -106,7 +113,7 @@ impl Mailbox {
     /// `process` heap.
     pub fn receive(&mut self, process: &Process) -> Option<AllocResult<Term>> {
         self.messages.pop_front().map(|message| match message {
-            Message::Process(message::Process { data }) => {
+            Message::Process(message::Process { data, .. }) => {
                 self.decrement_seen();
 
                 Ok(data)
@@ -114,6 +121,7 @@ impl Mailbox {
             Message::HeapFragment(message::HeapFragment {
                 ref unsafe_ref_heap_fragment,
                 data,
+                ..
             }) => match data.clone_to_heap(&mut process.acquire_heap()) {
                 Ok(heap_data) => {
                     let mut off_heap = process.off_heap.lock();
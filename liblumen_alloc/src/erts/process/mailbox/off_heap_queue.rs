@@ -0,0 +1,153 @@
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use alloc::boxed::Box;
+
+use intrusive_collections::UnsafeRef;
+
+use crate::erts::message::{self, Message};
+
+/// Messages sent to a process using `off_heap` `message_queue_data` that the process has not yet
+/// moved into its `Mailbox`.
+///
+/// Senders push without taking the receiver's heap or mailbox locks.  Only the receiver, while
+/// holding its mailbox lock, takes the messages out, so there is a single consumer and taking
+/// the whole queue at once with a swap avoids the ABA problem of popping single nodes.
+#[derive(Debug)]
+pub struct OffHeapQueue {
+    /// The most recently pushed node, which links to the previously pushed nodes.
+    head: AtomicPtr<Node>,
+}
+
+impl OffHeapQueue {
+    pub fn push(&self, message: Message) {
+        let node = Box::into_raw(Box::new(Node {
+            message,
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            unsafe { (*node).next = head };
+
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current_head) => head = current_head,
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    /// Takes all messages in the order they were pushed.
+    pub fn take(&self) -> Take {
+        let mut head = self.head.swap(ptr::null_mut(), Ordering::Acquire);
+
+        // The nodes are linked newest first, so reverse them to receive oldest first
+        let mut reversed = ptr::null_mut();
+
+        while !head.is_null() {
+            let next = unsafe { (*head).next };
+            unsafe { (*head).next = reversed };
+            reversed = head;
+            head = next;
+        }
+
+        Take { head: reversed }
+    }
+}
+
+impl Default for OffHeapQueue {
+    fn default() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+impl Drop for OffHeapQueue {
+    fn drop(&mut self) {
+        for message in self.take() {
+            if let Message::HeapFragment(message::HeapFragment {
+                unsafe_ref_heap_fragment,
+                ..
+            }) = message
+            {
+                let heap_fragment_ptr = UnsafeRef::into_raw(unsafe_ref_heap_fragment);
+                unsafe { ptr::drop_in_place(heap_fragment_ptr) };
+            }
+        }
+    }
+}
+
+unsafe impl Send for OffHeapQueue {}
+unsafe impl Sync for OffHeapQueue {}
+
+pub struct Take {
+    head: *mut Node,
+}
+
+impl Iterator for Take {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Message> {
+        if self.head.is_null() {
+            None
+        } else {
+            let node = unsafe { Box::from_raw(self.head) };
+            self.head = node.next;
+
+            Some(node.message)
+        }
+    }
+}
+
+impl Drop for Take {
+    fn drop(&mut self) {
+        // Messages that were taken, but not iterated are dropped like they are in the queue
+        let rest = OffHeapQueue {
+            head: AtomicPtr::new(self.head),
+        };
+        self.head = ptr::null_mut();
+
+        drop(rest);
+    }
+}
+
+struct Node {
+    message: Message,
+    next: *mut Node,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::erts::term::prelude::*;
+    use crate::fixnum;
+
+    #[test]
+    fn take_returns_messages_in_push_order() {
+        let queue: OffHeapQueue = Default::default();
+
+        for i in 0..3 {
+            queue.push(Message::Process(message::Process {
+                data: fixnum!(i),
+                seq_trace_token: None,
+            }));
+        }
+
+        assert!(!queue.is_empty());
+
+        let data: Vec<Term> = queue.take().map(|message| *message.data()).collect();
+
+        assert_eq!(data, vec![fixnum!(0), fixnum!(1), fixnum!(2)]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use core::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use crate::erts::term::prelude::*;
+
+/// The `max_heap_size` of a process.
+///
+/// As in the BEAM, the limit is checked during garbage collection against the size the heap will
+/// have after the collection.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MaxHeapSize {
+    /// The maximum heap size in words.  `0` disables the limit.
+    pub size: usize,
+    /// Whether the process is killed when its heap exceeds `size`
+    pub kill: bool,
+    /// Whether an error report is logged when the process's heap exceeds `size`
+    pub error_logger: bool,
+}
+
+impl MaxHeapSize {
+    pub fn is_enabled(&self) -> bool {
+        0 < self.size
+    }
+
+    /// Whether a heap of `total_heap_size` words exceeds the limit
+    pub fn is_exceeded_by(&self, total_heap_size: usize) -> bool {
+        self.is_enabled() && self.size < total_heap_size
+    }
+}
+
+impl Default for MaxHeapSize {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            kill: true,
+            error_logger: true,
+        }
+    }
+}
+
+impl TryFrom<Term> for MaxHeapSize {
+    type Error = anyhow::Error;
+
+    /// `Size` or `#{size => Size, kill => boolean(), error_logger => boolean()}`.  As in the BEAM,
+    /// `size` is required in the map, `kill` and `error_logger` default to `true`, and other keys
+    /// are ignored.
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut max_heap_size: Self = Default::default();
+
+        match term.decode().unwrap() {
+            TypedTerm::Map(map) => {
+                let size = map
+                    .get(atom!("size"))
+                    .ok_or_else(|| anyhow!("max_heap_size map ({}) is missing size", term))?;
+                max_heap_size.size = size
+                    .try_into()
+                    .context("max_heap_size size is not a non-negative integer")?;
+
+                if let Some(kill) = map.get(atom!("kill")) {
+                    max_heap_size.kill = kill
+                        .try_into()
+                        .context("max_heap_size kill is not a boolean")?;
+                }
+
+                if let Some(error_logger) = map.get(atom!("error_logger")) {
+                    max_heap_size.error_logger = error_logger
+                        .try_into()
+                        .context("max_heap_size error_logger is not a boolean")?;
+                }
+            }
+            _ => {
+                max_heap_size.size = term.try_into().context(
+                    "max_heap_size is not a non-negative integer or a map with size, kill, and error_logger",
+                )?;
+            }
+        }
+
+        Ok(max_heap_size)
+    }
+}
@@ -1,12 +1,17 @@
-use std::convert::{TryFrom, TryInto};
+use core::convert::{TryFrom, TryInto};
 
 use anyhow::Context;
 
-use liblumen_alloc::erts::term::prelude::*;
+use crate::erts::term::prelude::*;
 
-#[derive(Clone, Copy, Debug)]
+/// Where messages sent to a process are stored until they are received.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum MessageQueueData {
+    /// Senders copy messages directly into the receiver's heap when they can lock it.
     OnHeap,
+    /// Senders copy messages into heap fragments that are queued without locking the receiver's
+    /// heap or mailbox, so that sending does not contend with or trigger garbage collection of
+    /// the receiver.
     OffHeap,
 }
 
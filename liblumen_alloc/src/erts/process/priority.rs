@@ -1,10 +1,12 @@
 use core::convert::{TryFrom, TryInto};
+use core::sync::atomic::{AtomicU8, Ordering};
 
 use anyhow::Context;
 
 use crate::erts::term::prelude::*;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(u8)]
 pub enum Priority {
     Low,
     Normal,
@@ -34,3 +36,39 @@ impl TryFrom<Term> for Priority {
         }
     }
 }
+
+impl From<u8> for Priority {
+    fn from(n: u8) -> Self {
+        match n {
+            0 => Priority::Low,
+            1 => Priority::Normal,
+            2 => Priority::High,
+            3 => Priority::Max,
+            _ => unreachable!("{} is not a Priority", n),
+        }
+    }
+}
+
+/// This type is a wrapper around `AtomicU8` and provides atomic semantics for `Priority`, so that
+/// a process can change its own priority while other threads read it.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct AtomicPriority(AtomicU8);
+impl AtomicPriority {
+    #[inline]
+    pub fn new(priority: Priority) -> Self {
+        Self(AtomicU8::new(priority as u8))
+    }
+
+    /// Fetch the current value, using `Acquire` ordering
+    #[inline]
+    pub fn load(&self) -> Priority {
+        self.0.load(Ordering::Acquire).into()
+    }
+
+    /// Replaces the current value, returning the old value, using `AcqRel` ordering
+    #[inline]
+    pub fn swap(&self, priority: Priority) -> Priority {
+        self.0.swap(priority as u8, Ordering::AcqRel).into()
+    }
+}
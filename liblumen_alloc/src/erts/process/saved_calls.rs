@@ -0,0 +1,105 @@
+use alloc::collections::vec_deque::Iter;
+use alloc::collections::VecDeque;
+
+use crate::erts::ModuleFunctionArity;
+
+/// The most recent calls of a process, as enabled with `process_flag(save_calls, N)`.
+#[derive(Debug, Default)]
+pub struct SavedCalls {
+    capacity: usize,
+    calls: VecDeque<ModuleFunctionArity>,
+}
+
+impl SavedCalls {
+    /// The maximum number of calls that can be saved
+    pub const MAX_CAPACITY: usize = 10000;
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the old capacity.  When the capacity shrinks, the oldest calls are discarded.
+    pub fn set_capacity(&mut self, capacity: usize) -> usize {
+        assert!(capacity <= Self::MAX_CAPACITY);
+
+        while capacity < self.calls.len() {
+            self.calls.pop_front();
+        }
+
+        core::mem::replace(&mut self.capacity, capacity)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        0 < self.capacity
+    }
+
+    /// Calls from oldest to most recent
+    pub fn iter(&self) -> Iter<ModuleFunctionArity> {
+        self.calls.iter()
+    }
+
+    pub fn push(&mut self, module_function_arity: ModuleFunctionArity) {
+        if self.is_enabled() {
+            if self.calls.len() == self.capacity {
+                self.calls.pop_front();
+            }
+
+            self.calls.push_back(module_function_arity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::erts::term::prelude::Atom;
+
+    #[test]
+    fn push_is_ignored_when_not_enabled() {
+        let mut saved_calls: SavedCalls = Default::default();
+
+        saved_calls.push(module_function_arity(0));
+
+        assert_eq!(saved_calls.iter().count(), 0);
+    }
+
+    #[test]
+    fn push_discards_oldest_call_when_full() {
+        let mut saved_calls: SavedCalls = Default::default();
+        saved_calls.set_capacity(2);
+
+        for arity in 0..3 {
+            saved_calls.push(module_function_arity(arity));
+        }
+
+        let arities: Vec<u8> = saved_calls.iter().map(|mfa| mfa.arity).collect();
+
+        assert_eq!(arities, vec![1, 2]);
+    }
+
+    #[test]
+    fn set_capacity_returns_old_capacity_and_discards_oldest_calls() {
+        let mut saved_calls: SavedCalls = Default::default();
+
+        assert_eq!(saved_calls.set_capacity(3), 0);
+
+        for arity in 0..3 {
+            saved_calls.push(module_function_arity(arity));
+        }
+
+        assert_eq!(saved_calls.set_capacity(1), 3);
+
+        let arities: Vec<u8> = saved_calls.iter().map(|mfa| mfa.arity).collect();
+
+        assert_eq!(arities, vec![2]);
+    }
+
+    fn module_function_arity(arity: u8) -> ModuleFunctionArity {
+        ModuleFunctionArity {
+            module: Atom::from_str("module"),
+            function: Atom::from_str("function"),
+            arity,
+        }
+    }
+}
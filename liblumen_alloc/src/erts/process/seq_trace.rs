@@ -0,0 +1,338 @@
+use core::cmp;
+use core::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use crate::erts::process::Process;
+use crate::erts::term::prelude::*;
+use crate::fixnum;
+
+/// The flags of a `SeqTraceToken`.
+///
+/// The bits are the same as the `Flags` integer of the token tuple returned by
+/// `seq_trace:get_token/0`, so that tokens can be passed back to `seq_trace:set_token/1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct SeqTraceFlags(u8);
+impl SeqTraceFlags {
+    #![allow(non_upper_case_globals)]
+
+    /// No events are traced
+    pub const None: Self = Self(0);
+    /// Sending a message is traced
+    pub const Send: Self = Self(1 << 0);
+    /// Receiving a message is traced
+    pub const Receive: Self = Self(1 << 1);
+    /// `seq_trace:print/1,2` is traced
+    pub const Print: Self = Self(1 << 2);
+    /// Trace messages include an `erlang:now/0` timestamp
+    pub const Timestamp: Self = Self(1 << 3);
+    /// Trace messages include a strict monotonic timestamp
+    pub const StrictMonotonicTimestamp: Self = Self(1 << 4);
+    /// Trace messages include a monotonic timestamp
+    pub const MonotonicTimestamp: Self = Self(1 << 5);
+    /// Spawning a process is traced
+    pub const Spawn: Self = Self(1 << 6);
+
+    const ALL: u8 = (1 << 7) - 1;
+
+    /// The flag with the `name` used by `seq_trace:set_token/2`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "monotonic_timestamp" => Some(Self::MonotonicTimestamp),
+            "print" => Some(Self::Print),
+            "receive" => Some(Self::Receive),
+            "send" => Some(Self::Send),
+            "spawn" => Some(Self::Spawn),
+            "strict_monotonic_timestamp" => Some(Self::StrictMonotonicTimestamp),
+            "timestamp" => Some(Self::Timestamp),
+            _ => None,
+        }
+    }
+
+    pub fn are_set(&self, flags: Self) -> bool {
+        (self.0 & flags.0) == flags.0
+    }
+
+    /// Returns whether `flags` were set before
+    pub fn set(&mut self, flags: Self, value: bool) -> bool {
+        let old_value = self.are_set(flags);
+
+        if value {
+            self.0 |= flags.0;
+        } else {
+            self.0 &= !flags.0;
+        }
+
+        old_value
+    }
+}
+impl Into<u8> for SeqTraceFlags {
+    #[inline]
+    fn into(self) -> u8 {
+        self.0
+    }
+}
+impl TryFrom<u8> for SeqTraceFlags {
+    type Error = anyhow::Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        if n & !Self::ALL == 0 {
+            Ok(Self(n))
+        } else {
+            Err(anyhow!("flags ({}) has unsupported bits set", n))
+        }
+    }
+}
+
+/// The serial of a `SeqTraceToken`, which orders the trace events of a sequential trace.
+///
+/// Each send increments `current`.  On receive, `previous` becomes the `current` of the sender,
+/// so that the trace events can be ordered even though they come from different processes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SeqTraceSerial {
+    pub previous: usize,
+    pub current: usize,
+}
+impl SeqTraceSerial {
+    /// `{Previous, Current}`
+    pub fn to_term(&self, process: &Process) -> Term {
+        process.tuple_from_slice(&[
+            process.integer(self.previous),
+            process.integer(self.current),
+        ])
+    }
+}
+impl TryFrom<Term> for SeqTraceSerial {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .with_context(|| format!("serial ({}) is not a tuple", term))?;
+
+        if tuple.len() == 2 {
+            let previous: usize = tuple[0].try_into().with_context(|| {
+                format!(
+                    "previous ({}) in serial ({}) is not a non-negative integer",
+                    tuple[0], term
+                )
+            })?;
+            let current: usize = tuple[1].try_into().with_context(|| {
+                format!(
+                    "current ({}) in serial ({}) is not a non-negative integer",
+                    tuple[1], term
+                )
+            })?;
+
+            Ok(Self { previous, current })
+        } else {
+            Err(anyhow!(
+                "serial ({}) is not a 2-tuple {{previous, current}}",
+                term
+            ))
+        }
+    }
+}
+
+/// The sequential trace token of a process, which is passed along with every message the process
+/// sends, so that the receiver is traced too.
+///
+/// See http://erlang.org/doc/man/seq_trace.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeqTraceToken {
+    pub flags: SeqTraceFlags,
+    /// Only immediate terms are allowed, so that the token can be copied between processes
+    /// without copying any data between their heaps.
+    label: Term,
+    pub serial: SeqTraceSerial,
+    /// The last process to pass along the token
+    pub from: Pid,
+}
+impl SeqTraceToken {
+    pub fn new(from: Pid) -> Self {
+        Self {
+            flags: SeqTraceFlags::None,
+            label: fixnum!(0),
+            serial: Default::default(),
+            from,
+        }
+    }
+
+    pub fn label(&self) -> Term {
+        self.label
+    }
+
+    /// Returns the old label
+    pub fn set_label(&mut self, label: Term) -> anyhow::Result<Term> {
+        if label.is_immediate() {
+            Ok(core::mem::replace(&mut self.label, label))
+        } else {
+            Err(anyhow!(
+                "label ({}) is not an immediate term, such as an atom, small integer, or local pid",
+                label
+            ))
+        }
+    }
+
+    /// Increments the serial for passing the token along with a message sent by `sender`.
+    pub fn send(&mut self, sender: Pid) {
+        self.serial.current += 1;
+        self.from = sender;
+    }
+
+    /// The token the receiver of `message_token` has after receiving it.
+    ///
+    /// The receiver keeps its own `current`, if it is greater than the sender's, so that any
+    /// message it sends is ordered after everything it already sent.
+    pub fn receive(option_receiver_token: Option<Self>, message_token: Self) -> Self {
+        let receiver_current = option_receiver_token
+            .map(|receiver_token| receiver_token.serial.current)
+            .unwrap_or(0);
+
+        Self {
+            serial: SeqTraceSerial {
+                previous: message_token.serial.current,
+                current: cmp::max(receiver_current, message_token.serial.current),
+            },
+            ..message_token
+        }
+    }
+
+    /// `{Flags, Label, SeqTraceSerial, From, LastCnt}` as returned by `seq_trace:get_token/0`
+    pub fn to_term(&self, process: &Process) -> Term {
+        let flags: u8 = self.flags.into();
+
+        process.tuple_from_slice(&[
+            process.integer(flags),
+            self.label,
+            process.integer(self.serial.current),
+            self.from.encode().unwrap(),
+            process.integer(self.serial.previous),
+        ])
+    }
+}
+impl TryFrom<Term> for SeqTraceToken {
+    type Error = anyhow::Error;
+
+    /// `{Flags, Label, SeqTraceSerial, From, LastCnt}` as passed to `seq_trace:set_token/1`
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .with_context(|| format!("token ({}) is not a tuple", term))?;
+
+        if tuple.len() != 5 {
+            return Err(anyhow!(
+                "token ({}) is not a 5-tuple {{flags, label, serial, from, last_count}}",
+                term
+            ));
+        }
+
+        let flags_u8: u8 = tuple[0].try_into().with_context(|| {
+            format!(
+                "flags ({}) in token ({}) is not a byte-sized integer",
+                tuple[0], term
+            )
+        })?;
+        let flags = flags_u8.try_into()?;
+        let current: usize = tuple[2].try_into().with_context(|| {
+            format!(
+                "serial ({}) in token ({}) is not a non-negative integer",
+                tuple[2], term
+            )
+        })?;
+        let from: Pid = tuple[3]
+            .try_into()
+            .with_context(|| format!("from ({}) in token ({}) is not a pid", tuple[3], term))?;
+        let previous: usize = tuple[4].try_into().with_context(|| {
+            format!(
+                "last_count ({}) in token ({}) is not a non-negative integer",
+                tuple[4], term
+            )
+        })?;
+
+        let mut seq_trace_token = Self {
+            flags,
+            label: fixnum!(0),
+            serial: SeqTraceSerial { previous, current },
+            from,
+        };
+        seq_trace_token.set_label(tuple[1])?;
+
+        Ok(seq_trace_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_increments_current_serial() {
+        let mut seq_trace_token = SeqTraceToken::new(Pid::new(0, 0).unwrap());
+        let sender = Pid::new(1, 0).unwrap();
+
+        seq_trace_token.send(sender);
+
+        assert_eq!(
+            seq_trace_token.serial,
+            SeqTraceSerial {
+                previous: 0,
+                current: 1
+            }
+        );
+        assert_eq!(seq_trace_token.from, sender);
+    }
+
+    #[test]
+    fn receive_keeps_greater_receiver_current_serial() {
+        let from = Pid::new(0, 0).unwrap();
+        let mut receiver_token = SeqTraceToken::new(from);
+        receiver_token.serial.current = 5;
+        let mut message_token = SeqTraceToken::new(from);
+        message_token.serial.current = 3;
+
+        let received_token = SeqTraceToken::receive(Some(receiver_token), message_token);
+
+        assert_eq!(
+            received_token.serial,
+            SeqTraceSerial {
+                previous: 3,
+                current: 5
+            }
+        );
+    }
+
+    #[test]
+    fn receive_without_receiver_token_uses_message_current_serial() {
+        let mut message_token = SeqTraceToken::new(Pid::new(0, 0).unwrap());
+        message_token.serial.current = 3;
+
+        let received_token = SeqTraceToken::receive(None, message_token);
+
+        assert_eq!(
+            received_token.serial,
+            SeqTraceSerial {
+                previous: 3,
+                current: 3
+            }
+        );
+    }
+
+    #[test]
+    fn set_label_with_immediate_returns_old_label() {
+        let mut seq_trace_token = SeqTraceToken::new(Pid::new(0, 0).unwrap());
+
+        assert_eq!(seq_trace_token.set_label(fixnum!(1)).unwrap(), fixnum!(0));
+        assert_eq!(seq_trace_token.label(), fixnum!(1));
+    }
+
+    #[test]
+    fn flags_set_returns_old_value() {
+        let mut flags = SeqTraceFlags::None;
+
+        assert!(!flags.set(SeqTraceFlags::Send, true));
+        assert!(flags.set(SeqTraceFlags::Send, false));
+        assert!(!flags.are_set(SeqTraceFlags::Send));
+    }
+}
@@ -49,6 +49,331 @@ mod traps_exit {
     }
 }
 
+mod set_priority {
+    use super::*;
+
+    #[test]
+    fn returns_old_value() {
+        let process = process();
+
+        assert_eq!(process.set_priority(Priority::High), Priority::Normal);
+        assert_eq!(process.set_priority(Priority::Low), Priority::High);
+        assert_eq!(process.priority(), Priority::Low);
+    }
+}
+
+mod saved_calls {
+    use super::*;
+
+    #[test]
+    fn is_none_until_calls_are_saved() {
+        let process = process();
+
+        assert_eq!(process.saved_calls(), None);
+        assert_eq!(process.save_calls(2), 0);
+        assert_eq!(process.saved_calls(), Some(vec![]));
+    }
+
+    #[test]
+    fn save_calls_flag_is_only_set_while_calls_are_saved() {
+        let process = process();
+
+        assert!(!process.are_flags_set(ProcessFlags::SaveCalls));
+
+        process.save_calls(2);
+
+        assert!(process.are_flags_set(ProcessFlags::SaveCalls));
+
+        process.save_calls(0);
+
+        assert!(!process.are_flags_set(ProcessFlags::SaveCalls));
+    }
+}
+
+mod set_message_queue_data {
+    use super::*;
+
+    #[test]
+    fn returns_old_value() {
+        let process = process();
+
+        assert_eq!(
+            process.set_message_queue_data(MessageQueueData::OffHeap),
+            MessageQueueData::OnHeap
+        );
+        assert_eq!(
+            process.set_message_queue_data(MessageQueueData::OnHeap),
+            MessageQueueData::OffHeap
+        );
+    }
+}
+
+mod send_from_other {
+    use super::*;
+
+    use core::convert::TryInto;
+
+    use crate::erts::message::{self, Message};
+    use crate::erts::term::prelude::*;
+
+    #[test]
+    fn with_on_heap_message_queue_data_copies_into_heap() {
+        let sender = process();
+        let process = process();
+        let heap_used = process.young_heap_used();
+
+        process.send_from_other(sender.tuple_from_slice(&[fixnum!(1)]));
+
+        assert!(heap_used < process.young_heap_used());
+
+        let mailbox_guard = process.acquire_mailbox();
+        let mailbox = mailbox_guard.borrow();
+
+        assert!(match mailbox.iter().next() {
+            Some(Message::Process(message::Process { data, .. })) => element(*data) == fixnum!(1),
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn with_off_heap_message_queue_data_copies_into_heap_fragment() {
+        let sender = process();
+        let process = process();
+        process.set_message_queue_data(MessageQueueData::OffHeap);
+        let heap_used = process.young_heap_used();
+
+        process.send_from_other(sender.tuple_from_slice(&[fixnum!(1)]));
+
+        assert_eq!(process.young_heap_used(), heap_used);
+
+        let mailbox_guard = process.acquire_mailbox();
+        let mailbox = mailbox_guard.borrow();
+
+        assert!(match mailbox.iter().next() {
+            Some(Message::HeapFragment(message::HeapFragment { data, .. })) => {
+                element(*data) == fixnum!(1)
+            }
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn switching_message_queue_data_keeps_order() {
+        let sender = process();
+        let process = process();
+
+        process.send_from_other(sender.tuple_from_slice(&[fixnum!(0)]));
+        process.set_message_queue_data(MessageQueueData::OffHeap);
+        process.send_from_other(sender.tuple_from_slice(&[fixnum!(1)]));
+        process.set_message_queue_data(MessageQueueData::OnHeap);
+        process.send_from_other(sender.tuple_from_slice(&[fixnum!(2)]));
+
+        let mut mailbox_guard = process.acquire_mailbox();
+        let mailbox = mailbox_guard.get_mut();
+
+        for i in 0..3 {
+            let data = mailbox.receive(&process).unwrap().unwrap();
+
+            assert_eq!(element(data), fixnum!(i));
+        }
+
+        assert!(mailbox.receive(&process).is_none());
+    }
+
+    fn element(tuple: Term) -> Term {
+        let boxed_tuple: Boxed<Tuple> = tuple.try_into().unwrap();
+
+        boxed_tuple[0]
+    }
+}
+
+mod suspend {
+    use super::*;
+
+    #[test]
+    fn counts_suspends_per_suspender() {
+        let process = process();
+        let suspender_pid = Pid::new(1, 0).unwrap();
+        let other_suspender_pid = Pid::new(2, 0).unwrap();
+
+        assert!(!process.is_suspended());
+        assert_eq!(process.suspend(suspender_pid), 1);
+        assert_eq!(process.suspend(suspender_pid), 2);
+        assert_eq!(process.suspend(other_suspender_pid), 1);
+        assert!(process.is_suspended_by(suspender_pid));
+
+        assert_eq!(process.resume(suspender_pid), Some(1));
+        assert_eq!(process.resume(suspender_pid), Some(0));
+        assert_eq!(process.resume(suspender_pid), None);
+        assert!(!process.is_suspended_by(suspender_pid));
+        assert!(process.is_suspended());
+
+        assert!(process.resume_all(other_suspender_pid));
+        assert!(!process.is_suspended());
+    }
+}
+
+mod exhaust_reductions {
+    use super::*;
+
+    #[test]
+    fn process_is_reduced() {
+        let process = process();
+
+        assert!(!process.is_reduced());
+
+        process.exhaust_reductions();
+
+        assert!(process.is_reduced());
+    }
+}
+
+mod garbage_collect_if_forced {
+    use super::*;
+
+    #[test]
+    fn clears_forced_collection() {
+        let process = process();
+
+        assert!(!process.are_flags_set(ProcessFlags::ForceGC));
+
+        process.force_garbage_collection(true);
+
+        assert!(process.are_flags_set(ProcessFlags::ForceGC | ProcessFlags::NeedFullSweep));
+        assert!(process.garbage_collect_if_forced().is_ok());
+        assert!(!process.are_flags_set(ProcessFlags::ForceGC));
+    }
+
+    #[test]
+    fn counts_collection_in_statistics() {
+        let process = process();
+        let (collections_before, _) = gc::statistics();
+
+        process.force_garbage_collection(true);
+
+        assert!(process.garbage_collect_if_forced().is_ok());
+
+        let (collections_after, _) = gc::statistics();
+
+        assert!(collections_before < collections_after);
+    }
+}
+
+mod seq_trace_token {
+    use super::*;
+
+    #[test]
+    fn send_increments_serial_and_is_passed_along_with_message() {
+        let sender = process();
+        let receiver = process();
+        sender.set_seq_trace_token(Some(SeqTraceToken::new(sender.pid())));
+
+        let sent_seq_trace_token = sender.send_seq_trace_token();
+        receiver.send_from_other_with_seq_trace_token(fixnum!(0), sent_seq_trace_token);
+
+        assert_eq!(sent_seq_trace_token.unwrap().serial.current, 1);
+        assert_eq!(sender.seq_trace_token(), sent_seq_trace_token);
+        assert_eq!(
+            receiver
+                .acquire_mailbox()
+                .borrow()
+                .iter()
+                .next()
+                .unwrap()
+                .seq_trace_token(),
+            sent_seq_trace_token
+        );
+    }
+
+    #[test]
+    fn receive_without_message_token_clears_token() {
+        let process = process();
+        process.set_seq_trace_token(Some(SeqTraceToken::new(process.pid())));
+
+        assert_eq!(process.receive_seq_trace_token(None), None);
+        assert_eq!(process.seq_trace_token(), None);
+    }
+
+    #[test]
+    fn receive_with_message_token_sets_previous_serial() {
+        let sender = process();
+        let receiver = process();
+        sender.set_seq_trace_token(Some(SeqTraceToken::new(sender.pid())));
+        sender.send_seq_trace_token();
+        let message_seq_trace_token = sender.send_seq_trace_token();
+
+        let received_seq_trace_token = receiver
+            .receive_seq_trace_token(message_seq_trace_token)
+            .unwrap();
+
+        assert_eq!(
+            received_seq_trace_token.serial,
+            SeqTraceSerial {
+                previous: 2,
+                current: 2
+            }
+        );
+        assert_eq!(received_seq_trace_token.from, sender.pid());
+    }
+}
+
+mod tracing {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref TRACED_LINKS: Mutex<Vec<(Pid, Pid)>> = Mutex::new(Vec::new());
+    }
+
+    #[test]
+    fn link_traces_both_processes_with_procs_flag() {
+        set_trace_event_handler(|process, _tracing, event| match event {
+            TraceEvent::Link(other) | TraceEvent::GettingLinked(other) => {
+                TRACED_LINKS.lock().unwrap().push((process.pid(), *other));
+            }
+            _ => (),
+        });
+
+        let tracer = process();
+        let traced = process();
+        let other = process();
+        traced.set_tracing(Some(Tracing {
+            tracer: tracer.pid(),
+            flags: TraceFlags::Procs,
+        }));
+        other.set_tracing(Some(Tracing {
+            tracer: tracer.pid(),
+            flags: TraceFlags::Send,
+        }));
+
+        traced.link(&other);
+
+        let traced_links = TRACED_LINKS.lock().unwrap();
+
+        assert!(traced_links.contains(&(traced.pid(), other.pid())));
+        assert!(!traced_links.contains(&(other.pid(), traced.pid())));
+    }
+
+    #[test]
+    fn tracing_with_flags_is_none_when_sensitive() {
+        let process = process();
+        process.set_tracing(Some(Tracing {
+            tracer: process.pid(),
+            flags: TraceFlags::Send,
+        }));
+
+        assert!(process.tracing_with_flags(TraceFlags::Send).is_some());
+        assert!(process.tracing_with_flags(TraceFlags::Receive).is_none());
+
+        process.set_sensitive(true);
+
+        assert!(process.tracing_with_flags(TraceFlags::Send).is_none());
+    }
+}
+
 mod integer {
     use super::*;
 
@@ -0,0 +1,174 @@
+use once_cell::sync::OnceCell;
+
+use crate::erts::exception::Class;
+use crate::erts::process::Process;
+use crate::erts::term::prelude::*;
+use crate::erts::ModuleFunctionArity;
+
+/// The flags of the events traced for a process, as set with `erlang:trace/3`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct TraceFlags(u16);
+impl TraceFlags {
+    #![allow(non_upper_case_globals)]
+
+    /// No events are traced
+    pub const None: Self = Self(0);
+    /// Sending a message is traced
+    pub const Send: Self = Self(1 << 0);
+    /// Receiving a message is traced
+    pub const Receive: Self = Self(1 << 1);
+    /// Spawning, exiting, linking, and unlinking are traced
+    pub const Procs: Self = Self(1 << 2);
+    /// Calls to functions matching a trace pattern are traced
+    pub const Call: Self = Self(1 << 3);
+    /// Returning to a function is traced, in combination with `Call`
+    pub const ReturnTo: Self = Self(1 << 4);
+    /// Scheduling in and out is traced
+    pub const Running: Self = Self(1 << 5);
+    /// The start and end of garbage collections are traced
+    pub const GarbageCollection: Self = Self(1 << 6);
+    /// Trace messages include an `erlang:now/0` timestamp
+    pub const Timestamp: Self = Self(1 << 7);
+
+    /// The flag with the `name` used by `erlang:trace/3`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "call" => Some(Self::Call),
+            "garbage_collection" => Some(Self::GarbageCollection),
+            "procs" => Some(Self::Procs),
+            "receive" => Some(Self::Receive),
+            "return_to" => Some(Self::ReturnTo),
+            "running" => Some(Self::Running),
+            "send" => Some(Self::Send),
+            "timestamp" => Some(Self::Timestamp),
+            _ => None,
+        }
+    }
+
+    pub fn are_set(&self, flags: Self) -> bool {
+        (self.0 & flags.0) == flags.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether `flags` were set before
+    pub fn set(&mut self, flags: Self, value: bool) -> bool {
+        let old_value = self.are_set(flags);
+
+        if value {
+            self.0 |= flags.0;
+        } else {
+            self.0 &= !flags.0;
+        }
+
+        old_value
+    }
+}
+impl Into<u16> for TraceFlags {
+    #[inline]
+    fn into(self) -> u16 {
+        self.0
+    }
+}
+impl core::ops::BitOr for TraceFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The tracer and flags of a traced process
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tracing {
+    /// The process that receives the `{trace, Pid, ...}` messages
+    pub tracer: Pid,
+    pub flags: TraceFlags,
+}
+
+/// The trace events that happen inside of `Process`.  The events that need the rest of the
+/// runtime, such as sending, receiving, spawning, and scheduling, are traced by the runtime.
+#[derive(Debug)]
+pub enum TraceEvent<'a> {
+    /// A function is called with `arguments`
+    Call {
+        module_function_arity: ModuleFunctionArity,
+        arguments: &'a [Term],
+    },
+    /// A function returned to `module_function_arity`
+    ReturnTo {
+        module_function_arity: ModuleFunctionArity,
+    },
+    /// `module_function_arity` returned `returned`
+    ReturnFrom {
+        module_function_arity: ModuleFunctionArity,
+        returned: Term,
+    },
+    /// `module_function_arity` raised an exception of `class` with `reason`
+    ExceptionFrom {
+        module_function_arity: ModuleFunctionArity,
+        class: Class,
+        reason: Term,
+    },
+    GarbageCollectionStart {
+        major: bool,
+        heap_size: usize,
+    },
+    GarbageCollectionEnd {
+        major: bool,
+        heap_size: usize,
+    },
+    /// The process linked to `Pid`
+    Link(Pid),
+    /// The process was linked to by `Pid`
+    GettingLinked(Pid),
+    /// The process unlinked from `Pid`
+    Unlink(Pid),
+    /// The process was unlinked by `Pid`
+    GettingUnlinked(Pid),
+}
+
+/// Delivers the `TraceEvent` of the traced `Process` to the tracer of `Tracing`
+pub type TraceEventHandler = fn(&Process, Tracing, &TraceEvent);
+
+static TRACE_EVENT_HANDLER: OnceCell<TraceEventHandler> = OnceCell::new();
+
+/// Sets the handler for trace events.  Only the first handler is kept, so the runtime can set it
+/// every time tracing is enabled.
+pub fn set_trace_event_handler(trace_event_handler: TraceEventHandler) {
+    let _ = TRACE_EVENT_HANDLER.set(trace_event_handler);
+}
+
+pub(super) fn trace_event_handler() -> Option<&'static TraceEventHandler> {
+    TRACE_EVENT_HANDLER.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_matches_trace_3_flags() {
+        assert_eq!(TraceFlags::from_name("receive"), Some(TraceFlags::Receive));
+        assert_eq!(
+            TraceFlags::from_name("garbage_collection"),
+            Some(TraceFlags::GarbageCollection)
+        );
+        assert_eq!(TraceFlags::from_name("all"), None);
+    }
+
+    #[test]
+    fn set_returns_old_value() {
+        let mut flags = TraceFlags::None;
+
+        assert!(!flags.set(TraceFlags::Send | TraceFlags::Call, true));
+        assert!(flags.are_set(TraceFlags::Send));
+        assert!(flags.set(TraceFlags::Send, false));
+        assert!(!flags.is_empty());
+        assert!(flags.set(TraceFlags::Call, false));
+        assert!(flags.is_empty());
+    }
+}
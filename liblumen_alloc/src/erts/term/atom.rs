@@ -54,6 +54,16 @@ pub unsafe extern "C" fn InitializeLumenAtomTable(table: *const ConstantAtom, le
     }
 }
 
+/// The number of atoms currently in the atom table
+pub fn atom_count() -> usize {
+    ATOMS.read().len()
+}
+
+/// The number of bytes used by the atom table for the names and their entries
+pub fn atom_memory() -> usize {
+    ATOMS.read().memory()
+}
+
 pub fn dump_atoms() {
     let table = ATOMS.read();
     table.dump();
@@ -448,6 +458,18 @@ impl AtomTable {
         Ok(id)
     }
 
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    fn memory(&self) -> usize {
+        // Each name is in both `ids` and `names`
+        let entry_size = 2 * (mem::size_of::<&'static str>() + mem::size_of::<usize>());
+        let names_size: usize = self.names.values().map(|name| name.len()).sum();
+
+        mem::size_of::<Self>() + self.len() * entry_size + names_size
+    }
+
     fn dump(&self) {
         for (id, name) in self.names.iter() {
             println!("atom(id = {}, value = '{}')", *id, name);
@@ -21,6 +21,8 @@ use crate::erts::string::Encoding;
 
 use super::prelude::Boxed;
 
+pub use self::process::proc_bin_memory;
+
 // This module provides a limited set of exported types/traits for convenience
 pub mod prelude {
     // Expose the iterator traits for bytes/bits
@@ -1,14 +1,16 @@
-use core::cmp;
 use core::fmt;
-use core::mem;
-use core::ptr;
 
-use num_bigint::Sign;
+use alloc::vec::Vec;
+
+use num_bigint::{BigInt, Sign};
 
 use crate::erts::term::prelude::*;
 
 use liblumen_core::sys::Endianness;
 
+use super::matcher::{f64_to_f16_bits, is_little_endian, match_raw, Bits};
+use super::primitives::{bit_offset, byte_offset, num_bytes};
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct BinaryPushFlags(usize);
@@ -113,6 +115,7 @@ const BITS_PER_REDUCTION: usize = 8 * 1024;
 
 pub struct BinaryBuilder {
     buffer: Vec<u8>,
+    /// The number of bits pushed so far
     offset: usize,
 }
 impl BinaryBuilder {
@@ -124,524 +127,332 @@ impl BinaryBuilder {
         }
     }
 
+    /// The number of bits pushed so far, which may not be a whole number of bytes.
+    #[inline]
+    pub fn bit_len(&self) -> usize {
+        self.offset
+    }
+
+    /// The pushed bits as bytes.  If [BinaryBuilder::bit_len] is not a multiple of 8, the bits of
+    /// the last byte after it are `0`.
     pub fn finish(self) -> Vec<u8> {
         self.buffer
     }
 }
 
-macro_rules! nbytes {
-    ($n:expr) => {
-        (($n + 7) >> 3)
-    };
-}
-
-macro_rules! byte_offset {
-    ($n:expr) => {
-        ($n >> 3)
-    };
-}
-
-macro_rules! bit_offset {
-    ($n:expr) => {
-        ($n & 7)
-    };
-}
-
 impl BinaryBuilder {
+    /// Pushes the lower `num_bits` of `value` in two's complement, so that values that do not fit
+    /// are truncated as in the BEAM.
     pub fn push_integer(
         &mut self,
         value: Integer,
         num_bits: usize,
         flags: BinaryPushFlags,
     ) -> Result<(), ()> {
-        if num_bits == 0 {
-            return Ok(());
-        }
-
-        let mut bin_offset = self.offset;
-        let bit_offset = bit_offset!(bin_offset);
-        let mut b;
-        let num_bytes = nbytes!(num_bits);
-
-        self.ensure_needed(num_bytes);
-
-        let mut iptr: *mut u8 = self.buffer.as_mut_ptr();
-
-        match value {
-            Integer::Small(small) if bit_offset + num_bits <= 8 => {
-                let val: isize = small.into();
-                let rbits = 8 - bit_offset;
-                // All bits are in the same byte
-                unsafe {
-                    iptr = iptr.offset(byte_offset!(bin_offset) as isize);
-                    b = *iptr as usize & (0xFF << rbits);
-                    b |= (val as usize & ((1 << num_bits) - 1)) << (8 - bit_offset - num_bits);
-                    *iptr = b as u8;
-                }
-            }
-            Integer::Small(_) if bit_offset == 0 => {
-                // More than one bit, starting at a byte boundary.
-                let offset = fmt_int(
-                    unsafe { iptr.offset(byte_offset!(bin_offset) as isize) },
-                    num_bytes,
-                    value,
-                    num_bits,
-                    flags,
-                )?;
-                bin_offset += offset;
-            }
-            Integer::Small(_) if flags.is_little_endian() => {
-                // Small unaligned, little-endian number
-                //
-                // We must format the number into a temporary buffer, and
-                // then copy that into the binary.
-                let mut tmp_buffer: Vec<u8> = Vec::with_capacity(num_bytes);
-                let tmp_ptr = tmp_buffer.as_mut_ptr();
-                let offset = fmt_int(tmp_ptr, num_bytes, value, num_bits, flags)?;
-                unsafe {
-                    copy_bits(
-                        tmp_ptr,
-                        0,
-                        CopyDirection::Forward,
-                        iptr,
-                        bin_offset,
-                        CopyDirection::Forward,
-                        num_bits,
-                    );
-                }
-                bin_offset += offset;
-            }
+        let big_int: BigInt = match value {
             Integer::Small(small) => {
-                let rbits = 8 - bit_offset;
-
-                // Big-endian, more than one byte, but not aligned on a byte boundary.
-                // Handle the bits up to the next byte boundary specially, then let
-                // fmt_int handle the rest.
-                let shift_count = num_bits - rbits;
-                let val: isize = small.into();
-                unsafe {
-                    iptr = iptr.offset(byte_offset!(bin_offset) as isize);
-                    b = *iptr as usize & (0xFF << rbits);
-                }
+                let i: isize = small.into();
 
-                // Shifting with a shift count greater than or equal to the word
-                // size may be a no-op (instead of 0 the result may be the unshifted value).
-                // Therefore, only do the shift and the OR if the shift count is less than the
-                // word size if the number is positive; if negative, we must simulate the sign
-                // extension.
-                if shift_count < mem::size_of::<usize>() * 8 {
-                    b |= (val as usize >> shift_count) & ((1 << rbits) - 1);
-                } else if val < 0 {
-                    // Simulate sign extension
-                    b |= (-1isize & (1 << (rbits as isize) - 1)) as usize;
-                }
-                unsafe {
-                    iptr = iptr.offset(1);
-                    *iptr = b as u8;
-                }
-
-                // NOTE: fmt_int is known not to fail here
-                let offset = fmt_int(
-                    iptr,
-                    nbytes!(num_bits - rbits),
-                    value,
-                    num_bits - rbits,
-                    flags,
-                )?;
-                bin_offset += offset;
-            }
-            Integer::Big(_) if bit_offset == 0 => {
-                // Big integer, aligned on a byte boundary.
-                // We can format the integer directly into the binary.
-                let offset = fmt_int(
-                    unsafe { iptr.offset(byte_offset!(bin_offset) as isize) },
-                    num_bytes,
-                    value,
-                    num_bits,
-                    flags,
-                )?;
-                bin_offset += offset;
+                i.into()
             }
-            Integer::Big(_) => {
-                // We must format the number into a temporary buffer, and
-                // then copy that into the binary.
-                let mut tmp_buffer: Vec<u8> = Vec::with_capacity(num_bytes);
-                let tmp_ptr = tmp_buffer.as_mut_ptr();
-                let offset = fmt_int(tmp_ptr, num_bytes, value, num_bits, flags)?;
-                unsafe {
-                    copy_bits(
-                        tmp_ptr,
-                        0,
-                        CopyDirection::Forward,
-                        iptr,
-                        bin_offset,
-                        CopyDirection::Forward,
-                        num_bits,
-                    );
-                }
-                bin_offset += offset;
-            }
-        }
+            Integer::Big(big) => big.into(),
+        };
 
-        self.offset = bin_offset + num_bits;
+        self.push_big_int(&big_int, num_bits, is_little_endian(flags.as_endianness()));
 
         Ok(())
     }
 
-    // TODO: erts_new_bs_put_float(Process *c_p, Eterm arg, Uint num_bits, int flags);
+    /// Pushes `value` as a 16, 32 or 64 bit float.  Fails if `value` cannot be represented in
+    /// `num_bits`.
     pub fn push_float(
         &mut self,
-        _value: f64,
-        _num_bits: usize,
-        _flags: BinaryPushFlags,
+        value: f64,
+        num_bits: usize,
+        flags: BinaryPushFlags,
     ) -> Result<(), ()> {
-        unimplemented!()
+        let float_bits: u64 = match num_bits {
+            16 => f64_to_f16_bits(value).ok_or(())? as u64,
+            32 => {
+                let f = value as f32;
+
+                if f.is_finite() {
+                    f.to_bits() as u64
+                } else {
+                    return Err(());
+                }
+            }
+            64 => value.to_bits(),
+            _ => return Err(()),
+        };
+
+        self.push_big_int(
+            &float_bits.into(),
+            num_bits,
+            is_little_endian(flags.as_endianness()),
+        );
+
+        Ok(())
     }
 
     pub fn push_utf8(&mut self, value: isize) -> Result<(), ()> {
-        let bin_offset = self.offset;
-        let bit_offset;
-        let num_bits;
-        let mut tmp_buf = [0u8; 4];
-        let mut use_tmp = false;
-        let mut dst: *mut u8;
-
-        if value < 0 {
-            return Err(());
-        }
+        let c = isize_to_char(value)?;
+        let mut buffer = [0; 4];
+        let bytes = c.encode_utf8(&mut buffer).as_bytes();
 
-        bit_offset = bit_offset!(bin_offset);
-        if bit_offset == 0 {
-            // We can write directly into the destination binary
-            dst = unsafe {
-                self.buffer
-                    .as_mut_ptr()
-                    .offset(byte_offset!(bin_offset) as isize)
-            };
-        } else {
-            // Unaligned destination binary. Must use a temporary buffer.
-            dst = tmp_buf.as_mut_ptr();
-            use_tmp = true;
-        }
+        self.push_bits(bytes, bytes.len() * 8);
 
-        if value < 0x80 {
-            unsafe {
-                *dst = value as u8;
-            }
-            num_bits = 8;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-        } else if value < 0x800 {
-            num_bits = 16;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-            unsafe {
-                *dst = 0xC0 | ((value >> 6) as u8);
-                *(dst.offset(1)) = 0x80 | ((value & 0x3F) as u8);
-            }
-        } else if value < 0x10000 {
-            if 0xD800 <= value && value <= 0xDFFF {
-                return Err(());
-            }
-            num_bits = 24;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-            unsafe {
-                *dst = 0xE0 | ((value >> 12) as u8);
-                *(dst.offset(1)) = 0x80 | ((value >> 6) as u8 & 0x3F);
-                *(dst.offset(2)) = 0x80 | ((value & 0x3F) as u8);
-            }
-        } else if value < 0x110000 {
-            num_bits = 32;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-            unsafe {
-                *dst = 0xF0 | ((value >> 18) as u8);
-                *(dst.offset(1)) = 0x80 | ((value >> 12) as u8 & 0x3F);
-                *(dst.offset(2)) = 0x80 | ((value >> 6) as u8 & 0x3F);
-                *(dst.offset(3)) = 0x80 | ((value & 0x3F) as u8);
-            }
-        } else {
-            return Err(());
-        }
+        Ok(())
+    }
 
-        if bin_offset != 0 {
-            unsafe {
-                copy_bits(
-                    dst,
-                    0,
-                    CopyDirection::Forward,
-                    self.buffer.as_mut_ptr(),
-                    bin_offset,
-                    CopyDirection::Forward,
-                    num_bits,
-                );
-            }
+    pub fn push_utf16(&mut self, value: isize, flags: BinaryPushFlags) -> Result<(), ()> {
+        let c = isize_to_char(value)?;
+        let mut buffer = [0; 2];
+        let little_endian = is_little_endian(flags.as_endianness());
+
+        for code_unit in c.encode_utf16(&mut buffer).iter() {
+            self.push_big_int(&(*code_unit).into(), 16, little_endian);
         }
 
-        self.offset += num_bits;
+        Ok(())
+    }
+
+    pub fn push_utf32(&mut self, value: isize, flags: BinaryPushFlags) -> Result<(), ()> {
+        let c = isize_to_char(value)?;
+
+        self.push_big_int(
+            &(c as u32).into(),
+            32,
+            is_little_endian(flags.as_endianness()),
+        );
 
         Ok(())
     }
 
-    pub fn push_utf16(&mut self, value: isize, flags: BinaryPushFlags) -> Result<(), ()> {
-        let bin_offset = self.offset;
-        let bit_offset;
-        let num_bits;
-        let mut tmp_buf = [0u8; 4];
-        let mut use_tmp = false;
-        let mut dst: *mut u8;
-
-        if value > 0x10FFFF || (0xD800 <= value && value <= 0xDFFF) {
-            return Err(());
+    /// Pushes all the bits of the binary `value`, which must be a multiple of `unit` bits.
+    pub fn push_byte_unit(&mut self, value: Term, unit: u8) -> Result<(), ()> {
+        self.push_bitstring(value, unit, None)
+    }
+
+    /// Pushes `size` `unit`s from the start of the bitstring `value`, or all of `value` if `size`
+    /// is `None`, in which case its bits must be a multiple of `unit`.
+    pub fn push_bitstring(&mut self, value: Term, unit: u8, size: Option<usize>) -> Result<(), ()> {
+        let bits = Bits::new(value)?;
+        let (pushed, _) = match_raw(bits, unit, size).ok_or(())?;
+
+        self.push_bits(&pushed.to_byte_vec(), pushed.bit_len());
+
+        Ok(())
+    }
+
+    pub fn push_string(&mut self, value: &[u8]) -> Result<(), ()> {
+        self.push_bits(value, value.len() * 8);
+
+        Ok(())
+    }
+
+    /// Pushes the lower `num_bits` of `big_int`.
+    ///
+    /// Big endian integers are pushed from the most significant bit.  Little endian integers are
+    /// pushed as the full bytes from the least significant byte followed by the remaining most
+    /// significant bits, which is what [super::matcher::match_integer] expects.
+    fn push_big_int(&mut self, big_int: &BigInt, num_bits: usize, little_endian: bool) {
+        if num_bits == 0 {
+            return;
         }
 
-        bit_offset = bit_offset!(bin_offset);
-        if bit_offset == 0 {
-            // We can write directly into the destination binary
-            dst = unsafe {
-                self.buffer
-                    .as_mut_ptr()
-                    .offset(byte_offset!(bin_offset) as isize)
-            };
+        let len = num_bytes(num_bits);
+        let sign_extension = if big_int.sign() == Sign::Minus {
+            0xFF
         } else {
-            // Unaligned destination binary. Must use a temporary buffer.
-            dst = tmp_buf.as_mut_ptr();
-            use_tmp = true;
-        }
+            0
+        };
+        let mut bytes = big_int.to_signed_bytes_le();
+        bytes.resize(len, sign_extension);
 
-        if value < 0x10000 {
-            num_bits = 16;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-            if flags.is_little_endian() {
-                unsafe {
-                    *dst = value as u8;
-                    *(dst.offset(1)) = (value >> 8) as u8;
-                }
-            } else {
-                unsafe {
-                    *dst = (value >> 8) as u8;
-                    *(dst.offset(1)) = value as u8;
-                }
+        let partial_bit_len = bit_offset(num_bits);
+
+        if little_endian {
+            if partial_bit_len > 0 {
+                bytes[len - 1] <<= 8 - partial_bit_len;
             }
         } else {
-            let w1;
-            let w2;
-
-            num_bits = 32;
-            self.ensure_needed(nbytes!(num_bits));
-            if !use_tmp {
-                dst = unsafe {
-                    self.buffer
-                        .as_mut_ptr()
-                        .offset(byte_offset!(bin_offset) as isize)
-                };
-            }
-            let value = value - 0x10000;
-            let dst = dst as *mut u16;
-            w1 = 0xD800 | ((value >> 10) as u16);
-            w2 = 0xDC00 | ((value & 0x3FF) as u16);
-            if flags.is_little_endian() {
-                let w1 = w1.to_le();
-                let w2 = w2.to_le();
-                unsafe {
-                    *dst = w1;
-                    *(dst.offset(1)) = w2;
-                }
-            } else {
-                unsafe {
-                    *dst = w1;
-                    *(dst.offset(1)) = w2;
+            bytes.reverse();
+
+            if partial_bit_len > 0 {
+                let shift = 8 - partial_bit_len;
+
+                for index in 0..len {
+                    let low = if index + 1 < len {
+                        bytes[index + 1] >> (8 - shift)
+                    } else {
+                        0
+                    };
+
+                    bytes[index] = (bytes[index] << shift) | low;
                 }
             }
         }
 
-        if bin_offset != 0 {
-            unsafe {
-                copy_bits(
-                    dst,
-                    0,
-                    CopyDirection::Forward,
-                    self.buffer.as_mut_ptr(),
-                    bin_offset,
-                    CopyDirection::Forward,
-                    num_bits,
-                );
+        self.push_bits(&bytes, num_bits);
+    }
+
+    /// Pushes the first `num_bits` of `bytes`, where the first bit is the most significant bit of
+    /// the first byte.
+    fn push_bits(&mut self, bytes: &[u8], num_bits: usize) {
+        if num_bits == 0 {
+            return;
+        }
+
+        let shift = bit_offset(self.offset);
+        let mut index = byte_offset(self.offset);
+        self.buffer.resize(num_bytes(self.offset + num_bits), 0);
+
+        for (byte_index, byte) in bytes[..num_bytes(num_bits)].iter().enumerate() {
+            let end_bit_len = (byte_index + 1) * 8;
+            // Bits after `num_bits` must not be pushed, so that later pushes can OR into them
+            let byte = if num_bits < end_bit_len {
+                byte & (0xFF << (end_bit_len - num_bits))
+            } else {
+                *byte
+            };
+
+            self.buffer[index] |= byte >> shift;
+
+            if 0 < shift && index + 1 < self.buffer.len() {
+                self.buffer[index + 1] |= byte << (8 - shift);
             }
+
+            index += 1;
         }
 
         self.offset += num_bits;
+    }
+}
 
-        Ok(())
+fn isize_to_char(value: isize) -> Result<char, ()> {
+    if 0 <= value && value <= (core::u32::MAX as isize) {
+        core::char::from_u32(value as u32).ok_or(())
+    } else {
+        Err(())
     }
+}
 
-    pub fn push_byte_unit(&mut self, value: Term, unit: u8) -> Result<(), ()> {
-        match value.decode().unwrap() {
-            TypedTerm::SmallInteger(small_integer) => {
-                let bytes = small_integer.to_le_bytes();
-                let unit_usize = unit as usize;
-                assert!(1 <= unit && unit_usize <= bytes.len());
-                self.push_string(&bytes[0..unit_usize])
-            }
-            _ => unimplemented!("pushing value ({}) as byte with unit ({})", value, unit),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::erts::term::binary::matcher::match_integer;
+
+    fn flags(endianness: Endianness) -> BinaryPushFlags {
+        BinaryPushFlags::new(false, endianness)
     }
 
-    pub fn push_string(&mut self, value: &[u8]) -> Result<(), ()> {
-        self.ensure_needed(value.len());
-        let offset = unsafe { write_bytes(self.buffer.as_mut_ptr(), self.offset, value) };
-        self.offset += offset;
+    #[test]
+    fn push_integer_with_unaligned_offset_packs_bits() {
+        let mut builder = BinaryBuilder::new();
 
-        Ok(())
+        builder
+            .push_integer(Integer::from(0b101isize), 3, Default::default())
+            .unwrap();
+        builder
+            .push_integer(Integer::from(0xFFisize), 8, Default::default())
+            .unwrap();
+
+        assert_eq!(builder.bit_len(), 11);
+        assert_eq!(builder.finish(), vec![0b1011_1111, 0b1110_0000]);
     }
 
-    #[inline]
-    fn ensure_needed(&mut self, need: usize) {
-        self.buffer.resize(need + self.buffer.len(), 0);
+    #[test]
+    fn push_integer_with_negative_truncates_twos_complement() {
+        let mut builder = BinaryBuilder::new();
+
+        builder
+            .push_integer(Integer::from(-1isize), 12, Default::default())
+            .unwrap();
+
+        assert_eq!(builder.finish(), vec![0xFF, 0xF0]);
     }
-}
 
-fn fmt_int(
-    buf: *mut u8,
-    size_bytes: usize,
-    value: Integer,
-    size_bits: usize,
-    flags: BinaryPushFlags,
-) -> Result<usize, ()> {
-    let is_signed = flags.is_signed();
-    let is_little = flags.is_little_endian();
-    match value {
-        Integer::Small(small) => {
-            assert_ne!(size_bits, 0);
-
-            let v: isize = small.into();
-            let bytes = if is_little {
-                v.to_le_bytes()
-            } else {
-                v.to_be_bytes()
-            };
-            let sign = if is_signed && v < 0 { 1 } else { 0 };
-            unsafe { fmt_int_bytes(&bytes, sign, buf, size_bytes, size_bits, flags) }
-        }
-        Integer::Big(big) => {
-            if size_bits == 0 {
-                return Ok(0);
-            }
+    #[test]
+    fn push_integer_with_big_integer_little_endian() {
+        let mut builder = BinaryBuilder::new();
+        let big_int = BigInt::from(1) << 64;
 
-            let bytes = if is_little {
-                big.to_signed_bytes_le()
-            } else {
-                big.to_signed_bytes_be()
-            };
-            let sign = if is_signed && big.sign() == Sign::Minus {
-                1
-            } else {
-                0
-            };
-            unsafe { fmt_int_bytes(bytes.as_slice(), sign, buf, size_bytes, size_bits, flags) }
-        }
+        builder
+            .push_integer(big_int.into(), 72, flags(Endianness::Little))
+            .unwrap();
+
+        assert_eq!(builder.finish(), vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
     }
-}
 
-unsafe fn fmt_int_bytes(
-    bytes: &[u8],
-    sign: u8,
-    buf: *mut u8,
-    size_bytes: usize,
-    size_bits: usize,
-    flags: BinaryPushFlags,
-) -> Result<usize, ()> {
-    let mut bit_offset = bit_offset!(size_bits);
-    let num_bytes = cmp::min(size_bytes, bytes.len());
-    if num_bytes < size_bytes {
-        let diff = size_bytes - num_bytes;
-        // A larger value was requested than what was provided,
-        // so sign extend the value accordingly
-        if flags.is_little_endian() {
-            // Pad right
-            let offset = write_bytes(buf, bit_offset, &bytes[0..num_bytes]);
-            Ok(pad_bytes(buf, offset, diff, sign))
-        } else {
-            // Pad left
-            bit_offset += pad_bytes(buf, bit_offset, diff, sign);
-            Ok(write_bytes(buf, bit_offset, &bytes[0..num_bytes]))
+    #[test]
+    fn push_integer_roundtrips_through_match_integer() {
+        for endianness in &[Endianness::Big, Endianness::Little, Endianness::Native] {
+            let mut builder = BinaryBuilder::new();
+
+            builder
+                .push_integer(
+                    Integer::from(-300isize),
+                    13,
+                    BinaryPushFlags::new(true, *endianness),
+                )
+                .unwrap();
+
+            let bit_len = builder.bit_len();
+            let bytes = builder.finish();
+            let bits = unsafe { Bits::from_bytes(Term::NONE, &bytes) };
+            let (integer, _) = match_integer(bits, bit_len, true, *endianness).unwrap();
+
+            assert_eq!(integer, BigInt::from(-300));
         }
-    } else {
-        Ok(write_bytes(buf, bit_offset, &bytes[0..num_bytes]))
     }
-}
 
-unsafe fn pad_bytes(dst: *mut u8, offset: usize, padding: usize, sign: u8) -> usize {
-    use super::primitives::{make_bitmask, mask_bits};
+    #[test]
+    fn push_float_with_32_bits_too_large_fails() {
+        let mut builder = BinaryBuilder::new();
 
-    if offset % 8 == 0 {
-        // Byte-aligned
-        dst.offset(byte_offset!(offset) as isize)
-            .write_bytes(sign, padding);
-    } else {
-        // Not byte-aligned
-        let lmask = make_bitmask(8 - offset as u8);
-        // Handle bits in first (unaligned) byte
-        let base = dst.offset(byte_offset!(offset) as isize);
-        base.write(mask_bits(sign, *base, lmask));
-        // All that's left to copy are the remaining bytes, if any
-        let padding = padding - 1;
-        if padding > 0 {
-            base.offset(1).write_bytes(sign, padding);
-        }
+        assert!(builder
+            .push_float(core::f64::MAX, 32, Default::default())
+            .is_err());
     }
 
-    offset + (padding * 8)
-}
+    #[test]
+    fn push_float_with_16_bits() {
+        let mut builder = BinaryBuilder::new();
 
-unsafe fn write_bytes(dst: *mut u8, offset: usize, value: &[u8]) -> usize {
-    let ptr = value.as_ptr();
-    let num_bytes = value.len();
-    if bit_offset!(offset) != 0 {
-        copy_bits(
-            ptr,
-            0,
-            CopyDirection::Forward,
-            dst,
-            offset,
-            CopyDirection::Forward,
-            num_bytes * 8,
-        );
-    } else {
-        let byte_offs = byte_offset!(offset) as isize;
-        ptr::copy_nonoverlapping(ptr, dst.offset(byte_offs), num_bytes);
+        builder.push_float(1.5, 16, Default::default()).unwrap();
+
+        assert_eq!(builder.finish(), vec![0x3E, 0x00]);
     }
 
-    num_bytes * 8
+    #[test]
+    fn push_utf8_with_surrogate_fails() {
+        assert!(BinaryBuilder::new().push_utf8(0xD800).is_err());
+    }
+
+    #[test]
+    fn push_utf16_little_endian_with_surrogate_pair() {
+        let mut builder = BinaryBuilder::new();
+
+        builder
+            .push_utf16(0x1F608, flags(Endianness::Little))
+            .unwrap();
+
+        assert_eq!(builder.finish(), vec![0x3D, 0xD8, 0x08, 0xDE]);
+    }
+
+    #[test]
+    fn push_string_after_partial_byte_shifts_bytes() {
+        let mut builder = BinaryBuilder::new();
+
+        builder
+            .push_integer(Integer::from(1isize), 1, Default::default())
+            .unwrap();
+        builder.push_string(&[0xFF]).unwrap();
+
+        assert_eq!(builder.finish(), vec![0xFF, 0x80]);
+    }
 }
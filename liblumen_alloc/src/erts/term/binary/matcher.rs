@@ -1,5 +1,22 @@
+//! Matching of bit syntax segments against the bits left in a binary, sub-binary or match context.
+//!
+//! The functions here only read bits and return the matched value with the [Bits] after it; the
+//! runtime allocates the terms for them on the process heap.
+use core::cmp;
+use core::slice;
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigInt, Sign};
+
+use num_traits::ToPrimitive;
+
+use liblumen_core::sys::Endianness;
+
 use crate::erts::term::prelude::*;
 
+use super::primitives::{bit_offset, byte_offset, num_bytes};
+
 #[repr(C)]
 pub struct BinaryMatchResult {
     // The value matched by the match operation
@@ -27,18 +44,533 @@ impl BinaryMatchResult {
     }
 }
 
-pub fn match_raw<B>(bin: B, _unit: u8, size: Option<usize>) -> Result<BinaryMatchResult, ()>
-where
-    B: Bitstring + MaybePartialByte,
-{
-    let size = size.unwrap_or(0);
-    let total_bits = bin.total_bit_len();
+/// The bits left to match in a binary.
+///
+/// `original` is always the `ProcBin`, `BinaryLiteral` or `HeapBin` that owns the bytes, so that
+/// the bits can be turned back into a `SubBinary` with [Bits::to_subbinary_parts].
+#[derive(Clone, Copy, Debug)]
+pub struct Bits {
+    original: Term,
+    base: *const u8,
+    /// Offset in bits from `base` of the first bit
+    bit_offset: usize,
+    /// Number of bits after `bit_offset`
+    bit_len: usize,
+}
+impl Bits {
+    /// The bits of a binary, sub-binary or match context `term`
+    pub fn new(term: Term) -> Result<Self, ()> {
+        match term.decode().map_err(|_| ())? {
+            TypedTerm::HeapBinary(bin) => Ok(Self::from_aligned(term, bin.as_ref())),
+            TypedTerm::ProcBin(bin) => Ok(Self::from_aligned(term, bin.as_ref())),
+            TypedTerm::BinaryLiteral(bin) => Ok(Self::from_aligned(term, bin.as_ref())),
+            TypedTerm::SubBinary(subbinary) => {
+                let subbinary = subbinary.as_ref();
+
+                Ok(Self {
+                    original: subbinary.original(),
+                    base: unsafe { subbinary.as_byte_ptr() },
+                    bit_offset: subbinary.byte_offset() * 8 + (subbinary.bit_offset() as usize),
+                    bit_len: subbinary.total_bit_len(),
+                })
+            }
+            TypedTerm::MatchContext(match_context) => {
+                let match_context = match_context.as_ref();
+                let buffer = &match_context.buffer;
+                // Match contexts started on a sub-binary keep the sub-binary as the original
+                let original = match buffer.original.decode().map_err(|_| ())? {
+                    TypedTerm::SubBinary(subbinary) => subbinary.as_ref().original(),
+                    _ => buffer.original,
+                };
+
+                Ok(Self {
+                    original,
+                    base: unsafe { match_context.as_byte_ptr() },
+                    bit_offset: buffer.bit_offset,
+                    bit_len: buffer.bit_len - buffer.bit_offset,
+                })
+            }
+            _ => Err(()),
+        }
+    }
+
+    /// The bits of `bytes`, such as when there is no term for them.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must outlive the returned `Bits` and anything matched from them.
+    pub unsafe fn from_bytes(original: Term, bytes: &[u8]) -> Self {
+        Self {
+            original,
+            base: bytes.as_ptr(),
+            bit_offset: 0,
+            bit_len: bytes.len() * 8,
+        }
+    }
+
+    fn from_aligned<B: Bitstring>(original: Term, bin: &B) -> Self {
+        Self {
+            original,
+            base: unsafe { bin.as_byte_ptr() },
+            bit_offset: 0,
+            bit_len: bin.full_byte_len() * 8,
+        }
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bit_len == 0
+    }
+
+    /// The first `bit_len` bits and the bits after them, or `None` if there are not enough bits.
+    pub fn split_at(&self, bit_len: usize) -> Option<(Bits, Bits)> {
+        if bit_len <= self.bit_len {
+            let front = Self { bit_len, ..*self };
+            let back = Self {
+                bit_offset: self.bit_offset + bit_len,
+                bit_len: self.bit_len - bit_len,
+                ..*self
+            };
+
+            Some((front, back))
+        } else {
+            None
+        }
+    }
+
+    /// The arguments to `subbinary_from_original` for a `SubBinary` of these bits:
+    /// `(original, byte_offset, bit_offset, full_byte_len, partial_byte_bit_len)`.
+    pub fn to_subbinary_parts(&self) -> (Term, usize, u8, usize, u8) {
+        (
+            self.original,
+            byte_offset(self.bit_offset),
+            bit_offset(self.bit_offset) as u8,
+            byte_offset(self.bit_len),
+            bit_offset(self.bit_len) as u8,
+        )
+    }
+
+    /// The bits packed into bytes with the first bit as the most significant bit of the first
+    /// byte.  Any bits in the last byte after `bit_len` are `0`.
+    pub fn to_byte_vec(&self) -> Vec<u8> {
+        let len = num_bytes(self.bit_len);
+
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let first_byte_index = byte_offset(self.bit_offset);
+        let shift = bit_offset(self.bit_offset);
+        // Only the bytes that hold bits can be read, as the last one may be the end of the binary
+        let end_byte_index = num_bytes(self.bit_offset + self.bit_len);
+        let source = unsafe {
+            slice::from_raw_parts(
+                self.base.add(first_byte_index),
+                end_byte_index - first_byte_index,
+            )
+        };
+
+        let mut byte_vec = Vec::with_capacity(len);
+
+        for index in 0..len {
+            let high = source[index] << shift;
+            let low = if shift > 0 && index + 1 < source.len() {
+                source[index + 1] >> (8 - shift)
+            } else {
+                0
+            };
+
+            byte_vec.push(high | low);
+        }
+
+        let trailing_bit_len = len * 8 - self.bit_len;
+
+        if trailing_bit_len > 0 {
+            byte_vec[len - 1] &= 0xFF << trailing_bit_len;
+        }
+
+        byte_vec
+    }
+}
+
+/// Matches a `binary` or `bitstring` segment of `size` `unit`s, or all the remaining bits if
+/// `size` is `None`, in which case the remaining bits must be a multiple of `unit`.
+pub fn match_raw(bits: Bits, unit: u8, size: Option<usize>) -> Option<(Bits, Bits)> {
+    let unit = cmp::max(unit, 1) as usize;
+
+    match size {
+        Some(size) => bits.split_at(size.checked_mul(unit)?),
+        None if bits.bit_len() % unit == 0 => bits.split_at(bits.bit_len()),
+        None => None,
+    }
+}
+
+/// Matches an `integer` segment of `bit_len` bits.
+pub fn match_integer(
+    bits: Bits,
+    bit_len: usize,
+    signed: bool,
+    endianness: Endianness,
+) -> Option<(BigInt, Bits)> {
+    let (integer_bits, rest) = bits.split_at(bit_len)?;
+
+    Some((bits_to_integer(&integer_bits, signed, endianness), rest))
+}
+
+/// Matches a `float` segment of `bit_len` `16`, `32` or `64` bits.  As in the BEAM, the match
+/// fails if the bits are not a finite float.
+pub fn match_float(bits: Bits, bit_len: usize, endianness: Endianness) -> Option<(f64, Bits)> {
+    let (float_bits, rest) = bits.split_at(bit_len)?;
+    let integer = bits_to_u64(&float_bits, endianness);
+
+    let f = match bit_len {
+        16 => f16_bits_to_f64(integer as u16)?,
+        32 => f32::from_bits(integer as u32) as f64,
+        64 => f64::from_bits(integer),
+        _ => return None,
+    };
+
+    if f.is_finite() {
+        Some((f, rest))
+    } else {
+        None
+    }
+}
+
+/// Matches a `utf8` segment of 1 to 4 bytes.
+pub fn match_utf8(bits: Bits) -> Option<(char, Bits)> {
+    let (first_bits, _) = bits.split_at(8)?;
+    let first_byte = first_bits.to_byte_vec()[0];
+
+    let len = match first_byte {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+
+    let (char_bits, rest) = bits.split_at(len * 8)?;
+    let char_byte_vec = char_bits.to_byte_vec();
+    let c = core::str::from_utf8(&char_byte_vec).ok()?.chars().next()?;
+
+    Some((c, rest))
+}
+
+/// Matches a `utf16` segment of 1 or 2 16-bit code units.
+pub fn match_utf16(bits: Bits, endianness: Endianness) -> Option<(char, Bits)> {
+    let (first, after_first) = match_integer(bits, 16, false, endianness)?;
+    let first = u16_from_big_int(&first);
+
+    match first {
+        0xD800..=0xDBFF => {
+            let (second, rest) = match_integer(after_first, 16, false, endianness)?;
+            let second = u16_from_big_int(&second);
+
+            match second {
+                0xDC00..=0xDFFF => {
+                    let code_point =
+                        0x10000 + ((((first - 0xD800) as u32) << 10) | ((second - 0xDC00) as u32));
+
+                    core::char::from_u32(code_point).map(|c| (c, rest))
+                }
+                _ => None,
+            }
+        }
+        0xDC00..=0xDFFF => None,
+        _ => core::char::from_u32(first as u32).map(|c| (c, after_first)),
+    }
+}
+
+/// Matches a `utf32` segment of 32 bits.
+pub fn match_utf32(bits: Bits, endianness: Endianness) -> Option<(char, Bits)> {
+    let (code_point_bits, rest) = bits.split_at(32)?;
+    let code_point = bits_to_u64(&code_point_bits, endianness) as u32;
+
+    core::char::from_u32(code_point).map(|c| (c, rest))
+}
+
+/// Whether the bytes of segments with `endianness` are little endian.
+pub fn is_little_endian(endianness: Endianness) -> bool {
+    match endianness {
+        Endianness::Big => false,
+        Endianness::Little => true,
+        Endianness::Native => cfg!(target_endian = "little"),
+    }
+}
+
+/// The half-precision float of `bits`, or `None` if it is not finite.
+pub fn f16_bits_to_f64(bits: u16) -> Option<f64> {
+    let negative = (bits & 0x8000) != 0;
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f64;
+
+    let magnitude = match exponent {
+        // Subnormal
+        0 => mantissa * 2f64.powi(-24),
+        // Infinity or NaN
+        0x1F => return None,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    };
+
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// The bits of `f` as a half-precision float rounded to the nearest, or `None` if it is not
+/// finite or is too large.
+pub fn f64_to_f16_bits(f: f64) -> Option<u16> {
+    if !f.is_finite() {
+        return None;
+    }
+
+    let sign: u16 = if f.is_sign_negative() { 0x8000 } else { 0 };
+    let magnitude = f.abs();
+
+    if magnitude < 2f64.powi(-14) {
+        // Subnormal, which may round up to the smallest normal as `0x0400`
+        let scaled = magnitude * 2f64.powi(24);
+
+        return Some(sign | (round_half_to_even(scaled) as u16));
+    }
+
+    let f64_bits = magnitude.to_bits();
+    let exponent = ((f64_bits >> 52) as i32) - 1023;
+    let mantissa = f64_bits & ((1 << 52) - 1);
+
+    // Keep the top 10 of the 52 mantissa bits, rounding the 42 dropped bits to nearest even
+    let dropped = mantissa & ((1 << 42) - 1);
+    let half = 1 << 41;
+    let mut half_mantissa = (mantissa >> 42) as u32;
+
+    if dropped > half || (dropped == half && (half_mantissa & 1) == 1) {
+        half_mantissa += 1;
+    }
+
+    // A carry out of the mantissa increments the exponent, as it would in the bits
+    let half_bits = (((exponent + 15) as u32) << 10) + half_mantissa;
+
+    if half_bits < 0x7C00 {
+        Some(sign | (half_bits as u16))
+    } else {
+        None
+    }
+}
+
+// Private
+
+/// The integer of `bits` as formatted by [super::builder::BinaryBuilder::push_integer]: big
+/// endian integers are the bits from the most significant, while little endian integers are the
+/// full bytes from the least significant, followed by any partial byte's bits as the most
+/// significant bits.
+fn bits_to_integer(bits: &Bits, signed: bool, endianness: Endianness) -> BigInt {
+    let bit_len = bits.bit_len();
+
+    if bit_len == 0 {
+        return BigInt::from(0);
+    }
+
+    let mut byte_vec = bits.to_byte_vec();
+    let len = byte_vec.len();
+    let partial_bit_len = bit_len - (len - 1) * 8;
+
+    let unsigned = if is_little_endian(endianness) {
+        byte_vec[len - 1] >>= 8 - partial_bit_len;
+
+        BigInt::from_bytes_le(Sign::Plus, &byte_vec)
+    } else {
+        BigInt::from_bytes_be(Sign::Plus, &byte_vec) >> (len * 8 - bit_len)
+    };
+
+    if signed && (unsigned.clone() >> (bit_len - 1)) == BigInt::from(1) {
+        unsigned - (BigInt::from(1) << bit_len)
+    } else {
+        unsigned
+    }
+}
+
+fn bits_to_u64(bits: &Bits, endianness: Endianness) -> u64 {
+    // Only called with at most 64 bits, which always fit
+    bits_to_integer(bits, false, endianness).to_u64().unwrap()
+}
+
+fn round_half_to_even(f: f64) -> f64 {
+    let floor = f.floor();
+    let difference = f - floor;
+
+    if difference > 0.5 || (difference == 0.5 && (floor % 2.0) != 0.0) {
+        floor + 1.0
+    } else {
+        floor
+    }
+}
+
+fn u16_from_big_int(big_int: &BigInt) -> u16 {
+    // Only called with 16 bit code units, which always fit
+    big_int.to_u16().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    fn bits(bytes: &[u8]) -> Bits {
+        unsafe { Bits::from_bytes(Term::NONE, bytes) }
+    }
+
+    mod match_integer {
+        use super::*;
+
+        #[test]
+        fn with_unaligned_big_endian_reads_most_significant_bits_first() {
+            let bytes = [0b1010_1100, 0b0011_0000];
+            let (_, after_first) = bits(&bytes).split_at(4).unwrap();
+
+            let (integer, rest) = match_integer(after_first, 8, false, Endianness::Big).unwrap();
+
+            assert_eq!(integer, BigInt::from(0b1100_0011));
+            assert_eq!(rest.bit_len(), 4);
+        }
+
+        #[test]
+        fn with_little_endian_with_partial_byte_reads_partial_byte_as_most_significant() {
+            // <<1:12/little>> is <<1, 0:4>>
+            let bytes = [1, 0];
+            let (twelve_bits, _) = bits(&bytes).split_at(12).unwrap();
+
+            let (integer, rest) =
+                match_integer(twelve_bits, 12, false, Endianness::Little).unwrap();
+
+            assert_eq!(integer, BigInt::from(1));
+            assert!(rest.is_empty());
+        }
+
+        #[test]
+        fn with_signed_with_sign_bit_returns_negative() {
+            let bytes = [0xFF, 0xFE];
+
+            let (integer, _) = match_integer(bits(&bytes), 16, true, Endianness::Big).unwrap();
+
+            assert_eq!(integer, BigInt::from(-2));
+        }
+
+        #[test]
+        fn with_more_than_64_bits_returns_big_integer() {
+            let bytes = [0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+
+            let (integer, _) = match_integer(bits(&bytes), 72, false, Endianness::Big).unwrap();
+
+            assert_eq!(integer, BigInt::from(1) << 64);
+        }
+
+        #[test]
+        fn without_enough_bits_fails() {
+            assert!(match_integer(bits(&[0]), 9, false, Endianness::Big).is_none());
+        }
+    }
+
+    mod match_float {
+        use super::*;
+
+        #[test]
+        fn with_16_bits_returns_half_precision_float() {
+            // 1.5 as a half-precision float
+            let bytes = [0x3E, 0x00];
+
+            let (f, _) = match_float(bits(&bytes), 16, Endianness::Big).unwrap();
+
+            assert_eq!(f, 1.5);
+        }
+
+        #[test]
+        fn with_32_bits_little_endian_returns_float() {
+            let bytes = 2.5_f32.to_le_bytes();
+
+            let (f, _) = match_float(bits(&bytes), 32, Endianness::Little).unwrap();
 
-    if size == 0 && total_bits == 0 {
-        // TODO: t = bin_to_term
-        let t = Term::NONE;
-        return Ok(BinaryMatchResult::success(Term::NONE, t));
+            assert_eq!(f, 2.5);
+        }
+
+        #[test]
+        fn with_infinity_fails() {
+            let bytes = core::f64::INFINITY.to_be_bytes();
+
+            assert!(match_float(bits(&bytes), 64, Endianness::Big).is_none());
+        }
     }
 
-    unimplemented!()
+    mod match_utf {
+        use super::*;
+
+        #[test]
+        fn utf8_with_multibyte_returns_char() {
+            let bytes = vec![0xF0, 0x9F, 0x98, 0x88, 0x61];
+
+            let (c, rest) = match_utf8(bits(&bytes)).unwrap();
+
+            assert_eq!(c, '😈');
+            assert_eq!(rest.bit_len(), 8);
+        }
+
+        #[test]
+        fn utf8_with_invalid_continuation_fails() {
+            assert!(match_utf8(bits(&[0xE0, 0x41, 0x41])).is_none());
+        }
+
+        #[test]
+        fn utf16_with_surrogate_pair_returns_char() {
+            let bytes = [0xD8, 0x3D, 0xDE, 0x08];
+
+            let (c, rest) = match_utf16(bits(&bytes), Endianness::Big).unwrap();
+
+            assert_eq!(c, '😈');
+            assert!(rest.is_empty());
+        }
+
+        #[test]
+        fn utf32_with_surrogate_fails() {
+            assert!(match_utf32(bits(&[0, 0, 0xD8, 0]), Endianness::Big).is_none());
+        }
+    }
+
+    mod match_raw {
+        use super::*;
+
+        #[test]
+        fn without_size_with_remainder_not_multiple_of_unit_fails() {
+            let (twelve_bits, _) = bits(&[0, 0]).split_at(12).unwrap();
+
+            assert!(match_raw(twelve_bits, 8, None).is_none());
+        }
+
+        #[test]
+        fn with_size_returns_size_units() {
+            let (matched, rest) = match_raw(bits(&[0, 0, 0]), 8, Some(2)).unwrap();
+
+            assert_eq!(matched.bit_len(), 16);
+            assert_eq!(rest.bit_len(), 8);
+        }
+    }
+
+    mod f16 {
+        use super::*;
+
+        #[test]
+        fn roundtrips() {
+            for f in &[0.0, -2.0, 0.5, 65504.0, 2f64.powi(-24), 2f64.powi(-14)] {
+                let bits = f64_to_f16_bits(*f).unwrap();
+
+                assert_eq!(f16_bits_to_f64(bits), Some(*f));
+            }
+        }
+
+        #[test]
+        fn with_too_large_fails() {
+            assert_eq!(f64_to_f16_bits(65520.0), None);
+        }
+    }
 }
@@ -17,6 +17,14 @@ use crate::erts::process::Process;
 use crate::erts::string::Encoding;
 use crate::erts::term::prelude::*;
 
+/// The number of bytes allocated for the data of all `ProcBin`s, as for `erlang:memory(binary)`
+static PROC_BIN_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of bytes currently allocated for reference-counted binaries
+pub fn proc_bin_memory() -> usize {
+    PROC_BIN_BYTES.load(atomic::Ordering::Relaxed)
+}
+
 /// This is the header written alongside all procbin binaries in the heap,
 /// it owns the refcount and the raw binary data
 ///
@@ -138,6 +146,7 @@ impl ProcBin {
 
         unsafe {
             let block = sys_alloc::alloc(layout)?;
+            PROC_BIN_BYTES.fetch_add(layout.size(), atomic::Ordering::Relaxed);
             let len = s.len();
 
             let ptr: *mut u8 = block.ptr.as_ptr();
@@ -170,7 +179,8 @@ impl ProcBin {
         if self.inner().refc.fetch_sub(1, atomic::Ordering::Release) == 1 {
             atomic::fence(atomic::Ordering::Acquire);
             let inner = self.inner.as_ref();
-            let layout = Layout::for_value(&inner);
+            let layout = Layout::for_value(inner);
+            PROC_BIN_BYTES.fetch_sub(layout.size(), atomic::Ordering::Relaxed);
             sys_alloc::free(inner as *const _ as *mut u8, layout);
         }
     }
@@ -179,6 +189,17 @@ impl ProcBin {
     pub fn full_byte_iter<'a>(&'a self) -> iter::Copied<slice::Iter<'a, u8>> {
         self.inner().as_bytes().iter().copied()
     }
+
+    /// The address of the shared data, which identifies the binary in
+    /// `process_info(Pid, binary)`
+    pub fn id(&self) -> usize {
+        self.inner.as_ptr() as *const u8 as usize
+    }
+
+    /// The number of `ProcBin`s that share the data
+    pub fn reference_count(&self) -> usize {
+        self.inner().refc.load(atomic::Ordering::Relaxed)
+    }
 }
 impl Bitstring for ProcBin {
     #[inline]
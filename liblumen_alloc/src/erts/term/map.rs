@@ -1,52 +1,59 @@
+//! Maps live on the process heap, so that they are copied by the garbage collector like any other
+//! term.
+//!
+//! As in the BEAM, maps with up to [FLATMAP_MAX_LEN] entries are flatmaps: a `Tuple` of the keys
+//! in map key order and a `Tuple` of the values in the same order, so that updating the value of a
+//! key shares the keys `Tuple`.  Larger maps are hash maps: a hash array mapped trie of `Tuple`
+//! nodes, so that `put`, `update` and `remove` only copy the nodes on the path to the key.
 use core::alloc::Layout;
-use core::cmp;
+use core::cmp::Ordering;
 use core::convert::{TryFrom, TryInto};
 use core::fmt::{self, Debug, Display, Write};
 use core::hash::{Hash, Hasher};
 use core::mem;
-use core::ptr;
 
-use alloc::vec::Vec;
+use alloc::vec::{self, Vec};
 
 use anyhow::*;
-use hashbrown::HashMap;
 
+use crate::borrow::CloneToProcess;
 use crate::erts::exception::{AllocResult, InternalResult};
-use crate::erts::process::alloc::TermAlloc;
+use crate::erts::process::alloc::{HeapAlloc, TermAlloc};
 
 use super::prelude::*;
 
-#[derive(Clone)]
+/// Maps with at most this many entries are flatmaps.  Larger maps are hash maps.
+pub const FLATMAP_MAX_LEN: usize = 32;
+
+#[derive(Clone, Copy)]
 #[repr(C)]
 pub struct Map {
     header: Header<Map>,
-    value: HashMap<Term, Term>,
+    /// The number of entries as a `SmallInteger`, so that sweeping the heap skips it
+    len: Term,
+    /// For a flatmap, a `Tuple` of the keys in map key order.  For a hash map, the root node.
+    keys_or_root: Term,
+    /// For a flatmap, a `Tuple` of the values in the order of the keys.  For a hash map, `[]`.
+    values: Term,
 }
+impl_static_header!(Map, Term::HEADER_MAP);
 
 impl Map {
-    pub(in crate::erts) fn from_hash_map(value: HashMap<Term, Term>) -> Self {
-        Self {
-            header: Header::from_map(&value),
-            value,
-        }
-    }
-
-    pub(in crate::erts) fn from_slice(slice: &[(Term, Term)]) -> Self {
-        let mut value: HashMap<Term, Term> = HashMap::with_capacity(slice.len());
-
-        for (entry_key, entry_value) in slice {
-            value.insert(*entry_key, *entry_value);
-        }
-
-        Self::from_hash_map(value)
+    /// Allocates a map of the `entries` on `heap`.  If a key is in more than one entry, the last
+    /// entry wins.
+    pub fn from_slice<A>(heap: &mut A, entries: &[(Term, Term)]) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        Self::from_unique_sorted_entries(heap, unique_sorted_entries(entries))
     }
 
-    pub fn from_list(list: Term) -> InternalResult<HashMap<Term, Term>> {
+    pub fn from_list(list: Term) -> InternalResult<Vec<(Term, Term)>> {
         match list.decode()? {
-            TypedTerm::Nil => Ok(HashMap::new()),
+            TypedTerm::Nil => Ok(Vec::new()),
             TypedTerm::List(cons_ptr) => {
                 let cons = cons_ptr.as_ref();
-                let mut map = HashMap::new();
+                let mut entries = Vec::new();
 
                 for result_element in cons.into_iter() {
                     match result_element {
@@ -56,7 +63,7 @@ impl Map {
                             })?;
 
                             if tuple.len() == 2 {
-                                map.insert(tuple[0], tuple[1]);
+                                entries.push((tuple[0], tuple[1]));
                             } else {
                                 return Err(anyhow!(
                                     "element ({}) of list ({}) is not a 2-arity tuple",
@@ -74,7 +81,7 @@ impl Map {
                     }
                 }
 
-                Ok(map)
+                Ok(entries)
             }
             _ => Err(TypeError)
                 .context(format!("list ({}) is not a list", list))
@@ -82,133 +89,345 @@ impl Map {
         }
     }
 
-    pub fn get(&self, key: Term) -> Option<Term> {
-        self.value.get(&key).copied()
-    }
+    /// The most words that [Map::from_slice] can allocate for `entries` on a heap that holds none
+    /// of their terms
+    pub fn need_in_words_from_slice(entries: &[(Term, Term)]) -> usize {
+        let entries_need_in_words: usize = entries
+            .iter()
+            .map(|(key, value)| key.size_in_words() + value.size_in_words())
+            .sum();
+        let structure_need_in_words = if entries.len() <= FLATMAP_MAX_LEN {
+            // The keys and values tuples
+            2 * (1 + entries.len())
+        } else {
+            entries.len() * HASH_MAP_WORDS_PER_ENTRY + NODE_MAX_WORDS
+        };
 
-    pub fn take(&self, key: Term) -> Option<(Term, HashMap<Term, Term>)> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            let value = map.remove(&key).unwrap();
+        MAP_WORDS + entries_need_in_words + structure_need_in_words
+    }
 
-            Some((value, map))
+    /// The most words that [Map::put], [Map::update], [Map::remove] or [Map::take] can allocate
+    /// for `key` and `value` on a heap that holds none of this map
+    pub fn need_in_words_to_modify(&self, key: Term, value: Term) -> usize {
+        let len = self.len();
+        let structure_need_in_words = if len < FLATMAP_MAX_LEN {
+            // The keys and values tuples with, at most, one more entry
+            2 * (1 + len + 1)
+        } else if len == FLATMAP_MAX_LEN {
+            // Putting a new key makes a hash map of all the entries
+            (len + 1) * HASH_MAP_WORDS_PER_ENTRY + NODE_MAX_WORDS
         } else {
-            None
+            // The nodes on the path to the key and a collision node with, at most, one more
+            // entry, which is also more than the keys and values tuples when removing the key
+            // makes a flatmap
+            (COLLISION_DEPTH as usize) * NODE_MAX_WORDS + 1 + 2 * (len + 1)
+        };
+
+        MAP_WORDS + key.size_in_words() + value.size_in_words() + structure_need_in_words
+    }
+
+    pub fn get(&self, key: Term) -> Option<Term> {
+        match self.kind() {
+            Kind::Flat { keys, values } => flat_index(&keys, key).ok().map(|index| values[index]),
+            Kind::Hash { root } => node_get(root, hash_key(key), 0, key),
         }
     }
 
     pub fn is_key(&self, key: Term) -> bool {
-        self.value.contains_key(&key)
+        self.get(key).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
+    /// The keys in map key order for flatmaps and in key hash order for hash maps, as in the BEAM
     pub fn keys(&self) -> Vec<Term> {
-        self.value.keys().into_iter().copied().collect()
+        self.iter().map(|(key, _)| key).collect()
     }
 
+    /// The values in the order of [Map::keys]
     pub fn values(&self) -> Vec<Term> {
-        self.value.values().into_iter().copied().collect()
+        self.iter().map(|(_, value)| value).collect()
     }
 
     pub fn len(&self) -> usize {
-        self.value.len()
+        self.len.try_into().unwrap()
     }
 
-    pub fn remove(&self, key: Term) -> Option<HashMap<Term, Term>> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            map.remove(&key);
-            Some(map)
-        } else {
-            None
+    /// The entries in the order of [Map::keys]
+    pub fn iter(&self) -> vec::IntoIter<(Term, Term)> {
+        let mut entries = Vec::with_capacity(self.len());
+
+        match self.kind() {
+            Kind::Flat { keys, values } => {
+                entries.extend(keys.iter().copied().zip(values.iter().copied()))
+            }
+            Kind::Hash { root } => node_entries(root, 0, &mut entries),
         }
+
+        entries.into_iter()
     }
 
-    pub fn update(&self, key: Term, value: Term) -> Option<HashMap<Term, Term>> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            map.insert(key, value);
-            Some(map)
-        } else {
-            None
+    /// The first entry at or after `position` in the order of [Map::iter], and the position of
+    /// the entry after it, as used by `maps:iterator/1` and `maps:next/1`.  The first entry is at
+    /// position `0`.
+    ///
+    /// For flatmaps, the position is the index of the entry.  For hash maps, the high 64 bits are
+    /// the slots of the entry at each level, with the root's slot most significant, and the low 64
+    /// bits are the index of the entry in its collision node, so that the entry is found by
+    /// descending from the root once instead of by skipping the entries before it.
+    pub fn next_entry(&self, position: u128) -> Option<(Term, Term, u128)> {
+        match self.kind() {
+            Kind::Flat { keys, values } => {
+                if position < (keys.len() as u128) {
+                    let index = position as usize;
+
+                    Some((keys[index], values[index], position + 1))
+                } else {
+                    None
+                }
+            }
+            Kind::Hash { root } => {
+                let bound = ((position >> 64) as u64, position as u64);
+
+                node_next_entry(root, 0, 0, Some(bound))
+            }
         }
     }
 
-    pub fn put(&self, key: Term, value: Term) -> Option<HashMap<Term, Term>> {
-        if self.get(key).map_or(false, |val| val == value) {
-            None
+    /// This map with `key` associated with `value`, or `None` if `key` is already associated with
+    /// `value`.
+    pub fn put<A>(&self, heap: &mut A, key: Term, value: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let len = self.len();
+
+        match self.kind() {
+            Kind::Flat { keys, values } => match flat_index(&keys, key) {
+                Ok(index) => {
+                    if are_exactly_equal(values[index], value) {
+                        Ok(None)
+                    } else {
+                        self.replace_flat_value(heap, &values, index, value)
+                            .map(Some)
+                    }
+                }
+                Err(index) if len < FLATMAP_MAX_LEN => {
+                    let mut key_vec = keys.elements().to_vec();
+                    key_vec.insert(index, key);
+                    let mut value_vec = values.elements().to_vec();
+                    value_vec.insert(index, value);
+
+                    Self::from_flat_vecs(heap, &key_vec, &value_vec).map(Some)
+                }
+                Err(index) => {
+                    let mut entries: Vec<(Term, Term)> = self.iter().collect();
+                    entries.insert(index, (key, value));
+
+                    Self::from_unique_sorted_entries(heap, entries).map(Some)
+                }
+            },
+            Kind::Hash { root } => match node_put(heap, root, hash_key(key), 0, key, value)? {
+                Put::Unchanged => Ok(None),
+                Put::Replaced(root) => Self::alloc(heap, len, root, Term::NIL).map(Some),
+                Put::Added(root) => Self::alloc(heap, len + 1, root, Term::NIL).map(Some),
+            },
+        }
+    }
+
+    /// This map with `key` associated with `value`, or `None` if `key` is not in the map.
+    pub fn update<A>(&self, heap: &mut A, key: Term, value: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        if self.is_key(key) {
+            match self.put(heap, key, value)? {
+                Some(map) => Ok(Some(map)),
+                // `key` is already associated with `value`, so only the map itself is new and it
+                // shares the keys and values
+                None => Self::alloc(heap, self.len(), self.keys_or_root, self.values).map(Some),
+            }
         } else {
-            let mut map = self.value.clone();
-            map.insert(key, value);
-            Some(map)
+            Ok(None)
         }
     }
 
-    pub fn iter(&self) -> hashbrown::hash_map::Iter<Term, Term> {
-        self.value.iter()
+    /// This map without `key`, or `None` if `key` is not in the map.
+    pub fn remove<A>(&self, heap: &mut A, key: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let len = self.len();
+
+        match self.kind() {
+            Kind::Flat { keys, values } => match flat_index(&keys, key) {
+                Ok(index) => {
+                    let mut key_vec = keys.elements().to_vec();
+                    key_vec.remove(index);
+                    let mut value_vec = values.elements().to_vec();
+                    value_vec.remove(index);
+
+                    Self::from_flat_vecs(heap, &key_vec, &value_vec).map(Some)
+                }
+                Err(_) => Ok(None),
+            },
+            // Hash maps that become small enough are flatmaps again
+            Kind::Hash { .. } if len - 1 <= FLATMAP_MAX_LEN => {
+                if self.is_key(key) {
+                    let mut entries: Vec<(Term, Term)> = self
+                        .iter()
+                        .filter(|(entry_key, _)| !are_exactly_equal(*entry_key, key))
+                        .collect();
+                    entries.sort_by(|(left, _), (right, _)| key_cmp(left, right));
+
+                    Self::from_unique_sorted_entries(heap, entries).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+            Kind::Hash { root } => match node_remove(heap, root, hash_key(key), 0, key)? {
+                Some(Removed::Node(root)) => Self::alloc(heap, len - 1, root, Term::NIL).map(Some),
+                Some(Removed::Single(..)) => unreachable!("root of hash map only had one entry"),
+                None => Ok(None),
+            },
+        }
     }
 
-    pub fn iter_mut(&mut self) -> hashbrown::hash_map::IterMut<Term, Term> {
-        self.value.iter_mut()
+    /// The value of `key` and this map without `key`, or `None` if `key` is not in the map.
+    pub fn take<A>(&self, heap: &mut A, key: Term) -> AllocResult<Option<(Term, Boxed<Map>)>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self.get(key) {
+            Some(value) => self
+                .remove(heap, key)
+                .map(|option_map| option_map.map(|map| (value, map))),
+            None => Ok(None),
+        }
     }
 
     // Private
 
-    fn sorted_keys(&self) -> Vec<Term> {
-        let mut key_vec: Vec<Term> = Vec::new();
-        key_vec.extend(self.value.keys());
-        key_vec.sort_unstable_by(|key1, key2| key1.cmp(&key2));
+    fn alloc<A>(
+        heap: &mut A,
+        len: usize,
+        keys_or_root: Term,
+        values: Term,
+    ) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let len_small_integer: SmallInteger = len.try_into().unwrap();
+        let map = Self {
+            header: Default::default(),
+            len: len_small_integer.encode().unwrap(),
+            keys_or_root,
+            values,
+        };
 
-        key_vec
-    }
-}
+        unsafe {
+            let ptr = heap.alloc_layout(Layout::new::<Self>())?.as_ptr() as *mut Self;
+            ptr.write(map);
 
-impl AsRef<HashMap<Term, Term>> for Boxed<Map> {
-    fn as_ref(&self) -> &HashMap<Term, Term> {
-        &self.as_ref().value
+            Ok(Boxed::new_unchecked(ptr))
+        }
     }
-}
 
-impl AsRef<HashMap<Term, Term>> for Map {
-    fn as_ref(&self) -> &HashMap<Term, Term> {
-        &self.value
+    fn from_flat_vecs<A>(heap: &mut A, keys: &[Term], values: &[Term]) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let keys_tuple = Tuple::from_slice(heap, keys)?;
+        let values_tuple = Tuple::from_slice(heap, values)?;
+
+        Self::alloc(heap, keys.len(), keys_tuple.into(), values_tuple.into())
     }
-}
 
-impl crate::borrow::CloneToProcess for Map {
-    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
+    /// `entries` must have unique keys sorted by [key_cmp]
+    fn from_unique_sorted_entries<A>(
+        heap: &mut A,
+        entries: Vec<(Term, Term)>,
+    ) -> AllocResult<Boxed<Map>>
     where
         A: ?Sized + TermAlloc,
     {
-        let layout = Layout::for_value(self);
-        let ptr = unsafe { heap.alloc_layout(layout)?.as_ptr() };
+        let len = entries.len();
+
+        if len <= FLATMAP_MAX_LEN {
+            let (keys, values): (Vec<Term>, Vec<Term>) = entries.into_iter().unzip();
 
-        let self_value = &self.value;
-        let mut heap_value = HashMap::with_capacity(self_value.len());
+            Self::from_flat_vecs(heap, &keys, &values)
+        } else {
+            let mut plan = Plan::new(0);
+
+            for (key, value) in entries {
+                plan.insert(key, value, hash_key(key), 0);
+            }
+
+            let root = plan.write(heap)?;
 
-        for (entry_key, entry_value) in self_value {
-            let heap_entry_key = entry_key.clone_to_heap(heap)?;
-            let heap_entry_value = entry_value.clone_to_heap(heap)?;
-            heap_value.insert(heap_entry_key, heap_entry_value);
+            Self::alloc(heap, len, root, Term::NIL)
         }
+    }
 
-        // Clone to ensure `value` remains valid if caller is dropped
-        let heap_self = Self {
-            header: self.header.clone(),
-            value: heap_value,
-        };
+    fn kind(&self) -> Kind {
+        if self.len() <= FLATMAP_MAX_LEN {
+            Kind::Flat {
+                keys: self.keys_or_root.try_into().unwrap(),
+                values: self.values.try_into().unwrap(),
+            }
+        } else {
+            Kind::Hash {
+                root: self.keys_or_root.try_into().unwrap(),
+            }
+        }
+    }
 
-        let size = mem::size_of_val(self);
-        unsafe {
-            ptr::copy_nonoverlapping(&heap_self as *const _ as *const u8, ptr as *mut u8, size);
+    fn replace_flat_value<A>(
+        &self,
+        heap: &mut A,
+        values: &Tuple,
+        index: usize,
+        value: Term,
+    ) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let mut value_vec = values.elements().to_vec();
+        value_vec[index] = value;
+        let values_tuple = Tuple::from_slice(heap, &value_vec)?;
+
+        // The keys are the same, so they are shared
+        Self::alloc(heap, self.len(), self.keys_or_root, values_tuple.into())
+    }
+
+    /// The entries sorted by [key_cmp], as flatmaps are
+    fn sorted_entries(&self) -> Vec<(Term, Term)> {
+        let mut entries: Vec<(Term, Term)> = self.iter().collect();
+
+        if FLATMAP_MAX_LEN < entries.len() {
+            entries.sort_by(|(left, _), (right, _)| key_cmp(left, right));
         }
 
-        mem::forget(heap_self);
+        entries
+    }
+}
 
-        Ok((ptr as *mut Self).into())
+impl CloneToProcess for Map {
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let keys_or_root = self.keys_or_root.clone_to_heap(heap)?;
+        let values = self.values.clone_to_heap(heap)?;
+
+        Self::alloc(heap, self.len(), keys_or_root, values).map(|map| map.into())
     }
 
     fn size_in_words(&self) -> usize {
-        crate::erts::to_word_size(Layout::for_value(self).size())
+        MAP_WORDS + self.keys_or_root.size_in_words() + self.values.size_in_words()
     }
 }
 
@@ -216,7 +435,7 @@ impl Debug for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Map")
             .field("header", &self.header)
-            .field("value", &self.value)
+            .field("entries", &self.iter().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -243,9 +462,7 @@ impl Eq for Map {}
 
 impl Hash for Map {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        for key in self.sorted_keys() {
-            let value = self.value[&key];
-
+        for (key, value) in self.sorted_entries() {
             key.hash(state);
             value.hash(state);
         }
@@ -254,7 +471,14 @@ impl Hash for Map {
 
 impl PartialEq for Map {
     fn eq(&self, other: &Map) -> bool {
-        self.value.eq(&other.value)
+        (self.len() == other.len())
+            && self
+                .sorted_entries()
+                .iter()
+                .zip(other.sorted_entries().iter())
+                .all(|((self_key, self_value), (other_key, other_value))| {
+                    are_exactly_equal(*self_key, *other_key) && self_value.eq(other_value)
+                })
     }
 }
 impl<T> PartialEq<Boxed<T>> for Map
@@ -268,7 +492,7 @@ where
 }
 
 impl PartialOrd for Map {
-    fn partial_cmp(&self, other: &Map) -> Option<cmp::Ordering> {
+    fn partial_cmp(&self, other: &Map) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -277,7 +501,7 @@ where
     T: PartialOrd<Map>,
 {
     #[inline]
-    fn partial_cmp(&self, other: &Boxed<T>) -> Option<cmp::Ordering> {
+    fn partial_cmp(&self, other: &Boxed<T>) -> Option<Ordering> {
         other.as_ref().partial_cmp(self).map(|o| o.reverse())
     }
 }
@@ -286,36 +510,26 @@ impl Ord for Map {
     /// > * Maps are compared by size, then by keys in ascending term order,
     /// >   then by values in key order.   In the specific case of maps' key
     /// >   ordering, integers are always considered to be less than floats.
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
+    fn cmp(&self, other: &Self) -> Ordering {
         match self.len().cmp(&other.len()) {
-            cmp::Ordering::Equal => {
-                let self_key_vec = self.sorted_keys();
-                let other_key_vec = other.sorted_keys();
-
-                match self_key_vec.cmp(&other_key_vec) {
-                    cmp::Ordering::Equal => {
-                        let self_value = &self.value;
-                        let other_value = &other.value;
-                        let mut final_ordering = cmp::Ordering::Equal;
-
-                        for key in self_key_vec {
-                            match self_value
-                                .get(&key)
-                                .unwrap()
-                                .cmp(other_value.get(&key).unwrap())
-                            {
-                                cmp::Ordering::Equal => continue,
-                                ordering => {
-                                    final_ordering = ordering;
-
-                                    break;
-                                }
-                            }
-                        }
+            Ordering::Equal => {
+                let self_entries = self.sorted_entries();
+                let other_entries = other.sorted_entries();
 
-                        final_ordering
-                    }
-                    ordering => ordering,
+                let key_ordering = self_entries
+                    .iter()
+                    .zip(other_entries.iter())
+                    .map(|((self_key, _), (other_key, _))| key_cmp(self_key, other_key))
+                    .find(|ordering| *ordering != Ordering::Equal);
+
+                match key_ordering {
+                    Some(ordering) => ordering,
+                    None => self_entries
+                        .iter()
+                        .zip(other_entries.iter())
+                        .map(|((_, self_value), (_, other_value))| self_value.cmp(other_value))
+                        .find(|ordering| *ordering != Ordering::Equal)
+                        .unwrap_or(Ordering::Equal),
                 }
             }
             ordering => ordering,
@@ -333,3 +547,1034 @@ impl TryFrom<TypedTerm> for Boxed<Map> {
         }
     }
 }
+
+/// The order of keys in maps: term order, except that, at the first position where keys that are
+/// equal in term order are not exactly equal, an integer is less than the float equal to it, so
+/// that keys are only equal if they are exactly equal.
+pub fn key_cmp(left: &Term, right: &Term) -> Ordering {
+    match left.cmp(right) {
+        Ordering::Equal => exact_cmp(*left, *right),
+        ordering => ordering,
+    }
+}
+
+/// Keys that are exactly equal hash the same, whatever their representation, as binaries are
+/// hashed by their bits whether they are heap, reference-counted, literal or sub-binaries.
+pub fn hash_key(key: Term) -> u64 {
+    let mut hasher = KeyHasher::default();
+    write_key(&mut hasher, key);
+
+    hasher.finish()
+}
+
+// Private
+
+/// Bits of a key's hash used to pick a slot at each level of a hash map
+const BITS_PER_LEVEL: u32 = 4;
+/// Slots in a hash map node, so that the bitmaps of a node fit in a `SmallInteger` on all targets
+const SLOTS_PER_NODE: u32 = 1 << BITS_PER_LEVEL;
+/// The depth of collision nodes, which hold the entries whose keys have the same hash
+const COLLISION_DEPTH: u32 = 64 / BITS_PER_LEVEL;
+
+const MAP_WORDS: usize = mem::size_of::<Map>() / mem::size_of::<Term>();
+/// Header, data bitmap, node bitmap and 2 words for each slot
+const NODE_MAX_WORDS: usize = 3 + 2 * (SLOTS_PER_NODE as usize);
+/// The entry and, at most, one single child node at each level for it
+const HASH_MAP_WORDS_PER_ENTRY: usize = 2 + 4 * (COLLISION_DEPTH as usize);
+
+enum Kind {
+    Flat {
+        keys: Boxed<Tuple>,
+        values: Boxed<Tuple>,
+    },
+    Hash {
+        root: Boxed<Tuple>,
+    },
+}
+
+fn are_exactly_equal(left: Term, right: Term) -> bool {
+    (left.as_usize() == right.as_usize()) || (key_cmp(&left, &right) == Ordering::Equal)
+}
+
+fn flat_index(keys: &Tuple, key: Term) -> Result<usize, usize> {
+    keys.elements()
+        .binary_search_by(|probe| key_cmp(probe, &key))
+}
+
+/// `entries` sorted by [key_cmp] with only the last entry for each key
+fn unique_sorted_entries(entries: &[(Term, Term)]) -> Vec<(Term, Term)> {
+    let mut sorted = entries.to_vec();
+    // Stable, so that the entries for the same key stay in order
+    sorted.sort_by(|(left, _), (right, _)| key_cmp(left, right));
+
+    let mut unique: Vec<(Term, Term)> = Vec::with_capacity(sorted.len());
+
+    for (key, value) in sorted {
+        match unique.last_mut() {
+            Some((last_key, last_value)) if are_exactly_equal(*last_key, key) => {
+                *last_value = value;
+            }
+            _ => unique.push((key, value)),
+        }
+    }
+
+    unique
+}
+
+/// Orders `left` and `right`, which are equal in term order, so they have the same shape and only
+/// differ in whether their numbers are integers or floats.
+fn exact_cmp(left: Term, right: Term) -> Ordering {
+    match (left.decode().unwrap(), right.decode().unwrap()) {
+        (TypedTerm::Float(_), TypedTerm::Float(_)) => Ordering::Equal,
+        (TypedTerm::Float(_), _) => Ordering::Greater,
+        (_, TypedTerm::Float(_)) => Ordering::Less,
+        (TypedTerm::List(left_cons), TypedTerm::List(right_cons)) => left_cons
+            .into_iter()
+            .zip(right_cons.into_iter())
+            .map(|results| match results {
+                (Ok(left_element), Ok(right_element))
+                | (
+                    Err(ImproperList { tail: left_element }),
+                    Err(ImproperList {
+                        tail: right_element,
+                    }),
+                ) => key_cmp(&left_element, &right_element),
+                _ => Ordering::Equal,
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal),
+        (TypedTerm::Map(left_map), TypedTerm::Map(right_map)) => {
+            let left_entries = left_map.sorted_entries();
+            let right_entries = right_map.sorted_entries();
+
+            let key_ordering = left_entries
+                .iter()
+                .zip(right_entries.iter())
+                .map(|((left_key, _), (right_key, _))| key_cmp(left_key, right_key))
+                .find(|ordering| *ordering != Ordering::Equal);
+
+            match key_ordering {
+                Some(ordering) => ordering,
+                None => left_entries
+                    .iter()
+                    .zip(right_entries.iter())
+                    .map(|((_, left_value), (_, right_value))| key_cmp(left_value, right_value))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal),
+            }
+        }
+        (TypedTerm::Tuple(left_tuple), TypedTerm::Tuple(right_tuple)) => left_tuple
+            .iter()
+            .zip(right_tuple.iter())
+            .map(|(left_element, right_element)| key_cmp(left_element, right_element))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal),
+        _ => Ordering::Equal,
+    }
+}
+
+// Prefixes for the terms that `write_key` hashes itself instead of using their `Hash`
+const BITSTRING_PREFIX: u8 = 0;
+const LIST_PREFIX: u8 = 1;
+const IMPROPER_TAIL_PREFIX: u8 = 2;
+const MAP_PREFIX: u8 = 3;
+const TUPLE_PREFIX: u8 = 4;
+
+/// Writes `key` into `hasher`, descending into lists, maps and tuples, so that binaries in them
+/// are also hashed by their bits
+fn write_key(hasher: &mut KeyHasher, key: Term) {
+    match key.decode().unwrap() {
+        TypedTerm::HeapBinary(heap_binary) => write_aligned_binary(hasher, heap_binary.as_bytes()),
+        TypedTerm::ProcBin(proc_bin) => write_aligned_binary(hasher, proc_bin.as_bytes()),
+        TypedTerm::BinaryLiteral(binary_literal) => {
+            write_aligned_binary(hasher, binary_literal.as_bytes())
+        }
+        TypedTerm::SubBinary(subbinary) => write_bitstring(
+            hasher,
+            subbinary.full_byte_len(),
+            subbinary.full_byte_iter(),
+            subbinary.partial_byte_bit_len(),
+            subbinary.partial_byte_bit_iter(),
+        ),
+        TypedTerm::MatchContext(match_context) => write_bitstring(
+            hasher,
+            match_context.full_byte_len(),
+            match_context.full_byte_iter(),
+            match_context.partial_byte_bit_len(),
+            match_context.partial_byte_bit_iter(),
+        ),
+        TypedTerm::List(cons) => {
+            hasher.write_u8(LIST_PREFIX);
+
+            for result in cons.into_iter() {
+                match result {
+                    Ok(element) => write_key(hasher, element),
+                    Err(ImproperList { tail }) => {
+                        hasher.write_u8(IMPROPER_TAIL_PREFIX);
+                        write_key(hasher, tail);
+                    }
+                }
+            }
+        }
+        TypedTerm::Map(map) => {
+            hasher.write_u8(MAP_PREFIX);
+            hasher.write_usize(map.len());
+
+            for (key, value) in map.sorted_entries() {
+                write_key(hasher, key);
+                write_key(hasher, value);
+            }
+        }
+        TypedTerm::Tuple(tuple) => {
+            hasher.write_u8(TUPLE_PREFIX);
+            hasher.write_usize(tuple.len());
+
+            for element in tuple.iter() {
+                write_key(hasher, *element);
+            }
+        }
+        _ => key.hash(hasher),
+    }
+}
+
+fn write_aligned_binary(hasher: &mut KeyHasher, bytes: &[u8]) {
+    write_bitstring(
+        hasher,
+        bytes.len(),
+        bytes.iter().copied(),
+        0,
+        core::iter::empty(),
+    )
+}
+
+/// The length prefixes keep adjacent bitstrings in lists and tuples from running together
+fn write_bitstring(
+    hasher: &mut KeyHasher,
+    full_byte_len: usize,
+    full_byte_iter: impl Iterator<Item = u8>,
+    partial_byte_bit_len: u8,
+    partial_byte_bit_iter: impl Iterator<Item = u8>,
+) {
+    hasher.write_u8(BITSTRING_PREFIX);
+    hasher.write_usize(full_byte_len);
+
+    for byte in full_byte_iter {
+        hasher.write_u8(byte);
+    }
+
+    hasher.write_u8(partial_byte_bit_len);
+
+    for bit in partial_byte_bit_iter {
+        hasher.write_u8(bit);
+    }
+}
+
+/// FNV-1a with a final mix, so that the hash, and so the hash map layout, is the same for all
+/// processes
+struct KeyHasher(u64);
+
+impl Default for KeyHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        let mut hash = self.0;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        hash ^= hash >> 33;
+
+        hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// The slot of `hash` in a node at `depth`
+fn slot(hash: u64, depth: u32) -> u32 {
+    ((hash >> (depth * BITS_PER_LEVEL)) as u32) & (SLOTS_PER_NODE - 1)
+}
+
+fn bitmap_to_term(bitmap: u32) -> Term {
+    let small_integer: SmallInteger = (bitmap as usize).try_into().unwrap();
+
+    small_integer.encode().unwrap()
+}
+
+/// A hash map node: `{DataBitmap, NodeBitmap, Key1, Value1, ..., KeyN, ValueN, Node1, ..., NodeM}`
+/// with the entries and then the child nodes in slot order.  Collision nodes are only
+/// `{Key1, Value1, ..., KeyN, ValueN}`.
+struct Node {
+    tuple: Boxed<Tuple>,
+    data_bitmap: u32,
+    node_bitmap: u32,
+}
+
+impl Node {
+    fn new(tuple: Boxed<Tuple>) -> Self {
+        let data_bitmap: usize = tuple[0].try_into().unwrap();
+        let node_bitmap: usize = tuple[1].try_into().unwrap();
+
+        Self {
+            tuple,
+            data_bitmap: data_bitmap as u32,
+            node_bitmap: node_bitmap as u32,
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.data_bitmap.count_ones() as usize
+    }
+
+    fn data_index(&self, bit: u32) -> usize {
+        (self.data_bitmap & (bit - 1)).count_ones() as usize
+    }
+
+    fn node_index(&self, bit: u32) -> usize {
+        (self.node_bitmap & (bit - 1)).count_ones() as usize
+    }
+
+    fn key(&self, data_index: usize) -> Term {
+        self.tuple[2 + 2 * data_index]
+    }
+
+    fn value(&self, data_index: usize) -> Term {
+        self.tuple[3 + 2 * data_index]
+    }
+
+    fn child_position(&self, node_index: usize) -> usize {
+        2 + 2 * self.data_len() + node_index
+    }
+
+    fn child(&self, node_index: usize) -> Boxed<Tuple> {
+        self.tuple[self.child_position(node_index)]
+            .try_into()
+            .unwrap()
+    }
+
+    fn elements(&self) -> Vec<Term> {
+        self.tuple.elements().to_vec()
+    }
+}
+
+fn write_node<A>(
+    heap: &mut A,
+    data_bitmap: u32,
+    node_bitmap: u32,
+    mut elements: Vec<Term>,
+) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    elements[0] = bitmap_to_term(data_bitmap);
+    elements[1] = bitmap_to_term(node_bitmap);
+
+    Tuple::from_slice(heap, &elements).map(|tuple| tuple.into())
+}
+
+fn node_get(tuple: Boxed<Tuple>, hash: u64, depth: u32, key: Term) -> Option<Term> {
+    if depth == COLLISION_DEPTH {
+        return tuple
+            .elements()
+            .chunks(2)
+            .find(|pair| are_exactly_equal(pair[0], key))
+            .map(|pair| pair[1]);
+    }
+
+    let node = Node::new(tuple);
+    let bit = 1 << slot(hash, depth);
+
+    if (node.data_bitmap & bit) != 0 {
+        let data_index = node.data_index(bit);
+
+        if are_exactly_equal(node.key(data_index), key) {
+            Some(node.value(data_index))
+        } else {
+            None
+        }
+    } else if (node.node_bitmap & bit) != 0 {
+        node_get(node.child(node.node_index(bit)), hash, depth + 1, key)
+    } else {
+        None
+    }
+}
+
+fn node_entries(tuple: Boxed<Tuple>, depth: u32, entries: &mut Vec<(Term, Term)>) {
+    if depth == COLLISION_DEPTH {
+        entries.extend(tuple.elements().chunks(2).map(|pair| (pair[0], pair[1])));
+
+        return;
+    }
+
+    let node = Node::new(tuple);
+
+    for slot in 0..SLOTS_PER_NODE {
+        let bit = 1 << slot;
+
+        if (node.data_bitmap & bit) != 0 {
+            let data_index = node.data_index(bit);
+            entries.push((node.key(data_index), node.value(data_index)));
+        } else if (node.node_bitmap & bit) != 0 {
+            node_entries(node.child(node.node_index(bit)), depth + 1, entries);
+        }
+    }
+}
+
+/// The entries of `tuple` at `depth`, whose path is `prefix`, are at or after `bound` when its
+/// path starts with `prefix`, or all after it when `bound` is `None`.
+fn node_next_entry(
+    tuple: Boxed<Tuple>,
+    depth: u32,
+    prefix: u64,
+    bound: Option<(u64, u64)>,
+) -> Option<(Term, Term, u128)> {
+    if depth == COLLISION_DEPTH {
+        let start = bound.map_or(0, |(_, collision_index)| collision_index);
+
+        return tuple
+            .elements()
+            .chunks(2)
+            .nth(start.try_into().unwrap_or(usize::max_value()))
+            .map(|pair| {
+                let next_position = ((prefix as u128) << 64) | ((start as u128) + 1);
+
+                (pair[0], pair[1], next_position)
+            });
+    }
+
+    let node = Node::new(tuple);
+    let shift = path_shift(depth);
+    let start_slot = bound.map_or(0, |(path, _)| {
+        ((path >> shift) as u32) & (SLOTS_PER_NODE - 1)
+    });
+
+    for slot in start_slot..SLOTS_PER_NODE {
+        let bit = 1 << slot;
+        let slot_prefix = prefix | ((slot as u64) << shift);
+        // Only the entries in the start slot can be before `bound`
+        let slot_bound = bound.filter(|_| slot == start_slot);
+
+        if (node.data_bitmap & bit) != 0 {
+            // The entry's position has no deeper slots or collision index, so it is before a
+            // `bound` that has any
+            let is_before_bound = slot_bound.map_or(false, |(path, collision_index)| {
+                (path & ((1 << shift) - 1)) != 0 || collision_index != 0
+            });
+
+            if !is_before_bound {
+                let data_index = node.data_index(bit);
+
+                return Some((
+                    node.key(data_index),
+                    node.value(data_index),
+                    position_after_slot(slot_prefix, shift),
+                ));
+            }
+        } else if (node.node_bitmap & bit) != 0 {
+            let child = node.child(node.node_index(bit));
+
+            if let Some(entry) = node_next_entry(child, depth + 1, slot_prefix, slot_bound) {
+                return Some(entry);
+            }
+        }
+    }
+
+    None
+}
+
+/// The shift of the slot at `depth` in the path of a hash map position
+fn path_shift(depth: u32) -> u32 {
+    (COLLISION_DEPTH - 1 - depth) * BITS_PER_LEVEL
+}
+
+/// The position after all the entries under the slot at the end of `slot_prefix`, or
+/// `u128::max_value()`, which is after all entries, when the slot is the last of the map.
+fn position_after_slot(slot_prefix: u64, shift: u32) -> u128 {
+    let next_path = (((slot_prefix >> shift) as u128) + 1) << shift;
+
+    if next_path <= (u64::max_value() as u128) {
+        next_path << 64
+    } else {
+        u128::max_value()
+    }
+}
+
+enum Put {
+    Unchanged,
+    /// The value of an existing key was replaced in the new node
+    Replaced(Term),
+    /// The entry was added in the new node
+    Added(Term),
+}
+
+fn node_put<A>(
+    heap: &mut A,
+    tuple: Boxed<Tuple>,
+    hash: u64,
+    depth: u32,
+    key: Term,
+    value: Term,
+) -> AllocResult<Put>
+where
+    A: ?Sized + TermAlloc,
+{
+    if depth == COLLISION_DEPTH {
+        let mut elements = tuple.elements().to_vec();
+
+        return match elements
+            .chunks(2)
+            .position(|pair| are_exactly_equal(pair[0], key))
+        {
+            Some(pair_index) => {
+                if are_exactly_equal(elements[2 * pair_index + 1], value) {
+                    Ok(Put::Unchanged)
+                } else {
+                    elements[2 * pair_index + 1] = value;
+
+                    Tuple::from_slice(heap, &elements).map(|tuple| Put::Replaced(tuple.into()))
+                }
+            }
+            None => {
+                elements.push(key);
+                elements.push(value);
+
+                Tuple::from_slice(heap, &elements).map(|tuple| Put::Added(tuple.into()))
+            }
+        };
+    }
+
+    let node = Node::new(tuple);
+    let bit = 1 << slot(hash, depth);
+
+    if (node.data_bitmap & bit) != 0 {
+        let data_index = node.data_index(bit);
+        let existing_key = node.key(data_index);
+        let existing_value = node.value(data_index);
+
+        if are_exactly_equal(existing_key, key) {
+            if are_exactly_equal(existing_value, value) {
+                Ok(Put::Unchanged)
+            } else {
+                let mut elements = node.elements();
+                elements[3 + 2 * data_index] = value;
+
+                write_node(heap, node.data_bitmap, node.node_bitmap, elements).map(Put::Replaced)
+            }
+        } else {
+            // Both entries move down to a new child node in this slot
+            let child = merge(
+                heap,
+                (existing_key, existing_value, hash_key(existing_key)),
+                (key, value, hash),
+                depth + 1,
+            )?;
+            let child_position = node.child_position(node.node_index(bit)) - 2;
+
+            let mut elements = node.elements();
+            elements.drain((2 + 2 * data_index)..(4 + 2 * data_index));
+            elements.insert(child_position, child);
+
+            write_node(
+                heap,
+                node.data_bitmap ^ bit,
+                node.node_bitmap | bit,
+                elements,
+            )
+            .map(Put::Added)
+        }
+    } else if (node.node_bitmap & bit) != 0 {
+        let node_index = node.node_index(bit);
+
+        let (child, added) =
+            match node_put(heap, node.child(node_index), hash, depth + 1, key, value)? {
+                Put::Unchanged => return Ok(Put::Unchanged),
+                Put::Replaced(child) => (child, false),
+                Put::Added(child) => (child, true),
+            };
+
+        let mut elements = node.elements();
+        elements[node.child_position(node_index)] = child;
+        let new_tuple = write_node(heap, node.data_bitmap, node.node_bitmap, elements)?;
+
+        if added {
+            Ok(Put::Added(new_tuple))
+        } else {
+            Ok(Put::Replaced(new_tuple))
+        }
+    } else {
+        let data_position = 2 + 2 * node.data_index(bit);
+
+        let mut elements = node.elements();
+        elements.insert(data_position, value);
+        elements.insert(data_position, key);
+
+        write_node(heap, node.data_bitmap | bit, node.node_bitmap, elements).map(Put::Added)
+    }
+}
+
+/// The node at `depth` for 2 entries whose keys have the same slots above `depth`
+fn merge<A>(
+    heap: &mut A,
+    first: (Term, Term, u64),
+    second: (Term, Term, u64),
+    depth: u32,
+) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    let (first_key, first_value, first_hash) = first;
+    let (second_key, second_value, second_hash) = second;
+
+    if depth == COLLISION_DEPTH {
+        return Tuple::from_slice(heap, &[first_key, first_value, second_key, second_value])
+            .map(|tuple| tuple.into());
+    }
+
+    let first_slot = slot(first_hash, depth);
+    let second_slot = slot(second_hash, depth);
+
+    if first_slot == second_slot {
+        let child = merge(heap, first, second, depth + 1)?;
+
+        write_node(
+            heap,
+            0,
+            1 << first_slot,
+            vec![Term::NONE, Term::NONE, child],
+        )
+    } else {
+        let elements = if first_slot < second_slot {
+            vec![
+                Term::NONE,
+                Term::NONE,
+                first_key,
+                first_value,
+                second_key,
+                second_value,
+            ]
+        } else {
+            vec![
+                Term::NONE,
+                Term::NONE,
+                second_key,
+                second_value,
+                first_key,
+                first_value,
+            ]
+        };
+
+        write_node(heap, (1 << first_slot) | (1 << second_slot), 0, elements)
+    }
+}
+
+enum Removed {
+    Node(Term),
+    /// The node would only have this entry, so it belongs in the parent node instead
+    Single(Term, Term),
+}
+
+fn node_remove<A>(
+    heap: &mut A,
+    tuple: Boxed<Tuple>,
+    hash: u64,
+    depth: u32,
+    key: Term,
+) -> AllocResult<Option<Removed>>
+where
+    A: ?Sized + TermAlloc,
+{
+    if depth == COLLISION_DEPTH {
+        let mut elements = tuple.elements().to_vec();
+
+        return match elements
+            .chunks(2)
+            .position(|pair| are_exactly_equal(pair[0], key))
+        {
+            Some(pair_index) => {
+                elements.drain((2 * pair_index)..(2 * pair_index + 2));
+
+                if elements.len() == 2 {
+                    Ok(Some(Removed::Single(elements[0], elements[1])))
+                } else {
+                    Tuple::from_slice(heap, &elements)
+                        .map(|tuple| Some(Removed::Node(tuple.into())))
+                }
+            }
+            None => Ok(None),
+        };
+    }
+
+    let node = Node::new(tuple);
+    let bit = 1 << slot(hash, depth);
+
+    if (node.data_bitmap & bit) != 0 {
+        let data_index = node.data_index(bit);
+
+        if !are_exactly_equal(node.key(data_index), key) {
+            return Ok(None);
+        }
+
+        if 0 < depth && node.node_bitmap == 0 && node.data_len() == 2 {
+            let other_data_index = 1 - data_index;
+
+            return Ok(Some(Removed::Single(
+                node.key(other_data_index),
+                node.value(other_data_index),
+            )));
+        }
+
+        let mut elements = node.elements();
+        elements.drain((2 + 2 * data_index)..(4 + 2 * data_index));
+
+        write_node(heap, node.data_bitmap ^ bit, node.node_bitmap, elements)
+            .map(|tuple| Some(Removed::Node(tuple)))
+    } else if (node.node_bitmap & bit) != 0 {
+        let node_index = node.node_index(bit);
+
+        match node_remove(heap, node.child(node_index), hash, depth + 1, key)? {
+            Some(Removed::Node(child)) => {
+                let mut elements = node.elements();
+                elements[node.child_position(node_index)] = child;
+
+                write_node(heap, node.data_bitmap, node.node_bitmap, elements)
+                    .map(|tuple| Some(Removed::Node(tuple)))
+            }
+            Some(Removed::Single(single_key, single_value)) => {
+                if 0 < depth && node.data_bitmap == 0 && node.node_bitmap == bit {
+                    Ok(Some(Removed::Single(single_key, single_value)))
+                } else {
+                    // The single entry replaces the child node in this slot
+                    let mut elements = node.elements();
+                    elements.remove(node.child_position(node_index));
+                    let data_position = 2 + 2 * node.data_index(bit);
+                    elements.insert(data_position, single_value);
+                    elements.insert(data_position, single_key);
+
+                    write_node(
+                        heap,
+                        node.data_bitmap | bit,
+                        node.node_bitmap ^ bit,
+                        elements,
+                    )
+                    .map(|tuple| Some(Removed::Node(tuple)))
+                }
+            }
+            None => Ok(None),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// A hash map node built from entries before it is allocated, so that building a hash map only
+/// allocates the final nodes.
+enum Plan {
+    Bitmap(Vec<PlanSlot>),
+    Collision(Vec<(Term, Term)>),
+}
+
+enum PlanSlot {
+    Empty,
+    Entry(Term, Term, u64),
+    Node(Plan),
+}
+
+impl Plan {
+    fn new(depth: u32) -> Self {
+        if depth == COLLISION_DEPTH {
+            Plan::Collision(Vec::new())
+        } else {
+            Plan::Bitmap((0..SLOTS_PER_NODE).map(|_| PlanSlot::Empty).collect())
+        }
+    }
+
+    /// `key` must not already be in the plan
+    fn insert(&mut self, key: Term, value: Term, hash: u64, depth: u32) {
+        match self {
+            Plan::Bitmap(slots) => {
+                let plan_slot = &mut slots[slot(hash, depth) as usize];
+
+                *plan_slot = match mem::replace(plan_slot, PlanSlot::Empty) {
+                    PlanSlot::Empty => PlanSlot::Entry(key, value, hash),
+                    PlanSlot::Entry(existing_key, existing_value, existing_hash) => {
+                        let mut child = Plan::new(depth + 1);
+                        child.insert(existing_key, existing_value, existing_hash, depth + 1);
+                        child.insert(key, value, hash, depth + 1);
+
+                        PlanSlot::Node(child)
+                    }
+                    PlanSlot::Node(mut child) => {
+                        child.insert(key, value, hash, depth + 1);
+
+                        PlanSlot::Node(child)
+                    }
+                };
+            }
+            Plan::Collision(entries) => entries.push((key, value)),
+        }
+    }
+
+    fn write<A>(self, heap: &mut A) -> AllocResult<Term>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self {
+            Plan::Bitmap(slots) => {
+                let mut data_bitmap = 0;
+                let mut node_bitmap = 0;
+                let mut elements = vec![Term::NONE, Term::NONE];
+                let mut children = Vec::new();
+
+                for (slot, plan_slot) in slots.into_iter().enumerate() {
+                    match plan_slot {
+                        PlanSlot::Empty => (),
+                        PlanSlot::Entry(key, value, _) => {
+                            data_bitmap |= 1 << slot;
+                            elements.push(key);
+                            elements.push(value);
+                        }
+                        PlanSlot::Node(child) => {
+                            node_bitmap |= 1 << slot;
+                            children.push(child.write(heap)?);
+                        }
+                    }
+                }
+
+                elements.extend(children);
+
+                write_node(heap, data_bitmap, node_bitmap, elements)
+            }
+            Plan::Collision(entries) => {
+                let elements: Vec<Term> = entries
+                    .into_iter()
+                    .flat_map(|(key, value)| vec![key, value])
+                    .collect();
+
+                Tuple::from_slice(heap, &elements).map(|tuple| tuple.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::erts::testing::RegionHeap;
+
+    fn entries(range: core::ops::Range<isize>) -> Vec<(Term, Term)> {
+        range.map(|i| (fixnum!(i), fixnum!(-i))).collect()
+    }
+
+    #[test]
+    fn from_slice_with_duplicate_keys_keeps_last() {
+        let mut heap = RegionHeap::default();
+
+        let map = Map::from_slice(
+            &mut heap,
+            &[(atom!("a"), fixnum!(1)), (atom!("a"), fixnum!(2))],
+        )
+        .unwrap();
+
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(atom!("a")), Some(fixnum!(2)));
+    }
+
+    #[test]
+    fn flatmap_keys_are_in_map_key_order() {
+        let mut heap = RegionHeap::default();
+        let float: Term = heap.float(1.0).unwrap().into();
+
+        let map = Map::from_slice(
+            &mut heap,
+            &[
+                (atom!("b"), fixnum!(1)),
+                (float, fixnum!(2)),
+                (fixnum!(1), fixnum!(3)),
+                (atom!("a"), fixnum!(4)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(map.keys(), vec![fixnum!(1), float, atom!("a"), atom!("b")]);
+    }
+
+    #[test]
+    fn nested_integer_keys_are_before_equal_nested_float_keys() {
+        let mut heap = RegionHeap::default();
+        let float: Term = heap.float(1.0).unwrap().into();
+        let float_tuple: Term = heap.tuple_from_slice(&[float]).unwrap().into();
+        let integer_tuple: Term = heap.tuple_from_slice(&[fixnum!(1)]).unwrap().into();
+
+        assert_eq!(key_cmp(&integer_tuple, &float_tuple), Ordering::Less);
+        assert_eq!(key_cmp(&float_tuple, &integer_tuple), Ordering::Greater);
+
+        let map = Map::from_slice(
+            &mut heap,
+            &[(float_tuple, fixnum!(1)), (integer_tuple, fixnum!(2))],
+        )
+        .unwrap();
+
+        let key_addresses: Vec<usize> = map.keys().iter().map(|key| key.as_usize()).collect();
+
+        assert_eq!(
+            key_addresses,
+            vec![integer_tuple.as_usize(), float_tuple.as_usize()]
+        );
+    }
+
+    #[test]
+    fn update_shares_keys() {
+        let mut heap = RegionHeap::default();
+        let map = Map::from_slice(&mut heap, &entries(0..10)).unwrap();
+
+        let replaced = map
+            .update(&mut heap, fixnum!(1), atom!("new"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            replaced.keys_or_root.as_usize(),
+            map.keys_or_root.as_usize()
+        );
+
+        let unchanged = map
+            .update(&mut heap, fixnum!(1), fixnum!(-1))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            unchanged.keys_or_root.as_usize(),
+            map.keys_or_root.as_usize()
+        );
+        assert_eq!(unchanged.values.as_usize(), map.values.as_usize());
+    }
+
+    #[test]
+    fn put_past_flatmap_max_len_becomes_hash_map() {
+        let mut heap = RegionHeap::default();
+        let flatmap = Map::from_slice(&mut heap, &entries(0..(FLATMAP_MAX_LEN as isize))).unwrap();
+
+        let hash_map = flatmap
+            .put(&mut heap, fixnum!(FLATMAP_MAX_LEN), atom!("new"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(hash_map.len(), FLATMAP_MAX_LEN + 1);
+        assert!(hash_map.values.is_nil());
+
+        for i in 0..(FLATMAP_MAX_LEN as isize) {
+            assert_eq!(hash_map.get(fixnum!(i)), Some(fixnum!(-i)));
+        }
+
+        assert_eq!(hash_map.get(fixnum!(FLATMAP_MAX_LEN)), Some(atom!("new")));
+    }
+
+    #[test]
+    fn hash_map_put_update_and_remove_keep_other_entries() {
+        let mut heap = RegionHeap::default();
+        let map = Map::from_slice(&mut heap, &entries(0..100)).unwrap();
+
+        let updated = map
+            .update(&mut heap, fixnum!(50), atom!("updated"))
+            .unwrap()
+            .unwrap();
+        let removed = updated.remove(&mut heap, fixnum!(10)).unwrap().unwrap();
+
+        assert_eq!(map.get(fixnum!(50)), Some(fixnum!(-50)));
+        assert_eq!(updated.get(fixnum!(50)), Some(atom!("updated")));
+        assert_eq!(removed.len(), 99);
+        assert_eq!(removed.get(fixnum!(10)), None);
+
+        for i in (0..100).filter(|i| *i != 10 && *i != 50) {
+            assert_eq!(removed.get(fixnum!(i)), Some(fixnum!(-i)));
+        }
+    }
+
+    #[test]
+    fn put_with_same_value_is_unchanged() {
+        let mut heap = RegionHeap::default();
+        let map = Map::from_slice(&mut heap, &entries(0..100)).unwrap();
+
+        assert!(map
+            .put(&mut heap, fixnum!(1), fixnum!(-1))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn remove_to_flatmap_max_len_becomes_flatmap() {
+        let mut heap = RegionHeap::default();
+        let map = Map::from_slice(&mut heap, &entries(0..(FLATMAP_MAX_LEN as isize + 1))).unwrap();
+
+        let flatmap = map.remove(&mut heap, fixnum!(0)).unwrap().unwrap();
+
+        assert_eq!(flatmap.len(), FLATMAP_MAX_LEN);
+        assert!(!flatmap.values.is_nil());
+        assert_eq!(flatmap.keys()[0], fixnum!(1));
+    }
+
+    #[test]
+    fn hash_map_finds_binary_key_with_different_representation() {
+        let mut heap = RegionHeap::default();
+        let entries: Vec<(Term, Term)> = (0..100u8)
+            .map(|i| {
+                let key: Term = heap.heapbin_from_bytes(&[i, i]).unwrap().into();
+
+                (key, fixnum!(i as isize))
+            })
+            .collect();
+        let map = Map::from_slice(&mut heap, &entries).unwrap();
+
+        assert!(map.values.is_nil());
+
+        let original: Term = heap.heapbin_from_bytes(&[0, 42, 42, 0]).unwrap().into();
+        let subbinary: Term = heap
+            .subbinary_from_original(original, 1, 0, 2, 0)
+            .unwrap()
+            .into();
+
+        assert_eq!(map.get(subbinary), Some(fixnum!(42)));
+        assert!(map.is_key(subbinary));
+    }
+
+    #[test]
+    fn next_entry_visits_entries_in_iter_order() {
+        let mut heap = RegionHeap::default();
+
+        for len in &[0, 1, FLATMAP_MAX_LEN as isize, 100, 1_000] {
+            let map = Map::from_slice(&mut heap, &entries(0..*len)).unwrap();
+            let mut visited = Vec::new();
+            let mut position = 0;
+
+            while let Some((key, value, next_position)) = map.next_entry(position) {
+                assert!(position < next_position);
+
+                visited.push((key, value));
+                position = next_position;
+            }
+
+            assert_eq!(visited, map.iter().collect::<Vec<(Term, Term)>>());
+        }
+    }
+
+    #[test]
+    fn equal_maps_with_different_insertion_order_are_equal() {
+        let mut heap = RegionHeap::default();
+        let mut reversed = entries(0..100);
+        reversed.reverse();
+
+        let map = Map::from_slice(&mut heap, &entries(0..100)).unwrap();
+        let reversed_map = Map::from_slice(&mut heap, &reversed).unwrap();
+
+        assert_eq!(map.as_ref(), reversed_map.as_ref());
+        assert_eq!(map.keys(), reversed_map.keys());
+    }
+}
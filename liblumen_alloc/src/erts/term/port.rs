@@ -3,6 +3,9 @@ use core::cmp;
 use core::convert::TryFrom;
 use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use alloc::sync::Arc;
 
 use crate::borrow::CloneToProcess;
 use crate::erts::exception::AllocResult;
@@ -11,10 +14,25 @@ use crate::erts::process::alloc::TermAlloc;
 
 use super::prelude::*;
 
+// Starts at `1`, so that `#Port<0.0>`, which is what a zeroed port decodes to, is never handed out
+static COUNTER: AtomicUsize = AtomicUsize::new(1);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Port(usize);
 impl Port {
+    /// Generates the next `Port`.
+    ///
+    /// `Port`s are not reused for the lifetime of the VM.
+    pub fn next() -> Port {
+        Self(COUNTER.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Same as `next`, but directly encodes to `Term`
+    pub fn next_term() -> Term {
+        Self::next().encode().unwrap()
+    }
+
     /// Given a the raw pid value (as a usize), reifies it into a `Port`
     #[inline]
     pub unsafe fn from_raw(port: usize) -> Self {
@@ -28,8 +46,8 @@ impl Port {
 }
 
 impl Display for Port {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<0.{}>", self.0)
     }
 }
 
@@ -80,21 +98,43 @@ impl TryFrom<TypedTerm> for Port {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct ExternalPort {
     header: Header<ExternalPort>,
-    node: Node,
-    next: *mut u8,
+    arc_node: Arc<Node>,
     port: Port,
 }
 impl_static_header!(ExternalPort, Term::HEADER_EXTERN_PORT);
+impl ExternalPort {
+    pub fn new(arc_node: Arc<Node>, port: Port) -> Self {
+        Self {
+            header: Default::default(),
+            arc_node,
+            port,
+        }
+    }
+
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn port(&self) -> Port {
+        self.port
+    }
+}
 impl CloneToProcess for ExternalPort {
-    fn clone_to_heap<A>(&self, _heap: &mut A) -> AllocResult<Term>
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
     where
         A: ?Sized + TermAlloc,
     {
-        unimplemented!()
+        unsafe {
+            let layout = Layout::new::<Self>();
+            let ptr = heap.alloc_layout(layout)?.as_ptr() as *mut Self;
+            ptr.write(self.clone());
+
+            Ok(ptr.into())
+        }
     }
 
     fn size_in_words(&self) -> usize {
@@ -103,22 +143,23 @@ impl CloneToProcess for ExternalPort {
 }
 
 impl Display for ExternalPort {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<{}.{}>", self.arc_node.id(), self.port.as_usize())
     }
 }
 
 impl Hash for ExternalPort {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.node.hash(state);
+        self.arc_node.hash(state);
         self.port.hash(state);
     }
 }
 
+impl Eq for ExternalPort {}
 impl PartialEq for ExternalPort {
     #[inline]
     fn eq(&self, other: &ExternalPort) -> bool {
-        self.node == other.node && self.port == other.port
+        self.arc_node == other.arc_node && self.port == other.port
     }
 }
 impl<T> PartialEq<Boxed<T>> for ExternalPort
@@ -131,14 +172,17 @@ where
     }
 }
 
+impl Ord for ExternalPort {
+    fn cmp(&self, other: &ExternalPort) -> cmp::Ordering {
+        self.arc_node
+            .cmp(&other.arc_node)
+            .then_with(|| self.port.cmp(&other.port))
+    }
+}
 impl PartialOrd for ExternalPort {
     #[inline]
     fn partial_cmp(&self, other: &ExternalPort) -> Option<cmp::Ordering> {
-        use cmp::Ordering;
-        match self.node.partial_cmp(&other.node) {
-            Some(Ordering::Equal) => self.port.partial_cmp(&other.port),
-            result => result,
-        }
+        Some(self.cmp(other))
     }
 }
 impl<T> PartialOrd<Boxed<T>> for ExternalPort
@@ -105,6 +105,12 @@ impl Resource {
         self.inner().resource.is::<T>()
     }
 
+    /// The address of the shared resource, which identifies the resource as the number
+    /// identifies a `Reference`
+    pub fn id(&self) -> usize {
+        self.inner.as_ptr() as usize
+    }
+
     pub fn type_name(&self) -> &'static str {
         self.inner().resource_type_name
     }
@@ -57,7 +57,7 @@ mod test;
 pub use liblumen_core::alloc::SysAlloc;
 
 /// A tracing allocator for tracking statistics about the allocator it wraps
-pub use self::stats_alloc::StatsAlloc;
+pub use self::stats_alloc::{Statistics, StatsAlloc};
 
 // An allocator that uses segmented sub-allocators to more efficiently manage
 // allocations of variable sizes that fall within predictable size ranges
@@ -78,3 +78,12 @@ pub struct AllocatorInfo {
     num_multi_block_carriers: usize,
     num_single_block_carriers: usize,
 }
+impl AllocatorInfo {
+    pub fn num_multi_block_carriers(&self) -> usize {
+        self.num_multi_block_carriers
+    }
+
+    pub fn num_single_block_carriers(&self) -> usize {
+        self.num_single_block_carriers
+    }
+}
@@ -99,6 +99,31 @@ pub struct Statistics<H: Histogram + Clone + Default> {
     tag: &'static str,
     histogram: H,
 }
+impl<H: Histogram + Clone + Default> Statistics<H> {
+    pub fn alloc_calls(&self) -> usize {
+        self.alloc_calls
+    }
+
+    pub fn dealloc_calls(&self) -> usize {
+        self.dealloc_calls
+    }
+
+    pub fn realloc_calls(&self) -> usize {
+        self.realloc_calls
+    }
+
+    pub fn total_bytes_alloced(&self) -> usize {
+        self.total_bytes_alloced
+    }
+
+    pub fn total_bytes_freed(&self) -> usize {
+        self.total_bytes_freed
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+}
 impl<H: Histogram + Clone + Default> fmt::Display for Statistics<H> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "## Allocator Statistics (tag = {})", self.tag)?;
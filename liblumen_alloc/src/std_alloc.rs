@@ -56,7 +56,8 @@ use crate::carriers::{MultiBlockCarrier, SingleBlockCarrier};
 use crate::carriers::{MultiBlockCarrierTree, SingleBlockCarrierList};
 use crate::erts::exception::AllocResult;
 use crate::sorted::{SortKey, SortOrder, SortedKeyAdapter};
-use crate::AllocatorInfo;
+use crate::stats::DefaultHistogram;
+use crate::{AllocatorInfo, Statistics};
 
 // The global instance of StandardAlloc
 cfg_if! {
@@ -110,6 +111,20 @@ pub fn alloc_info() -> AllocatorInfo {
     STD_ALLOC.info()
 }
 
+/// Gets the statistics of the global standard allocator, which are only gathered with the
+/// `instrument` feature
+#[cfg(feature = "instrument")]
+pub fn alloc_stats() -> Option<Statistics<DefaultHistogram>> {
+    Some(STD_ALLOC.stats())
+}
+
+/// Gets the statistics of the global standard allocator, which are only gathered with the
+/// `instrument` feature
+#[cfg(not(feature = "instrument"))]
+pub fn alloc_stats() -> Option<Statistics<DefaultHistogram>> {
+    None
+}
+
 struct StandardAlloc {
     sbc_threshold: usize,
     sbc: CachePadded<SpinLock<SingleBlockCarrierList>>,
@@ -41,12 +41,23 @@ pub enum FromBeamError {
     #[fail(display = "debug info is required but not present")]
     NoDebugInfo,
 
+    #[fail(display = "unsupported debug info backend: {}", _0)]
+    UnsupportedDebugInfo(String),
+
     #[fail(display = "missing module attribute")]
     NoModuleAttribute,
 
     #[fail(display = "unexpected term: {}", _0)]
     UnexpectedTerm(UnmatchedTerms),
 }
+impl FromBeamError {
+    pub(crate) fn unexpected_term(value: etf::Term, pattern: &str) -> Self {
+        FromBeamError::UnexpectedTerm(UnmatchedTerms(vec![Unmatched {
+            value,
+            pattern: pattern.to_string(),
+        }]))
+    }
+}
 impl From<std::io::Error> for FromBeamError {
     fn from(x: std::io::Error) -> Self {
         FromBeamError::IO(x)
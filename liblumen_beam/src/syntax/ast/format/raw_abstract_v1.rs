@@ -10,6 +10,7 @@ use crate::serialization::etf::pattern::{Pattern, Unmatch};
 use crate::serialization::etf::pattern::{Uint, F64, I32, U32, U64};
 
 use crate::beam::chunk::Chunk;
+use crate::beam::reader::RawBeamFile;
 
 use crate::syntax::ast::ast::clause;
 use crate::syntax::ast::ast::common;
@@ -41,21 +42,164 @@ pub struct AbstractCode {
 }
 impl AbstractCode {
     pub fn from_beam_file<P: AsRef<Path>>(path: P) -> FromBeamResult<Self> {
-        let beam = crate::beam::reader::RawBeamFile::from_file(path)?;
-        let chunk = beam
-            .chunks()
+        let beam = RawBeamFile::from_file(path)?;
+        Self::from_raw_beam(&beam)
+    }
+
+    /// Extracts the abstract code from an already loaded BEAM file.
+    ///
+    /// The legacy `"Abst"` chunk is preferred when it is present and non-empty, otherwise the
+    /// `"Dbgi"` chunk written by modern versions of `erlc` with `+debug_info` is used.
+    pub fn from_raw_beam(beam: &RawBeamFile) -> FromBeamResult<Self> {
+        let chunks = beam.chunks();
+
+        if let Some(chunk) = chunks.iter().find(|c| c.id() == b"Abst") {
+            if !chunk.data.is_empty() {
+                let code = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+                return Ok(AbstractCode { code });
+            }
+        }
+
+        let chunk = chunks
             .into_iter()
-            .find(|c| c.id() == b"Abst")
+            .find(|c| c.id() == b"Dbgi")
             .ok_or(FromBeamError::NoDebugInfo)?;
-        let code = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+        let debug_info = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+        Self::from_debug_info(debug_info)
+    }
+
+    /// Converts the contents of a `"Dbgi"` chunk to the `raw_abstract_v1` format.
+    ///
+    /// Only the `erl_abstract_code` backend is supported, as other backends (e.g. `elixir_erl`)
+    /// require running Erlang code to produce abstract forms.
+    fn from_debug_info(debug_info: etf::Term) -> FromBeamResult<Self> {
+        let elements = match debug_info {
+            etf::Term::Tuple(etf::Tuple { elements }) if elements.len() == 3 => elements,
+            other => return Err(FromBeamError::unexpected_term(other, "{_, _, _}")),
+        };
+        let mut elements = elements.into_iter();
+        let version = elements.next().unwrap();
+        let backend = elements.next().unwrap();
+        let data = elements.next().unwrap();
+
+        match version {
+            etf::Term::Atom(ref atom) if atom.name == "debug_info_v1" => (),
+            other => return Err(FromBeamError::unexpected_term(other, "debug_info_v1")),
+        }
+        match backend {
+            etf::Term::Atom(ref atom) if atom.name == "erl_abstract_code" => (),
+            etf::Term::Atom(atom) => return Err(FromBeamError::UnsupportedDebugInfo(atom.name)),
+            other => return Err(FromBeamError::unexpected_term(other, "atom()")),
+        }
+
+        // `{AbstractCode, CompileOptions}`, where `AbstractCode` is `none` when the module was
+        // compiled without `debug_info`
+        let forms = match data {
+            etf::Term::Tuple(etf::Tuple { mut elements }) if elements.len() == 2 => {
+                elements.swap_remove(0)
+            }
+            other => return Err(FromBeamError::unexpected_term(other, "{_, _}")),
+        };
+        match forms {
+            etf::Term::List(_) => (),
+            etf::Term::Atom(ref atom) if atom.name == "none" => {
+                return Err(FromBeamError::NoDebugInfo)
+            }
+            other => return Err(FromBeamError::unexpected_term(other, "[_] | none")),
+        }
+
+        let code = etf::Term::from(etf::Tuple::from(vec![
+            etf::Term::from(etf::Atom::from("raw_abstract_v1")),
+            forms,
+        ]));
         Ok(AbstractCode { code })
     }
+
     pub fn to_forms(&self) -> FromBeamResult<Vec<form::Form>> {
         let (_, forms) = self
             .code
             .as_match(("raw_abstract_v1", VarList(to!(form::Form))))?;
         Ok(forms)
     }
+
+    /// Renders the abstract forms as Erlang terms, one per form and each terminated by `.`
+    ///
+    /// This is the same textual format consumed by frontends that read `.abstr` files, i.e. the
+    /// output of `io:format("~p.~n", [Form])` for each form.
+    pub fn to_source(&self) -> FromBeamResult<String> {
+        let (_, forms) = self
+            .code
+            .as_match(("raw_abstract_v1", VarList(any())))?;
+
+        let mut source = String::new();
+        for form in forms.iter() {
+            write_term(&mut source, form);
+            source.push_str(".\n");
+        }
+        Ok(source)
+    }
+}
+
+/// Writes `term` in Erlang syntax.
+///
+/// `etf::Term`'s `Display` implementation is close to Erlang syntax, except for floats, which drop
+/// the fractional part when it is zero and so would be read back as integers.
+fn write_term(out: &mut String, term: &etf::Term) {
+    use std::fmt::Write;
+
+    fn write_elements(out: &mut String, elements: &[etf::Term]) {
+        for (i, element) in elements.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            write_term(out, element);
+        }
+    }
+
+    match term {
+        etf::Term::Float(etf::Float { value }) => {
+            let formatted = format!("{:?}", value);
+            match formatted.find('e') {
+                // Erlang requires a fractional part before the exponent
+                Some(index) if !formatted[..index].contains('.') => {
+                    out.push_str(&formatted[..index]);
+                    out.push_str(".0");
+                    out.push_str(&formatted[index..]);
+                }
+                _ => out.push_str(&formatted),
+            }
+        }
+        etf::Term::List(etf::List { elements }) => {
+            out.push('[');
+            write_elements(out, elements);
+            out.push(']');
+        }
+        etf::Term::ImproperList(etf::ImproperList { elements, last }) => {
+            out.push('[');
+            write_elements(out, elements);
+            out.push('|');
+            write_term(out, last);
+            out.push(']');
+        }
+        etf::Term::Tuple(etf::Tuple { elements }) => {
+            out.push('{');
+            write_elements(out, elements);
+            out.push('}');
+        }
+        etf::Term::Map(etf::Map { entries }) => {
+            out.push_str("#{");
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                write_term(out, key);
+                out.push_str("=>");
+                write_term(out, value);
+            }
+            out.push('}');
+        }
+        other => write!(out, "{}", other).unwrap(),
+    }
 }
 
 trait FromTerm<'a> {
@@ -9,3 +9,13 @@ fn it_works() {
         })
         .unwrap();
 }
+
+#[test]
+fn abstract_code_to_source() {
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    let code = AbstractCode::from_beam_file("tests/testdata/ast/test.beam").unwrap();
+    let source = code.to_source().unwrap();
+    assert!(source.starts_with("{'attribute',"));
+    assert_eq!(code.to_forms().unwrap().len(), source.matches(".\n").count());
+}
@@ -180,6 +180,8 @@ impl ModuleFunctionArity {
             ident.to_string()
         } else if let Ok(_) = input.parse::<Token![loop]>() {
             "loop".to_string()
+        } else if let Ok(_) = input.parse::<Token![match]>() {
+            "match".to_string()
         } else if let Ok(_) = input.parse::<Token![self]>() {
             "self".to_string()
         } else if let Ok(_) = input.parse::<Token![*]>() {
@@ -0,0 +1,26 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::application;
+
+/// Returns `{ok, Value}` for the `Par` (`key`) in the environment of `application` loaded from
+/// `sys.config`, or `undefined`
+#[native_implemented::function(application:get_env/2)]
+pub fn result(process: &Process, application: Term, key: Term) -> exception::Result<Term> {
+    let application_atom = term_try_into_atom!(application)?;
+    let key_atom = term_try_into_atom!(key)?;
+
+    match application::get_env(application_atom, key_atom) {
+        Some(value) => {
+            let value_term = value.to_term(process);
+
+            Ok(process.tuple_from_slice(&[atom!("ok"), value_term]))
+        }
+        None => Ok(atom!("undefined")),
+    }
+}
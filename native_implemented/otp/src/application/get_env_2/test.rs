@@ -0,0 +1,79 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::application::{get_env_2, get_env_3};
+use crate::runtime::application;
+use crate::test::with_process;
+use lumen_rt_core::config::sys_config;
+
+#[test]
+fn without_atom_application_errors_badarg() {
+    with_process(|process| {
+        let application = process.integer(1);
+
+        assert_badarg!(
+            get_env_2::result(process, application, atom!("key")),
+            "application (1) is not an atom"
+        );
+    });
+}
+
+#[test]
+fn with_loaded_key_returns_ok_tuple_with_value() {
+    with_process(|process| {
+        application::load(
+            sys_config::parse(
+                r#"
+                %% Comments are ignored
+                [{get_env_2_loaded, [{port, 8080},
+                                     {name, "lumen"},
+                                     {bin, <<"lumen">>},
+                                     {pair, {'quoted atom', -16#ff}}]}].
+                "#,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(
+            get_env_2::result(process, atom!("get_env_2_loaded"), atom!("port")),
+            Ok(process.tuple_from_slice(&[atom!("ok"), process.integer(8080)]))
+        );
+        assert_eq!(
+            get_env_2::result(process, atom!("get_env_2_loaded"), atom!("name")),
+            Ok(process.tuple_from_slice(&[atom!("ok"), process.charlist_from_str("lumen")]))
+        );
+        assert_eq!(
+            get_env_2::result(process, atom!("get_env_2_loaded"), atom!("bin")),
+            Ok(process.tuple_from_slice(&[atom!("ok"), process.binary_from_str("lumen")]))
+        );
+        assert_eq!(
+            get_env_3::result(
+                process,
+                atom!("get_env_2_loaded"),
+                atom!("pair"),
+                atom!("default")
+            ),
+            Ok(process
+                .tuple_from_slice(&[Atom::str_to_term("quoted atom"), process.integer(-255)]))
+        );
+    });
+}
+
+#[test]
+fn without_loaded_key_returns_undefined() {
+    with_process(|process| {
+        assert_eq!(
+            get_env_2::result(process, atom!("get_env_2_unloaded"), atom!("key")),
+            Ok(atom!("undefined"))
+        );
+        assert_eq!(
+            get_env_3::result(
+                process,
+                atom!("get_env_2_unloaded"),
+                atom!("key"),
+                atom!("default")
+            ),
+            Ok(atom!("default"))
+        );
+    });
+}
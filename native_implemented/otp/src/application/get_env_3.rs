@@ -0,0 +1,23 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::application;
+
+/// Returns the value of `Par` (`key`) in the environment of `application` loaded from
+/// `sys.config`, or `default`
+#[native_implemented::function(application:get_env/3)]
+pub fn result(
+    process: &Process,
+    application: Term,
+    key: Term,
+    default: Term,
+) -> exception::Result<Term> {
+    let application_atom = term_try_into_atom!(application)?;
+    let key_atom = term_try_into_atom!(key)?;
+
+    match application::get_env(application_atom, key_atom) {
+        Some(value) => Ok(value.to_term(process)),
+        None => Ok(default),
+    }
+}
@@ -2,6 +2,9 @@
 
 pub mod abs_1;
 pub mod add_2;
+pub mod adler32_1;
+pub mod adler32_2;
+pub mod adler32_combine_3;
 pub mod and_2;
 pub mod andalso_2;
 pub mod append_element_2;
@@ -41,6 +44,9 @@ pub mod ceil_1;
 mod charlist_to_string;
 pub mod concatenate_2;
 pub mod convert_time_unit_3;
+pub mod crc32_1;
+pub mod crc32_2;
+pub mod crc32_combine_3;
 pub mod date_0;
 pub mod delete_element_2;
 pub mod demonitor_1;
@@ -54,6 +60,9 @@ pub mod erase_1;
 pub mod error_1;
 pub mod error_2;
 pub mod exit_1;
+pub mod exit_2;
+pub mod external_size_1;
+pub mod external_size_2;
 pub mod float_1;
 pub mod float_to_binary_1;
 pub mod float_to_binary_2;
@@ -62,6 +71,9 @@ pub mod float_to_list_2;
 mod float_to_string;
 pub mod floor_1;
 pub mod function_exported_3;
+pub mod garbage_collect_0;
+pub mod garbage_collect_1;
+pub mod garbage_collect_2;
 pub mod get_0;
 pub mod get_1;
 pub mod get_keys_0;
@@ -69,7 +81,12 @@ pub mod get_keys_1;
 pub mod get_stacktrace_0;
 pub mod group_leader_0;
 pub mod group_leader_2;
+pub mod halt_0;
+pub mod halt_1;
+pub mod halt_2;
+mod hash;
 pub mod hd_1;
+pub mod hibernate_3;
 pub mod insert_element_3;
 pub mod integer_to_binary_1;
 pub mod integer_to_binary_2;
@@ -123,6 +140,9 @@ pub mod make_tuple_3;
 pub mod map_get_2;
 pub mod map_size_1;
 pub mod max_2;
+pub mod md5_1;
+pub mod memory_0;
+pub mod memory_1;
 pub mod min_2;
 pub mod module_loaded_1;
 pub mod monitor_2;
@@ -136,9 +156,18 @@ pub mod not_1;
 pub mod now_0;
 pub mod number_or_badarith_1;
 mod number_to_integer;
+pub mod open_port_2;
 pub mod or_2;
 pub mod orelse_2;
+pub mod phash2_1;
+pub mod phash2_2;
+pub mod phash_2;
+pub mod port_close_1;
+pub mod port_command_2;
+pub mod port_info_1;
+pub mod port_info_2;
 pub mod process_flag_2;
+pub mod process_info_1;
 pub mod process_info_2;
 pub mod put_2;
 pub mod raise_3;
@@ -147,6 +176,7 @@ pub mod read_timer_2;
 pub mod register_2;
 pub mod registered_0;
 pub mod rem_2;
+pub mod resume_process_1;
 pub mod round_1;
 pub mod self_0;
 pub mod send_2;
@@ -173,22 +203,28 @@ pub mod spawn_opt_4;
 pub mod split_binary_2;
 pub mod start_timer_3;
 pub mod start_timer_4;
+pub mod statistics_1;
 mod string_to_float;
 mod string_to_integer;
 pub mod subtract_2;
 pub mod subtract_list_2;
+pub mod suspend_process_1;
+pub mod suspend_process_2;
 pub mod system_flag_2;
 pub mod system_info_1;
 pub mod system_time_0;
 pub mod system_time_1;
 mod term_to_binary;
 pub mod term_to_binary_1;
+pub mod term_to_binary_2;
 pub mod throw_1;
 pub mod time_0;
 pub mod time_offset_0;
 pub mod time_offset_1;
 pub mod timestamp_0;
 pub mod tl_1;
+pub mod trace_3;
+pub mod trace_pattern_3;
 pub mod trunc_1;
 pub mod tuple_size_1;
 pub mod tuple_to_list_1;
@@ -200,6 +236,7 @@ pub mod unlink_1;
 pub mod unregister_1;
 pub mod whereis_1;
 pub mod xor_2;
+pub mod yield_0;
 
 use std::convert::TryInto;
 use std::sync::Arc;
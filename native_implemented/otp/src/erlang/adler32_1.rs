@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::adler32_2::adler32;
+
+#[native_implemented::function(erlang:adler32/1)]
+pub fn result(process: &Process, data: Term) -> exception::Result<Term> {
+    let checksum = adler32(1, data)?;
+
+    Ok(process.integer(checksum as u64))
+}
@@ -0,0 +1,32 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use adler32::RollingAdler32;
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::iolist_or_binary;
+
+/// Continues computing the Adler-32 checksum of `old_adler` with `data`.
+#[native_implemented::function(erlang:adler32/2)]
+pub fn result(process: &Process, old_adler: Term, data: Term) -> exception::Result<Term> {
+    let old_adler_u32: u32 = old_adler
+        .try_into()
+        .with_context(|| format!("old_adler ({}) is not a 32-bit unsigned integer", old_adler))?;
+    let checksum = adler32(old_adler_u32, data)?;
+
+    Ok(process.integer(checksum as u64))
+}
+
+pub(in crate::erlang) fn adler32(old_adler: u32, data: Term) -> exception::Result<u32> {
+    let bytes = iolist_or_binary::iolist_or_binary_to_bytes("data", data)?;
+    let mut rolling_adler32 = RollingAdler32::from_value(old_adler);
+    rolling_adler32.update_buffer(&bytes);
+
+    Ok(rolling_adler32.hash())
+}
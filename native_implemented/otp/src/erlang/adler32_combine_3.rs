@@ -0,0 +1,75 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Combines the Adler-32 checksums of two adjoining blocks of data into the checksum of the
+/// combined block, as zlib's `adler32_combine` does.
+#[native_implemented::function(erlang:adler32_combine/3)]
+pub fn result(
+    process: &Process,
+    first_adler: Term,
+    second_adler: Term,
+    second_size: Term,
+) -> exception::Result<Term> {
+    let first_adler_u32: u32 = first_adler.try_into().with_context(|| {
+        format!(
+            "first_adler ({}) is not a 32-bit unsigned integer",
+            first_adler
+        )
+    })?;
+    let second_adler_u32: u32 = second_adler.try_into().with_context(|| {
+        format!(
+            "second_adler ({}) is not a 32-bit unsigned integer",
+            second_adler
+        )
+    })?;
+    let second_size_u64: u64 = second_size.try_into().with_context(|| {
+        format!(
+            "second_size ({}) is not a non-negative integer",
+            second_size
+        )
+    })?;
+
+    let checksum = adler32_combine(first_adler_u32, second_adler_u32, second_size_u64);
+
+    Ok(process.integer(checksum as u64))
+}
+
+// Private
+
+/// The largest prime smaller than 2^16
+const BASE: u32 = 65521;
+
+fn adler32_combine(first_adler: u32, second_adler: u32, second_size: u64) -> u32 {
+    let remainder = (second_size % (BASE as u64)) as u32;
+    let mut sum1 = first_adler & 0xffff;
+    let mut sum2 = (remainder * sum1) % BASE;
+
+    sum1 += (second_adler & 0xffff) + BASE - 1;
+    sum2 += ((first_adler >> 16) & 0xffff) + ((second_adler >> 16) & 0xffff) + BASE - remainder;
+
+    if sum1 >= BASE {
+        sum1 -= BASE;
+    }
+
+    if sum1 >= BASE {
+        sum1 -= BASE;
+    }
+
+    if sum2 >= (BASE << 1) {
+        sum2 -= BASE << 1;
+    }
+
+    if sum2 >= BASE {
+        sum2 -= BASE;
+    }
+
+    sum1 | (sum2 << 16)
+}
@@ -5,6 +5,7 @@ use liblumen_core::sys::dynamic_call::DynamicCallee;
 use liblumen_alloc::erts::apply::find_symbol;
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::{Arity, ModuleFunctionArity};
 
@@ -19,8 +20,16 @@ extern "Rust" {
     ) -> Term;
 }
 
+/// As in the BEAM, when `module:function/arity` is not exported, but the process's error handler
+/// module exports `undefined_function/3`, it is called with `module`, `function` and `arguments`
+/// instead of raising `undef`.
 #[native_implemented::function(erlang:apply/3)]
-fn result(module: Term, function: Term, arguments: Term) -> exception::Result<Term> {
+fn result(
+    process: &Process,
+    module: Term,
+    function: Term,
+    arguments: Term,
+) -> exception::Result<Term> {
     let module_atom = term_try_into_atom!(module)?;
     let function_atom = term_try_into_atom!(function)?;
     let argument_vec = arguments_term_to_vec(arguments)?;
@@ -35,6 +44,22 @@ fn result(module: Term, function: Term, arguments: Term) -> exception::Result<Te
     match find_symbol(&module_function_arity) {
         Some(callee) => Ok(unsafe { runtime_apply_3(module_function_arity, callee, argument_vec) }),
         None => {
+            let error_handler_module_function_arity = ModuleFunctionArity {
+                module: process.error_handler(),
+                function: Atom::from_str("undefined_function"),
+                arity: 3,
+            };
+
+            if let Some(callee) = find_symbol(&error_handler_module_function_arity) {
+                return Ok(unsafe {
+                    runtime_apply_3(
+                        error_handler_module_function_arity,
+                        callee,
+                        vec![module, function, arguments],
+                    )
+                });
+            }
+
             let trace = Trace::capture();
             trace.set_top_frame(&module_function_arity, argument_vec.as_slice());
             Err(exception::undef(
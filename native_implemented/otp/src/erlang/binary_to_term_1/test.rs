@@ -1,7 +1,11 @@
 use proptest::strategy::Just;
 
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
 use crate::erlang::binary_to_term_1::result;
-use crate::test::strategy;
+use crate::runtime::distribution::nodes;
+use crate::test::{strategy, with_process};
 
 #[test]
 fn without_binary_errors_badarg() {
@@ -23,6 +27,52 @@ fn without_binary_errors_badarg() {
     );
 }
 
+#[test]
+fn with_compressed_binary_returns_term() {
+    with_process(|process| {
+        let binary = process.binary_from_bytes(&compressed_bytes(105));
+
+        assert_eq!(
+            result(process, binary),
+            Ok(process.binary_from_bytes(&[0; 100]))
+        );
+    });
+}
+
+#[test]
+fn with_compressed_binary_inflating_past_uncompressed_size_errors_badarg() {
+    with_process(|process| {
+        let binary = process.binary_from_bytes(&compressed_bytes(5));
+
+        assert_badarg!(
+            result(process, binary),
+            "compressed term could not be inflated to its uncompressed size (5 bytes)"
+        );
+    });
+}
+
+#[test]
+fn with_new_port_on_another_node_returns_external_port() {
+    with_process(|process| {
+        let name = "port@external";
+        let arc_node = nodes::atom_to_or_insert_arc_node(Atom::from_str(name), 0);
+        let mut byte_vec = vec![131, 89, 119, name.len() as u8];
+        byte_vec.extend_from_slice(name.as_bytes());
+        // ID
+        byte_vec.extend_from_slice(&[0, 0, 0, 5]);
+        // Creation
+        byte_vec.extend_from_slice(&[0, 0, 0, 0]);
+        let binary = process.binary_from_bytes(&byte_vec);
+
+        let port = unsafe { Port::from_raw(5) };
+
+        assert_eq!(
+            result(process, binary),
+            Ok(ExternalPort::new(arc_node, port).clone_to_process(process))
+        );
+    });
+}
+
 // `with_binary_encoding_atom_returns_atom` in integration tests
 // `with_binary_encoding_empty_list_returns_empty_list` in integration tests
 // `with_binary_encoding_list_returns_list` in integration tests
@@ -35,3 +85,14 @@ fn without_binary_errors_badarg() {
 // `with_binary_encoding_small_big_integer_returns_big_integer` in integration tests
 // `with_binary_encoding_bit_string_returns_subbinary` in integration tests
 // `with_binary_encoding_small_atom_utf8_returns_atom` in integration tests
+
+/// `<<0:800>>` compressed with its `uncompressed_size` header set to `uncompressed_size`, which is
+/// 105 bytes when correct
+fn compressed_bytes(uncompressed_size: u8) -> Vec<u8> {
+    let mut byte_vec = vec![131, 80, 0, 0, 0, uncompressed_size];
+    byte_vec.extend_from_slice(&[
+        120, 156, 203, 101, 96, 96, 72, 97, 160, 3, 0, 0, 84, 146, 0, 210,
+    ]);
+
+    byte_vec
+}
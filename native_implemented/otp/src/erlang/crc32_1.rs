@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::crc32_2::crc32;
+
+#[native_implemented::function(erlang:crc32/1)]
+pub fn result(process: &Process, data: Term) -> exception::Result<Term> {
+    let crc = crc32(0, data)?;
+
+    Ok(process.integer(crc as u64))
+}
@@ -0,0 +1,57 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::erlang::crc32_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_iolist_or_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list_or_bitstring(arc_process),
+            )
+        },
+        |(arc_process, data)| {
+            prop_assert_badarg!(result(&arc_process, data), format!("data ({})", data));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_iolist_returns_same_checksum_as_binary() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::byte_vec()),
+        |(arc_process, byte_vec)| {
+            let binary = arc_process.binary_from_bytes(&byte_vec);
+            let iolist = arc_process.list_from_slice(&[binary]);
+
+            prop_assert_eq!(result(&arc_process, iolist), result(&arc_process, binary));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_returns_0() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.binary_from_bytes(&[])),
+            Ok(process.integer(0))
+        );
+    });
+}
+
+#[test]
+fn with_binary_returns_zlib_checksum() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"The quick brown fox jumps over the lazy dog");
+
+        assert_eq!(result(process, data), Ok(process.integer(0x414f_a339)));
+    });
+}
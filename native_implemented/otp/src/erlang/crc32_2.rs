@@ -0,0 +1,32 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use crc32fast::Hasher;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::iolist_or_binary;
+
+/// Continues computing the CRC-32 checksum of `old_crc` with `data`.
+#[native_implemented::function(erlang:crc32/2)]
+pub fn result(process: &Process, old_crc: Term, data: Term) -> exception::Result<Term> {
+    let old_crc_u32: u32 = old_crc
+        .try_into()
+        .with_context(|| format!("old_crc ({}) is not a 32-bit unsigned integer", old_crc))?;
+    let crc = crc32(old_crc_u32, data)?;
+
+    Ok(process.integer(crc as u64))
+}
+
+pub(in crate::erlang) fn crc32(old_crc: u32, data: Term) -> exception::Result<u32> {
+    let bytes = iolist_or_binary::iolist_or_binary_to_bytes("data", data)?;
+    let mut hasher = Hasher::new_with_initial(old_crc);
+    hasher.update(&bytes);
+
+    Ok(hasher.finalize())
+}
@@ -0,0 +1,47 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::erlang::{crc32_1, crc32_2};
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_32_bit_unsigned_integer_old_crc_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[]);
+
+        for old_crc in &[process.integer(-1), process.integer(1_u64 << 32)] {
+            assert_badarg!(
+                crc32_2::result(process, *old_crc, data),
+                format!("old_crc ({}) is not a 32-bit unsigned integer", old_crc)
+            );
+        }
+    });
+}
+
+#[test]
+fn continues_checksum_of_previous_data() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::byte_vec(),
+                strategy::byte_vec(),
+            )
+        },
+        |(arc_process, first_byte_vec, second_byte_vec)| {
+            let first = arc_process.binary_from_bytes(&first_byte_vec);
+            let second = arc_process.binary_from_bytes(&second_byte_vec);
+            let both = arc_process.list_from_slice(&[first, second]);
+
+            let old_crc = crc32_1::result(&arc_process, first).unwrap();
+
+            prop_assert_eq!(
+                crc32_2::result(&arc_process, old_crc, second),
+                crc32_1::result(&arc_process, both)
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,124 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Combines the CRC-32 checksums of two adjoining blocks of data into the checksum of the
+/// combined block, as zlib's `crc32_combine` does.
+#[native_implemented::function(erlang:crc32_combine/3)]
+pub fn result(
+    process: &Process,
+    first_crc: Term,
+    second_crc: Term,
+    second_size: Term,
+) -> exception::Result<Term> {
+    let first_crc_u32: u32 = first_crc
+        .try_into()
+        .with_context(|| format!("first_crc ({}) is not a 32-bit unsigned integer", first_crc))?;
+    let second_crc_u32: u32 = second_crc.try_into().with_context(|| {
+        format!(
+            "second_crc ({}) is not a 32-bit unsigned integer",
+            second_crc
+        )
+    })?;
+    let second_size_u64: u64 = second_size.try_into().with_context(|| {
+        format!(
+            "second_size ({}) is not a non-negative integer",
+            second_size
+        )
+    })?;
+
+    let crc = crc32_combine(first_crc_u32, second_crc_u32, second_size_u64);
+
+    Ok(process.integer(crc as u64))
+}
+
+// Private
+
+/// The reversed CRC-32 polynomial
+const POLYNOMIAL: u32 = 0xedb8_8320;
+
+/// Appends `second_size` zero bytes to `first_crc` by repeatedly squaring the matrix of the
+/// operator that appends a single zero bit, then `xor`s in `second_crc`.
+fn crc32_combine(first_crc: u32, second_crc: u32, second_size: u64) -> u32 {
+    if second_size == 0 {
+        return first_crc;
+    }
+
+    let mut crc = first_crc;
+    let mut even = [0; 32];
+    let mut odd = [0; 32];
+
+    // The operator for one zero bit
+    odd[0] = POLYNOMIAL;
+
+    for (n, row) in odd.iter_mut().enumerate().skip(1) {
+        *row = 1 << (n - 1);
+    }
+
+    // The operator for two zero bits
+    gf2_matrix_square(&mut even, &odd);
+    // The operator for four zero bits
+    gf2_matrix_square(&mut odd, &even);
+
+    let mut len = second_size;
+
+    // The first square gives the operator for one zero byte, eight zero bits
+    loop {
+        gf2_matrix_square(&mut even, &odd);
+
+        if len & 1 == 1 {
+            crc = gf2_matrix_times(&even, crc);
+        }
+
+        len >>= 1;
+
+        if len == 0 {
+            break;
+        }
+
+        gf2_matrix_square(&mut odd, &even);
+
+        if len & 1 == 1 {
+            crc = gf2_matrix_times(&odd, crc);
+        }
+
+        len >>= 1;
+
+        if len == 0 {
+            break;
+        }
+    }
+
+    crc ^ second_crc
+}
+
+fn gf2_matrix_square(square: &mut [u32; 32], matrix: &[u32; 32]) {
+    for (n, row) in square.iter_mut().enumerate() {
+        *row = gf2_matrix_times(matrix, matrix[n]);
+    }
+}
+
+fn gf2_matrix_times(matrix: &[u32; 32], vector: u32) -> u32 {
+    let mut sum = 0;
+    let mut vector = vector;
+    let mut rows = matrix.iter();
+
+    while vector != 0 {
+        let row = rows.next().unwrap();
+
+        if vector & 1 == 1 {
+            sum ^= row;
+        }
+
+        vector >>= 1;
+    }
+
+    sum
+}
@@ -0,0 +1,51 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::erlang::{crc32_1, crc32_combine_3};
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn returns_checksum_of_combined_data() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::byte_vec(),
+                strategy::byte_vec(),
+            )
+        },
+        |(arc_process, first_byte_vec, second_byte_vec)| {
+            let first = arc_process.binary_from_bytes(&first_byte_vec);
+            let second = arc_process.binary_from_bytes(&second_byte_vec);
+            let both = arc_process.list_from_slice(&[first, second]);
+
+            let first_crc = crc32_1::result(&arc_process, first).unwrap();
+            let second_crc = crc32_1::result(&arc_process, second).unwrap();
+            let second_size = arc_process.integer(second_byte_vec.len());
+
+            prop_assert_eq!(
+                crc32_combine_3::result(&arc_process, first_crc, second_crc, second_size),
+                crc32_1::result(&arc_process, both)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_negative_second_size_errors_badarg() {
+    with_process(|process| {
+        let crc = process.integer(0);
+        let second_size = process.integer(-1);
+
+        assert_badarg!(
+            crc32_combine_3::result(process, crc, crc, second_size),
+            format!(
+                "second_size ({}) is not a non-negative integer",
+                second_size
+            )
+        );
+    });
+}
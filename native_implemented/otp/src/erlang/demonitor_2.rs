@@ -6,8 +6,11 @@ use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::distribution;
+use crate::runtime::port;
 use crate::runtime::process::monitor::is_down;
 use crate::runtime::registry::pid_to_process;
+use crate::runtime::time::offset;
 
 use crate::erlang::demonitor_2::options::Options;
 
@@ -26,7 +29,7 @@ pub(in crate::erlang) fn demonitor(
     reference: &Reference,
     Options { flush, info }: Options,
 ) -> exception::Result<Term> {
-    match monitoring_process.demonitor(reference) {
+    let demonitored = match monitoring_process.demonitor(reference) {
         Some(monitored_pid) => {
             match pid_to_process(&monitored_pid) {
                 Some(monitored_arc_proces) => match monitored_arc_proces.demonitored(reference) {
@@ -36,32 +39,27 @@ pub(in crate::erlang) fn demonitor(
                 None => (),
             }
 
-            if flush {
-                let flushed = self::flush(monitoring_process, reference);
-
-                if info && flushed {
-                    Ok(false.into())
-                } else {
-                    Ok(true.into())
-                }
-            } else {
-                Ok(true.into())
-            }
+            true
         }
         None => {
-            if info {
-                Ok(false.into())
-            } else {
-                Ok(true.into())
-            }
+            offset::demonitor(monitoring_process.pid(), reference)
+                || port::demonitor(monitoring_process.pid(), reference)
+                || distribution::demonitor(reference)
         }
+    };
+
+    let flushed = flush && self::flush(monitoring_process, reference);
+
+    if info {
+        Ok((demonitored && !flushed).into())
+    } else {
+        Ok(true.into())
     }
 }
 
 fn flush(monitoring_process: &Process, reference: &Reference) -> bool {
     monitoring_process
-        .mailbox
-        .lock()
+        .acquire_mailbox()
         .borrow_mut()
         .flush(|message| is_down(message, reference), monitoring_process)
 }
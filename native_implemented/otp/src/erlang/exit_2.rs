@@ -0,0 +1,75 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, exit};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::registry::pid_to_process;
+use crate::runtime::{distribution, port, process};
+
+/// Sends an exit signal with `reason` to `pid_or_port`.  Signals to the calling process itself
+/// exit it immediately, so that it does not return to the caller.
+#[native_implemented::function(erlang:exit/2)]
+pub fn result(process: &Process, pid_or_port: Term, reason: Term) -> exception::Result<Term> {
+    match pid_or_port.decode()? {
+        TypedTerm::Pid(pid) => {
+            if pid == process.pid() {
+                exit_self(process, reason)
+            } else {
+                if let Some(pid_arc_process) = pid_to_process(&pid) {
+                    process::exit_signal(process, process.pid_term(), &pid_arc_process, reason);
+                }
+
+                Ok(true.into())
+            }
+        }
+        TypedTerm::ExternalPid(external_pid) => {
+            distribution::exit2(process, &external_pid, reason);
+
+            Ok(true.into())
+        }
+        // Ports act as if they are linked to the caller, so only `normal` is ignored
+        TypedTerm::Port(port) => {
+            if reason != atom!("normal") {
+                port::close(port, reason);
+            }
+
+            Ok(true.into())
+        }
+        _ => Err(TypeError)
+            .context(format!(
+                "pid_or_port ({}) is neither a pid nor a port",
+                pid_or_port
+            ))
+            .map_err(From::from),
+    }
+}
+
+fn exit_self(process: &Process, reason: Term) -> exception::Result<Term> {
+    if reason == atom!("kill") {
+        Err(exit(
+            atom!("killed"),
+            Trace::capture(),
+            Some(anyhow!("killed by exit signal to self").into()),
+        )
+        .into())
+    } else if process.traps_exit() {
+        let message = process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason]);
+        process.send_from_self(message);
+
+        Ok(true.into())
+    } else {
+        // unlike signals from other processes, `normal` exits the process when it signals itself
+        Err(exit(
+            reason,
+            Trace::capture(),
+            Some(anyhow!("exit signal to self").into()),
+        )
+        .into())
+    }
+}
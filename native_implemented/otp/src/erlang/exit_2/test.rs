@@ -0,0 +1,120 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::exit;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::exit_2::result;
+use crate::test;
+use crate::test::{has_message, with_process};
+
+#[test]
+fn without_pid_or_port_errors_badarg() {
+    with_process(|process| {
+        let pid_or_port = atom!("not_a_pid_or_port");
+
+        assert_badarg!(
+            result(process, pid_or_port, atom!("normal")),
+            format!("pid_or_port ({}) is neither a pid nor a port", pid_or_port)
+        );
+    });
+}
+
+#[test]
+fn with_non_existent_pid_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Pid::next_term(), atom!("reason")),
+            Ok(true.into())
+        );
+    });
+}
+
+#[test]
+fn with_self_trapping_exits_sends_exit_message_and_returns_true() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        let reason = atom!("reason");
+
+        assert_eq!(result(process, process.pid_term(), reason), Ok(true.into()));
+
+        assert_has_message!(
+            process,
+            process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+        );
+    });
+}
+
+#[test]
+fn with_self_not_trapping_exits_exits_even_with_normal() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.pid_term(), atom!("normal")),
+            Err(exit(atom!("normal"), Trace::capture(), None).into())
+        );
+    });
+}
+
+#[test]
+fn with_other_process_trapping_exits_sends_exit_message() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+
+        let reason = atom!("reason");
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+        assert_has_message!(
+            &other_arc_process,
+            process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+        );
+    });
+}
+
+#[test]
+fn with_other_process_not_trapping_exits_ignores_normal() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("normal")),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_process_not_trapping_exits_exits_it_with_reason() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("reason")),
+            Ok(true.into())
+        );
+
+        assert!(other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_process_trapping_exits_and_kill_exits_it() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("kill")),
+            Ok(true.into())
+        );
+
+        assert!(other_arc_process.is_exiting());
+    });
+}
@@ -0,0 +1,20 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::distribution::external_term_format::encode;
+
+/// Returns the number of bytes in the uncompressed encoding of `term` by `term_to_binary/1`.
+#[native_implemented::function(erlang:external_size/1)]
+pub fn result(process: &Process, term: Term) -> exception::Result<Term> {
+    external_size(term).map(|size| process.integer(size))
+}
+
+pub(in crate::erlang) fn external_size(term: Term) -> exception::Result<usize> {
+    let byte_vec = encode::term_to_byte_vec(term)?;
+
+    Ok(byte_vec.len())
+}
@@ -0,0 +1,35 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::external_size_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn returns_byte_size_of_term_to_binary() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            let binary = crate::erlang::term_to_binary_1::result(&arc_process, term).unwrap();
+
+            prop_assert_eq!(
+                result(&arc_process, term),
+                crate::erlang::byte_size_1::result(&arc_process, binary)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_atom_returns_size_of_version_tag_and_small_atom_utf8() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("a")),
+            Ok(process.integer(5))
+        );
+    });
+}
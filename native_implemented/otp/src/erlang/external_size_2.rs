@@ -0,0 +1,74 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::external_size_1::external_size;
+
+const SUPPORTED_OPTIONS_CONTEXT: &str =
+    "supported options are {minor_version, Version} where Version is 0-2";
+
+/// The encoding of terms does not depend on the minor version, so the options are only
+/// validated.
+#[native_implemented::function(erlang:external_size/2)]
+pub fn result(process: &Process, term: Term, options: Term) -> exception::Result<Term> {
+    validate_options(options).context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+    external_size(term).map(|size| process.integer(size))
+}
+
+// Private
+
+fn validate_options(options: Term) -> anyhow::Result<()> {
+    let mut options_term = options;
+
+    loop {
+        match options_term.decode().unwrap() {
+            TypedTerm::Nil => return Ok(()),
+            TypedTerm::List(cons) => {
+                validate_option(cons.head)?;
+                options_term = cons.tail;
+            }
+            _ => return Err(ImproperListError.into()),
+        }
+    }
+}
+
+fn validate_option(option: Term) -> anyhow::Result<()> {
+    let tuple: Boxed<Tuple> = option
+        .try_into()
+        .with_context(|| format!("option ({}) is not a tuple", option))?;
+
+    if tuple.len() == 2 {
+        let key: Atom = tuple[0]
+            .try_into()
+            .with_context(|| format!("option ({}) key is not an atom", option))?;
+
+        match key.name() {
+            "minor_version" => {
+                let version: u8 = tuple[1]
+                    .try_into()
+                    .with_context(|| format!("option ({}) Version is not an integer", option))?;
+
+                if version <= 2 {
+                    Ok(())
+                } else {
+                    Err(anyhow!("option ({}) Version is not 0-2", option))
+                }
+            }
+            name => Err(anyhow!(
+                "option ({}) key ({}) is not supported",
+                option,
+                name
+            )),
+        }
+    } else {
+        Err(anyhow!("option ({}) is not a 2-tuple", option))
+    }
+}
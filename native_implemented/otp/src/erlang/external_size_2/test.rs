@@ -0,0 +1,76 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::external_size_2::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn with_supported_minor_version_returns_external_size() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                (0_u8..=2).prop_map(move |version| {
+                    arc_process.list_from_slice(&[arc_process.tuple_from_slice(&[
+                        Atom::str_to_term("minor_version"),
+                        arc_process.integer(version),
+                    ])])
+                }),
+            )
+        },
+        |(arc_process, term, options)| {
+            prop_assert_eq!(
+                result(&arc_process, term, options),
+                crate::erlang::external_size_1::result(&arc_process, term)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_options_returns_external_size() {
+    with_process(|process| {
+        let term = Atom::str_to_term("a");
+
+        assert_eq!(
+            result(process, term, Term::NIL),
+            crate::erlang::external_size_1::result(process, term)
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_minor_version_errors_badarg() {
+    with_process(|process| {
+        let options =
+            process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[Atom::str_to_term("minor_version"), process.integer(3)])]);
+
+        assert_badarg!(
+            result(process, Atom::str_to_term("a"), options),
+            "Version is not 0-2"
+        );
+    });
+}
+
+#[test]
+fn with_unknown_option_errors_badarg() {
+    with_process(|process| {
+        let options =
+            process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[Atom::str_to_term("compressed"), process.integer(1)])]);
+
+        assert_badarg!(
+            result(process, Atom::str_to_term("a"), options),
+            "key (compressed) is not supported"
+        );
+    });
+}
@@ -0,0 +1,12 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// The collection happens as soon as the process yields to the scheduler, when all of its roots
+/// are on its stack.
+#[native_implemented::function(erlang:garbage_collect/0)]
+pub fn result(process: &Process) -> Term {
+    process.force_garbage_collection(true);
+    process.exhaust_reductions();
+
+    true.into()
+}
@@ -0,0 +1,59 @@
+mod options;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::registry::pid_to_process;
+
+use crate::erlang::garbage_collect_2::options::Options;
+
+#[native_implemented::function(erlang:garbage_collect/2)]
+pub fn result(process: &Process, pid: Term, options: Term) -> exception::Result<Term> {
+    let options_options: Options = options.try_into()?;
+
+    garbage_collect(process, pid, options_options)
+}
+
+// Private
+
+/// The collection of another process happens when it is next between runs, so the result only
+/// tells whether the process was alive to be collected.
+pub(in crate::erlang) fn garbage_collect(
+    process: &Process,
+    pid: Term,
+    Options { r#async, major }: Options,
+) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid("pid", pid)?;
+
+    let collected = if pid_pid == process.pid() {
+        process.force_garbage_collection(major);
+        process.exhaust_reductions();
+
+        true
+    } else {
+        match pid_to_process(&pid_pid) {
+            Some(pid_arc_process) => {
+                pid_arc_process.force_garbage_collection(major);
+
+                true
+            }
+            None => false,
+        }
+    };
+
+    match r#async {
+        Some(request_id) => {
+            let message =
+                process.tuple_from_slice(&[atom!("garbage_collect"), request_id, collected.into()]);
+            process.send_from_self(message);
+
+            Ok(atom!("async"))
+        }
+        None => Ok(collected.into()),
+    }
+}
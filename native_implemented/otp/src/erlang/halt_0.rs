@@ -0,0 +1,8 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::sys::halt;
+
+#[native_implemented::function(erlang:halt/0)]
+pub fn result() -> Term {
+    halt::halt(0, true)
+}
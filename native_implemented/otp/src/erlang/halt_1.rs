@@ -0,0 +1,9 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_2::halt;
+
+#[native_implemented::function(erlang:halt/1)]
+pub fn result(status: Term) -> exception::Result<Term> {
+    halt(status, Default::default())
+}
@@ -0,0 +1,64 @@
+mod options;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::charlist_to_string::charlist_to_string;
+use crate::erlang::halt_2::options::Options;
+use crate::runtime::sys;
+
+#[native_implemented::function(erlang:halt/2)]
+pub fn result(status: Term, options: Term) -> exception::Result<Term> {
+    let options_options: Options = options.try_into()?;
+
+    halt(status, options_options)
+}
+
+// Private
+
+/// `status` is a non-negative exit status, `abort` to dump core, or a string slogan, which exits
+/// with status `1`.  Only returns if `status` or the options are invalid.
+pub(in crate::erlang) fn halt(status: Term, Options { flush }: Options) -> exception::Result<Term> {
+    match status.decode()? {
+        TypedTerm::SmallInteger(small_integer) => {
+            let status_isize: isize = small_integer.into();
+
+            if 0 <= status_isize {
+                // Only the low byte is available as the exit status on most platforms
+                sys::halt::halt((status_isize & 0xFF) as i32, flush)
+            } else {
+                Err(status_is_invalid(status))
+            }
+        }
+        TypedTerm::BigInteger(big_integer) => {
+            let big_int: &BigInt = big_integer.as_ref().into();
+            let (sign, bytes) = big_int.to_bytes_le();
+
+            if sign != Sign::Minus {
+                sys::halt::halt(bytes.first().copied().unwrap_or(0) as i32, flush)
+            } else {
+                Err(status_is_invalid(status))
+            }
+        }
+        TypedTerm::Atom(atom) if atom == "abort" => sys::halt::abort(),
+        TypedTerm::Nil | TypedTerm::List(_) => {
+            let slogan = charlist_to_string(status)?;
+
+            sys::halt::crash(&slogan, flush)
+        }
+        _ => Err(status_is_invalid(status)),
+    }
+}
+
+fn status_is_invalid(status: Term) -> exception::Exception {
+    anyhow!(
+        "status ({}) is not a non-negative integer, abort, or a string",
+        status
+    )
+    .into()
+}
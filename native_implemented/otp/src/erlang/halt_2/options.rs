@@ -0,0 +1,69 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::proplist::TryPropListFromTermError;
+
+pub struct Options {
+    pub flush: bool,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported option is {:flush, boolean}";
+
+impl Options {
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .map_err(|_| TryPropListFromTermError::PropertyType)?;
+
+        if tuple.len() != 2 {
+            return Err(TryPropListFromTermError::TupleNotPair.into());
+        }
+
+        let key_atom: Atom = tuple[0]
+            .try_into()
+            .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+        match key_atom.name() {
+            "flush" => {
+                self.flush = term_try_into_bool("flush", tuple[1])?;
+
+                Ok(self)
+            }
+            name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { flush: true }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
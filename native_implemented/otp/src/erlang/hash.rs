@@ -0,0 +1,103 @@
+//! The term hashes of `erlang:phash/2` and `erlang:phash2/1,2`, which must produce the same values
+//! as the BEAM, so that terms hash the same on every node.
+//!
+//! See `make_hash` and `make_hash2` in `erts/emulator/beam/utils.c`.
+mod make_hash;
+mod make_hash2;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+pub use make_hash::make_hash;
+pub use make_hash2::make_hash2;
+
+/// The largest `Range` allowed by `phash/2` and `phash2/2`.
+pub const RANGE_MAX: u64 = 1 << 32;
+
+pub fn term_try_into_range(range: Term) -> anyhow::Result<u64> {
+    let range_u64: u64 = range.try_into().with_context(|| range_context(range))?;
+
+    if 1 <= range_u64 && range_u64 <= RANGE_MAX {
+        Ok(range_u64)
+    } else {
+        Err(anyhow!(range_context(range)))
+    }
+}
+
+// Private
+
+/// The `hashpjw` hash that the BEAM atom table stores for each atom.
+///
+/// Atom names are UTF-8, but the hash was defined when they were Latin-1, so Latin-1 characters
+/// encoded as 2 bytes in UTF-8 are hashed as their single Latin-1 byte.
+fn atom_hash(atom: Atom) -> u32 {
+    let bytes = atom.name().as_bytes();
+    let mut hash: u32 = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let mut byte = bytes[index];
+        index += 1;
+
+        if index < bytes.len() && (byte & 0xFE) == 0xC2 && (bytes[index] & 0xC0) == 0x80 {
+            byte = (byte << 6) | (bytes[index] & 0x3F);
+            index += 1;
+        }
+
+        hash = (hash << 4).wrapping_add(byte as u32);
+
+        let high_nibble = hash & 0xF000_0000;
+
+        if high_nibble != 0 {
+            hash ^= high_nibble >> 24;
+            hash ^= high_nibble;
+        }
+    }
+
+    hash
+}
+
+/// The magnitude of `big_int` in 32-bit digits, least significant first, as the BEAM stores
+/// bignums on 32-bit architectures.  Hashing by 32-bit digits makes the hash the same on 32- and
+/// 64-bit architectures.
+fn big_int_u32_digits(big_int: &BigInt) -> Vec<u32> {
+    let (_, little_endian_bytes) = big_int.to_bytes_le();
+
+    little_endian_bytes
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(0, |digit, byte| (digit << 8) | (*byte as u32))
+        })
+        .collect()
+}
+
+/// The BEAM's `-0.0` and `0.0` compare exactly equal, so `-0.0` is hashed as `0.0`.
+fn float_bits(float: f64) -> u64 {
+    if float == 0.0 {
+        0.0_f64.to_bits()
+    } else {
+        float.to_bits()
+    }
+}
+
+/// The first of the 32-bit words in the `NEWER_REFERENCE_EXT` encoding of `reference`, which is the
+/// only part of a reference the BEAM hashes.
+fn reference_number(reference: &Reference) -> u32 {
+    reference.scheduler_id().into()
+}
+
+/// The value of the bits in the partial byte of a bitstring.
+fn partial_byte_value(bit_iter: Box<dyn BitIterator>) -> u8 {
+    bit_iter.fold(0, |value, bit| (value << 1) | bit)
+}
+
+fn range_context(range: Term) -> String {
+    format!("range ({}) is not an integer in 1..2^32", range)
+}
@@ -0,0 +1,259 @@
+//! The hash of the deprecated `erlang:phash/2`.
+use std::convert::TryInto;
+
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::term::closure::Definition;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::*;
+
+pub fn make_hash(term: Term) -> u32 {
+    let mut hash: u32 = 0;
+    let mut stack: Vec<Item> = vec![Item::Term(term)];
+
+    while let Some(item) = stack.pop() {
+        match item {
+            Item::Term(term) => hash = hash_term(hash, term, &mut stack),
+            Item::ListTail => hash = hash.wrapping_mul(FUNNY_NUMBER8),
+            Item::TupleEnd(arity) => {
+                hash = hash.wrapping_mul(FUNNY_NUMBER9).wrapping_add(arity);
+            }
+        }
+    }
+
+    hash
+}
+
+// Private
+
+const FUNNY_NUMBER1: u32 = 268_440_163;
+const FUNNY_NUMBER2: u32 = 268_439_161;
+const FUNNY_NUMBER3: u32 = 268_435_459;
+const FUNNY_NUMBER4: u32 = 268_436_141;
+const FUNNY_NUMBER5: u32 = 268_438_633;
+const FUNNY_NUMBER6: u32 = 268_437_017;
+const FUNNY_NUMBER8: u32 = 268_437_511;
+const FUNNY_NUMBER9: u32 = 268_439_627;
+const FUNNY_NUMBER10: u32 = 268_440_479;
+const FUNNY_NUMBER11: u32 = 268_440_577;
+const FUNNY_NUMBER12: u32 = 268_440_581;
+const FUNNY_NUMBER13: u32 = 268_440_593;
+const FUNNY_NUMBER14: u32 = 268_440_611;
+
+enum Item {
+    Term(Term),
+    /// The tail of a list has been hashed.
+    ListTail,
+    /// The elements of a tuple with the arity have been hashed.
+    TupleEnd(u32),
+}
+
+fn hash_term(hash: u32, term: Term, stack: &mut Vec<Item>) -> u32 {
+    match term.decode().unwrap() {
+        TypedTerm::Nil => hash.wrapping_mul(FUNNY_NUMBER3).wrapping_add(1),
+        TypedTerm::Atom(atom) => hash
+            .wrapping_mul(FUNNY_NUMBER1)
+            .wrapping_add(atom_hash(atom)),
+        TypedTerm::SmallInteger(small_integer) => {
+            let integer: isize = small_integer.into();
+
+            hash_big_int(hash, &BigInt::from(integer))
+        }
+        TypedTerm::BigInteger(big_integer) => {
+            let big_int: &BigInt = big_integer.as_ref().into();
+
+            hash_big_int(hash, big_int)
+        }
+        TypedTerm::Float(float) => {
+            let bits = float_bits(float.into());
+
+            hash.wrapping_mul(FUNNY_NUMBER6)
+                .wrapping_add(((bits >> 32) as u32) ^ (bits as u32))
+        }
+        TypedTerm::List(cons) => hash_list(hash, cons, stack),
+        TypedTerm::Tuple(tuple) => {
+            stack.push(Item::TupleEnd(tuple.len() as u32));
+
+            for element in tuple.iter().rev() {
+                stack.push(Item::Term(*element));
+            }
+
+            hash
+        }
+        // Maps were added after `phash/2` was deprecated, so they use the `phash2/1,2` hash
+        TypedTerm::Map(_) => hash
+            .wrapping_mul(FUNNY_NUMBER13)
+            .wrapping_add(FUNNY_NUMBER14)
+            .wrapping_add(make_hash2(term)),
+        TypedTerm::Closure(closure) => {
+            let module_hash = atom_hash(closure.module());
+
+            match closure.definition() {
+                Definition::Export { function } => hash
+                    .wrapping_mul(FUNNY_NUMBER11)
+                    .wrapping_add(closure.arity() as u32)
+                    .wrapping_mul(FUNNY_NUMBER1)
+                    .wrapping_add(module_hash)
+                    .wrapping_mul(FUNNY_NUMBER1)
+                    .wrapping_add(atom_hash(*function)),
+                Definition::Anonymous {
+                    index, old_unique, ..
+                } => {
+                    let env = closure.env_slice();
+
+                    for term in env.iter().rev() {
+                        stack.push(Item::Term(*term));
+                    }
+
+                    hash.wrapping_mul(FUNNY_NUMBER10)
+                        .wrapping_add(env.len() as u32)
+                        .wrapping_mul(FUNNY_NUMBER1)
+                        .wrapping_add(module_hash)
+                        .wrapping_mul(FUNNY_NUMBER2)
+                        .wrapping_add(*index as u32)
+                        .wrapping_mul(FUNNY_NUMBER2)
+                        .wrapping_add(*old_unique)
+                }
+            }
+        }
+        TypedTerm::Pid(pid) => {
+            uint32_hash(hash, pid.number() as u32, FUNNY_NUMBER5).wrapping_mul(FUNNY_NUMBER6)
+        }
+        TypedTerm::ExternalPid(external_pid) => {
+            uint32_hash(hash, external_pid.number() as u32, FUNNY_NUMBER5)
+                .wrapping_mul(FUNNY_NUMBER6)
+        }
+        TypedTerm::Port(port) => {
+            uint32_hash(hash, port.as_usize() as u32, FUNNY_NUMBER9).wrapping_mul(FUNNY_NUMBER10)
+        }
+        TypedTerm::ExternalPort(external_port) => {
+            uint32_hash(hash, external_port.port().as_usize() as u32, FUNNY_NUMBER9)
+                .wrapping_mul(FUNNY_NUMBER10)
+        }
+        TypedTerm::Reference(reference) => {
+            uint32_hash(hash, reference_number(&reference), FUNNY_NUMBER9)
+                .wrapping_mul(FUNNY_NUMBER10)
+        }
+        TypedTerm::ExternalReference(external_reference) => uint32_hash(
+            hash,
+            reference_number(external_reference.reference()),
+            FUNNY_NUMBER9,
+        )
+        .wrapping_mul(FUNNY_NUMBER10),
+        // Resources are magic references in the BEAM
+        TypedTerm::ResourceReference(resource) => {
+            uint32_hash(hash, resource.id() as u32, FUNNY_NUMBER9).wrapping_mul(FUNNY_NUMBER10)
+        }
+        TypedTerm::BinaryLiteral(binary_literal) => {
+            hash_bitstring(hash, binary_literal.as_bytes(), 0, 0)
+        }
+        TypedTerm::HeapBinary(heap_binary) => hash_bitstring(hash, heap_binary.as_bytes(), 0, 0),
+        TypedTerm::ProcBin(proc_bin) => hash_bitstring(hash, proc_bin.as_bytes(), 0, 0),
+        TypedTerm::SubBinary(subbinary) => {
+            let bytes: Vec<u8> = subbinary.full_byte_iter().collect();
+
+            hash_bitstring(
+                hash,
+                &bytes,
+                partial_byte_value(subbinary.partial_byte_bit_iter()),
+                subbinary.partial_byte_bit_len(),
+            )
+        }
+        TypedTerm::MatchContext(match_context) => {
+            let bytes: Vec<u8> = match_context.full_byte_iter().collect();
+
+            hash_bitstring(
+                hash,
+                &bytes,
+                partial_byte_value(match_context.partial_byte_bit_iter()),
+                match_context.partial_byte_bit_len(),
+            )
+        }
+    }
+}
+
+/// Integers are hashed by the bytes of their magnitude, so small integers and bignums hash the
+/// same way.
+fn hash_big_int(hash: u32, big_int: &BigInt) -> u32 {
+    let magnitude_hash = big_int_u32_digits(big_int)
+        .into_iter()
+        .fold(hash, |acc, digit| uint32_hash(acc, digit, FUNNY_NUMBER2));
+
+    let sign_number = match big_int.sign() {
+        Sign::Minus => FUNNY_NUMBER4,
+        _ => FUNNY_NUMBER3,
+    };
+
+    magnitude_hash.wrapping_mul(sign_number)
+}
+
+fn hash_bitstring(
+    hash: u32,
+    bytes: &[u8],
+    partial_byte_value: u8,
+    partial_byte_bit_len: u8,
+) -> u32 {
+    let mut bitstring_hash = bytes.iter().fold(hash, |acc, byte| {
+        acc.wrapping_mul(FUNNY_NUMBER1).wrapping_add(*byte as u32)
+    });
+
+    if partial_byte_bit_len > 0 {
+        bitstring_hash = bitstring_hash
+            .wrapping_mul(FUNNY_NUMBER1)
+            .wrapping_add(partial_byte_value as u32)
+            .wrapping_mul(FUNNY_NUMBER12)
+            .wrapping_add(partial_byte_bit_len as u32);
+    }
+
+    bitstring_hash
+        .wrapping_mul(FUNNY_NUMBER4)
+        .wrapping_add(bytes.len() as u32)
+}
+
+/// Runs of bytes in the list are hashed directly instead of as integers, as strings are common.
+fn hash_list(hash: u32, cons: Boxed<Cons>, stack: &mut Vec<Item>) -> u32 {
+    let mut hash = hash;
+    let mut current = cons;
+
+    loop {
+        let result_byte: Result<u8, _> = current.head.try_into();
+
+        match result_byte {
+            Ok(byte) => {
+                hash = hash.wrapping_mul(FUNNY_NUMBER2).wrapping_add(byte as u32);
+
+                match current.tail.decode().unwrap() {
+                    TypedTerm::List(tail_cons) => current = tail_cons,
+                    _ => {
+                        stack.push(Item::ListTail);
+                        stack.push(Item::Term(current.tail));
+
+                        break;
+                    }
+                }
+            }
+            Err(_) => {
+                if current.tail.is_non_empty_list() {
+                    stack.push(Item::Term(current.tail));
+                } else {
+                    stack.push(Item::ListTail);
+                    stack.push(Item::Term(current.tail));
+                }
+
+                stack.push(Item::Term(current.head));
+
+                break;
+            }
+        }
+    }
+
+    hash
+}
+
+/// Hashes the bytes of `value`, least significant first.
+fn uint32_hash(hash: u32, value: u32, prefix: u32) -> u32 {
+    value.to_le_bytes().iter().fold(hash, |acc, byte| {
+        acc.wrapping_mul(prefix).wrapping_add(*byte as u32)
+    })
+}
@@ -0,0 +1,367 @@
+//! The portable hash of `erlang:phash2/1,2`, which is based on Bob Jenkins' `lookup2` hash.
+use std::convert::TryInto;
+
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::term::closure::Definition;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::*;
+
+pub fn make_hash2(term: Term) -> u32 {
+    let mut hasher = Hasher::default();
+    hasher.stack.push(Item::Term(term));
+
+    while let Some(item) = hasher.stack.pop() {
+        match item {
+            Item::Term(term) => hasher.term(term),
+            Item::MapPair => {
+                hasher.hash_xor_pairs ^= hasher.hash;
+                hasher.hash = 0;
+            }
+            Item::MapTail {
+                hash,
+                hash_xor_pairs,
+            } => {
+                let pairs_hash = hasher.hash_xor_pairs;
+                hasher.hash = hash;
+                hasher.uint32_hash(pairs_hash, HCONST_19);
+                hasher.hash_xor_pairs = hash_xor_pairs;
+            }
+        }
+    }
+
+    hasher.hash
+}
+
+// Private
+
+const HCONST: u32 = 0x9e37_79b9;
+// (HCONST * {2, ..., 22}) mod 2^32, but only those that are used
+const HCONST_2: u32 = 0xdaa6_6d2b;
+const HCONST_3: u32 = 0x78dd_e6e4;
+const HCONST_4: u32 = 0x1715_609d;
+const HCONST_5: u32 = 0xb54c_da56;
+const HCONST_6: u32 = 0x5384_540f;
+const HCONST_7: u32 = 0xf1bb_cdc8;
+const HCONST_9: u32 = 0x2e2a_c13a;
+const HCONST_10: u32 = 0xcc62_3af3;
+const HCONST_11: u32 = 0x6a99_b4ac;
+const HCONST_12: u32 = 0x08d1_2e65;
+const HCONST_13: u32 = 0xa708_a81e;
+const HCONST_14: u32 = 0x4540_21d7;
+const HCONST_15: u32 = 0xe377_9b90;
+const HCONST_16: u32 = 0x81af_1549;
+const HCONST_19: u32 = 0x5c55_8274;
+
+/// The hash of `[]` when it is the first term hashed.
+const NIL_HASH: u32 = 3_468_870_702;
+/// `NIL_DEF` in the BEAM, which is hashed for `[]` when it is not the first term hashed.
+const NIL_DEF: u32 = 0x2;
+
+/// Integers in `-2^27..2^27` are hashed as 32-bit integers; all others as bignums.
+const SSMALL28_MIN: isize = -(1 << 27);
+const SSMALL28_MAX: isize = (1 << 27) - 1;
+
+const BLOCK_HASH_BYTES_PER_ITER: usize = 12;
+
+enum Item {
+    Term(Term),
+    /// The key and value of a map pair have been hashed, so the hash of the pair can be mixed
+    /// into `hash_xor_pairs`.
+    MapPair,
+    /// All the pairs of a map have been hashed, so the hash before the map can be restored.
+    MapTail {
+        hash: u32,
+        hash_xor_pairs: u32,
+    },
+}
+
+#[derive(Default)]
+struct Hasher {
+    hash: u32,
+    /// The pair hashes of the innermost map being hashed are combined with `xor`, so that the hash
+    /// is independent of the order of the pairs.
+    hash_xor_pairs: u32,
+    stack: Vec<Item>,
+}
+
+impl Hasher {
+    fn term(&mut self, term: Term) {
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => {
+                let atom_hash = atom_hash(atom);
+
+                if self.hash == 0 {
+                    self.hash = atom_hash;
+                } else {
+                    self.uint32_hash(atom_hash, HCONST_3);
+                }
+            }
+            TypedTerm::Nil => {
+                if self.hash == 0 {
+                    self.hash = NIL_HASH;
+                } else {
+                    self.uint32_hash(NIL_DEF, HCONST_2);
+                }
+            }
+            TypedTerm::SmallInteger(small_integer) => {
+                let integer: isize = small_integer.into();
+
+                if SSMALL28_MIN <= integer && integer <= SSMALL28_MAX {
+                    self.sint32_hash(integer as i32, HCONST);
+                } else {
+                    self.big_int(&BigInt::from(integer));
+                }
+            }
+            TypedTerm::BigInteger(big_integer) => {
+                let big_int: &BigInt = big_integer.as_ref().into();
+
+                self.big_int(big_int);
+            }
+            TypedTerm::Float(float) => {
+                let bits = float_bits(float.into());
+
+                self.uint32_hash_2((bits >> 32) as u32, bits as u32, HCONST_12);
+            }
+            TypedTerm::List(cons) => self.list(cons),
+            TypedTerm::Tuple(tuple) => {
+                self.uint32_hash(tuple.len() as u32, HCONST_9);
+
+                for element in tuple.iter().rev() {
+                    self.stack.push(Item::Term(*element));
+                }
+            }
+            TypedTerm::Map(map) => {
+                self.uint32_hash(map.len() as u32, HCONST_16);
+
+                if map.len() > 0 {
+                    self.stack.push(Item::MapTail {
+                        hash: self.hash,
+                        hash_xor_pairs: self.hash_xor_pairs,
+                    });
+                    self.hash = 0;
+                    self.hash_xor_pairs = 0;
+
+                    for (key, value) in map.iter().rev() {
+                        self.stack.push(Item::MapPair);
+                        self.stack.push(Item::Term(value));
+                        self.stack.push(Item::Term(key));
+                    }
+                }
+            }
+            TypedTerm::Closure(closure) => {
+                let module_hash = atom_hash(closure.module());
+
+                match closure.definition() {
+                    Definition::Export { function } => {
+                        self.uint32_hash_2(closure.arity() as u32, module_hash, HCONST);
+                        self.uint32_hash(atom_hash(*function), HCONST_14);
+                    }
+                    Definition::Anonymous {
+                        index, old_unique, ..
+                    } => {
+                        let env = closure.env_slice();
+
+                        self.uint32_hash_2(env.len() as u32, module_hash, HCONST);
+                        self.uint32_hash_2(*index as u32, *old_unique, HCONST);
+
+                        for term in env.iter().rev() {
+                            self.stack.push(Item::Term(*term));
+                        }
+                    }
+                }
+            }
+            // Only the 15 bits of the number are hashed.
+            TypedTerm::Pid(pid) => self.uint32_hash(pid.number() as u32, HCONST_5),
+            TypedTerm::ExternalPid(external_pid) => {
+                self.uint32_hash(external_pid.number() as u32, HCONST_5)
+            }
+            TypedTerm::Port(port) => self.uint32_hash(port.as_usize() as u32, HCONST_6),
+            TypedTerm::ExternalPort(external_port) => {
+                self.uint32_hash(external_port.port().as_usize() as u32, HCONST_6)
+            }
+            TypedTerm::Reference(reference) => {
+                self.uint32_hash(reference_number(&reference), HCONST_7)
+            }
+            TypedTerm::ExternalReference(external_reference) => {
+                self.uint32_hash(reference_number(external_reference.reference()), HCONST_7)
+            }
+            // Resources are magic references in the BEAM
+            TypedTerm::ResourceReference(resource) => {
+                self.uint32_hash(resource.id() as u32, HCONST_7)
+            }
+            TypedTerm::BinaryLiteral(binary_literal) => {
+                self.bitstring(binary_literal.as_bytes(), 0, 0)
+            }
+            TypedTerm::HeapBinary(heap_binary) => self.bitstring(heap_binary.as_bytes(), 0, 0),
+            TypedTerm::ProcBin(proc_bin) => self.bitstring(proc_bin.as_bytes(), 0, 0),
+            TypedTerm::SubBinary(subbinary) => {
+                let bytes: Vec<u8> = subbinary.full_byte_iter().collect();
+
+                self.bitstring(
+                    &bytes,
+                    partial_byte_value(subbinary.partial_byte_bit_iter()),
+                    subbinary.partial_byte_bit_len(),
+                )
+            }
+            TypedTerm::MatchContext(match_context) => {
+                let bytes: Vec<u8> = match_context.full_byte_iter().collect();
+
+                self.bitstring(
+                    &bytes,
+                    partial_byte_value(match_context.partial_byte_bit_iter()),
+                    match_context.partial_byte_bit_len(),
+                )
+            }
+        }
+    }
+
+    fn big_int(&mut self, big_int: &BigInt) {
+        let constant = match big_int.sign() {
+            Sign::Minus => HCONST_10,
+            _ => HCONST_11,
+        };
+
+        // The 64-bit digits of the BEAM are hashed as pairs of 32-bit digits
+        for pair in big_int_u32_digits(big_int).chunks(2) {
+            self.uint32_hash_2(pair[0], pair.get(1).copied().unwrap_or(0), constant);
+        }
+    }
+
+    fn bitstring(&mut self, bytes: &[u8], partial_byte_value: u8, partial_byte_bit_len: u8) {
+        let constant = HCONST_13.wrapping_add(self.hash);
+
+        if bytes.is_empty() && partial_byte_bit_len == 0 {
+            self.hash = constant;
+        } else {
+            self.hash = block_hash(bytes, constant);
+
+            if partial_byte_bit_len > 0 {
+                self.uint32_hash_2(
+                    partial_byte_bit_len as u32,
+                    partial_byte_value as u32,
+                    HCONST_15,
+                );
+            }
+        }
+    }
+
+    /// Runs of bytes at the head of the list are hashed 4 at a time, as strings are common.
+    fn list(&mut self, cons: Boxed<Cons>) {
+        let mut current = cons;
+        let mut byte_count = 0;
+        let mut bytes: u32 = 0;
+
+        loop {
+            let result_byte: Result<u8, _> = current.head.try_into();
+
+            match result_byte {
+                Ok(byte) => {
+                    bytes = (bytes << 8) + (byte as u32);
+
+                    if byte_count == 3 {
+                        self.uint32_hash(bytes, HCONST_4);
+                        byte_count = 0;
+                        bytes = 0;
+                    } else {
+                        byte_count += 1;
+                    }
+
+                    match current.tail.decode().unwrap() {
+                        TypedTerm::List(tail_cons) => current = tail_cons,
+                        _ => {
+                            if byte_count > 0 {
+                                self.uint32_hash(bytes, HCONST_4);
+                            }
+
+                            self.stack.push(Item::Term(current.tail));
+
+                            break;
+                        }
+                    }
+                }
+                Err(_) => {
+                    if byte_count > 0 {
+                        self.uint32_hash(bytes, HCONST_4);
+                    }
+
+                    self.stack.push(Item::Term(current.tail));
+                    self.stack.push(Item::Term(current.head));
+
+                    break;
+                }
+            }
+        }
+    }
+
+    fn sint32_hash(&mut self, integer: i32, constant: u32) {
+        // Negative numbers are unnecessarily mixed twice, but the BEAM does it
+        if integer < 0 {
+            self.uint32_hash(integer.wrapping_neg() as u32, constant);
+        }
+
+        self.uint32_hash(integer as u32, constant);
+    }
+
+    fn uint32_hash(&mut self, value: u32, constant: u32) {
+        self.uint32_hash_2(value, 0, constant)
+    }
+
+    fn uint32_hash_2(&mut self, value1: u32, value2: u32, constant: u32) {
+        let mut a = constant.wrapping_add(value1);
+        let mut b = constant.wrapping_add(value2);
+
+        mix(&mut a, &mut b, &mut self.hash);
+    }
+}
+
+/// Hashes `bytes` 12 at a time with `initial_value` as the hash of the previous term.
+fn block_hash(bytes: &[u8], initial_value: u32) -> u32 {
+    let mut a = HCONST;
+    let mut b = HCONST;
+    let mut c = initial_value;
+
+    let mut chunks = bytes.chunks_exact(BLOCK_HASH_BYTES_PER_ITER);
+
+    for chunk in &mut chunks {
+        a = a.wrapping_add(u32_le(&chunk[0..4]));
+        b = b.wrapping_add(u32_le(&chunk[4..8]));
+        c = c.wrapping_add(u32_le(&chunk[8..12]));
+        mix(&mut a, &mut b, &mut c);
+    }
+
+    let remainder = chunks.remainder();
+    c = c.wrapping_add(bytes.len() as u32);
+
+    // The lowest byte of `c` is reserved for the length
+    for (index, byte) in remainder.iter().enumerate() {
+        let byte = *byte as u32;
+
+        match index {
+            0..=3 => a = a.wrapping_add(byte << (8 * index)),
+            4..=7 => b = b.wrapping_add(byte << (8 * (index - 4))),
+            _ => c = c.wrapping_add(byte << (8 * (index - 7))),
+        }
+    }
+
+    mix(&mut a, &mut b, &mut c);
+
+    c
+}
+
+fn mix(a: &mut u32, b: &mut u32, c: &mut u32) {
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 13);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 8);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 13);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 12);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 16);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 5);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 3);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 10);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 15);
+}
+
+fn u32_le(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
@@ -0,0 +1,51 @@
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, exit};
+use liblumen_alloc::erts::process::gc::GcError;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply::arguments_term_to_vec;
+use crate::erlang::apply_3;
+
+/// The callers of `erlang:hibernate/3` are discarded, so when `module:function(arguments...)`
+/// returns, the process exits instead of returning to them.
+#[native_implemented::function(erlang:hibernate/3)]
+pub fn result(
+    process: &Process,
+    module: Term,
+    function: Term,
+    arguments: Term,
+) -> exception::Result<Term> {
+    term_try_into_atom!(module)?;
+    term_try_into_atom!(function)?;
+    arguments_term_to_vec(arguments)?;
+
+    let mut roots = [module, function, arguments];
+
+    match process.hibernate(&mut roots[..]) {
+        Ok(_) => (),
+        Err(GcError::MaxHeapSizeExceeded) => {
+            return Err(exit(
+                atom!("killed"),
+                Trace::capture(),
+                Some(anyhow!("max_heap_size exceeded while hibernating").into()),
+            )
+            .into())
+        }
+        Err(gc_err) => panic!("fatal garbage collection error: {:?}", gc_err),
+    }
+
+    process.queue_frame_with_arguments(apply_3::frame().with_arguments(false, &roots));
+
+    // check under the mailbox lock, so that a message can't arrive between the check and `wait`
+    let mailbox_guard = process.acquire_mailbox();
+
+    if mailbox_guard.borrow().len() == 0 {
+        process.wait();
+    }
+
+    Ok(Term::NONE)
+}
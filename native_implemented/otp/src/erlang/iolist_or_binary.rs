@@ -47,7 +47,28 @@ pub fn result(
     }
 }
 
+/// Like `to_bytes`, but `value` itself must be an iolist or binary and not a byte.
+pub fn iolist_or_binary_to_bytes(name: &'static str, value: Term) -> exception::Result<Vec<u8>> {
+    if value.is_list() || value.is_binary() {
+        to_bytes(name, value)
+    } else {
+        Err(TypeError)
+            .context(term_is_not_type(
+                name,
+                value,
+                &format!("an iolist ({}) or binary", r#type::IOLIST),
+            ))
+            .map_err(From::from)
+    }
+}
+
 pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exception::Result<Term> {
+    let byte_vec = to_bytes(name, value)?;
+
+    Ok(process.binary_from_bytes(byte_vec.as_slice()))
+}
+
+pub fn to_bytes(name: &'static str, value: Term) -> exception::Result<Vec<u8>> {
     let mut byte_vec: Vec<u8> = Vec::new();
     let mut stack: Vec<Term> = vec![value];
 
@@ -83,6 +104,9 @@ pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exceptio
 
                 stack.push(boxed_cons.head);
             }
+            TypedTerm::BinaryLiteral(binary_literal) => {
+                byte_vec.extend_from_slice(binary_literal.as_bytes());
+            }
             TypedTerm::HeapBinary(heap_binary) => {
                 byte_vec.extend_from_slice(heap_binary.as_bytes());
             }
@@ -110,7 +134,7 @@ pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exceptio
         }
     }
 
-    Ok(process.binary_from_bytes(byte_vec.as_slice()))
+    Ok(byte_vec)
 }
 
 fn element_context(name: &'static str, value: Term, element: Term) -> String {
@@ -1,9 +1,6 @@
-use std::sync::Arc;
+use proptest::prop_assert;
+use proptest::strategy::Just;
 
-use proptest::strategy::{BoxedStrategy, Just, Strategy};
-use proptest::{prop_assert, prop_oneof};
-
-use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::iolist_size_1::result;
@@ -162,29 +159,3 @@ fn with_improper_list_smallint_tail_errors_badarg() {
         );
     });
 }
-
-pub fn is_not_list_or_bitstring(arc_process: Arc<Process>) -> BoxedStrategy<Term> {
-    let element = term(arc_process.clone());
-    let size_range = size_range();
-
-    prop_oneof![
-        integer::big(arc_process.clone()),
-        local_reference(arc_process.clone()),
-        is_function(arc_process.clone()),
-        float(arc_process.clone()),
-        // TODO `Export`
-        // TODO `ReferenceCountedBinary`
-        pid::external(arc_process.clone()),
-        // TODO `ExternalPort`
-        // TODO `ExternalReference`
-        pid::local(),
-        // TODO `LocalPort`,
-        term::atom(),
-        integer::small(arc_process.clone()),
-        prop_oneof![
-            tuple::intermediate(element.clone(), size_range.clone(), arc_process.clone()),
-            map::intermediate(element.clone(), size_range, arc_process.clone()),
-        ]
-    ]
-    .boxed()
-}
@@ -3,12 +3,13 @@ mod test;
 
 use anyhow::*;
 
-use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::exception::{self, error, exit};
 use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::runtime::registry::pid_to_process;
+use crate::runtime::{distribution, port};
 
 #[native_implemented::function(erlang:link/1)]
 fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
@@ -35,9 +36,46 @@ fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
                 }
             }
         }
-        TypedTerm::Port(_) => unimplemented!(),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
-        TypedTerm::ExternalPort(_) => unimplemented!(),
+        TypedTerm::Port(port) => match port::get(port) {
+            Some(arc_control_block) => {
+                arc_control_block.link(process.pid());
+
+                Ok(true.into())
+            }
+            None => Err(error(
+                Atom::str_to_term("noproc"),
+                None,
+                Trace::capture(),
+                Some(anyhow!("port ({}) is not open", port).into()),
+            )
+            .into()),
+        },
+        TypedTerm::ExternalPid(external_pid) => {
+            if distribution::link(process, &external_pid) {
+                Ok(true.into())
+            } else if process.traps_exit() {
+                let message = process.tuple_from_slice(&[
+                    Atom::str_to_term("EXIT"),
+                    pid_or_port,
+                    Atom::str_to_term("noconnection"),
+                ]);
+                process.send_from_self(message);
+
+                Ok(true.into())
+            } else {
+                Err(exit(
+                    Atom::str_to_term("noconnection"),
+                    Trace::capture(),
+                    Some(anyhow!("node of pid ({}) cannot be connected", pid_or_port).into()),
+                )
+                .into())
+            }
+        }
+        TypedTerm::ExternalPort(_) => Err(anyhow!(
+            "pid_or_port ({}) is a port on another node, which cannot be linked",
+            pid_or_port
+        )
+        .into()),
         _ => Err(TypeError)
             .context(format!(
                 "pid_or_port ({}) is neither a pid nor a port",
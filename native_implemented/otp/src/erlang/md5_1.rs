@@ -0,0 +1,17 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::iolist_or_binary;
+
+/// Returns the 16 byte MD5 message digest of `data`.
+#[native_implemented::function(erlang:md5/1)]
+pub fn result(process: &Process, data: Term) -> exception::Result<Term> {
+    let bytes = iolist_or_binary::iolist_or_binary_to_bytes("data", data)?;
+    let digest = md5::compute(&bytes);
+
+    Ok(process.binary_from_bytes(&digest.0))
+}
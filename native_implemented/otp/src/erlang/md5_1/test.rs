@@ -0,0 +1,71 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::erlang::md5_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_iolist_or_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list_or_bitstring(arc_process),
+            )
+        },
+        |(arc_process, data)| {
+            prop_assert_badarg!(result(&arc_process, data), format!("data ({})", data));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_iolist_or_binary_returns_16_byte_binary() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_iolist_or_binary(arc_process),
+            )
+        },
+        |(arc_process, data)| {
+            let digest = result(&arc_process, data).unwrap();
+
+            prop_assert_eq!(
+                crate::erlang::byte_size_1::result(&arc_process, digest),
+                Ok(arc_process.integer(16))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_returns_digest() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.binary_from_bytes(&[])),
+            Ok(process.binary_from_bytes(&[
+                0xd4, 0x1d, 0x8c, 0xd9, 0x8f, 0x00, 0xb2, 0x04, 0xe9, 0x80, 0x09, 0x98, 0xec, 0xf8,
+                0x42, 0x7e
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_charlist_returns_digest() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.charlist_from_str("abc")),
+            Ok(process.binary_from_bytes(&[
+                0x90, 0x01, 0x50, 0x98, 0x3c, 0xd2, 0x4f, 0xb0, 0xd6, 0x96, 0x3f, 0x7d, 0x28, 0xe1,
+                0x7f, 0x72
+            ]))
+        );
+    });
+}
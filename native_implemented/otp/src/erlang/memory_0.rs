@@ -0,0 +1,23 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::memory::Memory;
+
+use crate::erlang::memory_1::{type_size, TYPES};
+
+// See http://erlang.org/doc/man/erlang.html#memory-0
+#[native_implemented::function(erlang:memory/0)]
+pub fn result(process: &Process) -> Term {
+    let memory = Memory::get();
+    let type_size_vec: Vec<Term> = TYPES
+        .iter()
+        .map(|r#type| {
+            process.tuple_from_slice(&[
+                Atom::str_to_term(r#type),
+                process.integer(type_size(&memory, r#type).unwrap()),
+            ])
+        })
+        .collect();
+
+    process.list_from_slice(&type_size_vec)
+}
@@ -0,0 +1,93 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::memory::Memory;
+
+// See http://erlang.org/doc/man/erlang.html#memory-1
+#[native_implemented::function(erlang:memory/1)]
+pub fn result(process: &Process, type_or_types: Term) -> exception::Result<Term> {
+    let memory = Memory::get();
+
+    match type_or_types.decode()? {
+        TypedTerm::Atom(type_atom) => {
+            let size = type_atom_size(&memory, type_or_types, type_atom)?;
+
+            Ok(process.integer(size))
+        }
+        TypedTerm::Nil => Ok(Term::NIL),
+        TypedTerm::List(boxed_cons) => {
+            let mut type_size_vec = Vec::new();
+
+            for result in boxed_cons.into_iter() {
+                match result {
+                    Ok(r#type) => {
+                        let type_atom = term_try_into_atom("type", r#type)?;
+                        let size = type_atom_size(&memory, r#type, type_atom)?;
+
+                        type_size_vec
+                            .push(process.tuple_from_slice(&[r#type, process.integer(size)]));
+                    }
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("types ({}) is improper", type_or_types))
+                            .map_err(From::from)
+                    }
+                }
+            }
+
+            Ok(process.list_from_slice(&type_size_vec))
+        }
+        _ => Err(TypeError)
+            .context(format!(
+                "type_or_types ({}) is neither an atom nor a list of atoms",
+                type_or_types
+            ))
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+/// The types returned by `erlang:memory/0` in order
+pub(in crate::erlang) const TYPES: &[&str] = &[
+    "total",
+    "processes",
+    "processes_used",
+    "system",
+    "atom",
+    "atom_used",
+    "binary",
+    "code",
+    "ets",
+];
+
+pub(in crate::erlang) fn type_size(memory: &Memory, r#type: &str) -> Option<usize> {
+    match r#type {
+        "total" => Some(memory.total),
+        "processes" => Some(memory.processes),
+        "processes_used" => Some(memory.processes_used),
+        "system" => Some(memory.system),
+        "atom" => Some(memory.atom),
+        "atom_used" => Some(memory.atom_used),
+        "binary" => Some(memory.binary),
+        "code" => Some(memory.code),
+        "ets" => Some(memory.ets),
+        _ => None,
+    }
+}
+
+fn type_atom_size(memory: &Memory, r#type: Term, type_atom: Atom) -> anyhow::Result<usize> {
+    type_size(memory, type_atom.name()).ok_or_else(|| {
+        anyhow!(
+            "type ({}) is not a supported atom (total, processes, processes_used, system, atom, atom_used, binary, code, or ets)",
+            r#type
+        )
+    })
+}
@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::{memory_0, memory_1};
+use crate::test::with_process;
+
+#[test]
+fn without_supported_type_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            memory_1::result(process, atom!("unsupported")),
+            "type (unsupported) is not a supported atom"
+        );
+    });
+}
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            memory_1::result(process, process.cons(atom!("total"), atom!("ets"))),
+            "is improper"
+        );
+    });
+}
+
+#[test]
+fn with_total_is_sum_of_processes_and_system() {
+    with_process(|process| {
+        let types = process.list_from_slice(&[atom!("total"), atom!("processes"), atom!("system")]);
+        let type_sizes = memory_1::result(process, types).unwrap();
+        let type_size_cons: Boxed<Cons> = type_sizes.try_into().unwrap();
+        let sizes: Vec<usize> = type_size_cons
+            .into_iter()
+            .map(|result| {
+                let type_size: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+                type_size[1].try_into().unwrap()
+            })
+            .collect();
+
+        // Processes may be spawned or exit between the calls for each type in other tests, but
+        // all types come from the same snapshot
+        assert_eq!(sizes[0], sizes[1] + sizes[2]);
+    });
+}
+
+#[test]
+fn memory_0_includes_all_types() {
+    with_process(|process| {
+        let type_sizes = memory_0::result(process);
+        let type_size_cons: Boxed<Cons> = type_sizes.try_into().unwrap();
+        let types: Vec<Term> = type_size_cons
+            .into_iter()
+            .map(|result| {
+                let type_size: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+                type_size[0]
+            })
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                atom!("total"),
+                atom!("processes"),
+                atom!("processes_used"),
+                atom!("system"),
+                atom!("atom"),
+                atom!("atom_used"),
+                atom!("binary"),
+                atom!("code"),
+                atom!("ets")
+            ]
+        );
+    });
+}
@@ -12,8 +12,10 @@ use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::node_0;
 use crate::runtime::context::*;
+use crate::runtime::distribution::{self, control::Identifier};
 use crate::runtime::scheduler::SchedulerDependentAlloc;
-use crate::runtime::{process, registry};
+use crate::runtime::time::offset;
+use crate::runtime::{port, process, registry};
 
 const TYPE_CONTEXT: &str = "supported types are :port, :process, or :time_offset";
 
@@ -22,9 +24,9 @@ pub fn result(process: &Process, r#type: Term, item: Term) -> exception::Result<
     let type_atom: Atom = r#type.try_into().context(TYPE_CONTEXT)?;
 
     match type_atom.name() {
-        "port" => unimplemented!(),
+        "port" => monitor_port_identifier(process, item),
         "process" => monitor_process_identifier(process, item),
-        "time_offset" => unimplemented!(),
+        "time_offset" => monitor_time_offset(process, item),
         name => Err(TryAtomFromTermError(name))
             .context(TYPE_CONTEXT)
             .map_err(From::from),
@@ -33,6 +35,63 @@ pub fn result(process: &Process, r#type: Term, item: Term) -> exception::Result<
 
 // Private
 
+const PORT_IDENTIFIER_CONTEXT: &str =
+    "port identifier must be `port() | registered_name() | {registered_name(), node()}`";
+
+fn monitor_port_identifier(process: &Process, port_identifier: Term) -> exception::Result<Term> {
+    match port_identifier.decode()? {
+        TypedTerm::Atom(atom) => Ok(monitor_port_registered_name(process, port_identifier, atom)),
+        TypedTerm::Port(port) => Ok(match port::get(port) {
+            Some(control_block) => control_block.monitor(process, None),
+            None => monitor_identifier_noproc(process, atom!("port"), port_identifier),
+        }),
+        TypedTerm::Tuple(tuple) => {
+            if tuple.len() == 2 {
+                let registered_name = tuple[0];
+                let registered_name_atom = term_try_into_atom("registered name", registered_name)?;
+
+                if tuple[1] == node_0::result() {
+                    Ok(monitor_port_registered_name(
+                        process,
+                        registered_name,
+                        registered_name_atom,
+                    ))
+                } else {
+                    Err(anyhow!(
+                        "port identifier ({}) is not on the local node as ports cannot be monitored on other nodes",
+                        port_identifier
+                    )
+                    .into())
+                }
+            } else {
+                Err(anyhow!(PORT_IDENTIFIER_CONTEXT).into())
+            }
+        }
+        _ => Err(TypeError)
+            .context(PORT_IDENTIFIER_CONTEXT)
+            .map_err(From::from),
+    }
+}
+
+fn monitor_port_registered_name(process: &Process, port_identifier: Term, atom: Atom) -> Term {
+    match registry::atom_to_port(&atom).and_then(port::get) {
+        Some(control_block) => control_block.monitor(process, Some(atom)),
+        None => {
+            let identifier = process.tuple_from_slice(&[port_identifier, node_0::result()]);
+
+            monitor_identifier_noproc(process, atom!("port"), identifier)
+        }
+    }
+}
+
+fn monitor_time_offset(process: &Process, item: Term) -> exception::Result<Term> {
+    if item == Atom::str_to_term("clock_service") {
+        Ok(offset::monitor(process))
+    } else {
+        Err(anyhow!("time_offset item ({}) is not clock_service", item).into())
+    }
+}
+
 fn monitor_process_identifier(
     process: &Process,
     process_identifier: Term,
@@ -44,7 +103,9 @@ fn monitor_process_identifier(
             atom,
         )),
         TypedTerm::Pid(pid) => Ok(monitor_process_pid(process, process_identifier, pid)),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
+        TypedTerm::ExternalPid(external_pid) => {
+            Ok(monitor_process_external_pid(process, &external_pid))
+        }
         TypedTerm::Tuple(tuple) => monitor_process_tuple(process, process_identifier, &tuple),
         _ => Err(TypeError)
             .context(PROCESS_IDENTIFIER_CONTEXT)
@@ -52,18 +113,37 @@ fn monitor_process_identifier(
     }
 }
 
-fn monitor_process_identifier_noproc(process: &Process, identifier: Term) -> Term {
+fn monitor_identifier_noproc(process: &Process, r#type: Term, identifier: Term) -> Term {
     let monitor_reference = process.next_reference();
-    let noproc_message = noproc_message(process, monitor_reference, identifier);
+    let noproc_message = noproc_message(process, monitor_reference, r#type, identifier);
     process.send_from_self(noproc_message);
 
     monitor_reference
 }
 
+fn monitor_process_external_pid(process: &Process, external_pid: &ExternalPid) -> Term {
+    let reference = process.next_reference();
+    let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
+    let pid = Pid::new(
+        external_pid.number() as usize,
+        external_pid.serial() as usize,
+    )
+    .unwrap();
+
+    distribution::monitor(
+        process,
+        reference_reference.as_ref(),
+        Identifier::Pid(pid),
+        external_pid.arc_node().name(),
+    );
+
+    reference
+}
+
 fn monitor_process_pid(process: &Process, process_identifier: Term, pid: Pid) -> Term {
     match registry::pid_to_process(&pid) {
         Some(monitored_arc_process) => process::monitor(process, &monitored_arc_process),
-        None => monitor_process_identifier_noproc(process, process_identifier),
+        None => monitor_identifier_noproc(process, atom!("process"), process_identifier),
     }
 }
 
@@ -92,7 +172,7 @@ fn monitor_process_registered_name(
         None => {
             let identifier = process.tuple_from_slice(&[process_identifier, node_0::result()]);
 
-            monitor_process_identifier_noproc(process, identifier)
+            monitor_identifier_noproc(process, atom!("process"), identifier)
         }
     }
 }
@@ -118,28 +198,38 @@ fn monitor_process_tuple(
                 registered_name_atom,
             ))
         } else {
-            let _: Atom = term_try_into_atom!(node)?;
+            let node_atom: Atom = term_try_into_atom!(node)?;
+            let reference = process.next_reference();
+            let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
 
-            unimplemented!(
-                "node ({:?}) is not the local node ({:?})",
-                node,
-                node_0::result()
+            distribution::monitor(
+                process,
+                reference_reference.as_ref(),
+                Identifier::Name(registered_name_atom),
+                node_atom,
             );
+
+            Ok(reference)
         }
     } else {
         Err(anyhow!(PROCESS_IDENTIFIER_CONTEXT).into())
     }
 }
 
-fn noproc_message(process: &Process, reference: Term, identifier: Term) -> Term {
+fn noproc_message(process: &Process, reference: Term, r#type: Term, identifier: Term) -> Term {
     let noproc = atom!("noproc");
 
-    down_message(process, reference, identifier, noproc)
+    down_message(process, reference, r#type, identifier, noproc)
 }
 
-fn down_message(process: &Process, reference: Term, identifier: Term, info: Term) -> Term {
+fn down_message(
+    process: &Process,
+    reference: Term,
+    r#type: Term,
+    identifier: Term,
+    info: Term,
+) -> Term {
     let down = atom!("DOWN");
-    let r#type = atom!("process");
 
     process.tuple_from_slice(&[down, reference, r#type, identifier, info])
 }
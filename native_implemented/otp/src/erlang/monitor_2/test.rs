@@ -1,4 +1,6 @@
+mod with_port_type;
 mod with_process_type;
+mod with_time_offset_type;
 
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -0,0 +1,229 @@
+use super::*;
+
+use crate::erlang::{demonitor_2, open_port_2, port_close_1};
+
+#[test]
+fn without_registered_name_returns_reference_but_immediate_sends_noproc_message() {
+    with_process_arc(|monitoring_arc_process| {
+        let registered_name = registered_name();
+
+        let monitor_reference_result = result(&monitoring_arc_process, r#type(), registered_name);
+
+        assert!(monitor_reference_result.is_ok());
+
+        let monitor_reference = monitor_reference_result.unwrap();
+
+        assert!(monitor_reference.is_reference());
+
+        let tag = Atom::str_to_term("DOWN");
+        let reason = Atom::str_to_term("noproc");
+
+        assert_has_message!(
+            &monitoring_arc_process,
+            monitoring_arc_process.tuple_from_slice(&[
+                tag,
+                monitor_reference,
+                r#type(),
+                monitoring_arc_process.tuple_from_slice(&[registered_name, node_0::result()]),
+                reason
+            ])
+        );
+    });
+}
+
+#[test]
+fn with_pid_errors_badarg() {
+    with_process_arc(|monitoring_arc_process| {
+        assert_badarg!(
+            result(
+                &monitoring_arc_process,
+                r#type(),
+                monitoring_arc_process.pid_term()
+            ),
+            "port identifier must be `port() | registered_name() | {registered_name(), node()}`"
+        );
+    });
+}
+
+#[test]
+fn with_port_sends_down_message_with_normal_when_port_is_closed() {
+    with_process_arc(|monitoring_arc_process| {
+        let port = open_cat_port(&monitoring_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+
+        assert_eq!(
+            port_close_1::result(&monitoring_arc_process, port),
+            Ok(true.into())
+        );
+
+        assert_has_message!(
+            &monitoring_arc_process,
+            monitoring_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("DOWN"),
+                monitor_reference,
+                r#type(),
+                port,
+                Atom::str_to_term("normal")
+            ])
+        );
+    });
+}
+
+#[test]
+fn with_port_sends_down_message_with_exit_reason_of_owner() {
+    with_process_arc(|monitoring_arc_process| {
+        let owner_arc_process = test::process::child(&monitoring_arc_process);
+        let port = open_cat_port(&owner_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+
+        let reason = Atom::str_to_term("shutdown");
+        exit_when_run(&owner_arc_process, reason);
+
+        assert!(scheduler::run_through(&owner_arc_process));
+
+        assert!(owner_arc_process.is_exiting());
+
+        assert_has_message!(
+            &monitoring_arc_process,
+            monitoring_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("DOWN"),
+                monitor_reference,
+                r#type(),
+                port,
+                reason
+            ])
+        );
+    });
+}
+
+#[test]
+fn with_port_demonitor_with_flush_removes_down_message() {
+    with_process_arc(|monitoring_arc_process| {
+        let port = open_cat_port(&monitoring_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+        let down = monitoring_arc_process.tuple_from_slice(&[
+            Atom::str_to_term("DOWN"),
+            monitor_reference,
+            r#type(),
+            port,
+            Atom::str_to_term("normal"),
+        ]);
+
+        assert_eq!(
+            port_close_1::result(&monitoring_arc_process, port),
+            Ok(true.into())
+        );
+        assert_has_message!(&monitoring_arc_process, down);
+
+        let options = monitoring_arc_process.list_from_slice(&[Atom::str_to_term("flush")]);
+
+        assert_eq!(
+            demonitor_2::result(&monitoring_arc_process, monitor_reference, options),
+            Ok(true.into())
+        );
+        assert!(!has_message(&monitoring_arc_process, down));
+    });
+}
+
+#[test]
+fn with_port_demonitor_with_flush_and_info_returns_false_when_down_message_was_flushed() {
+    with_process_arc(|monitoring_arc_process| {
+        let port = open_cat_port(&monitoring_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+
+        assert_eq!(
+            port_close_1::result(&monitoring_arc_process, port),
+            Ok(true.into())
+        );
+
+        assert_eq!(
+            demonitor_2::result(
+                &monitoring_arc_process,
+                monitor_reference,
+                flush_and_info(&monitoring_arc_process)
+            ),
+            Ok(false.into())
+        );
+    });
+}
+
+#[test]
+fn with_port_demonitor_with_flush_and_info_returns_true_when_port_is_open() {
+    with_process_arc(|monitoring_arc_process| {
+        let port = open_cat_port(&monitoring_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+
+        assert_eq!(
+            demonitor_2::result(
+                &monitoring_arc_process,
+                monitor_reference,
+                flush_and_info(&monitoring_arc_process)
+            ),
+            Ok(true.into())
+        );
+
+        assert_eq!(
+            port_close_1::result(&monitoring_arc_process, port),
+            Ok(true.into())
+        );
+
+        assert!(!has_message(
+            &monitoring_arc_process,
+            monitoring_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("DOWN"),
+                monitor_reference,
+                r#type(),
+                port,
+                Atom::str_to_term("normal")
+            ])
+        ));
+    });
+}
+
+#[test]
+fn with_port_demonitor_from_other_process_leaves_monitor() {
+    with_process_arc(|monitoring_arc_process| {
+        let port = open_cat_port(&monitoring_arc_process);
+        let monitor_reference = result(&monitoring_arc_process, r#type(), port).unwrap();
+        let other_arc_process = test::process::child(&monitoring_arc_process);
+        let options = other_arc_process.list_from_slice(&[Atom::str_to_term("info")]);
+
+        assert_eq!(
+            demonitor_2::result(&other_arc_process, monitor_reference, options),
+            Ok(false.into())
+        );
+
+        assert_eq!(
+            port_close_1::result(&monitoring_arc_process, port),
+            Ok(true.into())
+        );
+
+        assert_has_message!(
+            &monitoring_arc_process,
+            monitoring_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("DOWN"),
+                monitor_reference,
+                r#type(),
+                port,
+                Atom::str_to_term("normal")
+            ])
+        );
+    });
+}
+
+fn r#type() -> Term {
+    Atom::str_to_term("port")
+}
+
+fn flush_and_info(process: &Process) -> Term {
+    process.list_from_slice(&[Atom::str_to_term("flush"), Atom::str_to_term("info")])
+}
+
+fn open_cat_port(process: &Process) -> Term {
+    let port_name = process.tuple_from_slice(&[
+        Atom::str_to_term("spawn_executable"),
+        process.charlist_from_str("/bin/cat"),
+    ]);
+
+    open_port_2::result(process, port_name, Term::NIL).unwrap()
+}
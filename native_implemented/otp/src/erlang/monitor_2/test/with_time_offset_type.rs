@@ -0,0 +1,151 @@
+use super::*;
+
+use liblumen_alloc::erts::time::Milliseconds;
+
+use crate::erlang::demonitor_2;
+use crate::runtime::time::{monotonic, offset};
+
+#[test]
+fn without_clock_service_item_errors_badarg() {
+    with_process_arc(|monitoring_arc_process| {
+        let item = Atom::str_to_term("clock");
+
+        assert_badarg!(
+            result(&monitoring_arc_process, r#type(), item),
+            "time_offset item (clock) is not clock_service"
+        );
+    });
+}
+
+#[test]
+fn with_clock_service_item_returns_reference() {
+    with_process_arc(|monitoring_arc_process| {
+        let item = Atom::str_to_term("clock_service");
+
+        let monitor_reference_result = result(&monitoring_arc_process, r#type(), item);
+
+        assert!(monitor_reference_result.is_ok());
+
+        let monitor_reference = monitor_reference_result.unwrap();
+
+        assert!(monitor_reference.is_reference());
+    });
+}
+
+#[test]
+fn with_clock_service_item_sends_change_message_when_time_offset_changes() {
+    with_process_arc(|monitoring_arc_process| {
+        let monitor_reference = result(&monitoring_arc_process, r#type(), clock_service()).unwrap();
+
+        change_time_offset();
+
+        assert!(has_change_message(
+            &monitoring_arc_process,
+            monitor_reference
+        ));
+    });
+}
+
+#[test]
+fn with_clock_service_item_demonitor_with_flush_removes_change_message() {
+    with_process_arc(|monitoring_arc_process| {
+        let monitor_reference = result(&monitoring_arc_process, r#type(), clock_service()).unwrap();
+
+        change_time_offset();
+
+        assert!(has_change_message(
+            &monitoring_arc_process,
+            monitor_reference
+        ));
+
+        let options = monitoring_arc_process.list_from_slice(&[Atom::str_to_term("flush")]);
+
+        assert_eq!(
+            demonitor_2::result(&monitoring_arc_process, monitor_reference, options),
+            Ok(true.into())
+        );
+        assert!(!has_change_message(
+            &monitoring_arc_process,
+            monitor_reference
+        ));
+
+        change_time_offset();
+
+        assert!(!has_change_message(
+            &monitoring_arc_process,
+            monitor_reference
+        ));
+    });
+}
+
+#[test]
+fn with_clock_service_item_demonitor_with_flush_and_info_returns_false_after_flush() {
+    with_process_arc(|monitoring_arc_process| {
+        let monitor_reference = result(&monitoring_arc_process, r#type(), clock_service()).unwrap();
+
+        change_time_offset();
+
+        let options = monitoring_arc_process
+            .list_from_slice(&[Atom::str_to_term("flush"), Atom::str_to_term("info")]);
+
+        assert_eq!(
+            demonitor_2::result(&monitoring_arc_process, monitor_reference, options),
+            Ok(false.into())
+        );
+    });
+}
+
+#[test]
+fn with_clock_service_item_demonitor_from_other_process_leaves_monitor() {
+    with_process_arc(|monitoring_arc_process| {
+        let monitor_reference = result(&monitoring_arc_process, r#type(), clock_service()).unwrap();
+        let other_arc_process = test::process::child(&monitoring_arc_process);
+        let options = other_arc_process.list_from_slice(&[Atom::str_to_term("info")]);
+
+        assert_eq!(
+            demonitor_2::result(&other_arc_process, monitor_reference, options),
+            Ok(false.into())
+        );
+
+        change_time_offset();
+
+        assert!(has_change_message(
+            &monitoring_arc_process,
+            monitor_reference
+        ));
+    });
+}
+
+fn r#type() -> Term {
+    Atom::str_to_term("time_offset")
+}
+
+/// Jumps the monotonic time of this thread ahead, which moves the time offset back by as much,
+/// and notifies the monitors
+fn change_time_offset() {
+    monotonic::freeze_at(monotonic::freeze() + Milliseconds(100_000));
+    offset::check();
+}
+
+fn clock_service() -> Term {
+    Atom::str_to_term("clock_service")
+}
+
+/// The new time offset depends on when the system time was read, so only the rest of
+/// `{'CHANGE', MonitorReference, time_offset, clock_service, NewTimeOffset}` is matched
+fn has_change_message(process: &Process, monitor_reference: Term) -> bool {
+    process.acquire_mailbox().borrow().iter().any(|message| {
+        let result_tuple: Result<Boxed<Tuple>, _> = (*message.data()).try_into();
+
+        match result_tuple {
+            Ok(tuple) => {
+                tuple.len() == 5
+                    && tuple[0] == Atom::str_to_term("CHANGE")
+                    && tuple[1] == monitor_reference
+                    && tuple[2] == r#type()
+                    && tuple[3] == clock_service()
+            }
+            Err(_) => false,
+        }
+    })
+}
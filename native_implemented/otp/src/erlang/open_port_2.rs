@@ -0,0 +1,63 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::io;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::port::{self, string_from_term, Options};
+
+const PORT_NAME_CONTEXT: &str = "supported port names are {spawn_executable, FileName}";
+
+#[native_implemented::function(erlang:open_port/2)]
+pub fn result(process: &Process, port_name: Term, options: Term) -> exception::Result<Term> {
+    let path = path_from_port_name(port_name)?;
+    let options: Options = options
+        .try_into()
+        .with_context(|| format!("options ({}) is not a valid list of port options", options))?;
+
+    match port::open(process, path.clone(), options) {
+        Ok(port) => Ok(port.encode()?),
+        Err(err) => Err(error(
+            Atom::str_to_term(posix_reason(&err)),
+            None,
+            Trace::capture(),
+            Some(anyhow!("could not spawn executable ({}): {}", path, err).into()),
+        )
+        .into()),
+    }
+}
+
+fn path_from_port_name(port_name: Term) -> exception::Result<String> {
+    let tuple = term_try_into_tuple("port_name", port_name).context(PORT_NAME_CONTEXT)?;
+
+    if tuple.len() == 2 {
+        let kind = term_try_into_atom("port_name kind", tuple[0]).context(PORT_NAME_CONTEXT)?;
+
+        match kind.name() {
+            "spawn_executable" => string_from_term(tuple[1])
+                .with_context(|| format!("FileName ({}) is not a string", tuple[1]))
+                .map_err(From::from),
+            name => Err(TryAtomFromTermError(name))
+                .context(PORT_NAME_CONTEXT)
+                .map_err(From::from),
+        }
+    } else {
+        Err(anyhow!("port_name ({}) is not a 2-tuple", port_name).into())
+    }
+}
+
+fn posix_reason(err: &io::Error) -> &'static str {
+    match err.kind() {
+        io::ErrorKind::NotFound => "enoent",
+        io::ErrorKind::PermissionDenied => "eacces",
+        _ => "einval",
+    }
+}
@@ -0,0 +1,174 @@
+use std::thread;
+use std::time::Duration;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{Exception, RuntimeException};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::open_port_2::result;
+use crate::erlang::{port_close_1, port_command_2, port_info_2};
+use crate::test::{self, has_message, with_process};
+
+#[test]
+fn without_spawn_executable_port_name_errors_badarg() {
+    with_process(|process| {
+        let port_name = process
+            .tuple_from_slice(&[Atom::str_to_term("spawn"), process.charlist_from_str("cat")]);
+
+        assert_badarg!(
+            result(process, port_name, Term::NIL),
+            "supported port names are {spawn_executable, FileName}"
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("nouse_stdio")]);
+
+        assert_badarg!(
+            result(process, cat_port_name(process), options),
+            "supported options are"
+        );
+    });
+}
+
+#[test]
+fn with_missing_executable_errors_enoent() {
+    with_process(|process| {
+        let port_name = process.tuple_from_slice(&[
+            Atom::str_to_term("spawn_executable"),
+            process.charlist_from_str("/nonexistent/executable"),
+        ]);
+
+        match result(process, port_name, Term::NIL) {
+            Err(Exception::Runtime(RuntimeException::Error(ref error))) => {
+                assert_eq!(error.reason(), atom!("enoent"))
+            }
+            other => panic!("Expected enoent error, but got {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn with_packet_sends_data_to_owner_until_closed() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("packet"), process.integer(2)]),
+            Atom::str_to_term("binary"),
+        ]);
+        let port = result(process, cat_port_name(process), options).unwrap();
+
+        assert!(port.is_port());
+
+        let data = process.binary_from_str("hello");
+
+        assert_eq!(port_command_2::result(port, data), Ok(true.into()));
+
+        let event = process.tuple_from_slice(&[atom!("data"), data]);
+        let message = process.tuple_from_slice(&[port, event]);
+
+        assert!(
+            wait_for_message(process, message),
+            "Mailbox does not contain {:?}",
+            message
+        );
+
+        assert_eq!(port_close_1::result(process, port), Ok(true.into()));
+        assert_eq!(
+            port_info_2::result(process, port, Atom::str_to_term("name")),
+            Ok(atom!("undefined"))
+        );
+        assert_badarg!(port_command_2::result(port, data), "is not open");
+    });
+}
+
+#[test]
+fn with_packet_closing_from_process_other_than_connected_errors_badarg() {
+    with_process(|process| {
+        let port = result(process, cat_port_name(process), Term::NIL).unwrap();
+        let other_process = test::process::child(process);
+
+        assert_badarg!(
+            port_close_1::result(&other_process, port),
+            "is connected to another process"
+        );
+        assert_eq!(port_close_1::result(process, port), Ok(true.into()));
+    });
+}
+
+#[test]
+fn with_exit_status_when_executable_is_killed_sends_128_plus_signal_to_owner() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[
+            sh_args(process, "kill -9 $$"),
+            Atom::str_to_term("exit_status"),
+        ]);
+        let port = result(process, sh_port_name(process), options).unwrap();
+
+        let event = process.tuple_from_slice(&[atom!("exit_status"), process.integer(128 + 9)]);
+        let message = process.tuple_from_slice(&[port, event]);
+
+        assert!(
+            wait_for_message(process, message),
+            "Mailbox does not contain {:?}",
+            message
+        );
+    });
+}
+
+#[test]
+fn when_executable_exits_with_owner_trapping_exits_sends_exit_normal_to_owner() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        let options = process.list_from_slice(&[sh_args(process, "exit 0")]);
+        let port = result(process, sh_port_name(process), options).unwrap();
+
+        let message = process.tuple_from_slice(&[atom!("EXIT"), port, atom!("normal")]);
+
+        assert!(
+            wait_for_message(process, message),
+            "Mailbox does not contain {:?}",
+            message
+        );
+    });
+}
+
+fn cat_port_name(process: &Process) -> Term {
+    process.tuple_from_slice(&[
+        Atom::str_to_term("spawn_executable"),
+        process.charlist_from_str("/bin/cat"),
+    ])
+}
+
+fn sh_args(process: &Process, command: &str) -> Term {
+    process.tuple_from_slice(&[
+        Atom::str_to_term("args"),
+        process.list_from_slice(&[
+            process.charlist_from_str("-c"),
+            process.charlist_from_str(command),
+        ]),
+    ])
+}
+
+fn sh_port_name(process: &Process) -> Term {
+    process.tuple_from_slice(&[
+        Atom::str_to_term("spawn_executable"),
+        process.charlist_from_str("/bin/sh"),
+    ])
+}
+
+fn wait_for_message(process: &Process, message: Term) -> bool {
+    for _ in 0..100 {
+        if has_message(process, message) {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    false
+}
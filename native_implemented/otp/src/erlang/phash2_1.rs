@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::hash::make_hash2;
+
+/// `phash2/1` returns a hash in `0..2^27`.
+const MASK: u32 = (1 << 27) - 1;
+
+#[native_implemented::function(erlang:phash2/1)]
+pub fn result(process: &Process, term: Term) -> Term {
+    process.integer((make_hash2(term) & MASK) as u64)
+}
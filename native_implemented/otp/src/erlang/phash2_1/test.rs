@@ -0,0 +1,87 @@
+use std::convert::TryInto;
+use std::panic::{self, AssertUnwindSafe};
+
+use proptest::strategy::Just;
+use proptest::{prop_assert, prop_assert_eq};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::{phash2_1, phash2_2};
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn returns_non_negative_integer_less_than_2_pow_27() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            let hash_u64: u64 = phash2_1::result(&arc_process, term).try_into().unwrap();
+
+            prop_assert!(hash_u64 < (1 << 27));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn is_phash2_2_with_2_pow_27_range() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            prop_assert_eq!(
+                Ok(phash2_1::result(&arc_process, term)),
+                phash2_2::result(&arc_process, term, arc_process.integer(1 << 27))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_any_term_does_not_panic() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            let caught =
+                panic::catch_unwind(AssertUnwindSafe(|| phash2_1::result(&arc_process, term)));
+
+            prop_assert!(caught.is_ok(), "phash2({}) panicked", term);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_resource_returns_same_hash_for_same_resource() {
+    with_process(|process| {
+        let resource = process.resource(0_u8);
+
+        assert_eq!(
+            phash2_1::result(process, resource),
+            phash2_1::result(process, resource)
+        );
+    });
+}
+
+#[test]
+fn with_atom_returns_atom_table_hash() {
+    with_process(|process| {
+        assert_eq!(
+            phash2_1::result(process, Atom::str_to_term("a")),
+            process.integer(97)
+        );
+    });
+}
+
+#[test]
+fn with_empty_list_returns_reference_hash() {
+    with_process(|process| {
+        assert_eq!(
+            phash2_1::result(process, Term::NIL),
+            process.integer(113_427_502)
+        );
+    });
+}
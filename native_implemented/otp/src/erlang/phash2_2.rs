@@ -0,0 +1,17 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::hash::{make_hash2, term_try_into_range};
+
+/// Returns a hash in `0..Range`.
+#[native_implemented::function(erlang:phash2/2)]
+pub fn result(process: &Process, term: Term, range: Term) -> exception::Result<Term> {
+    let range_u64 = term_try_into_range(range)?;
+    let hash = (make_hash2(term) as u64) % range_u64;
+
+    Ok(process.integer(hash))
+}
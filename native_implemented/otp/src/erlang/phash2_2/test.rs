@@ -0,0 +1,215 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+use proptest::{prop_assert, prop_assert_eq};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::phash2_2::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_integer_range_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::is_not_integer(arc_process),
+            )
+        },
+        |(arc_process, term, range)| {
+            prop_assert_badarg!(
+                result(&arc_process, term, range),
+                format!("range ({}) is not an integer in 1..2^32", range)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_range_outside_1_to_2_pow_32_errors_badarg() {
+    with_process(|process| {
+        let term = Atom::str_to_term("a");
+
+        for range in &[
+            process.integer(-1),
+            process.integer(0),
+            process.integer((1_u64 << 32) + 1),
+        ] {
+            assert_badarg!(
+                result(process, term, *range),
+                format!("range ({}) is not an integer in 1..2^32", range)
+            );
+        }
+    });
+}
+
+#[test]
+fn with_range_returns_non_negative_integer_less_than_range() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                1_u64..=(1 << 32),
+            )
+        },
+        |(arc_process, term, range)| {
+            let hash = result(&arc_process, term, arc_process.integer(range)).unwrap();
+            let hash_u64: u64 = hash.try_into().unwrap();
+
+            prop_assert!(hash_u64 < range);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_is_independent_of_insertion_order() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(
+                    strategy::term(arc_process.clone()),
+                    strategy::size_range(),
+                ),
+            )
+        },
+        |(arc_process, value_vec)| {
+            let range = arc_process.integer(1_u64 << 32);
+            // Keys must be unique, so that both maps have the same pairs
+            let mut key_value_vec: Vec<(Term, Term)> = value_vec
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (arc_process.integer(index), value))
+                .collect();
+            let map = arc_process.map_from_slice(&key_value_vec);
+            key_value_vec.reverse();
+            let reversed_map = arc_process.map_from_slice(&key_value_vec);
+
+            prop_assert_eq!(
+                result(&arc_process, map, range),
+                result(&arc_process, reversed_map, range)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+// With a range of 2^32, the hash is returned unchanged, so it can be compared to the values of the
+// BEAM, which hashes the same on every architecture.
+#[test]
+fn with_2_pow_32_range_returns_reference_hash() {
+    with_process(|process| {
+        let range = process.integer(1_u64 << 32);
+
+        for (term, hash) in reference_vectors(process) {
+            assert_eq!(
+                result(process, term, range),
+                Ok(process.integer(hash)),
+                "phash2({}, 1 bsl 32)",
+                term
+            );
+        }
+    });
+}
+
+fn reference_vectors(process: &Process) -> Vec<(Term, u64)> {
+    vec![
+        (Atom::str_to_term("a"), 97),
+        (Atom::str_to_term("hello_world"), 84192276),
+        (Atom::str_to_term("é"), 233),
+        (Term::NIL, 3468870702),
+        (process.integer(0), 3175731469),
+        (process.integer(1), 539485162),
+        (process.integer(-1), 1117813597),
+        (process.integer((1 << 27) - 1), 4273352567),
+        (process.integer(1 << 27), 2232048528),
+        (process.integer(-(1 << 27)), 874979335),
+        (process.integer(-(1 << 27) - 1), 3118511698),
+        (process.integer(1_u64 << 32), 3731526247),
+        (process.integer((1_u128 << 64) + 1), 1578977979),
+        (process.integer(-((1_i128 << 64) + 1)), 3388414072),
+        (process.float(0.0), 30973154),
+        (process.float(-0.0), 30973154),
+        (process.float(1.0), 2652214599),
+        (process.float(-2.5), 2986951467),
+        (process.charlist_from_str("abc"), 3936729570),
+        (process.charlist_from_str("hello"), 2588224145),
+        (
+            process.list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")]),
+            2079940793,
+        ),
+        (
+            process.cons(process.integer(1), process.integer(2)),
+            1060938387,
+        ),
+        (
+            process.list_from_slice(&[process.integer(256), process.integer(1)]),
+            3755108779,
+        ),
+        (process.tuple_from_slice(&[]), 3075096148),
+        (
+            process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(1)]),
+            365146095,
+        ),
+        (
+            process.tuple_from_slice(&[process.tuple_from_slice(&[]), Term::NIL]),
+            192718273,
+        ),
+        (process.map_from_slice(&[]), 715998056),
+        (
+            process.map_from_slice(&[(Atom::str_to_term("a"), process.integer(1))]),
+            989808798,
+        ),
+        (
+            process.map_from_slice(&[
+                (Atom::str_to_term("b"), process.integer(2)),
+                (Atom::str_to_term("a"), process.integer(1)),
+            ]),
+            2675014907,
+        ),
+        (process.binary_from_bytes(&[]), 2802362398),
+        (process.binary_from_bytes(&[1, 2, 3]), 3212422926),
+        (
+            process.binary_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]),
+            4072933693,
+        ),
+        (
+            // <<1:3>>
+            process.subbinary_from_original(process.binary_from_bytes(&[0b0010_0000]), 0, 0, 0, 3),
+            3762033543,
+        ),
+        (
+            // <<255, 5:4>>
+            process.subbinary_from_original(
+                process.binary_from_bytes(&[255, 0b0101_0000]),
+                0,
+                0,
+                1,
+                4,
+            ),
+            2658239241,
+        ),
+        (Pid::make_term(1, 0).unwrap(), 1954394636),
+        (
+            process.tuple_from_slice(&[
+                process.list_from_slice(&[Atom::str_to_term("a")]),
+                process.binary_from_bytes(&[1]),
+                process.map_from_slice(&[(
+                    Atom::str_to_term("k"),
+                    process.list_from_slice(&[process.float(1.5)]),
+                )]),
+            ]),
+            452960125,
+        ),
+    ]
+}
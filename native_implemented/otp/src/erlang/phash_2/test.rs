@@ -0,0 +1,196 @@
+use std::convert::TryInto;
+use std::panic::{self, AssertUnwindSafe};
+
+use proptest::strategy::Just;
+use proptest::{prop_assert, prop_assert_eq};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::phash_2::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn with_any_term_does_not_panic() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            let range = arc_process.integer(1 << 27);
+            let caught =
+                panic::catch_unwind(AssertUnwindSafe(|| result(&arc_process, term, range)));
+
+            prop_assert!(caught.is_ok(), "phash({}, {}) panicked", term, range);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_integer_range_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::is_not_integer(arc_process),
+            )
+        },
+        |(arc_process, term, range)| {
+            prop_assert_badarg!(
+                result(&arc_process, term, range),
+                format!("range ({}) is not an integer in 1..2^32", range)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_range_returns_positive_integer_less_than_or_equal_to_range() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                1_u64..=(1 << 32),
+            )
+        },
+        |(arc_process, term, range)| {
+            let hash = result(&arc_process, term, arc_process.integer(range)).unwrap();
+            let hash_u64: u64 = hash.try_into().unwrap();
+
+            prop_assert!(1 <= hash_u64);
+            prop_assert!(hash_u64 <= range);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_range_1_returns_1() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, term)| {
+            prop_assert_eq!(
+                result(&arc_process, term, arc_process.integer(1)),
+                Ok(arc_process.integer(1))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+// With a range of 2^32, the hash + 1 is returned, so it can be compared to the values of the BEAM,
+// which hashes the same on every architecture.
+#[test]
+fn with_2_pow_32_range_returns_reference_hash_plus_1() {
+    with_process(|process| {
+        let range = process.integer(1_u64 << 32);
+
+        for (term, hash) in reference_vectors(process) {
+            assert_eq!(
+                result(process, term, range),
+                Ok(process.integer(hash + 1)),
+                "phash({}, 1 bsl 32)",
+                term
+            );
+        }
+    });
+}
+
+fn reference_vectors(process: &Process) -> Vec<(Term, u64)> {
+    vec![
+        (Atom::str_to_term("a"), 97),
+        (Atom::str_to_term("hello_world"), 84192276),
+        (Atom::str_to_term("é"), 233),
+        (Term::NIL, 1),
+        (process.integer(0), 0),
+        (process.integer(1), 2788898427),
+        (process.integer(-1), 1680185269),
+        (process.integer((1 << 27) - 1), 1192343452),
+        (process.integer(1 << 27), 2147483672),
+        (process.integer(-(1 << 27)), 2147489128),
+        (process.integer(-(1 << 27) - 1), 3827674397),
+        (process.integer(1_u64 << 32), 2788898427),
+        (process.integer((1_u128 << 64) + 1), 84743990),
+        (process.integer(-((1_i128 << 64) + 1)), 380438826),
+        (process.float(0.0), 0),
+        (process.float(-0.0), 0),
+        (process.float(1.0), 1072693248),
+        (process.float(-2.5), 3221487616),
+        (process.charlist_from_str("abc"), 3654580165),
+        (process.charlist_from_str("hello"), 2340352115),
+        (
+            process.list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")]),
+            667932112,
+        ),
+        (
+            process.cons(process.integer(1), process.integer(2)),
+            2402949551,
+        ),
+        (
+            process.list_from_slice(&[process.integer(256), process.integer(1)]),
+            3220643891,
+        ),
+        (process.tuple_from_slice(&[]), 0),
+        (
+            process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(1)]),
+            3187717804,
+        ),
+        (
+            process.tuple_from_slice(&[process.tuple_from_slice(&[]), Term::NIL]),
+            268439629,
+        ),
+        (process.map_from_slice(&[]), 984438667),
+        (
+            process.map_from_slice(&[(Atom::str_to_term("a"), process.integer(1))]),
+            1258249409,
+        ),
+        (
+            process.map_from_slice(&[
+                (Atom::str_to_term("b"), process.integer(2)),
+                (Atom::str_to_term("a"), process.integer(1)),
+            ]),
+            2943455518,
+        ),
+        (process.binary_from_bytes(&[]), 0),
+        (process.binary_from_bytes(&[1, 2, 3]), 687692589),
+        (
+            process.binary_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]),
+            792277795,
+        ),
+        (
+            // <<1:3>>
+            process.subbinary_from_original(process.binary_from_bytes(&[0b0010_0000]), 0, 0, 0, 3),
+            1345689960,
+        ),
+        (
+            // <<255, 5:4>>
+            process.subbinary_from_original(
+                process.binary_from_bytes(&[255, 0b0101_0000]),
+                0,
+                0,
+                1,
+                4,
+            ),
+            2284214807,
+        ),
+        (Pid::make_term(1, 0).unwrap(), 3341103953),
+        (
+            process.tuple_from_slice(&[
+                process.list_from_slice(&[Atom::str_to_term("a")]),
+                process.binary_from_bytes(&[1]),
+                process.map_from_slice(&[(
+                    Atom::str_to_term("k"),
+                    process.list_from_slice(&[process.float(1.5)]),
+                )]),
+            ]),
+            2729565355,
+        ),
+    ]
+}
@@ -0,0 +1,36 @@
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::port_command_2::term_try_into_open_port;
+use crate::runtime::port;
+
+/// Closes the port.  The connected process is not sent any more messages from the port.
+///
+/// Only the connected process may close the port.
+#[native_implemented::function(erlang:port_close/1)]
+pub fn result(process: &Process, port: Term) -> exception::Result<Term> {
+    let port = term_try_into_open_port(port)?;
+    let arc_control_block =
+        port::get(port).ok_or_else(|| anyhow!("port ({}) is not open", port))?;
+    let connected = arc_control_block.connected();
+
+    if connected != process.pid() {
+        return Err(anyhow!(
+            "port ({}) is connected to another process ({}), not the calling process ({})",
+            port,
+            connected,
+            process.pid()
+        )
+        .into());
+    }
+
+    if port::close(port, atom!("normal")) {
+        Ok(true.into())
+    } else {
+        Err(anyhow!("port ({}) is not open", port).into())
+    }
+}
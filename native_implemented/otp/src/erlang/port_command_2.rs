@@ -0,0 +1,41 @@
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::iolist_or_binary;
+use crate::runtime::port;
+use crate::runtime::registry;
+
+/// Sends `data` to the port's executable
+#[native_implemented::function(erlang:port_command/2)]
+pub fn result(port: Term, data: Term) -> exception::Result<Term> {
+    let port = term_try_into_open_port(port)?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    port::command(port, &bytes)
+        .with_context(|| format!("could not write data ({}) to port ({})", data, port))?;
+
+    Ok(true.into())
+}
+
+/// Resolves a port or the registered name of a port to a port that is still open
+pub fn term_try_into_open_port(port_or_name: Term) -> exception::Result<Port> {
+    let option_port = match port_or_name.decode()? {
+        TypedTerm::Port(port) => Some(port),
+        TypedTerm::Atom(name) => registry::atom_to_port(&name),
+        _ => {
+            return Err(TypeError)
+                .context(format!(
+                    "port ({}) is neither a port nor a registered name",
+                    port_or_name
+                ))
+                .map_err(From::from)
+        }
+    };
+
+    match option_port {
+        Some(port) if port::is_open(port) => Ok(port),
+        _ => Err(anyhow!("port ({}) is not open", port_or_name).into()),
+    }
+}
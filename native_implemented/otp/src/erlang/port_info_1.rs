@@ -0,0 +1,38 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::port_info_2;
+use crate::runtime::port;
+
+const ITEMS: &[&str] = &[
+    "registered_name",
+    "name",
+    "links",
+    "id",
+    "connected",
+    "input",
+    "output",
+    "os_pid",
+];
+
+/// Returns a list of `{Item, Info}` tuples about the port or `undefined` if the port is not open
+#[native_implemented::function(erlang:port_info/1)]
+pub fn result(process: &Process, port: Term) -> exception::Result<Term> {
+    match port_info_2::term_try_into_port(port)?.and_then(port::get) {
+        Some(arc_control_block) => {
+            let mut item_info_vec = Vec::with_capacity(ITEMS.len());
+
+            for item in ITEMS {
+                if let Some(item_info) = port_info_2::item_info(process, &arc_control_block, item)?
+                {
+                    item_info_vec.push(item_info);
+                }
+            }
+
+            Ok(process.list_from_slice(&item_info_vec))
+        }
+        None => Ok(atom!("undefined")),
+    }
+}
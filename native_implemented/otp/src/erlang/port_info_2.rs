@@ -0,0 +1,81 @@
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::port::{self, ControlBlock};
+use crate::runtime::registry;
+
+/// Returns `{Item, Info}` for the port or `undefined` if the port is not open
+#[native_implemented::function(erlang:port_info/2)]
+pub fn result(process: &Process, port: Term, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom("item", item)?;
+
+    match term_try_into_port(port)?.and_then(port::get) {
+        Some(arc_control_block) => {
+            match item_info(process, &arc_control_block, item_atom.name())? {
+                Some(item_info) => Ok(item_info),
+                // `registered_name` when not registered
+                None => Ok(Term::NIL),
+            }
+        }
+        None => Ok(atom!("undefined")),
+    }
+}
+
+/// Returns `None` for `registered_name` when the port is not registered, so that it can be
+/// skipped by `port_info/1`.
+pub fn item_info(
+    process: &Process,
+    control_block: &ControlBlock,
+    item: &'static str,
+) -> exception::Result<Option<Term>> {
+    let info = match item {
+        "connected" => control_block.connected().encode()?,
+        "id" => process.integer(control_block.port().as_usize()),
+        "input" => process.integer(control_block.input()),
+        "links" => {
+            let linked_pid_terms: Vec<Term> = control_block
+                .linked_pids()
+                .into_iter()
+                .map(|pid| pid.encode().unwrap())
+                .collect();
+
+            process.list_from_slice(&linked_pid_terms)
+        }
+        "name" => process.charlist_from_str(control_block.name()),
+        "os_pid" => process.integer(control_block.os_pid() as usize),
+        "output" => process.integer(control_block.output()),
+        "registered_name" => match *control_block.registered_name.lock() {
+            Some(name) => name.encode()?,
+            None => return Ok(None),
+        },
+        name => {
+            return Err(TryAtomFromTermError(name))
+                .context("supported items are connected, id, input, links, name, os_pid, output, or registered_name")
+                .map_err(From::from)
+        }
+    };
+
+    let tag = Atom::str_to_term(item);
+
+    Ok(Some(process.tuple_from_slice(&[tag, info])))
+}
+
+/// Unlike `port_command/2` and `port_close/1`, `port_info` returns `undefined` for ports that are
+/// not open, so the port does not need to be open.
+pub fn term_try_into_port(port_or_name: Term) -> exception::Result<Option<Port>> {
+    match port_or_name.decode()? {
+        TypedTerm::Port(port) => Ok(Some(port)),
+        TypedTerm::Atom(name) => Ok(registry::atom_to_port(&name)),
+        _ => Err(TypeError)
+            .context(format!(
+                "port ({}) is neither a port nor a registered name",
+                port_or_name
+            ))
+            .map_err(From::from),
+    }
+}
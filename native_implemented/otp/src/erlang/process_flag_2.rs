@@ -1,12 +1,17 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::convert::TryInto;
+
 use anyhow::*;
 
 use liblumen_alloc::erts::exception;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Priority, Process, SavedCalls};
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::erlang::process_info_2::{
+    max_heap_size_to_term, message_queue_data_to_term, priority_to_term,
+};
 use crate::runtime::context::*;
 
 #[native_implemented::function(erlang:process_flag/2)]
@@ -14,14 +19,87 @@ pub fn result(process: &Process, flag: Term, value: Term) -> exception::Result<T
     let flag_atom = term_try_into_atom!(flag)?;
 
     match flag_atom.name() {
-        "error_handler" => unimplemented!(),
-        "max_heap_size" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "save_calls" => unimplemented!(),
-        "sensitive" => unimplemented!(),
+        "error_handler" => {
+            let module = term_try_into_atom("error_handler value", value)?;
+
+            Ok(process.set_error_handler(module).encode().unwrap())
+        }
+        "max_heap_size" => {
+            let max_heap_size: MaxHeapSize =
+                value.try_into().context("max_heap_size value")?;
+
+            if max_heap_size.is_enabled() && max_heap_size.size < process.min_heap_size() {
+                return Err(anyhow!(
+                    "max_heap_size size ({}) is less than min_heap_size ({})",
+                    max_heap_size.size,
+                    process.min_heap_size()
+                )
+                .into());
+            }
+
+            let old_max_heap_size = process.set_max_heap_size(max_heap_size);
+
+            Ok(max_heap_size_to_term(process, old_max_heap_size))
+        }
+        "message_queue_data" => {
+            let message_queue_data: MessageQueueData = value
+                .try_into()
+                .context("message_queue_data value")?;
+
+            Ok(message_queue_data_to_term(
+                process.set_message_queue_data(message_queue_data),
+            ))
+        }
+        "min_bin_vheap_size" => {
+            let min_bin_vheap_size: usize = value
+                .try_into()
+                .with_context(|| term_is_not_non_negative_integer("min_bin_vheap_size value", value))?;
+
+            Ok(process.integer(process.set_min_bin_vheap_size(min_bin_vheap_size)))
+        }
+        "min_heap_size" => {
+            let min_heap_size: usize = value
+                .try_into()
+                .with_context(|| term_is_not_non_negative_integer("min_heap_size value", value))?;
+            let max_heap_size = process.max_heap_size();
+
+            if max_heap_size.is_enabled() && max_heap_size.size < min_heap_size {
+                return Err(anyhow!(
+                    "min_heap_size ({}) is greater than max_heap_size size ({})",
+                    min_heap_size,
+                    max_heap_size.size
+                )
+                .into());
+            }
+
+            Ok(process.integer(process.set_min_heap_size(min_heap_size)))
+        }
+        "priority" => {
+            let priority: Priority = value.try_into().context("priority value")?;
+
+            Ok(priority_to_term(process.set_priority(priority)))
+        }
+        "save_calls" => {
+            let n: usize = value
+                .try_into()
+                .with_context(|| term_is_not_non_negative_integer("save_calls value", value))?;
+
+            if SavedCalls::MAX_CAPACITY < n {
+                return Err(anyhow!(
+                    "save_calls value ({}) is greater than {}",
+                    n,
+                    SavedCalls::MAX_CAPACITY
+                )
+                .into());
+            }
+
+            Ok(process.integer(process.save_calls(n)))
+        }
+        "sensitive" => {
+            let value_bool: bool = term_try_into_bool("sensitive value", value)?;
+
+            Ok(process.set_sensitive(value_bool).into())
+        }
         "trap_exit" => {
             let value_bool: bool = term_try_into_bool("trap_exit value", value)?;
 
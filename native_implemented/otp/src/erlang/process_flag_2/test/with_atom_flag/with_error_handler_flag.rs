@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn without_atom_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_is_not_atom!(
+                result(&arc_process, flag(), value),
+                "error_handler value",
+                value
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_atom_value_returns_old_value() {
+    with_process(|process| {
+        let module = Atom::str_to_term("custom_error_handler");
+
+        assert_eq!(
+            result(process, flag(), module),
+            Ok(Atom::str_to_term("error_handler"))
+        );
+        assert_eq!(
+            result(process, flag(), Atom::str_to_term("error_handler")),
+            Ok(module)
+        );
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("error_handler")
+}
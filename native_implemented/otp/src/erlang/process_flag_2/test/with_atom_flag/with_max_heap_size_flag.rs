@@ -0,0 +1,39 @@
+use super::*;
+
+use liblumen_alloc::atom;
+
+#[test]
+fn with_size_less_than_min_heap_size_errors_badarg() {
+    with_process(|process| {
+        let size = process.min_heap_size() - 1;
+
+        assert_badarg!(
+            result(process, flag(), process.integer(size)),
+            format!(
+                "max_heap_size size ({}) is less than min_heap_size ({})",
+                size,
+                process.min_heap_size()
+            )
+        );
+    });
+}
+
+#[test]
+fn with_size_returns_old_value_as_map() {
+    with_process(|process| {
+        let size = process.min_heap_size() + 1;
+
+        let old_value = result(process, flag(), process.integer(size)).unwrap();
+        let old_map: Boxed<Map> = old_value.try_into().unwrap();
+
+        assert_eq!(old_map.get(atom!("size")), Some(process.integer(0)));
+        assert_eq!(old_map.get(atom!("kill")), Some(true.into()));
+        assert_eq!(old_map.get(atom!("error_logger")), Some(true.into()));
+
+        assert_eq!(process.max_heap_size().size, size);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("max_heap_size")
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn without_message_queue_data_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::atom().prop_filter(
+                    "Value cannot be off_heap or on_heap",
+                    |value| {
+                        let value_atom: Atom = (*value).try_into().unwrap();
+
+                        match value_atom.name() {
+                            "off_heap" | "on_heap" => false,
+                            _ => true,
+                        }
+                    },
+                ),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                "supported message_queue_data are off_heap or on_heap"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_message_queue_data_value_returns_old_value() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, flag(), Atom::str_to_term("off_heap")),
+            Ok(Atom::str_to_term("on_heap"))
+        );
+        assert_eq!(
+            result(process, flag(), Atom::str_to_term("on_heap")),
+            Ok(Atom::str_to_term("off_heap"))
+        );
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("message_queue_data")
+}
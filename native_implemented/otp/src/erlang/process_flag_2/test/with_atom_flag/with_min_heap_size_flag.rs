@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn without_non_negative_integer_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                format!(
+                    "min_heap_size value ({}) is not a non-negative integer",
+                    value
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_non_negative_integer_value_returns_old_value() {
+    with_process(|process| {
+        let old_min_heap_size = process.min_heap_size();
+        let min_heap_size = old_min_heap_size + 1;
+
+        assert_eq!(
+            result(process, flag(), process.integer(min_heap_size)),
+            Ok(process.integer(old_min_heap_size))
+        );
+        assert_eq!(process.min_heap_size(), min_heap_size);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("min_heap_size")
+}
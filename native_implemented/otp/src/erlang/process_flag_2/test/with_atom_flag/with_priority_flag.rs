@@ -0,0 +1,49 @@
+use super::*;
+
+use liblumen_alloc::erts::process::Priority;
+
+#[test]
+fn without_priority_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()).prop_filter(
+                    "Value cannot be a priority",
+                    |value| match value.decode().unwrap() {
+                        TypedTerm::Atom(atom) => match atom.name() {
+                            "low" | "normal" | "high" | "max" => false,
+                            _ => true,
+                        },
+                        _ => true,
+                    },
+                ),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(result(&arc_process, flag(), value), "priority value");
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_priority_value_returns_old_value() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, flag(), Atom::str_to_term("high")),
+            Ok(Atom::str_to_term("normal"))
+        );
+        assert_eq!(process.priority(), Priority::High);
+        assert_eq!(
+            result(process, flag(), Atom::str_to_term("low")),
+            Ok(Atom::str_to_term("high"))
+        );
+        assert_eq!(process.priority(), Priority::Low);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("priority")
+}
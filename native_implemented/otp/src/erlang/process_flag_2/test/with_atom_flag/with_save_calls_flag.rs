@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn with_value_greater_than_max_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            result(process, flag(), process.integer(10001)),
+            "save_calls value (10001) is greater than 10000"
+        );
+    });
+}
+
+#[test]
+fn with_value_returns_old_value() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, flag(), process.integer(10)),
+            Ok(process.integer(0))
+        );
+        assert_eq!(
+            result(process, flag(), process.integer(0)),
+            Ok(process.integer(10))
+        );
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("save_calls")
+}
@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn without_boolean_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_boolean(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_is_not_boolean!(
+                result(&arc_process, flag(), value),
+                "sensitive value",
+                value
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_boolean_value_returns_old_value() {
+    with_process(|process| {
+        assert_eq!(result(process, flag(), true.into()), Ok(false.into()));
+        assert!(process.is_sensitive());
+        assert_eq!(result(process, flag(), false.into()), Ok(true.into()));
+        assert!(!process.is_sensitive());
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("sensitive")
+}
@@ -0,0 +1,63 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::process_info_2::process_info;
+use crate::runtime::registry::pid_to_process;
+
+#[native_implemented::function(erlang:process_info/1)]
+pub fn result(process: &Process, pid: Term) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid!(pid)?;
+
+    if process.pid() == pid_pid {
+        process_info_list(process, process)
+    } else {
+        match pid_to_process(&pid_pid) {
+            Some(pid_arc_process) => process_info_list(process, &pid_arc_process),
+            None => Ok(atom!("undefined")),
+        }
+    }
+}
+
+// Private
+
+/// The items that `process_info/1` returns after `registered_name`, if the process is registered
+const ITEMS: &[&str] = &[
+    "current_function",
+    "initial_call",
+    "status",
+    "message_queue_len",
+    "links",
+    "dictionary",
+    "trap_exit",
+    "error_handler",
+    "priority",
+    "group_leader",
+    "total_heap_size",
+    "heap_size",
+    "stack_size",
+    "reductions",
+    "garbage_collection",
+];
+
+fn process_info_list(process: &Process, info_process: &Process) -> exception::Result<Term> {
+    let mut vec = Vec::with_capacity(ITEMS.len() + 1);
+
+    if info_process.registered_name.read().is_some() {
+        vec.push(process_info(
+            process,
+            info_process,
+            Atom::from_str("registered_name"),
+        )?);
+    }
+
+    for item in ITEMS {
+        vec.push(process_info(process, info_process, Atom::from_str(item))?);
+    }
+
+    Ok(process.list_from_slice(&vec))
+}
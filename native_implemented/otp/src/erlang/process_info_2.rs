@@ -1,16 +1,22 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::mem;
+use std::sync::atomic::Ordering;
+
 use anyhow::*;
 
 use liblumen_alloc::atom;
 use liblumen_alloc::borrow::clone_to_process::CloneToProcess;
 use liblumen_alloc::erts::exception::{self, InternalResult};
 use liblumen_alloc::erts::message::{self, Message};
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{
+    MaxHeapSize, MessageQueueData, Priority, Process, Status, TraceFlags,
+};
 use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::ModuleFunctionArity;
 
-use crate::runtime::registry::pid_to_process;
+use crate::runtime::registry::{pid_to_process, processes};
 
 #[native_implemented::function(erlang:process_info/2)]
 pub fn result(process: &Process, pid: Term, item: Term) -> exception::Result<Term> {
@@ -18,56 +24,63 @@ pub fn result(process: &Process, pid: Term, item: Term) -> exception::Result<Ter
     let item_atom: Atom = term_try_into_atom!(item)?;
 
     if process.pid() == pid_pid {
-        process_info(process, item_atom)
+        process_info(process, process, item_atom)
     } else {
         match pid_to_process(&pid_pid) {
-            Some(pid_arc_process) => process_info(&pid_arc_process, item_atom),
+            Some(pid_arc_process) => process_info(process, &pid_arc_process, item_atom),
             None => Ok(atom!("undefined")),
         }
     }
     .map_err(From::from)
 }
 
-// Private
-
-fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
+/// The `{item, value}` tuple for `item` of `info_process` allocated on `process`, which is
+/// different from `info_process` when inspecting another process.
+pub(in crate::erlang) fn process_info(
+    process: &Process,
+    info_process: &Process,
+    item: Atom,
+) -> InternalResult<Term> {
     match item.name() {
-        "backtrace" => unimplemented!(),
-        "binary" => unimplemented!(),
-        "catchlevel" => unimplemented!(),
-        "current_function" => unimplemented!(),
-        "current_location" => unimplemented!(),
-        "current_stacktrace" => unimplemented!(),
-        "dictionary" => unimplemented!(),
-        "error_handler" => unimplemented!(),
-        "garbage_collection" => unimplemented!(),
-        "garbage_collection_info" => unimplemented!(),
-        "group_leader" => unimplemented!(),
-        "heap_size" => unimplemented!(),
-        "initial_call" => unimplemented!(),
-        "links" => Ok(links(process)),
-        "last_calls" => unimplemented!(),
-        "memory" => unimplemented!(),
-        "message_queue_len" => unimplemented!(),
-        "messages" => Ok(messages(process)),
-        "min_heap_size" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "monitored_by" => Ok(monitored_by(process)),
-        "monitors" => Ok(monitors(process)),
-        "message_queue_data" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "reductions" => unimplemented!(),
-        "registered_name" => Ok(registered_name(process)),
-        "sequential_trace_token" => unimplemented!(),
-        "stack_size" => unimplemented!(),
-        "status" => unimplemented!(),
-        "suspending" => unimplemented!(),
-        "total_heap_size" => unimplemented!(),
-        "trace" => unimplemented!(),
-        "trap_exit" => Ok(trap_exit(process)),
+        "backtrace" => Ok(backtrace(process, info_process)),
+        "binary" => Ok(binary(process, info_process)),
+        "catchlevel" => Err(anyhow!(
+            "catchlevel is not supported because native frames do not track catches"
+        )
+        .into()),
+        "current_function" => Ok(current_function(process, info_process)),
+        "current_location" => Ok(current_location(process, info_process)),
+        "current_stacktrace" => Ok(current_stacktrace(process, info_process)),
+        "dictionary" => Ok(dictionary(process, info_process)),
+        "error_handler" => Ok(error_handler(process, info_process)),
+        "garbage_collection" => Ok(garbage_collection(process, info_process)),
+        "garbage_collection_info" => Ok(garbage_collection_info(process, info_process)),
+        "group_leader" => Ok(group_leader(process, info_process)),
+        "heap_size" => Ok(heap_size(process, info_process)),
+        "initial_call" => Ok(initial_call(process, info_process)),
+        "links" => Ok(links(process, info_process)),
+        "last_calls" => Ok(last_calls(process, info_process)),
+        "memory" => Ok(memory(process, info_process)),
+        "message_queue_len" => Ok(message_queue_len(process, info_process)),
+        "messages" => Ok(messages(process, info_process)),
+        "min_heap_size" => Ok(min_heap_size(process, info_process)),
+        "min_bin_vheap_size" => Ok(min_bin_vheap_size(process, info_process)),
+        "monitored_by" => Ok(monitored_by(process, info_process)),
+        "monitors" => Ok(monitors(process, info_process)),
+        "message_queue_data" => Ok(message_queue_data(process, info_process)),
+        "priority" => Ok(priority(process, info_process)),
+        "reductions" => Ok(reductions(process, info_process)),
+        "registered_name" => Ok(registered_name(process, info_process)),
+        "sequential_trace_token" => Ok(sequential_trace_token(process, info_process)),
+        "stack_size" => Ok(stack_size(process, info_process)),
+        "status" => Ok(status(process, info_process)),
+        "suspending" => Ok(suspending(process, info_process)),
+        "total_heap_size" => Ok(total_heap_size(process, info_process)),
+        "trace" => Ok(trace(process, info_process)),
+        "trap_exit" => Ok(trap_exit(process, info_process)),
         name => Err(TryAtomFromTermError(name))
             .context(
-                "supported items are backtrace, binary, catchlevel, current_function, \
+                "supported items are backtrace, binary, current_function, \
                  current_location, current_stacktrace, dictionary, error_handler, \
                  garbage_collection, garbage_collection_info, group_leader, heap_size, \
                  initial_call, links, last_calls, memory, message_queue_len, messages, \
@@ -80,10 +93,194 @@ fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
     }
 }
 
-fn links(process: &Process) -> Term {
+// Private
+
+/// The stacktrace as text, from the current function to the initial call
+fn backtrace(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("backtrace");
+    let value = if info_process.is_sensitive() {
+        process.binary_from_str("")
+    } else {
+        process.binary_from_str(&info_process.stacktrace().to_string())
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+/// `{Id, Size, RefCount}` for each reference-counted binary on the heap
+fn binary(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("binary");
+
+    let vec: Vec<Term> = info_process
+        .binaries()
+        .into_iter()
+        .map(|(id, size, reference_count)| {
+            process.tuple_from_slice(&[
+                process.integer(id),
+                process.integer(size),
+                process.integer(reference_count),
+            ])
+        })
+        .collect();
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn current_function(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("current_function");
+    let value = match info_process.current_module_function_arity() {
+        Some(module_function_arity) => module_function_arity_tuple(process, &module_function_arity),
+        None => atom!("undefined"),
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn current_location(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("current_location");
+    let value = match info_process.current_module_function_arity() {
+        Some(module_function_arity) => process.tuple_from_slice(&[
+            module_function_arity.module.encode().unwrap(),
+            module_function_arity.function.encode().unwrap(),
+            process.integer(module_function_arity.arity),
+            Term::NIL,
+        ]),
+        None => atom!("undefined"),
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn current_stacktrace(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("current_stacktrace");
+
+    let value = if info_process.is_sensitive() {
+        Term::NIL
+    } else {
+        let location = Term::NIL;
+        let vec: Vec<Term> = info_process
+            .stacktrace()
+            .iter()
+            .map(|module_function_arity| {
+                process.tuple_from_slice(&[
+                    module_function_arity.module.encode().unwrap(),
+                    module_function_arity.function.encode().unwrap(),
+                    process.integer(module_function_arity.arity),
+                    location,
+                ])
+            })
+            .collect();
+
+        process.list_from_slice(&vec)
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn dictionary(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("dictionary");
+    let value = if info_process.is_sensitive() {
+        Term::NIL
+    } else if process.pid() == info_process.pid() {
+        info_process.get_entries()
+    } else {
+        info_process.get_entries_in(process)
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn error_handler(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("error_handler");
+    let value = info_process.error_handler().encode().unwrap();
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn garbage_collection(process: &Process, info_process: &Process) -> Term {
+    let max_heap_size = max_heap_size_to_term(process, info_process.max_heap_size());
+
+    let vec = [
+        process.tuple_from_slice(&[atom!("max_heap_size"), max_heap_size]),
+        process.tuple_from_slice(&[
+            atom!("min_bin_vheap_size"),
+            process.integer(info_process.min_bin_vheap_size()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("min_heap_size"),
+            process.integer(info_process.min_heap_size()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("fullsweep_after"),
+            process.integer(info_process.fullsweep_after()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("minor_gcs"),
+            process.integer(info_process.minor_gcs()),
+        ]),
+    ];
+
+    let tag = atom!("garbage_collection");
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+/// The sizes of the heap generations, heap fragments, stack and virtual binary heaps in words
+fn garbage_collection_info(process: &Process, info_process: &Process) -> Term {
+    let info = info_process.garbage_collection_info();
+
+    let vec: Vec<Term> = [
+        ("old_heap_block_size", info.old_heap_block_size),
+        ("heap_block_size", info.heap_block_size),
+        ("mbuf_size", info.mbuf_size),
+        ("recent_size", info.recent_size),
+        ("stack_size", info.stack_size),
+        ("old_heap_size", info.old_heap_size),
+        ("heap_size", info.heap_size),
+        ("bin_vheap_size", info.bin_vheap_size),
+        ("bin_vheap_block_size", info.bin_vheap_block_size),
+        ("bin_old_vheap_size", info.bin_old_vheap_size),
+        ("bin_old_vheap_block_size", info.bin_old_vheap_block_size),
+    ]
+    .iter()
+    .map(|(key, value)| {
+        process.tuple_from_slice(&[Atom::str_to_term(key), process.integer(*value)])
+    })
+    .collect();
+
+    let tag = atom!("garbage_collection_info");
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn group_leader(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("group_leader");
+    let value = info_process.get_group_leader_pid_term();
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn heap_size(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("heap_size");
+    let value = process.integer(info_process.heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn initial_call(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("initial_call");
+    let value = module_function_arity_tuple(process, &info_process.initial_module_function_arity);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn links(process: &Process, info_process: &Process) -> Term {
     let tag = atom!("links");
 
-    let vec: Vec<Term> = process
+    let vec: Vec<Term> = info_process
         .linked_pid_set
         .iter()
         .map(|ref_multi| ref_multi.encode().unwrap())
@@ -93,16 +290,71 @@ fn links(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn messages(process: &Process) -> Term {
+/// The size in bytes of the process control block, heap, heap fragments and stack
+fn memory(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("memory");
+    let words = info_process.total_heap_size() + info_process.stack_used();
+    let bytes = mem::size_of::<Process>() + words * mem::size_of::<Term>();
+    let value = process.integer(bytes);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn last_calls(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("last_calls");
+    let value = match info_process.saved_calls() {
+        Some(saved_calls) => {
+            let vec: Vec<Term> = saved_calls
+                .iter()
+                .map(|module_function_arity| {
+                    module_function_arity_tuple(process, module_function_arity)
+                })
+                .collect();
+
+            process.list_from_slice(&vec)
+        }
+        None => false.into(),
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn message_queue_data(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("message_queue_data");
+    let value = message_queue_data_to_term(info_process.message_queue_data());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn message_queue_len(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("message_queue_len");
+    let len = info_process.acquire_mailbox().borrow().len();
+    let value = process.integer(len);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn messages(process: &Process, info_process: &Process) -> Term {
     let tag = atom!("messages");
 
-    let vec: Vec<Term> = process
-        .mailbox
-        .lock()
+    if info_process.is_sensitive() {
+        return process.tuple_from_slice(&[tag, Term::NIL]);
+    }
+
+    let same_process = process.pid() == info_process.pid();
+
+    let vec: Vec<Term> = info_process
+        .acquire_mailbox()
         .borrow()
         .iter()
         .map(|message| match message {
-            Message::Process(message::Process { data }) => *data,
+            Message::Process(message::Process { data, .. }) => {
+                if same_process {
+                    *data
+                } else {
+                    data.clone_to_process(process)
+                }
+            }
             Message::HeapFragment(message::HeapFragment { data, .. }) => {
                 data.clone_to_process(process)
             }
@@ -114,10 +366,35 @@ fn messages(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn monitored_by(process: &Process) -> Term {
+fn min_bin_vheap_size(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("min_bin_vheap_size");
+    let value = process.integer(info_process.min_bin_vheap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn min_heap_size(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("min_heap_size");
+    let value = process.integer(info_process.min_heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn module_function_arity_tuple(
+    process: &Process,
+    module_function_arity: &ModuleFunctionArity,
+) -> Term {
+    process.tuple_from_slice(&[
+        module_function_arity.module.encode().unwrap(),
+        module_function_arity.function.encode().unwrap(),
+        process.integer(module_function_arity.arity),
+    ])
+}
+
+fn monitored_by(process: &Process, info_process: &Process) -> Term {
     let tag = atom!("monitored_by");
 
-    let vec: Vec<Term> = process
+    let vec: Vec<Term> = info_process
         .monitor_by_reference
         .iter()
         .map(|ref_multi| ref_multi.monitoring_pid().encode().unwrap())
@@ -127,11 +404,11 @@ fn monitored_by(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn monitors(process: &Process) -> Term {
+fn monitors(process: &Process, info_process: &Process) -> Term {
     let monitor_type = atom!("process");
     let mut vec = Vec::new();
 
-    for ref_multi in process.monitored_pid_by_reference.iter() {
+    for ref_multi in info_process.monitored_pid_by_reference.iter() {
         let pid = ref_multi.value();
         let monitor_value = pid.encode().unwrap();
         let monitor = process.tuple_from_slice(&[monitor_type, monitor_value]);
@@ -144,8 +421,22 @@ fn monitors(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn registered_name(process: &Process) -> Term {
-    match *process.registered_name.read() {
+fn priority(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("priority");
+    let value = priority_to_term(info_process.priority());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn reductions(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("reductions");
+    let value = process.integer(info_process.total_reductions.load(Ordering::SeqCst));
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn registered_name(process: &Process, info_process: &Process) -> Term {
+    match *info_process.registered_name.read() {
         Some(registered_name) => {
             let tag = atom!("registered_name");
             let value = registered_name.encode().unwrap();
@@ -156,9 +447,120 @@ fn registered_name(process: &Process) -> Term {
     }
 }
 
-fn trap_exit(process: &Process) -> Term {
+/// The token as returned by `seq_trace:get_token/0`, or `[]` when the process is not traced
+fn sequential_trace_token(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("sequential_trace_token");
+    let value = match info_process.seq_trace_token() {
+        Some(seq_trace_token) => seq_trace_token.to_term(process),
+        None => Term::NIL,
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn stack_size(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("stack_size");
+    let value = process.integer(info_process.stack_used());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn status(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("status");
+    let value = match *info_process.status.read() {
+        Status::Unrunnable | Status::Runnable | Status::Running | Status::Waiting
+            if info_process.is_suspended() =>
+        {
+            atom!("suspended")
+        }
+        Status::Unrunnable | Status::Runnable => atom!("runnable"),
+        Status::Running => atom!("running"),
+        Status::Waiting => atom!("waiting"),
+        Status::Exited | Status::SystemException(_) | Status::RuntimeException(_) => {
+            atom!("exiting")
+        }
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+/// `{Suspendee, ActiveSuspendCount, OutstandingSuspendCount}` for each process suspended by
+/// `info_process`.  Suspends take effect when the suspendee is next scheduled, instead of being
+/// outstanding until then, so `OutstandingSuspendCount` is always `0`.
+fn suspending(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("suspending");
+    let suspender_pid = info_process.pid();
+
+    let vec: Vec<Term> = processes()
+        .into_iter()
+        .filter_map(|suspendee_arc_process| {
+            let active_suspend_count = suspendee_arc_process.suspend_count_by(suspender_pid);
+
+            if 0 < active_suspend_count {
+                Some(process.tuple_from_slice(&[
+                    suspendee_arc_process.pid_term(),
+                    process.integer(active_suspend_count),
+                    process.integer(0),
+                ]))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn total_heap_size(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("total_heap_size");
+    let value = process.integer(info_process.total_heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+/// The mask of the flags set with `erlang:trace/3`, or `0` when the process is not traced
+fn trace(process: &Process, info_process: &Process) -> Term {
+    let tag = atom!("trace");
+    let flags: u16 = info_process
+        .tracing()
+        .map_or(TraceFlags::None, |tracing| tracing.flags)
+        .into();
+    let value = process.integer(flags as usize);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn trap_exit(process: &Process, info_process: &Process) -> Term {
     let tag = atom!("trap_exit");
-    let value = process.traps_exit().into();
+    let value = info_process.traps_exit().into();
 
     process.tuple_from_slice(&[tag, value])
 }
+
+pub(in crate::erlang) fn max_heap_size_to_term(
+    process: &Process,
+    max_heap_size: MaxHeapSize,
+) -> Term {
+    process.map_from_slice(&[
+        (atom!("error_logger"), max_heap_size.error_logger.into()),
+        (atom!("kill"), max_heap_size.kill.into()),
+        (atom!("size"), process.integer(max_heap_size.size)),
+    ])
+}
+
+pub(in crate::erlang) fn message_queue_data_to_term(message_queue_data: MessageQueueData) -> Term {
+    match message_queue_data {
+        MessageQueueData::OnHeap => atom!("on_heap"),
+        MessageQueueData::OffHeap => atom!("off_heap"),
+    }
+}
+
+pub(in crate::erlang) fn priority_to_term(priority: Priority) -> Term {
+    match priority {
+        Priority::Low => atom!("low"),
+        Priority::Normal => atom!("normal"),
+        Priority::High => atom!("high"),
+        Priority::Max => atom!("max"),
+    }
+}
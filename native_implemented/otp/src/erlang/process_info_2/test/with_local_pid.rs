@@ -1,4 +1,12 @@
+mod with_binary;
+mod with_catchlevel;
+mod with_dictionary;
+mod with_garbage_collection_info;
+mod with_message_queue_len;
 mod with_registered_name;
+mod with_sequential_trace_token;
+mod with_suspending;
+mod with_trace;
 
 use super::*;
 
@@ -10,7 +18,7 @@ fn without_supported_item_errors_badarg() {
                 let pid = arc_process.pid_term();
                 prop_assert_badarg!(
                     result(&arc_process, pid, item),
-                    "supported items are backtrace, binary, catchlevel, current_function, \
+                    "supported items are backtrace, binary, current_function, \
                      current_location, current_stacktrace, dictionary, error_handler, \
                      garbage_collection, garbage_collection_info, group_leader, heap_size, \
                      initial_call, links, last_calls, memory, message_queue_len, messages, \
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn returns_size_and_reference_count_of_reference_counted_binaries() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+
+        assert_eq!(
+            result(&parent_arc_process, child_arc_process.pid_term(), item()),
+            Ok(parent_arc_process.tuple_from_slice(&[item(), Term::NIL]))
+        );
+
+        // Large enough to be reference-counted instead of copied into the heap
+        child_arc_process.binary_from_bytes(&[0; 65]);
+
+        let info = result(&parent_arc_process, child_arc_process.pid_term(), item()).unwrap();
+        let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+        let binaries: Boxed<Cons> = info_tuple[1].try_into().unwrap();
+        let binary_tuples: Vec<Boxed<Tuple>> = binaries
+            .into_iter()
+            .map(|result| result.unwrap().try_into().unwrap())
+            .collect();
+
+        assert_eq!(binary_tuples.len(), 1);
+        assert_eq!(binary_tuples[0][1], parent_arc_process.integer(65));
+        assert_eq!(binary_tuples[0][2], parent_arc_process.integer(1));
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("binary")
+}
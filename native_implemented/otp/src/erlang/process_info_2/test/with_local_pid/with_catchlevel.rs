@@ -0,0 +1,13 @@
+use super::*;
+
+#[test]
+fn errors_badarg() {
+    with_process_arc(|arc_process| {
+        let item = Atom::str_to_term("catchlevel");
+
+        assert_badarg!(
+            result(&arc_process, arc_process.pid_term(), item),
+            "catchlevel is not supported because native frames do not track catches"
+        );
+    });
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn returns_heap_size_of_young_generation() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+
+        let info = result(&parent_arc_process, child_arc_process.pid_term(), item()).unwrap();
+        let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+
+        assert_eq!(info_tuple[0], item());
+
+        let key_values: Boxed<Cons> = info_tuple[1].try_into().unwrap();
+        let keys_values: Vec<(&'static str, usize)> = key_values
+            .into_iter()
+            .map(|result| {
+                let key_value: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+                let key: Atom = key_value[0].try_into().unwrap();
+
+                (key.name(), key_value[1].try_into().unwrap())
+            })
+            .collect();
+
+        assert_eq!(keys_values.len(), 11);
+        assert!(keys_values.contains(&("old_heap_block_size", 0)));
+        assert!(keys_values.contains(&("heap_block_size", child_arc_process.heap_size())));
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("garbage_collection_info")
+}
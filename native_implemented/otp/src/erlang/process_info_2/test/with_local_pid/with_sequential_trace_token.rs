@@ -0,0 +1,38 @@
+use super::*;
+
+use liblumen_alloc::erts::process::SeqTraceToken;
+
+#[test]
+fn without_token_returns_empty_list() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+
+        assert_eq!(
+            result(&parent_arc_process, child_arc_process.pid_term(), item()),
+            Ok(parent_arc_process.tuple_from_slice(&[item(), Term::NIL]))
+        );
+    });
+}
+
+#[test]
+fn with_token_returns_token() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+
+        let mut seq_trace_token = SeqTraceToken::new(child_arc_process.pid());
+        seq_trace_token
+            .set_label(Atom::str_to_term("label"))
+            .unwrap();
+        child_arc_process.set_seq_trace_token(Some(seq_trace_token));
+
+        assert_eq!(
+            result(&parent_arc_process, child_arc_process.pid_term(), item()),
+            Ok(parent_arc_process
+                .tuple_from_slice(&[item(), seq_trace_token.to_term(&parent_arc_process)]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("sequential_trace_token")
+}
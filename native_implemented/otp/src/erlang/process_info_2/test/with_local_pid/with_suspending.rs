@@ -0,0 +1,55 @@
+use super::*;
+
+use crate::runtime::process;
+
+#[test]
+fn returns_suspendees_with_suspend_counts() {
+    with_process_arc(|parent_arc_process| {
+        let suspender_arc_process = test::process::child(&parent_arc_process);
+        let suspendee_arc_process = test::process::child(&parent_arc_process);
+
+        assert_eq!(
+            result(
+                &parent_arc_process,
+                suspender_arc_process.pid_term(),
+                item()
+            ),
+            Ok(parent_arc_process.tuple_from_slice(&[item(), Term::NIL]))
+        );
+
+        process::suspend(&suspender_arc_process, &suspendee_arc_process);
+        process::suspend(&suspender_arc_process, &suspendee_arc_process);
+
+        assert_eq!(
+            result(
+                &parent_arc_process,
+                suspender_arc_process.pid_term(),
+                item()
+            ),
+            Ok(parent_arc_process.tuple_from_slice(&[
+                item(),
+                parent_arc_process.list_from_slice(&[parent_arc_process.tuple_from_slice(&[
+                    suspendee_arc_process.pid_term(),
+                    parent_arc_process.integer(2),
+                    parent_arc_process.integer(0)
+                ])])
+            ]))
+        );
+
+        process::resume(&suspender_arc_process, &suspendee_arc_process);
+        process::resume(&suspender_arc_process, &suspendee_arc_process);
+
+        assert_eq!(
+            result(
+                &parent_arc_process,
+                suspender_arc_process.pid_term(),
+                item()
+            ),
+            Ok(parent_arc_process.tuple_from_slice(&[item(), Term::NIL]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("suspending")
+}
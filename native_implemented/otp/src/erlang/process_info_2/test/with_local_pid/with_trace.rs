@@ -0,0 +1,40 @@
+use super::*;
+
+use liblumen_alloc::erts::process::{TraceFlags, Tracing};
+
+#[test]
+fn without_tracing_returns_zero() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+
+        assert_eq!(
+            result(&parent_arc_process, child_arc_process.pid_term(), item()),
+            Ok(parent_arc_process.tuple_from_slice(&[item(), parent_arc_process.integer(0)]))
+        );
+    });
+}
+
+#[test]
+fn with_tracing_returns_flag_mask() {
+    with_process_arc(|parent_arc_process| {
+        let child_arc_process = test::process::child(&parent_arc_process);
+        let flags = TraceFlags::Send | TraceFlags::Call;
+
+        child_arc_process.set_tracing(Some(Tracing {
+            tracer: parent_arc_process.pid(),
+            flags,
+        }));
+
+        let mask: u16 = flags.into();
+
+        assert_eq!(
+            result(&parent_arc_process, child_arc_process.pid_term(), item()),
+            Ok(parent_arc_process
+                .tuple_from_slice(&[item(), parent_arc_process.integer(mask as usize)]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("trace")
+}
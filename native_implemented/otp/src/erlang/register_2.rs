@@ -36,7 +36,13 @@ pub fn result(arc_process: Arc<Process>, name: Term, pid_or_port: Term) -> excep
                     pid_or_port
                 )
                 .into()),
-                TypedTerm::Port(_) => unimplemented!(),
+                TypedTerm::Port(port) => {
+                    if registry::put_atom_to_port(atom, port) {
+                        Ok(true.into())
+                    } else {
+                        Err(anyhow!("{} could not be registered as {}.  It may already be registered or not be open.", port, atom).into())
+                    }
+                }
                 TypedTerm::ExternalPort(_) => Err(anyhow!(
                     "{} is an external port, but only local ports can be registered",
                     pid_or_port
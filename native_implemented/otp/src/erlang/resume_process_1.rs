@@ -0,0 +1,29 @@
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::process;
+use crate::runtime::registry::pid_to_process;
+
+#[native_implemented::function(erlang:resume_process/1)]
+pub fn result(process: &Process, suspendee: Term) -> exception::Result<Term> {
+    let suspendee_pid = term_try_into_local_pid("suspendee", suspendee)?;
+
+    match pid_to_process(&suspendee_pid) {
+        Some(suspendee_arc_process) => {
+            if process::resume(process, &suspendee_arc_process) {
+                Ok(true.into())
+            } else {
+                Err(anyhow!(
+                    "suspendee ({}) is not suspended by the calling process",
+                    suspendee
+                )
+                .into())
+            }
+        }
+        None => Err(anyhow!("suspendee ({}) is not alive", suspendee).into()),
+    }
+}
@@ -1,4 +1,5 @@
 mod with_atom_destination;
+mod with_external_pid_destination;
 mod with_local_pid_destination;
 mod with_tuple_destination;
 
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn without_connection_returns_message() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::pid::external(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+            )
+        },
+        |(arc_process, destination, message)| {
+            prop_assert_eq!(result(&arc_process, destination, message), Ok(message));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn without_connection_returns_message() {
+    run!(
+        |arc_process| { (Just(arc_process.clone()), strategy::term(arc_process)) },
+        |(arc_process, message)| {
+            let name = registered_name();
+            let destination =
+                arc_process.tuple_from_slice(&[name, Atom::str_to_term("node@example.com")]);
+
+            prop_assert_eq!(result(&arc_process, destination, message), Ok(message));
+
+            Ok(())
+        },
+    );
+}
@@ -1,23 +1,73 @@
+use std::convert::TryInto;
+
+use anyhow::*;
+
 use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::{Process, SeqTraceFlags, SeqTraceSerial, SeqTraceToken};
 use liblumen_alloc::erts::term::prelude::*;
 
 use super::seq_trace::flag_is_not_a_supported_atom;
+use crate::runtime::context::*;
 
 // See https://github.com/lumen/otp/blob/30e2bfb9f1fd5c65bd7d9a4159f88cdcf72023fa/erts/emulator/beam/erl_bif_trace.c#L1705-L1828
 #[native_implemented::function(erlang:seq_trace/2)]
-pub fn result(flag: Term, _value: Term) -> exception::Result<Term> {
+pub fn result(process: &Process, flag: Term, value: Term) -> exception::Result<Term> {
     let flag_name = term_try_into_atom!(flag)?.name();
 
     match flag_name {
-        "label" => unimplemented!(),
-        "monotonic_timestamp" => unimplemented!(),
-        "print" => unimplemented!(),
-        "receive" => unimplemented!(),
-        "send" => unimplemented!(),
-        "serial" => unimplemented!(),
-        "spawn" => unimplemented!(),
-        "strict_monotonic_timestamp" => unimplemented!(),
-        "timestamp" => unimplemented!(),
-        _ => flag_is_not_a_supported_atom(flag),
+        "label" => {
+            let mut seq_trace_token = seq_trace_token_or_new(process);
+            let old_label = seq_trace_token.set_label(value)?;
+            process.set_seq_trace_token(Some(seq_trace_token));
+
+            Ok(old_label)
+        }
+        "sequential_trace_token" => {
+            let option_seq_trace_token = if value == Term::NIL {
+                None
+            } else {
+                let seq_trace_token: SeqTraceToken = value
+                    .try_into()
+                    .context("sequential_trace_token value is not [] or a token")?;
+
+                Some(seq_trace_token)
+            };
+
+            match process.set_seq_trace_token(option_seq_trace_token) {
+                Some(old_seq_trace_token) => Ok(old_seq_trace_token.to_term(process)),
+                None => Ok(Term::NIL),
+            }
+        }
+        "serial" => {
+            let serial: SeqTraceSerial = value.try_into().context("serial value")?;
+            let mut seq_trace_token = seq_trace_token_or_new(process);
+            let old_serial = std::mem::replace(&mut seq_trace_token.serial, serial);
+            process.set_seq_trace_token(Some(seq_trace_token));
+
+            Ok(old_serial.to_term(process))
+        }
+        name => match SeqTraceFlags::from_name(name) {
+            Some(flags) => {
+                let value_bool = term_try_into_bool(&format!("{} value", name), value)?;
+
+                // As in the BEAM, clearing a flag does not create a token
+                if !value_bool && process.seq_trace_token().is_none() {
+                    Ok(false.into())
+                } else {
+                    let mut seq_trace_token = seq_trace_token_or_new(process);
+                    let old_value = seq_trace_token.flags.set(flags, value_bool);
+                    process.set_seq_trace_token(Some(seq_trace_token));
+
+                    Ok(old_value.into())
+                }
+            }
+            None => flag_is_not_a_supported_atom(flag),
+        },
     }
 }
+
+fn seq_trace_token_or_new(process: &Process) -> SeqTraceToken {
+    process
+        .seq_trace_token()
+        .unwrap_or_else(|| SeqTraceToken::new(process.pid()))
+}
@@ -1,5 +1,5 @@
 use liblumen_alloc::erts::exception;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{Process, SeqTraceFlags};
 use liblumen_alloc::erts::term::prelude::*;
 
 use super::seq_trace::flag_is_not_a_supported_atom;
@@ -9,32 +9,51 @@ use super::seq_trace::flag_is_not_a_supported_atom;
 pub fn result(process: &Process, flag: Term) -> exception::Result<Term> {
     let flag_name = term_try_into_atom!(flag)?.name();
 
-    // Stub as if seq tracing is ALWAYS NOT enabled
-    // See https://github.com/lumen/otp/blob/30e2bfb9f1fd5c65bd7d9a4159f88cdcf72023fa/erts/emulator/beam/erl_bif_trace.c#L1865-L1879
     match flag_name {
         "label" => Ok(label(process, flag)),
-        "monotonic_timestamp"
-        | "print"
-        | "receive"
-        | "send"
-        | "spawn"
-        | "strict_monotonic_timestamp"
-        | "timestamp" => Ok(boolean_item(process, flag)),
+        "sequential_trace_token" => Ok(sequential_trace_token(process, flag)),
         "serial" => Ok(serial(process, flag)),
-        _ => flag_is_not_a_supported_atom(flag),
+        name => match SeqTraceFlags::from_name(name) {
+            Some(flags) => Ok(boolean_item(process, flag, flags)),
+            None => flag_is_not_a_supported_atom(flag),
+        },
     }
 }
 
-fn boolean_item(process: &Process, item: Term) -> Term {
-    tagged(process, item, false.into())
+fn boolean_item(process: &Process, item: Term, flags: SeqTraceFlags) -> Term {
+    let value = process
+        .seq_trace_token()
+        .map(|seq_trace_token| seq_trace_token.flags.are_set(flags))
+        .unwrap_or(false);
+
+    tagged(process, item, value.into())
 }
 
 fn label(process: &Process, item: Term) -> Term {
-    tagged(process, item, Term::NIL)
+    let value = process
+        .seq_trace_token()
+        .map(|seq_trace_token| seq_trace_token.label())
+        .unwrap_or(Term::NIL);
+
+    tagged(process, item, value)
+}
+
+fn sequential_trace_token(process: &Process, item: Term) -> Term {
+    let value = process
+        .seq_trace_token()
+        .map(|seq_trace_token| seq_trace_token.to_term(process))
+        .unwrap_or(Term::NIL);
+
+    tagged(process, item, value)
 }
 
 fn serial(process: &Process, item: Term) -> Term {
-    tagged(process, item, Term::NIL)
+    let value = process
+        .seq_trace_token()
+        .map(|seq_trace_token| seq_trace_token.serial.to_term(process))
+        .unwrap_or(Term::NIL);
+
+    tagged(process, item, value)
 }
 
 fn tagged(process: &Process, tag: Term, value: Term) -> Term {
@@ -1,9 +1,10 @@
+use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::seq_trace;
+
 // See https://github.com/lumen/otp/blob/30e2bfb9f1fd5c65bd7d9a4159f88cdcf72023fa/erts/emulator/beam/erl_bif_trace.c#L1919-L1936
 #[native_implemented::function(erlang:seq_trace_print/1)]
-pub fn result(_message: Term) -> Term {
-    // Stub as if `tracing_token` is not set
-    // See https://github.com/lumen/otp/blob/30e2bfb9f1fd5c65bd7d9a4159f88cdcf72023fa/erts/emulator/beam/erl_bif_trace.c#L1930
-    false.into()
+pub fn result(process: &Process, message: Term) -> Term {
+    seq_trace::print(process, None, message).into()
 }
@@ -6,6 +6,7 @@ use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::erlang::spawn_apply_3::spawn_error;
 use crate::runtime::process::spawn::options::Options;
 use crate::runtime::scheduler::Scheduled;
 
@@ -23,5 +24,5 @@ pub(in crate::erlang) fn result(
         .unwrap()
         .spawn_closure(Some(process), boxed_closure, options)
         .map(|spawned| spawned.to_term(process))
-        .map_err(From::from)
+        .map_err(spawn_error)
 }
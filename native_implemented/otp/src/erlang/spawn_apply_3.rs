@@ -1,9 +1,11 @@
-use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::exception::{self, error, Exception};
+use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::apply::arguments_term_to_vec;
 use crate::runtime::process::spawn::options::Options;
+use crate::runtime::process::spawn::SystemLimit;
 use crate::runtime::scheduler::Scheduled;
 
 pub(in crate::erlang) fn result(
@@ -28,5 +30,20 @@ pub(in crate::erlang) fn result(
             options,
         )
         .map(|spawned| spawned.to_term(process))
-        .map_err(From::from)
+        .map_err(spawn_error)
+}
+
+/// Spawning fails with `system_limit` when the process limit is reached
+pub(in crate::erlang) fn spawn_error(err: anyhow::Error) -> Exception {
+    if err.is::<SystemLimit>() {
+        error(
+            Atom::str_to_term("system_limit"),
+            None,
+            Trace::capture(),
+            Some(err.into()),
+        )
+        .into()
+    } else {
+        err.into()
+    }
 }
@@ -0,0 +1,73 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::gc;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::{port, statistics};
+
+// See http://erlang.org/doc/man/erlang.html#statistics-1
+#[native_implemented::function(erlang:statistics/1)]
+pub fn result(process: &Process, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom("item", item)?;
+
+    match item_atom.name() {
+        "active_tasks" => Ok(integer_list(process, &statistics::active_tasks())),
+        "context_switches" => Ok(process.tuple_from_slice(&[
+            process.integer(statistics::context_switches()),
+            process.integer(0),
+        ])),
+        "garbage_collection" => {
+            let (collections, words_reclaimed) = gc::statistics();
+
+            Ok(process.tuple_from_slice(&[
+                process.integer(collections),
+                process.integer(words_reclaimed),
+                process.integer(0),
+            ]))
+        }
+        "io" => {
+            let (input, output) = port::io();
+
+            Ok(process.tuple_from_slice(&[
+                process.tuple_from_slice(&[atom!("input"), process.integer(input)]),
+                process.tuple_from_slice(&[atom!("output"), process.integer(output)]),
+            ]))
+        }
+        "reductions" => Ok(total_and_since_last_call(
+            process,
+            statistics::reductions(),
+        )),
+        "run_queue" => Ok(process.integer(statistics::run_queue_lengths().iter().sum::<usize>())),
+        "run_queue_lengths" => Ok(integer_list(process, &statistics::run_queue_lengths())),
+        "runtime" => Ok(total_and_since_last_call(process, statistics::runtime())),
+        "wall_clock" => Ok(total_and_since_last_call(
+            process,
+            statistics::wall_clock(),
+        )),
+        _ => Err(anyhow!(
+            "item ({}) is not a supported atom (active_tasks, context_switches, garbage_collection, io, reductions, run_queue, run_queue_lengths, runtime, or wall_clock)",
+            item
+        )
+        .into()),
+    }
+}
+
+fn integer_list(process: &Process, integers: &[usize]) -> Term {
+    let integer_vec: Vec<Term> = integers
+        .iter()
+        .map(|integer| process.integer(*integer))
+        .collect();
+
+    process.list_from_slice(&integer_vec)
+}
+
+fn total_and_since_last_call(process: &Process, (total, since_last_call): (u64, u64)) -> Term {
+    process.tuple_from_slice(&[process.integer(total), process.integer(since_last_call)])
+}
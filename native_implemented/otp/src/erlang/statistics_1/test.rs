@@ -0,0 +1,62 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::statistics_1::result;
+use crate::test::with_process;
+
+#[test]
+fn without_supported_item_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            result(process, atom!("unsupported")),
+            "item (unsupported) is not a supported atom"
+        );
+    });
+}
+
+#[test]
+fn with_reductions_returns_total_and_since_last_call() {
+    with_process(|process| {
+        let first = result(process, atom!("reductions")).unwrap();
+        let first_tuple: Boxed<Tuple> = first.try_into().unwrap();
+
+        assert_eq!(first_tuple.len(), 2);
+
+        let second = result(process, atom!("reductions")).unwrap();
+        let second_tuple: Boxed<Tuple> = second.try_into().unwrap();
+
+        assert!(first_tuple[0] <= second_tuple[0]);
+        assert!(second_tuple[1] <= second_tuple[0]);
+    });
+}
+
+#[test]
+fn with_run_queue_lengths_returns_list_with_length_per_scheduler() {
+    with_process(|process| {
+        let run_queue_lengths = result(process, atom!("run_queue_lengths")).unwrap();
+
+        assert!(run_queue_lengths.is_list());
+
+        let run_queue = result(process, atom!("run_queue")).unwrap();
+
+        assert!(run_queue.is_integer());
+    });
+}
+
+#[test]
+fn with_io_returns_input_and_output() {
+    with_process(|process| {
+        let io = result(process, atom!("io")).unwrap();
+        let io_tuple: Boxed<Tuple> = io.try_into().unwrap();
+
+        assert_eq!(io_tuple.len(), 2);
+
+        let input_tuple: Boxed<Tuple> = io_tuple[0].try_into().unwrap();
+        assert_eq!(input_tuple[0], atom!("input"));
+
+        let output_tuple: Boxed<Tuple> = io_tuple[1].try_into().unwrap();
+        assert_eq!(output_tuple[0], atom!("output"));
+    });
+}
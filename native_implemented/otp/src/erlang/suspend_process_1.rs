@@ -0,0 +1,10 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::suspend_process_2::suspend_process;
+
+#[native_implemented::function(erlang:suspend_process/1)]
+pub fn result(process: &Process, suspendee: Term) -> exception::Result<Term> {
+    suspend_process(process, suspendee, Default::default())
+}
@@ -0,0 +1,53 @@
+mod options;
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::process;
+use crate::runtime::registry::pid_to_process;
+
+use crate::erlang::suspend_process_2::options::Options;
+
+#[native_implemented::function(erlang:suspend_process/2)]
+pub fn result(process: &Process, suspendee: Term, options: Term) -> exception::Result<Term> {
+    let options_options: Options = options.try_into()?;
+
+    suspend_process(process, suspendee, options_options)
+}
+
+// Private
+
+/// Suspends are always asynchronous, as a running suspendee is only stopped once it is next
+/// requeued by its scheduler, so the `asynchronous` option has no additional effect.
+pub(in crate::erlang) fn suspend_process(
+    process: &Process,
+    suspendee: Term,
+    Options { unless_suspending }: Options,
+) -> exception::Result<Term> {
+    let suspendee_pid = term_try_into_local_pid("suspendee", suspendee)?;
+
+    if suspendee_pid == process.pid() {
+        return Err(anyhow!("suspendee ({}) is the calling process", suspendee).into());
+    }
+
+    match pid_to_process(&suspendee_pid) {
+        Some(suspendee_arc_process) => {
+            if unless_suspending && suspendee_arc_process.is_suspended_by(process.pid()) {
+                Ok(false.into())
+            } else {
+                process::suspend(process, &suspendee_arc_process);
+
+                Ok(true.into())
+            }
+        }
+        None => Err(anyhow!("suspendee ({}) is not alive", suspendee).into()),
+    }
+}
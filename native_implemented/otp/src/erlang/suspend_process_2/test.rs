@@ -0,0 +1,87 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::resume_process_1;
+use crate::erlang::suspend_process_2::result;
+use crate::test;
+use crate::test::with_process;
+
+#[test]
+fn with_self_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            result(process, process.pid_term(), Term::NIL),
+            "is the calling process"
+        );
+    });
+}
+
+#[test]
+fn with_non_existent_pid_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(result(process, Pid::next_term(), Term::NIL), "is not alive");
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_badarg!(
+            result(
+                process,
+                other_arc_process.pid_term(),
+                process.list_from_slice(&[atom!("unsupported")])
+            ),
+            "supported options are :asynchronous or :unless_suspending"
+        );
+    });
+}
+
+#[test]
+fn suspends_until_resumed_as_many_times() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        let other_pid = other_arc_process.pid_term();
+
+        assert_eq!(result(process, other_pid, Term::NIL), Ok(true.into()));
+        assert_eq!(result(process, other_pid, Term::NIL), Ok(true.into()));
+        assert!(other_arc_process.is_suspended());
+
+        assert_eq!(
+            resume_process_1::result(process, other_pid),
+            Ok(true.into())
+        );
+        assert!(other_arc_process.is_suspended());
+
+        assert_eq!(
+            resume_process_1::result(process, other_pid),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_suspended());
+
+        assert_badarg!(
+            resume_process_1::result(process, other_pid),
+            "is not suspended by the calling process"
+        );
+    });
+}
+
+#[test]
+fn with_unless_suspending_when_already_suspended_returns_false() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        let other_pid = other_arc_process.pid_term();
+        let options = process.list_from_slice(&[atom!("unless_suspending")]);
+
+        assert_eq!(result(process, other_pid, options), Ok(true.into()));
+        assert_eq!(result(process, other_pid, options), Ok(false.into()));
+
+        assert_eq!(
+            resume_process_1::result(process, other_pid),
+            Ok(true.into())
+        );
+        assert!(!other_arc_process.is_suspended());
+    });
+}
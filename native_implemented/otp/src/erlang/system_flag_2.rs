@@ -3,8 +3,10 @@ use anyhow::*;
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::seq_trace;
+
 #[native_implemented::function(erlang:system_flag/2)]
-pub fn result(flag: Term, _value: Term) -> exception::Result<Term> {
+pub fn result(flag: Term, value: Term) -> exception::Result<Term> {
     let flag_atom = term_try_into_atom!(flag)?;
 
     match flag_atom.name() {
@@ -20,6 +22,25 @@ pub fn result(flag: Term, _value: Term) -> exception::Result<Term> {
         "multi_scheduling" => unimplemented!(),
         "scheduler_bind_type" => unimplemented!(),
         "schedulers_online" => unimplemented!(),
+        "sequential_tracer" => {
+            // Ports cannot be tracers yet
+            let option_system_tracer = match value.decode()? {
+                TypedTerm::Atom(atom) if atom.name() == "false" => None,
+                TypedTerm::Pid(pid) => Some(pid),
+                _ => {
+                    return Err(anyhow!(
+                        "sequential_tracer value ({}) is not false or a local pid",
+                        value
+                    )
+                    .into())
+                }
+            };
+
+            match seq_trace::set_system_tracer(option_system_tracer) {
+                Some(old_system_tracer) => Ok(old_system_tracer.encode().unwrap()),
+                None => Ok(false.into()),
+            }
+        }
         "system_logger" => unimplemented!(),
         "trace_control_word" => unimplemented!(),
         "time_offset" => unimplemented!(),
@@ -27,8 +48,8 @@ pub fn result(flag: Term, _value: Term) -> exception::Result<Term> {
             "flag ({}) is not supported (backtrace_depth, cpu_topology, \
              dirty_cpu_schedulers_online, erts_alloc, fullsweep_after, microstate_accounting, \
              min_heap_size, min_bin_vheap_size, max_heap_size, multi_scheduling, \
-             scheduler_bind_type, schedulers_online, system_logger, trace_control_word, \
-             time_offset)"
+             scheduler_bind_type, schedulers_online, sequential_tracer, system_logger, \
+             trace_control_word, time_offset)"
         )
         .into()),
     }
@@ -1,24 +1,42 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::mem;
+
 use anyhow::*;
 
 use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::alloc::{self, default_heap_size};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::atom::{atom_count, MAX_ATOMS};
 use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::std_alloc;
+
+use lumen_rt_core::process::spawn::max_processes;
+use lumen_rt_core::scheduler;
+use lumen_rt_core::sys::cpus;
+
+use crate::runtime::distribution::nodes::node;
+use crate::runtime::registry;
+use crate::runtime::seq_trace;
+use crate::runtime::time::{monotonic, Unit};
 
 #[native_implemented::function(erlang:system_info/1)]
-pub fn result(item: Term) -> exception::Result<Term> {
+pub fn result(process: &Process, item: Term) -> exception::Result<Term> {
     match item.decode().unwrap() {
         TypedTerm::Atom(atom) => match atom.name() {
             "alloc_util_allocators" => unimplemented!(),
             "allocated_areas" => unimplemented!(),
-            "allocator" => unimplemented!(),
-            "atom_count" => unimplemented!(),
-            "atom_limit" => unimplemented!(),
+            "allocator" => Ok(allocator(process)),
+            "atom_count" => Ok(process.integer(atom_count())),
+            "atom_limit" => Ok(process.integer(MAX_ATOMS)),
             "build_type" => unimplemented!(),
             "c_compiler_used" => unimplemented!(),
             "check_io" => unimplemented!(),
             "compat_rel" => unimplemented!(),
             "cpu_quota" => unimplemented!(),
             "cpu_topology" => unimplemented!(),
-            "creation" => unimplemented!(),
+            "creation" => Ok(process.integer(node::arc_node().creation() as usize)),
             "debug_compiled" => unimplemented!(),
             "delayed_node_table_gc" => unimplemented!(),
             "dirty_cpu_schedulers" => unimplemented!(),
@@ -34,46 +52,57 @@ pub fn result(item: Term) -> exception::Result<Term> {
             "end_time" => unimplemented!(),
             "ets_count" => unimplemented!(),
             "ets_limit" => unimplemented!(),
-            "fullsweep_after" => unimplemented!(),
+            "fullsweep_after" => Ok(process
+                .tuple_from_slice(&[item, process.integer(Process::DEFAULT_FULLSWEEP_AFTER)])),
             "garbage_collection" => unimplemented!(),
-            "heap_sizes" => unimplemented!(),
+            "heap_sizes" => Ok(self::heap_sizes(process)),
             "heap_type" => unimplemented!(),
             "info" => unimplemented!(),
             "kernel_poll" => unimplemented!(),
             "loaded" => unimplemented!(),
-            "logic_processors" => unimplemented!(),
-            "logic_processors_available" => unimplemented!(),
-            "logical_processors_online" => unimplemented!(),
-            "machine" => unimplemented!(),
+            "logical_processors" | "logical_processors_available" | "logical_processors_online" => {
+                Ok(process.integer(cpus::num_logical()))
+            }
+            "machine" => Ok(process.charlist_from_str(MACHINE)),
             "max_heap_size" => unimplemented!(),
             "message_queue_data" => unimplemented!(),
             "min_bin_vheap_size" => unimplemented!(),
-            "min_heap_size" => unimplemented!(),
+            "min_heap_size" => {
+                Ok(process.tuple_from_slice(&[item, process.integer(default_heap_size())]))
+            }
             "modified_timing_level" => unimplemented!(),
             "multi_scheduling" => unimplemented!(),
             "multi_scheduling_blockers" => unimplemented!(),
             "nif_version" => unimplemented!(),
             "normal_multi_scheduling_blockers" => unimplemented!(),
-            "os_monotonic_time_source" => unimplemented!(),
+            "os_monotonic_time_source" => Ok(os_monotonic_time_source(process)),
             "os_system_time_source" => unimplemented!(),
-            "otp_release" => unimplemented!(),
+            "otp_release" => Ok(process.charlist_from_str(OTP_RELEASE)),
             "port_count" => unimplemented!(),
             "port_limit" => unimplemented!(),
             "port_parallelism" => unimplemented!(),
-            "process_count" => unimplemented!(),
-            "process_limit" => unimplemented!(),
+            "process_count" => Ok(process.integer(registry::process_count())),
+            "process_limit" => {
+                Ok(process.integer(max_processes().unwrap_or(DEFAULT_PROCESS_LIMIT)))
+            }
             "procs" => unimplemented!(),
             "scheduler_bind_type" => unimplemented!(),
             "scheduler_bindings" => unimplemented!(),
             "scheduler_id" => unimplemented!(),
-            "schedulers" => unimplemented!(),
-            "schedulers_online" => unimplemented!(),
-            "sequential_tracer" => unimplemented!(),
+            "schedulers" | "schedulers_online" => Ok(process.integer(schedulers())),
+            "sequential_tracer" => {
+                let system_tracer = match seq_trace::system_tracer() {
+                    Some(system_tracer) => system_tracer.encode().unwrap(),
+                    None => false.into(),
+                };
+
+                Ok(process.tuple_from_slice(&[item, system_tracer]))
+            }
             "smp_support" => unimplemented!(),
             "start_time" => unimplemented!(),
             "system_architecture" => unimplemented!(),
             "system_logger" => unimplemented!(),
-            "system_version" => unimplemented!(),
+            "system_version" => Ok(process.charlist_from_str(&system_version())),
             "thread_pool_size" => unimplemented!(),
             "threads" => unimplemented!(),
             "time_correction" => unimplemented!(),
@@ -83,7 +112,7 @@ pub fn result(item: Term) -> exception::Result<Term> {
             "trace_control_word" => unimplemented!(),
             "update_cpu_info" => unimplemented!(),
             "version" => unimplemented!(),
-            "wordsize" => unimplemented!(),
+            "wordsize" => Ok(process.integer(mem::size_of::<Term>())),
             _ => Err(anyhow!(
                 "item ({}) is not a supported atom ({})",
                 item,
@@ -97,10 +126,10 @@ pub fn result(item: Term) -> exception::Result<Term> {
 
                 match tag.decode().unwrap() {
                     TypedTerm::Atom(tag_atom) => match tag_atom.name() {
-                        "allocator" => unimplemented!(),
+                        "allocator" => allocator_info(process, boxed_tuple[1]),
                         "allocator_sizes" => unimplemented!(),
                         "cpu_topology" => unimplemented!(),
-                        "wordsize" => unimplemented!(),
+                        "wordsize" => wordsize(process, item, boxed_tuple[1]),
                         _ => item_is_not_supported_tuple(item),
                     },
                     _ => item_is_not_supported_tuple(item),
@@ -120,8 +149,8 @@ pub fn result(item: Term) -> exception::Result<Term> {
 }
 
 const SUPPORTED_ATOMS: &'static str = "`allocated_areas`, `allocator`, \
-                 `alloc_util_allocators`, `elib_malloc`, `cpu_topology`, `logic_processors`, \
-                 `logic_processors_available`, `logical_processors_online`, \
+                 `alloc_util_allocators`, `elib_malloc`, `cpu_topology`, `logical_processors`, \
+                 `logical_processors_available`, `logical_processors_online`, \
                  `cpu_quota`, `update_cpu_info`, `fullsweep_after`, `garbage_collection`, \
                  `heap_sizes`, `heap_type`, `max_heap_size`, `message_queue_data`, `min_heap_size` \
                  `min_bin_vheap_size`, `procs`, `atom_count`, `atom_limit`, `ets_count`, \
@@ -152,3 +181,132 @@ fn item_is_not_supported_tuple(item: Term) -> exception::Result<Term> {
     )
     .into())
 }
+
+// Private
+
+/// The limit when `+P` is not set in `vm.args`, which is the same default as BEAM
+const DEFAULT_PROCESS_LIMIT: usize = 262_144;
+const MACHINE: &str = "BEAM";
+const OTP_RELEASE: &str = "23";
+
+fn allocator(process: &Process) -> Term {
+    let name = Atom::str_to_term("std_alloc");
+    let version = process.list_from_slice(&[]);
+    let features = process.list_from_slice(&[name]);
+    let settings = process.list_from_slice(&[]);
+
+    process.tuple_from_slice(&[name, version, features, settings])
+}
+
+fn allocator_info(process: &Process, alloc: Term) -> exception::Result<Term> {
+    let alloc_atom = term_try_into_atom!(alloc)?;
+
+    let info = match alloc_atom.name() {
+        "std_alloc" => {
+            let alloc_info = std_alloc::alloc_info();
+            let mut property_vec = vec![
+                carriers(process, "mbcs", alloc_info.num_multi_block_carriers()),
+                carriers(process, "sbcs", alloc_info.num_single_block_carriers()),
+            ];
+
+            // Calls and bytes are only counted when the allocator is instrumented
+            if let Some(statistics) = std_alloc::alloc_stats() {
+                property_vec.push(property(
+                    process,
+                    "calls",
+                    &[
+                        ("alloc", statistics.alloc_calls()),
+                        ("free", statistics.dealloc_calls()),
+                        ("realloc", statistics.realloc_calls()),
+                    ],
+                ));
+                property_vec.push(property(
+                    process,
+                    "bytes",
+                    &[
+                        ("alloced", statistics.total_bytes_alloced()),
+                        ("freed", statistics.total_bytes_freed()),
+                    ],
+                ));
+            }
+
+            process.list_from_slice(&property_vec)
+        }
+        _ => false.into(),
+    };
+
+    Ok(info)
+}
+
+fn carriers(process: &Process, name: &str, count: usize) -> Term {
+    property(process, name, &[("carriers", count)])
+}
+
+fn heap_sizes(process: &Process) -> Term {
+    let heap_size_vec: Vec<Term> = alloc::heap_sizes()
+        .iter()
+        .map(|heap_size| process.integer(*heap_size))
+        .collect();
+
+    process.list_from_slice(&heap_size_vec)
+}
+
+fn os_monotonic_time_source(process: &Process) -> Term {
+    let function = if cfg!(target_arch = "wasm32") {
+        "performance.now"
+    } else {
+        "std::time::Instant"
+    };
+    let time = process.integer(monotonic::time_in_unit(Unit::Native));
+
+    process.list_from_slice(&[
+        process.tuple_from_slice(&[Atom::str_to_term("function"), Atom::str_to_term(function)]),
+        process.tuple_from_slice(&[
+            Atom::str_to_term("resolution"),
+            process.integer(Unit::Native.hertz()),
+        ]),
+        process.tuple_from_slice(&[Atom::str_to_term("extended"), Atom::str_to_term("no")]),
+        process.tuple_from_slice(&[Atom::str_to_term("parallel"), Atom::str_to_term("yes")]),
+        process.tuple_from_slice(&[Atom::str_to_term("time"), time]),
+    ])
+}
+
+/// `{name, [{key, value}]}`
+fn property(process: &Process, name: &str, key_values: &[(&str, usize)]) -> Term {
+    let value_vec: Vec<Term> = key_values
+        .iter()
+        .map(|(key, value)| {
+            process.tuple_from_slice(&[Atom::str_to_term(key), process.integer(*value)])
+        })
+        .collect();
+
+    process.tuple_from_slice(&[Atom::str_to_term(name), process.list_from_slice(&value_vec)])
+}
+
+/// The number of schedulers is at least the one for the main thread, even before it registers
+fn schedulers() -> usize {
+    scheduler::all().len().max(1)
+}
+
+fn system_version() -> String {
+    let schedulers = schedulers();
+
+    format!(
+        "Erlang/OTP {} [lumen-{}] [smp:{}:{}]\n",
+        OTP_RELEASE,
+        env!("CARGO_PKG_VERSION"),
+        schedulers,
+        schedulers
+    )
+}
+
+fn wordsize(process: &Process, item: Term, kind: Term) -> exception::Result<Term> {
+    match kind.decode().unwrap() {
+        TypedTerm::Atom(kind_atom) => match kind_atom.name() {
+            // Terms are not tagged differently in memory than in the external API
+            "internal" | "external" => Ok(process.integer(mem::size_of::<Term>())),
+            _ => item_is_not_supported_tuple(item),
+        },
+        _ => item_is_not_supported_tuple(item),
+    }
+}
@@ -0,0 +1,76 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::alloc::default_heap_size;
+use liblumen_alloc::erts::term::atom::MAX_ATOMS;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::system_info_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_atom_count_returns_atoms_in_table() {
+    with_process(|process| {
+        let count = result(process, Atom::str_to_term("atom_count")).unwrap();
+        let count_usize: usize = count.try_into().unwrap();
+
+        assert!(0 < count_usize);
+        assert!(count_usize <= MAX_ATOMS);
+    });
+}
+
+#[test]
+fn with_atom_limit_returns_max_atoms() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("atom_limit")),
+            Ok(process.integer(MAX_ATOMS))
+        );
+    });
+}
+
+#[test]
+fn with_min_heap_size_returns_default_heap_size() {
+    with_process(|process| {
+        let item = Atom::str_to_term("min_heap_size");
+
+        assert_eq!(
+            result(process, item),
+            Ok(process.tuple_from_slice(&[item, process.integer(default_heap_size())]))
+        );
+    });
+}
+
+#[test]
+fn with_otp_release_returns_charlist() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("otp_release")),
+            Ok(process.charlist_from_str("23"))
+        );
+    });
+}
+
+#[test]
+fn with_wordsize_tuple_returns_bytes_per_word() {
+    with_process(|process| {
+        let item = process
+            .tuple_from_slice(&[Atom::str_to_term("wordsize"), Atom::str_to_term("external")]);
+
+        assert_eq!(
+            result(process, item),
+            Ok(process.integer(std::mem::size_of::<usize>()))
+        );
+    });
+}
+
+#[test]
+fn with_allocator_tuple_with_unknown_allocator_returns_false() {
+    with_process(|process| {
+        let item = process.tuple_from_slice(&[
+            Atom::str_to_term("allocator"),
+            Atom::str_to_term("unknown_alloc"),
+        ]);
+
+        assert_eq!(result(process, item), Ok(false.into()));
+    });
+}
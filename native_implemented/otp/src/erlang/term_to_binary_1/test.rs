@@ -6,7 +6,7 @@ use liblumen_alloc::erts::scheduler;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::binary_to_term_1;
-use crate::erlang::term_to_binary_1::result;
+use crate::erlang::term_to_binary_1;
 use crate::test::strategy;
 use crate::test::with_process;
 
@@ -26,6 +26,18 @@ fn roundtrips_through_binary_to_term() {
     );
 }
 
+#[test]
+fn with_resource_reference_errors_badarg() {
+    with_process(|process| {
+        let resource = process.resource(0_u8);
+
+        assert_badarg!(
+            term_to_binary_1::result(process, process.tuple_from_slice(&[resource])),
+            "cannot be encoded"
+        );
+    });
+}
+
 // NEW_FLOAT_EXT (70)
 #[test]
 fn with_negative_float_returns_new_float_ext() {
@@ -605,3 +617,7 @@ fn non_empty_tuple_byte_vec() -> Vec<u8> {
 
     byte_vec
 }
+
+fn result(process: &Process, term: Term) -> Term {
+    term_to_binary_1::result(process, term).unwrap()
+}
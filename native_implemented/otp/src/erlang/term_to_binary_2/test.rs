@@ -0,0 +1,67 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::binary_to_term_1;
+use crate::erlang::term_to_binary_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_compressed_with_compressible_term_returns_compressed_ext() {
+    with_process(|process| {
+        let term = process.list_from_slice(&[process.integer(0); 20]);
+        let options = process.list_from_slice(&[Atom::str_to_term("compressed")]);
+
+        let binary = result(process, term, options).unwrap();
+        let heap_binary: Boxed<HeapBin> = binary.try_into().unwrap();
+        let bytes = heap_binary.as_bytes();
+
+        // VERSION_NUMBER, COMPRESSED, and the 23 byte uncompressed size of the STRING_EXT
+        assert_eq!(&bytes[..6], &[131, 80, 0, 0, 0, 23]);
+        assert_eq!(binary_to_term_1::result(process, binary), Ok(term));
+    });
+}
+
+#[test]
+fn with_compressed_with_incompressible_term_returns_uncompressed() {
+    with_process(|process| {
+        let term = Atom::str_to_term("a");
+        let options = process.list_from_slice(&[Atom::str_to_term("compressed")]);
+
+        assert_eq!(
+            result(process, term, options),
+            Ok(process.binary_from_bytes(&[131, 100, 0, 1, 97]))
+        );
+    });
+}
+
+#[test]
+fn with_compressed_level_zero_returns_uncompressed() {
+    with_process(|process| {
+        let term = process.list_from_slice(&[process.integer(0); 20]);
+        let options =
+            process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[Atom::str_to_term("compressed"), process.integer(0)])]);
+
+        let binary = result(process, term, options).unwrap();
+        let heap_binary: Boxed<HeapBin> = binary.try_into().unwrap();
+
+        assert_eq!(&heap_binary.as_bytes()[..2], &[131, 107]);
+    });
+}
+
+#[test]
+fn with_compressed_level_out_of_range_errors_badarg() {
+    with_process(|process| {
+        let options =
+            process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[Atom::str_to_term("compressed"), process.integer(10)])]);
+
+        assert_badarg!(
+            result(process, Atom::str_to_term("a"), options),
+            "is not a list of compressed"
+        );
+    });
+}
@@ -4,12 +4,9 @@ mod test;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
-use crate::runtime::time::{monotonic, system, Unit::Native};
+use crate::runtime::time::{offset, Unit::Native};
 
 #[native_implemented::function(erlang:time_offset/0)]
 pub fn result(process: &Process) -> Term {
-    let system_time = system::time_in_unit(Native);
-    let monotonic_time = monotonic::time_in_unit(Native);
-
-    process.integer(system_time - monotonic_time)
+    process.integer(offset::in_unit(Native))
 }
@@ -0,0 +1,122 @@
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::{Process, TraceFlags, Tracing};
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::registry::{self, pid_to_process};
+use crate::runtime::trace;
+
+// See http://erlang.org/doc/man/erlang.html#trace-3
+#[native_implemented::function(erlang:trace/3)]
+pub fn result(
+    process: &Process,
+    pid_spec: Term,
+    how: Term,
+    flag_list: Term,
+) -> exception::Result<Term> {
+    let how_bool = term_try_into_bool("how", how)?;
+    let (flags, option_tracer) = flags_and_tracer(flag_list)?;
+    let tracer = match option_tracer {
+        Some(tracer) => {
+            if pid_to_process(&tracer).is_none() {
+                return Err(anyhow!("tracer ({}) is not alive", tracer).into());
+            }
+
+            tracer
+        }
+        None => process.pid(),
+    };
+
+    let traced_arc_processes = match pid_spec.decode()? {
+        TypedTerm::Pid(pid) => match pid_to_process(&pid) {
+            Some(arc_process) => vec![arc_process],
+            None => return Err(anyhow!("pid_spec ({}) is not alive", pid_spec).into()),
+        },
+        TypedTerm::Atom(atom) => match atom.name() {
+            "existing" | "existing_processes" => registry::processes(),
+            "all" | "new" | "new_processes" | "processes" => {
+                return Err(anyhow!(
+                    "pid_spec ({}) includes new processes, which cannot be traced yet",
+                    pid_spec
+                )
+                .into())
+            }
+            _ => return Err(pid_spec_is_invalid(pid_spec).into()),
+        },
+        _ => return Err(pid_spec_is_invalid(pid_spec).into()),
+    };
+
+    for traced_arc_process in traced_arc_processes.iter() {
+        let mut traced_flags = traced_arc_process
+            .tracing()
+            .map(|tracing| tracing.flags)
+            .unwrap_or(TraceFlags::None);
+        traced_flags.set(flags, how_bool);
+
+        trace::trace(
+            traced_arc_process,
+            Some(Tracing {
+                tracer,
+                flags: traced_flags,
+            }),
+        );
+    }
+
+    Ok(process.integer(traced_arc_processes.len()))
+}
+
+fn flags_and_tracer(flag_list: Term) -> anyhow::Result<(TraceFlags, Option<Pid>)> {
+    let mut flags = TraceFlags::None;
+    let mut option_tracer = None;
+
+    match flag_list.decode().unwrap() {
+        TypedTerm::Nil => (),
+        TypedTerm::List(boxed_cons) => {
+            for result in boxed_cons.into_iter() {
+                match result {
+                    Ok(flag) => match flag.decode().unwrap() {
+                        TypedTerm::Atom(atom) => match TraceFlags::from_name(atom.name()) {
+                            Some(name_flags) => {
+                                flags.set(name_flags, true);
+                            }
+                            None => return Err(flag_is_not_supported(flag)),
+                        },
+                        TypedTerm::Tuple(boxed_tuple) => {
+                            if boxed_tuple.len() == 2
+                                && boxed_tuple[0] == Atom::str_to_term("tracer")
+                            {
+                                let tracer = term_try_into_local_pid("tracer", boxed_tuple[1])?;
+                                option_tracer = Some(tracer);
+                            } else {
+                                return Err(flag_is_not_supported(flag));
+                            }
+                        }
+                        _ => return Err(flag_is_not_supported(flag)),
+                    },
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("flag_list ({}) is improper", flag_list))
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(TypeError).context(format!("flag_list ({}) is not a list", flag_list));
+        }
+    }
+
+    Ok((flags, option_tracer))
+}
+
+fn flag_is_not_supported(flag: Term) -> anyhow::Error {
+    anyhow!("flag ({}) is not a supported atom (call, garbage_collection, procs, receive, return_to, running, send, or timestamp) or {{tracer, pid()}}", flag)
+}
+
+fn pid_spec_is_invalid(pid_spec: Term) -> anyhow::Error {
+    anyhow!(
+        "pid_spec ({}) is not a local pid, existing, or existing_processes",
+        pid_spec
+    )
+}
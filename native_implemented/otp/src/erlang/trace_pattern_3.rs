@@ -0,0 +1,185 @@
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::runtime::trace::{self, CallTrace, TracePattern};
+
+// See http://erlang.org/doc/man/erlang.html#trace_pattern-3
+#[native_implemented::function(erlang:trace_pattern/3)]
+pub fn result(
+    process: &Process,
+    module_function_arity: Term,
+    match_spec: Term,
+    flag_list: Term,
+) -> exception::Result<Term> {
+    let trace_pattern = trace_pattern(module_function_arity)?;
+    let call_trace = call_trace(match_spec)?;
+    check_flag_list(flag_list)?;
+
+    let matched = trace::set_trace_pattern(trace_pattern, call_trace);
+
+    Ok(process.integer(matched))
+}
+
+fn check_flag_list(flag_list: Term) -> anyhow::Result<()> {
+    match flag_list.decode().unwrap() {
+        TypedTerm::Nil => Ok(()),
+        TypedTerm::List(boxed_cons) => {
+            for result in boxed_cons.into_iter() {
+                match result {
+                    Ok(flag) => match term_try_into_atom("flag", flag)?.name() {
+                        "global" | "local" => (),
+                        _ => {
+                            return Err(anyhow!(
+                                "flag ({}) is not a supported atom (global or local)",
+                                flag
+                            ))
+                        }
+                    },
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("flag_list ({}) is improper", flag_list))
+                    }
+                }
+            }
+
+            Ok(())
+        }
+        _ => Err(TypeError).context(format!("flag_list ({}) is not a list", flag_list)),
+    }
+}
+
+/// `true` and `[]` trace calls, `false` stops tracing them, and a match specification traces
+/// calls with the actions of its first clause.  Only clauses that match any arguments without
+/// conditions, `{'_', [], Body}`, are supported, where `Body` may only contain `{return_trace}`
+/// and `{exception_trace}`.
+fn call_trace(match_spec: Term) -> anyhow::Result<Option<CallTrace>> {
+    match match_spec.decode().unwrap() {
+        TypedTerm::Nil => Ok(Some(Default::default())),
+        TypedTerm::Atom(atom) => match atom.name() {
+            "true" => Ok(Some(Default::default())),
+            "false" => Ok(None),
+            _ => Err(match_spec_is_not_supported(match_spec)),
+        },
+        TypedTerm::List(boxed_cons) => {
+            let mut option_call_trace = None;
+
+            for result in boxed_cons.into_iter() {
+                match result {
+                    Ok(clause) => {
+                        let clause_call_trace = clause_call_trace(match_spec, clause)?;
+
+                        // Later clauses are checked, but never match after a `'_'` head
+                        option_call_trace.get_or_insert(clause_call_trace);
+                    }
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("match_spec ({}) is improper", match_spec))
+                    }
+                }
+            }
+
+            Ok(option_call_trace)
+        }
+        _ => Err(match_spec_is_not_supported(match_spec)),
+    }
+}
+
+fn clause_call_trace(match_spec: Term, clause: Term) -> anyhow::Result<CallTrace> {
+    let tuple: Boxed<Tuple> = clause
+        .try_into()
+        .map_err(|_| match_spec_is_not_supported(match_spec))?;
+
+    if tuple.len() != 3 || tuple[0] != Atom::str_to_term("_") || tuple[1] != Term::NIL {
+        return Err(match_spec_is_not_supported(match_spec));
+    }
+
+    let mut call_trace = CallTrace::default();
+
+    match tuple[2].decode().unwrap() {
+        TypedTerm::Nil => (),
+        TypedTerm::List(boxed_cons) => {
+            for result in boxed_cons.into_iter() {
+                let action = result.map_err(|_| match_spec_is_not_supported(match_spec))?;
+
+                match action_name(action) {
+                    Some("return_trace") => call_trace.return_trace = true,
+                    Some("exception_trace") => call_trace.exception_trace = true,
+                    _ => return Err(match_spec_is_not_supported(match_spec)),
+                }
+            }
+        }
+        _ => return Err(match_spec_is_not_supported(match_spec)),
+    }
+
+    Ok(call_trace)
+}
+
+/// The name of a `{Name}` action
+fn action_name(action: Term) -> Option<&'static str> {
+    let tuple: Boxed<Tuple> = action.try_into().ok()?;
+
+    if tuple.len() == 1 {
+        let atom: Atom = tuple[0].try_into().ok()?;
+
+        Some(atom.name())
+    } else {
+        None
+    }
+}
+
+fn match_spec_is_not_supported(match_spec: Term) -> anyhow::Error {
+    anyhow!(
+        "match_spec ({}) is not true, false, [], or [{{'_', [], Body}}] where Body only has \
+         {{return_trace}} and {{exception_trace}}",
+        match_spec
+    )
+}
+
+/// `{Module, Function, Arity}` where each element may be `'_'`, but only after all elements
+/// before it are `'_'` too.
+fn trace_pattern(module_function_arity: Term) -> anyhow::Result<TracePattern> {
+    let tuple = term_try_into_tuple("module_function_arity", module_function_arity)?;
+
+    if tuple.len() != 3 {
+        return Err(anyhow!(
+            "module_function_arity ({}) is not a 3-tuple {{module, function, arity}}",
+            module_function_arity
+        ));
+    }
+
+    let module = wildcard_or(tuple[0], |module| term_try_into_atom("module", module))?;
+    let function = wildcard_or(tuple[1], |function| {
+        term_try_into_atom("function", function)
+    })?;
+    let arity = wildcard_or(tuple[2], term_try_into_arity)?;
+
+    if (module.is_none() && function.is_some()) || (function.is_none() && arity.is_some()) {
+        return Err(anyhow!(
+            "module_function_arity ({}) has '_' before a module, function, or arity",
+            module_function_arity
+        ));
+    }
+
+    Ok(TracePattern {
+        module,
+        function,
+        arity,
+    })
+}
+
+fn wildcard_or<T, F>(term: Term, try_into: F) -> anyhow::Result<Option<T>>
+where
+    F: FnOnce(Term) -> anyhow::Result<T>,
+{
+    if term == Atom::str_to_term("_") {
+        Ok(None)
+    } else {
+        try_into(term).map(Some)
+    }
+}
@@ -8,6 +8,7 @@ use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::runtime::registry::pid_to_process;
+use crate::runtime::{distribution, port};
 
 #[native_implemented::function(erlang:unlink/1)]
 fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
@@ -26,9 +27,23 @@ fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
                 Ok(true.into())
             }
         }
-        TypedTerm::Port(_) => unimplemented!(),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
-        TypedTerm::ExternalPort(_) => unimplemented!(),
+        TypedTerm::Port(port) => {
+            if let Some(arc_control_block) = port::get(port) {
+                arc_control_block.unlink(process.pid());
+            }
+
+            Ok(true.into())
+        }
+        TypedTerm::ExternalPid(external_pid) => {
+            distribution::unlink(process, &external_pid);
+
+            Ok(true.into())
+        }
+        TypedTerm::ExternalPort(_) => Err(anyhow!(
+            "pid_or_port ({}) is a port on another node, which cannot be unlinked",
+            pid_or_port
+        )
+        .into()),
         _ => Err(TypeError)
             .context(format!(
                 "pid_or_port ({}) is neither a pid nor a port",
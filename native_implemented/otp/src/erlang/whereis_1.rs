@@ -10,11 +10,12 @@ use crate::runtime::registry;
 #[native_implemented::function(erlang:whereis/1)]
 pub fn result(name: Term) -> exception::Result<Term> {
     let atom = term_try_into_atom!(name)?;
-    let option = registry::atom_to_process(&atom).map(|arc_process| arc_process.pid());
-
-    let term = match option {
-        Some(pid) => pid.encode()?,
-        None => atom!("undefined"),
+    let term = match registry::atom_to_process(&atom) {
+        Some(arc_process) => arc_process.pid().encode()?,
+        None => match registry::atom_to_port(&atom) {
+            Some(port) => port.encode()?,
+            None => atom!("undefined"),
+        },
     };
 
     Ok(term)
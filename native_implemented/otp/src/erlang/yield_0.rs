@@ -0,0 +1,9 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+#[native_implemented::function(erlang:yield/0)]
+pub fn result(process: &Process) -> Term {
+    process.exhaust_reductions();
+
+    true.into()
+}
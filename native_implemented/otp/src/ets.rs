@@ -0,0 +1,72 @@
+//! Mirrors [ets](http://erlang.org/doc/man/ets.html) module
+
+pub mod delete_1;
+pub mod delete_2;
+pub mod foldl_3;
+pub mod info_1;
+pub mod info_2;
+pub mod insert_2;
+pub mod lookup_2;
+pub mod match_2;
+pub mod new_2;
+pub mod select_2;
+pub mod tab2list_1;
+pub mod update_counter_3;
+
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::{self, Table};
+
+fn module() -> Atom {
+    Atom::from_str("ets")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
+
+fn table(tab: Term) -> exception::Result<Arc<Table>> {
+    match ets::get(tab) {
+        Some(table) => Ok(table),
+        None => Err(anyhow!("tab ({}) is not an existing table", tab).into()),
+    }
+}
+
+/// The table referred to by `tab` if `process` can read it
+fn readable_table(process: &Process, tab: Term) -> exception::Result<Arc<Table>> {
+    let table = table(tab)?;
+
+    if table.can_read(process.pid()) {
+        Ok(table)
+    } else {
+        Err(anyhow!(
+            "table ({}) is private to its owner ({})",
+            tab,
+            table.owner()
+        )
+        .into())
+    }
+}
+
+/// The table referred to by `tab` if `process` can write it
+fn writable_table(process: &Process, tab: Term) -> exception::Result<Arc<Table>> {
+    let table = table(tab)?;
+
+    if table.can_write(process.pid()) {
+        Ok(table)
+    } else {
+        Err(anyhow!(
+            "table ({}) is {} and can only be written by its owner ({})",
+            tab,
+            table.access().as_str(),
+            table.owner()
+        )
+        .into())
+    }
+}
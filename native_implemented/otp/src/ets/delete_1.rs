@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets;
+
+#[native_implemented::function(ets:delete/1)]
+pub fn result(process: &Process, tab: Term) -> exception::Result<Term> {
+    let table = super::writable_table(process, tab)?;
+    ets::delete(&table);
+
+    Ok(true.into())
+}
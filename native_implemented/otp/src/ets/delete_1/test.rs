@@ -0,0 +1,53 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{delete_1, insert_2, new_2};
+use crate::runtime::ets;
+use crate::test::{self, with_process};
+
+#[test]
+fn deletes_table() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(delete_1::result(process, tab), Ok(true.into()));
+
+        assert!(ets::get(tab).is_none());
+        assert_badarg!(
+            insert_2::result(
+                process,
+                tab,
+                process.tuple_from_slice(&[Atom::str_to_term("key")])
+            ),
+            format!("tab ({}) is not an existing table", tab)
+        );
+    });
+}
+
+#[test]
+fn with_named_table_frees_name() {
+    with_process(|process| {
+        let name = Atom::str_to_term("with_named_table_frees_name");
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(new_2::result(process, name, options), Ok(name));
+        assert_eq!(delete_1::result(process, name), Ok(true.into()));
+        assert_eq!(new_2::result(process, name, options), Ok(name));
+    });
+}
+
+#[test]
+fn with_protected_table_of_other_process_errors_badarg() {
+    with_process(|process| {
+        let owner_arc_process = test::process::child(process);
+        let name = Atom::str_to_term("with_protected_table_of_other_process_errors_badarg");
+        let options = owner_arc_process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(new_2::result(&owner_arc_process, name, options), Ok(name));
+
+        assert_badarg!(
+            delete_1::result(process, name),
+            "is protected and can only be written by its owner"
+        );
+        assert!(ets::get(name).is_some());
+    });
+}
@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Deletes all objects with `key`
+#[native_implemented::function(ets:delete/2)]
+pub fn result(process: &Process, tab: Term, key: Term) -> exception::Result<Term> {
+    let table = super::writable_table(process, tab)?;
+    table.delete_key(key);
+
+    Ok(true.into())
+}
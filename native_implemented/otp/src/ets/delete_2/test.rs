@@ -0,0 +1,42 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{delete_2, insert_2, lookup_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn deletes_objects_with_key() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("bag")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let key = Atom::str_to_term("key");
+        let other_key = Atom::str_to_term("other_key");
+        let other_object = process.tuple_from_slice(&[other_key, process.integer(3)]);
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[key, process.integer(1)]),
+            process.tuple_from_slice(&[key, process.integer(2)]),
+            other_object,
+        ]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(delete_2::result(process, tab, key), Ok(true.into()));
+
+        assert_eq!(lookup_2::result(process, tab, key), Ok(Term::NIL));
+        assert_eq!(
+            lookup_2::result(process, tab, other_key),
+            Ok(process.list_from_slice(&[other_object]))
+        );
+    });
+}
+
+#[test]
+fn without_key_returns_true() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(
+            delete_2::result(process, tab, Atom::str_to_term("key")),
+            Ok(true.into())
+        );
+    });
+}
@@ -0,0 +1,66 @@
+//! ```elixir
+//! def foldl(function, acc0, tab) do
+//!   fold(function, acc0, :ets.tab2list(tab))
+//! end
+//!
+//! defp fold(_function, acc, []), do: acc
+//! defp fold(function, acc, [object | objects]) do
+//!   acc = function.(object, acc)
+//!   fold(function, acc, objects)
+//! end
+//! ```
+//!
+//! The objects are copied when the fold starts, so that `function` can change the table.
+
+mod label_1;
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+
+// Private
+
+#[native_implemented::function(ets:foldl/3)]
+fn result(process: &Process, function: Term, acc0: Term, tab: Term) -> exception::Result<Term> {
+    let function_boxed_closure: Boxed<Closure> = function
+        .try_into()
+        .with_context(|| format!("function ({}) is not a function", function))?;
+
+    if function_boxed_closure.arity() != 2 {
+        return Err(anyhow!("function ({}) is not arity 2", function).into());
+    }
+
+    let table = super::readable_table(process, tab)?;
+    let objects = table.to_list(process);
+
+    Ok(fold(process, function, acc0, objects))
+}
+
+/// Calls `function` with the first of the `objects` and continues with the rest of the `objects`
+/// in `label_1` when it returns
+fn fold(process: &Process, function: Term, acc: Term, objects: Term) -> Term {
+    match objects.decode().unwrap() {
+        TypedTerm::Nil => acc,
+        TypedTerm::List(cons) => {
+            let arguments = process.list_from_slice(&[cons.head, acc]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[function, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[function, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("objects ({}) is not a list", objects),
+    }
+}
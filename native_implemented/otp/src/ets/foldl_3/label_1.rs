@@ -0,0 +1,21 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (function, objects)
+//! # returned from call: acc
+//! # full stack: (acc, function, objects)
+//! # returns: acc
+//! fold(function, acc, objects)
+//! ```
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, acc: Term, function: Term, objects: Term) -> Term {
+    assert!(function.is_boxed_function());
+    assert!(objects.is_list());
+
+    super::fold(process, function, acc, objects)
+}
@@ -0,0 +1,109 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+use crate::ets::{insert_2, new_2};
+use crate::test::{self, with_process};
+
+use super::{label_1, result};
+
+#[test]
+fn without_function_errors_badarg() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let function = Atom::str_to_term("function");
+
+        assert_badarg!(
+            result(process, function, Term::NIL, tab),
+            format!("function ({}) is not a function", function)
+        );
+    });
+}
+
+#[test]
+fn without_arity_2_function_errors_badarg() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let function = test::anonymous_1::anonymous_closure(process);
+
+        assert_badarg!(
+            result(process, function, Term::NIL, tab),
+            format!("function ({}) is not arity 2", function)
+        );
+    });
+}
+
+#[test]
+fn without_table_errors_badarg() {
+    with_process(|process| {
+        let tab = Atom::str_to_term("without_table_errors_badarg");
+
+        assert_badarg!(
+            result(process, function(process), Term::NIL, tab),
+            format!("tab ({}) is not an existing table", tab)
+        );
+    });
+}
+
+#[test]
+fn without_objects_returns_acc0() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let acc0 = Atom::str_to_term("acc0");
+
+        assert_eq!(result(process, function(process), acc0, tab), Ok(acc0));
+    });
+}
+
+#[test]
+fn with_objects_applies_function_to_first_object_and_continues_with_rest() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("ordered_set")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let first = process.tuple_from_slice(&[process.integer(1)]);
+        let second = process.tuple_from_slice(&[process.integer(2)]);
+        let objects = process.list_from_slice(&[first, second]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        let function = function(process);
+        let acc0 = Atom::str_to_term("acc0");
+
+        assert_eq!(result(process, function, acc0, tab), Ok(Term::NONE));
+
+        let queued = process.frames.lock().drain_queue();
+
+        assert_eq!(queued.len(), 2);
+        assert_eq!(
+            queued[0].frame.module_function_arity(),
+            apply_2::frame().module_function_arity()
+        );
+        assert_eq!(
+            queued[0].arguments,
+            vec![function, process.list_from_slice(&[first, acc0])]
+        );
+        assert_eq!(
+            queued[1].frame.module_function_arity(),
+            label_1::frame().module_function_arity()
+        );
+        assert!(queued[1].uses_returned);
+        assert_eq!(
+            queued[1].arguments,
+            vec![function, process.list_from_slice(&[second])]
+        );
+    });
+}
+
+/// An arity 2 function, which is only queued and never run
+fn function(process: &Process) -> Term {
+    process.anonymous_closure_with_env_from_slice(
+        test::module(),
+        0,
+        0,
+        [0; 16],
+        2,
+        None,
+        process.pid().into(),
+        &[],
+    )
+}
@@ -0,0 +1,44 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets;
+
+use super::info_2::item_info;
+
+/// Returns `{Item, Info}` for all items or `undefined` if the table does not exist
+#[native_implemented::function(ets:info/1)]
+pub fn result(process: &Process, tab: Term) -> exception::Result<Term> {
+    match ets::get(tab) {
+        Some(table) => {
+            let mut item_info_vec = Vec::with_capacity(ITEMS.len());
+
+            for item in ITEMS {
+                let info = item_info(process, &table, item)?;
+                item_info_vec.push(process.tuple_from_slice(&[Atom::str_to_term(item), info]));
+            }
+
+            Ok(process.list_from_slice(&item_info_vec))
+        }
+        None => Ok(atom!("undefined")),
+    }
+}
+
+const ITEMS: &[&str] = &[
+    "id",
+    "decentralized_counters",
+    "read_concurrency",
+    "write_concurrency",
+    "compressed",
+    "memory",
+    "owner",
+    "heir",
+    "name",
+    "size",
+    "node",
+    "named_table",
+    "type",
+    "keypos",
+    "protection",
+];
@@ -0,0 +1,53 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{atom, CloneToProcess};
+
+use crate::runtime::distribution::nodes::node;
+use crate::runtime::ets::{self, Table};
+
+/// Returns the `item` for the table or `undefined` if the table does not exist
+#[native_implemented::function(ets:info/2)]
+pub fn result(process: &Process, tab: Term, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom!(item)?;
+
+    match ets::get(tab) {
+        Some(table) => item_info(process, &table, item_atom.name()),
+        None => Ok(atom!("undefined")),
+    }
+}
+
+pub fn item_info(process: &Process, table: &Table, item: &'static str) -> exception::Result<Term> {
+    let info = match item {
+        // Tables are always safe for concurrent access and store objects as-is
+        "compressed" | "decentralized_counters" | "read_concurrency" | "write_concurrency" => {
+            false.into()
+        }
+        "heir" => match table.heir() {
+            Some(heir) => heir.encode()?,
+            None => atom!("none"),
+        },
+        "id" => table.reference().clone_to_process(process),
+        "keypos" => process.integer(table.keypos()),
+        "memory" => process.integer(table.memory()),
+        "name" => table.name().encode()?,
+        "named_table" => table.named_table().into(),
+        "node" => node::term(),
+        "owner" => table.owner().encode()?,
+        "protection" => Atom::str_to_term(table.access().as_str()),
+        "size" => process.integer(table.size()),
+        "type" => Atom::str_to_term(table.r#type().as_str()),
+        name => {
+            return Err(TryAtomFromTermError(name))
+                .context("supported items are compressed, decentralized_counters, heir, id, keypos, memory, name, named_table, node, owner, protection, read_concurrency, size, type, or write_concurrency")
+                .map_err(From::from)
+        }
+    };
+
+    Ok(info)
+}
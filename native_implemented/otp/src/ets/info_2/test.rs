@@ -0,0 +1,60 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{delete_1, info_2, insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_table_returns_undefined() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        assert_eq!(delete_1::result(process, tab), Ok(true.into()));
+
+        assert_eq!(
+            info_2::result(process, tab, Atom::str_to_term("size")),
+            Ok(atom!("undefined"))
+        );
+    });
+}
+
+#[test]
+fn with_size_returns_number_of_objects() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(1)]),
+            process.tuple_from_slice(&[process.integer(2)]),
+        ]);
+        insert_2::result(process, tab, objects).unwrap();
+
+        assert_eq!(
+            info_2::result(process, tab, Atom::str_to_term("size")),
+            Ok(process.integer(2))
+        );
+    });
+}
+
+#[test]
+fn with_owner_returns_creating_process() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(
+            info_2::result(process, tab, Atom::str_to_term("owner")),
+            Ok(process.pid_term())
+        );
+    });
+}
+
+#[test]
+fn with_type_returns_type_option() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("duplicate_bag")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+
+        assert_eq!(
+            info_2::result(process, tab, Atom::str_to_term("type")),
+            Ok(Atom::str_to_term("duplicate_bag"))
+        );
+    });
+}
@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Inserts a single object tuple or a list of object tuples
+#[native_implemented::function(ets:insert/2)]
+pub fn result(process: &Process, tab: Term, object_or_objects: Term) -> exception::Result<Term> {
+    let table = super::writable_table(process, tab)?;
+
+    let object_vec = match object_or_objects.decode()? {
+        TypedTerm::Nil => Vec::new(),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .collect::<Result<Vec<Term>, _>>()
+            .map_err(|_| ImproperListError)
+            .with_context(|| format!("objects ({}) is not a proper list", object_or_objects))?,
+        _ => vec![object_or_objects],
+    };
+
+    table.insert(&object_vec).with_context(|| {
+        format!(
+            "object_or_objects ({}) is not a tuple or list of tuples with a key at keypos ({})",
+            object_or_objects,
+            table.keypos()
+        )
+    })?;
+
+    Ok(true.into())
+}
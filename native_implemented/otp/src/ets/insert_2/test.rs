@@ -0,0 +1,147 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{insert_2, lookup_2, new_2};
+use crate::test::{process, with_process};
+
+#[test]
+fn with_set_replaces_object_with_same_key() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+        let first = process.tuple_from_slice(&[key, process.integer(1)]);
+        let second = process.tuple_from_slice(&[key, process.integer(2)]);
+
+        assert_eq!(insert_2::result(process, tab, first), Ok(true.into()));
+        assert_eq!(insert_2::result(process, tab, second), Ok(true.into()));
+
+        assert_eq!(
+            lookup_2::result(process, tab, key),
+            Ok(process.list_from_slice(&[second]))
+        );
+    });
+}
+
+#[test]
+fn with_set_keeps_integer_and_equal_float_keys_apart() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let integer_key = process.integer(1);
+        let float_key = process.float(1.0);
+        let integer_object = process.tuple_from_slice(&[integer_key, Atom::str_to_term("integer")]);
+        let float_object = process.tuple_from_slice(&[float_key, Atom::str_to_term("float")]);
+        let objects = process.list_from_slice(&[integer_object, float_object]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(
+            lookup_2::result(process, tab, integer_key),
+            Ok(process.list_from_slice(&[integer_object]))
+        );
+        assert_eq!(
+            lookup_2::result(process, tab, float_key),
+            Ok(process.list_from_slice(&[float_object]))
+        );
+    });
+}
+
+#[test]
+fn with_ordered_set_replaces_object_with_equal_float_key() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("ordered_set")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let integer_object =
+            process.tuple_from_slice(&[process.integer(1), Atom::str_to_term("integer")]);
+        let float_object =
+            process.tuple_from_slice(&[process.float(1.0), Atom::str_to_term("float")]);
+        let objects = process.list_from_slice(&[integer_object, float_object]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(
+            lookup_2::result(process, tab, process.integer(1)),
+            Ok(process.list_from_slice(&[float_object]))
+        );
+    });
+}
+
+#[test]
+fn with_set_finds_binary_key_with_different_representation() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = process.binary_from_bytes(&[1, 2]);
+        let object = process.tuple_from_slice(&[key, Atom::str_to_term("value")]);
+
+        assert_eq!(insert_2::result(process, tab, object), Ok(true.into()));
+
+        let original = process.binary_from_bytes(&[0, 1, 2, 0]);
+        let subbinary = process.subbinary_from_original(original, 1, 0, 2, 0);
+
+        assert_eq!(
+            lookup_2::result(process, tab, subbinary),
+            Ok(process.list_from_slice(&[object]))
+        );
+    });
+}
+
+#[test]
+fn with_bag_keeps_only_one_copy_of_each_object() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("bag")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let key = Atom::str_to_term("key");
+        let first = process.tuple_from_slice(&[key, process.integer(1)]);
+        let second = process.tuple_from_slice(&[key, process.integer(2)]);
+        let objects = process.list_from_slice(&[first, second, first]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(
+            lookup_2::result(process, tab, key),
+            Ok(process.list_from_slice(&[first, second]))
+        );
+    });
+}
+
+#[test]
+fn with_duplicate_bag_keeps_copies_of_objects() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("duplicate_bag")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key"), process.integer(1)]);
+        let objects = process.list_from_slice(&[object, object]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(
+            lookup_2::result(process, tab, Atom::str_to_term("key")),
+            Ok(process.list_from_slice(&[object, object]))
+        );
+    });
+}
+
+#[test]
+fn without_tuple_errors_badarg() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let object = Atom::str_to_term("object");
+
+        assert_badarg!(
+            insert_2::result(process, tab, object),
+            format!("object ({}) is not a tuple", object)
+        );
+    });
+}
+
+#[test]
+fn with_protected_table_from_other_process_errors_badarg() {
+    with_process(|owner| {
+        let tab = new_2::result(owner, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let other = process::child(owner);
+        let object = other.tuple_from_slice(&[Atom::str_to_term("key")]);
+
+        assert_badarg!(
+            insert_2::result(&other, tab, object),
+            "is protected and can only be written by its owner"
+        );
+    });
+}
@@ -0,0 +1,10 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+#[native_implemented::function(ets:lookup/2)]
+pub fn result(process: &Process, tab: Term, key: Term) -> exception::Result<Term> {
+    let table = super::readable_table(process, tab)?;
+
+    Ok(table.lookup(process, key))
+}
@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::MatchSpec;
+
+/// Returns the list of bindings of the `'$N'` variables in `pattern` for each matching object
+#[native_implemented::function(ets:match/2)]
+pub fn result(process: &Process, tab: Term, pattern: Term) -> exception::Result<Term> {
+    let table = super::readable_table(process, tab)?;
+
+    Ok(table.select(process, &MatchSpec::from_pattern(pattern)))
+}
@@ -0,0 +1,44 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{insert_2, match_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn returns_bindings_of_matching_objects_ordered_by_variable_number() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("ordered_set")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[
+                process.integer(1),
+                Atom::str_to_term("a"),
+                Atom::str_to_term("match"),
+            ]),
+            process.tuple_from_slice(&[
+                process.integer(2),
+                Atom::str_to_term("b"),
+                Atom::str_to_term("skip"),
+            ]),
+            process.tuple_from_slice(&[
+                process.integer(3),
+                Atom::str_to_term("c"),
+                Atom::str_to_term("match"),
+            ]),
+        ]);
+        insert_2::result(process, tab, objects).unwrap();
+
+        let pattern = process.tuple_from_slice(&[
+            Atom::str_to_term("$2"),
+            Atom::str_to_term("$1"),
+            Atom::str_to_term("match"),
+        ]);
+
+        assert_eq!(
+            match_2::result(process, tab, pattern),
+            Ok(process.list_from_slice(&[
+                process.list_from_slice(&[Atom::str_to_term("a"), process.integer(1)]),
+                process.list_from_slice(&[Atom::str_to_term("c"), process.integer(3)]),
+            ]))
+        );
+    });
+}
@@ -0,0 +1,25 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::{self, Options};
+
+#[native_implemented::function(ets:new/2)]
+pub fn result(process: &Process, name: Term, options: Term) -> exception::Result<Term> {
+    let name_atom = term_try_into_atom!(name)?;
+    let options: Options = options
+        .try_into()
+        .with_context(|| format!("options ({}) is not a valid list of table options", options))?;
+
+    match ets::new(process, name_atom, options) {
+        Some(table) => Ok(table.to_term(process)),
+        None => Err(anyhow!("name ({}) is already the name of a named table", name).into()),
+    }
+}
@@ -0,0 +1,149 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::new_2::result;
+use crate::runtime::{ets, scheduler};
+use crate::test::{self, exit_when_run, has_message, with_process};
+
+#[test]
+fn without_atom_name_errors_badarg() {
+    with_process(|process| {
+        let name = process.integer(0);
+
+        assert_badarg!(
+            result(process, name, Term::NIL),
+            format!("name ({}) is not an atom", name)
+        );
+    });
+}
+
+#[test]
+fn without_named_table_returns_reference() {
+    with_process(|process| {
+        let name = Atom::str_to_term("without_named_table_returns_reference");
+
+        let tab = result(process, name, Term::NIL).unwrap();
+
+        assert!(tab.is_reference());
+    });
+}
+
+#[test]
+fn with_named_table_returns_name() {
+    with_process(|process| {
+        let name = Atom::str_to_term("with_named_table_returns_name");
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(result(process, name, options), Ok(name));
+    });
+}
+
+#[test]
+fn with_named_table_with_name_taken_errors_badarg() {
+    with_process(|process| {
+        let name = Atom::str_to_term("with_named_table_with_name_taken_errors_badarg");
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(result(process, name, options), Ok(name));
+        assert_badarg!(
+            result(process, name, options),
+            format!("name ({}) is already the name of a named table", name)
+        );
+    });
+}
+
+#[test]
+fn with_unknown_option_errors_badarg() {
+    with_process(|process| {
+        let name = Atom::str_to_term("with_unknown_option_errors_badarg");
+        let options = process.list_from_slice(&[Atom::str_to_term("unknown")]);
+
+        assert_badarg!(
+            result(process, name, options),
+            format!("options ({}) is not a valid list of table options", options)
+        );
+    });
+}
+
+#[test]
+fn without_heir_deletes_table_when_owner_exits() {
+    with_process(|process| {
+        let owner_arc_process = test::process::child(process);
+        let name = Atom::str_to_term("without_heir_deletes_table_when_owner_exits");
+        let options = owner_arc_process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(result(&owner_arc_process, name, options), Ok(name));
+
+        exit_when_run(&owner_arc_process, Atom::str_to_term("normal"));
+
+        assert!(scheduler::run_through(&owner_arc_process));
+
+        assert!(owner_arc_process.is_exiting());
+        assert!(ets::get(name).is_none());
+    });
+}
+
+#[test]
+fn with_heir_transfers_table_to_heir_when_owner_exits() {
+    with_process(|process| {
+        let owner_arc_process = test::process::child(process);
+        let name = Atom::str_to_term("with_heir_transfers_table_to_heir_when_owner_exits");
+        let data = Atom::str_to_term("data");
+        let options = owner_arc_process.list_from_slice(&[
+            Atom::str_to_term("named_table"),
+            owner_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("heir"),
+                process.pid_term(),
+                data,
+            ]),
+        ]);
+
+        assert_eq!(result(&owner_arc_process, name, options), Ok(name));
+
+        exit_when_run(&owner_arc_process, Atom::str_to_term("normal"));
+
+        assert!(scheduler::run_through(&owner_arc_process));
+
+        assert!(owner_arc_process.is_exiting());
+        assert_eq!(ets::get(name).unwrap().owner(), process.pid());
+        assert!(has_message(
+            process,
+            process.tuple_from_slice(&[
+                Atom::str_to_term("ETS-TRANSFER"),
+                name,
+                owner_arc_process.pid_term(),
+                data
+            ])
+        ));
+    });
+}
+
+#[test]
+fn with_heir_deletes_table_when_heir_then_exits() {
+    with_process(|process| {
+        let owner_arc_process = test::process::child(process);
+        let heir_arc_process = test::process::child(process);
+        let name = Atom::str_to_term("with_heir_deletes_table_when_heir_then_exits");
+        let options = owner_arc_process.list_from_slice(&[
+            Atom::str_to_term("named_table"),
+            owner_arc_process.tuple_from_slice(&[
+                Atom::str_to_term("heir"),
+                heir_arc_process.pid_term(),
+                Atom::str_to_term("data"),
+            ]),
+        ]);
+
+        assert_eq!(result(&owner_arc_process, name, options), Ok(name));
+
+        exit_when_run(&owner_arc_process, Atom::str_to_term("normal"));
+
+        assert!(scheduler::run_through(&owner_arc_process));
+
+        assert_eq!(ets::get(name).unwrap().owner(), heir_arc_process.pid());
+
+        exit_when_run(&heir_arc_process, Atom::str_to_term("normal"));
+
+        assert!(scheduler::run_through(&heir_arc_process));
+
+        assert!(ets::get(name).is_none());
+    });
+}
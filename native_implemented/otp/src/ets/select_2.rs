@@ -0,0 +1,25 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::MatchSpec;
+
+#[native_implemented::function(ets:select/2)]
+pub fn result(process: &Process, tab: Term, match_spec: Term) -> exception::Result<Term> {
+    let table = super::readable_table(process, tab)?;
+    let match_spec: MatchSpec = match_spec.try_into().with_context(|| {
+        format!(
+            "match_spec ({}) is not a valid match specification",
+            match_spec
+        )
+    })?;
+
+    Ok(table.select(process, &match_spec))
+}
@@ -0,0 +1,78 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{insert_2, new_2, select_2};
+use crate::test::with_process;
+
+#[test]
+fn with_guard_returns_body_of_objects_passing_guard() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("ordered_set")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(1)]),
+            process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(2)]),
+            process.tuple_from_slice(&[Atom::str_to_term("c"), process.integer(3)]),
+        ]);
+        insert_2::result(process, tab, objects).unwrap();
+
+        // [{{'$1', '$2'}, [{'>', '$2', 1}], [{{'$2', '$1'}}]}]
+        let head = process.tuple_from_slice(&[Atom::str_to_term("$1"), Atom::str_to_term("$2")]);
+        let guard = process.tuple_from_slice(&[
+            Atom::str_to_term(">"),
+            Atom::str_to_term("$2"),
+            process.integer(1),
+        ]);
+        let body = process.tuple_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("$2"), Atom::str_to_term("$1")])
+        ]);
+        let match_spec = process.list_from_slice(&[process.tuple_from_slice(&[
+            head,
+            process.list_from_slice(&[guard]),
+            process.list_from_slice(&[body]),
+        ])]);
+
+        assert_eq!(
+            select_2::result(process, tab, match_spec),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(2), Atom::str_to_term("b")]),
+                process.tuple_from_slice(&[process.integer(3), Atom::str_to_term("c")]),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_whole_object_body_returns_objects() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key"), process.integer(1)]);
+        insert_2::result(process, tab, object).unwrap();
+
+        let match_spec = process.list_from_slice(&[process.tuple_from_slice(&[
+            Atom::str_to_term("_"),
+            Term::NIL,
+            process.list_from_slice(&[Atom::str_to_term("$_")]),
+        ])]);
+
+        assert_eq!(
+            select_2::result(process, tab, match_spec),
+            Ok(process.list_from_slice(&[object]))
+        );
+    });
+}
+
+#[test]
+fn without_match_spec_list_errors_badarg() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let match_spec = Atom::str_to_term("match_spec");
+
+        assert_badarg!(
+            select_2::result(process, tab, match_spec),
+            format!(
+                "match_spec ({}) is not a valid match specification",
+                match_spec
+            )
+        );
+    });
+}
@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+#[native_implemented::function(ets:tab2list/1)]
+pub fn result(process: &Process, tab: Term) -> exception::Result<Term> {
+    let table = super::readable_table(process, tab)?;
+
+    Ok(table.to_list(process))
+}
@@ -0,0 +1,42 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{insert_2, new_2, tab2list_1};
+use crate::test::{self, with_process};
+
+#[test]
+fn without_objects_returns_empty_list() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(tab2list_1::result(process, tab), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_ordered_set_returns_objects_in_key_order() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("ordered_set")]);
+        let tab = new_2::result(process, Atom::str_to_term("table"), options).unwrap();
+        let first = process.tuple_from_slice(&[process.integer(1), Atom::str_to_term("one")]);
+        let second = process.tuple_from_slice(&[process.integer(2), Atom::str_to_term("two")]);
+        let objects = process.list_from_slice(&[second, first]);
+
+        assert_eq!(insert_2::result(process, tab, objects), Ok(true.into()));
+
+        assert_eq!(
+            tab2list_1::result(process, tab),
+            Ok(process.list_from_slice(&[first, second]))
+        );
+    });
+}
+
+#[test]
+fn with_private_table_of_other_process_errors_badarg() {
+    with_process(|process| {
+        let owner_arc_process = test::process::child(process);
+        let options = owner_arc_process.list_from_slice(&[Atom::str_to_term("private")]);
+        let tab = new_2::result(&owner_arc_process, Atom::str_to_term("table"), options).unwrap();
+
+        assert_badarg!(tab2list_1::result(process, tab), "is private to its owner");
+    });
+}
@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+#[native_implemented::function(ets:update_counter/3)]
+pub fn result(process: &Process, tab: Term, key: Term, update_op: Term) -> exception::Result<Term> {
+    let table = super::writable_table(process, tab)?;
+
+    table
+        .update_counter(process, key, update_op)
+        .map_err(From::from)
+}
@@ -0,0 +1,79 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::{insert_2, lookup_2, new_2, update_counter_3};
+use crate::test::with_process;
+
+#[test]
+fn with_increment_updates_element_after_key() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+        let object = process.tuple_from_slice(&[key, process.integer(1)]);
+        insert_2::result(process, tab, object).unwrap();
+
+        assert_eq!(
+            update_counter_3::result(process, tab, key, process.integer(2)),
+            Ok(process.integer(3))
+        );
+        assert_eq!(
+            lookup_2::result(process, tab, key),
+            Ok(process.list_from_slice(&[process.tuple_from_slice(&[key, process.integer(3)])]))
+        );
+    });
+}
+
+#[test]
+fn with_threshold_sets_value_when_passed() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+        let object = process.tuple_from_slice(&[key, process.integer(9)]);
+        insert_2::result(process, tab, object).unwrap();
+
+        // {Pos, Incr, Threshold, SetValue}
+        let update_op = process.tuple_from_slice(&[
+            process.integer(2),
+            process.integer(1),
+            process.integer(9),
+            process.integer(0),
+        ]);
+
+        assert_eq!(
+            update_counter_3::result(process, tab, key, update_op),
+            Ok(process.integer(0))
+        );
+    });
+}
+
+#[test]
+fn with_list_of_operations_returns_list() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+        let object = process.tuple_from_slice(&[key, process.integer(1), process.integer(10)]);
+        insert_2::result(process, tab, object).unwrap();
+
+        let update_op = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(2), process.integer(1)]),
+            process.tuple_from_slice(&[process.integer(3), process.integer(-1)]),
+        ]);
+
+        assert_eq!(
+            update_counter_3::result(process, tab, key, update_op),
+            Ok(process.list_from_slice(&[process.integer(2), process.integer(9)]))
+        );
+    });
+}
+
+#[test]
+fn without_key_errors_badarg() {
+    with_process(|process| {
+        let tab = new_2::result(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+
+        assert_badarg!(
+            update_counter_3::result(process, tab, key, process.integer(1)),
+            format!("key ({}) is not in the table", key)
+        );
+    });
+}
@@ -0,0 +1,13 @@
+//! Mirrors [init](http://erlang.org/doc/man/init.html) module
+
+pub mod get_status_0;
+
+use liblumen_alloc::erts::term::prelude::Atom;
+
+fn module() -> Atom {
+    Atom::from_str("init")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
@@ -0,0 +1,23 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::boot;
+
+/// Returns `{InternalStatus, ProvidedStatus}`, where `ProvidedStatus` is the `Name` of the last
+/// `{progress, Name}` instruction run from the boot script.  The system is `started` once the
+/// boot script reaches `{progress, started}` or when there is no boot script.
+#[native_implemented::function(init:get_status/0)]
+pub fn result(process: &Process) -> Term {
+    let (internal_status, provided_status) = match boot::progress() {
+        Some(progress) if progress == "started" => (atom!("started"), atom!("started")),
+        Some(progress) => (atom!("starting"), progress.encode().unwrap()),
+        None if boot::script().is_some() => (atom!("starting"), atom!("starting")),
+        None => (atom!("started"), atom!("started")),
+    };
+
+    process.tuple_from_slice(&[internal_status, provided_status])
+}
@@ -0,0 +1,14 @@
+use liblumen_alloc::atom;
+
+use crate::init::get_status_0::result;
+use crate::test::with_process;
+
+#[test]
+fn without_boot_script_returns_started() {
+    with_process(|process| {
+        assert_eq!(
+            result(process),
+            process.tuple_from_slice(&[atom!("started"), atom!("started")])
+        );
+    });
+}
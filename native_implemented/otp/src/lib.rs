@@ -9,8 +9,11 @@
 #[macro_use]
 mod macros;
 
+pub mod application;
 pub mod binary;
 pub mod erlang;
+pub mod ets;
+pub mod init;
 pub mod lists;
 pub mod lumen;
 pub mod maps;
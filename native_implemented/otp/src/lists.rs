@@ -1,12 +1,43 @@
 //! Mirrors [lists](http://erlang.org/doc/man/lists.html) module
 
+pub mod append_1;
+pub mod append_2;
+pub mod duplicate_2;
+pub mod flatten_1;
+pub mod keydelete_3;
 pub mod keyfind_3;
 pub mod keymember_3;
+pub mod keysort_2;
+pub mod keystore_4;
+pub mod keytake_3;
+pub mod last_1;
+pub mod max_1;
 pub mod member_2;
+pub mod min_1;
+pub mod nth_2;
+pub mod nthtail_2;
 pub mod reverse_1;
 pub mod reverse_2;
+pub mod seq_2;
+pub mod seq_3;
+pub mod sort_1;
+pub mod sort_2;
+pub mod split_2;
+pub mod sublist_2;
+pub mod sublist_3;
+pub mod sum_1;
+pub mod ukeysort_2;
+pub mod unzip_1;
+pub mod usort_1;
+pub mod zip_2;
 
-use liblumen_alloc::erts::term::prelude::Atom;
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
 
 fn module() -> Atom {
     Atom::from_str("lists")
@@ -15,3 +46,84 @@ fn module() -> Atom {
 fn module_id() -> usize {
     module().id()
 }
+
+/// The element at `index` of `element` if `element` is a tuple with at least `index` elements.
+fn element_key(index: OneBasedIndex, element: Term) -> Option<Term> {
+    let result_tuple: Result<Boxed<Tuple>, _> = element.try_into();
+
+    result_tuple
+        .ok()
+        .and_then(|tuple| tuple.get_element(index).ok())
+}
+
+/// Whether `element` is a tuple whose element at `index` compares equal to `key`.
+fn element_key_matches(index: OneBasedIndex, key: Term, element: Term) -> bool {
+    match element_key(index, element) {
+        Some(element_key) => element_key == key,
+        None => false,
+    }
+}
+
+/// Pairs the elements of `tuple_list` with their key at `index`, so they can be sorted by key.
+fn keyed_vec(
+    index: OneBasedIndex,
+    tuple_list: Term,
+    vec: Vec<Term>,
+) -> anyhow::Result<Vec<(Term, Term)>> {
+    vec.into_iter()
+        .map(|element| match element_key(index, element) {
+            Some(key) => Ok((key, element)),
+            None => Err(anyhow!(
+                "element ({}) of tuple_list ({}) is not a tuple with an element at index",
+                element,
+                tuple_list
+            )),
+        })
+        .collect()
+}
+
+/// The tail of `list` after the first `n` elements.  Only the first `n` elements need to be proper,
+/// so, like the BEAM, the rest of `list` is not checked.
+fn nthtail(name: &str, list: Term, n: usize) -> anyhow::Result<Term> {
+    let mut tail = list;
+
+    for _ in 0..n {
+        match tail.decode().unwrap() {
+            TypedTerm::List(cons) => tail = cons.tail,
+            _ => {
+                return Err(anyhow!(
+                    "{} ({}) is not a list with at least {} elements",
+                    name,
+                    list,
+                    n
+                ))
+            }
+        }
+    }
+
+    Ok(tail)
+}
+
+fn term_is_not_proper_list(name: &str, value: Term) -> String {
+    term_is_not_type(name, value, "a proper list")
+}
+
+fn term_try_into_non_negative_usize(name: &str, value: Term) -> anyhow::Result<usize> {
+    value
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer(name, value))
+}
+
+pub(crate) fn term_try_into_vec(name: &str, list: Term) -> anyhow::Result<Vec<Term>> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => match cons
+            .into_iter()
+            .collect::<std::result::Result<Vec<Term>, _>>()
+        {
+            Ok(vec) => Ok(vec),
+            Err(_) => Err(ImproperListError).context(term_is_not_proper_list(name, list)),
+        },
+        _ => Err(TypeError).context(term_is_not_proper_list(name, list)),
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Appends the lists in `list_of_lists`.  Like `++/2`, the last list is not checked, so it can be
+/// any term.
+#[native_implemented::function(lists:append/1)]
+pub fn result(process: &Process, list_of_lists: Term) -> exception::Result<Term> {
+    let vec = super::term_try_into_vec("list_of_lists", list_of_lists)?;
+
+    match vec.split_last() {
+        Some((last, init)) => {
+            let mut elements = Vec::new();
+
+            for list in init {
+                let list_elements = super::term_try_into_vec("list", *list).with_context(|| {
+                    format!(
+                        "list_of_lists ({}) is not a list of proper lists",
+                        list_of_lists
+                    )
+                })?;
+                elements.extend(list_elements);
+            }
+
+            Ok(process.improper_list_from_slice(&elements, *last))
+        }
+        None => Ok(Term::NIL),
+    }
+}
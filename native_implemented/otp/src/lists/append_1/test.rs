@@ -0,0 +1,63 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::append_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_lists_returns_elements_in_order() {
+    with_process(|process| {
+        let list_of_lists = process.list_from_slice(&[
+            process.list_from_slice(&[process.integer(1), process.integer(2)]),
+            Term::NIL,
+            process.list_from_slice(&[process.integer(3)]),
+        ]);
+
+        assert_eq!(
+            result(process, list_of_lists),
+            Ok(process.list_from_slice(&[
+                process.integer(1),
+                process.integer(2),
+                process.integer(3)
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_non_list_last_element_returns_improper_list() {
+    with_process(|process| {
+        let tail = Atom::str_to_term("tail");
+        let list_of_lists =
+            process.list_from_slice(&[process.list_from_slice(&[process.integer(1)]), tail]);
+
+        assert_eq!(
+            result(process, list_of_lists),
+            Ok(process.improper_list_from_slice(&[process.integer(1)], tail))
+        );
+    });
+}
+
+#[test]
+fn with_improper_list_before_last_errors_badarg() {
+    with_process(|process| {
+        let list_of_lists = process.list_from_slice(&[
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail")),
+            Term::NIL,
+        ]);
+
+        assert_badarg!(
+            result(process, list_of_lists),
+            format!(
+                "list_of_lists ({}) is not a list of proper lists",
+                list_of_lists
+            )
+        );
+    });
+}
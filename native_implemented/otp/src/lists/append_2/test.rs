@@ -0,0 +1,30 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::append_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_improper_list1_errors_badarg() {
+    with_process(|process| {
+        let list1 =
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(process, list1, Term::NIL),
+            format!("list ({}) is improper", list1)
+        );
+    });
+}
+
+#[test]
+fn with_lists_returns_elements_of_list1_then_list2() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+        let list2 = process.list_from_slice(&[process.integer(2)]);
+
+        assert_eq!(
+            result(process, list1, list2),
+            Ok(process.list_from_slice(&[process.integer(1), process.integer(2)]))
+        );
+    });
+}
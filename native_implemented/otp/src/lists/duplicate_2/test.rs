@@ -0,0 +1,29 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::duplicate_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_negative_n_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(-1);
+
+        assert_badarg!(
+            result(process, n, Atom::str_to_term("element")),
+            format!("n ({}) is not a non-negative integer", n)
+        );
+    });
+}
+
+#[test]
+fn with_non_negative_n_returns_n_copies() {
+    with_process(|process| {
+        let element = Atom::str_to_term("element");
+
+        assert_eq!(result(process, process.integer(0), element), Ok(Term::NIL));
+        assert_eq!(
+            result(process, process.integer(3), element),
+            Ok(process.list_from_slice(&[element, element, element]))
+        );
+    });
+}
@@ -0,0 +1,37 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns the elements of `deep_list` and of any lists in it, recursively, as a flat list.
+#[native_implemented::function(lists:flatten/1)]
+pub fn result(process: &Process, deep_list: Term) -> exception::Result<Term> {
+    let elements = flatten(deep_list)?;
+
+    Ok(process.list_from_slice(&elements))
+}
+
+// Private
+
+fn flatten(deep_list: Term) -> exception::Result<Vec<Term>> {
+    let mut elements = Vec::new();
+    // The rest of each list being flattened, innermost last, so that deep nesting does not grow the
+    // native stack
+    let mut stack = vec![super::term_try_into_vec("deep_list", deep_list)?.into_iter()];
+
+    while let Some(rest) = stack.last_mut() {
+        match rest.next() {
+            Some(element) if element.is_list() => {
+                stack.push(super::term_try_into_vec("deep_list", element)?.into_iter());
+            }
+            Some(element) => elements.push(element),
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    Ok(elements)
+}
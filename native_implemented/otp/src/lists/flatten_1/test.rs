@@ -0,0 +1,82 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::flatten_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, deep_list)| {
+            prop_assert_badarg!(
+                result(&arc_process, deep_list),
+                format!("deep_list ({}) is not a proper list", deep_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_nested_improper_list_errors_badarg() {
+    with_process(|process| {
+        let improper =
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail"));
+        let deep_list = process.list_from_slice(&[improper]);
+
+        assert_badarg!(
+            result(process, deep_list),
+            format!("deep_list ({}) is not a proper list", improper)
+        );
+    });
+}
+
+#[test]
+fn with_nested_lists_returns_flat_list() {
+    with_process(|process| {
+        let deep_list = process.list_from_slice(&[
+            process.integer(1),
+            process.list_from_slice(&[
+                process.integer(2),
+                Term::NIL,
+                process.list_from_slice(&[process.integer(3)]),
+            ]),
+            process.integer(4),
+        ]);
+
+        assert_eq!(
+            result(process, deep_list),
+            Ok(process.list_from_slice(&[
+                process.integer(1),
+                process.integer(2),
+                process.integer(3),
+                process.integer(4),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_deeply_nested_list_returns_flat_list() {
+    with_process(|process| {
+        let mut deep_list = process.list_from_slice(&[process.integer(1)]);
+
+        for _ in 0..100_000 {
+            deep_list = process.list_from_slice(&[deep_list]);
+        }
+
+        assert_eq!(
+            result(process, deep_list),
+            Ok(process.list_from_slice(&[process.integer(1)]))
+        );
+    });
+}
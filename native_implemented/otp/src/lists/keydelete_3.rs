@@ -0,0 +1,32 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::term_try_into_one_based_index;
+
+/// Returns `tuple_list` without the first tuple whose element at `index` compares equal to `key`.
+#[native_implemented::function(lists:keydelete/3)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index)?;
+    let mut vec = super::term_try_into_vec("tuple_list", tuple_list)?;
+
+    match vec
+        .iter()
+        .position(|element| super::element_key_matches(index, key, *element))
+    {
+        Some(position) => {
+            vec.remove(position);
+
+            Ok(process.list_from_slice(&vec))
+        }
+        None => Ok(tuple_list),
+    }
+}
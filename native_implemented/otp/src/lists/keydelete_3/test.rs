@@ -0,0 +1,60 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keydelete_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let tuple_list = process.improper_list_from_slice(
+            &[process.tuple_from_slice(&[Atom::str_to_term("a")])],
+            Atom::str_to_term("tail"),
+        );
+
+        assert_badarg!(
+            result(
+                process,
+                Atom::str_to_term("a"),
+                process.integer(1),
+                tuple_list
+            ),
+            format!("tuple_list ({}) is not a proper list", tuple_list)
+        );
+    });
+}
+
+#[test]
+fn with_key_deletes_only_first_tuple_with_key() {
+    with_process(|process| {
+        let key = Atom::str_to_term("key");
+        let first = process.tuple_from_slice(&[key, process.integer(1)]);
+        let other = process.tuple_from_slice(&[Atom::str_to_term("other"), process.integer(2)]);
+        let second = process.tuple_from_slice(&[key, process.integer(3)]);
+        let tuple_list =
+            process.list_from_slice(&[other, Atom::str_to_term("skipped"), first, second]);
+
+        assert_eq!(
+            result(process, key, process.integer(1), tuple_list),
+            Ok(process.list_from_slice(&[other, Atom::str_to_term("skipped"), second]))
+        );
+    });
+}
+
+#[test]
+fn without_key_returns_tuple_list() {
+    with_process(|process| {
+        let tuple_list = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("other"), process.integer(1)])
+        ]);
+
+        assert_eq!(
+            result(
+                process,
+                Atom::str_to_term("key"),
+                process.integer(1),
+                tuple_list
+            ),
+            Ok(tuple_list)
+        );
+    });
+}
@@ -0,0 +1,28 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::term_try_into_one_based_index;
+
+/// Sorts `tuple_list` by the element at `index` of each tuple.  The sort is stable, so tuples
+/// with keys that compare equal stay in their original order.
+#[native_implemented::function(lists:keysort/2)]
+pub fn result(process: &Process, index: Term, tuple_list: Term) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index)?;
+    let vec = super::term_try_into_vec("tuple_list", tuple_list)?;
+
+    // Like the BEAM, a list with only one element is already sorted, so it isn't checked.
+    if vec.len() <= 1 {
+        return Ok(tuple_list);
+    }
+
+    let mut keyed_vec = super::keyed_vec(index, tuple_list, vec)?;
+    keyed_vec.sort_by(|(left_key, _), (right_key, _)| left_key.cmp(right_key));
+
+    let sorted: Vec<Term> = keyed_vec.into_iter().map(|(_, element)| element).collect();
+
+    Ok(process.list_from_slice(&sorted))
+}
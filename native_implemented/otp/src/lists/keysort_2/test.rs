@@ -0,0 +1,77 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keysort_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_one_based_index_errors_badarg() {
+    with_process(|process| {
+        let index = process.integer(0);
+
+        assert_badarg!(
+            result(process, index, Term::NIL),
+            format!("index ({}) is not a 1-based integer", index)
+        );
+    });
+}
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let tuple_list = process.improper_list_from_slice(
+            &[process.tuple_from_slice(&[process.integer(1)])],
+            Atom::str_to_term("tail"),
+        );
+
+        assert_badarg!(
+            result(process, process.integer(1), tuple_list),
+            format!("tuple_list ({}) is not a proper list", tuple_list)
+        );
+    });
+}
+
+#[test]
+fn with_tuple_without_index_errors_badarg() {
+    with_process(|process| {
+        let short = process.tuple_from_slice(&[process.integer(1)]);
+        let tuple_list = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(1), process.integer(2)]),
+            short,
+        ]);
+
+        assert_badarg!(
+            result(process, process.integer(2), tuple_list),
+            format!(
+                "element ({}) of tuple_list ({}) is not a tuple with an element at index",
+                short, tuple_list
+            )
+        );
+    });
+}
+
+#[test]
+fn with_one_element_returns_list_without_checking_element() {
+    with_process(|process| {
+        let tuple_list = process.list_from_slice(&[Atom::str_to_term("not_a_tuple")]);
+
+        assert_eq!(
+            result(process, process.integer(2), tuple_list),
+            Ok(tuple_list)
+        );
+    });
+}
+
+#[test]
+fn with_tuples_sorts_stably_by_key() {
+    with_process(|process| {
+        let b1 = process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(1)]);
+        let a2 = process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(2)]);
+        let b3 = process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(3)]);
+        let tuple_list = process.list_from_slice(&[b1, a2, b3]);
+
+        assert_eq!(
+            result(process, process.integer(1), tuple_list),
+            Ok(process.list_from_slice(&[a2, b1, b3]))
+        );
+    });
+}
@@ -0,0 +1,33 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::{term_try_into_one_based_index, term_try_into_tuple};
+
+/// Replaces the first tuple in `tuple_list` whose element at `index` compares equal to `key` with
+/// `new_tuple`.  If there is no such tuple, `new_tuple` is appended to `tuple_list` instead.
+#[native_implemented::function(lists:keystore/4)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+    new_tuple: Term,
+) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index)?;
+    term_try_into_tuple("new_tuple", new_tuple)?;
+    let mut vec = super::term_try_into_vec("tuple_list", tuple_list)?;
+
+    match vec
+        .iter()
+        .position(|element| super::element_key_matches(index, key, *element))
+    {
+        Some(position) => vec[position] = new_tuple,
+        None => vec.push(new_tuple),
+    }
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::term_try_into_one_based_index;
+
+/// Returns `{value, tuple, tuple_list_without_tuple}` for the first `tuple` in `tuple_list` whose
+/// element at `index` compares equal to `key`, or `false` if there is no such tuple.
+#[native_implemented::function(lists:keytake/3)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index)?;
+    let mut vec = super::term_try_into_vec("tuple_list", tuple_list)?;
+
+    match vec
+        .iter()
+        .position(|element| super::element_key_matches(index, key, *element))
+    {
+        Some(position) => {
+            let tuple = vec.remove(position);
+            let rest = process.list_from_slice(&vec);
+
+            Ok(process.tuple_from_slice(&[Atom::str_to_term("value"), tuple, rest]))
+        }
+        None => Ok(false.into()),
+    }
+}
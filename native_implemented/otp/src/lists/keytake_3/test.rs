@@ -0,0 +1,59 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keytake_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let tuple_list = process.improper_list_from_slice(&[], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(
+                process,
+                Atom::str_to_term("key"),
+                process.integer(1),
+                tuple_list
+            ),
+            format!("tuple_list ({}) is not a proper list", tuple_list)
+        );
+    });
+}
+
+#[test]
+fn with_key_returns_value_tuple_and_rest() {
+    with_process(|process| {
+        let key = Atom::str_to_term("key");
+        let other = process.tuple_from_slice(&[Atom::str_to_term("other"), process.integer(1)]);
+        let tuple = process.tuple_from_slice(&[process.integer(2), key]);
+        let tuple_list = process.list_from_slice(&[other, tuple]);
+
+        assert_eq!(
+            result(process, key, process.integer(2), tuple_list),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("value"),
+                tuple,
+                process.list_from_slice(&[other]),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn without_key_returns_false() {
+    with_process(|process| {
+        let tuple_list = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("other"), process.integer(1)])
+        ]);
+
+        assert_eq!(
+            result(
+                process,
+                Atom::str_to_term("key"),
+                process.integer(1),
+                tuple_list
+            ),
+            Ok(false.into())
+        );
+    });
+}
@@ -0,0 +1,20 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+
+/// Returns the last element of `list`.
+#[native_implemented::function(lists:last/1)]
+pub fn result(list: Term) -> exception::Result<Term> {
+    let vec = super::term_try_into_vec("list", list)?;
+
+    match vec.last() {
+        Some(last) => Ok(*last),
+        None => Err(anyhow!(term_is_not_non_empty_list("list", list)).into()),
+    }
+}
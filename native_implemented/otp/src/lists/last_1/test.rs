@@ -0,0 +1,34 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::last_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_empty_list_errors_badarg() {
+    assert_badarg!(
+        result(Term::NIL),
+        format!("list ({}) is not a non-empty list", Term::NIL)
+    );
+}
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let list =
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(list),
+            format!("list ({}) is not a proper list", list)
+        );
+    });
+}
+
+#[test]
+fn with_non_empty_list_returns_last_element() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[process.integer(1), process.integer(2)]);
+
+        assert_eq!(result(list), Ok(process.integer(2)));
+    });
+}
@@ -0,0 +1,31 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+
+/// Returns the first of the elements of `list` that compare greater than or equal to all others.
+#[native_implemented::function(lists:max/1)]
+pub fn result(list: Term) -> exception::Result<Term> {
+    let vec = super::term_try_into_vec("list", list)?;
+
+    match vec.split_first() {
+        Some((first, rest)) => {
+            Ok(rest.iter().fold(
+                *first,
+                |max, element| {
+                    if *element > max {
+                        *element
+                    } else {
+                        max
+                    }
+                },
+            ))
+        }
+        None => Err(anyhow!(term_is_not_non_empty_list("list", list)).into()),
+    }
+}
@@ -0,0 +1,34 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::min_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_empty_list_errors_badarg() {
+    assert_badarg!(
+        result(Term::NIL),
+        format!("list ({}) is not a non-empty list", Term::NIL)
+    );
+}
+
+#[test]
+fn with_non_empty_list_returns_least_element_in_term_order() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[
+            Atom::str_to_term("a"),
+            process.integer(3),
+            process.float(2.5),
+        ]);
+
+        assert_eq!(result(list), Ok(process.float(2.5)));
+    });
+}
+
+#[test]
+fn with_elements_that_compare_equal_returns_first() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[process.float(1.0), process.integer(1)]);
+
+        assert!(result(list).unwrap().is_boxed_float());
+    });
+}
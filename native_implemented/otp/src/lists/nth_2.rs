@@ -0,0 +1,25 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+
+/// Returns the `n`th element of `list`, counting from 1.
+#[native_implemented::function(lists:nth/2)]
+pub fn result(n: Term, list: Term) -> exception::Result<Term> {
+    let n_usize = super::term_try_into_non_negative_usize("n", n)?;
+
+    if n_usize == 0 {
+        return Err(anyhow!(term_is_not_type("n", n, "a 1-based integer")).into());
+    }
+
+    let tail = super::nthtail("list", list, n_usize - 1)?;
+    let cons = term_try_into_non_empty_list("list", tail)
+        .with_context(|| format!("list ({}) has fewer than n ({}) elements", list, n))?;
+
+    Ok(cons.head)
+}
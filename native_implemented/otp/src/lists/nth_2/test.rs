@@ -0,0 +1,52 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::nth_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_zero_n_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(0);
+
+        assert_badarg!(
+            result(n, process.list_from_slice(&[process.integer(1)])),
+            format!("n ({}) is not a 1-based integer", n)
+        );
+    });
+}
+
+#[test]
+fn with_n_greater_than_length_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(2);
+        let list = process.list_from_slice(&[process.integer(1)]);
+
+        assert_badarg!(
+            result(n, list),
+            format!("list ({}) has fewer than n ({}) elements", list, n)
+        );
+    });
+}
+
+#[test]
+fn with_n_in_list_returns_nth_element() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[
+            Atom::str_to_term("a"),
+            Atom::str_to_term("b"),
+            Atom::str_to_term("c"),
+        ]);
+
+        assert_eq!(result(process.integer(2), list), Ok(Atom::str_to_term("b")));
+    });
+}
+
+#[test]
+fn with_improper_tail_after_nth_element_returns_nth_element() {
+    with_process(|process| {
+        let list =
+            process.improper_list_from_slice(&[Atom::str_to_term("a")], Atom::str_to_term("tail"));
+
+        assert_eq!(result(process.integer(1), list), Ok(Atom::str_to_term("a")));
+    });
+}
@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns the tail of `list` after its first `n` elements.
+#[native_implemented::function(lists:nthtail/2)]
+pub fn result(n: Term, list: Term) -> exception::Result<Term> {
+    let n_usize = super::term_try_into_non_negative_usize("n", n)?;
+
+    super::nthtail("list", list, n_usize).map_err(From::from)
+}
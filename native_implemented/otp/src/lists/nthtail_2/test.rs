@@ -0,0 +1,49 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::nthtail_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_negative_n_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(-1);
+
+        assert_badarg!(
+            result(n, Term::NIL),
+            format!("n ({}) is not a non-negative integer", n)
+        );
+    });
+}
+
+#[test]
+fn with_zero_n_returns_list() {
+    with_process(|process| {
+        let list = Atom::str_to_term("not_a_list");
+
+        assert_eq!(result(process.integer(0), list), Ok(list));
+    });
+}
+
+#[test]
+fn with_n_less_than_or_equal_to_length_returns_tail() {
+    with_process(|process| {
+        let tail = process.list_from_slice(&[process.integer(3)]);
+        let list =
+            process.improper_list_from_slice(&[process.integer(1), process.integer(2)], tail);
+
+        assert_eq!(result(process.integer(2), list), Ok(tail));
+        assert_eq!(result(process.integer(3), list), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_n_greater_than_length_errors_badarg() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[process.integer(1)]);
+
+        assert_badarg!(
+            result(process.integer(2), list),
+            format!("list ({}) is not a list with at least 2 elements", list)
+        );
+    });
+}
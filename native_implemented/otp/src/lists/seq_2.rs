@@ -0,0 +1,14 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::seq_3;
+
+/// Returns the integers from `from` to `to`, inclusive.
+#[native_implemented::function(lists:seq/2)]
+pub fn result(process: &Process, from: Term, to: Term) -> exception::Result<Term> {
+    seq_3::result(process, from, to, process.integer(1))
+}
@@ -0,0 +1,54 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::seq_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_integer_from_errors_badarg() {
+    with_process(|process| {
+        let from = Atom::str_to_term("from");
+
+        assert_badarg!(
+            result(process, from, process.integer(1)),
+            format!("from ({}) is not an integer", from)
+        );
+    });
+}
+
+#[test]
+fn with_to_less_than_from_minus_one_errors_badarg() {
+    with_process(|process| {
+        let from = process.integer(2);
+        let to = process.integer(0);
+
+        assert_badarg!(
+            result(process, from, to),
+            format!("from ({}) cannot reach to ({}) by incr (1)", from, to)
+        );
+    });
+}
+
+#[test]
+fn with_to_one_less_than_from_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(1), process.integer(0)),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_to_greater_than_from_returns_integers_from_from_to_to() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(-1), process.integer(2)),
+            Ok(process.list_from_slice(&[
+                process.integer(-1),
+                process.integer(0),
+                process.integer(1),
+                process.integer(2),
+            ]))
+        );
+    });
+}
@@ -0,0 +1,49 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+
+/// Returns the integers starting at `from` and stepping by `incr` up to, but not past, `to`.
+///
+/// Like the BEAM, `to` can be at most one `incr` short of `from`, in which case the list is empty,
+/// and an `incr` of `0` is only allowed when `from` and `to` are the same.
+#[native_implemented::function(lists:seq/3)]
+pub fn result(process: &Process, from: Term, to: Term, incr: Term) -> exception::Result<Term> {
+    let from_isize = term_try_into_isize("from", from)?;
+    let to_isize = term_try_into_isize("to", to)?;
+    let incr_isize = term_try_into_isize("incr", incr)?;
+
+    // `i128`, so that stepping back from `from` cannot overflow
+    let from_minus_incr = (from_isize as i128) - (incr_isize as i128);
+
+    let vec: Vec<Term> = if 0 < incr_isize && from_minus_incr <= (to_isize as i128) {
+        (from_isize..=to_isize)
+            .step_by(incr_isize as usize)
+            .map(|integer| process.integer(integer))
+            .collect()
+    } else if incr_isize < 0 && (to_isize as i128) <= from_minus_incr {
+        (to_isize..=from_isize)
+            .rev()
+            .step_by(incr_isize.wrapping_neg() as usize)
+            .map(|integer| process.integer(integer))
+            .collect()
+    } else if incr_isize == 0 && from_isize == to_isize {
+        vec![from]
+    } else {
+        return Err(anyhow!(
+            "from ({}) cannot reach to ({}) by incr ({})",
+            from,
+            to,
+            incr
+        )
+        .into());
+    };
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,136 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::seq_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_positive_incr_returns_integers_up_to_to() {
+    with_process(|process| {
+        assert_eq!(
+            result(
+                process,
+                process.integer(1),
+                process.integer(10),
+                process.integer(4)
+            ),
+            Ok(process.list_from_slice(&[
+                process.integer(1),
+                process.integer(5),
+                process.integer(9),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_negative_incr_returns_integers_down_to_to() {
+    with_process(|process| {
+        assert_eq!(
+            result(
+                process,
+                process.integer(10),
+                process.integer(1),
+                process.integer(-4)
+            ),
+            Ok(process.list_from_slice(&[
+                process.integer(10),
+                process.integer(6),
+                process.integer(2),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_to_one_incr_short_of_from_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(
+            result(
+                process,
+                process.integer(5),
+                process.integer(3),
+                process.integer(2)
+            ),
+            Ok(Term::NIL)
+        );
+        assert_eq!(
+            result(
+                process,
+                process.integer(3),
+                process.integer(5),
+                process.integer(-2)
+            ),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_zero_incr_and_from_equal_to_to_returns_from() {
+    with_process(|process| {
+        let from = process.integer(3);
+
+        assert_eq!(
+            result(process, from, process.integer(3), process.integer(0)),
+            Ok(process.list_from_slice(&[from]))
+        );
+    });
+}
+
+#[test]
+fn with_zero_incr_and_from_not_equal_to_to_errors_badarg() {
+    with_process(|process| {
+        let from = process.integer(1);
+        let to = process.integer(3);
+        let incr = process.integer(0);
+
+        assert_badarg!(
+            result(process, from, to, incr),
+            format!(
+                "from ({}) cannot reach to ({}) by incr ({})",
+                from, to, incr
+            )
+        );
+    });
+}
+
+#[test]
+fn with_to_more_than_one_incr_short_of_from_errors_badarg() {
+    with_process(|process| {
+        let from = process.integer(5);
+        let to = process.integer(2);
+        let incr = process.integer(2);
+
+        assert_badarg!(
+            result(process, from, to, incr),
+            format!(
+                "from ({}) cannot reach to ({}) by incr ({})",
+                from, to, incr
+            )
+        );
+    });
+}
+
+#[test]
+fn with_from_at_isize_min_and_positive_incr_returns_from() {
+    with_process(|process| {
+        let from = process.integer(isize::min_value());
+
+        assert_eq!(
+            result(process, from, from, process.integer(1)),
+            Ok(process.list_from_slice(&[from]))
+        );
+    });
+}
+
+#[test]
+fn with_from_at_isize_max_and_negative_incr_returns_from() {
+    with_process(|process| {
+        let from = process.integer(isize::max_value());
+
+        assert_eq!(
+            result(process, from, from, process.integer(-1)),
+            Ok(process.list_from_slice(&[from]))
+        );
+    });
+}
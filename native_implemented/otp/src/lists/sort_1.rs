@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Sorts `list` in term order.  The sort is stable, so elements that compare equal, such as `1`
+/// and `1.0`, stay in their original order.
+#[native_implemented::function(lists:sort/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    let mut vec = super::term_try_into_vec("list", list)?;
+    vec.sort();
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,69 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sort_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_badarg!(
+                result(&arc_process, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_proper_list_returns_elements_in_term_order() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[
+            Atom::str_to_term("b"),
+            process.integer(2),
+            Term::NIL,
+            process.float(1.5),
+            Atom::str_to_term("a"),
+        ]);
+
+        assert_eq!(
+            result(process, list),
+            Ok(process.list_from_slice(&[
+                process.float(1.5),
+                process.integer(2),
+                Atom::str_to_term("a"),
+                Atom::str_to_term("b"),
+                Term::NIL,
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_elements_that_compare_equal_keeps_original_order() {
+    with_process(|process| {
+        let float = process.float(1.0);
+        let integer = process.integer(1);
+        let list = process.list_from_slice(&[process.integer(2), float, integer]);
+
+        let sorted: Boxed<Cons> = result(process, list).unwrap().try_into().unwrap();
+        let vec: Vec<Term> = sorted.into_iter().map(|result| result.unwrap()).collect();
+
+        assert!(vec[0].is_boxed_float());
+        assert!(vec[1].is_smallint());
+        assert_eq!(vec[2], process.integer(2));
+    });
+}
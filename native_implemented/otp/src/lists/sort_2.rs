@@ -0,0 +1,136 @@
+//! ```elixir
+//! def sort(fun, list) do
+//!   runs = Enum.map(list, &[&1])
+//!   merge_runs(fun, runs, [])
+//! end
+//!
+//! defp merge_runs(_fun, [], []), do: []
+//! defp merge_runs(_fun, [], [run]), do: run
+//! defp merge_runs(fun, [], merged_runs), do: merge_runs(fun, :lists.reverse(merged_runs), [])
+//! defp merge_runs(fun, [run], merged_runs), do: merge_runs(fun, [], [run | merged_runs])
+//! defp merge_runs(fun, [left, right | runs], merged_runs) do
+//!   merge(fun, {left, right, []}, runs, merged_runs)
+//! end
+//!
+//! defp merge(fun, {[], right, merged_reversed}, runs, merged_runs) do
+//!   merge_runs(fun, runs, [:lists.reverse(merged_reversed, right) | merged_runs])
+//! end
+//! defp merge(fun, {left, [], merged_reversed}, runs, merged_runs) do
+//!   merge_runs(fun, runs, [:lists.reverse(merged_reversed, left) | merged_runs])
+//! end
+//! defp merge(fun, {[l | ls] = left, [r | rs] = right, merged_reversed}, runs, merged_runs) do
+//!   case fun.(l, r) do
+//!     true -> merge(fun, {ls, right, [l | merged_reversed]}, runs, merged_runs)
+//!     false -> merge(fun, {left, rs, [r | merged_reversed]}, runs, merged_runs)
+//!   end
+//! end
+//! ```
+//!
+//! A bottom-up merge sort, so that `fun` is called through frames instead of blocking the
+//! scheduler.  `l` is taken when `fun.(l, r)` is `true`, so the sort is stable.
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+use crate::lists::reverse_2;
+
+#[native_implemented::function(lists:sort/2)]
+pub fn result(process: &Process, fun: Term, list: Term) -> exception::Result<Term> {
+    let fun_boxed_closure: Boxed<Closure> = fun
+        .try_into()
+        .with_context(|| format!("fun ({}) is not a function", fun))?;
+
+    if fun_boxed_closure.arity() != 2 {
+        return Err(anyhow!("fun ({}) is not arity 2", fun).into());
+    }
+
+    let runs: Vec<Term> = super::term_try_into_vec("list", list)?
+        .into_iter()
+        .map(|element| process.list_from_slice(&[element]))
+        .collect();
+
+    Ok(merge_runs(
+        process,
+        fun,
+        process.list_from_slice(&runs),
+        Term::NIL,
+    ))
+}
+
+// Private
+
+/// Merges `runs` in pairs onto `merged_runs`, which is in reverse order, and then merges the
+/// `merged_runs` in the next pass until only one run remains.
+fn merge_runs(process: &Process, fun: Term, runs: Term, merged_runs: Term) -> Term {
+    match runs.decode().unwrap() {
+        TypedTerm::Nil => match merged_runs.decode().unwrap() {
+            TypedTerm::Nil => Term::NIL,
+            TypedTerm::List(merged_runs_cons) if merged_runs_cons.tail.is_nil() => {
+                merged_runs_cons.head
+            }
+            _ => {
+                let next_runs = reverse_2::result(process, merged_runs, Term::NIL).unwrap();
+
+                merge_runs(process, fun, next_runs, Term::NIL)
+            }
+        },
+        TypedTerm::List(runs_cons) => match runs_cons.tail.decode().unwrap() {
+            TypedTerm::Nil => merge_runs(
+                process,
+                fun,
+                Term::NIL,
+                process.cons(runs_cons.head, merged_runs),
+            ),
+            TypedTerm::List(tail_cons) => {
+                let merge = process.tuple_from_slice(&[runs_cons.head, tail_cons.head, Term::NIL]);
+
+                self::merge(process, fun, merge, tail_cons.tail, merged_runs)
+            }
+            _ => unreachable!("runs ({}) is not a proper list", runs),
+        },
+        _ => unreachable!("runs ({}) is not a list", runs),
+    }
+}
+
+/// Calls `fun` with the heads of the `left` and `right` runs in `merge` and continues in `label_1`
+/// when it returns.  When either run is empty, the merged run is complete.
+fn merge(process: &Process, fun: Term, merge: Term, runs: Term, merged_runs: Term) -> Term {
+    let merge_tuple: Boxed<Tuple> = merge.try_into().unwrap();
+    let left = merge_tuple[0];
+    let right = merge_tuple[1];
+    let merged_reversed = merge_tuple[2];
+
+    match (left.decode().unwrap(), right.decode().unwrap()) {
+        (TypedTerm::List(left_cons), TypedTerm::List(right_cons)) => {
+            let arguments = process.list_from_slice(&[left_cons.head, right_cons.head]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[fun, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[fun, merge, runs, merged_runs]),
+            );
+
+            Term::NONE
+        }
+        (TypedTerm::Nil, _) => {
+            let run = reverse_2::result(process, merged_reversed, right).unwrap();
+
+            merge_runs(process, fun, runs, process.cons(run, merged_runs))
+        }
+        (_, TypedTerm::Nil) => {
+            let run = reverse_2::result(process, merged_reversed, left).unwrap();
+
+            merge_runs(process, fun, runs, process.cons(run, merged_runs))
+        }
+        _ => unreachable!("merge ({}) runs are not lists", merge),
+    }
+}
@@ -0,0 +1,64 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, merge, runs, merged_runs)
+//! # returned from call: left_first
+//! # full stack: (left_first, fun, merge, runs, merged_runs)
+//! # returns: sorted
+//! {[l | ls] = left, [r | rs] = right, merged_reversed} = merge
+//! case left_first do
+//!   true -> merge(fun, {ls, right, [l | merged_reversed]}, runs, merged_runs)
+//!   false -> merge(fun, {left, rs, [r | merged_reversed]}, runs, merged_runs)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    left_first: Term,
+    fun: Term,
+    merge: Term,
+    runs: Term,
+    merged_runs: Term,
+) -> exception::Result<Term> {
+    assert!(fun.is_boxed_function());
+    assert!(runs.is_list());
+    assert!(merged_runs.is_list());
+
+    let left_first_bool: bool = left_first.try_into().with_context(|| {
+        format!(
+            "fun ({}) returned ({}), which is not a boolean",
+            fun, left_first
+        )
+    })?;
+
+    let merge_tuple: Boxed<Tuple> = merge.try_into().unwrap();
+    let left_cons: Boxed<Cons> = merge_tuple[0].try_into().unwrap();
+    let right_cons: Boxed<Cons> = merge_tuple[1].try_into().unwrap();
+    let merged_reversed = merge_tuple[2];
+
+    let next_merge = if left_first_bool {
+        process.tuple_from_slice(&[
+            left_cons.tail,
+            merge_tuple[1],
+            process.cons(left_cons.head, merged_reversed),
+        ])
+    } else {
+        process.tuple_from_slice(&[
+            merge_tuple[0],
+            right_cons.tail,
+            process.cons(right_cons.head, merged_reversed),
+        ])
+    };
+
+    Ok(super::merge(process, fun, next_merge, runs, merged_runs))
+}
@@ -0,0 +1,36 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Splits `list1` into `{list2, list3}`, where `list2` is the first `n` elements of `list1` and
+/// `list3` is the rest.
+#[native_implemented::function(lists:split/2)]
+pub fn result(process: &Process, n: Term, list1: Term) -> exception::Result<Term> {
+    let n_usize = super::term_try_into_non_negative_usize("n", n)?;
+    let mut elements = Vec::with_capacity(n_usize);
+    let mut tail = list1;
+
+    while elements.len() < n_usize {
+        match tail.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                elements.push(cons.head);
+                tail = cons.tail;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "list1 ({}) is not a list with at least n ({}) elements",
+                    list1,
+                    n
+                )
+                .into())
+            }
+        }
+    }
+
+    Ok(process.tuple_from_slice(&[process.list_from_slice(&elements), tail]))
+}
@@ -0,0 +1,52 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::split_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_negative_n_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(-1);
+
+        assert_badarg!(
+            result(process, n, Term::NIL),
+            format!("n ({}) is not a non-negative integer", n)
+        );
+    });
+}
+
+#[test]
+fn with_n_greater_than_length_errors_badarg() {
+    with_process(|process| {
+        let n = process.integer(2);
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+
+        assert_badarg!(
+            result(process, n, list1),
+            format!(
+                "list1 ({}) is not a list with at least n ({}) elements",
+                list1, n
+            )
+        );
+    });
+}
+
+#[test]
+fn with_n_less_than_or_equal_to_length_returns_prefix_and_suffix() {
+    with_process(|process| {
+        let list1 =
+            process.list_from_slice(&[process.integer(1), process.integer(2), process.integer(3)]);
+
+        assert_eq!(
+            result(process, process.integer(1), list1),
+            Ok(process.tuple_from_slice(&[
+                process.list_from_slice(&[process.integer(1)]),
+                process.list_from_slice(&[process.integer(2), process.integer(3)]),
+            ]))
+        );
+        assert_eq!(
+            result(process, process.integer(3), list1),
+            Ok(process.tuple_from_slice(&[list1, Term::NIL]))
+        );
+    });
+}
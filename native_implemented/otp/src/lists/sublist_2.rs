@@ -0,0 +1,46 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+
+/// Returns the first `len` elements of `list1`, or all of `list1` if it is shorter than `len`.
+#[native_implemented::function(lists:sublist/2)]
+pub fn result(process: &Process, list1: Term, len: Term) -> exception::Result<Term> {
+    let len_usize = super::term_try_into_non_negative_usize("len", len)?;
+
+    sublist(process, list1, list1, len_usize)
+}
+
+/// The first `len` elements of `tail`, which is `list1` or one of its tails.
+pub(in crate::lists) fn sublist(
+    process: &Process,
+    list1: Term,
+    tail: Term,
+    len: usize,
+) -> exception::Result<Term> {
+    let mut elements = Vec::new();
+    let mut tail = tail;
+
+    while elements.len() < len {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => break,
+            TypedTerm::List(cons) => {
+                elements.push(cons.head);
+                tail = cons.tail;
+            }
+            _ => {
+                return Err(anyhow!(ImproperListError))
+                    .context(term_is_not_type("list1", list1, "a proper list"))
+                    .map_err(From::from)
+            }
+        }
+    }
+
+    Ok(process.list_from_slice(&elements))
+}
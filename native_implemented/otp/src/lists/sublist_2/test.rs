@@ -0,0 +1,51 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sublist_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_negative_len_errors_badarg() {
+    with_process(|process| {
+        let len = process.integer(-1);
+
+        assert_badarg!(
+            result(process, Term::NIL, len),
+            format!("len ({}) is not a non-negative integer", len)
+        );
+    });
+}
+
+#[test]
+fn with_len_less_than_length_returns_first_len_elements() {
+    with_process(|process| {
+        let list1 =
+            process.list_from_slice(&[process.integer(1), process.integer(2), process.integer(3)]);
+
+        assert_eq!(
+            result(process, list1, process.integer(2)),
+            Ok(process.list_from_slice(&[process.integer(1), process.integer(2)]))
+        );
+    });
+}
+
+#[test]
+fn with_len_greater_than_length_returns_list() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+
+        assert_eq!(result(process, list1, process.integer(5)), Ok(list1));
+    });
+}
+
+#[test]
+fn with_improper_list_shorter_than_len_errors_badarg() {
+    with_process(|process| {
+        let list1 =
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(process, list1, process.integer(2)),
+            format!("list1 ({}) is not a proper list", list1)
+        );
+    });
+}
@@ -0,0 +1,27 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sublist_2::sublist;
+use crate::runtime::context::*;
+
+/// Returns up to `len` elements of `list1` starting at the 1-based `start`.  `start` can be one
+/// past the end of `list1`, in which case the sublist is empty.
+#[native_implemented::function(lists:sublist/3)]
+pub fn result(process: &Process, list1: Term, start: Term, len: Term) -> exception::Result<Term> {
+    let start_usize = super::term_try_into_non_negative_usize("start", start)?;
+
+    if start_usize == 0 {
+        return Err(anyhow!(term_is_not_type("start", start, "a 1-based integer")).into());
+    }
+
+    let len_usize = super::term_try_into_non_negative_usize("len", len)?;
+    let tail = super::nthtail("list1", list1, start_usize - 1)?;
+
+    sublist(process, list1, tail, len_usize)
+}
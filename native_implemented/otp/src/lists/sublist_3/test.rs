@@ -0,0 +1,57 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sublist_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_zero_start_errors_badarg() {
+    with_process(|process| {
+        let start = process.integer(0);
+
+        assert_badarg!(
+            result(process, Term::NIL, start, process.integer(1)),
+            format!("start ({}) is not a 1-based integer", start)
+        );
+    });
+}
+
+#[test]
+fn with_start_in_list_returns_len_elements_from_start() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[
+            process.integer(1),
+            process.integer(2),
+            process.integer(3),
+            process.integer(4),
+        ]);
+
+        assert_eq!(
+            result(process, list1, process.integer(2), process.integer(2)),
+            Ok(process.list_from_slice(&[process.integer(2), process.integer(3)]))
+        );
+    });
+}
+
+#[test]
+fn with_start_one_past_end_returns_empty_list() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+
+        assert_eq!(
+            result(process, list1, process.integer(2), process.integer(1)),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_start_more_than_one_past_end_errors_badarg() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+
+        assert_badarg!(
+            result(process, list1, process.integer(3), process.integer(1)),
+            format!("list1 ({}) is not a list with at least 2 elements", list1)
+        );
+    });
+}
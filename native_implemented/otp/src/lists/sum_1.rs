@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::add_2;
+
+/// Returns the sum of the numbers in `list`.  Like `+/2`, non-numbers are a `badarith` error.
+#[native_implemented::function(lists:sum/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    super::term_try_into_vec("list", list)?
+        .into_iter()
+        .try_fold(process.integer(0), |sum, element| {
+            add_2::result(process, sum, element)
+        })
+}
@@ -0,0 +1,43 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sum_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_empty_list_returns_zero() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(process.integer(0)));
+    });
+}
+
+#[test]
+fn with_numbers_returns_sum() {
+    with_process(|process| {
+        let list =
+            process.list_from_slice(&[process.integer(1), process.integer(2), process.float(0.5)]);
+
+        assert_eq!(result(process, list), Ok(process.float(3.5)));
+    });
+}
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let list =
+            process.improper_list_from_slice(&[process.integer(1)], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(process, list),
+            format!("list ({}) is not a proper list", list)
+        );
+    });
+}
+
+#[test]
+fn with_non_number_errors_badarith() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[process.integer(1), Atom::str_to_term("a")]);
+
+        assert_badarith!(result(process, list));
+    });
+}
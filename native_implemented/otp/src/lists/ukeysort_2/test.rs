@@ -0,0 +1,48 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::ukeysort_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let tuple_list = process.improper_list_from_slice(
+            &[process.tuple_from_slice(&[process.integer(1)])],
+            Atom::str_to_term("tail"),
+        );
+
+        assert_badarg!(
+            result(process, process.integer(1), tuple_list),
+            format!("tuple_list ({}) is not a proper list", tuple_list)
+        );
+    });
+}
+
+#[test]
+fn with_tuples_with_equal_keys_keeps_first() {
+    with_process(|process| {
+        let b1 = process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(1)]);
+        let a2 = process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(2)]);
+        let b3 = process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(3)]);
+        let tuple_list = process.list_from_slice(&[b1, a2, b3]);
+
+        assert_eq!(
+            result(process, process.integer(1), tuple_list),
+            Ok(process.list_from_slice(&[a2, b1]))
+        );
+    });
+}
+
+#[test]
+fn with_keys_that_compare_equal_keeps_first() {
+    with_process(|process| {
+        let integer = process.tuple_from_slice(&[process.integer(1), Atom::str_to_term("integer")]);
+        let float = process.tuple_from_slice(&[process.float(1.0), Atom::str_to_term("float")]);
+        let tuple_list = process.list_from_slice(&[float, integer]);
+
+        assert_eq!(
+            result(process, process.integer(1), tuple_list),
+            Ok(process.list_from_slice(&[float]))
+        );
+    });
+}
@@ -0,0 +1,43 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Splits the `{element1, element2}` tuples of `list1` into `{list_of_element1s,
+/// list_of_element2s}`.
+#[native_implemented::function(lists:unzip/1)]
+pub fn result(process: &Process, list1: Term) -> exception::Result<Term> {
+    let vec = super::term_try_into_vec("list1", list1)?;
+    let mut vec1 = Vec::with_capacity(vec.len());
+    let mut vec2 = Vec::with_capacity(vec.len());
+
+    for element in vec {
+        let result_tuple: Result<Boxed<Tuple>, _> = element.try_into();
+
+        match result_tuple {
+            Ok(tuple) if tuple.len() == 2 => {
+                vec1.push(tuple[0]);
+                vec2.push(tuple[1]);
+            }
+            _ => {
+                return Err(anyhow!(
+                    "element ({}) of list1 ({}) is not a 2-tuple",
+                    element,
+                    list1
+                )
+                .into())
+            }
+        }
+    }
+
+    Ok(process.tuple_from_slice(&[
+        process.list_from_slice(&vec1),
+        process.list_from_slice(&vec2),
+    ]))
+}
@@ -0,0 +1,38 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::unzip_1::result;
+use crate::test::with_process;
+
+#[test]
+fn without_2_tuple_element_errors_badarg() {
+    with_process(|process| {
+        let element = process.tuple_from_slice(&[process.integer(1)]);
+        let list1 = process.list_from_slice(&[element]);
+
+        assert_badarg!(
+            result(process, list1),
+            format!(
+                "element ({}) of list1 ({}) is not a 2-tuple",
+                element, list1
+            )
+        );
+    });
+}
+
+#[test]
+fn with_2_tuples_returns_pair_of_lists() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(1), Atom::str_to_term("a")]),
+            process.tuple_from_slice(&[process.integer(2), Atom::str_to_term("b")]),
+        ]);
+
+        assert_eq!(
+            result(process, list1),
+            Ok(process.tuple_from_slice(&[
+                process.list_from_slice(&[process.integer(1), process.integer(2)]),
+                process.list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")]),
+            ]))
+        );
+    });
+}
@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Sorts `list` in term order, keeping only the first of the elements that compare equal.
+#[native_implemented::function(lists:usort/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    let mut vec = super::term_try_into_vec("list", list)?;
+    vec.sort();
+    vec.dedup();
+
+    Ok(process.list_from_slice(&vec))
+}
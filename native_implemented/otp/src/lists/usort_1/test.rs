@@ -0,0 +1,56 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::usort_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_badarg!(
+                result(&arc_process, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_duplicates_returns_sorted_first_of_each() {
+    with_process(|process| {
+        let list = process.list_from_slice(&[
+            Atom::str_to_term("b"),
+            process.integer(1),
+            Atom::str_to_term("a"),
+            Atom::str_to_term("b"),
+            process.float(1.0),
+        ]);
+
+        assert_eq!(
+            result(process, list),
+            Ok(process.list_from_slice(&[
+                process.integer(1),
+                Atom::str_to_term("a"),
+                Atom::str_to_term("b"),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(Term::NIL));
+    });
+}
@@ -0,0 +1,33 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns a list of `{element1, element2}` tuples from the elements of `list1` and `list2`, which
+/// must be the same length.
+#[native_implemented::function(lists:zip/2)]
+pub fn result(process: &Process, list1: Term, list2: Term) -> exception::Result<Term> {
+    let vec1 = super::term_try_into_vec("list1", list1)?;
+    let vec2 = super::term_try_into_vec("list2", list2)?;
+
+    if vec1.len() == vec2.len() {
+        let tuples: Vec<Term> = vec1
+            .into_iter()
+            .zip(vec2.into_iter())
+            .map(|(element1, element2)| process.tuple_from_slice(&[element1, element2]))
+            .collect();
+
+        Ok(process.list_from_slice(&tuples))
+    } else {
+        Err(anyhow!(
+            "list1 ({}) and list2 ({}) are not the same length",
+            list1,
+            list2
+        )
+        .into())
+    }
+}
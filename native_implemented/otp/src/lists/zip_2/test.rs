@@ -0,0 +1,50 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::zip_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_different_lengths_errors_badarg() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+        let list2 = Term::NIL;
+
+        assert_badarg!(
+            result(process, list1, list2),
+            format!(
+                "list1 ({}) and list2 ({}) are not the same length",
+                list1, list2
+            )
+        );
+    });
+}
+
+#[test]
+fn with_improper_list2_errors_badarg() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1)]);
+        let list2 =
+            process.improper_list_from_slice(&[process.integer(2)], Atom::str_to_term("tail"));
+
+        assert_badarg!(
+            result(process, list1, list2),
+            format!("list2 ({}) is not a proper list", list2)
+        );
+    });
+}
+
+#[test]
+fn with_same_lengths_returns_list_of_pairs() {
+    with_process(|process| {
+        let list1 = process.list_from_slice(&[process.integer(1), process.integer(2)]);
+        let list2 = process.list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")]);
+
+        assert_eq!(
+            result(process, list1, list2),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(1), Atom::str_to_term("a")]),
+                process.tuple_from_slice(&[process.integer(2), Atom::str_to_term("b")]),
+            ]))
+        );
+    });
+}
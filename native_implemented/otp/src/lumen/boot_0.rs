@@ -0,0 +1,113 @@
+//! Runs the boot script loaded with `--boot` in place of `init:start/0`.
+//!
+//! Instructions are run in order.  Each `{apply, {Mod, Func, Args}}` and
+//! `{kernelProcess, Name, {Mod, Func, Args}}` must return before the next instruction is run, so
+//! the remaining instructions are run from a label after the call returns.
+
+mod label_1;
+mod label_2;
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::apply::module_loaded;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_3;
+use crate::runtime::boot::{self, BootInstruction, BootScript};
+
+#[native_implemented::function(lumen:boot/0)]
+fn result(process: &Process) -> exception::Result<Term> {
+    run(process, 0)
+}
+
+/// Runs the instructions of the loaded boot script starting at `index` until one calls a function
+fn run(process: &Process, index: usize) -> exception::Result<Term> {
+    let script = boot::script().ok_or_else(|| anyhow!("no boot script is loaded"))?;
+
+    run_script(process, &script, index)
+}
+
+fn run_script(process: &Process, script: &BootScript, index: usize) -> exception::Result<Term> {
+    for (offset, instruction) in script.instructions[index..].iter().enumerate() {
+        let next_index = index + offset + 1;
+
+        match instruction {
+            BootInstruction::Progress(name) => boot::set_progress(Atom::from_str(name)),
+            // All modules are linked into the executable, so there are no paths to search
+            BootInstruction::Path(_)
+            | BootInstruction::PreLoaded(_)
+            | BootInstruction::KernelLoadCompleted => (),
+            BootInstruction::PrimLoad(modules) => {
+                for module in modules {
+                    if !module_loaded(Atom::from_str(module)) {
+                        return Err(error(
+                            atom!("undef"),
+                            None,
+                            Trace::capture(),
+                            Some(
+                                anyhow!("module ({}) is not linked into the executable", module)
+                                    .into(),
+                            ),
+                        )
+                        .into());
+                    }
+                }
+            }
+            BootInstruction::KernelProcess {
+                name,
+                module,
+                function,
+                arguments,
+            } => {
+                let argument_vec = arguments
+                    .iter()
+                    .map(|argument| argument.to_term(process))
+                    .collect();
+                queue_apply_3(process, module, function, argument_vec);
+                process.queue_frame_with_arguments(label_2::frame().with_arguments(
+                    true,
+                    &[Atom::str_to_term(name), process.integer(next_index)],
+                ));
+
+                return Ok(Term::NONE);
+            }
+            BootInstruction::Apply {
+                module,
+                function,
+                arguments,
+            } => {
+                let argument_vec = arguments
+                    .iter()
+                    .map(|argument| argument.to_term(process))
+                    .collect();
+                queue_apply_3(process, module, function, argument_vec);
+                process.queue_frame_with_arguments(
+                    label_1::frame().with_arguments(true, &[process.integer(next_index)]),
+                );
+
+                return Ok(Term::NONE);
+            }
+        }
+    }
+
+    Ok(atom!("ok"))
+}
+
+fn queue_apply_3(process: &Process, module: &str, function: &str, argument_vec: Vec<Term>) {
+    let argument_list = process.list_from_slice(&argument_vec);
+
+    process.queue_frame_with_arguments(apply_3::frame().with_arguments(
+        false,
+        &[
+            Atom::str_to_term(module),
+            Atom::str_to_term(function),
+            argument_list,
+        ],
+    ));
+}
@@ -0,0 +1,16 @@
+//! Continues the boot script after `{apply, {Mod, Func, Args}}` returns
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, _returned: Term, next_index: Term) -> exception::Result<Term> {
+    let next_index: usize = next_index.try_into().unwrap();
+
+    super::run(process, next_index)
+}
@@ -0,0 +1,83 @@
+//! Links init to the process started by `{kernelProcess, Name, {Mod, Func, Args}}`, so that the
+//! system stops if a kernel process exits, and remembers it as `Name`, then continues the boot
+//! script.
+//!
+//! Like OTP's `init`, the process is not registered under `Name`: kernel processes, such as
+//! `heart`, `error_logger` and `application_controller`, register themselves.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, exit};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::boot;
+use crate::runtime::registry::pid_to_process;
+
+/// Links `process` to the kernel process that `returned` `{ok, Pid}` and remembers it as `name`
+pub(super) fn started(process: &Process, name: Term, returned: Term) -> exception::Result<()> {
+    let name_atom: Atom = name.try_into().unwrap();
+
+    match started_process(returned) {
+        Some(arc_process) => {
+            process.link(&arc_process);
+            boot::put_kernel_process(name_atom, arc_process.pid());
+
+            Ok(())
+        }
+        None => Err(kernel_process_exit(
+            process,
+            name,
+            returned,
+            format!(
+                "kernel process ({}) start returned ({}) instead of {{ok, Pid}}",
+                name_atom, returned
+            ),
+        )),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    returned: Term,
+    name: Term,
+    next_index: Term,
+) -> exception::Result<Term> {
+    started(process, name, returned)?;
+
+    let next_index: usize = next_index.try_into().unwrap();
+
+    super::run(process, next_index)
+}
+
+/// `{ok, Pid}` where `Pid` is alive
+fn started_process(returned: Term) -> Option<Arc<Process>> {
+    let tuple: Boxed<Tuple> = returned.try_into().ok()?;
+
+    if tuple.len() == 2 && tuple[0] == Atom::str_to_term("ok") {
+        let pid: Pid = tuple[1].try_into().ok()?;
+
+        pid_to_process(&pid)
+    } else {
+        None
+    }
+}
+
+/// Exits init with `{Name, Returned}`
+fn kernel_process_exit(
+    process: &Process,
+    name: Term,
+    returned: Term,
+    message: String,
+) -> exception::Exception {
+    let reason = process.tuple_from_slice(&[name, returned]);
+
+    exit(reason, Trace::capture(), Some(anyhow!(message).into())).into()
+}
@@ -0,0 +1,93 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{Exception, RuntimeException};
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lumen::boot_0::{label_2, run_script};
+use crate::runtime::boot::{self, BootInstruction, BootScript};
+use crate::runtime::registry;
+use crate::test::{self, with_process};
+
+#[test]
+fn with_kernel_process_queues_start_and_stops_before_later_instructions() {
+    with_process(|process| {
+        let script = script(vec![
+            BootInstruction::Progress("test_kernel_starting".to_string()),
+            BootInstruction::KernelProcess {
+                name: "test_kernel".to_string(),
+                module: "test".to_string(),
+                function: "loop".to_string(),
+                arguments: vec![],
+            },
+            BootInstruction::Progress("test_kernel_started".to_string()),
+        ]);
+
+        assert_eq!(run_script(process, &script, 0), Ok(Term::NONE));
+        assert_eq!(
+            boot::progress(),
+            Some(Atom::from_str("test_kernel_starting"))
+        );
+    });
+}
+
+#[test]
+fn without_function_calls_runs_all_instructions() {
+    with_process(|process| {
+        let script = script(vec![
+            BootInstruction::Path(vec!["ebin".to_string()]),
+            BootInstruction::KernelLoadCompleted,
+        ]);
+
+        assert_eq!(run_script(process, &script, 0), Ok(atom!("ok")));
+    });
+}
+
+#[test]
+fn with_kernel_process_that_registered_itself_links_and_remembers_it() {
+    with_process(|process| {
+        let name_atom = Atom::from_str("test_kernel_registered_itself");
+        let kernel_arc_process = test::process::child(process);
+
+        // Like `heart`, `error_logger` and `application_controller`, the kernel process registers
+        // itself while starting
+        assert!(registry::put_atom_to_process(
+            name_atom,
+            kernel_arc_process.clone()
+        ));
+
+        let returned = process.tuple_from_slice(&[atom!("ok"), kernel_arc_process.pid_term()]);
+
+        assert_eq!(
+            label_2::started(process, name_atom.encode().unwrap(), returned),
+            Ok(())
+        );
+
+        let registered_arc_process = registry::atom_to_process(&name_atom).unwrap();
+
+        assert_eq!(registered_arc_process.pid(), kernel_arc_process.pid());
+        assert!(process.linked_pid_set.contains(&kernel_arc_process.pid()));
+        assert!(boot::kernel_processes().contains(&(name_atom, kernel_arc_process.pid())));
+    });
+}
+
+#[test]
+fn with_kernel_process_not_returning_ok_pid_exits_with_name_and_returned() {
+    with_process(|process| {
+        let name = Atom::str_to_term("test_kernel_ignored");
+        let returned = atom!("ignore");
+
+        match label_2::started(process, name, returned) {
+            Err(Exception::Runtime(RuntimeException::Exit(ref exit))) => {
+                assert_eq!(exit.reason(), process.tuple_from_slice(&[name, returned]))
+            }
+            other => panic!("Expected exit, but got {:?}", other),
+        }
+    });
+}
+
+fn script(instructions: Vec<BootInstruction>) -> BootScript {
+    BootScript {
+        name: "test".to_string(),
+        version: "1".to_string(),
+        instructions,
+    }
+}
@@ -1,17 +1,38 @@
+pub mod filter_2;
 pub mod find_2;
+pub mod fold_3;
+pub mod from_keys_2;
 pub mod from_list_1;
 pub mod get_2;
 pub mod get_3;
+pub mod intersect_2;
 pub mod is_key_2;
+pub mod iterator_1;
 pub mod keys_1;
+pub mod map_2;
 pub mod merge_2;
+pub mod merge_with_3;
+pub mod new_0;
+pub mod next_1;
 pub mod put_3;
 pub mod remove_2;
+pub mod size_1;
 pub mod take_2;
+pub mod to_list_1;
 pub mod update_3;
+pub mod update_with_3;
+pub mod update_with_4;
 pub mod values_1;
+pub mod with_2;
+pub mod without_2;
 
-use liblumen_alloc::erts::term::prelude::Atom;
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Arity;
 
 fn module() -> Atom {
     Atom::from_str("maps")
@@ -20,3 +41,31 @@ fn module() -> Atom {
 fn module_id() -> usize {
     module().id()
 }
+
+/// The entries of `map` as a list of `{key, value}` tuples in the order of [Map::iter].  The
+/// functions that call back into Erlang for each entry walk this list, so that the entries are
+/// only collected once.
+fn entry_list(process: &Process, map: &Map) -> Term {
+    let entries: Vec<Term> = map
+        .iter()
+        .map(|(key, value)| process.tuple_from_slice(&[key, value]))
+        .collect();
+
+    process.list_from_slice(&entries)
+}
+
+fn term_try_into_function_of_arity(
+    name: &str,
+    value: Term,
+    arity: Arity,
+) -> anyhow::Result<Boxed<Closure>> {
+    let boxed_closure: Boxed<Closure> = value
+        .try_into()
+        .with_context(|| format!("{} ({}) is not a function", name, value))?;
+
+    if boxed_closure.arity() == arity {
+        Ok(boxed_closure)
+    } else {
+        Err(anyhow!("{} ({}) is not arity {}", name, value, arity))
+    }
+}
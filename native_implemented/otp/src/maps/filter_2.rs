@@ -0,0 +1,61 @@
+//! ```elixir
+//! def filter(pred, map) do
+//!   filter_entries(pred, :maps.to_list(map), [])
+//! end
+//!
+//! defp filter_entries(_pred, [], kept), do: :maps.from_list(kept)
+//! defp filter_entries(pred, [{key, value} = entry | entries], kept) do
+//!   case pred.(key, value) do
+//!     true -> filter_entries(pred, entries, [entry | kept])
+//!     false -> filter_entries(pred, entries, kept)
+//!   end
+//! end
+//! ```
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+
+#[native_implemented::function(maps:filter/2)]
+pub fn result(process: &Process, pred: Term, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    super::term_try_into_function_of_arity("pred", pred, 2)?;
+
+    let entries = super::entry_list(process, &boxed_map);
+
+    Ok(filter_entries(process, pred, entries, Term::NIL))
+}
+
+// Private
+
+/// Calls `pred` with the first of the `entries` and continues with the rest of the `entries` in
+/// `label_1` when it returns.  When there are no more `entries`, the `kept` entries are the map.
+fn filter_entries(process: &Process, pred: Term, entries: Term, kept: Term) -> Term {
+    match entries.decode().unwrap() {
+        TypedTerm::Nil => {
+            let kept_entries = Map::from_list(kept).unwrap();
+
+            process.map_from_slice(&kept_entries)
+        }
+        TypedTerm::List(cons) => {
+            let entry: Boxed<Tuple> = cons.head.try_into().unwrap();
+            let arguments = process.list_from_slice(&[entry[0], entry[1]]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[pred, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[pred, cons.head, cons.tail, kept]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("entries ({}) is not a list", entries),
+    }
+}
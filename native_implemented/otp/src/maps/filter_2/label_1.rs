@@ -0,0 +1,51 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (pred, entry, entries, kept)
+//! # returned from call: keep
+//! # full stack: (keep, pred, entry, entries, kept)
+//! # returns: map
+//! case keep do
+//!   true -> filter_entries(pred, entries, [entry | kept])
+//!   false -> filter_entries(pred, entries, kept)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    keep: Term,
+    pred: Term,
+    entry: Term,
+    entries: Term,
+    kept: Term,
+) -> exception::Result<Term> {
+    assert!(pred.is_boxed_function());
+    assert!(entry.is_boxed_tuple());
+    assert!(entries.is_list());
+    assert!(kept.is_list());
+
+    let keep_bool: bool = keep.try_into().with_context(|| {
+        format!(
+            "pred ({}) returned ({}), which is not a boolean",
+            pred, keep
+        )
+    })?;
+
+    let kept = if keep_bool {
+        process.cons(entry, kept)
+    } else {
+        kept
+    };
+
+    Ok(super::filter_entries(process, pred, entries, kept))
+}
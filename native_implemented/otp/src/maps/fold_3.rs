@@ -0,0 +1,55 @@
+//! ```elixir
+//! def fold(fun, init, map) do
+//!   fold_entries(fun, init, :maps.to_list(map))
+//! end
+//!
+//! defp fold_entries(_fun, acc, []), do: acc
+//! defp fold_entries(fun, acc, [{key, value} | entries]) do
+//!   acc = fun.(key, value, acc)
+//!   fold_entries(fun, acc, entries)
+//! end
+//! ```
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+
+#[native_implemented::function(maps:fold/3)]
+pub fn result(process: &Process, fun: Term, init: Term, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    super::term_try_into_function_of_arity("fun", fun, 3)?;
+
+    let entries = super::entry_list(process, &boxed_map);
+
+    Ok(fold(process, fun, init, entries))
+}
+
+// Private
+
+/// Calls `fun` with the first of the `entries` and continues with the rest of the `entries` in
+/// `label_1` when it returns
+fn fold(process: &Process, fun: Term, acc: Term, entries: Term) -> Term {
+    match entries.decode().unwrap() {
+        TypedTerm::Nil => acc,
+        TypedTerm::List(cons) => {
+            let entry: Boxed<Tuple> = cons.head.try_into().unwrap();
+            let arguments = process.list_from_slice(&[entry[0], entry[1], acc]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[fun, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[fun, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("entries ({}) is not a list", entries),
+    }
+}
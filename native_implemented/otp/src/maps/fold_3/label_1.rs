@@ -0,0 +1,21 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, entries)
+//! # returned from call: acc
+//! # full stack: (acc, fun, entries)
+//! # returns: acc
+//! fold_entries(fun, acc, entries)
+//! ```
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, acc: Term, fun: Term, entries: Term) -> Term {
+    assert!(fun.is_boxed_function());
+    assert!(entries.is_list());
+
+    super::fold(process, fun, acc, entries)
+}
@@ -0,0 +1,19 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists;
+
+/// Returns a map with each of the `keys` associated with `value`.
+#[native_implemented::function(maps:from_keys/2)]
+pub fn result(process: &Process, keys: Term, value: Term) -> exception::Result<Term> {
+    let entries: Vec<(Term, Term)> = lists::term_try_into_vec("keys", keys)?
+        .into_iter()
+        .map(|key| (key, value))
+        .collect();
+
+    Ok(process.map_from_slice(&entries))
+}
@@ -0,0 +1,40 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::from_keys_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_improper_list_errors_badarg() {
+    with_process(|process| {
+        let keys = process.improper_list_from_slice(&[atom!("a")], atom!("tail"));
+
+        assert_badarg!(
+            result(process, keys, atom!("value")),
+            format!("keys ({}) is not a proper list", keys)
+        );
+    });
+}
+
+#[test]
+fn with_keys_returns_map_with_value_for_each_key() {
+    with_process(|process| {
+        let value = atom!("value");
+        let keys = process.list_from_slice(&[atom!("a"), atom!("b"), atom!("a")]);
+
+        assert_eq!(
+            result(process, keys, value),
+            Ok(process.map_from_slice(&[(atom!("a"), value), (atom!("b"), value)]))
+        );
+    });
+}
+
+#[test]
+fn with_empty_list_returns_empty_map() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Term::NIL, atom!("value")),
+            Ok(process.map_from_slice(&[]))
+        );
+    });
+}
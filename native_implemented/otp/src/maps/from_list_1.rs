@@ -4,8 +4,8 @@ use liblumen_alloc::erts::term::prelude::*;
 
 #[native_implemented::function(maps:from_list/1)]
 pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
-    let hash_map = Map::from_list(list)?;
-    let map = process.map_from_hash_map(hash_map);
+    let entries = Map::from_list(list)?;
+    let map = process.map_from_slice(&entries);
 
     Ok(map)
 }
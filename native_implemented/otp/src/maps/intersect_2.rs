@@ -0,0 +1,20 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns the entries of `map2` whose keys are also in `map1`.
+#[native_implemented::function(maps:intersect/2)]
+pub fn result(process: &Process, map1: Term, map2: Term) -> exception::Result<Term> {
+    let boxed_map1 = term_try_into_map_or_badmap!(process, map1)?;
+    let boxed_map2 = term_try_into_map_or_badmap!(process, map2)?;
+
+    let entries: Vec<(Term, Term)> = boxed_map2
+        .iter()
+        .filter(|(key, _)| boxed_map1.is_key(*key))
+        .collect();
+
+    Ok(process.map_from_slice(&entries))
+}
@@ -0,0 +1,64 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::maps::intersect_2::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_map_map1_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map1)| {
+            let map2 = arc_process.map_from_slice(&[]);
+
+            prop_assert_badmap!(result(&arc_process, map1, map2), &arc_process, map1);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_map_map2_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map2)| {
+            let map1 = arc_process.map_from_slice(&[]);
+
+            prop_assert_badmap!(result(&arc_process, map1, map2), &arc_process, map2);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_maps_returns_map2_entries_with_keys_in_map1() {
+    with_process(|process| {
+        let map1 = process.map_from_slice(&[
+            (atom!("a"), process.integer(1)),
+            (atom!("b"), process.integer(2)),
+        ]);
+        let map2 = process.map_from_slice(&[
+            (atom!("b"), process.integer(3)),
+            (atom!("c"), process.integer(4)),
+        ]);
+
+        assert_eq!(
+            result(process, map1, map2),
+            Ok(process.map_from_slice(&[(atom!("b"), process.integer(3))]))
+        );
+    });
+}
@@ -0,0 +1,17 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns an iterator over the entries of `map` for `maps:next/1`.
+///
+/// The iterator is `[position | map]`, where `position` is `0` for the first entry in the order of
+/// `maps:keys/1`.  See `Map::next_entry` for how later positions are encoded.
+#[native_implemented::function(maps:iterator/1)]
+pub fn result(process: &Process, map: Term) -> exception::Result<Term> {
+    term_try_into_map_or_badmap!(process, map)?;
+
+    Ok(process.cons(process.integer(0), map))
+}
@@ -31,3 +31,26 @@ fn returns_list_of_keys() {
             .unwrap();
     });
 }
+
+#[test]
+fn with_at_most_32_keys_returns_keys_in_map_key_order() {
+    with_process_arc(|arc_process| {
+        let float = arc_process.float(1.0);
+        let map = arc_process.map_from_slice(&[
+            (atom!("b"), atom!("value")),
+            (float, atom!("value")),
+            (arc_process.integer(1), atom!("value")),
+            (atom!("a"), atom!("value")),
+        ]);
+
+        assert_eq!(
+            result(&arc_process, map),
+            Ok(arc_process.list_from_slice(&[
+                arc_process.integer(1),
+                float,
+                atom!("a"),
+                atom!("b")
+            ]))
+        );
+    });
+}
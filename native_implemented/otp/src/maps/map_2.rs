@@ -0,0 +1,60 @@
+//! ```elixir
+//! def map(fun, map) do
+//!   map_entries(fun, :maps.to_list(map), [])
+//! end
+//!
+//! defp map_entries(_fun, [], mapped), do: :maps.from_list(mapped)
+//! defp map_entries(fun, [{key, value} | entries], mapped) do
+//!   value = fun.(key, value)
+//!   map_entries(fun, entries, [{key, value} | mapped])
+//! end
+//! ```
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+
+#[native_implemented::function(maps:map/2)]
+pub fn result(process: &Process, fun: Term, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    super::term_try_into_function_of_arity("fun", fun, 2)?;
+
+    let entries = super::entry_list(process, &boxed_map);
+
+    Ok(map_entries(process, fun, entries, Term::NIL))
+}
+
+// Private
+
+/// Calls `fun` with the first of the `entries` and continues with the rest of the `entries` in
+/// `label_1` when it returns.  When there are no more `entries`, the `mapped` entries are the map.
+fn map_entries(process: &Process, fun: Term, entries: Term, mapped: Term) -> Term {
+    match entries.decode().unwrap() {
+        TypedTerm::Nil => {
+            let mapped_entries = Map::from_list(mapped).unwrap();
+
+            process.map_from_slice(&mapped_entries)
+        }
+        TypedTerm::List(cons) => {
+            let entry: Boxed<Tuple> = cons.head.try_into().unwrap();
+            let key = entry[0];
+            let arguments = process.list_from_slice(&[key, entry[1]]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[fun, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[fun, key, cons.tail, mapped]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("entries ({}) is not a list", entries),
+    }
+}
@@ -0,0 +1,31 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, key, entries, mapped)
+//! # returned from call: value
+//! # full stack: (value, fun, key, entries, mapped)
+//! # returns: map
+//! map_entries(fun, entries, [{key, value} | mapped])
+//! ```
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    value: Term,
+    fun: Term,
+    key: Term,
+    entries: Term,
+    mapped: Term,
+) -> Term {
+    assert!(fun.is_boxed_function());
+    assert!(entries.is_list());
+    assert!(mapped.is_list());
+
+    let mapped = process.cons(process.tuple_from_slice(&[key, value]), mapped);
+
+    super::map_entries(process, fun, entries, mapped)
+}
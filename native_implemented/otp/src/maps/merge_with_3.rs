@@ -0,0 +1,79 @@
+//! ```elixir
+//! def merge_with(combiner, map1, map2) do
+//!   merged = :maps.merge(map1, map2)
+//!
+//!   common =
+//!     for {key, value1} <- :maps.to_list(map1), :maps.is_key(key, map2) do
+//!       {key, value1, :maps.get(key, map2)}
+//!     end
+//!
+//!   combine(combiner, common, merged)
+//! end
+//!
+//! defp combine(_combiner, [], merged), do: merged
+//! defp combine(combiner, [{key, value1, value2} | common], merged) do
+//!   value = combiner.(key, value1, value2)
+//!   combine(combiner, common, :maps.put(key, value, merged))
+//! end
+//! ```
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+use crate::maps::merge_2;
+
+#[native_implemented::function(maps:merge_with/3)]
+pub fn result(
+    process: &Process,
+    combiner: Term,
+    map1: Term,
+    map2: Term,
+) -> exception::Result<Term> {
+    let boxed_map1 = term_try_into_map_or_badmap!(process, map1)?;
+    let boxed_map2 = term_try_into_map_or_badmap!(process, map2)?;
+    super::term_try_into_function_of_arity("combiner", combiner, 3)?;
+
+    let merged = merge_2::result(process, map1, map2)?;
+    let common_vec: Vec<Term> = boxed_map1
+        .iter()
+        .filter_map(|(key, value1)| {
+            boxed_map2
+                .get(key)
+                .map(|value2| process.tuple_from_slice(&[key, value1, value2]))
+        })
+        .collect();
+    let common = process.list_from_slice(&common_vec);
+
+    Ok(combine(process, combiner, common, merged))
+}
+
+// Private
+
+/// Calls `combiner` with the first of the `common` entries and continues with the rest of the
+/// `common` entries in `label_1` when it returns.
+fn combine(process: &Process, combiner: Term, common: Term, merged: Term) -> Term {
+    match common.decode().unwrap() {
+        TypedTerm::Nil => merged,
+        TypedTerm::List(cons) => {
+            let entry: Boxed<Tuple> = cons.head.try_into().unwrap();
+            let key = entry[0];
+            let arguments = process.list_from_slice(&[key, entry[1], entry[2]]);
+
+            process.queue_frame_with_arguments(
+                apply_2::frame().with_arguments(false, &[combiner, arguments]),
+            );
+            process.queue_frame_with_arguments(
+                label_1::frame().with_arguments(true, &[combiner, key, cons.tail, merged]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("common ({}) is not a list", common),
+    }
+}
@@ -0,0 +1,33 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (combiner, key, common, merged)
+//! # returned from call: value
+//! # full stack: (value, combiner, key, common, merged)
+//! # returns: merged
+//! combine(combiner, common, :maps.put(key, value, merged))
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    value: Term,
+    combiner: Term,
+    key: Term,
+    common: Term,
+    merged: Term,
+) -> Term {
+    assert!(combiner.is_boxed_function());
+    assert!(common.is_list());
+
+    let boxed_merged: Boxed<Map> = merged.try_into().unwrap();
+    let merged = process.map_put(boxed_merged, key, value);
+
+    super::combine(process, combiner, common, merged)
+}
@@ -0,0 +1,9 @@
+use crate::maps::new_0::result;
+use crate::test::with_process;
+
+#[test]
+fn returns_empty_map() {
+    with_process(|process| {
+        assert_eq!(result(process), process.map_from_slice(&[]));
+    });
+}
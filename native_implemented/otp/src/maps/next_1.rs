@@ -0,0 +1,70 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Returns `{key, value, next_iterator}` for the next entry of `iterator` from `maps:iterator/1`,
+/// or `none` when there are no more entries.
+#[native_implemented::function(maps:next/1)]
+pub fn result(process: &Process, iterator: Term) -> exception::Result<Term> {
+    match iterator.decode().unwrap() {
+        TypedTerm::Atom(atom) if atom.name() == "none" => Ok(iterator),
+        TypedTerm::Tuple(tuple) if tuple.len() == 3 => Ok(iterator),
+        TypedTerm::List(cons) => {
+            let position =
+                term_try_into_position(cons.head).with_context(|| iterator_context(iterator))?;
+            let boxed_map: Boxed<Map> = cons
+                .tail
+                .try_into()
+                .with_context(|| iterator_context(iterator))?;
+
+            match boxed_map.next_entry(position) {
+                Some((key, value, next_position)) => {
+                    let next_iterator = process.cons(process.integer(next_position), cons.tail);
+
+                    Ok(process.tuple_from_slice(&[key, value, next_iterator]))
+                }
+                None => Ok(atom!("none")),
+            }
+        }
+        _ => Err(anyhow!(iterator_context(iterator)).into()),
+    }
+}
+
+// Private
+
+/// The position of `Map::next_entry`, which can be a big integer for hash maps.
+fn term_try_into_position(term: Term) -> anyhow::Result<u128> {
+    let option_position = match term.decode().unwrap() {
+        TypedTerm::SmallInteger(small_integer) => {
+            let position_isize: isize = small_integer.into();
+
+            if 0 <= position_isize {
+                Some(position_isize as u128)
+            } else {
+                None
+            }
+        }
+        TypedTerm::BigInteger(big_integer) => {
+            let big_int: &BigInt = big_integer.as_ref().into();
+
+            big_int.to_u128()
+        }
+        _ => None,
+    };
+
+    option_position.ok_or_else(|| anyhow!("position ({}) is not a map iterator position", term))
+}
+
+fn iterator_context(iterator: Term) -> String {
+    format!("iterator ({}) is not a map iterator", iterator)
+}
@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::iterator_1;
+use crate::maps::next_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_none_returns_none() {
+    with_process(|process| {
+        assert_eq!(result(process, atom!("none")), Ok(atom!("none")));
+    });
+}
+
+#[test]
+fn without_iterator_errors_badarg() {
+    with_process(|process| {
+        let iterator = atom!("iterator");
+
+        assert_badarg!(
+            result(process, iterator),
+            format!("iterator ({}) is not a map iterator", iterator)
+        );
+    });
+}
+
+#[test]
+fn with_iterator_returns_entries_in_key_order_then_none() {
+    with_process(|process| {
+        let map = process.map_from_slice(&[
+            (atom!("b"), process.integer(2)),
+            (atom!("a"), process.integer(1)),
+        ]);
+        let iterator = iterator_1::result(process, map).unwrap();
+
+        let first_iterator = process.cons(process.integer(1), map);
+        assert_eq!(
+            result(process, iterator),
+            Ok(process.tuple_from_slice(&[atom!("a"), process.integer(1), first_iterator]))
+        );
+
+        let second_iterator = process.cons(process.integer(2), map);
+        assert_eq!(
+            result(process, first_iterator),
+            Ok(process.tuple_from_slice(&[atom!("b"), process.integer(2), second_iterator]))
+        );
+
+        assert_eq!(result(process, second_iterator), Ok(atom!("none")));
+    });
+}
+
+#[test]
+fn with_hash_map_iterator_returns_every_entry_once_then_none() {
+    with_process(|process| {
+        let entries: Vec<(Term, Term)> = (0..100)
+            .map(|i| (process.integer(i), process.integer(-i)))
+            .collect();
+        let map = process.map_from_slice(&entries);
+        let boxed_map: Boxed<Map> = map.try_into().unwrap();
+        let mut visited = Vec::new();
+        let mut iterator = iterator_1::result(process, map).unwrap();
+
+        loop {
+            let next = result(process, iterator).unwrap();
+
+            if next == atom!("none") {
+                break;
+            }
+
+            let next_tuple: Boxed<Tuple> = next.try_into().unwrap();
+            visited.push((next_tuple[0], next_tuple[1]));
+            iterator = next_tuple[2];
+        }
+
+        assert_eq!(visited, boxed_map.iter().collect::<Vec<(Term, Term)>>());
+    });
+}
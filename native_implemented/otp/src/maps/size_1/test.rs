@@ -0,0 +1,36 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::maps::size_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_map_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map)| {
+            prop_assert_badmap!(result(&arc_process, map), &arc_process, map);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_returns_number_of_entries() {
+    with_process(|process| {
+        let map = process.map_from_slice(&[
+            (atom!("a"), process.integer(1)),
+            (atom!("b"), process.integer(2)),
+        ]);
+
+        assert_eq!(result(process, map), Ok(process.integer(2)));
+    });
+}
@@ -0,0 +1,50 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::to_list_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_map_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map)| {
+            prop_assert_badmap!(result(&arc_process, map), &arc_process, map);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_map_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(result(process, process.map_from_slice(&[])), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_map_returns_entries_in_key_order() {
+    with_process(|process| {
+        let map = process.map_from_slice(&[
+            (atom!("b"), process.integer(2)),
+            (atom!("a"), process.integer(1)),
+        ]);
+
+        assert_eq!(
+            result(process, map),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[atom!("a"), process.integer(1)]),
+                process.tuple_from_slice(&[atom!("b"), process.integer(2)]),
+            ]))
+        );
+    });
+}
@@ -12,8 +12,8 @@ use liblumen_alloc::erts::term::prelude::*;
 pub fn result(process: &Process, key: Term, value: Term, map: Term) -> exception::Result<Term> {
     let boxed_map = term_try_into_map_or_badmap!(process, map)?;
 
-    match boxed_map.update(key, value) {
-        Some(hash_map) => Ok(process.map_from_hash_map(hash_map)),
+    match process.map_update(boxed_map, key, value) {
+        Some(updated_map) => Ok(updated_map),
         None => Err(badkey(
             process,
             key,
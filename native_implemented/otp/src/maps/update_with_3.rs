@@ -0,0 +1,52 @@
+//! ```elixir
+//! def update_with(key, fun, map) do
+//!   case :maps.find(key, map) do
+//!     {:ok, value} -> :maps.put(key, fun.(value), map)
+//!     :error -> :erlang.error({:badkey, key})
+//!   end
+//! end
+//! ```
+
+mod label_1;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, *};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply_2;
+
+#[native_implemented::function(maps:update_with/3)]
+pub fn result(process: &Process, key: Term, fun: Term, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    super::term_try_into_function_of_arity("fun", fun, 1)?;
+
+    match boxed_map.get(key) {
+        Some(value) => Ok(update_with(process, key, fun, map, value)),
+        None => Err(badkey(
+            process,
+            key,
+            Trace::capture(),
+            anyhow!("key ({}) does not exist in map ({})", key, map).into(),
+        )),
+    }
+}
+
+/// Calls `fun` with the `value` of `key` in `map` and puts the new value in `map` in `label_1`
+/// when it returns.
+pub(in crate::maps) fn update_with(
+    process: &Process,
+    key: Term,
+    fun: Term,
+    map: Term,
+    value: Term,
+) -> Term {
+    let arguments = process.list_from_slice(&[value]);
+
+    process.queue_frame_with_arguments(apply_2::frame().with_arguments(false, &[fun, arguments]));
+    process.queue_frame_with_arguments(label_1::frame().with_arguments(true, &[key, map]));
+
+    Term::NONE
+}
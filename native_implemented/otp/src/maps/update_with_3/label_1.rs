@@ -0,0 +1,22 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (key, map)
+//! # returned from call: value
+//! # full stack: (value, key, map)
+//! # returns: map
+//! :maps.put(key, value, map)
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, value: Term, key: Term, map: Term) -> Term {
+    let boxed_map: Boxed<Map> = map.try_into().unwrap();
+
+    process.map_put(boxed_map, key, value)
+}
@@ -0,0 +1,31 @@
+//! ```elixir
+//! def update_with(key, fun, init, map) do
+//!   case :maps.find(key, map) do
+//!     {:ok, value} -> :maps.put(key, fun.(value), map)
+//!     :error -> :maps.put(key, init, map)
+//!   end
+//! end
+//! ```
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::update_with_3::update_with;
+
+#[native_implemented::function(maps:update_with/4)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    fun: Term,
+    init: Term,
+    map: Term,
+) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    super::term_try_into_function_of_arity("fun", fun, 1)?;
+
+    match boxed_map.get(key) {
+        Some(value) => Ok(update_with(process, key, fun, map, value)),
+        None => Ok(process.map_put(boxed_map, key, init)),
+    }
+}
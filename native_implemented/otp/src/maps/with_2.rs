@@ -0,0 +1,23 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists;
+
+/// Returns the entries of `map1` whose keys are in `keys`.  Keys that are not in `map1` are
+/// ignored.
+#[native_implemented::function(maps:with/2)]
+pub fn result(process: &Process, keys: Term, map1: Term) -> exception::Result<Term> {
+    let boxed_map1 = term_try_into_map_or_badmap!(process, map1)?;
+    let key_vec = lists::term_try_into_vec("keys", keys)?;
+
+    let entries: Vec<(Term, Term)> = key_vec
+        .into_iter()
+        .filter_map(|key| boxed_map1.get(key).map(|value| (key, value)))
+        .collect();
+
+    Ok(process.map_from_slice(&entries))
+}
@@ -0,0 +1,54 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::with_2::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_keys_errors_badarg() {
+    with_process(|process| {
+        let keys = atom!("keys");
+
+        assert_badarg!(
+            result(process, keys, process.map_from_slice(&[])),
+            format!("keys ({}) is not a proper list", keys)
+        );
+    });
+}
+
+#[test]
+fn without_map_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map1)| {
+            prop_assert_badmap!(result(&arc_process, Term::NIL, map1), &arc_process, map1);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_keys_returns_only_entries_with_keys() {
+    with_process(|process| {
+        let map1 = process.map_from_slice(&[
+            (atom!("a"), process.integer(1)),
+            (atom!("b"), process.integer(2)),
+            (process.integer(1), atom!("integer")),
+        ]);
+        let keys = process.list_from_slice(&[atom!("b"), atom!("missing"), process.float(1.0)]);
+
+        assert_eq!(
+            result(process, keys, map1),
+            Ok(process.map_from_slice(&[(atom!("b"), process.integer(2))]))
+        );
+    });
+}
@@ -0,0 +1,32 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists;
+
+/// Returns the entries of `map1` whose keys are not in `keys`.
+#[native_implemented::function(maps:without/2)]
+pub fn result(process: &Process, keys: Term, map1: Term) -> exception::Result<Term> {
+    let boxed_map1 = term_try_into_map_or_badmap!(process, map1)?;
+    let typed_key_vec: Vec<TypedTerm> = lists::term_try_into_vec("keys", keys)?
+        .into_iter()
+        .map(|key| key.decode().unwrap())
+        .collect();
+
+    // Like map keys, `keys` only match when they are exactly equal
+    let entries: Vec<(Term, Term)> = boxed_map1
+        .iter()
+        .filter(|(key, _)| {
+            let typed_key = key.decode().unwrap();
+
+            !typed_key_vec
+                .iter()
+                .any(|without_typed_key| without_typed_key.exact_eq(&typed_key))
+        })
+        .collect();
+
+    Ok(process.map_from_slice(&entries))
+}
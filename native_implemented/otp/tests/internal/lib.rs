@@ -1,5 +1,7 @@
 #[path = "lib/erlang.rs"]
 pub mod erlang;
+#[path = "lib/lists.rs"]
+pub mod lists;
 #[path = "lib/maps.rs"]
 pub mod maps;
 
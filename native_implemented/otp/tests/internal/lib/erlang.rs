@@ -52,6 +52,8 @@ pub mod ceil_1;
 pub mod concatenate_2;
 #[path = "erlang/convert_time_unit_3.rs"]
 pub mod convert_time_unit_3;
+#[path = "erlang/crc32_1.rs"]
+pub mod crc32_1;
 #[path = "erlang/date_0.rs"]
 pub mod date_0;
 #[path = "erlang/delete_element_2.rs"]
@@ -78,6 +80,8 @@ pub mod error_1;
 pub mod error_2;
 #[path = "erlang/exit_1.rs"]
 pub mod exit_1;
+#[path = "erlang/exit_2.rs"]
+pub mod exit_2;
 #[path = "erlang/float_1.rs"]
 pub mod float_1;
 #[path = "erlang/float_to_binary_1.rs"]
@@ -92,6 +96,10 @@ pub mod float_to_list_2;
 pub mod floor_1;
 #[path = "erlang/function_exported_3.rs"]
 pub mod function_exported_3;
+#[path = "erlang/garbage_collect_0.rs"]
+pub mod garbage_collect_0;
+#[path = "erlang/garbage_collect_2.rs"]
+pub mod garbage_collect_2;
 #[path = "erlang/get_0.rs"]
 pub mod get_0;
 #[path = "erlang/get_1.rs"]
@@ -100,6 +108,8 @@ pub mod get_1;
 pub mod get_keys_0;
 #[path = "erlang/get_keys_1.rs"]
 pub mod get_keys_1;
+#[path = "erlang/halt_1.rs"]
+pub mod halt_1;
 #[path = "erlang/hd_1.rs"]
 pub mod hd_1;
 #[path = "erlang/insert_element_3.rs"]
@@ -134,12 +144,22 @@ pub mod is_process_alive_1;
 pub mod link_1;
 #[path = "erlang/load_nif_2.rs"]
 pub mod load_nif_2;
+#[path = "erlang/md5_1.rs"]
+pub mod md5_1;
+#[path = "erlang/memory_1.rs"]
+pub mod memory_1;
 #[path = "erlang/module_loaded_1.rs"]
 pub mod module_loaded_1;
+#[path = "erlang/monitor_2.rs"]
+pub mod monitor_2;
 #[path = "erlang/nif_error_1.rs"]
 pub mod nif_error_1;
 #[path = "erlang/or_2.rs"]
 pub mod or_2;
+#[path = "erlang/phash2_1.rs"]
+pub mod phash2_1;
+#[path = "erlang/phash2_2.rs"]
+pub mod phash2_2;
 #[path = "erlang/process_flag_2.rs"]
 pub mod process_flag_2;
 #[path = "erlang/seq_trace_2.rs"]
@@ -164,7 +184,17 @@ pub mod spawn_monitor_3;
 pub mod spawn_opt_2;
 #[path = "erlang/spawn_opt_4.rs"]
 pub mod spawn_opt_4;
+#[path = "erlang/statistics_1.rs"]
+pub mod statistics_1;
+#[path = "erlang/suspend_process_1.rs"]
+pub mod suspend_process_1;
 #[path = "erlang/system_flag_2.rs"]
 pub mod system_flag_2;
 #[path = "erlang/tl_1.rs"]
 pub mod tl_1;
+#[path = "erlang/trace_3.rs"]
+pub mod trace_3;
+#[path = "erlang/trace_pattern_3.rs"]
+pub mod trace_pattern_3;
+#[path = "erlang/yield_0.rs"]
+pub mod yield_0;
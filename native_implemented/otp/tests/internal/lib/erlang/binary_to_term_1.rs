@@ -12,6 +12,7 @@ test_stdout!(
     "-2147483648\n"
 );
 test_stdout!(with_binary_encoding_new_float_returns_float, "1.0\n");
+test_stdout!(with_binary_encoding_float_returns_float, "1.5\n");
 test_stdout!(
     with_binary_encoding_small_tuple_returns_tuple,
     "{zero, 1}\n"
@@ -27,3 +28,4 @@ test_stdout!(
     "<<1,2:3>>\n"
 );
 test_stdout!(with_binary_encoding_small_atom_utf8_returns_atom, "'😈'\n");
+test_stdout!(with_binary_encoding_compressed_returns_term, "true\n");
@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [binary_to_term/1, display/1]).
+
+start() ->
+  %% term_to_binary(lists:duplicate(20, 0), [compressed])
+  Binary = <<131, 80, 0, 0, 0, 23, 120, 156, 203, 102, 16, 97, 192, 2, 0, 11, 88, 0, 128>>,
+  Zeros = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+  display(binary_to_term(Binary) == Zeros).
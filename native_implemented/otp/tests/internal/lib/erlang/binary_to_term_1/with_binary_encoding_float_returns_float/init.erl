@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [binary_to_term/1, display/1]).
+
+start() ->
+  %% term_to_binary(1.5, [{minor_version, 0}])
+  display(binary_to_term(<<131, 99, "1.50000000000000000000e+00", 0, 0, 0, 0, 0>>)).
@@ -0,0 +1 @@
+test_stdout!(with_binary_returns_checksum, "1095738169\ntrue\n");
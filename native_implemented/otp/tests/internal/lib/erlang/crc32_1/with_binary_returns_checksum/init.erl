@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [crc32/1, display/1]).
+
+start() ->
+  Binary = <<"The quick brown fox jumps over the lazy dog">>,
+  display(crc32(Binary)),
+  display(crc32(binary_to_list(Binary)) == crc32(Binary)).
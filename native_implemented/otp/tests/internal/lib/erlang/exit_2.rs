@@ -0,0 +1,12 @@
+test_stdout!(
+    with_self_trapping_exits_sends_exit_message,
+    "{'EXIT', true, reason}\n"
+);
+test_stdout!(
+    with_linked_process_not_trapping_exits_exits_it,
+    "true\n{'EXIT', reason}\n"
+);
+test_stdout!(
+    with_process_trapping_exits_and_kill_exits_it_with_killed,
+    "true\n{'DOWN', killed}\n"
+);
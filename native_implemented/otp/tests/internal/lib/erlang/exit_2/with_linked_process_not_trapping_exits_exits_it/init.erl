@@ -0,0 +1,18 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, exit/2, process_flag/2, spawn_link/1]).
+
+start() ->
+  process_flag(trap_exit, true),
+  Child = spawn_link(fun () ->
+    receive
+      _ -> ok
+    end
+  end),
+  display(exit(Child, reason)),
+  receive
+    {'EXIT', Child, Reason} ->
+      display({'EXIT', Reason})
+  after 10 ->
+    display(timeout)
+  end.
@@ -0,0 +1,18 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, exit/2, process_flag/2, spawn_monitor/1]).
+
+start() ->
+  {Child, MonitorReference} = spawn_monitor(fun () ->
+    process_flag(trap_exit, true),
+    receive
+      _ -> ok
+    end
+  end),
+  display(exit(Child, kill)),
+  receive
+    {'DOWN', MonitorReference, process, Child, Info} ->
+      display({'DOWN', Info})
+  after 10 ->
+    display(timeout)
+  end.
@@ -0,0 +1,14 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, exit/2, process_flag/2]).
+
+start() ->
+  process_flag(trap_exit, true),
+  Self = self(),
+  true = exit(Self, reason),
+  receive
+    {'EXIT', From, Reason} ->
+      display({'EXIT', From == Self, Reason})
+  after 5 ->
+    display(timeout)
+  end.
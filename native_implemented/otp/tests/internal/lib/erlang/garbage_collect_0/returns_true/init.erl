@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, garbage_collect/0]).
+
+start() ->
+  display(garbage_collect()).
@@ -0,0 +1,5 @@
+test_stdout!(
+    with_async_sends_garbage_collect_message,
+    "async\n{garbage_collect, request_id, true}\n"
+);
+test_stdout!(with_invalid_type_errors_badarg, "{caught, error, badarg}\n");
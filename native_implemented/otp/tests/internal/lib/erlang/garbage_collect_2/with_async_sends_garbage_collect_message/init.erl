@@ -0,0 +1,12 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, garbage_collect/2]).
+
+start() ->
+  display(garbage_collect(self(), [{async, request_id}, {type, minor}])),
+  receive
+    {garbage_collect, RequestId, Result} ->
+      display({garbage_collect, RequestId, Result})
+  after 5 ->
+    display(timeout)
+  end.
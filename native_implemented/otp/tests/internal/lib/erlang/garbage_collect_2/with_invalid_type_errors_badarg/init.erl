@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [garbage_collect/2]).
+
+start() ->
+  test:caught(fun () ->
+    garbage_collect(self(), [{type, full}])
+  end).
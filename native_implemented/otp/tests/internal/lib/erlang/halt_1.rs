@@ -0,0 +1,13 @@
+test_stdout!(with_zero_flushes_and_exits, "before\n");
+test_stdout!(
+    with_non_negative_big_integer_flushes_and_exits,
+    "before\n"
+);
+test_stderr_substrings!(
+    with_string_prints_slogan,
+    vec!["Runtime terminating: slogan"]
+);
+test_stdout!(
+    with_negative_integer_errors_badarg,
+    "{caught, error, badarg}\n"
+);
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [halt/1]).
+
+start() ->
+  test:caught(fun () ->
+    halt(-1)
+  end).
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, halt/1]).
+
+start() ->
+  display(before),
+  halt(18446744073709551616),
+  display(after).
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [halt/1]).
+
+start() ->
+  halt("slogan").
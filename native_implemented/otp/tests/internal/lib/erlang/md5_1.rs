@@ -0,0 +1 @@
+test_stdout!(with_binary_returns_digest, "16\ntrue\n");
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, md5/1]).
+
+start() ->
+  Digest = md5(<<"abc">>),
+  display(byte_size(Digest)),
+  display(Digest == <<144, 1, 80, 152, 60, 210, 79, 176, 214, 150, 63, 125, 40, 225, 127, 114>>).
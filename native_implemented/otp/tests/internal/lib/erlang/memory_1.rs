@@ -0,0 +1,5 @@
+test_stdout!(with_total_is_sum_of_processes_and_system, "true\n");
+test_stdout!(
+    with_unsupported_type_errors_badarg,
+    "{caught, error, badarg}\n"
+);
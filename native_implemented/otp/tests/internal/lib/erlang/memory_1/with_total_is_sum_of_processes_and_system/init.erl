@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, memory/1]).
+
+start() ->
+  [{total, Total}, {processes, Processes}, {system, System}] =
+    memory([total, processes, system]),
+  display(Total == Processes + System).
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [memory/1]).
+
+start() ->
+  test:caught(fun () ->
+    memory(unsupported)
+  end).
@@ -0,0 +1,12 @@
+test_stdout!(
+    with_time_offset_type_returns_reference_that_can_be_demonitored,
+    "true\ntrue\nfalse\n"
+);
+test_stdout!(
+    with_time_offset_type_without_clock_service_errors_badarg,
+    "{caught, error, badarg}\n"
+);
+test_stdout!(
+    with_port_type_without_registered_name_sends_noproc,
+    "true\nnoproc\n"
+);
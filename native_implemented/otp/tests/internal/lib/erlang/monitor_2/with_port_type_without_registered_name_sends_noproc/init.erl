@@ -0,0 +1,13 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, monitor/2]).
+
+start() ->
+  MonitorReference = monitor(port, unregistered_port),
+  display(is_reference(MonitorReference)),
+  receive
+    {'DOWN', MonitorReference, port, {unregistered_port, _Node}, Info} ->
+      display(Info)
+  after 5 ->
+    display(timeout)
+  end.
@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [demonitor/2, display/1, monitor/2]).
+
+start() ->
+  MonitorReference = monitor(time_offset, clock_service),
+  display(is_reference(MonitorReference)),
+  display(demonitor(MonitorReference, [info])),
+  display(demonitor(MonitorReference, [info])).
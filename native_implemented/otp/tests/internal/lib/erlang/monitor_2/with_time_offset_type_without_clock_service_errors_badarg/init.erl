@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [monitor/2]).
+
+start() ->
+  test:caught(fun () ->
+    monitor(time_offset, clock)
+  end).
@@ -0,0 +1 @@
+test_stdout!(with_atom_returns_atom_hash, "97\n113427502\n");
@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, phash2/1]).
+
+start() ->
+  display(phash2(a)),
+  display(phash2([])).
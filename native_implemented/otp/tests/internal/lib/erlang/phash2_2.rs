@@ -0,0 +1,4 @@
+test_stdout!(
+    with_invalid_range_errors_badarg,
+    "{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n"
+);
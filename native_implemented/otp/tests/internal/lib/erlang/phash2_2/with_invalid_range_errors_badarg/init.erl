@@ -0,0 +1,13 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [phash2/2]).
+
+start() ->
+  test(0),
+  test(4294967297),
+  test(range).
+
+test(Range) ->
+  test:caught(fun () ->
+    phash2(term, Range)
+  end).
@@ -0,0 +1,4 @@
+test_stdout!(
+    with_priority_value_is_requeued_with_new_priority,
+    "normal\n{priority, high}\nhigh\n{priority, low}\n"
+);
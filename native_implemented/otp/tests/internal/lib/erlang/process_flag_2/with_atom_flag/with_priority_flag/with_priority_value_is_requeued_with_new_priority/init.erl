@@ -0,0 +1,16 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(process_flag(priority, high)),
+  display(process_info(self(), priority)),
+  display(process_flag(priority, low)),
+  Parent = self(),
+  %% waiting for the child requeues the process in the low priority run queue
+  spawn(fun () ->
+    Parent ! {priority, process_info(Parent, priority)}
+  end),
+  receive
+    {priority, Priority} -> display(Priority)
+  end.
@@ -0,0 +1,4 @@
+test_stdout!(
+    with_true_value_hides_messages_and_dictionary,
+    "false\n{messages, []}\n{dictionary, []}\ntrue\n"
+);
@@ -0,0 +1,11 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  put(key, value),
+  self() ! message,
+  display(process_flag(sensitive, true)),
+  display(process_info(self(), messages)),
+  display(process_info(self(), dictionary)),
+  display(process_flag(sensitive, false)).
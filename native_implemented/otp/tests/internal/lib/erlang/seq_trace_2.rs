@@ -1,7 +1,10 @@
-// TODO test flags
-
 test_stdout!(without_atom_flag_errors_badarg, "{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n{caught, error, badarg}\n");
 test_stdout!(
     with_atom_flag_without_support_errors_badarg,
     "{caught, error, badarg}\n"
 );
+test_stdout!(with_label_flag_returns_old_label, "0\n17\n{label, 42}\n");
+test_stdout!(
+    with_system_tracer_traces_send_and_receive,
+    "{17, send, {0, 1}, true, true, hello}\n{17, receive, {1, 1}, true, true, hello}\n"
+);
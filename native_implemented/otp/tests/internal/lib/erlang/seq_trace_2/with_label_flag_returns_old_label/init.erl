@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, seq_trace/2, seq_trace_info/1]).
+
+start() ->
+  %% a new token starts with label 0
+  display(seq_trace(label, 17)),
+  display(seq_trace(label, 42)),
+  display(seq_trace_info(label)).
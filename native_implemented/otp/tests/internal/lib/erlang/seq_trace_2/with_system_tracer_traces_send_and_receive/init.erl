@@ -0,0 +1,34 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, seq_trace/2, system_flag/2]).
+
+start() ->
+  Parent = self(),
+  Tracer = spawn(fun () -> tracer(Parent, 2) end),
+  system_flag(sequential_tracer, Tracer),
+  Child = spawn(fun () ->
+    receive
+      hello -> ok
+    end
+  end),
+  seq_trace(label, 17),
+  seq_trace(send, true),
+  seq_trace('receive', true),
+  Child ! hello,
+  seq_trace(sequential_trace_token, []),
+  display_traced(Parent, Child),
+  display_traced(Parent, Child).
+
+tracer(_Parent, 0) ->
+  ok;
+tracer(Parent, N) ->
+  receive
+    Trace -> Parent ! {traced, Trace}
+  end,
+  tracer(Parent, N - 1).
+
+display_traced(Parent, Child) ->
+  receive
+    {traced, {seq_trace, Label, {Event, Serial, From, To, Message}}} ->
+      display({Label, Event, Serial, From == Parent, To == Child, Message})
+  end.
@@ -1,3 +1,4 @@
 #[path = "seq_trace_info_1/without_seq_trace_token.rs"]
 mod without_seq_trace_token;
-// TODO with_seq_trace_token
+#[path = "seq_trace_info_1/with_seq_trace_token.rs"]
+mod with_seq_trace_token;
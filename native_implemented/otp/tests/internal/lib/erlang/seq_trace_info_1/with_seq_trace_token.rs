@@ -0,0 +1,4 @@
+test_stdout!(
+    with_label_and_send_returns_values,
+    "{label, 17}\n{send, true}\n{receive, false}\n{serial, {0, 0}}\n"
+);
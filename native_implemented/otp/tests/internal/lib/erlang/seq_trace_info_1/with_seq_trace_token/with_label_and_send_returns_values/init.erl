@@ -0,0 +1,11 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, seq_trace/2, seq_trace_info/1]).
+
+start() ->
+  seq_trace(label, 17),
+  seq_trace(send, true),
+  display(seq_trace_info(label)),
+  display(seq_trace_info(send)),
+  display(seq_trace_info('receive')),
+  display(seq_trace_info(serial)).
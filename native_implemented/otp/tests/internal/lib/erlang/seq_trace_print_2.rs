@@ -1,4 +1,4 @@
 #[path = "seq_trace_print_2/without_seq_trace.rs"]
 mod without_seq_trace;
-// TODO
-// mod with_seq_trace;
+#[path = "seq_trace_print_2/with_seq_trace.rs"]
+mod with_seq_trace;
@@ -0,0 +1 @@
+test_stdout!(with_label_returns_true, "true\nfalse\n");
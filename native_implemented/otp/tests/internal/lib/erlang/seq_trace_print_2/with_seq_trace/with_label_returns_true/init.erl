@@ -0,0 +1,10 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, seq_trace/2, seq_trace_print/2]).
+
+start() ->
+  Label = label,
+  Message = message,
+  seq_trace(label, Label),
+  display(seq_trace_print(Label, Message)),
+  display(seq_trace_print(other_label, Message)).
@@ -1,9 +1,13 @@
 #[path = "with_function/with_empty_list_options.rs"]
 mod with_empty_list_options;
+#[path = "with_function/with_heap_size_options_in_options_list.rs"]
+mod with_heap_size_options_in_options_list;
 #[path = "with_function/with_link_and_monitor_in_options_list.rs"]
 mod with_link_and_monitor_in_options_list;
 #[path = "with_function/with_link_in_options_list.rs"]
 mod with_link_in_options_list;
+#[path = "with_function/with_message_queue_data_in_options_list.rs"]
+mod with_message_queue_data_in_options_list;
 #[path = "with_function/with_monitor_in_options_list.rs"]
 mod with_monitor_in_options_list;
 
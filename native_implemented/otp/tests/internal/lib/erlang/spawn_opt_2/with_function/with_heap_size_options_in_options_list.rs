@@ -0,0 +1,4 @@
+test_stdout!(
+    sets_garbage_collection_process_info,
+    "{fullsweep_after, 10}\n1000000\nfalse\ntrue\n{min_bin_vheap_size, 46422}\n"
+);
@@ -0,0 +1,27 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  Options = [
+    {fullsweep_after, 10},
+    {max_heap_size, #{size => 1000000, kill => false}},
+    {min_bin_vheap_size, 46422}
+  ],
+  ChildPid = spawn_opt(fun () ->
+    wait_to_shutdown()
+  end, Options),
+  {garbage_collection, GarbageCollection} = process_info(ChildPid, garbage_collection),
+  display(lists:keyfind(fullsweep_after, 1, GarbageCollection)),
+  {max_heap_size, MaxHeapSize} = lists:keyfind(max_heap_size, 1, GarbageCollection),
+  display(maps:get(size, MaxHeapSize)),
+  display(maps:get(kill, MaxHeapSize)),
+  display(maps:get(error_logger, MaxHeapSize)),
+  display(lists:keyfind(min_bin_vheap_size, 1, GarbageCollection)),
+  ChildPid ! shutdown,
+  ok.
+
+wait_to_shutdown() ->
+  receive
+    shutdown -> ok
+  end.
@@ -0,0 +1,4 @@
+test_stdout!(
+    with_off_heap_receives_messages_in_order,
+    "{message_queue_data, off_heap}\n{message_queue_data, off_heap}\n[1, 2, 3]\n"
+);
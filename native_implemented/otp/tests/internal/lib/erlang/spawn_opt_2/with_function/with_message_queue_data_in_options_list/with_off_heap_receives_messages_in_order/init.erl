@@ -0,0 +1,38 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  Parent = self(),
+  ChildPid = spawn_opt(fun () ->
+    Parent ! {self(), process_info(self(), message_queue_data)},
+    receive
+      go -> ok
+    end,
+    Parent ! {self(), {message_queue_data, process_flag(message_queue_data, on_heap)}},
+    receive
+      stop -> ok
+    end,
+    Parent ! {self(), receive_all([])}
+  end, [{message_queue_data, off_heap}]),
+  receive
+    {ChildPid, MessageQueueData} -> display(MessageQueueData)
+  end,
+  ChildPid ! 1,
+  ChildPid ! 2,
+  ChildPid ! go,
+  receive
+    {ChildPid, OldMessageQueueData} -> display(OldMessageQueueData)
+  end,
+  ChildPid ! 3,
+  ChildPid ! stop,
+  receive
+    {ChildPid, Received} -> display(Received)
+  end.
+
+receive_all(Acc) ->
+  receive
+    N when is_integer(N) -> receive_all([N | Acc])
+  after 0 ->
+    lists:reverse(Acc)
+  end.
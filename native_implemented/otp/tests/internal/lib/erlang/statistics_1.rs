@@ -0,0 +1,16 @@
+test_stdout!(
+    with_reductions_returns_total_and_since_last_call,
+    "true\ntrue\n"
+);
+test_stdout!(
+    with_wall_clock_returns_total_and_since_last_call,
+    "true\ntrue\n"
+);
+test_stdout!(
+    with_garbage_collection_returns_collections_and_words_reclaimed,
+    "true\n"
+);
+test_stdout!(
+    with_unsupported_item_errors_badarg,
+    "{caught, error, badarg}\n"
+);
@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, statistics/1]).
+
+start() ->
+  {Collections, WordsReclaimed, 0} = statistics(garbage_collection),
+  display(is_integer(Collections) andalso is_integer(WordsReclaimed)).
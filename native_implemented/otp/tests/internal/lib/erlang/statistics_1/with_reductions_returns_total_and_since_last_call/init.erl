@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, statistics/1]).
+
+start() ->
+  {Total, _} = statistics(reductions),
+  {NextTotal, SinceLastCall} = statistics(reductions),
+  display(Total =< NextTotal),
+  display(SinceLastCall == NextTotal - Total).
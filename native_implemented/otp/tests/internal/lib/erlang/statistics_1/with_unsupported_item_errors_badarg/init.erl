@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [statistics/1]).
+
+start() ->
+  test:caught(fun () ->
+    statistics(unsupported)
+  end).
@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, statistics/1]).
+
+start() ->
+  {Total, _} = statistics(wall_clock),
+  {NextTotal, SinceLastCall} = statistics(wall_clock),
+  display(Total =< NextTotal),
+  display(SinceLastCall == NextTotal - Total).
@@ -0,0 +1,5 @@
+test_stdout!(
+    suspends_until_resumed,
+    "true\n{status, suspended}\ntrue\nresumed\n"
+);
+test_stdout!(with_self_errors_badarg, "{caught, error, badarg}\n");
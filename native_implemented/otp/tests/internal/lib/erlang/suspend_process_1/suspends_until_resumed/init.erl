@@ -0,0 +1,20 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, process_info/2, resume_process/1, suspend_process/1]).
+
+start() ->
+  Parent = self(),
+  Child = spawn(fun () ->
+    receive
+      resume -> Parent ! resumed
+    end
+  end),
+  display(suspend_process(Child)),
+  Child ! resume,
+  display(process_info(Child, status)),
+  display(resume_process(Child)),
+  receive
+    resumed -> display(resumed)
+  after 10 ->
+    display(timeout)
+  end.
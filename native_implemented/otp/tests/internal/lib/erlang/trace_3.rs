@@ -0,0 +1,9 @@
+test_stdout!(
+    with_send_and_receive_flags_traces_messages,
+    "1\n{receive, hello}\n{send, world, true}\nworld\n"
+);
+test_stdout!(with_procs_flag_traces_exit, "1\n{exit, normal}\n");
+test_stdout!(
+    with_unsupported_flag_errors_badarg,
+    "{caught, error, badarg}\n"
+);
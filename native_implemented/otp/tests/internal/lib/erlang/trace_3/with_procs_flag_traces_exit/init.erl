@@ -0,0 +1,15 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, trace/3]).
+
+start() ->
+  Child = spawn(fun () ->
+    receive
+      stop -> ok
+    end
+  end),
+  display(trace(Child, true, [procs])),
+  Child ! stop,
+  receive
+    {trace, Child, exit, Reason} -> display({exit, Reason})
+  end.
@@ -0,0 +1,22 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, trace/3]).
+
+start() ->
+  Parent = self(),
+  Child = spawn(fun () ->
+    receive
+      hello -> Parent ! world
+    end
+  end),
+  display(trace(Child, true, [send, 'receive'])),
+  Child ! hello,
+  receive
+    {trace, Child, 'receive', Received} -> display({'receive', Received})
+  end,
+  receive
+    {trace, Child, send, Sent, To} -> display({send, Sent, To == Parent})
+  end,
+  receive
+    world -> display(world)
+  end.
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [trace/3]).
+
+start() ->
+  test:caught(fun () ->
+    trace(self(), true, [unsupported_flag])
+  end).
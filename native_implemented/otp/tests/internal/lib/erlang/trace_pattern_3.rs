@@ -0,0 +1,12 @@
+test_stdout!(
+    with_true_match_spec_returns_matched_function_count,
+    "1\n1\n"
+);
+test_stdout!(
+    with_return_trace_match_spec_returns_matched_function_count,
+    "1\n1\n1\n"
+);
+test_stdout!(
+    with_unsupported_match_spec_errors_badarg,
+    "{caught, error, badarg}\n"
+);
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, trace_pattern/3]).
+
+start() ->
+  display(trace_pattern({init, start, 0}, [{'_', [], [{return_trace}]}], [local])),
+  display(trace_pattern({init, start, 0}, [{'_', [], [{exception_trace}]}], [local])),
+  display(trace_pattern({init, start, 0}, false, [local])).
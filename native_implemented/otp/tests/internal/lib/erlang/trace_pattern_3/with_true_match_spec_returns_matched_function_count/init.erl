@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, trace_pattern/3]).
+
+start() ->
+  display(trace_pattern({init, start, 0}, true, [local])),
+  display(trace_pattern({init, start, 0}, false, [local])).
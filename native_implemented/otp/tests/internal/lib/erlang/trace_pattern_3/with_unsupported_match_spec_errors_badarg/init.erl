@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [trace_pattern/3]).
+
+start() ->
+  test:caught(fun () ->
+    trace_pattern({init, start, 0}, [{'_', [], [{message, false}]}], [local])
+  end).
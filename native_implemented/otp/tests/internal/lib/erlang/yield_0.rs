@@ -0,0 +1 @@
+test_stdout!(returns_true, "true\n");
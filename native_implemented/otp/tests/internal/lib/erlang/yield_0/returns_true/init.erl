@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1, yield/0]).
+
+start() ->
+  display(yield()).
@@ -0,0 +1,2 @@
+#[path = "lists/sort_2.rs"]
+pub mod sort_2;
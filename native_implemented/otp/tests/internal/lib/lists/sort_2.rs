@@ -0,0 +1,9 @@
+test_stdout!(with_fun_returns_sorted_list, "[]\n[1]\n[5, 4, 3, 2, 1]\n");
+test_stdout!(
+    with_fun_keeps_equal_elements_in_order,
+    "[{a, 2}, {a, 4}, {b, 1}, {b, 3}, {c, 5}]\n"
+);
+test_stdout!(
+    without_function_errors_badarg,
+    "{caught, error, badarg}\n{caught, error, badarg}\n"
+);
@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  ByKey = fun ({LeftKey, _}, {RightKey, _}) -> LeftKey =< RightKey end,
+  display(lists:sort(ByKey, [{b, 1}, {a, 2}, {c, 5}, {b, 3}, {a, 4}])).
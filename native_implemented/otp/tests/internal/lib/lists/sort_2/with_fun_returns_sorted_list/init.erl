@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  Descending = fun (Left, Right) -> Left >= Right end,
+  display(lists:sort(Descending, [])),
+  display(lists:sort(Descending, [1])),
+  display(lists:sort(Descending, [3, 1, 4, 5, 2])).
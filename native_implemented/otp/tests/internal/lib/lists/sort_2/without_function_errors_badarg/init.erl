@@ -0,0 +1,10 @@
+-module(init).
+-export([start/0]).
+
+start() ->
+  test:caught(fun () ->
+    lists:sort(not_a_function, [2, 1])
+  end),
+  test:caught(fun () ->
+    lists:sort(fun (Element) -> Element end, [2, 1])
+  end).
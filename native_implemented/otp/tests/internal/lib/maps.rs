@@ -1,2 +1,14 @@
+#[path = "maps/filter_2.rs"]
+mod filter_2;
+#[path = "maps/fold_3.rs"]
+mod fold_3;
 #[path = "maps/from_list_1.rs"]
 mod from_list_1;
+#[path = "maps/map_2.rs"]
+mod map_2;
+#[path = "maps/merge_with_3.rs"]
+mod merge_with_3;
+#[path = "maps/update_with_3.rs"]
+mod update_with_3;
+#[path = "maps/update_with_4.rs"]
+mod update_with_4;
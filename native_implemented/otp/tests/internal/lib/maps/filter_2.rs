@@ -0,0 +1,8 @@
+test_stdout!(
+    with_pred_keeps_entries_returning_true,
+    "#{b => 2, c => 4}\n"
+);
+test_stdout!(
+    with_pred_returning_non_boolean_errors_badarg,
+    "{caught, error, badarg}\n"
+);
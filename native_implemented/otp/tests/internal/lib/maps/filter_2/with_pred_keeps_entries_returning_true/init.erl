@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:filter(fun (_Key, Value) -> Value rem 2 == 0 end, #{a => 1, b => 2, c => 4})).
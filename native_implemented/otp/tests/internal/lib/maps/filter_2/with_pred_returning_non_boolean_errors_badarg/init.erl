@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+
+start() ->
+  test:caught(fun () ->
+    maps:filter(fun (_Key, Value) -> Value end, #{a => 1})
+  end).
@@ -0,0 +1,5 @@
+test_stdout!(with_fun_folds_entries_in_key_order, "[{b, 2}, {a, 1}]\n0\n");
+test_stdout!(
+    without_function_errors_badarg,
+    "{caught, error, badarg}\n{caught, error, badarg}\n"
+);
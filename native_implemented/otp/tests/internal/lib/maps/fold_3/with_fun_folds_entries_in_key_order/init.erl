@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:fold(fun (Key, Value, Acc) -> [{Key, Value} | Acc] end, [], #{b => 2, a => 1})),
+  display(maps:fold(fun (_Key, Value, Sum) -> Value + Sum end, 0, #{})).
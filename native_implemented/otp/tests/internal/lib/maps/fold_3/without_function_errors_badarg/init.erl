@@ -0,0 +1,10 @@
+-module(init).
+-export([start/0]).
+
+start() ->
+  test:caught(fun () ->
+    maps:fold(not_a_function, 0, #{a => 1})
+  end),
+  test:caught(fun () ->
+    maps:fold(fun (Key, Value) -> {Key, Value} end, 0, #{a => 1})
+  end).
@@ -0,0 +1 @@
+test_stdout!(with_fun_maps_values, "#{a => {a, 2}, b => {b, 4}}\n#{}\n");
@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:map(fun (Key, Value) -> {Key, Value * 2} end, #{a => 1, b => 2})),
+  display(maps:map(fun (_Key, Value) -> Value end, #{})).
@@ -0,0 +1,4 @@
+test_stdout!(
+    with_common_keys_combines_values,
+    "#{a => 1, b => 5, c => 4}\n"
+);
@@ -0,0 +1,7 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  Combiner = fun (_Key, Value1, Value2) -> Value1 + Value2 end,
+  display(maps:merge_with(Combiner, #{a => 1, b => 2}, #{b => 3, c => 4})).
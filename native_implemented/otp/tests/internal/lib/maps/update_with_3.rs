@@ -0,0 +1,2 @@
+test_stdout!(with_key_updates_value, "#{a => 2, b => 2}\n");
+test_stdout!(without_key_errors_badkey, "{caught, error, {badkey, c}}\n");
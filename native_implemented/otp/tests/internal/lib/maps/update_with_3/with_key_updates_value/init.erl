@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:update_with(a, fun (Value) -> Value + 1 end, #{a => 1, b => 2})).
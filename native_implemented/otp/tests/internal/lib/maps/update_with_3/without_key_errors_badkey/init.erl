@@ -0,0 +1,10 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  try maps:update_with(c, fun (Value) -> Value + 1 end, #{a => 1}) of
+    Map -> display({map, Map})
+  catch
+    Class:Exception -> display({caught, Class, Exception})
+  end.
@@ -0,0 +1,2 @@
+test_stdout!(with_key_updates_value, "#{a => 2}\n");
+test_stdout!(without_key_puts_init, "#{a => 1, b => 0}\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:update_with(a, fun (Value) -> Value + 1 end, 0, #{a => 1})).
@@ -0,0 +1,28 @@
+//! The environment of each application, as read by `application:get_env/2,3`
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::config::sys_config::{self, AppConfig};
+use crate::config::term::Value;
+
+lazy_static! {
+    static ref RW_LOCK_ENV_BY_APPLICATION: RwLock<AppConfig> = RwLock::new(HashMap::new());
+}
+
+pub fn get_env(application: Atom, key: Atom) -> Option<Value> {
+    RW_LOCK_ENV_BY_APPLICATION
+        .read()
+        .get(&application)
+        .and_then(|env| env.get(&key))
+        .cloned()
+}
+
+/// Loads the application environments from `sys.config`, overriding any parameters already set
+pub fn load(app_config: AppConfig) {
+    sys_config::merge(&mut RW_LOCK_ENV_BY_APPLICATION.write(), app_config);
+}
@@ -0,0 +1,57 @@
+//! The boot script that the init process runs in place of `init:start/0`, how far it has
+//! progressed, as given by its `{progress, Name}` instructions, and the kernel processes it has
+//! started.
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+pub use crate::config::boot_script::{BootInstruction, BootScript};
+
+lazy_static! {
+    static ref RW_LOCK_OPTION_ARC_SCRIPT: RwLock<Option<Arc<BootScript>>> = RwLock::new(None);
+    static ref RW_LOCK_OPTION_PROGRESS: RwLock<Option<Atom>> = RwLock::new(None);
+    static ref RW_LOCK_KERNEL_PROCESSES: RwLock<Vec<(Atom, Pid)>> = RwLock::new(Vec::new());
+}
+
+/// The module and function of the init process: `lumen:boot/0` runs the loaded boot script and
+/// `init:start/0` is used when there is no boot script.
+pub fn init_module_function() -> (Atom, Atom) {
+    if script().is_some() {
+        (Atom::from_str("lumen"), Atom::from_str("boot"))
+    } else {
+        (Atom::from_str("init"), Atom::from_str("start"))
+    }
+}
+
+/// The `{Name, Pid}` of each process started by a `{kernelProcess, Name, {Mod, Func, Args}}`
+/// instruction, in the order they were started, like the `kernel` list kept by OTP's `init`.
+pub fn kernel_processes() -> Vec<(Atom, Pid)> {
+    RW_LOCK_KERNEL_PROCESSES.read().clone()
+}
+
+/// Loads the boot script that `init` will run.  Must be called before the init process is
+/// spawned.
+pub fn load(script: BootScript) {
+    *RW_LOCK_OPTION_ARC_SCRIPT.write() = Some(Arc::new(script));
+}
+
+/// The `Name` of the last `{progress, Name}` instruction run
+pub fn progress() -> Option<Atom> {
+    *RW_LOCK_OPTION_PROGRESS.read()
+}
+
+pub fn put_kernel_process(name: Atom, pid: Pid) {
+    RW_LOCK_KERNEL_PROCESSES.write().push((name, pid));
+}
+
+pub fn script() -> Option<Arc<BootScript>> {
+    RW_LOCK_OPTION_ARC_SCRIPT.read().clone()
+}
+
+pub fn set_progress(name: Atom) {
+    *RW_LOCK_OPTION_PROGRESS.write() = Some(name);
+}
@@ -1,9 +1,8 @@
 use std::convert::TryInto;
 use std::panic;
 
-use hashbrown::HashMap;
-
-use liblumen_alloc::erts::term::{binary, prelude::*};
+use liblumen_alloc::erts::term::binary::matcher::{self, Bits};
+use liblumen_alloc::erts::term::prelude::*;
 use liblumen_core::sys::Endianness;
 
 use crate::process::current_process;
@@ -36,18 +35,14 @@ pub extern "C" fn builtin_bigint_from_cstr(ptr: *const u8, size: usize) -> Term
 
 #[export_name = "__lumen_builtin_map.new"]
 pub extern "C" fn builtin_map_new() -> Term {
-    current_process().map_from_hash_map(HashMap::default())
+    current_process().map_from_slice(&[])
 }
 
 #[export_name = "__lumen_builtin_map.insert"]
 pub extern "C" fn builtin_map_insert(map: Term, key: Term, value: Term) -> Term {
     let decoded_map: Result<Boxed<Map>, _> = map.decode().unwrap().try_into();
     if let Ok(m) = decoded_map {
-        if let Some(new_map) = m.put(key, value) {
-            current_process().map_from_hash_map(new_map)
-        } else {
-            map
-        }
+        current_process().map_put(m, key, value)
     } else {
         Term::NONE
     }
@@ -57,12 +52,10 @@ pub extern "C" fn builtin_map_insert(map: Term, key: Term, value: Term) -> Term
 pub extern "C" fn builtin_map_update(map: Term, key: Term, value: Term) -> Term {
     let decoded_map: Result<Boxed<Map>, _> = map.decode().unwrap().try_into();
     if let Ok(m) = decoded_map {
-        if let Some(new_map) = m.update(key, value) {
-            current_process().map_from_hash_map(new_map)
-        } else {
-            // TODO: Trigger badkey error
-            Term::NONE
-        }
+        // TODO: Trigger badkey error
+        current_process()
+            .map_update(m, key, value)
+            .unwrap_or(Term::NONE)
     } else {
         Term::NONE
     }
@@ -199,7 +192,7 @@ macro_rules! math_builtin {
                 Term::NONE
             }
         }
-    }
+    };
 }
 
 macro_rules! integer_math_builtin {
@@ -221,7 +214,7 @@ macro_rules! integer_math_builtin {
                 Term::NONE
             }
         }
-    }
+    };
 }
 
 #[export_name = "__lumen_builtin_math.add"]
@@ -300,8 +293,17 @@ pub extern "C" fn builtin_binary_start() -> *mut BinaryBuilder {
 #[export_name = "__lumen_builtin_binary_finish"]
 pub extern "C" fn builtin_binary_finish(builder: *mut BinaryBuilder) -> Term {
     let builder = unsafe { Box::from_raw(builder) };
+    let bit_len = builder.bit_len();
     let bytes = builder.finish();
-    current_process().binary_from_bytes(bytes.as_slice())
+    let process = current_process();
+    let binary = process.binary_from_bytes(bytes.as_slice());
+
+    if bit_len % 8 == 0 {
+        binary
+    } else {
+        // The last byte is only partially in the bitstring
+        process.subbinary_from_original(binary, 0, 0, bit_len / 8, (bit_len % 8) as u8)
+    }
 }
 
 #[export_name = "__lumen_builtin_binary_push_integer"]
@@ -317,8 +319,8 @@ pub extern "C" fn builtin_binary_push_integer(
     let val: Result<Integer, _> = tt.try_into();
     let result = if let Ok(i) = val {
         let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        builder.push_integer(i, bit_size, flags)
+        calculate_bit_size(size, unit, flags)
+            .and_then(|bit_size| builder.push_integer(i, bit_size, flags))
     } else {
         Err(())
     };
@@ -338,11 +340,17 @@ pub extern "C" fn builtin_binary_push_float(
     endianness: Endianness,
 ) -> BinaryPushResult {
     let tt = value.decode().unwrap();
-    let val: Result<Float, _> = tt.try_into();
+    // Integers are converted to floats as in the BEAM
+    let val: Result<f64, ()> = match tt {
+        TypedTerm::Float(float) => Ok(float.into()),
+        TypedTerm::SmallInteger(small_integer) => Ok(small_integer.into()),
+        TypedTerm::BigInteger(big_integer) => Ok(big_integer.into()),
+        _ => Err(()),
+    };
     let result = if let Ok(f) = val {
         let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        builder.push_float(f.into(), bit_size, flags)
+        calculate_bit_size(size, unit, flags)
+            .and_then(|bit_size| builder.push_float(f, bit_size, flags))
     } else {
         Err(())
     };
@@ -395,42 +403,38 @@ pub extern "C" fn builtin_binary_push_utf16(
 pub extern "C" fn builtin_binary_push_utf32(
     builder: &mut BinaryBuilder,
     value: Term,
-    size: Term,
-    unit: u8,
+    _size: Term,
+    _unit: u8,
     signed: bool,
     endianness: Endianness,
 ) -> BinaryPushResult {
     let tt = value.decode().unwrap();
-    let result: Result<SmallInteger, _> = tt.try_into();
-    if let Ok(small) = result {
-        let i: isize = small.into();
-        if i > 0x10FFFF || (0xD800 <= i && i <= 0xDFFF) {
-            // Invalid utf32 integer
-            return BinaryPushResult {
-                builder,
-                success: false,
-            };
-        }
+    let val: Result<SmallInteger, _> = tt.try_into();
+    let result = if let Ok(small) = val {
+        // utf32 segments are always 32 bits
         let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        let success = builder.push_integer(small.into(), bit_size, flags).is_ok();
-        BinaryPushResult { builder, success }
+        builder.push_utf32(small.into(), flags)
     } else {
-        BinaryPushResult {
-            builder,
-            success: false,
-        }
+        Err(())
+    };
+    BinaryPushResult {
+        builder,
+        success: result.is_ok(),
     }
 }
 
 #[export_name = "__lumen_builtin_binary_push_byte_size_unit"]
 pub extern "C" fn builtin_binary_push_byte_size_unit(
-    _builder: &mut BinaryBuilder,
-    _value: Term,
-    _size: Term,
-    _unit: u8,
+    builder: &mut BinaryBuilder,
+    value: Term,
+    size: Term,
+    unit: u8,
 ) -> BinaryPushResult {
-    unimplemented!()
+    let result = match_size(size).and_then(|size| builder.push_bitstring(value, unit, size));
+    BinaryPushResult {
+        builder,
+        success: result.is_ok(),
+    }
 }
 
 #[export_name = "__lumen_builtin_binary_push_byte_unit"]
@@ -447,21 +451,28 @@ pub extern "C" fn builtin_binary_push_byte_unit(
 
 #[export_name = "__lumen_builtin_binary_push_bits_size_unit"]
 pub extern "C" fn builtin_binary_push_bits_size_unit(
-    _builder: &mut BinaryBuilder,
-    _value: Term,
-    _size: Term,
-    _unit: u8,
+    builder: &mut BinaryBuilder,
+    value: Term,
+    size: Term,
+    unit: u8,
 ) -> BinaryPushResult {
-    unimplemented!();
+    let result = match_size(size).and_then(|size| builder.push_bitstring(value, unit, size));
+    BinaryPushResult {
+        builder,
+        success: result.is_ok(),
+    }
 }
 
 #[export_name = "__lumen_builtin_binary_push_bits_unit"]
 pub extern "C" fn builtin_binary_push_bits_unit(
-    _builder: &mut BinaryBuilder,
-    _value: Term,
-    _unit: u8,
+    builder: &mut BinaryBuilder,
+    value: Term,
+    unit: u8,
 ) -> BinaryPushResult {
-    unimplemented!();
+    BinaryPushResult {
+        builder,
+        success: builder.push_bitstring(value, unit, None).is_ok(),
+    }
 }
 
 #[export_name = "__lumen_builtin_binary_push_string"]
@@ -477,65 +488,121 @@ pub extern "C" fn builtin_binary_push_string(
 
 #[export_name = "__lumen_builtin_binary_match.raw"]
 pub extern "C" fn builtin_binary_match_raw(bin: Term, unit: u8, size: Term) -> BinaryMatchResult {
-    let size_opt = if size.is_none() {
-        None
-    } else {
-        let size_decoded: Result<SmallInteger, _> = size.decode().unwrap().try_into();
-        // TODO: Should throw badarg
-        let size: usize = size_decoded.unwrap().try_into().unwrap();
-        Some(size)
-    };
-    let result = match bin.decode().unwrap() {
-        TypedTerm::HeapBinary(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::ProcBin(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::BinaryLiteral(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::SubBinary(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::MatchContext(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        _ => Err(()),
-    };
-    result.unwrap_or_else(|_| BinaryMatchResult::failed())
+    match_bits(bin, size, |bits, size| {
+        let (value, rest) = matcher::match_raw(bits, unit, size)?;
+
+        Some((bits_to_term(value), rest))
+    })
 }
 
 #[export_name = "__lumen_builtin_binary_match.integer"]
 pub extern "C" fn builtin_binary_match_integer(
-    _bin: Term,
-    _signed: bool,
-    _endianness: Endianness,
-    _unit: u8,
-    _size: Term,
+    bin: Term,
+    signed: bool,
+    endianness: Endianness,
+    unit: u8,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    match_bits(bin, size, |bits, size| {
+        // The default size of integer segments is 8
+        let bit_len = size.unwrap_or(8).checked_mul(unit as usize)?;
+        let (value, rest) = matcher::match_integer(bits, bit_len, signed, endianness)?;
+
+        Some((current_process().integer(value), rest))
+    })
 }
 
 #[export_name = "__lumen_builtin_binary_match.float"]
 pub extern "C" fn builtin_binary_match_float(
-    _bin: Term,
-    _endianness: Endianness,
-    _unit: u8,
-    _size: Term,
+    bin: Term,
+    endianness: Endianness,
+    unit: u8,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    match_bits(bin, size, |bits, size| {
+        // The default size of float segments is 64
+        let bit_len = size.unwrap_or(64).checked_mul(unit as usize)?;
+        let (value, rest) = matcher::match_float(bits, bit_len, endianness)?;
+
+        Some((current_process().float(value), rest))
+    })
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf8"]
-pub extern "C" fn builtin_binary_match_utf8(_bin: Term, _size: Term) -> BinaryMatchResult {
-    unimplemented!()
+pub extern "C" fn builtin_binary_match_utf8(bin: Term, size: Term) -> BinaryMatchResult {
+    match_bits(bin, size, |bits, _| {
+        let (value, rest) = matcher::match_utf8(bits)?;
+
+        Some((current_process().integer(value), rest))
+    })
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf16"]
 pub extern "C" fn builtin_binary_match_utf16(
-    _bin: Term,
-    _endianness: Endianness,
-    _size: Term,
+    bin: Term,
+    endianness: Endianness,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    match_bits(bin, size, |bits, _| {
+        let (value, rest) = matcher::match_utf16(bits, endianness)?;
+
+        Some((current_process().integer(value), rest))
+    })
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf32"]
 pub extern "C" fn builtin_binary_match_utf32(
-    _bin: Term,
-    _endianness: Endianness,
-    _size: Term,
+    bin: Term,
+    endianness: Endianness,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!();
+    match_bits(bin, size, |bits, _| {
+        let (value, rest) = matcher::match_utf32(bits, endianness)?;
+
+        Some((current_process().integer(value), rest))
+    })
+}
+
+/// Runs `match_segment` on the bits of `bin` with the segment `size`, if any, and returns the
+/// matched value and the rest of the bits as a sub-binary.
+fn match_bits<F>(bin: Term, size: Term, match_segment: F) -> BinaryMatchResult
+where
+    F: FnOnce(Bits, Option<usize>) -> Option<(Term, Bits)>,
+{
+    let matched = Bits::new(bin).and_then(|bits| {
+        let size = match_size(size)?;
+
+        match_segment(bits, size).ok_or(())
+    });
+
+    match matched {
+        Ok((value, rest)) => BinaryMatchResult::success(value, bits_to_term(rest)),
+        Err(()) => BinaryMatchResult::failed(),
+    }
+}
+
+/// The optional `size` of a segment, which is `Term::NONE` if the segment has no size.
+fn match_size(size: Term) -> Result<Option<usize>, ()> {
+    if size.is_none() {
+        Ok(None)
+    } else {
+        let size_small_integer: SmallInteger =
+            size.decode().map_err(|_| ())?.try_into().map_err(|_| ())?;
+        let size_usize: usize = size_small_integer.try_into().map_err(|_| ())?;
+
+        Ok(Some(size_usize))
+    }
+}
+
+fn bits_to_term(bits: Bits) -> Term {
+    let (original, byte_offset, bit_offset, full_byte_len, partial_byte_bit_len) =
+        bits.to_subbinary_parts();
+
+    current_process().subbinary_from_original(
+        original,
+        byte_offset,
+        bit_offset,
+        full_byte_len,
+        partial_byte_bit_len,
+    )
 }
@@ -0,0 +1,136 @@
+//! Parsing of the `sys.config`, `vm.args`, and boot script files used to configure a release
+pub mod boot_script;
+pub mod sys_config;
+pub mod term;
+pub mod vm_args;
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use liblumen_alloc::erts::term::prelude::Atom;
+
+use crate::application;
+use crate::boot;
+use crate::distribution::nodes::node;
+use crate::process::spawn::set_max_processes;
+
+use self::boot_script::BootScript;
+use self::sys_config::AppConfig;
+use self::vm_args::VmArgs;
+
+pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    FileError(OsString, io::Error),
+    ParseError(OsString, String),
+    /// A flag that is valid for the BEAM, but that the runtime can't honor
+    Unsupported(String),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ConfigError::FileError(ref path, ref err) => write!(
+                f,
+                "Failed to load {}: {}",
+                path.to_string_lossy(),
+                err.to_string()
+            ),
+            ConfigError::ParseError(ref path, ref err) => {
+                write!(f, "Failed to parse {}: {}", path.to_string_lossy(), err)
+            }
+            ConfigError::Unsupported(ref message) => write!(f, "Unsupported: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            ConfigError::FileError(ref _path, ref err) => Some(err),
+            ConfigError::ParseError(_, _) | ConfigError::Unsupported(_) => None,
+        }
+    }
+}
+
+/// Applies the configuration to the runtime.  Must be called before any process is spawned.
+pub fn apply(vm_args: &VmArgs, app_config: &AppConfig, boot: Option<&BootScript>) {
+    for (variable, value) in &vm_args.env {
+        std::env::set_var(variable, value);
+    }
+
+    if let Some(name) = &vm_args.name {
+        node::set_name(Atom::from_str(name));
+    }
+
+    if let Some(cookie) = &vm_args.cookie {
+        node::set_cookie(Atom::from_str(cookie));
+    }
+
+    if let Some(max_processes) = vm_args.max_processes {
+        set_max_processes(max_processes);
+    }
+
+    application::load(app_config.clone());
+
+    if let Some(boot) = boot {
+        boot::load(boot.clone());
+    }
+}
+
+/// Later `--config` files override the parameters of earlier ones
+pub fn load_app_config<'a, I>(paths: I) -> ConfigResult<AppConfig>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    let mut app_config = AppConfig::new();
+
+    for path in paths {
+        let other = with_file(path, sys_config::parse)?;
+        sys_config::merge(&mut app_config, other);
+    }
+
+    Ok(app_config)
+}
+
+/// Later `--args_file` files override the flags of earlier ones
+pub fn load_vm_args<'a, I>(paths: I) -> ConfigResult<VmArgs>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    let mut args = VmArgs::default();
+
+    for path in paths {
+        let other = with_file(path, vm_args::parse)?;
+        args.merge(other);
+    }
+
+    Ok(args)
+}
+
+/// `.boot` files are binary, so unlike other files, they can't be read as a `String`
+pub fn load_boot_script(path: Option<&OsStr>) -> ConfigResult<Option<BootScript>> {
+    match path {
+        None => Ok(None),
+        Some(p) => match fs::read(Path::new(p)) {
+            Err(err) => Err(ConfigError::FileError(p.to_os_string(), err)),
+            Ok(bytes) => boot_script::parse(&bytes)
+                .map(Some)
+                .map_err(|err| ConfigError::ParseError(p.to_os_string(), err)),
+        },
+    }
+}
+
+// Private
+
+fn with_file<T>(p: &OsStr, parse: fn(&str) -> Result<T, String>) -> ConfigResult<T> {
+    match fs::read_to_string(Path::new(p)) {
+        Err(err) => Err(ConfigError::FileError(p.to_os_string(), err)),
+        Ok(contents) => {
+            parse(&contents).map_err(|err| ConfigError::ParseError(p.to_os_string(), err))
+        }
+    }
+}
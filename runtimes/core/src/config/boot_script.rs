@@ -0,0 +1,355 @@
+//! Parses boot scripts, which describe how `init` boots the system.
+//!
+//! A boot script is either a `.script` file, which contains `{script, {Name, Vsn}, Instructions}.`
+//! as text, or a `.boot` file, which contains the same term encoded with `term_to_binary/1`.
+use std::io::Cursor;
+
+use liblumen_beam::serialization::etf;
+
+use super::term::{self, Value};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BootScript {
+    pub name: String,
+    pub version: String,
+    pub instructions: Vec<BootInstruction>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BootInstruction {
+    /// `{progress, Name}`
+    Progress(String),
+    /// `{preLoaded, [Mod]}`
+    PreLoaded(Vec<String>),
+    /// `{path, [Dir]}`
+    Path(Vec<String>),
+    /// `{primLoad, [Mod]}`
+    PrimLoad(Vec<String>),
+    /// `kernel_load_completed`
+    KernelLoadCompleted,
+    /// `{kernelProcess, Name, {Mod, Func, Args}}`
+    KernelProcess {
+        name: String,
+        module: String,
+        function: String,
+        arguments: Vec<Value>,
+    },
+    /// `{apply, {Mod, Func, Args}}`
+    Apply {
+        module: String,
+        function: String,
+        arguments: Vec<Value>,
+    },
+}
+
+/// Parses the `bytes` of a `.boot` file if they start with the external term format version or
+/// of a `.script` file otherwise.
+pub fn parse(bytes: &[u8]) -> Result<BootScript, String> {
+    let value = match bytes.first() {
+        Some(&VERSION) => {
+            let etf_term = etf::Term::decode(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+
+            value_from_etf(&etf_term)?
+        }
+        _ => {
+            let contents = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+            let mut values = term::consult(contents).map_err(|err| err.to_string())?;
+
+            if values.len() != 1 {
+                return Err(format!(
+                    "script must contain exactly 1 term, but contains {}",
+                    values.len()
+                ));
+            }
+
+            values.pop().unwrap()
+        }
+    };
+
+    boot_script(&value)
+}
+
+// Private
+
+const VERSION: u8 = 131;
+
+fn boot_script(value: &Value) -> Result<BootScript, String> {
+    match value.as_tuple() {
+        Some([tag, name_version, instructions]) if tag.as_atom() == Some("script") => {
+            let (name, version) = match name_version.as_tuple() {
+                Some([name, version]) => (string(name)?, string(version)?),
+                _ => {
+                    return Err(format!(
+                        "script name and version ({:?}) must be {{Name, Vsn}}",
+                        name_version
+                    ))
+                }
+            };
+            let instructions = instructions
+                .as_list()
+                .ok_or_else(|| format!("script instructions ({:?}) must be a list", instructions))?
+                .iter()
+                .map(boot_instruction)
+                .collect::<Result<_, _>>()?;
+
+            Ok(BootScript {
+                name,
+                version,
+                instructions,
+            })
+        }
+        _ => Err(format!(
+            "script ({:?}) must be {{script, {{Name, Vsn}}, Instructions}}",
+            value
+        )),
+    }
+}
+
+fn boot_instruction(value: &Value) -> Result<BootInstruction, String> {
+    if value.as_atom() == Some("kernel_load_completed") {
+        return Ok(BootInstruction::KernelLoadCompleted);
+    }
+
+    let instruction = match value.as_tuple() {
+        Some([tag, name]) if tag.as_atom() == Some("progress") => {
+            BootInstruction::Progress(atom(name)?)
+        }
+        Some([tag, modules]) if tag.as_atom() == Some("preLoaded") => {
+            BootInstruction::PreLoaded(atoms(modules)?)
+        }
+        Some([tag, directories]) if tag.as_atom() == Some("path") => {
+            BootInstruction::Path(strings(directories)?)
+        }
+        Some([tag, modules]) if tag.as_atom() == Some("primLoad") => {
+            BootInstruction::PrimLoad(atoms(modules)?)
+        }
+        Some([tag, name, mfa]) if tag.as_atom() == Some("kernelProcess") => {
+            let (module, function, arguments) = module_function_arguments(mfa)?;
+
+            BootInstruction::KernelProcess {
+                name: atom(name)?,
+                module,
+                function,
+                arguments,
+            }
+        }
+        Some([tag, mfa]) if tag.as_atom() == Some("apply") => {
+            let (module, function, arguments) = module_function_arguments(mfa)?;
+
+            BootInstruction::Apply {
+                module,
+                function,
+                arguments,
+            }
+        }
+        _ => return Err(format!("unsupported boot instruction ({:?})", value)),
+    };
+
+    Ok(instruction)
+}
+
+fn module_function_arguments(value: &Value) -> Result<(String, String, Vec<Value>), String> {
+    match value.as_tuple() {
+        Some([module, function, arguments]) => {
+            let arguments = arguments
+                .as_list()
+                .ok_or_else(|| format!("arguments ({:?}) must be a list", arguments))?;
+
+            Ok((atom(module)?, atom(function)?, arguments.to_vec()))
+        }
+        _ => Err(format!("({:?}) must be {{Mod, Func, Args}}", value)),
+    }
+}
+
+fn atom(value: &Value) -> Result<String, String> {
+    value
+        .as_atom()
+        .map(ToString::to_string)
+        .ok_or_else(|| format!("({:?}) must be an atom", value))
+}
+
+fn atoms(value: &Value) -> Result<Vec<String>, String> {
+    value
+        .as_list()
+        .ok_or_else(|| format!("({:?}) must be a list of atoms", value))?
+        .iter()
+        .map(atom)
+        .collect()
+}
+
+/// `Name` and `Vsn` are strings, but may be binaries in hand-written scripts
+fn string(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Binary(bytes) => String::from_utf8(bytes.clone()).map_err(|err| err.to_string()),
+        _ => value
+            .as_string()
+            .ok_or_else(|| format!("({:?}) must be a string", value)),
+    }
+}
+
+fn strings(value: &Value) -> Result<Vec<String>, String> {
+    value
+        .as_list()
+        .ok_or_else(|| format!("({:?}) must be a list of strings", value))?
+        .iter()
+        .map(string)
+        .collect()
+}
+
+fn value_from_etf(etf_term: &etf::Term) -> Result<Value, String> {
+    let value = match etf_term {
+        etf::Term::Atom(atom) => Value::Atom(atom.name.clone()),
+        etf::Term::FixInteger(fix_integer) => Value::Integer(fix_integer.value.into()),
+        etf::Term::BigInteger(big_integer) => Value::Integer(big_integer.value.clone()),
+        etf::Term::Float(float) => Value::Float(float.value),
+        etf::Term::Binary(binary) => Value::Binary(binary.bytes.clone()),
+        etf::Term::List(list) => Value::List(values_from_etf(&list.elements)?, None),
+        etf::Term::ImproperList(improper_list) => Value::List(
+            values_from_etf(&improper_list.elements)?,
+            Some(Box::new(value_from_etf(&improper_list.last)?)),
+        ),
+        etf::Term::Tuple(tuple) => Value::Tuple(values_from_etf(&tuple.elements)?),
+        etf::Term::Map(map) => Value::Map(
+            map.entries
+                .iter()
+                .map(|(key, value)| Ok((value_from_etf(key)?, value_from_etf(value)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+        _ => return Err(format!("({}) cannot be in a boot script", etf_term)),
+    };
+
+    Ok(value)
+}
+
+fn values_from_etf(etf_terms: &[etf::Term]) -> Result<Vec<Value>, String> {
+    etf_terms.iter().map(value_from_etf).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_script_parses_instructions() {
+        let script = br#"
+            %% script generated by systools
+            {script, {"lumen", "1.0"},
+             [{preLoaded, [erlang, init]},
+              {progress, preloaded},
+              {path, ["$ROOT/lib/kernel/ebin"]},
+              {primLoad, [error_handler, application]},
+              kernel_load_completed,
+              {kernelProcess, heart, {heart, start, []}},
+              {apply, {application, start_boot, [kernel, permanent]}}]}.
+        "#;
+
+        assert_eq!(
+            parse(script),
+            Ok(BootScript {
+                name: "lumen".to_string(),
+                version: "1.0".to_string(),
+                instructions: vec![
+                    BootInstruction::PreLoaded(vec!["erlang".to_string(), "init".to_string()]),
+                    BootInstruction::Progress("preloaded".to_string()),
+                    BootInstruction::Path(vec!["$ROOT/lib/kernel/ebin".to_string()]),
+                    BootInstruction::PrimLoad(vec![
+                        "error_handler".to_string(),
+                        "application".to_string()
+                    ]),
+                    BootInstruction::KernelLoadCompleted,
+                    BootInstruction::KernelProcess {
+                        name: "heart".to_string(),
+                        module: "heart".to_string(),
+                        function: "start".to_string(),
+                        arguments: vec![],
+                    },
+                    BootInstruction::Apply {
+                        module: "application".to_string(),
+                        function: "start_boot".to_string(),
+                        arguments: vec![
+                            Value::Atom("kernel".to_string()),
+                            Value::Atom("permanent".to_string())
+                        ],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_script_accepts_binary_name_and_version() {
+        assert_eq!(
+            parse(br#"{script, {<<"lumen">>, <<"1.0">>}, []}."#),
+            Ok(BootScript {
+                name: "lumen".to_string(),
+                version: "1.0".to_string(),
+                instructions: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_boot_decodes_external_term_format() {
+        // term_to_binary({script, {"n", "1"}, [kernel_load_completed]})
+        let mut boot = vec![VERSION, 104, 3, 100, 0, 6];
+        boot.extend_from_slice(b"script");
+        boot.extend_from_slice(&[104, 2, 107, 0, 1, b'n', 107, 0, 1, b'1', 108, 0, 0, 0, 1]);
+        boot.extend_from_slice(&[100, 0, 21]);
+        boot.extend_from_slice(b"kernel_load_completed");
+        boot.push(106);
+
+        assert_eq!(
+            parse(&boot),
+            Ok(BootScript {
+                name: "n".to_string(),
+                version: "1".to_string(),
+                instructions: vec![BootInstruction::KernelLoadCompleted],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors_on_invalid_scripts() {
+        let cases: Vec<(&[u8], &str)> = vec![
+            (
+                b"a. b.",
+                "script must contain exactly 1 term, but contains 2",
+            ),
+            (
+                b"{not_script, {\"n\", \"1\"}, []}.",
+                "must be {script, {Name, Vsn}, Instructions}",
+            ),
+            (b"{script, n, []}.", "must be {Name, Vsn}"),
+            (b"{script, {\"n\", \"1\"}, instructions}.", "must be a list"),
+            (
+                b"{script, {\"n\", \"1\"}, [{unknown, 1}]}.",
+                "unsupported boot instruction",
+            ),
+            (
+                b"{script, {\"n\", \"1\"}, [{apply, {m, f, a}}]}.",
+                "arguments (Atom(\"a\")) must be a list",
+            ),
+            (
+                b"{script, {\"n\", \"1\"}, [{progress, \"p\"}]}.",
+                "must be an atom",
+            ),
+        ];
+
+        for (script, message) in cases {
+            match parse(script) {
+                Err(error) => assert!(
+                    error.contains(message),
+                    "parse({:?}) error ({:?}) does not contain {:?}",
+                    String::from_utf8_lossy(script),
+                    error,
+                    message
+                ),
+                Ok(boot_script) => panic!(
+                    "parse({:?}) parsed {:?}",
+                    String::from_utf8_lossy(script),
+                    boot_script
+                ),
+            }
+        }
+    }
+}
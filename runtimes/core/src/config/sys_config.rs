@@ -0,0 +1,111 @@
+//! Parses `sys.config` files, which contain a single term of the form
+//! `[{Application, [{Key, Value}]} | File]`, where each `File` is the path of another config file
+//! to include.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::term::{self, Value};
+
+/// The environment of each application
+pub type AppConfig = HashMap<Atom, HashMap<Atom, Value>>;
+
+/// Parses the `contents` of a `sys.config` file.
+///
+/// Included files are read relative to the current working directory and may omit the `.config`
+/// extension, as in OTP.  Parameters from later entries override those of earlier entries.
+pub fn parse(contents: &str) -> Result<AppConfig, String> {
+    let mut app_config = AppConfig::new();
+    put_all(&mut app_config, contents)?;
+
+    Ok(app_config)
+}
+
+/// Puts all the parameters in `other` into `app_config`, overriding parameters that are in both
+pub fn merge(app_config: &mut AppConfig, other: AppConfig) {
+    for (application, env) in other {
+        app_config.entry(application).or_default().extend(env);
+    }
+}
+
+// Private
+
+fn put_all(app_config: &mut AppConfig, contents: &str) -> Result<(), String> {
+    let values = term::consult(contents).map_err(|err| err.to_string())?;
+
+    let entries = match values.as_slice() {
+        [value] => value
+            .as_list()
+            .ok_or_else(|| format!("config ({:?}) must be a list", value))?,
+        _ => {
+            return Err(format!(
+                "config must contain exactly 1 term, but contains {}",
+                values.len()
+            ))
+        }
+    };
+
+    for entry in entries {
+        if let Some(path) = entry.as_string() {
+            put_all_from_file(app_config, &path)?;
+        } else {
+            let (application, env) = application_env(entry)?;
+            app_config.entry(application).or_default().extend(env);
+        }
+    }
+
+    Ok(())
+}
+
+fn put_all_from_file(app_config: &mut AppConfig, path: &str) -> Result<(), String> {
+    let mut path_buf = PathBuf::from(path);
+
+    if path_buf.extension().is_none() {
+        path_buf.set_extension("config");
+    }
+
+    let contents = fs::read_to_string(Path::new(&path_buf))
+        .map_err(|err| format!("Failed to load {}: {}", path_buf.display(), err))?;
+
+    put_all(app_config, &contents)
+}
+
+fn application_env(entry: &Value) -> Result<(Atom, HashMap<Atom, Value>), String> {
+    let (application, parameters) = atom_pair(entry).ok_or_else(|| {
+        format!(
+            "entry ({:?}) must be {{Application, [{{Par, Val}}]}}",
+            entry
+        )
+    })?;
+    let parameters = parameters.as_list().ok_or_else(|| {
+        format!(
+            "application ({}) parameters ({:?}) must be a list",
+            application, parameters
+        )
+    })?;
+
+    let mut env = HashMap::with_capacity(parameters.len());
+
+    for parameter in parameters {
+        let (key, value) = atom_pair(parameter).ok_or_else(|| {
+            format!(
+                "application ({}) parameter ({:?}) must be {{Par, Val}}",
+                application, parameter
+            )
+        })?;
+
+        env.insert(key, value.clone());
+    }
+
+    Ok((application, env))
+}
+
+/// `{Atom, Value}`
+fn atom_pair(value: &Value) -> Option<(Atom, &Value)> {
+    match value.as_tuple() {
+        Some([key, value]) => key.as_atom().map(|name| (Atom::from_str(name), value)),
+        _ => None,
+    }
+}
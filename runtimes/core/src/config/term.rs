@@ -0,0 +1,746 @@
+//! Parses the Erlang term syntax used in `sys.config` and `.script` files into `Value`s that live
+//! outside of any process heap, so they can be converted to `Term`s for any process that asks for
+//! them.
+use std::iter::Peekable;
+use std::str::Chars;
+
+use num_bigint::BigInt;
+use num_traits::Num;
+use thiserror::Error;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Atom(String),
+    Integer(BigInt),
+    Float(f64),
+    Binary(Vec<u8>),
+    /// A list with its improper tail, if any
+    List(Vec<Value>, Option<Box<Value>>),
+    Tuple(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+impl Value {
+    pub fn as_atom(&self) -> Option<&str> {
+        match self {
+            Value::Atom(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a proper list
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(elements, None) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Returns the characters of a string, which is a proper list of unicode scalar values
+    pub fn as_string(&self) -> Option<String> {
+        self.as_list().and_then(|elements| {
+            elements
+                .iter()
+                .map(|element| match element {
+                    Value::Integer(integer) => {
+                        num_traits::ToPrimitive::to_u32(integer).and_then(std::char::from_u32)
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
+    pub fn as_tuple(&self) -> Option<&[Value]> {
+        match self {
+            Value::Tuple(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Copies the value onto the `process` heap
+    pub fn to_term(&self, process: &Process) -> Term {
+        match self {
+            Value::Atom(name) => Atom::str_to_term(name),
+            Value::Integer(integer) => process.integer(integer.clone()),
+            Value::Float(float) => process.float(*float),
+            Value::Binary(bytes) => process.binary_from_bytes(bytes),
+            Value::List(elements, tail) => {
+                let element_terms: Vec<Term> = elements
+                    .iter()
+                    .map(|element| element.to_term(process))
+                    .collect();
+
+                match tail {
+                    Some(tail) => {
+                        let tail_term = tail.to_term(process);
+
+                        process.improper_list_from_slice(&element_terms, tail_term)
+                    }
+                    None => process.list_from_slice(&element_terms),
+                }
+            }
+            Value::Tuple(elements) => {
+                let element_terms: Vec<Term> = elements
+                    .iter()
+                    .map(|element| element.to_term(process))
+                    .collect();
+
+                process.tuple_from_slice(&element_terms)
+            }
+            Value::Map(entries) => {
+                let entry_terms: Vec<(Term, Term)> = entries
+                    .iter()
+                    .map(|(key, value)| (key.to_term(process), value.to_term(process)))
+                    .collect();
+
+                process.map_from_slice(&entry_terms)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("line {line}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parses a sequence of terms, each terminated by `.`, as read by `file:consult/1`.
+pub fn consult(source: &str) -> Result<Vec<Value>, ParseError> {
+    let mut parser = Parser::new(source);
+    let mut values = Vec::new();
+
+    loop {
+        parser.skip_whitespace_and_comments();
+
+        if parser.peek().is_none() {
+            break;
+        }
+
+        values.push(parser.parse_value()?);
+        parser.expect('.')?;
+    }
+
+    Ok(values)
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn error<T>(&self, message: String) -> Result<T, ParseError> {
+        Err(ParseError {
+            line: self.line,
+            message,
+        })
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace_and_comments();
+
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => self.error(format!("expected '{}', but found '{}'", expected, c)),
+            None => self.error(format!("expected '{}', but found end of file", expected)),
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let option_c = self.chars.next();
+
+        if option_c == Some('\n') {
+            self.line += 1;
+        }
+
+        option_c
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.next();
+            } else if c == '%' {
+                while let Some(c) = self.next() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace_and_comments();
+
+        match self.peek() {
+            Some('{') => {
+                self.next();
+                let elements = self.parse_sequence('}')?;
+
+                Ok(Value::Tuple(elements))
+            }
+            Some('[') => {
+                self.next();
+                self.parse_list()
+            }
+            Some('#') => {
+                self.next();
+                self.expect('{')?;
+                self.parse_map()
+            }
+            Some('<') => {
+                self.next();
+                self.expect('<')?;
+                self.parse_binary()
+            }
+            Some('"') => {
+                self.next();
+                let string = self.parse_quoted('"')?;
+
+                Ok(Value::List(
+                    string
+                        .chars()
+                        .map(|c| Value::Integer((c as u32).into()))
+                        .collect(),
+                    None,
+                ))
+            }
+            Some('\'') => {
+                self.next();
+                let name = self.parse_quoted('\'')?;
+
+                Ok(Value::Atom(name))
+            }
+            Some('$') => {
+                self.next();
+                let c = match self.next() {
+                    Some('\\') => self.parse_escape()?,
+                    Some(c) => c,
+                    None => return self.error("expected character after '$'".to_string()),
+                };
+
+                Ok(Value::Integer((c as u32).into()))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c.is_lowercase() => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '@');
+
+                Ok(Value::Atom(name))
+            }
+            Some(c) => self.error(format!("unexpected '{}'", c)),
+            None => self.error("unexpected end of file".to_string()),
+        }
+    }
+
+    /// Parses comma-separated values until `close`, which is consumed
+    fn parse_sequence(&mut self, close: char) -> Result<Vec<Value>, ParseError> {
+        let mut elements = Vec::new();
+
+        self.skip_whitespace_and_comments();
+
+        if self.peek() == Some(close) {
+            self.next();
+
+            return Ok(elements);
+        }
+
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace_and_comments();
+
+            match self.next() {
+                Some(',') => continue,
+                Some(c) if c == close => return Ok(elements),
+                Some(c) => {
+                    return self.error(format!("expected ',' or '{}', but found '{}'", close, c))
+                }
+                None => return self.error(format!("expected '{}', but found end of file", close)),
+            }
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Value, ParseError> {
+        let mut elements = Vec::new();
+
+        self.skip_whitespace_and_comments();
+
+        if self.peek() == Some(']') {
+            self.next();
+
+            return Ok(Value::List(elements, None));
+        }
+
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace_and_comments();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('|') => {
+                    let tail = self.parse_value()?;
+                    self.expect(']')?;
+
+                    return Ok(Value::List(elements, Some(Box::new(tail))));
+                }
+                Some(']') => return Ok(Value::List(elements, None)),
+                Some(c) => {
+                    return self.error(format!("expected ',', '|' or ']', but found '{}'", c))
+                }
+                None => return self.error("expected ']', but found end of file".to_string()),
+            }
+        }
+    }
+
+    fn parse_map(&mut self) -> Result<Value, ParseError> {
+        let mut entries = Vec::new();
+
+        self.skip_whitespace_and_comments();
+
+        if self.peek() == Some('}') {
+            self.next();
+
+            return Ok(Value::Map(entries));
+        }
+
+        loop {
+            let key = self.parse_value()?;
+            self.expect('=')?;
+            self.expect('>')?;
+            let value = self.parse_value()?;
+            entries.push((key, value));
+            self.skip_whitespace_and_comments();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Value::Map(entries)),
+                Some(c) => return self.error(format!("expected ',' or '}}', but found '{}'", c)),
+                None => return self.error("expected '}', but found end of file".to_string()),
+            }
+        }
+    }
+
+    /// Parses the segments of a binary, which may be strings or bytes, after the opening `<<`
+    fn parse_binary(&mut self) -> Result<Value, ParseError> {
+        let mut bytes = Vec::new();
+
+        self.skip_whitespace_and_comments();
+
+        if self.peek() == Some('>') {
+            self.expect('>')?;
+            self.expect('>')?;
+
+            return Ok(Value::Binary(bytes));
+        }
+
+        loop {
+            self.skip_whitespace_and_comments();
+
+            match self.parse_value()? {
+                Value::List(elements, None) => {
+                    // a string segment, which is UTF-8 encoded
+                    let string: String = elements
+                        .iter()
+                        .filter_map(|element| match element {
+                            Value::Integer(integer) => num_traits::ToPrimitive::to_u32(integer)
+                                .and_then(std::char::from_u32),
+                            _ => None,
+                        })
+                        .collect();
+                    bytes.extend_from_slice(string.as_bytes());
+                }
+                Value::Integer(integer) => match num_traits::ToPrimitive::to_u8(&integer) {
+                    Some(byte) => bytes.push(byte),
+                    None => {
+                        return self.error(format!("binary segment ({}) is not a byte", integer))
+                    }
+                },
+                value => {
+                    return self.error(format!(
+                        "binary segment ({:?}) is neither a string nor a byte",
+                        value
+                    ))
+                }
+            }
+
+            self.skip_whitespace_and_comments();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('>') => {
+                    self.expect('>')?;
+
+                    return Ok(Value::Binary(bytes));
+                }
+                Some(c) => return self.error(format!("expected ',' or '>>', but found '{}'", c)),
+                None => return self.error("expected '>>', but found end of file".to_string()),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, ParseError> {
+        let negative = if self.peek() == Some('-') {
+            self.next();
+            true
+        } else {
+            false
+        };
+
+        let digits = self.take_while(|c| c.is_ascii_digit() || c == '_');
+        let digits = digits.replace('_', "");
+
+        if digits.is_empty() {
+            return self.error("expected digits".to_string());
+        }
+
+        let sign = if negative { "-" } else { "" };
+
+        match self.peek() {
+            Some('#') => {
+                self.next();
+                let radix: u32 = digits.parse().unwrap();
+
+                if radix < 2 || 36 < radix {
+                    return self.error(format!("radix ({}) must be between 2 and 36", radix));
+                }
+
+                let radix_digits = self
+                    .take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+                    .replace('_', "");
+
+                match BigInt::from_str_radix(&format!("{}{}", sign, radix_digits), radix) {
+                    Ok(integer) => Ok(Value::Integer(integer)),
+                    Err(_) => self.error(format!(
+                        "{} is not a valid base {} integer",
+                        radix_digits, radix
+                    )),
+                }
+            }
+            Some('.') => {
+                // Could be the terminating `.` of the term instead of a fraction
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+
+                match lookahead.peek() {
+                    Some(c) if c.is_ascii_digit() => {
+                        self.next();
+                        let fraction = self.take_while(|c| c.is_ascii_digit());
+                        let mut float = format!("{}{}.{}", sign, digits, fraction);
+
+                        if let Some('e') | Some('E') = self.peek() {
+                            self.next();
+                            float.push('e');
+
+                            if let Some(c @ '-') | Some(c @ '+') = self.peek() {
+                                self.next();
+                                float.push(c);
+                            }
+
+                            float.push_str(&self.take_while(|c| c.is_ascii_digit()));
+                        }
+
+                        match float.parse() {
+                            Ok(float) => Ok(Value::Float(float)),
+                            Err(_) => self.error(format!("{} is not a valid float", float)),
+                        }
+                    }
+                    _ => Ok(Value::Integer(
+                        BigInt::from_str_radix(&format!("{}{}", sign, digits), 10).unwrap(),
+                    )),
+                }
+            }
+            _ => Ok(Value::Integer(
+                BigInt::from_str_radix(&format!("{}{}", sign, digits), 10).unwrap(),
+            )),
+        }
+    }
+
+    /// Parses the contents of a quoted atom or string after the opening `quote`
+    fn parse_quoted(&mut self, quote: char) -> Result<String, ParseError> {
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('\\') => string.push(self.parse_escape()?),
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+                None => return self.error(format!("expected closing {}", quote)),
+            }
+        }
+    }
+
+    /// Parses an escape sequence after the `\`, as described in
+    /// [Escape Sequences](https://erlang.org/doc/reference_manual/data_types.html#escape-sequences)
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        match self.next() {
+            Some('b') => Ok('\x08'),
+            Some('d') => Ok('\x7f'),
+            Some('e') => Ok('\x1b'),
+            Some('f') => Ok('\x0c'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('s') => Ok(' '),
+            Some('t') => Ok('\t'),
+            Some('v') => Ok('\x0b'),
+            Some(first @ '0'..='7') => {
+                let mut digits = first.to_string();
+
+                // up to 3 octal digits
+                while digits.len() < 3 {
+                    match self.peek() {
+                        Some(c @ '0'..='7') => {
+                            self.next();
+                            digits.push(c);
+                        }
+                        _ => break,
+                    }
+                }
+
+                self.char_from_str_radix(&digits, 8)
+            }
+            Some('x') => {
+                if self.peek() == Some('{') {
+                    self.next();
+                    let digits = self.take_while(|c| c.is_ascii_hexdigit());
+                    self.expect('}')?;
+
+                    if digits.is_empty() {
+                        return self.error("expected hexadecimal digits in \\x{...}".to_string());
+                    }
+
+                    self.char_from_str_radix(&digits, 16)
+                } else {
+                    let mut digits = String::new();
+
+                    // exactly 2 hexadecimal digits
+                    while digits.len() < 2 {
+                        match self.next() {
+                            Some(c) if c.is_ascii_hexdigit() => digits.push(c),
+                            _ => {
+                                return self
+                                    .error("expected 2 hexadecimal digits after \\x".to_string())
+                            }
+                        }
+                    }
+
+                    self.char_from_str_radix(&digits, 16)
+                }
+            }
+            Some('^') => match self.next() {
+                // control characters, such as `\^a` for `\x01`
+                Some(c) if c.is_ascii_alphabetic() => Ok(((c as u8) & 0x1f) as char),
+                Some(c) => self.error(format!("'{}' is not a control character letter", c)),
+                None => self.error("expected control character letter after \\^".to_string()),
+            },
+            Some(c) => Ok(c),
+            None => self.error("expected escaped character".to_string()),
+        }
+    }
+
+    fn char_from_str_radix(&self, digits: &str, radix: u32) -> Result<char, ParseError> {
+        match u32::from_str_radix(digits, radix)
+            .ok()
+            .and_then(std::char::from_u32)
+        {
+            Some(c) => Ok(c),
+            None => self.error(format!(
+                "{} is not a valid base {} character code",
+                digits, radix
+            )),
+        }
+    }
+
+    fn take_while<P>(&mut self, predicate: P) -> String
+    where
+        P: Fn(char) -> bool,
+    {
+        let mut taken = String::new();
+
+        while let Some(c) = self.peek() {
+            if predicate(c) {
+                taken.push(c);
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consult_parses_values() {
+        let cases: Vec<(&str, Value)> = vec![
+            ("atom.", atom("atom")),
+            ("'quoted atom'.", atom("quoted atom")),
+            ("node@host.", atom("node@host")),
+            ("42.", integer(42)),
+            ("-42.", integer(-42)),
+            ("1_000.", integer(1000)),
+            ("16#ff.", integer(255)),
+            ("-2#101.", integer(-5)),
+            ("1.5.", Value::Float(1.5)),
+            ("-1.5e3.", Value::Float(-1500.0)),
+            ("$a.", integer(97)),
+            ("\"ab\".", string("ab")),
+            ("<<>>.", Value::Binary(vec![])),
+            ("<<\"ab\", 1>>.", Value::Binary(vec![b'a', b'b', 1])),
+            ("[].", Value::List(vec![], None)),
+            ("[a, 1].", Value::List(vec![atom("a"), integer(1)], None)),
+            (
+                "[a | b].",
+                Value::List(vec![atom("a")], Some(Box::new(atom("b")))),
+            ),
+            ("{}.", Value::Tuple(vec![])),
+            (
+                "{a, [1]}.",
+                Value::Tuple(vec![atom("a"), Value::List(vec![integer(1)], None)]),
+            ),
+            ("#{}.", Value::Map(vec![])),
+            ("#{a => 1}.", Value::Map(vec![(atom("a"), integer(1))])),
+            (
+                "% comment\n{a, % inline\n b}.",
+                Value::Tuple(vec![atom("a"), atom("b")]),
+            ),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(consult(source), Ok(vec![expected]), "consult({:?})", source);
+        }
+    }
+
+    #[test]
+    fn consult_parses_escapes() {
+        let cases: Vec<(&str, &str)> = vec![
+            (r#""\b""#, "\x08"),
+            (r#""\d""#, "\x7f"),
+            (r#""\e""#, "\x1b"),
+            (r#""\f""#, "\x0c"),
+            (r#""\n""#, "\n"),
+            (r#""\r""#, "\r"),
+            (r#""\s""#, " "),
+            (r#""\t""#, "\t"),
+            (r#""\v""#, "\x0b"),
+            (r#""\0""#, "\0"),
+            (r#""\7""#, "\x07"),
+            (r#""\101""#, "A"),
+            (r#""\1010""#, "A0"),
+            (r#""\x41""#, "A"),
+            (r#""\x411""#, "A1"),
+            (r#""\x{41}""#, "A"),
+            (r#""\x{1F600}""#, "\u{1F600}"),
+            (r#""\^a""#, "\x01"),
+            (r#""\^Z""#, "\x1a"),
+            (r#""\"""#, "\""),
+            (r#""\\""#, "\\"),
+            (r#""\q""#, "q"),
+        ];
+
+        for (source, expected) in cases {
+            let source = format!("{}.", source);
+
+            assert_eq!(
+                consult(&source),
+                Ok(vec![string(expected)]),
+                "consult({:?})",
+                source
+            );
+        }
+
+        assert_eq!(consult(r"'\x41\101'."), Ok(vec![atom("AA")]));
+        assert_eq!(consult(r"$\x41."), Ok(vec![integer(65)]));
+    }
+
+    #[test]
+    fn consult_parses_multiple_terms() {
+        assert_eq!(
+            consult("a.\n\n{b, 1}.\n"),
+            Ok(vec![atom("a"), Value::Tuple(vec![atom("b"), integer(1)])])
+        );
+    }
+
+    #[test]
+    fn consult_errors_with_line() {
+        let cases: Vec<(&str, usize, &str)> = vec![
+            ("a", 1, "expected '.'"),
+            ("\n{a, b", 2, "expected '}', but found end of file"),
+            ("[a b].", 1, "expected ',', '|' or ']'"),
+            ("37#1.", 1, "radix (37) must be between 2 and 36"),
+            ("<<256>>.", 1, "binary segment (256) is not a byte"),
+            (r#""\xg1"."#, 1, "expected 2 hexadecimal digits after \\x"),
+            (r#""\x{}"."#, 1, "expected hexadecimal digits"),
+            (
+                r#""\x{110000}"."#,
+                1,
+                "is not a valid base 16 character code",
+            ),
+            (r#""\^1"."#, 1, "is not a control character letter"),
+            ("\"open.", 1, "expected closing \""),
+        ];
+
+        for (source, line, message) in cases {
+            match consult(source) {
+                Err(error) => {
+                    assert_eq!(error.line, line, "consult({:?})", source);
+                    assert!(
+                        error.message.contains(message),
+                        "consult({:?}) message ({:?}) does not contain {:?}",
+                        source,
+                        error.message,
+                        message
+                    );
+                }
+                Ok(values) => panic!("consult({:?}) parsed {:?}", source, values),
+            }
+        }
+    }
+
+    #[test]
+    fn as_string_returns_characters_of_string() {
+        assert_eq!(string("héllo").as_string(), Some("héllo".to_string()));
+        assert_eq!(atom("hello").as_string(), None);
+        assert_eq!(Value::List(vec![atom("a")], None).as_string(), None);
+    }
+
+    fn atom(name: &str) -> Value {
+        Value::Atom(name.to_string())
+    }
+
+    fn integer(integer: i64) -> Value {
+        Value::Integer(integer.into())
+    }
+
+    fn string(string: &str) -> Value {
+        Value::List(
+            string
+                .chars()
+                .map(|c| Value::Integer((c as u32).into()))
+                .collect(),
+            None,
+        )
+    }
+}
@@ -0,0 +1,279 @@
+//! Parses `vm.args` files, which contain emulator flags separated by whitespace, with `#`
+//! starting a comment that runs to the end of the line.
+//!
+//! Only the flags that the Lumen runtimes support are kept; all others are ignored, so that the
+//! same `vm.args` can be shared with BEAM releases.
+
+/// The flags from one or more `vm.args` files
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VmArgs {
+    /// `-name Name` or `-sname Name`
+    pub name: Option<String>,
+    /// `-setcookie Cookie`
+    pub cookie: Option<String>,
+    /// `+S Schedulers[:SchedulersOnline]`
+    pub schedulers: Option<usize>,
+    /// `+P MaxProcesses`
+    pub max_processes: Option<usize>,
+    /// `-env Variable Value`, in the order given
+    pub env: Vec<(String, String)>,
+}
+
+impl VmArgs {
+    /// Flags in `other` override those in `self`, except for `env`, which is appended
+    pub fn merge(&mut self, other: VmArgs) {
+        if other.name.is_some() {
+            self.name = other.name;
+        }
+
+        if other.cookie.is_some() {
+            self.cookie = other.cookie;
+        }
+
+        if other.schedulers.is_some() {
+            self.schedulers = other.schedulers;
+        }
+
+        if other.max_processes.is_some() {
+            self.max_processes = other.max_processes;
+        }
+
+        self.env.extend(other.env);
+    }
+}
+
+pub fn parse(contents: &str) -> Result<VmArgs, String> {
+    let mut vm_args = VmArgs::default();
+    let mut tokens = contents
+        .lines()
+        .map(|line| match line.find('#') {
+            Some(index) => &line[..index],
+            None => line,
+        })
+        .flat_map(|line| line.split_whitespace());
+
+    while let Some(flag) = tokens.next() {
+        match flag {
+            "-name" => {
+                let name = value(flag, tokens.next())?;
+                vm_args.name = Some(node_name(name));
+            }
+            "-sname" => {
+                let name = value(flag, tokens.next())?;
+                vm_args.name = Some(short_node_name(name)?);
+            }
+            "-setcookie" => {
+                let cookie = value(flag, tokens.next())?;
+                vm_args.cookie = Some(cookie.to_string());
+            }
+            "-env" => {
+                let variable = value(flag, tokens.next())?;
+                let variable_value = value(flag, tokens.next())?;
+                vm_args
+                    .env
+                    .push((variable.to_string(), variable_value.to_string()));
+            }
+            "+S" => {
+                let schedulers = value(flag, tokens.next())?;
+                // Only the number of schedulers matters, as all schedulers are online
+                let schedulers = schedulers.split(':').next().unwrap();
+                vm_args.schedulers = Some(positive_integer(flag, schedulers)?);
+            }
+            "+P" => {
+                let max_processes = value(flag, tokens.next())?;
+                vm_args.max_processes = Some(positive_integer(flag, max_processes)?);
+            }
+            // Unsupported flags and their values
+            _ => (),
+        }
+    }
+
+    Ok(vm_args)
+}
+
+/// Node names must have a host, which defaults to `localhost`
+pub fn node_name(name: &str) -> String {
+    if name.contains('@') {
+        name.to_string()
+    } else {
+        format!("{}@localhost", name)
+    }
+}
+
+/// Short node names, from `-sname`, can only have a host name, not a fully qualified domain name
+/// like long node names from `-name`.  The host defaults to `localhost`.
+pub fn short_node_name(name: &str) -> Result<String, String> {
+    match name.find('@').map(|index| &name[index + 1..]) {
+        Some(host) if host.contains('.') => Err(format!(
+            "-sname value ({}) has a fully qualified host ({}), use -name instead",
+            name, host
+        )),
+        _ => Ok(node_name(name)),
+    }
+}
+
+// Private
+
+fn positive_integer(flag: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) | Err(_) => Err(format!(
+            "{} value ({}) must be a positive integer",
+            flag, value
+        )),
+        Ok(integer) => Ok(integer),
+    }
+}
+
+fn value<'a>(flag: &str, option_value: Option<&'a str>) -> Result<&'a str, String> {
+    option_value.ok_or_else(|| format!("{} is missing its value", flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keeps_supported_flags() {
+        let cases: Vec<(&str, VmArgs)> = vec![
+            ("", VmArgs::default()),
+            (
+                "-name node",
+                VmArgs {
+                    name: Some("node@localhost".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "-name node@host.example.com",
+                VmArgs {
+                    name: Some("node@host.example.com".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "-sname node",
+                VmArgs {
+                    name: Some("node@localhost".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "-sname node@host",
+                VmArgs {
+                    name: Some("node@host".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "-setcookie secret",
+                VmArgs {
+                    cookie: Some("secret".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "+S 4",
+                VmArgs {
+                    schedulers: Some(4),
+                    ..Default::default()
+                },
+            ),
+            (
+                "+S 4:2",
+                VmArgs {
+                    schedulers: Some(4),
+                    ..Default::default()
+                },
+            ),
+            (
+                "+P 1024",
+                VmArgs {
+                    max_processes: Some(1024),
+                    ..Default::default()
+                },
+            ),
+            (
+                "-env A 1\n-env B 2",
+                VmArgs {
+                    env: vec![
+                        ("A".to_string(), "1".to_string()),
+                        ("B".to_string(), "2".to_string()),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                "# comment -name ignored\n+K true -name node # trailing comment\n+A 10",
+                VmArgs {
+                    name: Some("node@localhost".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (contents, expected) in cases {
+            assert_eq!(parse(contents), Ok(expected), "parse({:?})", contents);
+        }
+    }
+
+    #[test]
+    fn parse_errors_on_invalid_flags() {
+        let cases: Vec<(&str, &str)> = vec![
+            ("-name", "-name is missing its value"),
+            ("-env A", "-env is missing its value"),
+            ("+S 0", "+S value (0) must be a positive integer"),
+            ("+S many", "+S value (many) must be a positive integer"),
+            ("+P -1", "+P value (-1) must be a positive integer"),
+            (
+                "-sname node@host.example.com",
+                "has a fully qualified host (host.example.com), use -name instead",
+            ),
+        ];
+
+        for (contents, message) in cases {
+            match parse(contents) {
+                Err(error) => assert!(
+                    error.contains(message),
+                    "parse({:?}) error ({:?}) does not contain {:?}",
+                    contents,
+                    error,
+                    message
+                ),
+                Ok(vm_args) => panic!("parse({:?}) parsed {:?}", contents, vm_args),
+            }
+        }
+    }
+
+    #[test]
+    fn merge_overrides_flags_and_appends_env() {
+        let mut vm_args = VmArgs {
+            name: Some("first@localhost".to_string()),
+            cookie: Some("first".to_string()),
+            schedulers: Some(1),
+            max_processes: None,
+            env: vec![("A".to_string(), "1".to_string())],
+        };
+
+        vm_args.merge(VmArgs {
+            name: Some("second@localhost".to_string()),
+            cookie: None,
+            schedulers: None,
+            max_processes: Some(10),
+            env: vec![("B".to_string(), "2".to_string())],
+        });
+
+        assert_eq!(
+            vm_args,
+            VmArgs {
+                name: Some("second@localhost".to_string()),
+                cookie: Some("first".to_string()),
+                schedulers: Some(1),
+                max_processes: Some(10),
+                env: vec![
+                    ("A".to_string(), "1".to_string()),
+                    ("B".to_string(), "2".to_string()),
+                ],
+            }
+        );
+    }
+}
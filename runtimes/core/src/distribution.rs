@@ -1,2 +1,248 @@
+pub mod connection;
+pub mod control;
+pub mod epmd;
 pub mod external_term_format;
+pub mod handshake;
 pub mod nodes;
+
+use std::io;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::RuntimeException;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+use liblumen_alloc::CloneToProcess;
+
+use crate::distribution::connection::Connection;
+use crate::distribution::control::Identifier;
+use crate::distribution::handshake::{Peer, Status};
+use crate::distribution::nodes::node;
+
+/// Starts listening for connections from other nodes and registers the local node with `epmd`,
+/// so that other nodes can connect to it by name.
+///
+/// The local node must already be named with [node::set_name].
+pub fn start() -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    let port = listener.local_addr()?.port();
+    let (alive_name, _) = split_name(node::atom());
+    let registration = epmd::register(alive_name, port)?;
+    *REGISTRATION.lock() = Some(registration);
+
+    thread::Builder::new()
+        .name("distribution".to_string())
+        .spawn(move || accept(listener))?;
+
+    Ok(())
+}
+
+/// Whether the local node is registered with `epmd`, so that other nodes can connect to it
+pub fn is_alive() -> bool {
+    REGISTRATION.lock().is_some()
+}
+
+pub fn is_connected(name: Atom) -> bool {
+    connection::get(&name).is_some()
+}
+
+/// Sends an exit signal with `reason` from `process` to the process of `external_pid`.
+///
+/// The signal is dropped if the node cannot be connected, as with any other signal to a process
+/// that does not exist.
+pub fn exit2(process: &Process, external_pid: &ExternalPid, reason: Term) {
+    if let Some(connection) = connect(external_pid.arc_node().name()) {
+        connection.exit2(process.pid(), local_pid(external_pid), reason);
+    }
+}
+
+/// Links `process` to the process of `external_pid`.
+///
+/// Returns `false` if this node is not alive, so the node of `external_pid` cannot be connected,
+/// in which case `process` should get a `noconnection` exit signal, as the linked process may as
+/// well have exited.  If the connection cannot be set up later, `process` gets the exit signal
+/// then.
+pub fn link(process: &Process, external_pid: &ExternalPid) -> bool {
+    match connect(external_pid.arc_node().name()) {
+        Some(connection) => {
+            connection.link(process.pid(), local_pid(external_pid));
+
+            true
+        }
+        None => false,
+    }
+}
+
+/// Monitors the `identifier` process on the `node` from `process`.
+///
+/// If this node is not alive, so `node` cannot be connected, `process` gets a `DOWN` message
+/// with `noconnection` immediately.  If the connection cannot be set up later, `process` gets the
+/// `DOWN` message then.
+pub fn monitor(process: &Process, reference: &Reference, identifier: Identifier, name: Atom) {
+    match connect(name) {
+        Some(connection) => connection.monitor(process.pid(), reference, identifier),
+        None => {
+            let identifier_term = match identifier {
+                Identifier::Pid(pid) => ExternalPid::new(
+                    nodes::atom_to_or_insert_arc_node(name, 0),
+                    pid.number() as usize,
+                    pid.serial() as usize,
+                )
+                .unwrap()
+                .clone_to_process(process),
+                Identifier::Name(registered_name) => process
+                    .tuple_from_slice(&[registered_name.encode().unwrap(), name.encode().unwrap()]),
+            };
+            let reference_term = reference.clone_to_process(process);
+            let message = process.tuple_from_slice(&[
+                atom!("DOWN"),
+                reference_term,
+                atom!("process"),
+                identifier_term,
+                atom!("noconnection"),
+            ]);
+
+            process.send_from_self(message);
+        }
+    }
+}
+
+/// Stops monitoring a process on another node.  Returns whether `reference` was a monitor of a
+/// process on another node.
+pub fn demonitor(reference: &Reference) -> bool {
+    connection::all()
+        .iter()
+        .any(|connection| connection.demonitor(reference))
+}
+
+/// Tells the other nodes that `process` exited, so that their linked and monitoring processes
+/// get exit signals and `DOWN` messages, and that it no longer monitors their processes.
+pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
+    let reason = match exception {
+        Some(exception) => exception.reason(),
+        None => atom!("normal"),
+    };
+
+    for connection in connection::all() {
+        connection.propagate_exit(process, reason);
+    }
+}
+
+/// Sends `message` to the process registered as `name` on the `node`.
+///
+/// The message is dropped if the node cannot be connected, as with any other message to a
+/// process that does not exist.
+pub fn reg_send(process: &Process, name: Atom, node: Atom, message: Term) {
+    if let Some(connection) = connect(node) {
+        connection.reg_send(process.pid(), name, message);
+    }
+}
+
+/// Sends `message` to the process of `external_pid`.
+///
+/// The message is dropped if the node cannot be connected, as with any other message to a
+/// process that does not exist.
+pub fn send(external_pid: &ExternalPid, message: Term) {
+    if let Some(connection) = connect(external_pid.arc_node().name()) {
+        connection.send(local_pid(external_pid), message);
+    }
+}
+
+pub fn unlink(process: &Process, external_pid: &ExternalPid) {
+    if let Some(connection) = connection::get(&external_pid.arc_node().name()) {
+        connection.unlink(process.pid(), local_pid(external_pid));
+    }
+}
+
+// Private
+
+lazy_static! {
+    static ref REGISTRATION: Mutex<Option<epmd::Registration>> = Default::default();
+}
+
+fn accept(listener: TcpListener) {
+    for result in listener.incoming() {
+        if let Ok(stream) = result {
+            let _ = thread::Builder::new()
+                .name("distribution handshake".to_string())
+                .spawn(move || {
+                    if let Err(error) = accept_stream(stream) {
+                        log::warn!("Could not accept connection from node: {}", error);
+                    }
+                });
+        }
+    }
+}
+
+fn accept_stream(mut stream: TcpStream) -> io::Result<()> {
+    let peer = handshake::accept(&mut stream, |name| match connection::get(&name) {
+        // This node is connecting to the other node at the same time, so the node with the greater
+        // name keeps the connection it set up
+        Some(connection) if connection.is_connecting() => {
+            if node::atom().name() > name.name() {
+                Status::Nok
+            } else {
+                Status::OkSimultaneous
+            }
+        }
+        Some(_) => Status::Alive,
+        None => Status::Ok,
+    })?;
+
+    connection::establish(stream, peer).map(|_| ())
+}
+
+/// The connection to the `name` node, which is set up on its own thread if this node is alive and
+/// not already connected or connecting to it
+fn connect(name: Atom) -> Option<Arc<Connection>> {
+    if let Some(connection) = connection::get(&name) {
+        return Some(connection);
+    }
+
+    if !is_alive() || name == node::atom() {
+        return None;
+    }
+
+    let arc_node = nodes::atom_to_or_insert_arc_node(name, 0);
+
+    Some(connection::connect(arc_node, move || connect_stream(name)))
+}
+
+fn connect_stream(name: Atom) -> io::Result<(TcpStream, Peer)> {
+    let (alive_name, host) = split_name(name);
+    let port = epmd::port_please(host, alive_name)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("node ({}) is not registered with epmd", name),
+        )
+    })?;
+    let mut stream = TcpStream::connect((host, port))?;
+    let peer = handshake::connect(&mut stream)?;
+
+    Ok((stream, peer))
+}
+
+/// `external_pid` as the local pid it is on its own node
+fn local_pid(external_pid: &ExternalPid) -> Pid {
+    Pid::new(
+        external_pid.number() as usize,
+        external_pid.serial() as usize,
+    )
+    .unwrap()
+}
+
+/// Splits the node `name` into the alive name before the `@` and the host after it
+fn split_name(name: Atom) -> (&'static str, &'static str) {
+    let name = name.name();
+
+    match name.find('@') {
+        Some(index) => (&name[..index], &name[index + 1..]),
+        None => (name, "localhost"),
+    }
+}
@@ -0,0 +1,1149 @@
+//! Connections to other nodes over TCP.
+//!
+//! Once the handshake is done, each message is framed by a 4 byte length, with an empty message
+//! being a tick that keeps an idle connection alive.  Every connection has a thread that reads
+//! and handles the control messages from the other node and a thread that sends ticks.
+//!
+//! Connections to other nodes are set up on their own thread, so that the processes that send to
+//! them don't wait for `epmd` and the handshake.  Packets sent while the connection is set up are
+//! queued and written in order once it is.
+//!
+//! The terms in received messages are decoded into a carrier process that is never scheduled,
+//! so that they can be copied to the destination process, after which the carrier is garbage
+//! collected.
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::*;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::{Mutex, RwLock};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::term::prelude::{Pid, *};
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
+
+use crate::distribution::control::{Control, Identifier};
+use crate::distribution::external_term_format::reference_id::ReferenceId;
+use crate::distribution::external_term_format::{atom_cache_reference, encode, term, version};
+use crate::distribution::handshake::Peer;
+use crate::distribution::nodes;
+use crate::process::{self, spawn};
+use crate::registry::{self, pid_to_process};
+use crate::scheduler::Scheduled;
+
+pub struct Connection {
+    arc_node: Arc<Node>,
+    writer: Mutex<Writer>,
+    closed: AtomicBool,
+    /// The links between a local pid and a pid on the other node
+    link_set: Mutex<HashSet<(Pid, Pid)>>,
+    /// Processes on the other node monitored by local processes
+    monitoring_by_reference: Mutex<HashMap<Reference, Monitoring>>,
+    /// Local processes monitored by processes on the other node
+    monitored_vec: Mutex<Vec<Monitored>>,
+}
+
+impl Connection {
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    /// Closes the connection, so that the reader thread takes it down
+    pub fn close(&self) {
+        if !self.closed.swap(true, Ordering::SeqCst) {
+            let mut writer = self.writer.lock();
+
+            if let Writer::Connected(stream) = &*writer {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+
+            *writer = Writer::Closed;
+        }
+    }
+
+    /// Whether the connection is still being set up, in which case packets are queued
+    pub fn is_connecting(&self) -> bool {
+        match *self.writer.lock() {
+            Writer::Connecting(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn external_pid(&self, pid: Pid) -> ExternalPid {
+        ExternalPid::new(
+            self.arc_node.clone(),
+            pid.number() as usize,
+            pid.serial() as usize,
+        )
+        .unwrap()
+    }
+
+    /// Sends an exit signal with `reason` from the local `from` process to the `to` process on the
+    /// other node, as with `erlang:exit/2`
+    pub fn exit2(&self, from: Pid, to: Pid, reason: Term) {
+        self.send_control(Control::Exit2 { from, to, reason }, None);
+    }
+
+    /// Links the local `from` process to the `to` process on the other node
+    pub fn link(&self, from: Pid, to: Pid) {
+        if self.link_set.lock().insert((from, to)) {
+            self.send_control(Control::Link { from, to }, None);
+        }
+    }
+
+    /// Monitors the `identifier` process on the other node from the local `monitoring_pid`
+    /// process
+    pub fn monitor(&self, monitoring_pid: Pid, reference: &Reference, identifier: Identifier) {
+        self.monitoring_by_reference.lock().insert(
+            *reference,
+            Monitoring {
+                monitoring_pid,
+                identifier,
+            },
+        );
+
+        self.send_control(
+            Control::MonitorP {
+                from: monitoring_pid,
+                to_proc: identifier,
+                reference: reference.into(),
+            },
+            None,
+        );
+    }
+
+    /// Stops monitoring the process on the other node.  Returns whether `reference` was a monitor
+    /// of a process on the other node.
+    pub fn demonitor(&self, reference: &Reference) -> bool {
+        let option_monitoring = self.monitoring_by_reference.lock().remove(reference);
+
+        match option_monitoring {
+            Some(Monitoring {
+                monitoring_pid,
+                identifier,
+            }) => {
+                self.send_control(
+                    Control::DemonitorP {
+                        from: monitoring_pid,
+                        to_proc: identifier,
+                        reference: reference.into(),
+                    },
+                    None,
+                );
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sends the exit of the local `process` to the processes on the other node that are linked
+    /// to or monitor it
+    pub fn propagate_exit(&self, process: &Process, reason: Term) {
+        let pid = process.pid();
+
+        let linked_vec: Vec<Pid> = {
+            let mut link_set = self.link_set.lock();
+            let linked_vec = link_set
+                .iter()
+                .filter(|(local, _)| *local == pid)
+                .map(|(_, remote)| *remote)
+                .collect();
+            link_set.retain(|(local, _)| *local != pid);
+
+            linked_vec
+        };
+
+        for to in linked_vec {
+            self.send_control(
+                Control::Exit {
+                    from: pid,
+                    to,
+                    reason,
+                },
+                None,
+            );
+        }
+
+        let monitored_vec: Vec<Monitored> = {
+            let mut monitored_vec = self.monitored_vec.lock();
+            let (exited_vec, alive_vec) = monitored_vec
+                .drain(..)
+                .partition(|monitored| monitored.monitored_pid == pid);
+            *monitored_vec = alive_vec;
+
+            exited_vec
+        };
+
+        for monitored in monitored_vec {
+            self.send_control(
+                Control::MonitorPExit {
+                    from_proc: monitored.identifier,
+                    to: monitored.monitoring_pid,
+                    reference: monitored.reference,
+                    reason,
+                },
+                None,
+            );
+        }
+
+        let monitoring_vec: Vec<(Reference, Monitoring)> = {
+            let mut monitoring_by_reference = self.monitoring_by_reference.lock();
+            let references: Vec<Reference> = monitoring_by_reference
+                .iter()
+                .filter(|(_, monitoring)| monitoring.monitoring_pid == pid)
+                .map(|(reference, _)| *reference)
+                .collect();
+
+            references
+                .into_iter()
+                .filter_map(|reference| {
+                    monitoring_by_reference
+                        .remove(&reference)
+                        .map(|monitoring| (reference, monitoring))
+                })
+                .collect()
+        };
+
+        for (reference, monitoring) in monitoring_vec {
+            self.send_control(
+                Control::DemonitorP {
+                    from: pid,
+                    to_proc: monitoring.identifier,
+                    reference: (&reference).into(),
+                },
+                None,
+            );
+        }
+    }
+
+    /// Sends `message` to the `to` process on the other node
+    pub fn send(&self, to: Pid, message: Term) {
+        self.send_control(Control::Send { to }, Some(message));
+    }
+
+    /// Sends `message` to the process registered as `to_name` on the other node
+    pub fn reg_send(&self, from: Pid, to_name: Atom, message: Term) {
+        self.send_control(Control::RegSend { from, to_name }, Some(message));
+    }
+
+    /// Unlinks the local `from` process from the `to` process on the other node
+    pub fn unlink(&self, from: Pid, to: Pid) {
+        if self.link_set.lock().remove(&(from, to)) {
+            self.send_control(Control::Unlink { from, to }, None);
+        }
+    }
+
+    // Private
+
+    fn new(arc_node: Arc<Node>) -> Self {
+        Self {
+            arc_node,
+            writer: Mutex::new(Writer::Connecting(Vec::new())),
+            closed: AtomicBool::new(false),
+            link_set: Default::default(),
+            monitoring_by_reference: Default::default(),
+            monitored_vec: Default::default(),
+        }
+    }
+
+    /// Writes the packets queued while connecting to `stream`, which packets are then written to
+    /// directly.
+    ///
+    /// Fails if the connection was closed or is already connected with another stream, such as
+    /// the stream of a simultaneous connection from the other node.
+    fn connected(&self, mut stream: TcpStream) -> io::Result<()> {
+        let mut writer = self.writer.lock();
+
+        match std::mem::replace(&mut *writer, Writer::Closed) {
+            Writer::Connecting(queue) => {
+                for framed in queue {
+                    stream.write_all(&framed)?;
+                }
+
+                *writer = Writer::Connected(stream);
+
+                Ok(())
+            }
+            other => {
+                *writer = other;
+
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "connection is already connected or closed",
+                ))
+            }
+        }
+    }
+
+    fn deliver(&self, destination_arc_process: &Process, message: Term) {
+        destination_arc_process.send_from_other(message);
+        destination_arc_process
+            .scheduler()
+            .unwrap()
+            .stop_waiting(destination_arc_process);
+    }
+
+    /// Handles the control message and message in `packet`, which starts with a distribution
+    /// header
+    fn handle_packet(
+        &self,
+        carrier: &Process,
+        atom_cache: &mut AtomCache,
+        packet: &[u8],
+    ) -> InternalResult<()> {
+        match packet {
+            // Pass through, in which the control message and message each have a version
+            [version::NUMBER, PASS_THROUGH, after_pass_through_bytes @ ..] => {
+                let after_version_bytes = version::check(after_pass_through_bytes)?;
+                let (option_control, after_control_bytes) =
+                    Control::decode(carrier, after_version_bytes)?;
+
+                match option_control {
+                    Some(control) => self.handle_control(carrier, control, |carrier| {
+                        let after_version_bytes = version::check(after_control_bytes)?;
+
+                        term::decode_tagged(carrier, false, after_version_bytes)
+                            .map(|(message, _)| message)
+                    }),
+                    None => Ok(()),
+                }
+            }
+            [version::NUMBER, DIST_HEADER, after_dist_header_bytes @ ..] => {
+                let (atoms, after_atom_cache_bytes) =
+                    atom_cache.decode_references(after_dist_header_bytes)?;
+
+                atom_cache_reference::with_atoms(atoms, || {
+                    let (option_control, after_control_bytes) =
+                        Control::decode(carrier, after_atom_cache_bytes)?;
+
+                    match option_control {
+                        Some(control) => self.handle_control(carrier, control, |carrier| {
+                            term::decode_tagged(carrier, false, after_control_bytes)
+                                .map(|(message, _)| message)
+                        }),
+                        None => Ok(()),
+                    }
+                })
+            }
+            _ => Err(anyhow!("packet does not start with a distribution header").into()),
+        }
+    }
+
+    /// `message` decodes the message that follows the control message, so that it is only
+    /// decoded when there is a process to receive it
+    fn handle_control<M>(
+        &self,
+        carrier: &Process,
+        control: Control,
+        message: M,
+    ) -> InternalResult<()>
+    where
+        M: FnOnce(&Process) -> InternalResult<Term>,
+    {
+        match control {
+            Control::Link { from, to } => match pid_to_process(&to) {
+                Some(_) => {
+                    self.link_set.lock().insert((to, from));
+                }
+                None => self.send_control(
+                    Control::Exit {
+                        from: to,
+                        to: from,
+                        reason: atom!("noproc"),
+                    },
+                    None,
+                ),
+            },
+            Control::Send { to } => {
+                if let Some(destination_arc_process) = pid_to_process(&to) {
+                    let message = message(carrier)?;
+                    self.deliver(&destination_arc_process, message);
+                }
+            }
+            Control::Exit { from, to, reason } => {
+                if self.link_set.lock().remove(&(to, from)) {
+                    if let Some(arc_process) = pid_to_process(&to) {
+                        let from_term = self.external_pid(from).clone_to_process(carrier);
+                        exit_signal(carrier, &arc_process, from_term, reason);
+                    }
+                }
+            }
+            Control::Unlink { from, to } => {
+                self.link_set.lock().remove(&(to, from));
+            }
+            Control::RegSend { to_name, .. } => {
+                if let Some(destination_arc_process) = registry::atom_to_process(&to_name) {
+                    let message = message(carrier)?;
+                    self.deliver(&destination_arc_process, message);
+                }
+            }
+            Control::Exit2 { from, to, reason } => {
+                if let Some(arc_process) = pid_to_process(&to) {
+                    let from_term = self.external_pid(from).clone_to_process(carrier);
+
+                    process::exit_signal(carrier, from_term, &arc_process, reason);
+                }
+            }
+            Control::MonitorP {
+                from,
+                to_proc,
+                reference,
+            } => {
+                let option_arc_process = match to_proc {
+                    Identifier::Pid(pid) => pid_to_process(&pid),
+                    Identifier::Name(name) => registry::atom_to_process(&name),
+                };
+
+                match option_arc_process {
+                    Some(arc_process) => self.monitored_vec.lock().push(Monitored {
+                        monitoring_pid: from,
+                        monitored_pid: arc_process.pid(),
+                        identifier: to_proc,
+                        reference,
+                    }),
+                    None => self.send_control(
+                        Control::MonitorPExit {
+                            from_proc: to_proc,
+                            to: from,
+                            reference,
+                            reason: atom!("noproc"),
+                        },
+                        None,
+                    ),
+                }
+            }
+            Control::DemonitorP { reference, .. } => {
+                self.monitored_vec
+                    .lock()
+                    .retain(|monitored| monitored.reference != reference);
+            }
+            Control::MonitorPExit {
+                reference, reason, ..
+            } => {
+                let option_monitoring = reference.to_local().and_then(|reference| {
+                    self.monitoring_by_reference
+                        .lock()
+                        .remove(&reference)
+                        .map(|monitoring| (reference, monitoring))
+                });
+
+                if let Some((reference, monitoring)) = option_monitoring {
+                    self.down(carrier, &reference, &monitoring, reason);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends `{'DOWN', Reference, process, Identifier, Reason}` to the monitoring process
+    fn down(
+        &self,
+        carrier: &Process,
+        reference: &Reference,
+        monitoring: &Monitoring,
+        reason: Term,
+    ) {
+        if let Some(monitoring_arc_process) = pid_to_process(&monitoring.monitoring_pid) {
+            let identifier = match monitoring.identifier {
+                Identifier::Pid(pid) => self.external_pid(pid).clone_to_process(carrier),
+                Identifier::Name(name) => carrier.tuple_from_slice(&[
+                    name.encode().unwrap(),
+                    self.arc_node.name().encode().unwrap(),
+                ]),
+            };
+            let down = carrier.tuple_from_slice(&[
+                atom!("DOWN"),
+                reference.clone_to_process(carrier),
+                atom!("process"),
+                identifier,
+                reason,
+            ]);
+
+            self.deliver(&monitoring_arc_process, down);
+        }
+    }
+
+    fn read_packet(&self, stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+        loop {
+            let mut len = [0; 4];
+            stream.read_exact(&mut len)?;
+
+            match u32::from_be_bytes(len) as usize {
+                // Tick
+                0 => continue,
+                len => {
+                    let mut packet = vec![0; len];
+                    stream.read_exact(&mut packet)?;
+
+                    return Ok(packet);
+                }
+            }
+        }
+    }
+
+    /// Reads from the other node until the connection is closed and then takes it down
+    fn run(self: Arc<Self>, mut stream: TcpStream) {
+        let carrier = carrier();
+        let mut atom_cache = AtomCache::new();
+
+        if stream.set_read_timeout(Some(NET_TICKTIME)).is_ok() {
+            while let Ok(packet) = self.read_packet(&mut stream) {
+                if let Err(error) = self.handle_packet(&carrier, &mut atom_cache, &packet) {
+                    log::error!(
+                        "Closing connection to node ({}): {:?}",
+                        self.arc_node.name(),
+                        error
+                    );
+                    break;
+                }
+
+                let _ = carrier.garbage_collect(0, &mut [][..]);
+            }
+        }
+
+        self.take_down(&carrier);
+    }
+
+    /// Sets up the connection with `setup`, taking it down if that fails, so that linked and
+    /// monitoring processes get `noconnection`
+    fn set_up<F>(self: Arc<Self>, setup: F)
+    where
+        F: FnOnce() -> io::Result<(TcpStream, Peer)>,
+    {
+        if let Err(error) = setup().and_then(|(stream, _)| self.start(stream)) {
+            if error.kind() == io::ErrorKind::ConnectionAborted {
+                // The other node is connecting to this node at the same time and that connection
+                // wins, so [establish] starts this connection with its stream instead
+                let deadline = Instant::now() + CONNECT_TIMEOUT;
+
+                while self.is_connecting() && Instant::now() < deadline {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            } else {
+                log::warn!(
+                    "Could not connect to node ({}): {}",
+                    self.arc_node.name(),
+                    error
+                );
+            }
+
+            self.take_down_unless_connected();
+        }
+    }
+
+    /// Starts reading from and ticking over `stream`
+    fn start(self: &Arc<Self>, stream: TcpStream) -> io::Result<()> {
+        let name = self.arc_node.name();
+        let reader_stream = stream.try_clone()?;
+        self.connected(stream)?;
+
+        let reader_arc_connection = self.clone();
+        let ticker_arc_connection = self.clone();
+
+        thread::Builder::new()
+            .name(format!("{} reader", name))
+            .spawn(move || reader_arc_connection.run(reader_stream))
+            .and_then(|_| {
+                thread::Builder::new()
+                    .name(format!("{} ticker", name))
+                    .spawn(move || ticker_arc_connection.tick())
+            })
+            .map(|_| ())
+            .map_err(|error| {
+                self.close();
+
+                error
+            })
+    }
+
+    /// Removes the connection and tells the processes linked to or monitoring processes on the
+    /// other node that it is gone
+    fn take_down(self: &Arc<Self>, carrier: &Process) {
+        self.close();
+        remove(self);
+
+        let noconnection = atom!("noconnection");
+
+        let link_vec: Vec<(Pid, Pid)> = self.link_set.lock().drain().collect();
+
+        for (local, remote) in link_vec {
+            if let Some(arc_process) = pid_to_process(&local) {
+                let from_term = self.external_pid(remote).clone_to_process(carrier);
+                exit_signal(carrier, &arc_process, from_term, noconnection);
+            }
+        }
+
+        let monitoring_vec: Vec<(Reference, Monitoring)> =
+            self.monitoring_by_reference.lock().drain().collect();
+
+        for (reference, monitoring) in monitoring_vec {
+            self.down(carrier, &reference, &monitoring, noconnection);
+        }
+
+        self.monitored_vec.lock().clear();
+    }
+
+    /// Takes down the connection if starting it failed, but not if it was started with the
+    /// stream of a simultaneous connection from the other node in the meantime
+    fn take_down_unless_connected(self: &Arc<Self>) {
+        let connected = match *self.writer.lock() {
+            Writer::Connected(_) => true,
+            _ => false,
+        };
+
+        if !connected {
+            self.take_down(&carrier());
+        }
+    }
+
+    /// Sends ticks until the connection is closed, so that the other node knows this node is
+    /// alive even when there is nothing else to send
+    fn tick(self: Arc<Self>) {
+        while !self.closed.load(Ordering::SeqCst) {
+            thread::sleep(TICK_INTERVAL);
+
+            if self.write(&[]).is_err() {
+                self.close();
+            }
+        }
+    }
+
+    fn send_control(&self, control: Control, option_message: Option<Term>) {
+        let mut byte_vec = vec![version::NUMBER, DIST_HEADER, 0];
+        let result =
+            control
+                .append(&mut byte_vec, &self.arc_node)
+                .and_then(|_| match option_message {
+                    Some(message) => encode::append_term(&mut byte_vec, message),
+                    None => Ok(()),
+                });
+
+        match result {
+            Ok(()) => {
+                if self.write(&byte_vec).is_err() {
+                    self.close();
+                }
+            }
+            // Dropped, as a message to a process that does not exist would be
+            Err(error) => log::warn!(
+                "Could not send to node ({}): {:?}",
+                self.arc_node.name(),
+                error
+            ),
+        }
+    }
+
+    fn write(&self, packet: &[u8]) -> io::Result<()> {
+        let mut framed = (packet.len() as u32).to_be_bytes().to_vec();
+        framed.extend_from_slice(packet);
+
+        match &mut *self.writer.lock() {
+            Writer::Connecting(queue) => {
+                queue.push(framed);
+
+                Ok(())
+            }
+            Writer::Connected(stream) => stream.write_all(&framed),
+            Writer::Closed => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "connection is closed",
+            )),
+        }
+    }
+}
+
+/// The connection to the `arc_node`, which is set up with `setup` on its own thread if this node
+/// is not already connected or connecting to it.
+///
+/// The connection can be used immediately, as packets are queued until it is set up.  If it
+/// cannot be set up, the processes linked to or monitoring processes on `arc_node` get
+/// `noconnection`.
+pub fn connect<F>(arc_node: Arc<Node>, setup: F) -> Arc<Connection>
+where
+    F: FnOnce() -> io::Result<(TcpStream, Peer)> + Send + 'static,
+{
+    let name = arc_node.name();
+
+    let arc_connection = {
+        let mut connection_by_name = RW_LOCK_CONNECTION_BY_NAME.write();
+
+        if let Some(arc_connection) = connection_by_name.get(&name) {
+            return arc_connection.clone();
+        }
+
+        let arc_connection = Arc::new(Connection::new(arc_node));
+        connection_by_name.insert(name, arc_connection.clone());
+
+        arc_connection
+    };
+
+    let setup_arc_connection = arc_connection.clone();
+
+    if let Err(error) = thread::Builder::new()
+        .name(format!("{} setup", name))
+        .spawn(move || setup_arc_connection.set_up(setup))
+    {
+        log::warn!("Could not connect to node ({}): {}", name, error);
+        arc_connection.take_down(&carrier());
+    }
+
+    arc_connection
+}
+
+/// Starts using `stream` to the `peer` once the handshake is done.
+///
+/// If this node is also connecting to the `peer`, but lets the connection from the `peer` win,
+/// the connection being set up is started with `stream`, so that its queued packets are sent.
+pub fn establish(stream: TcpStream, peer: Peer) -> io::Result<Arc<Connection>> {
+    let arc_connection = {
+        let mut connection_by_name = RW_LOCK_CONNECTION_BY_NAME.write();
+
+        match connection_by_name.get(&peer.name) {
+            Some(connecting) if connecting.is_connecting() => connecting.clone(),
+            _ => {
+                let arc_node = nodes::atom_to_or_insert_arc_node(peer.name, peer.creation);
+                let arc_connection = Arc::new(Connection::new(arc_node));
+
+                if let Some(replaced_arc_connection) =
+                    connection_by_name.insert(peer.name, arc_connection.clone())
+                {
+                    replaced_arc_connection.close();
+                }
+
+                arc_connection
+            }
+        }
+    };
+
+    match arc_connection.start(stream) {
+        Ok(()) => Ok(arc_connection),
+        Err(error) => {
+            arc_connection.take_down_unless_connected();
+
+            Err(error)
+        }
+    }
+}
+
+pub fn get(name: &Atom) -> Option<Arc<Connection>> {
+    RW_LOCK_CONNECTION_BY_NAME.read().get(name).cloned()
+}
+
+pub fn all() -> Vec<Arc<Connection>> {
+    RW_LOCK_CONNECTION_BY_NAME
+        .read()
+        .values()
+        .cloned()
+        .collect()
+}
+
+// Private
+
+const DIST_HEADER: u8 = 68;
+const PASS_THROUGH: u8 = 112;
+
+/// How long the other node can be silent before it is considered down
+const NET_TICKTIME: Duration = Duration::from_secs(60);
+const TICK_INTERVAL: Duration = Duration::from_secs(15);
+/// How long to wait for a simultaneous connection from the other node to replace the one being
+/// set up
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(7);
+
+lazy_static! {
+    static ref RW_LOCK_CONNECTION_BY_NAME: RwLock<HashMap<Atom, Arc<Connection>>> =
+        Default::default();
+}
+
+/// The atoms that the other node has put in its atom cache for this connection with
+/// `NewCacheEntryFlag` in distribution headers
+struct AtomCache {
+    atoms: Vec<Option<Atom>>,
+}
+
+impl AtomCache {
+    /// 8 segments of 256 entries
+    const LEN: usize = 2048;
+
+    fn new() -> Self {
+        Self {
+            atoms: vec![None; Self::LEN],
+        }
+    }
+
+    /// Decodes the atom cache references of a distribution header, which follow
+    /// `NumberOfAtomCacheRefs` as `Flags` and then an `AtomCacheRefs` for each reference.
+    fn decode_references<'a>(&mut self, bytes: &'a [u8]) -> InternalResult<(Vec<Atom>, &'a [u8])> {
+        let (len, after_len_bytes) = split_first(bytes)?;
+        let len = *len as usize;
+
+        if len == 0 {
+            return Ok((Vec::new(), after_len_bytes));
+        }
+
+        let flags_len = len / 2 + 1;
+
+        if after_len_bytes.len() < flags_len {
+            return Err(anyhow!("distribution header flags are truncated").into());
+        }
+
+        let (flags, mut remaining_bytes) = after_len_bytes.split_at(flags_len);
+        // The half byte after the flags of the references has `LongAtoms` in its lowest bit
+        let long_atoms = half_byte(flags, len) & 0x1 == 0x1;
+        let mut atoms = Vec::with_capacity(len);
+
+        for index in 0..len {
+            let reference_flags = half_byte(flags, index);
+            let new_cache_entry = reference_flags & 0x8 == 0x8;
+            let segment_index = (reference_flags & 0x7) as usize;
+
+            let (internal_segment_index, after_internal_segment_index_bytes) =
+                split_first(remaining_bytes)?;
+            let cache_index = segment_index * 256 + (*internal_segment_index as usize);
+            remaining_bytes = after_internal_segment_index_bytes;
+
+            let atom = if new_cache_entry {
+                let (name_len, after_len_bytes) = if long_atoms {
+                    let (high, after_high_bytes) = split_first(remaining_bytes)?;
+                    let (low, after_low_bytes) = split_first(after_high_bytes)?;
+
+                    (u16::from_be_bytes([*high, *low]) as usize, after_low_bytes)
+                } else {
+                    let (len, after_len_bytes) = split_first(remaining_bytes)?;
+
+                    (*len as usize, after_len_bytes)
+                };
+
+                if after_len_bytes.len() < name_len {
+                    return Err(anyhow!("atom cache entry text is truncated").into());
+                }
+
+                let (name_bytes, after_name_bytes) = after_len_bytes.split_at(name_len);
+                let name = std::str::from_utf8(name_bytes).context("atom text is not UTF-8")?;
+                let atom = Atom::try_from_str(name)?;
+                remaining_bytes = after_name_bytes;
+
+                self.atoms[cache_index] = Some(atom);
+
+                atom
+            } else {
+                self.atoms[cache_index]
+                    .ok_or_else(|| anyhow!("atom cache entry ({}) was never set", cache_index))?
+            };
+
+            atoms.push(atom);
+        }
+
+        Ok((atoms, remaining_bytes))
+    }
+}
+
+/// Where packets are written
+enum Writer {
+    /// The connection is being set up, so packets are queued with their frames
+    Connecting(Vec<Vec<u8>>),
+    Connected(TcpStream),
+    Closed,
+}
+
+struct Monitored {
+    /// The process on the other node
+    monitoring_pid: Pid,
+    monitored_pid: Pid,
+    /// How the monitoring process identified the monitored process
+    identifier: Identifier,
+    reference: ReferenceId,
+}
+
+struct Monitoring {
+    monitoring_pid: Pid,
+    /// The process on the other node
+    identifier: Identifier,
+}
+
+/// A process that is never scheduled, so that terms can be decoded into it before they are copied
+/// to their destination processes
+fn carrier() -> Process {
+    spawn::Options::default()
+        .spawn(
+            None,
+            Atom::from_str("erlang"),
+            Atom::from_str("dist_carrier"),
+            0,
+        )
+        .unwrap()
+}
+
+/// Flags for even references are in the low half of the byte and flags for odd references are
+/// in the high half
+fn half_byte(flags: &[u8], index: usize) -> u8 {
+    let byte = flags[index / 2];
+
+    if index % 2 == 0 {
+        byte & 0xF
+    } else {
+        byte >> 4
+    }
+}
+
+/// Kills `process` with `reason`, even if it traps exits
+fn exit(process: &Process, reason: Term) {
+    let (heap_fragment_reason, mut heap_fragment) = reason.clone_to_fragment().unwrap();
+
+    process.attach_fragment(unsafe { heap_fragment.as_mut() });
+    process.exit(heap_fragment_reason, Trace::capture(), None);
+    process.scheduler().unwrap().stop_waiting(process);
+}
+
+/// Sends an exit signal from the `from` process on the other node to `process`, which receives
+/// it as `{'EXIT', From, Reason}` if it traps exits
+fn exit_signal(carrier: &Process, process: &Process, from: Term, reason: Term) {
+    if process.traps_exit() {
+        let carrier_message = carrier.tuple_from_slice(&[atom!("EXIT"), from, reason]);
+        process.send_from_other(carrier_message);
+        process.scheduler().unwrap().stop_waiting(process);
+    } else if reason != atom!("normal") {
+        exit(process, reason);
+    }
+}
+
+fn remove(connection: &Arc<Connection>) {
+    let mut connection_by_name = RW_LOCK_CONNECTION_BY_NAME.write();
+    let name = connection.arc_node.name();
+
+    let is_current = connection_by_name
+        .get(&name)
+        .map(|current| Arc::ptr_eq(current, connection))
+        .unwrap_or(false);
+
+    if is_current {
+        connection_by_name.remove(&name);
+    }
+}
+
+fn split_first(bytes: &[u8]) -> InternalResult<(&u8, &[u8])> {
+    bytes
+        .split_first()
+        .ok_or_else(|| anyhow!("distribution header is truncated").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+
+    use crate::distribution::nodes::node;
+
+    mod atom_cache {
+        use super::*;
+
+        #[test]
+        fn decode_references_without_references_returns_rest() {
+            let mut atom_cache = AtomCache::new();
+
+            let (atoms, rest) = atom_cache.decode_references(&[0, 104]).unwrap();
+
+            assert!(atoms.is_empty());
+            assert_eq!(rest, &[104]);
+        }
+
+        #[test]
+        fn decode_references_caches_new_entries_for_later_references() {
+            let mut atom_cache = AtomCache::new();
+            // 2 new entries: `foo` at segment 0, index 5 and `bar` at segment 1, index 2
+            let mut bytes = vec![2, 0x98, 0x00, 5, 3];
+            bytes.extend_from_slice(b"foo");
+            bytes.extend_from_slice(&[2, 3]);
+            bytes.extend_from_slice(b"bar");
+            bytes.push(104);
+
+            let (atoms, rest) = atom_cache.decode_references(&bytes).unwrap();
+
+            assert_eq!(atoms, vec![Atom::from_str("foo"), Atom::from_str("bar")]);
+            assert_eq!(rest, &[104]);
+
+            // 2 old entries: segment 1, index 2 and segment 0, index 5
+            let (atoms, rest) = atom_cache
+                .decode_references(&[2, 0x01, 0x00, 2, 5, 104])
+                .unwrap();
+
+            assert_eq!(atoms, vec![Atom::from_str("bar"), Atom::from_str("foo")]);
+            assert_eq!(rest, &[104]);
+        }
+
+        #[test]
+        fn decode_references_with_long_atoms_has_2_byte_lengths() {
+            let mut atom_cache = AtomCache::new();
+            // 1 new entry at segment 7, index 255 with `LongAtoms` set in the following half byte
+            let mut bytes = vec![1, 0x1F, 255, 0, 4];
+            bytes.extend_from_slice(b"long");
+
+            let (atoms, rest) = atom_cache.decode_references(&bytes).unwrap();
+
+            assert_eq!(atoms, vec![Atom::from_str("long")]);
+            assert!(rest.is_empty());
+            assert_eq!(
+                atom_cache.atoms[7 * 256 + 255],
+                Some(Atom::from_str("long"))
+            );
+        }
+
+        #[test]
+        fn decode_references_errors_on_entry_that_was_never_set() {
+            let mut atom_cache = AtomCache::new();
+
+            assert!(atom_cache.decode_references(&[1, 0x00, 3]).is_err());
+        }
+
+        #[test]
+        fn decode_references_errors_on_truncated_header() {
+            let mut atom_cache = AtomCache::new();
+
+            for bytes in &[&[2, 0x98][..], &[1, 0x08, 0, 3, b'f'][..], &[1, 0x08][..]] {
+                assert!(
+                    atom_cache.decode_references(bytes).is_err(),
+                    "{:?} is not truncated",
+                    bytes
+                );
+            }
+        }
+    }
+
+    mod loopback {
+        use super::*;
+
+        use std::convert::TryInto;
+
+        #[test]
+        fn link_to_missing_process_is_answered_with_noproc_exit() {
+            let (local, mut local_reader, remote, mut remote_reader) = connected_pair();
+            let carrier = carrier();
+            let from = Pid::new(1, 0).unwrap();
+            let to = Pid::new(2, 0).unwrap();
+
+            local.link(from, to);
+
+            assert!(local.link_set.lock().contains(&(from, to)));
+
+            let link_packet = remote.read_packet(&mut remote_reader).unwrap();
+            remote
+                .handle_packet(&carrier, &mut AtomCache::new(), &link_packet)
+                .unwrap();
+
+            let exit_packet = local.read_packet(&mut local_reader).unwrap();
+            let (control, _) = Control::decode(&carrier, &exit_packet[3..]).unwrap();
+
+            assert_eq!(
+                control,
+                Some(Control::Exit {
+                    from: to,
+                    to: from,
+                    reason: atom!("noproc")
+                })
+            );
+
+            local
+                .handle_packet(&carrier, &mut AtomCache::new(), &exit_packet)
+                .unwrap();
+
+            assert!(local.link_set.lock().is_empty());
+        }
+
+        #[test]
+        fn send_is_framed_with_distribution_header_and_message() {
+            let (local, _local_reader, remote, mut remote_reader) = connected_pair();
+            let carrier = carrier();
+            let to = Pid::new(2, 0).unwrap();
+            let message = carrier.tuple_from_slice(&[atom!("hello"), atom!("world")]);
+
+            local.send(to, message);
+
+            let packet = remote.read_packet(&mut remote_reader).unwrap();
+
+            assert_eq!(&packet[..3], &[version::NUMBER, DIST_HEADER, 0]);
+
+            let (control, after_control_bytes) = Control::decode(&carrier, &packet[3..]).unwrap();
+
+            assert_eq!(control, Some(Control::Send { to }));
+
+            let (decoded, rest) =
+                term::decode_tagged(&carrier, false, after_control_bytes).unwrap();
+            let tuple: Boxed<Tuple> = decoded.try_into().unwrap();
+
+            assert_eq!(tuple.elements(), &[atom!("hello"), atom!("world")][..]);
+            assert!(rest.is_empty());
+        }
+
+        #[test]
+        fn packets_sent_while_connecting_are_written_in_order_once_connected() {
+            let (local_stream, remote_stream) = loopback();
+            let local = Arc::new(Connection::new(node::arc_node()));
+            let remote = Arc::new(Connection::new(node::arc_node()));
+            let mut remote_reader = remote_stream;
+            let carrier = carrier();
+            let from = Pid::new(1, 0).unwrap();
+            let to = Pid::new(2, 0).unwrap();
+
+            local.link(from, to);
+            local.unlink(from, to);
+
+            assert!(local.is_connecting());
+
+            local.connected(local_stream).unwrap();
+
+            assert!(!local.is_connecting());
+
+            for expected in vec![Control::Link { from, to }, Control::Unlink { from, to }] {
+                let packet = remote.read_packet(&mut remote_reader).unwrap();
+                let (control, _) = Control::decode(&carrier, &packet[3..]).unwrap();
+
+                assert_eq!(control, Some(expected));
+            }
+        }
+
+        #[test]
+        fn connected_fails_once_closed() {
+            let (local_stream, _remote_stream) = loopback();
+            let local = Connection::new(node::arc_node());
+
+            local.close();
+
+            assert!(!local.is_connecting());
+            assert!(local.connected(local_stream).is_err());
+        }
+
+        /// Connections to each other, which are not started, so that the tests read and handle
+        /// the packets, and the streams to read them from
+        fn connected_pair() -> (Arc<Connection>, TcpStream, Arc<Connection>, TcpStream) {
+            let (local_stream, remote_stream) = loopback();
+            let local = Arc::new(Connection::new(node::arc_node()));
+            let local_reader = local_stream.try_clone().unwrap();
+            local.connected(local_stream).unwrap();
+
+            let remote = Arc::new(Connection::new(node::arc_node()));
+            let remote_reader = remote_stream.try_clone().unwrap();
+            remote.connected(remote_stream).unwrap();
+
+            (local, local_reader, remote, remote_reader)
+        }
+
+        fn loopback() -> (TcpStream, TcpStream) {
+            let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+            let connecting = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+            let (accepting, _) = listener.accept().unwrap();
+
+            (connecting, accepting)
+        }
+    }
+}
@@ -0,0 +1,476 @@
+//! The control messages that nodes send each other, such as to send a message or to link
+//! processes.
+//!
+//! Pids in control messages are only the number and serial of the pid.  Control messages sent by
+//! this node have local pids in `from` fields and pids of the other node in `to` fields, while
+//! control messages received by this node have them the other way around.
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::{Pid, *};
+use liblumen_alloc::erts::{Node, Process};
+
+use crate::distribution::external_term_format::reference_id::{self, ReferenceId};
+use crate::distribution::external_term_format::{self, encode, term, Tag};
+use crate::distribution::nodes::node;
+
+/// A process either by its pid or its registered name
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Identifier {
+    Pid(Pid),
+    Name(Atom),
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum Control {
+    /// `{1, FromPid, ToPid}`
+    Link { from: Pid, to: Pid },
+    /// `{2, Unused, ToPid}` followed by the message
+    Send { to: Pid },
+    /// `{3, FromPid, ToPid, Reason}` when a linked process exits
+    Exit { from: Pid, to: Pid, reason: Term },
+    /// `{4, FromPid, ToPid}`
+    Unlink { from: Pid, to: Pid },
+    /// `{6, FromPid, Unused, ToName}` followed by the message
+    RegSend { from: Pid, to_name: Atom },
+    /// `{8, FromPid, ToPid, Reason}` as sent by `exit/2`
+    Exit2 { from: Pid, to: Pid, reason: Term },
+    /// `{19, FromPid, ToProc, Ref}`
+    MonitorP {
+        from: Pid,
+        to_proc: Identifier,
+        reference: ReferenceId,
+    },
+    /// `{20, FromPid, ToProc, Ref}`
+    DemonitorP {
+        from: Pid,
+        to_proc: Identifier,
+        reference: ReferenceId,
+    },
+    /// `{21, FromProc, ToPid, Ref, Reason}` when a monitored process exits
+    MonitorPExit {
+        from_proc: Identifier,
+        to: Pid,
+        reference: ReferenceId,
+        reason: Term,
+    },
+}
+
+impl Control {
+    /// Decodes a control message received from another node, decoding any terms in it into
+    /// `process`.
+    ///
+    /// Returns `None` for the operations that this node does not support, such as the trace token
+    /// variants, which are skipped.  The bytes after the control message are the message of
+    /// [Control::Send] and [Control::RegSend].
+    pub fn decode<'a>(
+        process: &Process,
+        bytes: &'a [u8],
+    ) -> InternalResult<(Option<Self>, &'a [u8])> {
+        let (tag, after_tag_bytes) = Tag::decode(bytes)?;
+
+        let (len, after_len_bytes) = match (tag, after_tag_bytes.split_first()) {
+            (Tag::SmallTuple, Some((len, after_len_bytes))) if 0 < *len => {
+                (*len as usize, after_len_bytes)
+            }
+            _ => return Err(anyhow!("control message is not a non-empty small tuple").into()),
+        };
+
+        let mut elements = Elements {
+            process,
+            bytes: after_len_bytes,
+            remaining: len,
+        };
+
+        let control = match elements.integer()? {
+            LINK => Some(Control::Link {
+                from: elements.pid()?,
+                to: elements.pid()?,
+            }),
+            SEND => {
+                elements.term()?;
+
+                Some(Control::Send {
+                    to: elements.pid()?,
+                })
+            }
+            EXIT => Some(Control::Exit {
+                from: elements.pid()?,
+                to: elements.pid()?,
+                reason: elements.term()?,
+            }),
+            UNLINK => Some(Control::Unlink {
+                from: elements.pid()?,
+                to: elements.pid()?,
+            }),
+            REG_SEND => {
+                let from = elements.pid()?;
+                elements.term()?;
+
+                Some(Control::RegSend {
+                    from,
+                    to_name: elements.atom()?,
+                })
+            }
+            EXIT2 => Some(Control::Exit2 {
+                from: elements.pid()?,
+                to: elements.pid()?,
+                reason: elements.term()?,
+            }),
+            MONITOR_P => Some(Control::MonitorP {
+                from: elements.pid()?,
+                to_proc: elements.identifier()?,
+                reference: elements.reference()?,
+            }),
+            DEMONITOR_P => Some(Control::DemonitorP {
+                from: elements.pid()?,
+                to_proc: elements.identifier()?,
+                reference: elements.reference()?,
+            }),
+            MONITOR_P_EXIT => Some(Control::MonitorPExit {
+                from_proc: elements.identifier()?,
+                to: elements.pid()?,
+                reference: elements.reference()?,
+                reason: elements.term()?,
+            }),
+            _ => None,
+        };
+
+        while 0 < elements.remaining {
+            elements.term()?;
+        }
+
+        Ok((control, elements.bytes))
+    }
+
+    /// Appends this control message sent to the `remote` node.
+    ///
+    /// Fails if the reason of an exit cannot be encoded.
+    pub fn append(&self, byte_vec: &mut Vec<u8>, remote: &Arc<Node>) -> anyhow::Result<()> {
+        let local = node::arc_node();
+
+        match self {
+            Control::Link { from, to } => {
+                encode::append_tuple_header(byte_vec, 3);
+                encode::append_integer(byte_vec, LINK);
+                append_pid(byte_vec, &local, *from);
+                append_pid(byte_vec, remote, *to);
+            }
+            Control::Send { to } => {
+                encode::append_tuple_header(byte_vec, 3);
+                encode::append_integer(byte_vec, SEND);
+                encode::append_atom(byte_vec, unused());
+                append_pid(byte_vec, remote, *to);
+            }
+            Control::Exit { from, to, reason } => {
+                encode::append_tuple_header(byte_vec, 4);
+                encode::append_integer(byte_vec, EXIT);
+                append_pid(byte_vec, &local, *from);
+                append_pid(byte_vec, remote, *to);
+                encode::append_term(byte_vec, *reason)?;
+            }
+            Control::Unlink { from, to } => {
+                encode::append_tuple_header(byte_vec, 3);
+                encode::append_integer(byte_vec, UNLINK);
+                append_pid(byte_vec, &local, *from);
+                append_pid(byte_vec, remote, *to);
+            }
+            Control::RegSend { from, to_name } => {
+                encode::append_tuple_header(byte_vec, 4);
+                encode::append_integer(byte_vec, REG_SEND);
+                append_pid(byte_vec, &local, *from);
+                encode::append_atom(byte_vec, unused());
+                encode::append_atom(byte_vec, *to_name);
+            }
+            Control::Exit2 { from, to, reason } => {
+                encode::append_tuple_header(byte_vec, 4);
+                encode::append_integer(byte_vec, EXIT2);
+                append_pid(byte_vec, &local, *from);
+                append_pid(byte_vec, remote, *to);
+                encode::append_term(byte_vec, *reason)?;
+            }
+            Control::MonitorP {
+                from,
+                to_proc,
+                reference,
+            } => {
+                encode::append_tuple_header(byte_vec, 4);
+                encode::append_integer(byte_vec, MONITOR_P);
+                append_pid(byte_vec, &local, *from);
+                append_identifier(byte_vec, remote, *to_proc);
+                reference.append(byte_vec);
+            }
+            Control::DemonitorP {
+                from,
+                to_proc,
+                reference,
+            } => {
+                encode::append_tuple_header(byte_vec, 4);
+                encode::append_integer(byte_vec, DEMONITOR_P);
+                append_pid(byte_vec, &local, *from);
+                append_identifier(byte_vec, remote, *to_proc);
+                reference.append(byte_vec);
+            }
+            Control::MonitorPExit {
+                from_proc,
+                to,
+                reference,
+                reason,
+            } => {
+                encode::append_tuple_header(byte_vec, 5);
+                encode::append_integer(byte_vec, MONITOR_P_EXIT);
+                append_identifier(byte_vec, &local, *from_proc);
+                append_pid(byte_vec, remote, *to);
+                reference.append(byte_vec);
+                encode::append_term(byte_vec, *reason)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Private
+
+const LINK: isize = 1;
+const SEND: isize = 2;
+const EXIT: isize = 3;
+const UNLINK: isize = 4;
+const REG_SEND: isize = 6;
+const EXIT2: isize = 8;
+const MONITOR_P: isize = 19;
+const DEMONITOR_P: isize = 20;
+const MONITOR_P_EXIT: isize = 21;
+
+/// The elements of the control message tuple that are not decoded yet
+struct Elements<'a, 'b> {
+    process: &'a Process,
+    bytes: &'b [u8],
+    remaining: usize,
+}
+
+impl<'a, 'b> Elements<'a, 'b> {
+    fn atom(&mut self) -> InternalResult<Atom> {
+        let term = self.term()?;
+        let atom = term
+            .try_into()
+            .with_context(|| format!("{} is not an atom", term))?;
+
+        Ok(atom)
+    }
+
+    fn identifier(&mut self) -> InternalResult<Identifier> {
+        let (tag, _) = Tag::decode(self.bytes)?;
+
+        match tag {
+            Tag::PID | Tag::NewPID => self.pid().map(Identifier::Pid),
+            _ => self.atom().map(Identifier::Name),
+        }
+    }
+
+    fn integer(&mut self) -> InternalResult<isize> {
+        let term = self.term()?;
+        let integer = term
+            .try_into()
+            .with_context(|| format!("{} is not an operation", term))?;
+
+        Ok(integer)
+    }
+
+    fn next(&mut self) -> InternalResult<()> {
+        if 0 < self.remaining {
+            self.remaining -= 1;
+
+            Ok(())
+        } else {
+            Err(anyhow!("control message has too few elements").into())
+        }
+    }
+
+    fn pid(&mut self) -> InternalResult<Pid> {
+        self.next()?;
+        let (pid, after_pid_bytes) = external_term_format::Pid::decode(false, self.bytes)?;
+        self.bytes = after_pid_bytes;
+
+        let (number, serial) = match pid {
+            external_term_format::Pid::Local(local_pid) => (local_pid.number(), local_pid.serial()),
+            external_term_format::Pid::External(external_pid) => {
+                (external_pid.number(), external_pid.serial())
+            }
+        };
+
+        let pid = Pid::new(number as usize, serial as usize)?;
+
+        Ok(pid)
+    }
+
+    fn reference(&mut self) -> InternalResult<ReferenceId> {
+        self.next()?;
+        let (reference, after_reference_bytes) = reference_id::decode_tagged(false, self.bytes)?;
+        self.bytes = after_reference_bytes;
+
+        Ok(reference)
+    }
+
+    fn term(&mut self) -> InternalResult<Term> {
+        self.next()?;
+        let (term, after_term_bytes) = term::decode_tagged(self.process, false, self.bytes)?;
+        self.bytes = after_term_bytes;
+
+        Ok(term)
+    }
+}
+
+fn append_identifier(byte_vec: &mut Vec<u8>, arc_node: &Arc<Node>, identifier: Identifier) {
+    match identifier {
+        Identifier::Pid(pid) => append_pid(byte_vec, arc_node, pid),
+        Identifier::Name(name) => encode::append_atom(byte_vec, name),
+    }
+}
+
+fn append_pid(byte_vec: &mut Vec<u8>, arc_node: &Arc<Node>, pid: Pid) {
+    encode::append_pid(
+        byte_vec,
+        arc_node.clone(),
+        pid.number() as u32,
+        pid.serial() as u32,
+    );
+}
+
+/// The `Unused` element of `SEND` and `REG_SEND`
+fn unused() -> Atom {
+    Atom::from_str("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liblumen_alloc::atom;
+
+    use crate::process::spawn;
+
+    #[test]
+    fn append_encodes_link_as_small_tuple_of_operation_and_pids() {
+        let mut byte_vec = Vec::new();
+        Control::Link {
+            from: Pid::new(1, 2).unwrap(),
+            to: Pid::new(3, 4).unwrap(),
+        }
+        .append(&mut byte_vec, &node::arc_node())
+        .unwrap();
+
+        let mut expected = vec![104, 3, 97, 1];
+
+        for (number, serial) in &[(1_u32, 2_u32), (3, 4)] {
+            expected.extend_from_slice(&[103, 100, 0, 13]);
+            expected.extend_from_slice(b"nonode@nohost");
+            expected.extend_from_slice(&number.to_be_bytes());
+            expected.extend_from_slice(&serial.to_be_bytes());
+            expected.push(0);
+        }
+
+        assert_eq!(byte_vec, expected);
+    }
+
+    #[test]
+    fn append_encodes_send_with_unused_atom() {
+        let mut byte_vec = Vec::new();
+        Control::Send {
+            to: Pid::new(3, 4).unwrap(),
+        }
+        .append(&mut byte_vec, &node::arc_node())
+        .unwrap();
+
+        assert_eq!(&byte_vec[..7], &[104, 3, 97, 2, 100, 0, 0]);
+    }
+
+    #[test]
+    fn decode_returns_appended_control() {
+        let process = carrier();
+        let from = Pid::new(1, 2).unwrap();
+        let to = Pid::new(3, 4).unwrap();
+        let reference = ReferenceId {
+            node: node::atom(),
+            creation: 0,
+            ids: vec![1, 2, 3],
+        };
+        let reason = atom!("normal");
+
+        let controls = vec![
+            Control::Link { from, to },
+            Control::Send { to },
+            Control::Exit { from, to, reason },
+            Control::Unlink { from, to },
+            Control::RegSend {
+                from,
+                to_name: Atom::from_str("registered"),
+            },
+            Control::Exit2 { from, to, reason },
+            Control::MonitorP {
+                from,
+                to_proc: Identifier::Pid(to),
+                reference: reference.clone(),
+            },
+            Control::DemonitorP {
+                from,
+                to_proc: Identifier::Name(Atom::from_str("registered")),
+                reference: reference.clone(),
+            },
+            Control::MonitorPExit {
+                from_proc: Identifier::Pid(from),
+                to,
+                reference: reference.clone(),
+                reason,
+            },
+        ];
+
+        for control in controls {
+            let mut byte_vec = Vec::new();
+            control.append(&mut byte_vec, &node::arc_node()).unwrap();
+            byte_vec.push(106);
+
+            let (decoded, after_control_bytes) = Control::decode(&process, &byte_vec).unwrap();
+
+            assert_eq!(decoded, Some(control));
+            assert_eq!(after_control_bytes, &[106]);
+        }
+    }
+
+    #[test]
+    fn decode_skips_unsupported_operations() {
+        let process = carrier();
+        let mut byte_vec = Vec::new();
+        // `{12, Unused, ToPid, TraceToken}` is `SEND_TT`
+        encode::append_tuple_header(&mut byte_vec, 4);
+        encode::append_integer(&mut byte_vec, 12);
+        encode::append_atom(&mut byte_vec, unused());
+        append_pid(&mut byte_vec, &node::arc_node(), Pid::new(3, 4).unwrap());
+        encode::append_atom(&mut byte_vec, Atom::from_str("token"));
+
+        let (decoded, after_control_bytes) = Control::decode(&process, &byte_vec).unwrap();
+
+        assert_eq!(decoded, None);
+        assert!(after_control_bytes.is_empty());
+    }
+
+    #[test]
+    fn decode_errors_on_too_few_elements() {
+        let process = carrier();
+        let mut byte_vec = Vec::new();
+        encode::append_tuple_header(&mut byte_vec, 2);
+        encode::append_integer(&mut byte_vec, LINK);
+        append_pid(&mut byte_vec, &node::arc_node(), Pid::new(1, 2).unwrap());
+
+        assert!(Control::decode(&process, &byte_vec).is_err());
+    }
+
+    fn carrier() -> Process {
+        spawn::Options::default()
+            .spawn(None, Atom::from_str("test"), Atom::from_str("carrier"), 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,242 @@
+//! A client of the Erlang Port Mapper Daemon (`epmd`), which maps the names of the nodes on a host
+//! to the ports their distribution listens on.
+//!
+//! When no `epmd` is running on the local host, a stand-in that supports only the requests of
+//! this client and `epmd -names` is started in this node, so that Lumen nodes on the same host can
+//! find each other without an OTP installation.
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::thread;
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+pub const PORT: u16 = 4369;
+
+/// The registration of the local node, which lasts as long as its connection to `epmd` is open
+pub struct Registration {
+    _stream: TcpStream,
+}
+
+/// Registers the local node as `alive_name`, the part of the node name before the `@`, listening
+/// for distribution on `port`.
+pub fn register(alive_name: &str, port: u16) -> io::Result<Registration> {
+    let mut stream = connect_local()?;
+
+    let mut request = vec![ALIVE2_REQ];
+    request.extend_from_slice(&port.to_be_bytes());
+    request.push(NODE_TYPE_NORMAL);
+    request.push(PROTOCOL_TCP_IPV4);
+    request.extend_from_slice(&VERSION.to_be_bytes());
+    request.extend_from_slice(&VERSION.to_be_bytes());
+    append_name(&mut request, alive_name);
+    // Extra
+    request.extend_from_slice(&0_u16.to_be_bytes());
+    write_request(&mut stream, &request)?;
+
+    let mut response = [0; 2];
+    stream.read_exact(&mut response)?;
+
+    match response {
+        [ALIVE2_X_RESP, 0] => {
+            let mut creation = [0; 4];
+            stream.read_exact(&mut creation)?;
+        }
+        [ALIVE2_RESP, 0] => {
+            let mut creation = [0; 2];
+            stream.read_exact(&mut creation)?;
+        }
+        [ALIVE2_X_RESP, _] | [ALIVE2_RESP, _] => {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("epmd refused to register node name ({})", alive_name),
+            ))
+        }
+        _ => return Err(unexpected_response(response[0])),
+    }
+
+    Ok(Registration { _stream: stream })
+}
+
+/// The distribution port of the node named `alive_name` on `host`, if it is registered
+pub fn port_please(host: &str, alive_name: &str) -> io::Result<Option<u16>> {
+    let mut stream = TcpStream::connect((host, PORT))?;
+
+    let mut request = vec![PORT_PLEASE2_REQ];
+    request.extend_from_slice(alive_name.as_bytes());
+    write_request(&mut stream, &request)?;
+
+    let mut response = [0; 2];
+    stream.read_exact(&mut response)?;
+
+    match response {
+        [PORT2_RESP, 0] => {
+            let mut port = [0; 2];
+            stream.read_exact(&mut port)?;
+
+            Ok(Some(u16::from_be_bytes(port)))
+        }
+        [PORT2_RESP, _] => Ok(None),
+        _ => Err(unexpected_response(response[0])),
+    }
+}
+
+// Private
+
+const ALIVE2_X_RESP: u8 = 118;
+const ALIVE2_REQ: u8 = 120;
+const ALIVE2_RESP: u8 = 121;
+const NAMES_REQ: u8 = 110;
+const PORT2_RESP: u8 = 119;
+const PORT_PLEASE2_REQ: u8 = 122;
+
+const NODE_TYPE_NORMAL: u8 = 77;
+const PROTOCOL_TCP_IPV4: u8 = 0;
+const VERSION: u16 = 6;
+
+lazy_static! {
+    static ref STAND_IN_PORT_BY_NAME: Mutex<HashMap<String, u16>> = Default::default();
+}
+
+fn append_name(byte_vec: &mut Vec<u8>, name: &str) {
+    byte_vec.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    byte_vec.extend_from_slice(name.as_bytes());
+}
+
+/// Connects to the `epmd` on the local host, starting the stand-in if there is none
+fn connect_local() -> io::Result<TcpStream> {
+    match TcpStream::connect((Ipv4Addr::LOCALHOST, PORT)) {
+        Ok(stream) => Ok(stream),
+        Err(error) if error.kind() == io::ErrorKind::ConnectionRefused => {
+            match TcpListener::bind((Ipv4Addr::LOCALHOST, PORT)) {
+                Ok(listener) => {
+                    thread::Builder::new()
+                        .name("epmd".to_string())
+                        .spawn(move || run_stand_in(listener))?;
+                }
+                // Another node started its stand-in first
+                Err(error) if error.kind() == io::ErrorKind::AddrInUse => (),
+                Err(error) => return Err(error),
+            }
+
+            TcpStream::connect((Ipv4Addr::LOCALHOST, PORT))
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+
+    let mut request = vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut request)?;
+
+    Ok(request)
+}
+
+fn run_stand_in(listener: TcpListener) {
+    for result in listener.incoming() {
+        if let Ok(stream) = result {
+            let _ = thread::Builder::new()
+                .name("epmd connection".to_string())
+                .spawn(move || {
+                    let _ = serve(stream);
+                });
+        }
+    }
+}
+
+/// Serves one request of a client of the stand-in
+fn serve(mut stream: TcpStream) -> io::Result<()> {
+    let request = read_request(&mut stream)?;
+
+    match request.split_first() {
+        // PortNo, NodeType, Protocol, HighestVersion, LowestVersion, and Nlen come before Name
+        Some((&ALIVE2_REQ, fields)) if fields.len() >= 10 => {
+            let port = u16::from_be_bytes([fields[0], fields[1]]);
+            let name_len = u16::from_be_bytes([fields[8], fields[9]]) as usize;
+            let name = match fields.get(10..10 + name_len) {
+                Some(name_bytes) => String::from_utf8_lossy(name_bytes).to_string(),
+                None => return Ok(()),
+            };
+
+            let registered = {
+                let mut port_by_name = STAND_IN_PORT_BY_NAME.lock();
+
+                if port_by_name.contains_key(&name) {
+                    false
+                } else {
+                    port_by_name.insert(name.clone(), port);
+
+                    true
+                }
+            };
+
+            if registered {
+                stream.write_all(&[ALIVE2_X_RESP, 0, 0, 0, 0, 1])?;
+
+                // The registration lasts until the node closes the connection
+                let mut buffer = [0; 64];
+                while let Ok(len) = stream.read(&mut buffer) {
+                    if len == 0 {
+                        break;
+                    }
+                }
+
+                STAND_IN_PORT_BY_NAME.lock().remove(&name);
+            } else {
+                stream.write_all(&[ALIVE2_X_RESP, 1, 0, 0, 0, 0])?;
+            }
+        }
+        Some((&PORT_PLEASE2_REQ, name_bytes)) => {
+            let name = String::from_utf8_lossy(name_bytes).to_string();
+            let option_port = STAND_IN_PORT_BY_NAME.lock().get(&name).copied();
+
+            match option_port {
+                Some(port) => {
+                    let mut response = vec![PORT2_RESP, 0];
+                    response.extend_from_slice(&port.to_be_bytes());
+                    response.push(NODE_TYPE_NORMAL);
+                    response.push(PROTOCOL_TCP_IPV4);
+                    response.extend_from_slice(&VERSION.to_be_bytes());
+                    response.extend_from_slice(&VERSION.to_be_bytes());
+                    append_name(&mut response, &name);
+                    // Extra
+                    response.extend_from_slice(&0_u16.to_be_bytes());
+
+                    stream.write_all(&response)?;
+                }
+                None => stream.write_all(&[PORT2_RESP, 1])?,
+            }
+        }
+        Some((&NAMES_REQ, _)) => {
+            let mut response = (PORT as u32).to_be_bytes().to_vec();
+
+            for (name, port) in STAND_IN_PORT_BY_NAME.lock().iter() {
+                response.extend_from_slice(format!("name {} at port {}\n", name, port).as_bytes());
+            }
+
+            stream.write_all(&response)?;
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+fn unexpected_response(tag: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unexpected epmd response ({})", tag),
+    )
+}
+
+fn write_request(stream: &mut TcpStream, request: &[u8]) -> io::Result<()> {
+    let mut framed = (request.len() as u16).to_be_bytes().to_vec();
+    framed.extend_from_slice(request);
+
+    stream.write_all(&framed)
+}
@@ -1,11 +1,16 @@
 mod arc_node;
 mod atom;
+pub mod atom_cache_reference;
 mod atom_utf8;
 mod big;
 mod binary;
 mod bit_binary;
+mod compressed;
+pub mod encode;
 mod export;
 mod f64;
+mod float;
+mod function;
 mod i32;
 mod integer;
 mod isize;
@@ -14,8 +19,12 @@ mod map;
 mod new_float;
 mod new_function;
 mod new_pid;
+mod new_reference;
 mod newer_reference;
 mod pid;
+mod port;
+mod reference;
+pub mod reference_id;
 mod sign;
 mod small_atom;
 mod small_atom_utf8;
@@ -87,6 +96,7 @@ impl From<DecodeError> for InternalException {
 #[repr(u8)]
 pub enum Tag {
     NewFloat = 70,
+    Compressed = 80,
     BitBinary = 77,
     AtomCacheReference = 82,
     NewPID = 88,
@@ -115,6 +125,7 @@ pub enum Tag {
     Map = 116,
     AtomUTF8 = 118,
     SmallAtomUTF8 = 119,
+    V4Port = 120,
 }
 
 impl Tag {
@@ -140,7 +151,7 @@ pub enum Pid {
 }
 
 impl Pid {
-    fn decode(safe: bool, bytes: &[u8]) -> InternalResult<(Self, &[u8])> {
+    pub fn decode(safe: bool, bytes: &[u8]) -> InternalResult<(Self, &[u8])> {
         let (tag, after_tag_bytes) = Tag::decode(bytes)?;
 
         match tag {
@@ -169,7 +180,7 @@ impl Pid {
         Ok(pid)
     }
 
-    fn clone_to_process(&self, process: &Process) -> Term {
+    pub fn clone_to_process(&self, process: &Process) -> Term {
         match self {
             Pid::Local(local_pid) => local_pid.clone().into(),
             Pid::External(external_pid) => external_pid.clone_to_process(process),
@@ -0,0 +1,46 @@
+//! `ATOM_CACHE_REF` refers to an atom by its index in the distribution header of the message
+//! being decoded, so the atoms of that header are set for the current thread while it decodes
+//! the message.
+use std::cell::RefCell;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::atom::atom_bytes_to_term_bytes;
+use super::u8;
+
+/// Calls `f` with `atoms` as the atom cache references of the current thread.
+pub fn with_atoms<F, T>(atoms: Vec<Atom>, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let previous_atoms = ATOMS.with(|cell| cell.replace(atoms));
+    let result = f();
+    ATOMS.with(|cell| cell.replace(previous_atoms));
+
+    result
+}
+
+pub fn decode_atom(bytes: &[u8]) -> InternalResult<(Atom, &[u8])> {
+    let (index, after_index_bytes) = u8::decode(bytes)?;
+    let atom = ATOMS
+        .with(|cell| cell.borrow().get(index as usize).copied())
+        .ok_or_else(|| {
+            anyhow!(
+                "atom cache reference ({}) is not in the distribution header",
+                index
+            )
+        })?;
+
+    Ok((atom, after_index_bytes))
+}
+
+pub fn decode_term(bytes: &[u8]) -> InternalResult<(Term, &[u8])> {
+    decode_atom(bytes).map(atom_bytes_to_term_bytes)
+}
+
+thread_local! {
+    static ATOMS: RefCell<Vec<Atom>> = RefCell::new(Vec::new());
+}
@@ -0,0 +1,56 @@
+//! `COMPRESSED` (80) wraps the zlib-deflated encoding of a term, as produced by
+//! `term_to_binary(Term, [compressed])`.
+use anyhow::*;
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::{term, u32};
+
+/// Decodes the compressed term, which must use all of the remaining `bytes`, as the zlib stream
+/// is not length-prefixed.  Inflating stops at the uncompressed size, so that a small stream
+/// cannot allocate more than its header claims.
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (uncompressed_size, after_uncompressed_size_bytes) = u32::decode(bytes)?;
+    let uncompressed_byte_vec = decompress_to_vec_zlib_with_limit(
+        after_uncompressed_size_bytes,
+        uncompressed_size as usize,
+    )
+    .map_err(|status| {
+        anyhow!(
+            "compressed term could not be inflated to its uncompressed size ({} bytes) ({:?})",
+            uncompressed_size,
+            status
+        )
+    })?;
+
+    if uncompressed_byte_vec.len() != (uncompressed_size as usize) {
+        return Err(anyhow!(
+            "compressed term inflated to {} bytes, but its uncompressed size is {} bytes",
+            uncompressed_byte_vec.len(),
+            uncompressed_size
+        )
+        .into());
+    }
+
+    let (term, after_term_bytes) = term::decode_tagged(process, safe, &uncompressed_byte_vec)?;
+
+    if after_term_bytes.is_empty() {
+        Ok((
+            term,
+            &after_uncompressed_size_bytes[after_uncompressed_size_bytes.len()..],
+        ))
+    } else {
+        Err(anyhow!(
+            "compressed term has {} bytes after the term",
+            after_term_bytes.len()
+        )
+        .into())
+    }
+}
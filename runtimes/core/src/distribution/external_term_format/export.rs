@@ -2,6 +2,8 @@ use std::ffi::c_void;
 use std::mem;
 use std::ptr::NonNull;
 
+use anyhow::*;
+
 use liblumen_alloc::erts::exception::InternalResult;
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::Process;
@@ -30,6 +32,15 @@ pub fn decode<'a>(
         NonNull::new_unchecked(ptr)
     });
 
+    // `safe` must not create new external function references
+    if safe && option_native.is_none() {
+        return Err(anyhow!(
+            "export ({}) does not exist and safe decoding cannot create it",
+            module_function_arity
+        )
+        .into());
+    }
+
     let closure = process.export_closure(module, function, arity, option_native);
 
     Ok((closure, after_arity_bytes))
@@ -0,0 +1,28 @@
+//! `FLOAT_EXT` (99) is the float formatted as a NUL-padded 31 byte string, as used before
+//! `NEW_FLOAT_EXT`.
+use std::str;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use crate::distribution::external_term_format::try_split_at;
+
+const LEN: usize = 31;
+
+pub fn decode<'a>(process: &Process, bytes: &'a [u8]) -> InternalResult<(Term, &'a [u8])> {
+    let (float_bytes, after_float_bytes) = try_split_at(bytes, LEN)?;
+    let string_len = float_bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(LEN);
+    let string = str::from_utf8(&float_bytes[..string_len]).context("float bytes are not UTF-8")?;
+    let f: f64 = string
+        .trim()
+        .parse()
+        .with_context(|| format!("float string ({:?}) is not a float", string))?;
+
+    Ok((process.float(f), after_float_bytes))
+}
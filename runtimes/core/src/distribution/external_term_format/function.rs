@@ -0,0 +1,74 @@
+//! `FUN_EXT` (117) is the encoding of anonymous functions before `NEW_FUN_EXT`.  It has neither
+//! the unique MD5 of the module nor the arity, so the arity is that of the native function
+//! generated for the fun, if any is found.
+use std::convert::TryInto;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr::NonNull;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::apply::find_symbol;
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::closure::{Definition, OldUnique, Unique};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+use liblumen_alloc::ModuleFunctionArity;
+
+use super::{atom, decode_vec_term, isize, u32, Pid};
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (num_free, after_num_free_bytes) = u32::decode(bytes)?;
+    let (creator, after_creator_bytes) = Pid::decode(safe, after_num_free_bytes)?;
+    let (module, after_module_bytes) = atom::decode_tagged(safe, after_creator_bytes)?;
+    let (index, after_index_bytes) = isize::decode(after_module_bytes)?;
+    let index: u32 = index
+        .try_into()
+        .with_context(|| format!("index ({}) is not a valid fun index", index))?;
+    let (old_uniq, after_old_uniq_bytes) = isize::decode(after_index_bytes)?;
+    let old_unique = old_uniq as OldUnique;
+
+    let env_len: usize = num_free as usize;
+    let (env_vec, after_vec_term_bytes) =
+        decode_vec_term(process, safe, after_old_uniq_bytes, env_len)?;
+
+    let unique: Unique = [0; 16];
+    let definition = Definition::Anonymous {
+        index: index as usize,
+        unique,
+        old_unique,
+    };
+    let function = definition.function_name();
+    let (arity, option_native) = (0..=std::u8::MAX)
+        .find_map(|arity| {
+            let module_function_arity = ModuleFunctionArity {
+                module,
+                function,
+                arity,
+            };
+
+            find_symbol(&module_function_arity).map(|dynamic_callee| unsafe {
+                let ptr = mem::transmute::<_, *mut c_void>(dynamic_callee);
+
+                (arity, Some(NonNull::new_unchecked(ptr)))
+            })
+        })
+        .unwrap_or((0, None));
+
+    let closure = process.anonymous_closure_with_env_from_slice(
+        module,
+        index,
+        old_unique,
+        unique,
+        arity,
+        option_native,
+        creator.into(),
+        &env_vec,
+    );
+
+    Ok((closure, after_vec_term_bytes))
+}
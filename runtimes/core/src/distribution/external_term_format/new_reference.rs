@@ -0,0 +1,30 @@
+use std::mem;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use crate::distribution::external_term_format::try_split_at;
+
+use super::newer_reference::{decode_ids, ids_to_term};
+use super::{arc_node, u16, u8};
+
+/// `NEW_REFERENCE_EXT` (114) is `NEWER_REFERENCE_EXT` with an 8-bit creation.
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (u32_len_u16, after_len_bytes) = u16::decode(bytes)?;
+    let len_usize = (u32_len_u16 as usize) * mem::size_of::<u32>();
+
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, after_len_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_node_bytes)?;
+
+    try_split_at(after_creation_bytes, len_usize).and_then(|(id_bytes, after_id_bytes)| {
+        let ids = decode_ids(id_bytes)?;
+
+        Ok((ids_to_term(process, arc_node, &ids), after_id_bytes))
+    })
+}
@@ -1,13 +1,15 @@
 use std::mem;
+use std::sync::Arc;
 
 use liblumen_alloc::erts::exception::InternalResult;
 use liblumen_alloc::erts::term::prelude::*;
-use liblumen_alloc::erts::Process;
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
 
 use crate::distribution::external_term_format::try_split_at;
 use crate::distribution::nodes::node;
 
-use super::{arc_node, u16, u32, u64};
+use super::{arc_node, u16, u32};
 
 pub fn decode<'a>(
     process: &Process,
@@ -22,15 +24,39 @@ pub fn decode<'a>(
     let (_creation, after_creation_bytes) = u32::decode(after_node_bytes)?;
 
     try_split_at(after_creation_bytes, len_usize).and_then(|(id_bytes, after_id_bytes)| {
-        if arc_node == node::arc_node() {
-            let (scheduler_id_u32, after_scheduler_id_bytes) = u32::decode(id_bytes)?;
-            let (number_u64, _) = u64::decode(after_scheduler_id_bytes)?;
+        let ids = decode_ids(id_bytes)?;
 
-            let reference = process.reference_from_scheduler(scheduler_id_u32.into(), number_u64);
-
-            Ok((reference, after_id_bytes))
-        } else {
-            unimplemented!()
-        }
+        Ok((ids_to_term(process, arc_node, &ids), after_id_bytes))
     })
 }
+
+pub fn decode_ids(mut bytes: &[u8]) -> InternalResult<Vec<u32>> {
+    let mut ids = Vec::with_capacity(bytes.len() / mem::size_of::<u32>());
+
+    while !bytes.is_empty() {
+        let (id, after_id_bytes) = u32::decode(bytes)?;
+        ids.push(id);
+        bytes = after_id_bytes;
+    }
+
+    Ok(ids)
+}
+
+/// The reference with the `ids` of a `REFERENCE_EXT`, `NEW_REFERENCE_EXT` or
+/// `NEWER_REFERENCE_EXT` from the `arc_node`.
+///
+/// The first id is the scheduler id and the rest are the number, most significant first, as
+/// [super::encode::append_reference] encodes local references.
+pub fn ids_to_term(process: &Process, arc_node: Arc<Node>, ids: &[u32]) -> Term {
+    let scheduler_id = ids.first().copied().unwrap_or(0);
+    let number = ids
+        .iter()
+        .skip(1)
+        .fold(0_u64, |number, id| (number << 32) | (*id as u64));
+
+    if arc_node == node::arc_node() {
+        process.reference_from_scheduler(scheduler_id.into(), number)
+    } else {
+        ExternalReference::new(arc_node, scheduler_id.into(), number).clone_to_process(process)
+    }
+}
@@ -0,0 +1,61 @@
+//! Decodes `PORT_EXT` (102), `NEW_PORT_EXT` (89) and `V4_PORT_EXT` (120), which only differ in
+//! the sizes of the id and creation.
+use std::sync::Arc;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
+
+use crate::distribution::nodes::node;
+
+use super::{arc_node, u32, u64, u8};
+
+pub fn decode_port<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    Ok((to_term(process, arc_node, id as u64), after_creation_bytes))
+}
+
+pub fn decode_new_port<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u32::decode(after_id_bytes)?;
+
+    Ok((to_term(process, arc_node, id as u64), after_creation_bytes))
+}
+
+pub fn decode_v4_port<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u64::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u32::decode(after_id_bytes)?;
+
+    Ok((to_term(process, arc_node, id), after_creation_bytes))
+}
+
+fn to_term(process: &Process, arc_node: Arc<Node>, id: u64) -> Term {
+    let port = unsafe { Port::from_raw(id as usize) };
+
+    if arc_node == node::arc_node() {
+        port.encode().unwrap()
+    } else {
+        ExternalPort::new(arc_node, port).clone_to_process(process)
+    }
+}
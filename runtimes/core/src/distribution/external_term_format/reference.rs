@@ -0,0 +1,24 @@
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::newer_reference::ids_to_term;
+use super::{arc_node, u32, u8};
+
+/// `REFERENCE_EXT` (101) is the deprecated single id reference.
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    // The id is the number of the reference, as there is no scheduler id
+    Ok((
+        ids_to_term(process, arc_node, &[0, id]),
+        after_creation_bytes,
+    ))
+}
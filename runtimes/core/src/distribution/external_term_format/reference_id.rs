@@ -0,0 +1,107 @@
+//! References created by other nodes cannot be terms yet, so the distribution protocol keeps the
+//! parts of their `NEWER_REFERENCE_EXT` to echo back to the node that created them.
+use std::backtrace::Backtrace;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::distribution::nodes::node;
+
+use super::{atom, encode, u16, u32, u8, DecodeError, Tag};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ReferenceId {
+    pub node: Atom,
+    pub creation: u32,
+    pub ids: Vec<u32>,
+}
+
+impl ReferenceId {
+    pub fn append(&self, byte_vec: &mut Vec<u8>) {
+        byte_vec.push(Tag::NewerReference.into());
+        byte_vec.extend_from_slice(&(self.ids.len() as u16).to_be_bytes());
+        encode::append_atom(byte_vec, self.node);
+        byte_vec.extend_from_slice(&self.creation.to_be_bytes());
+
+        for id in &self.ids {
+            byte_vec.extend_from_slice(&id.to_be_bytes());
+        }
+    }
+
+    /// The reference if it was created by the local node
+    pub fn to_local(&self) -> Option<Reference> {
+        match self.ids.as_slice() {
+            [scheduler_id, number_high, number_low] if self.node == node::atom() => {
+                let number = ((*number_high as u64) << 32) | (*number_low as u64);
+
+                Some(Reference::new((*scheduler_id).into(), number))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<&Reference> for ReferenceId {
+    fn from(reference: &Reference) -> Self {
+        let scheduler_id: u32 = reference.scheduler_id().into();
+        let number: u64 = reference.number().into();
+
+        Self {
+            node: node::atom(),
+            creation: 0,
+            ids: vec![scheduler_id, (number >> 32) as u32, number as u32],
+        }
+    }
+}
+
+pub fn decode_tagged(safe: bool, bytes: &[u8]) -> InternalResult<(ReferenceId, &[u8])> {
+    let (tag, after_tag_bytes) = Tag::decode(bytes)?;
+
+    match tag {
+        Tag::NewReference | Tag::NewerReference => {
+            let (len, after_len_bytes) = u16::decode(after_tag_bytes)?;
+            let (node, after_node_bytes) = atom::decode_tagged(safe, after_len_bytes)?;
+
+            let (creation, after_creation_bytes) = match tag {
+                Tag::NewReference => {
+                    u8::decode(after_node_bytes).map(|(creation, after_creation_bytes)| {
+                        (creation as u32, after_creation_bytes)
+                    })?
+                }
+                _ => u32::decode(after_node_bytes)?,
+            };
+
+            let mut ids = Vec::with_capacity(len as usize);
+            let mut remaining_bytes = after_creation_bytes;
+
+            for _ in 0..len {
+                let (id, after_id_bytes) = u32::decode(remaining_bytes)?;
+                ids.push(id);
+                remaining_bytes = after_id_bytes;
+            }
+
+            Ok((
+                ReferenceId {
+                    node,
+                    creation,
+                    ids,
+                },
+                remaining_bytes,
+            ))
+        }
+        _ => Err(DecodeError::UnexpectedTag {
+            tag,
+            backtrace: Backtrace::capture(),
+        })
+        .with_context(|| {
+            format!(
+                "Expected tags are {:?} or {:?}",
+                Tag::NewReference,
+                Tag::NewerReference
+            )
+        })
+        .map_err(|error| error.into()),
+    }
+}
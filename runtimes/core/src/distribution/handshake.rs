@@ -0,0 +1,482 @@
+//! The version 6 distribution handshake, in which each node proves to the other that it has the
+//! same magic cookie by answering a challenge with the MD5 digest of the cookie and the challenge.
+//!
+//! Handshake messages are framed by a 2 byte length.
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::distribution::nodes::node;
+
+/// What the accepting node tells the connecting node after receiving its name
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    /// There is also a connection being set up in the other direction, which the connecting node
+    /// should abandon
+    OkSimultaneous,
+    /// There is also a connection being set up in the other direction and it should be used
+    /// instead
+    Nok,
+    /// There is already a connection to the connecting node, which must confirm that it is a new
+    /// incarnation, so that the old connection is replaced
+    Alive,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::OkSimultaneous => "ok_simultaneous",
+            Status::Nok => "nok",
+            Status::Alive => "alive",
+        }
+    }
+}
+
+/// The other node of a completed handshake
+pub struct Peer {
+    pub name: Atom,
+    pub flags: u64,
+    pub creation: u32,
+}
+
+/// Hands shake as the node that connected.
+///
+/// Fails with [io::ErrorKind::ConnectionAborted] if the other node answered [Status::Nok], so
+/// that its connection in the other direction is used instead.
+pub fn connect(stream: &mut TcpStream) -> io::Result<Peer> {
+    with_timeout(stream, |stream| {
+        let mut send_name = vec![b'N'];
+        send_name.extend_from_slice(&FLAGS.to_be_bytes());
+        append_creation_and_name(&mut send_name);
+        write_packet(stream, &send_name)?;
+
+        let status = read_packet(stream)?;
+
+        match status.as_slice() {
+            b"sok" | b"sok_simultaneous" => (),
+            b"salive" => write_packet(stream, b"strue")?,
+            b"snok" => {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    "simultaneous connection",
+                ))
+            }
+            _ => {
+                return Err(invalid_data(format!(
+                    "connection not allowed ({})",
+                    String::from_utf8_lossy(&status)
+                )))
+            }
+        }
+
+        let challenge_packet = read_packet(stream)?;
+        let mut reader = Reader::new(&challenge_packet);
+        reader.tag(b'N')?;
+        let flags = reader.u64()?;
+        let challenge = reader.u32()?;
+        let creation = reader.u32()?;
+        let name = reader.name()?;
+        check_flags(flags)?;
+
+        let own_challenge = rand::random::<u32>();
+        let mut challenge_reply = vec![b'r'];
+        challenge_reply.extend_from_slice(&own_challenge.to_be_bytes());
+        challenge_reply.extend_from_slice(&digest(challenge));
+        write_packet(stream, &challenge_reply)?;
+
+        let challenge_ack = read_packet(stream)?;
+        let mut reader = Reader::new(&challenge_ack);
+        reader.tag(b'a')?;
+
+        if reader.bytes(DIGEST_LEN)? != digest(own_challenge) {
+            return Err(invalid_data(format!(
+                "node ({}) does not have the same cookie",
+                name
+            )));
+        }
+
+        Ok(Peer {
+            name,
+            flags,
+            creation,
+        })
+    })
+}
+
+/// Hands shake as the node that accepted the connection.
+///
+/// `status` decides whether to continue once the name of the connecting node is known.
+pub fn accept<F>(stream: &mut TcpStream, status: F) -> io::Result<Peer>
+where
+    F: FnOnce(Atom) -> Status,
+{
+    with_timeout(stream, |stream| {
+        let send_name = read_packet(stream)?;
+        let mut reader = Reader::new(&send_name);
+        reader.tag(b'N')?;
+        let flags = reader.u64()?;
+        let creation = reader.u32()?;
+        let name = reader.name()?;
+        check_flags(flags)?;
+
+        let status = status(name);
+        let mut status_packet = vec![b's'];
+        status_packet.extend_from_slice(status.as_str().as_bytes());
+        write_packet(stream, &status_packet)?;
+
+        match status {
+            Status::Ok | Status::OkSimultaneous => (),
+            Status::Nok => {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    "simultaneous connection",
+                ))
+            }
+            Status::Alive => {
+                if read_packet(stream)?.as_slice() != b"strue" {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "already connected",
+                    ));
+                }
+            }
+        }
+
+        let own_challenge = rand::random::<u32>();
+        let mut send_challenge = vec![b'N'];
+        send_challenge.extend_from_slice(&FLAGS.to_be_bytes());
+        send_challenge.extend_from_slice(&own_challenge.to_be_bytes());
+        append_creation_and_name(&mut send_challenge);
+        write_packet(stream, &send_challenge)?;
+
+        let challenge_reply = read_packet(stream)?;
+        let mut reader = Reader::new(&challenge_reply);
+        reader.tag(b'r')?;
+        let challenge = reader.u32()?;
+
+        if reader.bytes(DIGEST_LEN)? != digest(own_challenge) {
+            return Err(invalid_data(format!(
+                "node ({}) does not have the same cookie",
+                name
+            )));
+        }
+
+        let mut challenge_ack = vec![b'a'];
+        challenge_ack.extend_from_slice(&digest(challenge));
+        write_packet(stream, &challenge_ack)?;
+
+        Ok(Peer {
+            name,
+            flags,
+            creation,
+        })
+    })
+}
+
+// Private
+
+const DIGEST_LEN: usize = 16;
+const TIMEOUT: Duration = Duration::from_secs(7);
+
+const DFLAG_EXTENDED_REFERENCES: u64 = 0x4;
+const DFLAG_DIST_MONITOR: u64 = 0x8;
+const DFLAG_FUN_TAGS: u64 = 0x10;
+const DFLAG_NEW_FUN_TAGS: u64 = 0x80;
+const DFLAG_EXTENDED_PIDS_PORTS: u64 = 0x100;
+const DFLAG_EXPORT_PTR_TAG: u64 = 0x200;
+const DFLAG_BIT_BINARIES: u64 = 0x400;
+const DFLAG_NEW_FLOATS: u64 = 0x800;
+const DFLAG_DIST_HDR_ATOM_CACHE: u64 = 0x2000;
+const DFLAG_UTF8_ATOMS: u64 = 0x1_0000;
+const DFLAG_MAP_TAG: u64 = 0x2_0000;
+const DFLAG_BIG_CREATION: u64 = 0x4_0000;
+const DFLAG_HANDSHAKE_23: u64 = 0x100_0000;
+
+/// The flags that both nodes must have for the encoding of the control messages and terms, as
+/// this node only speaks version 6 and always uses a distribution header
+const REQUIRED_FLAGS: u64 = DFLAG_EXTENDED_REFERENCES
+    | DFLAG_EXTENDED_PIDS_PORTS
+    | DFLAG_DIST_HDR_ATOM_CACHE
+    | DFLAG_UTF8_ATOMS
+    | DFLAG_HANDSHAKE_23;
+
+const FLAGS: u64 = REQUIRED_FLAGS
+    | DFLAG_DIST_MONITOR
+    | DFLAG_FUN_TAGS
+    | DFLAG_NEW_FUN_TAGS
+    | DFLAG_EXPORT_PTR_TAG
+    | DFLAG_BIT_BINARIES
+    | DFLAG_NEW_FLOATS
+    | DFLAG_MAP_TAG
+    | DFLAG_BIG_CREATION;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if len <= self.bytes.len() {
+            let (bytes, after_bytes) = self.bytes.split_at(len);
+            self.bytes = after_bytes;
+
+            Ok(bytes)
+        } else {
+            Err(invalid_data(format!(
+                "needed {} bytes, but only {} available",
+                len,
+                self.bytes.len()
+            )))
+        }
+    }
+
+    fn name(&mut self) -> io::Result<Atom> {
+        let len = u16::from_be_bytes([self.u8()?, self.u8()?]) as usize;
+        let name_bytes = self.bytes(len)?;
+        let name = std::str::from_utf8(name_bytes)
+            .map_err(|error| invalid_data(format!("node name is not UTF-8 ({})", error)))?;
+
+        Atom::try_from_str(name).map_err(|error| invalid_data(error.to_string()))
+    }
+
+    fn tag(&mut self, expected: u8) -> io::Result<()> {
+        let tag = self.u8()?;
+
+        if tag == expected {
+            Ok(())
+        } else {
+            Err(invalid_data(format!(
+                "expected tag ({:?}), but got ({:?})",
+                expected as char, tag as char
+            )))
+        }
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let mut array = [0; 4];
+        array.copy_from_slice(self.bytes(4)?);
+
+        Ok(u32::from_be_bytes(array))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let mut array = [0; 8];
+        array.copy_from_slice(self.bytes(8)?);
+
+        Ok(u64::from_be_bytes(array))
+    }
+}
+
+fn append_creation_and_name(byte_vec: &mut Vec<u8>) {
+    let arc_node = node::arc_node();
+    let name = arc_node.name();
+    let name_bytes = name.name().as_bytes();
+
+    byte_vec.extend_from_slice(&arc_node.creation().to_be_bytes());
+    byte_vec.extend_from_slice(&(name_bytes.len() as u16).to_be_bytes());
+    byte_vec.extend_from_slice(name_bytes);
+}
+
+fn check_flags(flags: u64) -> io::Result<()> {
+    if flags & REQUIRED_FLAGS == REQUIRED_FLAGS {
+        Ok(())
+    } else {
+        Err(invalid_data(format!(
+            "flags ({:#x}) are missing required flags ({:#x})",
+            flags,
+            REQUIRED_FLAGS & !flags
+        )))
+    }
+}
+
+/// The MD5 digest of the cookie followed by the decimal `challenge`
+fn digest(challenge: u32) -> [u8; DIGEST_LEN] {
+    md5::compute(format!("{}{}", node::cookie().name(), challenge)).0
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_packet(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+
+    let mut packet = vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut packet)?;
+
+    Ok(packet)
+}
+
+fn with_timeout<F, T>(stream: &mut TcpStream, f: F) -> io::Result<T>
+where
+    F: FnOnce(&mut TcpStream) -> io::Result<T>,
+{
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let result = f(stream);
+
+    stream.set_read_timeout(None)?;
+    stream.set_write_timeout(None)?;
+
+    result
+}
+
+fn write_packet(stream: &mut TcpStream, packet: &[u8]) -> io::Result<()> {
+    let mut framed = (packet.len() as u16).to_be_bytes().to_vec();
+    framed.extend_from_slice(packet);
+
+    stream.write_all(&framed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn digest_is_md5_of_cookie_and_decimal_challenge() {
+        assert_eq!(node::cookie().name(), "nocookie");
+        // `erlang:md5("nocookie12345")`
+        assert_eq!(
+            digest(12345),
+            [
+                0x65, 0xc5, 0xe1, 0x23, 0xd1, 0x2c, 0x7b, 0x42, 0x8b, 0xd7, 0x85, 0x13, 0xda, 0xa8,
+                0x7c, 0xa1
+            ]
+        );
+    }
+
+    #[test]
+    fn connect_and_accept_complete_over_loopback() {
+        let (mut connecting, mut accepting) = loopback();
+
+        let accept_thread =
+            thread::spawn(move || accept(&mut accepting, |_| Status::Ok).map(|peer| peer.name));
+        let connected = connect(&mut connecting).map(|peer| peer.name);
+        let accepted = accept_thread.join().unwrap();
+
+        assert_eq!(connected.unwrap(), node::atom());
+        assert_eq!(accepted.unwrap(), node::atom());
+    }
+
+    #[test]
+    fn connect_is_aborted_when_accepting_node_answers_nok() {
+        let (mut connecting, mut accepting) = loopback();
+
+        let accept_thread = thread::spawn(move || {
+            accept(&mut accepting, |_| Status::Nok)
+                .map(|_| ())
+                .map_err(|error| error.kind())
+        });
+        let connected = connect(&mut connecting)
+            .map(|_| ())
+            .map_err(|error| error.kind());
+        let accepted = accept_thread.join().unwrap();
+
+        assert_eq!(connected, Err(io::ErrorKind::ConnectionAborted));
+        assert_eq!(accepted, Err(io::ErrorKind::ConnectionAborted));
+    }
+
+    #[test]
+    fn connect_answers_challenge_with_digest() {
+        let (mut connecting, mut accepting) = loopback();
+
+        let accept_thread = thread::spawn(move || {
+            read_packet(&mut accepting).unwrap();
+            write_packet(&mut accepting, b"sok").unwrap();
+
+            let mut send_challenge = vec![b'N'];
+            send_challenge.extend_from_slice(&FLAGS.to_be_bytes());
+            send_challenge.extend_from_slice(&12345_u32.to_be_bytes());
+            append_creation_and_name(&mut send_challenge);
+            write_packet(&mut accepting, &send_challenge).unwrap();
+
+            let challenge_reply = read_packet(&mut accepting).unwrap();
+            let mut reader = Reader::new(&challenge_reply);
+            reader.tag(b'r').unwrap();
+            let challenge = reader.u32().unwrap();
+            let reply_digest = reader.bytes(DIGEST_LEN).unwrap().to_vec();
+
+            let mut challenge_ack = vec![b'a'];
+            challenge_ack.extend_from_slice(&digest(challenge));
+            write_packet(&mut accepting, &challenge_ack).unwrap();
+
+            reply_digest
+        });
+
+        assert!(connect(&mut connecting).is_ok());
+        assert_eq!(accept_thread.join().unwrap(), digest(12345).to_vec());
+    }
+
+    #[test]
+    fn accept_rejects_wrong_digest() {
+        let (mut connecting, mut accepting) = loopback();
+
+        let accept_thread = thread::spawn(move || {
+            accept(&mut accepting, |_| Status::Ok)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        });
+
+        let mut send_name = vec![b'N'];
+        send_name.extend_from_slice(&FLAGS.to_be_bytes());
+        append_creation_and_name(&mut send_name);
+        write_packet(&mut connecting, &send_name).unwrap();
+        assert_eq!(read_packet(&mut connecting).unwrap(), b"sok");
+        read_packet(&mut connecting).unwrap();
+
+        let mut challenge_reply = vec![b'r'];
+        challenge_reply.extend_from_slice(&0_u32.to_be_bytes());
+        challenge_reply.extend_from_slice(&[0; DIGEST_LEN]);
+        write_packet(&mut connecting, &challenge_reply).unwrap();
+
+        let error = accept_thread.join().unwrap().unwrap_err();
+
+        assert!(error.contains("does not have the same cookie"), "{}", error);
+    }
+
+    #[test]
+    fn accept_rejects_missing_required_flags() {
+        let (mut connecting, mut accepting) = loopback();
+
+        let accept_thread = thread::spawn(move || {
+            accept(&mut accepting, |_| Status::Ok)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        });
+
+        let mut send_name = vec![b'N'];
+        send_name.extend_from_slice(&(FLAGS & !DFLAG_UTF8_ATOMS).to_be_bytes());
+        append_creation_and_name(&mut send_name);
+        write_packet(&mut connecting, &send_name).unwrap();
+
+        let error = accept_thread.join().unwrap().unwrap_err();
+
+        assert!(error.contains("missing required flags"), "{}", error);
+    }
+
+    /// The connecting and accepting ends of a TCP connection to this host
+    fn loopback() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let connecting = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (accepting, _) = listener.accept().unwrap();
+
+        (connecting, accepting)
+    }
+}
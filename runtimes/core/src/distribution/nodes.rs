@@ -1,6 +1,7 @@
 pub mod node;
 
 use std::backtrace::Backtrace;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use hashbrown::HashMap;
@@ -32,6 +33,21 @@ pub fn try_atom_to_arc_node(atom: &Atom) -> Result<Arc<Node>, NodeNotFound> {
     }
 }
 
+/// Returns the node named `name`, such as a node that just connected, inserting it with a new id
+/// if it is not known yet.
+pub fn atom_to_or_insert_arc_node(name: Atom, creation: u32) -> Arc<Node> {
+    match atom_to_arc_node(&name) {
+        Some(arc_node) => arc_node,
+        None => {
+            let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+            let arc_node = Arc::new(Node::new(id, name, creation));
+            insert(arc_node.clone());
+
+            arc_node
+        }
+    }
+}
+
 pub fn id_to_arc_node(id: &usize) -> Option<Arc<Node>> {
     RW_LOCK_ARC_NODE_BY_ID
         .read()
@@ -100,6 +116,9 @@ impl From<NodeNotFound> for RuntimeException {
     }
 }
 
+/// The local node always has id `0`
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
 lazy_static! {
     static ref RW_LOCK_ARC_NODE_BY_ID: RwLock<HashMap<usize, Arc<Node>>> = {
         let mut hash_map = HashMap::new();
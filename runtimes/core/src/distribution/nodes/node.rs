@@ -2,13 +2,19 @@ use std::sync::Arc;
 
 use lazy_static::lazy_static;
 
+use liblumen_core::locks::RwLock;
+
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::Node;
 
 pub const DEAD_ATOM_NAME: &str = "nonode@nohost";
+pub const NO_COOKIE_ATOM_NAME: &str = "nocookie";
 
 lazy_static! {
-    pub(super) static ref ARC_NODE: Arc<Node> = Arc::new(Node::new(ID, dead_atom(), CREATION));
+    static ref RW_LOCK_ARC_NODE: RwLock<Arc<Node>> =
+        RwLock::new(Arc::new(Node::new(ID, dead_atom(), CREATION)));
+    static ref RW_LOCK_COOKIE: RwLock<Atom> =
+        RwLock::new(Atom::try_from_str(NO_COOKIE_ATOM_NAME).unwrap());
 }
 
 pub fn dead_atom() -> Atom {
@@ -16,15 +22,35 @@ pub fn dead_atom() -> Atom {
 }
 
 pub fn arc_node() -> Arc<Node> {
-    ARC_NODE.clone()
+    RW_LOCK_ARC_NODE.read().clone()
 }
 
 pub fn atom() -> Atom {
-    ARC_NODE.name()
+    arc_node().name()
+}
+
+/// The magic cookie of the local node, which is `nocookie` until set by `-setcookie`
+pub fn cookie() -> Atom {
+    *RW_LOCK_COOKIE.read()
 }
 
 pub fn id() -> usize {
-    ARC_NODE.id()
+    arc_node().id()
+}
+
+/// Names the local node, such as from `-name` in `vm.args`.
+///
+/// The `id` of the local node does not change, so pids and references created before the rename
+/// are still local.
+pub fn set_name(name: Atom) {
+    let arc_node = Arc::new(Node::new(ID, name, CREATION));
+    *RW_LOCK_ARC_NODE.write() = arc_node.clone();
+
+    super::insert(arc_node);
+}
+
+pub fn set_cookie(cookie: Atom) {
+    *RW_LOCK_COOKIE.write() = cookie;
 }
 
 pub fn term() -> Term {
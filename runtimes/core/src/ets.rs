@@ -0,0 +1,740 @@
+//! Erlang Term Storage tables for `ets`
+//!
+//! Objects are copied out of the inserting process into their own heap fragments, so that they
+//! outlive the process, and are copied back onto the heap of any process that reads them.  Each
+//! table is owned by the process that created it, which is the only process that can write
+//! `protected` tables and read or write `private` tables.  When the owner exits, the table is
+//! either transferred to its heir or deleted.
+pub mod match_spec;
+mod options;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::{TryFrom, TryInto};
+use std::hash::{Hash, Hasher};
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+
+use anyhow::*;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::erts::process::alloc::TermAlloc;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::to_word_size;
+use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Process};
+
+use crate::registry::pid_to_process;
+use crate::scheduler::{Scheduled, SchedulerDependentAlloc};
+
+pub use self::match_spec::MatchSpec;
+pub use self::options::*;
+
+lazy_static! {
+    static ref TABLE_BY_REFERENCE: DashMap<Reference, Arc<Table>> = Default::default();
+    static ref TABLE_BY_NAME: DashMap<Atom, Arc<Table>> = Default::default();
+    /// Indexes tables by owner, so that an exiting process only visits the tables it owns
+    static ref TABLES_BY_OWNER: DashMap<Pid, Vec<Arc<Table>>> = Default::default();
+}
+
+/// A term copied into its own heap fragment, so that it is not tied to the heap of any process
+pub struct Owned {
+    term: Term,
+    heap_fragment: Option<NonNull<HeapFragment>>,
+}
+
+impl Owned {
+    pub fn new(term: Term) -> Self {
+        if term.is_immediate() || term.is_literal() {
+            Self {
+                term,
+                heap_fragment: None,
+            }
+        } else {
+            let (term, heap_fragment) = term.clone_to_fragment().unwrap();
+
+            Self {
+                term,
+                heap_fragment: Some(heap_fragment),
+            }
+        }
+    }
+
+    pub fn term(&self) -> Term {
+        self.term
+    }
+
+    fn size_in_words(&self) -> usize {
+        self.term.size_in_words()
+    }
+}
+
+impl Borrow<Term> for Owned {
+    fn borrow(&self) -> &Term {
+        &self.term
+    }
+}
+
+impl Drop for Owned {
+    fn drop(&mut self) {
+        if let Some(heap_fragment) = self.heap_fragment {
+            unsafe { ptr::drop_in_place(heap_fragment.as_ptr()) };
+        }
+    }
+}
+
+impl Eq for Owned {}
+
+impl Ord for Owned {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.term.cmp(&other.term)
+    }
+}
+
+impl PartialEq for Owned {
+    fn eq(&self, other: &Self) -> bool {
+        self.term.eq(&other.term)
+    }
+}
+
+impl PartialOrd for Owned {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The heap fragment is only ever accessed through the `Owned` that allocated it
+unsafe impl Send for Owned {}
+unsafe impl Sync for Owned {}
+
+pub struct Table {
+    reference: Reference,
+    name: Atom,
+    r#type: Type,
+    access: Access,
+    keypos: usize,
+    named_table: bool,
+    owner: Mutex<Pid>,
+    heir: Mutex<Option<Heir>>,
+    objects: Mutex<Objects>,
+}
+
+impl Table {
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    /// Only the owner can read `private` tables
+    pub fn can_read(&self, pid: Pid) -> bool {
+        self.access != Access::Private || self.owner() == pid
+    }
+
+    /// Only the owner can write `protected` and `private` tables
+    pub fn can_write(&self, pid: Pid) -> bool {
+        self.access == Access::Public || self.owner() == pid
+    }
+
+    /// Removes all objects with `key`
+    pub fn delete_key(&self, key: Term) {
+        match &mut *self.objects.lock() {
+            Objects::Set(map) => {
+                map.remove(ExactTerm::new(&key));
+            }
+            Objects::OrderedSet(map) => {
+                map.remove(&key);
+            }
+            Objects::Bag(map) => {
+                map.remove(ExactTerm::new(&key));
+            }
+        }
+    }
+
+    pub fn heir(&self) -> Option<Pid> {
+        self.heir.lock().as_ref().map(|heir| heir.pid)
+    }
+
+    /// Inserts `objects`, replacing any objects with the same key in `set` and `ordered_set`
+    /// tables.  Either all or none of the `objects` are inserted.
+    pub fn insert(&self, objects: &[Term]) -> anyhow::Result<()> {
+        let mut key_object_vec = Vec::with_capacity(objects.len());
+
+        for object in objects {
+            key_object_vec.push((self.key(*object)?, *object));
+        }
+
+        let mut guard = self.objects.lock();
+
+        for (key, object) in key_object_vec {
+            match &mut *guard {
+                Objects::Set(map) => {
+                    map.insert(ExactKey(Owned::new(key)), Owned::new(object));
+                }
+                Objects::OrderedSet(map) => {
+                    map.insert(Owned::new(key), Owned::new(object));
+                }
+                Objects::Bag(map) => match map.get_mut(ExactTerm::new(&key)) {
+                    Some(object_vec) => {
+                        if self.r#type == Type::DuplicateBag
+                            || !object_vec
+                                .iter()
+                                .any(|stored| exact_eq(stored.term(), object))
+                        {
+                            object_vec.push(Owned::new(object));
+                        }
+                    }
+                    None => {
+                        map.insert(ExactKey(Owned::new(key)), vec![Owned::new(object)]);
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn keypos(&self) -> usize {
+        self.keypos
+    }
+
+    /// The objects with `key` as a list on the heap of `process`
+    pub fn lookup(&self, process: &Process, key: Term) -> Term {
+        let guard = self.objects.lock();
+
+        let object_vec: Vec<Term> = match &*guard {
+            Objects::Set(map) => map
+                .get(ExactTerm::new(&key))
+                .map(|object| object.term().clone_to_process(process))
+                .into_iter()
+                .collect(),
+            Objects::OrderedSet(map) => map
+                .get(&key)
+                .map(|object| object.term().clone_to_process(process))
+                .into_iter()
+                .collect(),
+            Objects::Bag(map) => match map.get(ExactTerm::new(&key)) {
+                Some(object_vec) => object_vec
+                    .iter()
+                    .map(|object| object.term().clone_to_process(process))
+                    .collect(),
+                None => Vec::new(),
+            },
+        };
+
+        process.list_from_slice(&object_vec)
+    }
+
+    /// The number of words used by the keys and objects
+    pub fn memory(&self) -> usize {
+        let mut words = 0;
+
+        self.for_each(|key, object| {
+            words += key.size_in_words() + object.size_in_words();
+        });
+
+        words
+    }
+
+    pub fn name(&self) -> Atom {
+        self.name
+    }
+
+    pub fn named_table(&self) -> bool {
+        self.named_table
+    }
+
+    pub fn owner(&self) -> Pid {
+        *self.owner.lock()
+    }
+
+    pub fn reference(&self) -> Reference {
+        self.reference
+    }
+
+    /// The results of running `match_spec` against each object as a list on the heap of
+    /// `process`.  Objects that `match_spec` does not match are skipped.
+    pub fn select(&self, process: &Process, match_spec: &MatchSpec) -> Term {
+        let mut result_vec = Vec::new();
+
+        self.for_each(|_, object| {
+            if let Some(result) = match_spec.run(process, object.term()) {
+                result_vec.push(result);
+            }
+        });
+
+        process.list_from_slice(&result_vec)
+    }
+
+    /// The number of objects
+    pub fn size(&self) -> usize {
+        match &*self.objects.lock() {
+            Objects::Set(map) => map.len(),
+            Objects::OrderedSet(map) => map.len(),
+            Objects::Bag(map) => map.values().map(|object_vec| object_vec.len()).sum(),
+        }
+    }
+
+    /// All objects as a list on the heap of `process`
+    pub fn to_list(&self, process: &Process) -> Term {
+        let mut object_vec = Vec::new();
+
+        self.for_each(|_, object| object_vec.push(object.term().clone_to_process(process)));
+
+        process.list_from_slice(&object_vec)
+    }
+
+    /// The table identifier that is returned from `ets:new/2`: the name for a named table and
+    /// the reference otherwise
+    pub fn to_term(&self, process: &Process) -> Term {
+        if self.named_table {
+            self.name.encode().unwrap()
+        } else {
+            self.reference.clone_to_process(process)
+        }
+    }
+
+    pub fn r#type(&self) -> Type {
+        self.r#type
+    }
+
+    /// Updates the counters described by `operations` in the object with `key` and returns the
+    /// new values, as `ets:update_counter/3` does.
+    pub fn update_counter(
+        &self,
+        process: &Process,
+        key: Term,
+        operations: Term,
+    ) -> anyhow::Result<Term> {
+        let mut guard = self.objects.lock();
+
+        let object = match &mut *guard {
+            Objects::Set(map) => map.get_mut(ExactTerm::new(&key)),
+            Objects::OrderedSet(map) => map.get_mut(&key),
+            Objects::Bag(_) => {
+                return Err(anyhow!(
+                    "update_counter is not supported for {} tables",
+                    self.r#type.as_str()
+                ))
+            }
+        }
+        .ok_or_else(|| anyhow!("key ({}) is not in the table", key))?;
+
+        let tuple: Boxed<Tuple> = object.term().try_into().unwrap();
+        let mut element_vec: Vec<Term> = tuple
+            .iter()
+            .map(|element| element.clone_to_process(process))
+            .collect();
+
+        let (update_counter_vec, returns_list) = UpdateCounter::vec_from_term(operations)
+            .or_else(|_| UpdateCounter::try_from(operations).map(|update| (vec![update], false)))
+            .with_context(|| {
+                format!(
+                    "operations ({}) is not an Incr, {{Pos, Incr}}, {{Pos, Incr, Threshold, SetValue}}, or a list of them",
+                    operations
+                )
+            })?;
+
+        let mut value_vec = Vec::with_capacity(update_counter_vec.len());
+
+        for update_counter in update_counter_vec {
+            let position = update_counter.position.unwrap_or(self.keypos + 1);
+
+            if position == self.keypos {
+                return Err(anyhow!("position ({}) is the key position", position));
+            }
+
+            let index = position
+                .checked_sub(1)
+                .filter(|index| *index < element_vec.len())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "position ({}) is not in object ({}) with {} elements",
+                        position,
+                        object.term(),
+                        element_vec.len()
+                    )
+                })?;
+            let counter: Integer = element_vec[index]
+                .decode()
+                .unwrap()
+                .try_into()
+                .with_context(|| {
+                    format!(
+                        "element ({}) at position ({}) is not an integer",
+                        element_vec[index], position
+                    )
+                })?;
+
+            let value = update_counter.apply(counter);
+            let value_term = process.integer(value);
+            element_vec[index] = value_term;
+            value_vec.push(value_term);
+        }
+
+        *object = Owned::new(process.tuple_from_slice(&element_vec));
+
+        if returns_list {
+            Ok(process.list_from_slice(&value_vec))
+        } else {
+            Ok(value_vec[0])
+        }
+    }
+
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&Owned, &Owned),
+    {
+        match &*self.objects.lock() {
+            Objects::Set(map) => {
+                for (ExactKey(key), object) in map {
+                    f(key, object)
+                }
+            }
+            Objects::OrderedSet(map) => {
+                for (key, object) in map {
+                    f(key, object)
+                }
+            }
+            Objects::Bag(map) => {
+                for (ExactKey(key), object_vec) in map {
+                    for object in object_vec {
+                        f(key, object)
+                    }
+                }
+            }
+        }
+    }
+
+    fn key(&self, object: Term) -> anyhow::Result<Term> {
+        let tuple: Boxed<Tuple> = object
+            .try_into()
+            .with_context(|| format!("object ({}) is not a tuple", object))?;
+
+        if self.keypos <= tuple.len() {
+            Ok(tuple[self.keypos - 1])
+        } else {
+            Err(anyhow!(
+                "object ({}) does not have an element at keypos ({})",
+                object,
+                self.keypos
+            ))
+        }
+    }
+
+    /// Sends `{'ETS-TRANSFER', Tab, FromPid, HeirData}` to the process that now owns the table
+    fn send_transfer(&self, heir: &Process, from: Pid, data: Term) {
+        let tag = atom!("ETS-TRANSFER");
+        let from_term = from.encode().unwrap();
+        let words = Tuple::need_in_words_from_elements(&[tag, tag, from_term, data])
+            + to_word_size(Reference::layout().size());
+        let mut non_null_heap_fragment = HeapFragment::new_from_word_size(words).unwrap();
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+        let tab = if self.named_table {
+            self.name.encode().unwrap()
+        } else {
+            self.reference.clone_to_heap(heap_fragment).unwrap()
+        };
+        let data = data.clone_to_heap(heap_fragment).unwrap();
+        let message = heap_fragment
+            .tuple_from_slice(&[tag, tab, from_term, data])
+            .unwrap();
+
+        heir.send_heap_message(non_null_heap_fragment, message.into());
+        heir.scheduler().unwrap().stop_waiting(heir);
+    }
+}
+
+/// Creates a table owned by `owner`.
+///
+/// Returns `None` if the table is a `named_table` and another table already has `name`.
+pub fn new(owner: &Process, name: Atom, options: Options) -> Option<Arc<Table>> {
+    let Options {
+        r#type,
+        access,
+        keypos,
+        named_table,
+        heir,
+    } = options;
+
+    let reference_term = owner.next_reference();
+    let reference: Boxed<Reference> = reference_term.try_into().unwrap();
+    let objects = match r#type {
+        Type::Set => Objects::Set(Default::default()),
+        Type::OrderedSet => Objects::OrderedSet(Default::default()),
+        Type::Bag | Type::DuplicateBag => Objects::Bag(Default::default()),
+    };
+
+    let arc_table = Arc::new(Table {
+        reference: *reference.as_ref(),
+        name,
+        r#type,
+        access,
+        keypos,
+        named_table,
+        owner: Mutex::new(owner.pid()),
+        heir: Mutex::new(heir),
+        objects: Mutex::new(objects),
+    });
+
+    if named_table {
+        match TABLE_BY_NAME.entry(name) {
+            Entry::Occupied(_) => return None,
+            Entry::Vacant(vacant) => {
+                vacant.insert(arc_table.clone());
+            }
+        }
+    }
+
+    TABLE_BY_REFERENCE.insert(arc_table.reference, arc_table.clone());
+    TABLES_BY_OWNER
+        .entry(owner.pid())
+        .or_default()
+        .push(arc_table.clone());
+
+    Some(arc_table)
+}
+
+/// Deletes the table, so that it can no longer be found by its reference or name.
+///
+/// Returns `false` if the table was already deleted.
+pub fn delete(table: &Table) -> bool {
+    if table.named_table {
+        TABLE_BY_NAME.remove(&table.name);
+    }
+
+    let owner = table.owner();
+
+    if let Some(mut owned_table_vec) = TABLES_BY_OWNER.get_mut(&owner) {
+        owned_table_vec.retain(|owned_table| owned_table.reference != table.reference);
+    }
+
+    TABLES_BY_OWNER.remove_if(&owner, |_, owned_table_vec| owned_table_vec.is_empty());
+
+    TABLE_BY_REFERENCE.remove(&table.reference).is_some()
+}
+
+/// The table referred to by `tab`, which is either the reference returned from `ets:new/2` or the
+/// name of a `named_table`
+pub fn get(tab: Term) -> Option<Arc<Table>> {
+    match tab.decode().unwrap() {
+        TypedTerm::Atom(name) => TABLE_BY_NAME.get(&name).map(|entry| entry.value().clone()),
+        TypedTerm::Reference(reference) => TABLE_BY_REFERENCE
+            .get(reference.as_ref())
+            .map(|entry| entry.value().clone()),
+        _ => None,
+    }
+}
+
+/// The number of tables
+pub fn count() -> usize {
+    TABLE_BY_REFERENCE.len()
+}
+
+/// The number of words used by the keys and objects of all tables
+pub fn memory() -> usize {
+    TABLE_BY_REFERENCE
+        .iter()
+        .map(|entry| entry.value().memory())
+        .sum()
+}
+
+/// Transfers the tables owned by the exiting `process` to their heirs or deletes them if they
+/// have no living heir.
+pub fn propagate_exit(process: &Process) {
+    let pid = process.pid();
+    let owned_table_vec = match TABLES_BY_OWNER.remove(&pid) {
+        Some((_, owned_table_vec)) => owned_table_vec,
+        None => return,
+    };
+
+    for table in owned_table_vec {
+        let option_heir = table.heir.lock().take();
+
+        match option_heir.and_then(|heir| {
+            if heir.pid == pid {
+                None
+            } else {
+                pid_to_process(&heir.pid).map(|heir_arc_process| (heir, heir_arc_process))
+            }
+        }) {
+            Some((heir, heir_arc_process)) => {
+                *table.owner.lock() = heir.pid;
+                TABLES_BY_OWNER
+                    .entry(heir.pid)
+                    .or_default()
+                    .push(table.clone());
+                table.send_transfer(&heir_arc_process, pid, heir.data.term());
+            }
+            None => {
+                delete(&table);
+            }
+        }
+    }
+}
+
+// Private
+
+/// As in OTP, keys of `set` and `bag` tables only match if they are exactly equal, while keys of
+/// `ordered_set` tables match if they are equal in term order, so `1` and `1.0` are the same key.
+enum Objects {
+    Set(HashMap<ExactKey, Owned>),
+    OrderedSet(BTreeMap<Owned, Owned>),
+    /// `bag` and `duplicate_bag`
+    Bag(HashMap<ExactKey, Vec<Owned>>),
+}
+
+/// A key of a `set` or `bag` table, hashed with the key hash of maps, so that keys that are
+/// exactly equal hash the same whatever the representation of their binaries
+struct ExactKey(Owned);
+
+impl Borrow<ExactTerm> for ExactKey {
+    fn borrow(&self) -> &ExactTerm {
+        ExactTerm::new(&self.0.term)
+    }
+}
+
+impl Eq for ExactKey {}
+
+impl Hash for ExactKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ExactTerm::new(&self.0.term).hash(state)
+    }
+}
+
+impl PartialEq for ExactKey {
+    fn eq(&self, other: &Self) -> bool {
+        exact_eq(self.0.term, other.0.term)
+    }
+}
+
+/// A borrowed `Term` that looks up an [ExactKey]
+#[repr(transparent)]
+struct ExactTerm(Term);
+
+impl ExactTerm {
+    fn new(term: &Term) -> &Self {
+        // `ExactTerm` is a transparent wrapper of `Term`
+        unsafe { &*(term as *const Term as *const Self) }
+    }
+}
+
+impl Eq for ExactTerm {}
+
+impl Hash for ExactTerm {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(hash_key(self.0))
+    }
+}
+
+impl PartialEq for ExactTerm {
+    fn eq(&self, other: &Self) -> bool {
+        exact_eq(self.0, other.0)
+    }
+}
+
+/// `=:=`, which, unlike `ExactEq`, also tells apart integers and floats nested in lists, maps and
+/// tuples
+fn exact_eq(left: Term, right: Term) -> bool {
+    key_cmp(&left, &right) == Ordering::Equal
+}
+
+/// An operation for `ets:update_counter/3`
+struct UpdateCounter {
+    /// `None` when the operation is only `Incr`, which updates the element after the key
+    position: Option<usize>,
+    increment: Integer,
+    threshold_set_value: Option<(Integer, Integer)>,
+}
+
+impl UpdateCounter {
+    fn apply(self, counter: Integer) -> Integer {
+        let is_increment = Integer::from(0) <= self.increment;
+        let value = counter + self.increment;
+
+        match self.threshold_set_value {
+            Some((threshold, set_value))
+                if (is_increment && threshold < value) || (!is_increment && value < threshold) =>
+            {
+                set_value
+            }
+            _ => value,
+        }
+    }
+
+    /// A list of operations returns a list of the new values
+    fn vec_from_term(term: Term) -> anyhow::Result<(Vec<Self>, bool)> {
+        match term.decode().unwrap() {
+            TypedTerm::Nil => Ok((Vec::new(), true)),
+            TypedTerm::List(cons) => {
+                let mut update_counter_vec = Vec::new();
+
+                for result in cons.into_iter() {
+                    let element = result.map_err(|_| ImproperListError)?;
+                    let update_counter = Self::try_from(element)?;
+
+                    if update_counter.position.is_none() {
+                        return Err(anyhow!(
+                            "list element ({}) is not {{Pos, Incr}} or {{Pos, Incr, Threshold, SetValue}}",
+                            element
+                        ));
+                    }
+
+                    update_counter_vec.push(update_counter);
+                }
+
+                Ok((update_counter_vec, true))
+            }
+            _ => Err(TypeError.into()),
+        }
+    }
+}
+
+impl TryFrom<Term> for UpdateCounter {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Self, Self::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Tuple(tuple) => {
+                let (position, increment) = match tuple.len() {
+                    2 | 4 => {
+                        let position: usize = tuple[0].try_into().with_context(|| {
+                            format!("position ({}) is not a positive integer", tuple[0])
+                        })?;
+                        let increment = integer(tuple[1])?;
+
+                        (position, increment)
+                    }
+                    len => return Err(anyhow!("tuple ({}) has {} elements", term, len)),
+                };
+
+                let threshold_set_value = if tuple.len() == 4 {
+                    Some((integer(tuple[2])?, integer(tuple[3])?))
+                } else {
+                    None
+                };
+
+                Ok(Self {
+                    position: Some(position),
+                    increment,
+                    threshold_set_value,
+                })
+            }
+            _ => Ok(Self {
+                position: None,
+                increment: integer(term)?,
+                threshold_set_value: None,
+            }),
+        }
+    }
+}
+
+fn integer(term: Term) -> anyhow::Result<Integer> {
+    term.decode()
+        .unwrap()
+        .try_into()
+        .with_context(|| format!("{} is not an integer", term))
+}
@@ -0,0 +1,378 @@
+//! [Match specifications](http://erlang.org/doc/apps/erts/match_spec.html) as used by
+//! `ets:select/2` and the patterns of `ets:match/2`
+//!
+//! Match specifications are not compiled: the head, guards and body terms are interpreted
+//! directly against each object while the table is locked, with bound variables referring into
+//! the table until they are copied onto the heap of the calling process by the body.
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{CloneToProcess, Process};
+
+pub struct MatchSpec {
+    clause_vec: Vec<Clause>,
+}
+
+impl MatchSpec {
+    /// A match specification for `ets:match/2`, which returns the list of the bindings of each
+    /// matching object ordered by variable number
+    pub fn from_pattern(pattern: Term) -> Self {
+        Self {
+            clause_vec: vec![Clause {
+                head: pattern,
+                guard_vec: Vec::new(),
+                body_vec: vec![Atom::str_to_term("$$")],
+            }],
+        }
+    }
+
+    /// Runs the first clause whose head matches `object` and whose guards are all `true`,
+    /// returning the value of the last expression of its body on the heap of `process`.
+    ///
+    /// Returns `None` if no clause matches or the body fails.
+    pub fn run(&self, process: &Process, object: Term) -> Option<Term> {
+        for clause in &self.clause_vec {
+            let mut bindings = Bindings::new();
+
+            if match_head(clause.head, object, &mut bindings) {
+                let context = Context {
+                    process,
+                    object,
+                    bindings: &bindings,
+                };
+
+                if clause
+                    .guard_vec
+                    .iter()
+                    .all(|guard| context.evaluate(*guard) == Ok(true.into()))
+                {
+                    let mut result = Err(());
+
+                    for expression in &clause.body_vec {
+                        result = context.evaluate(*expression);
+
+                        if result.is_err() {
+                            break;
+                        }
+                    }
+
+                    return result.ok();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl TryFrom<Term> for MatchSpec {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Self, Self::Error> {
+        let mut clause_vec = Vec::new();
+
+        for clause in proper_list_to_vec(term)
+            .with_context(|| format!("match_spec ({}) is not a proper list", term))?
+        {
+            clause_vec.push(clause.try_into()?);
+        }
+
+        Ok(Self { clause_vec })
+    }
+}
+
+// Private
+
+/// Variable number to bound term
+type Bindings = BTreeMap<usize, Term>;
+
+struct Clause {
+    head: Term,
+    guard_vec: Vec<Term>,
+    body_vec: Vec<Term>,
+}
+
+impl TryFrom<Term> for Clause {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .ok()
+            .filter(|tuple: &Boxed<Tuple>| tuple.len() == 3)
+            .ok_or_else(|| anyhow!("clause ({}) is not {{Head, Guards, Body}}", term))?;
+
+        let guard_vec = proper_list_to_vec(tuple[1])
+            .with_context(|| format!("guards ({}) is not a proper list", tuple[1]))?;
+        let body_vec = proper_list_to_vec(tuple[2])
+            .ok()
+            .filter(|body_vec| !body_vec.is_empty())
+            .ok_or_else(|| anyhow!("body ({}) is not a non-empty proper list", tuple[2]))?;
+
+        Ok(Self {
+            head: tuple[0],
+            guard_vec,
+            body_vec,
+        })
+    }
+}
+
+struct Context<'a> {
+    process: &'a Process,
+    object: Term,
+    bindings: &'a Bindings,
+}
+
+impl<'a> Context<'a> {
+    fn evaluate(&self, expression: Term) -> Result<Term, ()> {
+        match expression.decode().unwrap() {
+            TypedTerm::Atom(atom) => match atom.name() {
+                "$_" => Ok(self.object.clone_to_process(self.process)),
+                "$$" => {
+                    let binding_vec: Vec<Term> = self
+                        .bindings
+                        .values()
+                        .map(|binding| binding.clone_to_process(self.process))
+                        .collect();
+
+                    Ok(self.process.list_from_slice(&binding_vec))
+                }
+                name => match variable_number(name) {
+                    Some(number) => self
+                        .bindings
+                        .get(&number)
+                        .map(|binding| binding.clone_to_process(self.process))
+                        .ok_or(()),
+                    None => Ok(expression),
+                },
+            },
+            TypedTerm::List(cons) => {
+                let mut element_vec = Vec::new();
+
+                for result in cons.into_iter() {
+                    element_vec.push(self.evaluate(result.map_err(|_| ())?)?);
+                }
+
+                Ok(self.process.list_from_slice(&element_vec))
+            }
+            TypedTerm::Tuple(tuple) => {
+                if tuple.len() == 0 {
+                    return Ok(expression);
+                }
+
+                match tuple[0].decode().unwrap() {
+                    // `{{...}}` constructs a tuple
+                    TypedTerm::Tuple(constructed) if tuple.len() == 1 => {
+                        let mut element_vec = Vec::with_capacity(constructed.len());
+
+                        for element in constructed.iter() {
+                            element_vec.push(self.evaluate(*element)?);
+                        }
+
+                        Ok(self.process.tuple_from_slice(&element_vec))
+                    }
+                    TypedTerm::Atom(function) => self.call(function.name(), &tuple[1..]),
+                    _ => Err(()),
+                }
+            }
+            _ => Ok(expression),
+        }
+    }
+
+    fn call(&self, function: &str, arguments: &[Term]) -> Result<Term, ()> {
+        match (function, arguments.len()) {
+            ("const", 1) => Ok(arguments[0]),
+            // Short-circuit, so that the second argument is not evaluated
+            ("andalso", 2) => match self.evaluate_bool(arguments[0])? {
+                true => self.evaluate_bool(arguments[1]).map(From::from),
+                false => Ok(false.into()),
+            },
+            ("orelse", 2) => match self.evaluate_bool(arguments[0])? {
+                true => Ok(true.into()),
+                false => self.evaluate_bool(arguments[1]).map(From::from),
+            },
+            ("self", 0) => Ok(self.process.pid_term()),
+            _ => {
+                let mut value_vec = Vec::with_capacity(arguments.len());
+
+                for argument in arguments {
+                    value_vec.push(self.evaluate(*argument)?);
+                }
+
+                self.apply(function, &value_vec)
+            }
+        }
+    }
+
+    fn apply(&self, function: &str, values: &[Term]) -> Result<Term, ()> {
+        let process = self.process;
+
+        match (function, values) {
+            ("is_atom", [value]) => Ok(value.is_atom().into()),
+            ("is_binary", [value]) => Ok(value.is_binary().into()),
+            ("is_float", [value]) => Ok(value.is_float().into()),
+            ("is_function", [value]) => Ok(value.is_function().into()),
+            ("is_integer", [value]) => Ok(value.is_integer().into()),
+            ("is_list", [value]) => Ok(value.is_list().into()),
+            ("is_map", [value]) => Ok(value.is_map().into()),
+            ("is_number", [value]) => Ok(value.is_number().into()),
+            ("is_pid", [value]) => Ok(value.is_pid().into()),
+            ("is_port", [value]) => Ok(value.is_port().into()),
+            ("is_reference", [value]) => Ok(value.is_reference().into()),
+            ("is_tuple", [value]) => Ok(value.is_tuple().into()),
+            ("not", [value]) => bool_from_term(*value).map(|value| (!value).into()),
+            ("and", [left, right]) => Ok((bool_from_term(*left)? & bool_from_term(*right)?).into()),
+            ("or", [left, right]) => Ok((bool_from_term(*left)? | bool_from_term(*right)?).into()),
+            ("xor", [left, right]) => Ok((bool_from_term(*left)? ^ bool_from_term(*right)?).into()),
+            ("==", [left, right]) => Ok((left == right).into()),
+            ("/=", [left, right]) => Ok((left != right).into()),
+            ("=:=", [left, right]) => Ok(exact_eq(*left, *right).into()),
+            ("=/=", [left, right]) => Ok((!exact_eq(*left, *right)).into()),
+            ("<", [left, right]) => Ok((left < right).into()),
+            ("=<", [left, right]) => Ok((left <= right).into()),
+            (">", [left, right]) => Ok((left > right).into()),
+            (">=", [left, right]) => Ok((left >= right).into()),
+            ("+", [left, right]) => Ok(process.integer(integer(*left)? + integer(*right)?)),
+            ("-", [left, right]) => Ok(process.integer(integer(*left)? - integer(*right)?)),
+            ("-", [value]) => Ok(process.integer(-integer(*value)?)),
+            ("*", [left, right]) => Ok(process.integer(integer(*left)? * integer(*right)?)),
+            ("div", [left, right]) => {
+                let divisor = non_zero_integer(*right)?;
+
+                Ok(process.integer(integer(*left)? / divisor))
+            }
+            ("rem", [left, right]) => {
+                let divisor = non_zero_integer(*right)?;
+
+                Ok(process.integer(integer(*left)? % divisor))
+            }
+            ("abs", [value]) => {
+                let integer = integer(*value)?;
+
+                if integer < Integer::from(0) {
+                    Ok(process.integer(-integer))
+                } else {
+                    Ok(*value)
+                }
+            }
+            ("element", [index, tuple]) => {
+                let index: OneBasedIndex = (*index).try_into().map_err(|_| ())?;
+                let tuple: Boxed<Tuple> = (*tuple).try_into().map_err(|_| ())?;
+
+                tuple.get_element(index).map_err(|_| ())
+            }
+            ("hd", [list]) => match list.decode().unwrap() {
+                TypedTerm::List(cons) => Ok(cons.head),
+                _ => Err(()),
+            },
+            ("tl", [list]) => match list.decode().unwrap() {
+                TypedTerm::List(cons) => Ok(cons.tail),
+                _ => Err(()),
+            },
+            ("length", [list]) => proper_list_to_vec(*list)
+                .map(|element_vec| process.integer(element_vec.len()))
+                .map_err(|_| ()),
+            ("size", [tuple]) | ("tuple_size", [tuple]) => {
+                let tuple: Boxed<Tuple> = (*tuple).try_into().map_err(|_| ())?;
+
+                Ok(process.integer(tuple.len()))
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn evaluate_bool(&self, expression: Term) -> Result<bool, ()> {
+        self.evaluate(expression).and_then(bool_from_term)
+    }
+}
+
+fn bool_from_term(term: Term) -> Result<bool, ()> {
+    term.try_into().map_err(|_| ())
+}
+
+fn exact_eq(left: Term, right: Term) -> bool {
+    left.decode().unwrap().exact_eq(&right.decode().unwrap())
+}
+
+fn integer(term: Term) -> Result<Integer, ()> {
+    term.decode().unwrap().try_into().map_err(|_| ())
+}
+
+fn non_zero_integer(term: Term) -> Result<Integer, ()> {
+    integer(term).and_then(|integer| {
+        if integer == Integer::from(0) {
+            Err(())
+        } else {
+            Ok(integer)
+        }
+    })
+}
+
+/// Matches `term` against the `pattern` of a match specification head, binding the `'$N'`
+/// variables in `bindings`.  `'_'` matches anything.
+fn match_head(pattern: Term, term: Term, bindings: &mut Bindings) -> bool {
+    match pattern.decode().unwrap() {
+        TypedTerm::Atom(atom) => match atom.name() {
+            "_" => true,
+            name => match variable_number(name) {
+                Some(number) => match bindings.get(&number) {
+                    Some(bound) => exact_eq(*bound, term),
+                    None => {
+                        bindings.insert(number, term);
+
+                        true
+                    }
+                },
+                None => exact_eq(pattern, term),
+            },
+        },
+        TypedTerm::Tuple(pattern_tuple) => match term.decode().unwrap() {
+            TypedTerm::Tuple(tuple) => {
+                pattern_tuple.len() == tuple.len()
+                    && pattern_tuple
+                        .iter()
+                        .zip(tuple.iter())
+                        .all(|(pattern_element, element)| {
+                            match_head(*pattern_element, *element, bindings)
+                        })
+            }
+            _ => false,
+        },
+        TypedTerm::List(pattern_cons) => match term.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                match_head(pattern_cons.head, cons.head, bindings)
+                    && match_head(pattern_cons.tail, cons.tail, bindings)
+            }
+            _ => false,
+        },
+        _ => exact_eq(pattern, term),
+    }
+}
+
+fn proper_list_to_vec(list: Term) -> anyhow::Result<Vec<Term>> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => {
+            let mut vec = Vec::new();
+
+            for result in cons.into_iter() {
+                vec.push(result.map_err(|_| ImproperListError)?);
+            }
+
+            Ok(vec)
+        }
+        _ => Err(TypeError.into()),
+    }
+}
+
+/// The `N` in `'$N'`
+fn variable_number(name: &str) -> Option<usize> {
+    if name.starts_with('$') {
+        name[1..].parse().ok()
+    } else {
+        None
+    }
+}
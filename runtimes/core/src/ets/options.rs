@@ -0,0 +1,193 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::Owned;
+use crate::proplist::TryPropListFromTermError;
+
+/// Options for `ets:new/2`
+pub struct Options {
+    pub r#type: Type,
+    pub access: Access,
+    /// The one-based index of the key in each object
+    pub keypos: usize,
+    /// The table can be referred to by its name instead of its reference
+    pub named_table: bool,
+    /// The process that inherits the table when the owner exits and the data it is sent
+    pub heir: Option<Heir>,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str =
+    "supported options are set, ordered_set, bag, duplicate_bag, public, protected, private, \
+     named_table, {keypos, Pos}, {heir, Pid, HeirData}, {heir, none}, {read_concurrency, boolean}, \
+     {write_concurrency, boolean}, {decentralized_counters, boolean}, or compressed";
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> core::result::Result<&Options, anyhow::Error> {
+        match option.decode().unwrap() {
+            TypedTerm::Atom(atom) => {
+                match atom.name() {
+                    "set" => self.r#type = Type::Set,
+                    "ordered_set" => self.r#type = Type::OrderedSet,
+                    "bag" => self.r#type = Type::Bag,
+                    "duplicate_bag" => self.r#type = Type::DuplicateBag,
+                    "public" => self.access = Access::Public,
+                    "protected" => self.access = Access::Protected,
+                    "private" => self.access = Access::Private,
+                    "named_table" => self.named_table = true,
+                    // Objects are always stored as-is
+                    "compressed" => (),
+                    name => {
+                        return Err(TryPropListFromTermError::AtomName(name))
+                            .context(SUPPORTED_OPTIONS_CONTEXT)
+                    }
+                }
+
+                Ok(self)
+            }
+            TypedTerm::Tuple(tuple) => {
+                let name: Atom = tuple[0]
+                    .try_into()
+                    .map_err(|_| TryPropListFromTermError::KeywordKeyType)
+                    .context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+                match (name.name(), tuple.len()) {
+                    ("keypos", 2) => {
+                        let keypos: usize = tuple[1]
+                            .try_into()
+                            .ok()
+                            .filter(|keypos| 1 <= *keypos)
+                            .ok_or_else(|| {
+                                anyhow!("keypos ({}) must be a positive integer", tuple[1])
+                            })?;
+                        self.keypos = keypos;
+
+                        Ok(self)
+                    }
+                    ("heir", 2) => match tuple[1].decode().unwrap() {
+                        TypedTerm::Atom(none) if none == "none" => {
+                            self.heir = None;
+
+                            Ok(self)
+                        }
+                        _ => Err(anyhow!(
+                            "heir ({}) must be none or {{heir, Pid, HeirData}}",
+                            tuple[1]
+                        )),
+                    },
+                    ("heir", 3) => {
+                        let pid: Pid = tuple[1]
+                            .try_into()
+                            .with_context(|| format!("heir ({}) must be a local pid", tuple[1]))?;
+                        self.heir = Some(Heir {
+                            pid,
+                            data: Owned::new(tuple[2]),
+                        });
+
+                        Ok(self)
+                    }
+                    // Tables are always safe for concurrent access, so these are only validated
+                    ("read_concurrency", 2)
+                    | ("write_concurrency", 2)
+                    | ("decentralized_counters", 2) => {
+                        let _: bool = tuple[1].try_into().with_context(|| {
+                            format!("{} ({}) must be a boolean", name, tuple[1])
+                        })?;
+
+                        Ok(self)
+                    }
+                    (name, _) => Err(TryPropListFromTermError::KeywordKeyName(name))
+                        .context(SUPPORTED_OPTIONS_CONTEXT),
+                }
+            }
+            _ => Err(TryPropListFromTermError::PropertyType).context(SUPPORTED_OPTIONS_CONTEXT),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            r#type: Type::Set,
+            access: Access::Protected,
+            keypos: 1,
+            named_table: false,
+            heir: None,
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Options, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options.put_option_term(cons.head)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError.into()),
+            }
+        }
+    }
+}
+
+/// Which processes can read and write the table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Any process can read and write
+    Public,
+    /// Any process can read, but only the owner can write
+    Protected,
+    /// Only the owner can read and write
+    Private,
+}
+
+impl Access {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Access::Public => "public",
+            Access::Protected => "protected",
+            Access::Private => "private",
+        }
+    }
+}
+
+/// The process that inherits a table when its owner exits
+pub struct Heir {
+    pub pid: Pid,
+    /// Sent to the heir in the `{'ETS-TRANSFER', Tab, FromPid, HeirData}` message
+    pub data: Owned,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// One object per key
+    Set,
+    /// One object per key, traversed in term order of the keys
+    OrderedSet,
+    /// Many objects per key, but only one copy of each object
+    Bag,
+    /// Many objects per key, including copies of the same object
+    DuplicateBag,
+}
+
+impl Type {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Type::Set => "set",
+            Type::OrderedSet => "ordered_set",
+            Type::Bag => "bag",
+            Type::DuplicateBag => "duplicate_bag",
+        }
+    }
+}
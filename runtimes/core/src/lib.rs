@@ -5,16 +5,25 @@
 #![feature(trait_alias)]
 #![feature(core_intrinsics)]
 
+pub mod application;
 pub mod binary_to_string;
+pub mod boot;
 pub mod builtins;
+pub mod config;
 pub mod context;
 pub mod distribution;
+pub mod ets;
+pub mod memory;
+pub mod port;
 pub mod process;
 pub mod proplist;
 pub mod registry;
 pub mod scheduler;
 pub mod send;
+pub mod seq_trace;
+pub mod statistics;
 pub mod sys;
 pub mod test;
 pub mod time;
 pub mod timer;
+pub mod trace;
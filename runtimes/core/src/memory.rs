@@ -0,0 +1,86 @@
+//! Memory allocated by the runtime for `erlang:memory/0,1`, in bytes.
+//!
+//! See http://erlang.org/doc/man/erlang.html#memory-0
+use std::mem;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::atom::atom_memory;
+use liblumen_alloc::erts::term::binary::proc_bin_memory;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::std_alloc;
+
+use crate::ets;
+use crate::registry;
+
+pub struct Memory {
+    pub total: usize,
+    pub processes: usize,
+    pub processes_used: usize,
+    pub system: usize,
+    pub atom: usize,
+    pub atom_used: usize,
+    pub binary: usize,
+    /// Code is compiled into the executable instead of being loaded into allocated memory, so
+    /// this is always `0`.
+    pub code: usize,
+    pub ets: usize,
+}
+
+impl Memory {
+    pub fn get() -> Self {
+        let (processes, processes_used) = processes();
+        let atom = atom_memory();
+        let binary = proc_bin_memory();
+        let ets = ets::memory() * mem::size_of::<Term>();
+        let known_system = atom + binary + ets;
+
+        // Only an instrumented allocator knows about the memory used by the runtime itself, in
+        // addition to the memory tracked by the atom table, binaries and ETS.
+        let system = match std_alloc::alloc_stats() {
+            Some(statistics) => {
+                let allocated = statistics
+                    .total_bytes_alloced()
+                    .saturating_sub(statistics.total_bytes_freed());
+
+                allocated.saturating_sub(processes).max(known_system)
+            }
+            None => known_system,
+        };
+
+        Self {
+            total: processes + system,
+            processes,
+            processes_used,
+            system,
+            atom,
+            atom_used: atom,
+            binary,
+            code: 0,
+            ets,
+        }
+    }
+}
+
+// Private
+
+/// `(allocated, used)` bytes of all processes
+fn processes() -> (usize, usize) {
+    registry::processes()
+        .iter()
+        .fold((0, 0), |(allocated, used), process| {
+            let off_heap_size =
+                process.total_heap_size() - process.heap_size() - process.old_heap_size();
+            let allocated_words = process.total_heap_size() + process.stack_used();
+            let used_words = process.heap_used() + off_heap_size + process.stack_used();
+
+            (
+                allocated + process_bytes(allocated_words),
+                used + process_bytes(used_words),
+            )
+        })
+}
+
+/// The bytes for the control block of a process and its `words` of heap and stack
+fn process_bytes(words: usize) -> usize {
+    mem::size_of::<Process>() + words * mem::size_of::<Term>()
+}
@@ -0,0 +1,547 @@
+//! Ports to executables spawned by `erlang:open_port/2`
+//!
+//! Each open port owns a spawned OS process whose standard input and output are piped.  Output
+//! from the executable is read on a dedicated thread and sent to the port's connected (owner)
+//! process as `{Port, {data, Data}}` messages.
+mod options;
+
+use std::alloc::Layout;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::erts::exception::{AllocResult, RuntimeException};
+use liblumen_alloc::erts::process::alloc::TermAlloc;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{atom, fixnum, CloneToProcess, HeapFragment, Process};
+
+use crate::distribution::nodes::node;
+use crate::registry::{self, pid_to_process};
+use crate::scheduler::Scheduled;
+
+pub use options::*;
+
+lazy_static! {
+    static ref CONTROL_BLOCK_BY_PORT: DashMap<Port, Arc<ControlBlock>> = Default::default();
+}
+
+/// The number of bytes read from the executables of all ports, including closed ports
+static TOTAL_INPUT: AtomicUsize = AtomicUsize::new(0);
+/// The number of bytes written to the executables of all ports, including closed ports
+static TOTAL_OUTPUT: AtomicUsize = AtomicUsize::new(0);
+
+/// The state of an open port
+pub struct ControlBlock {
+    port: Port,
+    name: String,
+    options: Options,
+    os_pid: u32,
+    connected: Mutex<Pid>,
+    linked_pid_set: Mutex<HashSet<Pid>>,
+    /// Processes monitoring the port with `erlang:monitor(port, _)`
+    monitoring_by_reference: Mutex<HashMap<Reference, Monitoring>>,
+    pub registered_name: Mutex<Option<Atom>>,
+    stdin: Mutex<Option<ChildStdin>>,
+    child: Mutex<Child>,
+    input: AtomicUsize,
+    output: AtomicUsize,
+}
+
+impl ControlBlock {
+    /// The connected process is the only process that receives messages from the port
+    pub fn connected(&self) -> Pid {
+        *self.connected.lock()
+    }
+
+    /// The number of bytes read from the executable
+    pub fn input(&self) -> usize {
+        self.input.load(Ordering::SeqCst)
+    }
+
+    pub fn link(&self, pid: Pid) {
+        self.linked_pid_set.lock().insert(pid);
+    }
+
+    pub fn linked_pids(&self) -> Vec<Pid> {
+        self.linked_pid_set.lock().iter().copied().collect()
+    }
+
+    /// Monitors the port from `process`, which identified the port by its registered `name`, if
+    /// any.  Returns the monitor reference.
+    pub fn monitor(&self, process: &Process, name: Option<Atom>) -> Term {
+        let reference = process.next_reference();
+        let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
+
+        self.monitoring_by_reference.lock().insert(
+            reference_reference.as_ref().clone(),
+            Monitoring {
+                monitoring_pid: process.pid(),
+                name,
+            },
+        );
+
+        reference
+    }
+
+    /// The command that was spawned
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn os_pid(&self) -> u32 {
+        self.os_pid
+    }
+
+    /// The number of bytes written to the executable
+    pub fn output(&self) -> usize {
+        self.output.load(Ordering::SeqCst)
+    }
+
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    pub fn unlink(&self, pid: Pid) {
+        self.linked_pid_set.lock().remove(&pid);
+    }
+
+    fn read_packet(&self, stdout: &mut ChildStdout) -> io::Result<Option<Vec<u8>>> {
+        match self.options.packet {
+            Some(packet) => {
+                let mut header = vec![0; packet.header_len()];
+
+                match stdout.read_exact(&mut header) {
+                    Ok(()) => {
+                        let mut data = vec![0; packet.decode_header(&header)];
+                        stdout.read_exact(&mut data)?;
+
+                        Ok(Some(data))
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }
+            None => {
+                let mut buffer = [0; 4096];
+                let len = stdout.read(&mut buffer)?;
+
+                if len == 0 {
+                    Ok(None)
+                } else {
+                    Ok(Some(buffer[..len].to_vec()))
+                }
+            }
+        }
+    }
+
+    /// Reads from the executable until it closes its standard output and then reaps it
+    fn run(self: Arc<Self>, mut stdout: ChildStdout) {
+        loop {
+            match self.read_packet(&mut stdout) {
+                Ok(Some(data)) => {
+                    self.input.fetch_add(data.len(), Ordering::SeqCst);
+                    TOTAL_INPUT.fetch_add(data.len(), Ordering::SeqCst);
+                    self.send_data(&data);
+                }
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        let status = self.child.lock().wait();
+
+        // A port closed by `port_close/1` no longer sends messages to its connected process
+        if is_open(self.port) {
+            if self.options.exit_status {
+                let code = match status {
+                    Ok(exit_status) => exit_status_code(exit_status),
+                    Err(_) => 128,
+                };
+
+                self.send_to_connected(atom!("exit_status"), ElementLayout::Immediate, |_| {
+                    Ok(fixnum!(code))
+                });
+            }
+
+            close(self.port, atom!("normal"));
+            self.send_normal_exit_to_linked();
+        }
+    }
+
+    /// Sends the exit signal `normal` from the port to the linked processes when the executable
+    /// exits.  As with any `normal` exit signal, only processes that trap exits see it, as
+    /// `{'EXIT', Port, normal}`.
+    fn send_normal_exit_to_linked(&self) {
+        for linked_pid in self.linked_pids() {
+            if let Some(linked_arc_process) = pid_to_process(&linked_pid) {
+                if linked_arc_process.traps_exit() {
+                    let mut non_null_heap_fragment =
+                        HeapFragment::new(Tuple::layout_for_len(3)).unwrap();
+                    let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+                    let message = heap_fragment
+                        .tuple_from_slice(&[
+                            atom!("EXIT"),
+                            self.port.encode().unwrap(),
+                            atom!("normal"),
+                        ])
+                        .unwrap();
+
+                    linked_arc_process.send_heap_message(non_null_heap_fragment, message.into());
+                    linked_arc_process
+                        .scheduler()
+                        .unwrap()
+                        .stop_waiting(&linked_arc_process);
+                }
+            }
+        }
+    }
+
+    fn send_data(&self, data: &[u8]) {
+        if self.options.binary {
+            self.send_to_connected(atom!("data"), ElementLayout::Binary(data), |heap| {
+                heap.binary_from_bytes(data)
+            });
+        } else {
+            self.send_to_connected(atom!("data"), ElementLayout::List(data.len()), |heap| {
+                heap.list_from_iter(data.iter().map(|byte| fixnum!(*byte)))
+                    .map(|option_cons| match option_cons {
+                        Some(cons) => cons.into(),
+                        None => Term::NIL,
+                    })
+            });
+        }
+    }
+
+    /// Sends `{Port, {tag, value}}` to the connected process
+    fn send_to_connected<F>(&self, tag: Term, value_layout: ElementLayout, value: F)
+    where
+        F: FnOnce(&mut HeapFragment) -> AllocResult<Term>,
+    {
+        if let Some(connected_arc_process) = pid_to_process(&self.connected()) {
+            let layout = value_layout
+                .layout()
+                .extend(Tuple::layout_for_len(2))
+                .unwrap()
+                .0
+                .extend(Tuple::layout_for_len(2))
+                .unwrap()
+                .0;
+            let mut non_null_heap_fragment = HeapFragment::new(layout).unwrap();
+            let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+            // The value is allocated first, so that it is released if the heap fragment is
+            // dropped
+            let value_term = value(heap_fragment).unwrap();
+            let event = heap_fragment.tuple_from_slice(&[tag, value_term]).unwrap();
+            let message = heap_fragment
+                .tuple_from_slice(&[self.port.encode().unwrap(), event.into()])
+                .unwrap();
+
+            connected_arc_process.send_heap_message(non_null_heap_fragment, message.into());
+            connected_arc_process
+                .scheduler()
+                .unwrap()
+                .stop_waiting(&connected_arc_process);
+        }
+    }
+}
+
+/// Spawns the executable at `path` and opens a port to it that is connected to `owner`
+pub fn open(owner: &Process, path: String, options: Options) -> io::Result<Port> {
+    let mut child = Command::new(&path)
+        .args(&options.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take().unwrap();
+    let os_pid = child.id();
+
+    let port = Port::next();
+    let mut linked_pid_set = HashSet::new();
+    linked_pid_set.insert(owner.pid());
+
+    let arc_control_block = Arc::new(ControlBlock {
+        port,
+        name: path,
+        options,
+        os_pid,
+        connected: Mutex::new(owner.pid()),
+        linked_pid_set: Mutex::new(linked_pid_set),
+        monitoring_by_reference: Default::default(),
+        registered_name: Mutex::new(None),
+        stdin: Mutex::new(stdin),
+        child: Mutex::new(child),
+        input: AtomicUsize::new(0),
+        output: AtomicUsize::new(0),
+    });
+
+    CONTROL_BLOCK_BY_PORT.insert(port, arc_control_block.clone());
+
+    let reader_control_block = arc_control_block.clone();
+    let spawn_result = thread::Builder::new()
+        .name(port.to_string())
+        .spawn(move || reader_control_block.run(stdout));
+
+    if let Err(err) = spawn_result {
+        close(port, atom!("normal"));
+        let _ = arc_control_block.child.lock().kill();
+
+        return Err(err);
+    }
+
+    Ok(port)
+}
+
+/// Closes the port, so that the executable sees its standard input closed, and sends
+/// `{'DOWN', MonitorReference, port, Port, Reason}` to the monitoring processes.
+///
+/// Returns `false` if the port was not open.
+pub fn close(port: Port, reason: Term) -> bool {
+    match CONTROL_BLOCK_BY_PORT.remove(&port) {
+        Some((_, arc_control_block)) => {
+            arc_control_block.stdin.lock().take();
+
+            let option_registered_name = arc_control_block.registered_name.lock().take();
+
+            if let Some(name) = option_registered_name {
+                registry::unregister(&name);
+            }
+
+            let monitoring_vec: Vec<(Reference, Monitoring)> = arc_control_block
+                .monitoring_by_reference
+                .lock()
+                .drain()
+                .collect();
+
+            for (reference, monitoring) in monitoring_vec {
+                if let Some(monitoring_arc_process) = pid_to_process(&monitoring.monitoring_pid) {
+                    send_down_message(
+                        &monitoring_arc_process,
+                        &reference,
+                        port,
+                        monitoring.name,
+                        reason,
+                    );
+                }
+            }
+
+            true
+        }
+        None => false,
+    }
+}
+
+/// Writes `data` to the executable's standard input, framed as a packet if the port was opened
+/// with `{packet, N}`.
+pub fn command(port: Port, data: &[u8]) -> anyhow::Result<()> {
+    let arc_control_block =
+        get(port).ok_or_else(|| anyhow::anyhow!("port ({}) is not open", port))?;
+
+    let mut framed = match arc_control_block.options.packet {
+        Some(packet) => {
+            if packet.max_len() < data.len() {
+                return Err(anyhow::anyhow!(
+                    "data ({} bytes) is too long for {} byte packet header",
+                    data.len(),
+                    packet.header_len()
+                ));
+            }
+
+            packet.encode_header(data.len())
+        }
+        None => Vec::with_capacity(data.len()),
+    };
+    framed.extend_from_slice(data);
+
+    let mut stdin_guard = arc_control_block.stdin.lock();
+    let stdin = stdin_guard
+        .as_mut()
+        .ok_or_else(|| anyhow::anyhow!("port ({}) is closed", port))?;
+    stdin.write_all(&framed)?;
+    stdin.flush()?;
+
+    arc_control_block
+        .output
+        .fetch_add(data.len(), Ordering::SeqCst);
+    TOTAL_OUTPUT.fetch_add(data.len(), Ordering::SeqCst);
+
+    Ok(())
+}
+
+/// Returns whether `reference` was a port monitor of `monitoring_pid`.  Monitors of other
+/// processes are left in place.
+pub fn demonitor(monitoring_pid: Pid, reference: &Reference) -> bool {
+    CONTROL_BLOCK_BY_PORT.iter().any(|entry| {
+        let mut monitoring_by_reference = entry.value().monitoring_by_reference.lock();
+
+        match monitoring_by_reference.get(reference) {
+            Some(monitoring) if monitoring.monitoring_pid == monitoring_pid => {
+                monitoring_by_reference.remove(reference);
+
+                true
+            }
+            _ => false,
+        }
+    })
+}
+
+pub fn get(port: Port) -> Option<Arc<ControlBlock>> {
+    CONTROL_BLOCK_BY_PORT
+        .get(&port)
+        .map(|control_block| control_block.value().clone())
+}
+
+/// The number of bytes `(input, output)` read from and written to the executables of all ports,
+/// as for `erlang:statistics(io)`
+pub fn io() -> (usize, usize) {
+    (
+        TOTAL_INPUT.load(Ordering::SeqCst),
+        TOTAL_OUTPUT.load(Ordering::SeqCst),
+    )
+}
+
+pub fn is_open(port: Port) -> bool {
+    CONTROL_BLOCK_BY_PORT.contains_key(&port)
+}
+
+/// Closes all ports connected to the exiting `process`, as ports are linked to their owner, with
+/// the exit reason of `process` as the reason of their `'DOWN'` messages.
+pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
+    let pid = process.pid();
+    let reason = exception
+        .map(|exception| exception.reason())
+        .unwrap_or_else(|| atom!("normal"));
+    let connected_ports: Vec<Port> = CONTROL_BLOCK_BY_PORT
+        .iter()
+        .filter(|entry| entry.value().connected() == pid)
+        .map(|entry| *entry.key())
+        .collect();
+
+    for port in connected_ports {
+        close(port, reason);
+    }
+
+    for entry in CONTROL_BLOCK_BY_PORT.iter() {
+        let control_block = entry.value();
+        control_block.unlink(pid);
+        control_block
+            .monitoring_by_reference
+            .lock()
+            .retain(|_, monitoring| monitoring.monitoring_pid != pid);
+    }
+}
+
+// Private
+
+/// The `Status` in `{exit_status, Status}`.  An executable killed by a signal has no exit code,
+/// so, like the BEAM, `128 + Signal` is reported instead.
+fn exit_status_code(exit_status: ExitStatus) -> i32 {
+    match exit_status.code() {
+        Some(code) => code,
+        None => 128 + exit_status_signal(exit_status).unwrap_or(0),
+    }
+}
+
+#[cfg(unix)]
+fn exit_status_signal(exit_status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+
+    exit_status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_status_signal(_exit_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+struct Monitoring {
+    monitoring_pid: Pid,
+    /// The registered name the monitoring process used to identify the port
+    name: Option<Atom>,
+}
+
+/// Sends `{'DOWN', MonitorReference, port, Identifier, Reason}` to the monitoring process
+fn send_down_message(
+    monitoring_process: &Process,
+    reference: &Reference,
+    port: Port,
+    name: Option<Atom>,
+    reason: Term,
+) {
+    let identifier_layout = match name {
+        Some(_) => Tuple::layout_for_len(2),
+        None => Layout::new::<Term>(),
+    };
+    let (layout, _) = Tuple::layout_for_len(5)
+        .extend(Reference::layout())
+        .unwrap();
+    let (layout, _) = layout.extend(identifier_layout).unwrap();
+    let reason_layout = Layout::from_size_align(
+        reason.size_in_words() * mem::size_of::<Term>(),
+        mem::align_of::<Term>(),
+    )
+    .unwrap();
+    let (layout, _) = layout.extend(reason_layout).unwrap();
+    let mut non_null_heap_fragment = HeapFragment::new(layout).unwrap();
+    let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+    let reference_term = reference.clone_to_heap(heap_fragment).unwrap();
+    let identifier = match name {
+        Some(name) => heap_fragment
+            .tuple_from_slice(&[name.encode().unwrap(), node::term()])
+            .unwrap()
+            .into(),
+        None => port.encode().unwrap(),
+    };
+    // The reason may be on the heap of the exiting owner
+    let reason_term = reason.clone_to_heap(heap_fragment).unwrap();
+    let down = heap_fragment
+        .tuple_from_slice(&[
+            atom!("DOWN"),
+            reference_term,
+            atom!("port"),
+            identifier,
+            reason_term,
+        ])
+        .unwrap();
+
+    monitoring_process.send_heap_message(non_null_heap_fragment, down.into());
+    monitoring_process
+        .scheduler()
+        .unwrap()
+        .stop_waiting(monitoring_process);
+}
+
+/// The memory needed for the `value` in `{Port, {tag, value}}`
+enum ElementLayout<'a> {
+    Immediate,
+    Binary(&'a [u8]),
+    List(usize),
+}
+
+impl<'a> ElementLayout<'a> {
+    fn layout(&self) -> Layout {
+        match self {
+            ElementLayout::Immediate => Layout::new::<Term>(),
+            ElementLayout::Binary(bytes) => {
+                if bytes.len() > HeapBin::MAX_SIZE {
+                    Layout::new::<ProcBin>()
+                } else {
+                    HeapBin::layout_for(bytes).0
+                }
+            }
+            ElementLayout::List(len) => Layout::array::<Cons>(std::cmp::max(*len, 1)).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary_to_string::binary_to_string;
+use crate::proplist::TryPropListFromTermError;
+
+/// Options for `erlang:open_port/2`
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Arguments passed to the spawned executable
+    pub args: Vec<String>,
+    /// Messages are sent and received as packets preceded by a big-endian length header of this
+    /// many bytes.  If `None`, data is streamed without any framing.
+    pub packet: Option<Packet>,
+    /// Data is sent to the owner as binaries instead of lists of bytes.
+    pub binary: bool,
+    /// Send `{Port, {exit_status, Status}}` to the owner when the executable exits.
+    pub exit_status: bool,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str =
+    "supported options are {args, [string]}, {packet, 1 | 2 | 4}, stream, binary, exit_status, or use_stdio";
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> core::result::Result<&Options, anyhow::Error> {
+        match option.decode().unwrap() {
+            TypedTerm::Atom(atom) => match atom.name() {
+                "binary" => {
+                    self.binary = true;
+
+                    Ok(self)
+                }
+                "exit_status" => {
+                    self.exit_status = true;
+
+                    Ok(self)
+                }
+                "stream" => {
+                    self.packet = None;
+
+                    Ok(self)
+                }
+                // Standard I/O is the only way to communicate with the executable, so this is
+                // always true
+                "use_stdio" => Ok(self),
+                name => {
+                    Err(TryPropListFromTermError::AtomName(name)).context(SUPPORTED_OPTIONS_CONTEXT)
+                }
+            },
+            TypedTerm::Tuple(tuple) => {
+                if tuple.len() == 2 {
+                    let name: Atom = tuple[0]
+                        .try_into()
+                        .map_err(|_| TryPropListFromTermError::KeywordKeyType)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+                    match name.name() {
+                        "args" => {
+                            self.args = strings_from_term(tuple[1]).with_context(|| {
+                                format!("args ({}) must be a list of strings", tuple[1])
+                            })?;
+
+                            Ok(self)
+                        }
+                        "packet" => {
+                            let packet = tuple[1].try_into().with_context(|| {
+                                format!("packet ({}) must be 1, 2, or 4", tuple[1])
+                            })?;
+                            self.packet = Some(packet);
+
+                            Ok(self)
+                        }
+                        name => Err(TryPropListFromTermError::KeywordKeyName(name))
+                            .context(SUPPORTED_OPTIONS_CONTEXT),
+                    }
+                } else {
+                    Err(TryPropListFromTermError::TupleNotPair).context(SUPPORTED_OPTIONS_CONTEXT)
+                }
+            }
+            _ => Err(TryPropListFromTermError::PropertyType).context(SUPPORTED_OPTIONS_CONTEXT),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            args: Vec::new(),
+            packet: None,
+            binary: false,
+            exit_status: false,
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Options, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options.put_option_term(cons.head)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError.into()),
+            }
+        }
+    }
+}
+
+/// The size of the big-endian length header that precedes each packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Packet {
+    One,
+    Two,
+    Four,
+}
+
+impl Packet {
+    pub fn header_len(self) -> usize {
+        match self {
+            Packet::One => 1,
+            Packet::Two => 2,
+            Packet::Four => 4,
+        }
+    }
+
+    pub fn max_len(self) -> usize {
+        match self {
+            Packet::One => u8::max_value() as usize,
+            Packet::Two => u16::max_value() as usize,
+            Packet::Four => u32::max_value() as usize,
+        }
+    }
+
+    pub fn encode_header(self, len: usize) -> Vec<u8> {
+        let bytes = (len as u32).to_be_bytes();
+
+        bytes[(4 - self.header_len())..].to_vec()
+    }
+
+    pub fn decode_header(self, header: &[u8]) -> usize {
+        header
+            .iter()
+            .fold(0, |len, byte| (len << 8) | (*byte as usize))
+    }
+}
+
+impl TryFrom<Term> for Packet {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> std::result::Result<Packet, Self::Error> {
+        let header_len: usize = term.try_into()?;
+
+        match header_len {
+            1 => Ok(Packet::One),
+            2 => Ok(Packet::Two),
+            4 => Ok(Packet::Four),
+            _ => Err(anyhow!("packet header length must be 1, 2, or 4")),
+        }
+    }
+}
+
+/// Converts a string given as a character list or binary, such as an executable path or argument
+pub fn string_from_term(term: Term) -> anyhow::Result<String> {
+    match term.decode().unwrap() {
+        TypedTerm::Nil => Ok(String::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| match result {
+                Ok(element) => element.try_into().with_context(|| {
+                    format!(
+                        "string ({}) element ({}) must be a unicode scalar value",
+                        term, element
+                    )
+                }),
+                Err(_) => Err(ImproperListError).context(format!("string ({}) is improper", term)),
+            })
+            .collect(),
+        _ => binary_to_string(term)
+            .map_err(|_| anyhow!("string ({}) must be a list of characters or a binary", term)),
+    }
+}
+
+fn strings_from_term(term: Term) -> anyhow::Result<Vec<String>> {
+    match term.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| match result {
+                Ok(element) => string_from_term(element),
+                Err(_) => Err(ImproperListError.into()),
+            })
+            .collect(),
+        _ => Err(TypeError.into()),
+    }
+}
@@ -7,12 +7,18 @@ use std::sync::Arc;
 
 use liblumen_alloc::erts::exception::{self, RuntimeException};
 use liblumen_alloc::erts::process::alloc::{Heap, TermAlloc};
+use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::{Process, ProcessHeap};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Monitor};
 
+use crate::distribution;
+use crate::ets;
+use crate::port;
 use crate::registry::*;
 use crate::scheduler::{Scheduled, SchedulerDependentAlloc};
+use crate::time::offset;
+use crate::trace;
 
 thread_local! {
   pub static CURRENT_PROCESS: RefCell<Option<Arc<Process>>> = RefCell::new(None);
@@ -87,9 +93,56 @@ pub fn monitor(process: &Process, monitored_process: &Process) -> Term {
     reference
 }
 
+/// Sends an exit signal with `reason` from `from`, as with `erlang:exit/2`, to another
+/// `process`.  `sender` is the process whose heap is used to build the `{'EXIT', From, Reason}`
+/// message.
+///
+/// `kill` cannot be trapped and exits `process` with `killed`.  Otherwise, `process` receives
+/// `{'EXIT', From, Reason}` if it traps exits, or exits with `reason` unless it is `normal`.
+pub fn exit_signal(sender: &Process, from: Term, process: &Process, reason: Term) {
+    if reason == atom!("kill") {
+        exit_with_reason(process, atom!("killed"));
+    } else if process.traps_exit() {
+        let exit_message = sender.tuple_from_slice(&[atom!("EXIT"), from, reason]);
+        process.send_from_other(exit_message);
+    } else if reason != atom!("normal") {
+        exit_with_reason(process, reason);
+    } else {
+        return;
+    }
+
+    process.scheduler().unwrap().stop_waiting(process);
+}
+
+/// Suspends `process` on behalf of `suspender`.  Returns how many times `suspender` has now
+/// suspended `process`.
+pub fn suspend(suspender: &Process, process: &Process) -> usize {
+    process.suspend(suspender.pid())
+}
+
+/// Undoes one suspend of `process` by `suspender`, so that `process` is scheduled again once no
+/// process has it suspended.  Returns `false` if `suspender` had not suspended `process`.
+pub fn resume(suspender: &Process, process: &Process) -> bool {
+    match process.resume(suspender.pid()) {
+        Some(_) => {
+            process.scheduler().unwrap().stop_waiting(process);
+
+            true
+        }
+        None => false,
+    }
+}
+
 pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
+    trace::exit(process, exception);
     monitor::propagate_exit(process, exception);
     propagate_exit_to_links(process, exception);
+    resume_suspended(process);
+    port::propagate_exit(process, exception);
+    offset::propagate_exit(process);
+    ets::propagate_exit(process);
+    distribution::propagate_exit(process, exception);
+    remove_pid_to_process(&process.pid());
 }
 
 pub fn propagate_exit_to_links(process: &Process, exception: Option<&RuntimeException>) {
@@ -167,6 +220,24 @@ pub fn propagate_exit_to_links(process: &Process, exception: Option<&RuntimeExce
     }
 }
 
+/// Resumes the processes that the exiting `process` suspended
+fn resume_suspended(process: &Process) {
+    let pid = process.pid();
+
+    for arc_process in processes() {
+        if arc_process.resume_all(pid) {
+            arc_process.scheduler().unwrap().stop_waiting(&arc_process);
+        }
+    }
+}
+
+fn exit_with_reason(process: &Process, reason: Term) {
+    let (heap_fragment_reason, mut heap_fragment) = reason.clone_to_fragment().unwrap();
+
+    process.attach_fragment(unsafe { heap_fragment.as_mut() });
+    process.exit(heap_fragment_reason, Trace::capture(), None);
+}
+
 fn send_self_exit_message(
     process: &Process,
     heap: &mut ProcessHeap,
@@ -1,3 +1,40 @@
 pub mod options;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use thiserror::Error;
+
+use crate::registry;
+
 pub use self::options::{Connection, Options};
+
+/// `0` means there is no limit
+static MAX_PROCESSES: AtomicUsize = AtomicUsize::new(0);
+
+/// The maximum number of simultaneously live processes, as set by `+P` in `vm.args`
+pub fn max_processes() -> Option<usize> {
+    match MAX_PROCESSES.load(Ordering::SeqCst) {
+        0 => None,
+        max_processes => Some(max_processes),
+    }
+}
+
+pub fn set_max_processes(max_processes: usize) {
+    MAX_PROCESSES.store(max_processes, Ordering::SeqCst);
+}
+
+/// Checks that another process can be spawned without exceeding `max_processes`
+pub fn check_system_limit() -> Result<(), SystemLimit> {
+    match max_processes() {
+        Some(max_processes) if max_processes <= registry::process_count() => {
+            Err(SystemLimit { max_processes })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("maximum number of processes ({max_processes}) are alive")]
+pub struct SystemLimit {
+    pub max_processes: usize,
+}
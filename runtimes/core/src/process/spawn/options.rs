@@ -1,5 +1,3 @@
-mod message_queue_data;
-
 use std::convert::{TryFrom, TryInto};
 
 use anyhow::*;
@@ -7,14 +5,13 @@ use anyhow::*;
 use liblumen_alloc::erts::exception::Alloc;
 use liblumen_alloc::erts::process::alloc::{default_heap_size, heap, next_heap_size};
 use liblumen_alloc::erts::process::priority::Priority;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Process};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::ModuleFunctionArity;
 
 use crate::process;
 use crate::proplist::TryPropListFromTermError;
-
-use message_queue_data::*;
+use crate::trace;
 
 #[must_use]
 pub struct Connection {
@@ -23,13 +20,6 @@ pub struct Connection {
     pub monitor_reference: Option<Term>,
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct MaxHeapSize {
-    size: Option<usize>,
-    kill: Option<bool>,
-    error_logger: Option<bool>,
-}
-
 #[derive(Clone, Copy, Debug)]
 pub struct Options {
     pub link: bool,
@@ -49,13 +39,17 @@ impl Options {
         match self.priority {
             Some(priority) => priority,
             None => match parent_process {
-                Some(process) => process.priority,
+                Some(process) => process.priority(),
                 None => Default::default(),
             },
         }
     }
 
     pub fn connect(&self, parent_process: Option<&Process>, child_process: &Process) -> Connection {
+        if let Some(parent_process) = parent_process {
+            trace::spawn(parent_process, child_process);
+        }
+
         let linked = if self.link {
             parent_process.unwrap().link(child_process);
 
@@ -104,7 +98,7 @@ impl Options {
         };
         let (heap, heap_size) = self.sized_heap()?;
 
-        let process = Process::new(
+        let mut process = Process::new(
             priority,
             parent_process,
             module_function_arity,
@@ -112,6 +106,20 @@ impl Options {
             heap_size,
         );
 
+        if let Some(fullsweep_after) = self.fullsweep_after {
+            process.set_fullsweep_after(fullsweep_after);
+        }
+
+        if let Some(max_heap_size) = self.max_heap_size {
+            process.set_max_heap_size(max_heap_size);
+        }
+
+        if let Some(min_bin_vheap_size) = self.min_bin_vheap_size {
+            process.set_min_bin_vheap_size(min_bin_vheap_size);
+        }
+
+        process.set_message_queue_data(self.message_queue_data);
+
         Ok(process)
     }
 
@@ -125,6 +133,22 @@ impl Options {
         }
     }
 
+    /// As in the BEAM, a `max_heap_size` is only allowed if it is at least the `min_heap_size`
+    fn validate(self) -> Result<Self, anyhow::Error> {
+        match (self.max_heap_size, self.min_heap_size) {
+            (Some(max_heap_size), Some(min_heap_size))
+                if max_heap_size.is_enabled() && max_heap_size.size < min_heap_size =>
+            {
+                Err(anyhow!(
+                    "max_heap_size ({}) is less than min_heap_size ({})",
+                    max_heap_size.size,
+                    min_heap_size
+                ))
+            }
+            _ => Ok(self),
+        }
+    }
+
     fn put_option_atom(&mut self, atom: Atom) -> Result<&Self, anyhow::Error> {
         match atom.name() {
             "link" => {
@@ -162,7 +186,12 @@ impl Options {
 
                     Ok(self)
                 }
-                "max_heap_size" => unimplemented!(),
+                "max_heap_size" => {
+                    let max_heap_size = tuple[1].try_into().context("max_heap_size")?;
+                    self.max_heap_size = Some(max_heap_size);
+
+                    Ok(self)
+                }
                 "message_queue_data" => {
                     let message_queue_data = tuple[1].try_into().context("message_queue_data")?;
                     self.message_queue_data = message_queue_data;
@@ -212,7 +241,7 @@ impl Default for Options {
 
 const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are :link, :monitor, \
      {:fullsweep_after, generational_collections :: pos_integer()}, \
-     {:max_heap_size, words :: pos_integer()}, \
+     {:max_heap_size, words :: non_neg_integer() | %{size: words :: non_neg_integer(), kill: boolean(), error_logger: boolean()}}, \
      {:message_queue_data, :off_heap | :on_heap}, \
      {:min_bin_vheap_size, words :: pos_integer()}, \
      {:min_heap_size, words :: pos_integer()}, and \
@@ -227,7 +256,7 @@ impl TryFrom<Term> for Options {
 
         loop {
             match options_term.decode().unwrap() {
-                TypedTerm::Nil => return Ok(options),
+                TypedTerm::Nil => return options.validate().context(SUPPORTED_OPTIONS_CONTEXT),
                 TypedTerm::List(cons) => {
                     options
                         .put_option_term(cons.head)
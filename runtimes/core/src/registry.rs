@@ -1,6 +1,8 @@
 /// Maps registered names (`Atom`) to `LocalPid` or `Port`
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Weak};
 
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use lazy_static::lazy_static;
 
@@ -9,17 +11,33 @@ use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::exception;
 use liblumen_alloc::Process;
 
+use crate::port;
+
 lazy_static! {
     static ref REGISTERED_BY_NAME: DashMap<Atom, Registered> = Default::default();
     // Strong references are owned by the scheduler run queues
     static ref WEAK_PROCESS_CONTROL_BLOCK_BY_PID: DashMap<Pid, Weak<Process>> = Default::default();
 }
 
+/// The number of processes in `WEAK_PROCESS_CONTROL_BLOCK_BY_PID`, so that counting the live
+/// processes, such as when checking the `+P` limit on every spawn, doesn't scan all of them.
+static PROCESS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn atom_to_port(name: &Atom) -> Option<Port> {
+    REGISTERED_BY_NAME
+        .get(name)
+        .and_then(|registered| match registered.value() {
+            Registered::Port(port) if port::is_open(*port) => Some(*port),
+            _ => None,
+        })
+}
+
 pub fn atom_to_process(name: &Atom) -> Option<Arc<Process>> {
     REGISTERED_BY_NAME
         .get(name)
         .and_then(|registered| match registered.value() {
             Registered::Process(weak_process) => weak_process.upgrade(),
+            Registered::Port(_) => None,
         })
 }
 
@@ -51,6 +69,40 @@ pub fn pid_to_self_or_process(pid: Pid, process_arc: &Arc<Process>) -> Option<Ar
     }
 }
 
+/// The number of live processes
+pub fn process_count() -> usize {
+    PROCESS_COUNT.load(Ordering::SeqCst)
+}
+
+/// The live processes
+pub fn processes() -> Vec<Arc<Process>> {
+    WEAK_PROCESS_CONTROL_BLOCK_BY_PID
+        .iter()
+        .filter_map(|entry| entry.value().upgrade())
+        .collect()
+}
+
+pub fn put_atom_to_port(name: Atom, port: Port) -> bool {
+    if REGISTERED_BY_NAME.contains_key(&name) {
+        return false;
+    }
+
+    match port::get(port) {
+        Some(arc_control_block) => {
+            let mut writable_registered_name = arc_control_block.registered_name.lock();
+
+            if let None = *writable_registered_name {
+                REGISTERED_BY_NAME.insert(name, Registered::Port(port));
+                *writable_registered_name = Some(name);
+                true
+            } else {
+                false
+            }
+        }
+        None => false,
+    }
+}
+
 pub fn put_atom_to_process(name: Atom, arc_process: Arc<Process>) -> bool {
     if !REGISTERED_BY_NAME.contains_key(&name) {
         register_in(arc_process, name)
@@ -59,13 +111,21 @@ pub fn put_atom_to_process(name: Atom, arc_process: Arc<Process>) -> bool {
     }
 }
 
+/// Registers `arc_process` under `name`.  Returns `false` if the process already has a name or
+/// `name` is already registered to another process or port.
 pub fn register_in(arc_process: Arc<Process>, name: Atom) -> bool {
     let mut writable_registered_name = arc_process.registered_name.write();
 
     if let None = *writable_registered_name {
-        REGISTERED_BY_NAME.insert(name, Registered::Process(Arc::downgrade(&arc_process)));
-        *writable_registered_name = Some(name);
-        true
+        match REGISTERED_BY_NAME.entry(name) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(vacant) => {
+                vacant.insert(Registered::Process(Arc::downgrade(&arc_process)));
+                *writable_registered_name = Some(name);
+
+                true
+            }
+        }
     } else {
         false
     }
@@ -77,6 +137,16 @@ pub fn put_pid_to_process(arc_process: &Arc<Process>) {
     {
         panic!("Process already registered with pid");
     }
+
+    PROCESS_COUNT.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Removes the `pid` of an exited process, so that it no longer resolves to the process or counts
+/// as live.
+pub fn remove_pid_to_process(pid: &Pid) {
+    if WEAK_PROCESS_CONTROL_BLOCK_BY_PID.remove(pid).is_some() {
+        PROCESS_COUNT.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 pub fn unregister(name: &Atom) -> bool {
@@ -90,6 +160,13 @@ pub fn unregister(name: &Atom) -> bool {
             }
             None => false,
         },
+        Some((_, Registered::Port(port))) => {
+            if let Some(arc_control_block) = port::get(port) {
+                *arc_control_block.registered_name.lock() = None;
+            }
+
+            true
+        }
         None => false,
     }
 }
@@ -97,6 +174,7 @@ pub fn unregister(name: &Atom) -> bool {
 #[cfg_attr(test, derive(Debug))]
 pub enum Registered {
     Process(Weak<Process>),
+    Port(Port),
 }
 
 impl PartialEq for Registered {
@@ -105,6 +183,8 @@ impl PartialEq for Registered {
             (Registered::Process(self_weak_process), Registered::Process(other_weak_process)) => {
                 Weak::ptr_eq(&self_weak_process, &other_weak_process)
             }
+            (Registered::Port(self_port), Registered::Port(other_port)) => self_port == other_port,
+            _ => false,
         }
     }
 }
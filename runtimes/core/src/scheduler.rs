@@ -67,6 +67,16 @@ fn registered() -> Arc<dyn Scheduler> {
     arc_scheduler
 }
 
+/// Returns all schedulers that are currently registered, such as for finding a scheduler to steal
+/// work from.
+pub fn all() -> Vec<Arc<dyn Scheduler>> {
+    SCHEDULER_BY_ID
+        .lock()
+        .values()
+        .filter_map(|weak_scheduler| weak_scheduler.upgrade())
+        .collect()
+}
+
 pub fn unregister(id: &ID) {
     let mut locked_scheduler_by_id = SCHEDULER_BY_ID.lock();
 
@@ -32,8 +32,10 @@ impl Queues {
         }
     }
 
+    /// A suspended process is moved to the waiting processes instead of being run, so the caller
+    /// is told to ask again, as with a `Run::Delayed` process.
     pub fn dequeue(&mut self) -> Run {
-        if 0 < self.max.len() {
+        let run = if 0 < self.max.len() {
             self.max.dequeue()
         } else if 0 < self.high.len() {
             self.high.dequeue()
@@ -43,25 +45,76 @@ impl Queues {
             Run::Waiting
         } else {
             Run::None
+        };
+
+        match run {
+            Run::Now(arc_process) if is_suspended(&arc_process) => {
+                self.waiting.insert(arc_process);
+
+                Run::Delayed
+            }
+            run => run,
         }
     }
 
+    /// The run queue is chosen by the priority of `arc_process` when it is enqueued, so a process
+    /// that changes its own priority while running moves to the new run queue when it is requeued.
     pub fn enqueue(&mut self, arc_process: Arc<Process>) {
-        match arc_process.priority {
-            Priority::Low | Priority::Normal => self.normal_low.enqueue(arc_process),
+        // read only once, so that the delay matches the run queue
+        let priority = arc_process.priority();
+
+        match priority {
+            Priority::Low | Priority::Normal => self.normal_low.enqueue(arc_process, priority),
             Priority::High => self.high.enqueue(arc_process),
             Priority::Max => self.max.enqueue(arc_process),
         }
     }
 
     pub fn len(&self) -> usize {
-        self.waiting.len() + self.normal_low.len() + self.high.len() + self.max.len()
+        self.waiting.len() + self.runnable_len()
+    }
+
+    /// The number of processes that are ready to run, excluding those that are waiting
+    pub fn runnable_len(&self) -> usize {
+        self.normal_low.len() + self.high.len() + self.max.len()
+    }
+
+    /// Removes the most recently enqueued process from the highest priority run queue that is
+    /// not empty, so that it can be migrated to another scheduler.
+    ///
+    /// Waiting processes are never stolen, as they are woken by the scheduler they are waiting
+    /// on.
+    pub fn steal(&mut self) -> Option<Stolen> {
+        self.max
+            .steal()
+            .or_else(|| self.high.steal())
+            .map(|arc_process| Stolen {
+                arc_process,
+                delay: None,
+            })
+            .or_else(|| self.normal_low.steal())
+    }
+
+    /// Enqueues a process stolen from another scheduler's `Queues`, keeping what remained of its
+    /// delay, so that migrating a `Priority::Low` process doesn't make it run sooner.
+    pub fn enqueue_stolen(&mut self, stolen: Stolen) {
+        match stolen.delay {
+            Some(delay) => self.normal_low.enqueue_delayed(DelayedProcess {
+                delay,
+                arc_process: stolen.arc_process,
+            }),
+            None => self.enqueue(stolen.arc_process),
+        }
     }
 
     /// Returns the process is not pushed back because it is exiting
     #[must_use]
     pub fn requeue(&mut self, arc_process: Arc<Process>) -> Option<Arc<Process>> {
-        let next = Next::from_status(&arc_process.status.read());
+        let next = if is_suspended(&arc_process) {
+            Next::Wait
+        } else {
+            Next::from_status(&arc_process.status.read())
+        };
 
         // has to be separate so that `arc_process` can be moved
         match next {
@@ -77,21 +130,42 @@ impl Queues {
         }
     }
 
+    /// Suspended processes keep waiting until they are resumed, unless they are exiting.
     pub fn stop_waiting(&mut self, process: &Process) {
         match self.waiting.get(process) {
-            Some(arc_process) => {
+            Some(arc_process) if !is_suspended(arc_process) => {
                 let arc_process = Arc::clone(arc_process);
                 self.waiting.remove(&arc_process);
 
                 self.enqueue(arc_process);
             }
-            None => (),
+            _ => (),
         }
     }
 }
 
+/// A process removed from a scheduler's `Queues` by `Queues::steal`, to be enqueued on another
+/// scheduler with `Queues::enqueue_stolen`.
+#[derive(Debug)]
+pub struct Stolen {
+    arc_process: Arc<Process>,
+    /// The remaining delay when stolen from the `Priority::Normal` and `Priority::Low` run queue
+    delay: Option<Delay>,
+}
+
+impl Stolen {
+    pub fn arc_process(&self) -> &Arc<Process> {
+        &self.arc_process
+    }
+}
+
 // Private
 
+/// Exiting processes are never kept suspended, so that their exit is propagated.
+fn is_suspended(process: &Process) -> bool {
+    process.is_suspended() && !process.is_exiting()
+}
+
 enum Next {
     Wait,
     PushBack,
@@ -182,6 +256,10 @@ impl Immediate {
     pub fn enqueue(&mut self, process: Arc<Process>) {
         self.0.push_back(process);
     }
+
+    pub fn steal(&mut self) -> Option<Arc<Process>> {
+        self.0.pop_back()
+    }
 }
 
 /// A run queue where the `Arc<Process` is run only when its delay is `0`.  This allows
@@ -217,8 +295,19 @@ impl Delayed {
         }
     }
 
-    pub fn enqueue(&mut self, arc_process: Arc<Process>) {
-        let delayed_process = DelayedProcess::new(arc_process);
+    pub fn enqueue(&mut self, arc_process: Arc<Process>, priority: Priority) {
+        let delayed_process = DelayedProcess::new(arc_process, priority);
+        self.0.push_back(delayed_process);
+    }
+
+    pub fn steal(&mut self) -> Option<Stolen> {
+        self.0.pop_back().map(|delayed_process| Stolen {
+            arc_process: delayed_process.arc_process,
+            delay: Some(delayed_process.delay),
+        })
+    }
+
+    fn enqueue_delayed(&mut self, delayed_process: DelayedProcess) {
         self.0.push_back(delayed_process);
     }
 }
@@ -232,9 +321,9 @@ struct DelayedProcess {
 }
 
 impl DelayedProcess {
-    fn new(arc_process: Arc<Process>) -> DelayedProcess {
+    fn new(arc_process: Arc<Process>, priority: Priority) -> DelayedProcess {
         DelayedProcess {
-            delay: Self::priority_to_delay(arc_process.priority),
+            delay: Self::priority_to_delay(priority),
             arc_process,
         }
     }
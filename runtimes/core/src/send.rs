@@ -8,9 +8,11 @@ use liblumen_alloc::erts::exception::InternalResult;
 use liblumen_alloc::term::prelude::*;
 use liblumen_alloc::Process;
 
-use crate::distribution::nodes::node;
+use crate::distribution::{self, nodes::node};
 use crate::registry::{self, pid_to_process};
 use crate::scheduler::Scheduled;
+use crate::seq_trace;
+use crate::trace;
 
 pub use options::*;
 
@@ -37,31 +39,35 @@ pub fn send(
                     )
                 })?;
 
-                match node_atom.name() {
-                    node::DEAD_ATOM_NAME => send_to_name(name_atom, message, options, process),
-                    _ => {
-                        if !options.connect {
-                            Ok(Sent::ConnectRequired)
-                        } else if !options.suspend {
-                            Ok(Sent::SuspendRequired)
-                        } else {
-                            unimplemented!("distribution")
-                        }
-                    }
+                if node_atom.name() == node::DEAD_ATOM_NAME || node_atom == node::atom() {
+                    send_to_name(name_atom, message, options, process)
+                } else if !distribution::is_connected(node_atom) && !options.connect {
+                    Ok(Sent::ConnectRequired)
+                } else if !distribution::is_connected(node_atom) && !options.suspend {
+                    // Connecting blocks the sender until the handshake is done
+                    Ok(Sent::SuspendRequired)
+                } else {
+                    distribution::reg_send(process, name_atom, node_atom, message);
+
+                    Ok(Sent::Sent)
                 }
             } else {
                 Err(anyhow!("destination ({}) is a tuple, but not 2-arity", destination).into())
             }
         }
         TypedTerm::Pid(destination_pid) => {
+            let seq_trace_token = seq_trace::send(process, destination, message);
+            trace::send(process, destination, message);
+
             if destination_pid == process.pid() {
-                process.send_from_self(message);
+                process.send_from_self_with_seq_trace_token(message, seq_trace_token);
 
                 Ok(Sent::Sent)
             } else {
                 match pid_to_process(&destination_pid) {
                     Some(destination_arc_process) => {
-                        destination_arc_process.send_from_other(message);
+                        destination_arc_process
+                            .send_from_other_with_seq_trace_token(message, seq_trace_token);
                         destination_arc_process
                             .scheduler()
                             .unwrap()
@@ -73,6 +79,11 @@ pub fn send(
                 }
             }
         }
+        TypedTerm::ExternalPid(destination_external_pid) => {
+            distribution::send(&destination_external_pid, message);
+
+            Ok(Sent::Sent)
+        }
         _ => Err(TypeError)
             .context(format!(
                 "destination ({}) is not registered_name (atom), {{registered_name, node}}, or pid",
@@ -98,13 +109,19 @@ fn send_to_name(
     process: &Process,
 ) -> InternalResult<Sent> {
     if *process.registered_name.read() == Some(destination) {
-        process.send_from_self(message);
+        let seq_trace_token = seq_trace::send(process, destination.encode().unwrap(), message);
+        trace::send(process, destination.encode().unwrap(), message);
+        process.send_from_self_with_seq_trace_token(message, seq_trace_token);
 
         Ok(Sent::Sent)
     } else {
         match registry::atom_to_process(&destination) {
             Some(destination_arc_process) => {
-                destination_arc_process.send_from_other(message);
+                let seq_trace_token =
+                    seq_trace::send(process, destination.encode().unwrap(), message);
+                trace::send(process, destination.encode().unwrap(), message);
+                destination_arc_process
+                    .send_from_other_with_seq_trace_token(message, seq_trace_token);
                 destination_arc_process
                     .scheduler()
                     .unwrap()
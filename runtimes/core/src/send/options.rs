@@ -7,10 +7,9 @@ use liblumen_alloc::erts::term::prelude::*;
 use crate::proplist::TryPropListFromTermError;
 
 pub struct Options {
-    // Send only suspends for some sends to ports and for remote (`ExternalPid` or
-    // `{name, remote_node}`) sends, so it does not apply at this time.
+    // Send only suspends for `{name, remote_node}` sends that need to connect to the remote node.
     pub suspend: bool,
-    // Connect only applies when there is distribution, which isn't implemented yet.
+    // Connect only applies to `{name, remote_node}` sends to nodes that are not connected yet.
     pub connect: bool,
 }
 
@@ -0,0 +1,167 @@
+//! Sequential tracing of the messages passed along with a `SeqTraceToken`.  Trace events are sent
+//! as messages to the system sequential tracer set with `seq_trace:set_system_tracer/1`.
+//!
+//! See http://erlang.org/doc/man/seq_trace.html
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::process::{SeqTraceFlags, SeqTraceToken};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::time::Milliseconds;
+use liblumen_alloc::{atom, Process};
+
+use crate::registry::pid_to_process;
+use crate::scheduler::Scheduled;
+use crate::time::{monotonic, system, Unit};
+
+lazy_static! {
+    static ref RW_LOCK_OPTION_SYSTEM_TRACER: RwLock<Option<Pid>> = RwLock::new(None);
+}
+
+/// Makes `strict_monotonic_timestamp`s unique when the monotonic time has not changed
+static STRICT_MONOTONIC_UNIQUE: AtomicU64 = AtomicU64::new(0);
+
+pub fn system_tracer() -> Option<Pid> {
+    *RW_LOCK_OPTION_SYSTEM_TRACER.read()
+}
+
+/// Returns the old system tracer
+pub fn set_system_tracer(system_tracer: Option<Pid>) -> Option<Pid> {
+    std::mem::replace(&mut *RW_LOCK_OPTION_SYSTEM_TRACER.write(), system_tracer)
+}
+
+/// Passes along the token of `sender`, if any, with `message` sent to `destination`.  Returns the
+/// token to send with the message.
+pub fn send(sender: &Process, destination: Term, message: Term) -> Option<SeqTraceToken> {
+    let option_seq_trace_token = sender.send_seq_trace_token();
+
+    if let Some(ref seq_trace_token) = option_seq_trace_token {
+        if seq_trace_token.flags.are_set(SeqTraceFlags::Send) {
+            trace(
+                sender,
+                seq_trace_token,
+                atom!("send"),
+                sender.pid_term(),
+                destination,
+                message,
+            );
+        }
+    }
+
+    option_seq_trace_token
+}
+
+/// Receives the token sent along with `message`, if any, in place of the token of `receiver`.
+pub fn receive(receiver: &Process, message_seq_trace_token: Option<SeqTraceToken>, message: Term) {
+    if let Some(seq_trace_token) = receiver.receive_seq_trace_token(message_seq_trace_token) {
+        if seq_trace_token.flags.are_set(SeqTraceFlags::Receive) {
+            trace(
+                receiver,
+                &seq_trace_token,
+                atom!("receive"),
+                seq_trace_token.from.encode().unwrap(),
+                receiver.pid_term(),
+                message,
+            );
+        }
+    }
+}
+
+/// Sends a `print` trace event with `message` if `process` has a token whose label is
+/// `option_label`, when given.  Returns whether `process` has such a token.
+pub fn print(process: &Process, option_label: Option<Term>, message: Term) -> bool {
+    match process.seq_trace_token() {
+        Some(seq_trace_token) => match option_label {
+            Some(label) if label != seq_trace_token.label() => false,
+            _ => {
+                if seq_trace_token.flags.are_set(SeqTraceFlags::Print) {
+                    trace(
+                        process,
+                        &seq_trace_token,
+                        atom!("print"),
+                        process.pid_term(),
+                        Term::NIL,
+                        message,
+                    );
+                }
+
+                true
+            }
+        },
+        None => false,
+    }
+}
+
+// Private
+
+/// Sends `{seq_trace, Label, {Event, Serial, From, To, Message}}` to the system tracer, with the
+/// timestamp as a fourth element when one of the timestamp flags is set.  Events of sensitive
+/// processes are not traced.
+fn trace(
+    process: &Process,
+    seq_trace_token: &SeqTraceToken,
+    event: Term,
+    from: Term,
+    to: Term,
+    message: Term,
+) {
+    if process.is_sensitive() {
+        return;
+    }
+
+    if let Some(system_tracer) = system_tracer() {
+        let info = process.tuple_from_slice(&[
+            event,
+            seq_trace_token.serial.to_term(process),
+            from,
+            to,
+            message,
+        ]);
+        let tag = atom!("seq_trace");
+        let label = seq_trace_token.label();
+
+        let trace_message = match timestamp(process, seq_trace_token.flags) {
+            Some(timestamp) => process.tuple_from_slice(&[tag, label, info, timestamp]),
+            None => process.tuple_from_slice(&[tag, label, info]),
+        };
+
+        if system_tracer == process.pid() {
+            process.send_from_self(trace_message);
+        } else if let Some(system_tracer_arc_process) = pid_to_process(&system_tracer) {
+            system_tracer_arc_process.send_from_other(trace_message);
+            system_tracer_arc_process
+                .scheduler()
+                .unwrap()
+                .stop_waiting(&system_tracer_arc_process);
+        }
+    }
+}
+
+/// As in the BEAM, `strict_monotonic_timestamp` takes precedence over `monotonic_timestamp`,
+/// which takes precedence over `timestamp`.
+fn timestamp(process: &Process, flags: SeqTraceFlags) -> Option<Term> {
+    if flags.are_set(SeqTraceFlags::StrictMonotonicTimestamp) {
+        let unique = STRICT_MONOTONIC_UNIQUE.fetch_add(1, Ordering::SeqCst);
+
+        Some(process.tuple_from_slice(&[
+            process.integer(monotonic::time_in_unit(Unit::Nanosecond)),
+            process.integer(unique),
+        ]))
+    } else if flags.are_set(SeqTraceFlags::MonotonicTimestamp) {
+        Some(process.integer(monotonic::time_in_unit(Unit::Nanosecond)))
+    } else if flags.are_set(SeqTraceFlags::Timestamp) {
+        let milliseconds: Milliseconds = system::time().into();
+        let microseconds = milliseconds.0 * 1_000;
+
+        Some(process.tuple_from_slice(&[
+            process.integer(microseconds / 1_000_000_000_000),
+            process.integer((microseconds / 1_000_000) % 1_000_000),
+            process.integer(microseconds % 1_000_000),
+        ]))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,130 @@
+//! Runtime-wide counters for `erlang:statistics/1`.  The counters are updated by the schedulers
+//! each time they run a process.
+//!
+//! See http://erlang.org/doc/man/erlang.html#statistics-1
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use liblumen_alloc::erts::process::Status;
+use liblumen_alloc::erts::time::Milliseconds;
+use liblumen_alloc::Priority;
+
+use crate::registry;
+use crate::scheduler::{self, Scheduler};
+use crate::time::monotonic;
+
+/// The number of times a process was scheduled in
+static CONTEXT_SWITCHES: AtomicU64 = AtomicU64::new(0);
+/// The reductions of all processes, including those that have exited
+static REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+
+// The totals when `statistics/1` was last called for the item, for the "since last call" values
+static LAST_REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+static LAST_RUNTIME: AtomicU64 = AtomicU64::new(0);
+static LAST_WALL_CLOCK: AtomicU64 = AtomicU64::new(0);
+
+/// Counts a process being scheduled in and the `reductions` it used before being scheduled out
+pub fn ran(reductions: u64) {
+    CONTEXT_SWITCHES.fetch_add(1, Ordering::Relaxed);
+    REDUCTIONS.fetch_add(reductions, Ordering::Relaxed);
+}
+
+pub fn context_switches() -> u64 {
+    CONTEXT_SWITCHES.load(Ordering::Relaxed)
+}
+
+/// `(Total, SinceLastCall)` reductions
+pub fn reductions() -> (u64, u64) {
+    total_and_since_last_call(REDUCTIONS.load(Ordering::Relaxed), &LAST_REDUCTIONS)
+}
+
+/// `(Total, SinceLastCall)` milliseconds of CPU time used by the runtime
+pub fn runtime() -> (u64, u64) {
+    total_and_since_last_call(cpu_time_milliseconds(), &LAST_RUNTIME)
+}
+
+/// `(Total, SinceLastCall)` milliseconds of wall clock time since the runtime started
+pub fn wall_clock() -> (u64, u64) {
+    total_and_since_last_call(wall_clock_milliseconds(), &LAST_WALL_CLOCK)
+}
+
+/// The number of processes that are ready to run on each scheduler, in scheduler ID order
+pub fn run_queue_lengths() -> Vec<usize> {
+    schedulers()
+        .iter()
+        .map(|scheduler| runnable_len(scheduler.as_ref()))
+        .collect()
+}
+
+/// The number of processes that are ready to run or running on each scheduler, in scheduler ID
+/// order
+pub fn active_tasks() -> Vec<usize> {
+    let processes = registry::processes();
+
+    schedulers()
+        .iter()
+        .map(|scheduler| {
+            let id = scheduler.id();
+            let running = processes
+                .iter()
+                .filter(|process| {
+                    process.scheduler_id() == Some(id) && *process.status.read() == Status::Running
+                })
+                .count();
+
+            runnable_len(scheduler.as_ref()) + running
+        })
+        .collect()
+}
+
+// Private
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        fn cpu_time_milliseconds() -> u64 {
+            let mut timespec = libc::timespec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            };
+
+            unsafe {
+                libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut timespec);
+            }
+
+            (timespec.tv_sec as u64) * 1_000 + (timespec.tv_nsec as u64) / 1_000_000
+        }
+    } else {
+        /// Without a CPU clock, the runtime is assumed to have been busy since it started
+        fn cpu_time_milliseconds() -> u64 {
+            wall_clock_milliseconds()
+        }
+    }
+}
+
+/// `Priority::Low` processes share the run queue of `Priority::Normal` processes, so `Low` is not
+/// counted separately.
+fn runnable_len(scheduler: &dyn Scheduler) -> usize {
+    [Priority::Normal, Priority::High, Priority::Max]
+        .iter()
+        .map(|priority| scheduler.run_queue_len(*priority))
+        .sum()
+}
+
+fn schedulers() -> Vec<Arc<dyn Scheduler>> {
+    let mut schedulers = scheduler::all();
+    schedulers.sort_by_key(|scheduler| scheduler.id());
+
+    schedulers
+}
+
+fn total_and_since_last_call(total: u64, last: &AtomicU64) -> (u64, u64) {
+    let last_total = last.swap(total, Ordering::Relaxed);
+
+    (total, total.saturating_sub(last_total))
+}
+
+fn wall_clock_milliseconds() -> u64 {
+    let milliseconds: Milliseconds = monotonic::time().into();
+
+    milliseconds.0
+}
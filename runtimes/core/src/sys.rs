@@ -1 +1,3 @@
+pub mod cpus;
+pub mod halt;
 pub mod io;
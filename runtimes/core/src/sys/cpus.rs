@@ -0,0 +1,10 @@
+extern "Rust" {
+    /// The number of logical CPUs available to this node, as determined by the runtime for the
+    /// host it was built for.
+    #[link_name = "lumen_rt_sys_cpus_num_logical"]
+    fn lumen_rt_sys_cpus_num_logical() -> usize;
+}
+
+pub fn num_logical() -> usize {
+    unsafe { lumen_rt_sys_cpus_num_logical() }
+}
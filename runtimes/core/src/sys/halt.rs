@@ -0,0 +1,43 @@
+//! Halting the runtime system with `erlang:halt/0,1,2`
+use std::io::{self, Write};
+
+/// Halts the runtime system with `status` as the exit status of the OS process.
+///
+/// When `flush` is `false`, buffered output is discarded instead of being written before the OS
+/// process exits.
+pub fn halt(status: i32, flush: bool) -> ! {
+    if flush {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+
+        std::process::exit(status)
+    } else {
+        exit_without_flush(status)
+    }
+}
+
+/// Halts the runtime system with `slogan` as the reason, as with `erlang:halt(Slogan)`.
+///
+/// There are no crash dumps, so only the `slogan` is written to standard error.
+pub fn crash(slogan: &str, flush: bool) -> ! {
+    eprintln!("Runtime terminating: {}", slogan);
+
+    halt(1, flush)
+}
+
+/// Halts the runtime system with a core dump, as with `erlang:halt(abort)`
+pub fn abort() -> ! {
+    std::process::abort()
+}
+
+// Private
+
+#[cfg(not(target_arch = "wasm32"))]
+fn exit_without_flush(status: i32) -> ! {
+    unsafe { libc::_exit(status) }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn exit_without_flush(status: i32) -> ! {
+    std::process::exit(status)
+}
@@ -1,5 +1,6 @@
 pub mod datetime;
 pub mod monotonic;
+pub mod offset;
 pub mod system;
 
 use core::convert::{TryFrom, TryInto};
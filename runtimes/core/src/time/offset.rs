@@ -0,0 +1,130 @@
+//! The time offset between Erlang monotonic time and Erlang system time, and the processes
+//! monitoring it with `erlang:monitor(time_offset, clock_service)`.
+use std::convert::TryInto;
+
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+use liblumen_core::alloc::Layout;
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::erts::process::alloc::TermAlloc;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Process};
+
+use crate::registry::pid_to_process;
+use crate::scheduler::Scheduled;
+use crate::time::{monotonic, system, Unit};
+
+lazy_static! {
+    static ref MONITORING_PID_BY_REFERENCE: DashMap<Reference, Pid> = Default::default();
+    /// The offset when the monitors were last notified, in `Unit::Native`
+    static ref MUTEX_OPTION_NOTIFIED_OFFSET: Mutex<Option<BigInt>> = Default::default();
+}
+
+/// The system and monotonic clocks are not read at the same instant, so differences smaller than
+/// this many `Unit::Native` (milliseconds) are not changes of the offset.
+const CHANGE_TOLERANCE: u32 = 1_000;
+
+pub fn in_unit(unit: Unit) -> BigInt {
+    let system_time = system::time_in_unit(unit);
+    let monotonic_time = monotonic::time_in_unit(unit);
+
+    system_time - monotonic_time
+}
+
+/// Monitors the time offset from `process`.  Returns the monitor reference.
+pub fn monitor(process: &Process) -> Term {
+    let reference = process.next_reference();
+    let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
+
+    MUTEX_OPTION_NOTIFIED_OFFSET
+        .lock()
+        .get_or_insert_with(|| in_unit(Unit::Native));
+    MONITORING_PID_BY_REFERENCE.insert(reference_reference.as_ref().clone(), process.pid());
+
+    reference
+}
+
+/// Returns whether `reference` was a time offset monitor of `monitoring_pid`.  Monitors of other
+/// processes are left in place.
+pub fn demonitor(monitoring_pid: Pid, reference: &Reference) -> bool {
+    MONITORING_PID_BY_REFERENCE
+        .remove_if(reference, |_, pid| *pid == monitoring_pid)
+        .is_some()
+}
+
+/// Sends `{'CHANGE', MonitorReference, time_offset, clock_service, NewTimeOffset}` to the
+/// monitoring processes when the time offset changed since they were last notified.
+///
+/// Called by the schedulers whenever they check for timeouts.
+pub fn check() {
+    if MONITORING_PID_BY_REFERENCE.is_empty() {
+        return;
+    }
+
+    let offset = in_unit(Unit::Native);
+
+    let changed = {
+        let mut option_notified_offset = MUTEX_OPTION_NOTIFIED_OFFSET.lock();
+
+        match &*option_notified_offset {
+            Some(notified_offset)
+                if (&offset - notified_offset).abs() < BigInt::from(CHANGE_TOLERANCE) =>
+            {
+                false
+            }
+            _ => {
+                *option_notified_offset = Some(offset.clone());
+
+                true
+            }
+        }
+    };
+
+    if changed {
+        for entry in MONITORING_PID_BY_REFERENCE.iter() {
+            if let Some(monitoring_arc_process) = pid_to_process(entry.value()) {
+                send_change_message(&monitoring_arc_process, entry.key(), &offset);
+            }
+        }
+    }
+}
+
+/// Removes the time offset monitors of the exiting `process`
+pub fn propagate_exit(process: &Process) {
+    let pid = process.pid();
+
+    MONITORING_PID_BY_REFERENCE.retain(|_, monitoring_pid| *monitoring_pid != pid);
+}
+
+// Private
+
+fn send_change_message(monitoring_process: &Process, reference: &Reference, offset: &BigInt) {
+    let (layout, _) = Tuple::layout_for_len(5)
+        .extend(Reference::layout())
+        .unwrap();
+    let (layout, _) = layout.extend(Layout::new::<BigInteger>()).unwrap();
+    let mut non_null_heap_fragment = HeapFragment::new(layout).unwrap();
+    let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+    let reference_term = reference.clone_to_heap(heap_fragment).unwrap();
+    let offset_term = heap_fragment.integer(offset.clone()).unwrap();
+    let change = heap_fragment
+        .tuple_from_slice(&[
+            atom!("CHANGE"),
+            reference_term,
+            atom!("time_offset"),
+            atom!("clock_service"),
+            offset_term,
+        ])
+        .unwrap();
+
+    monitoring_process.send_heap_message(non_null_heap_fragment, change.into());
+    monitoring_process
+        .scheduler()
+        .unwrap()
+        .stop_waiting(monitoring_process);
+}
@@ -111,6 +111,25 @@ impl Hierarchy {
             })
     }
 
+    /// The earliest time at which a timer in the hierarchy will time out, so that an idle
+    /// scheduler knows how long it can sleep.
+    ///
+    /// Slots are sorted, so only the first timer in `at_once`, `long_term` and the next occupied
+    /// slot of each wheel need to be checked.
+    ///
+    /// Returns `None` if there are no timers.
+    pub fn next_deadline(&self) -> Option<Monotonic> {
+        [
+            self.at_once.first_monotonic(),
+            self.soon.next_monotonic(),
+            self.later.next_monotonic(),
+            self.long_term.first_monotonic(),
+        ]
+        .iter()
+        .filter_map(|option_monotonic| *option_monotonic)
+        .min()
+    }
+
     fn position(&self, monotonic: Monotonic) -> Position {
         if monotonic < self.soon.slot_monotonic {
             Position::AtOnce
@@ -288,8 +307,7 @@ impl Hierarchy {
     }
 
     fn transfer_later_to_soon(&mut self, soon_max_monotonic: Monotonic) {
-        let transferable_arc_timers: Vec<Arc<Timer>> =
-            self.later.drain_before_or_at(soon_max_monotonic).collect();
+        let transferable_arc_timers = self.later.drain_before_or_at(soon_max_monotonic);
 
         for arc_timer in transferable_arc_timers {
             self.transfer(arc_timer, WheelName::Soon)
@@ -609,6 +627,10 @@ impl Slot {
         self.0.drain(0..exclusive_end_bound)
     }
 
+    fn first_monotonic(&self) -> Option<Monotonic> {
+        self.0.first().map(|arc_timer| arc_timer.monotonic)
+    }
+
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -690,6 +712,9 @@ struct Wheel {
     milliseconds_per_slot: MillisecondsPerSlot,
     total_milliseconds: Milliseconds,
     slots: Vec<Slot>,
+    // A bit per slot that is set when the slot has timers, so that the next timer can be found
+    // without checking every slot.
+    occupied: Vec<u64>,
     slot_index: SlotIndex,
     slot_monotonic: Monotonic,
 }
@@ -697,6 +722,7 @@ struct Wheel {
 impl Wheel {
     // same as values used in BEAM
     const SLOTS: Slots = Slots(1 << 14);
+    const SLOTS_PER_OCCUPIED_WORD: usize = 64;
 
     fn new(
         milliseconds_per_slot: MillisecondsPerSlot,
@@ -707,6 +733,7 @@ impl Wheel {
             milliseconds_per_slot,
             total_milliseconds: milliseconds_per_slot * Self::SLOTS,
             slots: vec![Default::default(); Self::SLOTS.0 as usize],
+            occupied: vec![0; (Self::SLOTS.0 as usize) / Self::SLOTS_PER_OCCUPIED_WORD],
             slot_index,
             slot_monotonic,
         }
@@ -717,18 +744,31 @@ impl Wheel {
         slot_index: SlotIndex,
         reference_number: ReferenceNumber,
     ) -> Option<Arc<Timer>> {
-        self.slots[slot_index.0 as usize].cancel(reference_number)
+        let option_arc_timer = self.slots[slot_index.0 as usize].cancel(reference_number);
+        self.update_occupied(slot_index);
+
+        option_arc_timer
     }
 
-    fn drain<R>(&mut self, range: R) -> Drain<Arc<Timer>>
+    fn drain<R>(&mut self, range: R) -> Vec<Arc<Timer>>
     where
         R: RangeBounds<usize>,
     {
-        self.slots[self.slot_index.0 as usize].drain(range)
+        let slot_index = self.slot_index;
+        let drained = self.slots[slot_index.0 as usize].drain(range).collect();
+        self.update_occupied(slot_index);
+
+        drained
     }
 
-    fn drain_before_or_at(&mut self, max_monotonic: Monotonic) -> Drain<Arc<Timer>> {
-        self.slots[self.slot_index.0 as usize].drain_before_or_at(max_monotonic)
+    fn drain_before_or_at(&mut self, max_monotonic: Monotonic) -> Vec<Arc<Timer>> {
+        let slot_index = self.slot_index;
+        let drained = self.slots[slot_index.0 as usize]
+            .drain_before_or_at(max_monotonic)
+            .collect();
+        self.update_occupied(slot_index);
+
+        drained
     }
 
     fn is_empty(&self) -> bool {
@@ -757,8 +797,59 @@ impl Wheel {
         (self.slot_index + slots) % Wheel::SLOTS
     }
 
+    /// The time of the first timer in the first slot, at or after the current slot, that has
+    /// timers.
+    fn next_monotonic(&self) -> Option<Monotonic> {
+        self.next_occupied_slot_index()
+            .and_then(|slot_index| self.slots[slot_index.0 as usize].first_monotonic())
+    }
+
+    /// Searches `occupied` a word at a time, wrapping around to the slots before the current
+    /// slot, like the BEAM does instead of checking each slot.
+    fn next_occupied_slot_index(&self) -> Option<SlotIndex> {
+        let words = self.occupied.len();
+        let start = self.slot_index.0 as usize;
+        let start_word_index = start / Self::SLOTS_PER_OCCUPIED_WORD;
+        let start_bit = start % Self::SLOTS_PER_OCCUPIED_WORD;
+
+        for offset in 0..=words {
+            let word_index = (start_word_index + offset) % words;
+            let mut word = self.occupied[word_index];
+
+            if offset == 0 {
+                // only the current slot and those after it
+                word &= !0 << start_bit;
+            } else if offset == words {
+                // wrapped around, so only the slots before the current slot
+                word &= !(!0 << start_bit);
+            }
+
+            if word != 0 {
+                let index =
+                    word_index * Self::SLOTS_PER_OCCUPIED_WORD + (word.trailing_zeros() as usize);
+
+                return Some(SlotIndex(index as u16));
+            }
+        }
+
+        None
+    }
+
     fn start(&mut self, slot_index: SlotIndex, arc_timer: Arc<Timer>) {
-        self.slots[slot_index.0 as usize].start(arc_timer)
+        self.slots[slot_index.0 as usize].start(arc_timer);
+        self.update_occupied(slot_index);
+    }
+
+    fn update_occupied(&mut self, slot_index: SlotIndex) {
+        let index = slot_index.0 as usize;
+        let word_index = index / Self::SLOTS_PER_OCCUPIED_WORD;
+        let bit = 1 << (index % Self::SLOTS_PER_OCCUPIED_WORD);
+
+        if self.slots[index].is_empty() {
+            self.occupied[word_index] &= !bit;
+        } else {
+            self.occupied[word_index] |= bit;
+        }
     }
 }
 
@@ -849,3 +940,201 @@ pub fn long_term_milliseconds() -> Milliseconds {
 
     milliseconds
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod hierarchy {
+        use super::*;
+
+        #[test]
+        fn without_timers_has_no_next_deadline() {
+            let hierarchy = Hierarchy::default();
+
+            assert_eq!(hierarchy.next_deadline(), None);
+        }
+
+        #[test]
+        fn next_deadline_is_earliest_soon_timer() {
+            let mut hierarchy = Hierarchy::default();
+            let base = hierarchy.soon.slot_monotonic;
+
+            start(&mut hierarchy, 0, base + Milliseconds(5));
+            start(&mut hierarchy, 1, base + Milliseconds(3));
+            start(&mut hierarchy, 2, base + Milliseconds(3));
+
+            assert_eq!(hierarchy.next_deadline(), Some(base + Milliseconds(3)));
+        }
+
+        #[test]
+        fn next_deadline_is_later_timer_without_soon_timers() {
+            let mut hierarchy = Hierarchy::default();
+            let later = hierarchy.later.slot_monotonic + Milliseconds(10);
+
+            start(&mut hierarchy, 0, later);
+
+            assert_eq!(hierarchy.next_deadline(), Some(later));
+        }
+
+        #[test]
+        fn next_deadline_is_long_term_timer_without_wheel_timers() {
+            let mut hierarchy = Hierarchy::default();
+            let long_term = hierarchy.later.slot_monotonic
+                + Hierarchy::LATER_TOTAL_MILLISECONDS
+                + Milliseconds(1);
+
+            start(&mut hierarchy, 0, long_term);
+
+            assert_eq!(hierarchy.next_deadline(), Some(long_term));
+        }
+
+        #[test]
+        fn next_deadline_is_earliest_across_levels() {
+            let mut hierarchy = Hierarchy::default();
+            let soon = hierarchy.soon.slot_monotonic + Milliseconds(7);
+            let later = hierarchy.later.slot_monotonic + Milliseconds(10);
+            let long_term = hierarchy.later.slot_monotonic
+                + Hierarchy::LATER_TOTAL_MILLISECONDS
+                + Milliseconds(1);
+
+            start(&mut hierarchy, 0, long_term);
+            start(&mut hierarchy, 1, later);
+            start(&mut hierarchy, 2, soon);
+
+            assert_eq!(hierarchy.next_deadline(), Some(soon));
+        }
+
+        #[test]
+        fn cancel_moves_next_deadline_to_next_timer() {
+            let mut hierarchy = Hierarchy::default();
+            let first = hierarchy.soon.slot_monotonic + Milliseconds(1);
+            let second = hierarchy.later.slot_monotonic + Milliseconds(10);
+
+            start(&mut hierarchy, 0, first);
+            start(&mut hierarchy, 1, second);
+
+            assert_eq!(hierarchy.next_deadline(), Some(first));
+
+            assert!(hierarchy.cancel(0).is_some());
+
+            assert_eq!(hierarchy.next_deadline(), Some(second));
+
+            assert!(hierarchy.cancel(1).is_some());
+
+            assert_eq!(hierarchy.next_deadline(), None);
+        }
+
+        fn start(
+            hierarchy: &mut Hierarchy,
+            reference_number: ReferenceNumber,
+            monotonic: Monotonic,
+        ) {
+            let position = hierarchy.position(monotonic);
+            let arc_timer = timer(reference_number, monotonic, position);
+
+            hierarchy
+                .timer_by_reference_number
+                .insert(reference_number, Arc::downgrade(&arc_timer));
+
+            match position {
+                Position::AtOnce => hierarchy.at_once.start(arc_timer),
+                Position::Soon { slot_index } => hierarchy.soon.start(slot_index, arc_timer),
+                Position::Later { slot_index } => hierarchy.later.start(slot_index, arc_timer),
+                Position::LongTerm => hierarchy.long_term.start(arc_timer),
+            }
+        }
+    }
+
+    mod wheel {
+        use super::*;
+
+        #[test]
+        fn without_timers_has_no_next_monotonic() {
+            let wheel = wheel(SlotIndex(0));
+
+            assert_eq!(wheel.next_occupied_slot_index().map(|index| index.0), None);
+            assert_eq!(wheel.next_monotonic(), None);
+        }
+
+        #[test]
+        fn next_monotonic_wraps_around_to_slots_before_current_slot() {
+            let current_slot_index = SlotIndex(Wheel::SLOTS.0 - 2);
+            let mut wheel = wheel(current_slot_index);
+            let monotonic = wheel.slot_monotonic + Milliseconds(5);
+            let slot_index = wheel.slot_index(monotonic);
+
+            assert_eq!(slot_index.0, 3);
+
+            wheel.start(
+                slot_index,
+                timer(0, monotonic, Position::Soon { slot_index }),
+            );
+
+            assert_eq!(
+                wheel.next_occupied_slot_index().map(|index| index.0),
+                Some(3)
+            );
+            assert_eq!(wheel.next_monotonic(), Some(monotonic));
+        }
+
+        #[test]
+        fn next_monotonic_prefers_slots_after_current_slot_over_wrapped_slots() {
+            let current_slot_index = SlotIndex(100);
+            let mut wheel = wheel(current_slot_index);
+            let sooner = wheel.slot_monotonic + Milliseconds(200);
+            let later = wheel.slot_monotonic + Milliseconds((Wheel::SLOTS.0 - 10) as u64);
+
+            for (reference_number, monotonic) in [(0, later), (1, sooner)].iter() {
+                let slot_index = wheel.slot_index(*monotonic);
+                wheel.start(
+                    slot_index,
+                    timer(*reference_number, *monotonic, Position::Soon { slot_index }),
+                );
+            }
+
+            assert_eq!(wheel.next_monotonic(), Some(sooner));
+        }
+
+        #[test]
+        fn draining_current_slot_clears_it() {
+            let current_slot_index = SlotIndex(63);
+            let mut wheel = wheel(current_slot_index);
+            let monotonic = wheel.slot_monotonic;
+
+            wheel.start(
+                current_slot_index,
+                timer(
+                    0,
+                    monotonic,
+                    Position::Soon {
+                        slot_index: current_slot_index,
+                    },
+                ),
+            );
+
+            assert_eq!(wheel.next_monotonic(), Some(monotonic));
+            assert_eq!(wheel.drain(..).len(), 1);
+            assert_eq!(wheel.next_monotonic(), None);
+        }
+
+        fn wheel(slot_index: SlotIndex) -> Wheel {
+            Wheel::new(MillisecondsPerSlot(1), slot_index, Monotonic(1_000_000))
+        }
+    }
+
+    fn timer(
+        reference_number: ReferenceNumber,
+        monotonic: Monotonic,
+        position: Position,
+    ) -> Arc<Timer> {
+        Arc::new(Timer {
+            reference_number,
+            monotonic,
+            event: DestinationEvent::StopWaiting {
+                process: Weak::new(),
+            },
+            position: Mutex::new(position),
+        })
+    }
+}
@@ -0,0 +1,329 @@
+//! Tracing of processes with `erlang:trace/3`.  Trace events are sent as
+//! `{trace, Pid, Tag, ...}` messages to the tracer of the traced process, or as
+//! `{trace_ts, Pid, Tag, ..., Timestamp}` when the `timestamp` flag is set.
+//!
+//! See http://erlang.org/doc/man/erlang.html#trace-3
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::apply;
+use liblumen_alloc::erts::exception::RuntimeException;
+use liblumen_alloc::erts::process::{set_trace_event_handler, TraceEvent, TraceFlags, Tracing};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::time::Milliseconds;
+use liblumen_alloc::{atom, Arity, ModuleFunctionArity, Process};
+
+use crate::registry::pid_to_process;
+use crate::scheduler::Scheduled;
+use crate::time::system;
+
+lazy_static! {
+    static ref RW_LOCK_TRACE_PATTERNS: RwLock<Vec<(TracePattern, CallTrace)>> = Default::default();
+}
+
+/// A `{Module, Function, Arity}` pattern of `erlang:trace_pattern/3`, where `None` is `'_'`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TracePattern {
+    pub module: Option<Atom>,
+    pub function: Option<Atom>,
+    pub arity: Option<Arity>,
+}
+impl TracePattern {
+    pub fn matches(&self, module_function_arity: &ModuleFunctionArity) -> bool {
+        self.module
+            .map_or(true, |module| module == module_function_arity.module)
+            && self
+                .function
+                .map_or(true, |function| function == module_function_arity.function)
+            && self
+                .arity
+                .map_or(true, |arity| arity == module_function_arity.arity)
+    }
+
+    /// Whether every function matched by `other` is also matched by `self`
+    fn covers(&self, other: &Self) -> bool {
+        (self.module.is_none() || self.module == other.module)
+            && (self.function.is_none() || self.function == other.function)
+            && (self.arity.is_none() || self.arity == other.arity)
+    }
+}
+
+/// Sets the tracer and flags of `process`, or turns tracing off when `flags` is empty.  Returns
+/// the old tracing.
+pub fn trace(process: &Process, tracing: Option<Tracing>) -> Option<Tracing> {
+    set_trace_event_handler(handle_trace_event);
+
+    process.set_tracing(tracing.filter(|tracing| !tracing.flags.is_empty()))
+}
+
+/// What is traced, besides the call itself, for calls to the functions matching a
+/// `TracePattern`, as set by the match specification of `erlang:trace_pattern/3`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallTrace {
+    /// `{return_trace}`: returning from the function is traced with `return_from`
+    pub return_trace: bool,
+    /// `{exception_trace}`: like `return_trace`, but exceptions are also traced with
+    /// `exception_from`
+    pub exception_trace: bool,
+}
+
+/// Enables call tracing for the functions matching `trace_pattern` when `call_trace` is `Some`, or
+/// disables it when `None`.  Enabling replaces the `CallTrace` of the functions matching
+/// `trace_pattern`.  Returns the number of loaded functions matching `trace_pattern`.
+pub fn set_trace_pattern(trace_pattern: TracePattern, call_trace: Option<CallTrace>) -> usize {
+    let mut trace_patterns = RW_LOCK_TRACE_PATTERNS.write();
+
+    trace_patterns.retain(|(existing, _)| !trace_pattern.covers(existing));
+
+    if let Some(call_trace) = call_trace {
+        trace_patterns.push((trace_pattern, call_trace));
+    }
+
+    apply::count_symbols(|module_function_arity| trace_pattern.matches(module_function_arity))
+}
+
+pub fn is_call_traced(module_function_arity: &ModuleFunctionArity) -> bool {
+    call_trace(module_function_arity).is_some()
+}
+
+/// The `CallTrace` of the most recently set `TracePattern` matching `module_function_arity`
+pub fn call_trace(module_function_arity: &ModuleFunctionArity) -> Option<CallTrace> {
+    RW_LOCK_TRACE_PATTERNS
+        .read()
+        .iter()
+        .rev()
+        .find(|(trace_pattern, _)| trace_pattern.matches(module_function_arity))
+        .map(|(_, call_trace)| *call_trace)
+}
+
+/// `{trace, Pid, send, Message, To}`
+pub fn send(sender: &Process, destination: Term, message: Term) {
+    if let Some(tracing) = sender.tracing_with_flags(TraceFlags::Send) {
+        send_trace_message(sender, tracing, atom!("send"), &[message, destination]);
+    }
+}
+
+/// `{trace, Pid, 'receive', Message}`
+pub fn receive(receiver: &Process, message: Term) {
+    if let Some(tracing) = receiver.tracing_with_flags(TraceFlags::Receive) {
+        send_trace_message(receiver, tracing, atom!("receive"), &[message]);
+    }
+}
+
+/// `{trace, Pid, spawn, Pid2, {Module, Function, Arity}}`.  Unlike the BEAM, the arity is given
+/// instead of the arguments, as the arguments are already on the stack of `child`.
+pub fn spawn(parent: &Process, child: &Process) {
+    if let Some(tracing) = parent.tracing_with_flags(TraceFlags::Procs) {
+        let module_function_arity =
+            module_function_arity_to_term(parent, child.initial_module_function_arity);
+
+        send_trace_message(
+            parent,
+            tracing,
+            atom!("spawn"),
+            &[child.pid_term(), module_function_arity],
+        );
+    }
+}
+
+/// `{trace, Pid, exit, Reason}`
+pub fn exit(process: &Process, exception: Option<&RuntimeException>) {
+    if let Some(tracing) = process.tracing_with_flags(TraceFlags::Procs) {
+        let reason = match exception {
+            Some(exception) => exception.reason(),
+            None => atom!("normal"),
+        };
+
+        send_trace_message(process, tracing, atom!("exit"), &[reason]);
+    }
+}
+
+/// `{trace, Pid, in | out, {Module, Function, Arity}}` when `process` is scheduled in or out
+pub fn running(process: &Process, scheduled_in: bool) {
+    if let Some(tracing) = process.tracing_with_flags(TraceFlags::Running) {
+        let tag = if scheduled_in {
+            atom!("in")
+        } else {
+            atom!("out")
+        };
+        let module_function_arity = process
+            .current_module_function_arity()
+            .unwrap_or(process.initial_module_function_arity);
+        let module_function_arity_term =
+            module_function_arity_to_term(process, module_function_arity);
+
+        send_trace_message(process, tracing, tag, &[module_function_arity_term]);
+    }
+}
+
+// Private
+
+fn handle_trace_event(process: &Process, tracing: Tracing, event: &TraceEvent) {
+    match event {
+        TraceEvent::Call {
+            module_function_arity,
+            arguments,
+        } => {
+            if is_call_traced(module_function_arity) {
+                let call = process.tuple_from_slice(&[
+                    module_function_arity.module.encode().unwrap(),
+                    module_function_arity.function.encode().unwrap(),
+                    process.list_from_slice(arguments),
+                ]);
+
+                send_trace_message(process, tracing, atom!("call"), &[call]);
+            }
+        }
+        TraceEvent::ReturnTo {
+            module_function_arity,
+        } => {
+            if is_call_traced(module_function_arity) {
+                let return_to = module_function_arity_to_term(process, *module_function_arity);
+
+                send_trace_message(process, tracing, atom!("return_to"), &[return_to]);
+            }
+        }
+        TraceEvent::ReturnFrom {
+            module_function_arity,
+            returned,
+        } => {
+            if call_trace(module_function_arity).map_or(false, |call_trace| {
+                call_trace.return_trace || call_trace.exception_trace
+            }) {
+                let return_from = module_function_arity_to_term(process, *module_function_arity);
+
+                send_trace_message(
+                    process,
+                    tracing,
+                    atom!("return_from"),
+                    &[return_from, *returned],
+                );
+            }
+        }
+        TraceEvent::ExceptionFrom {
+            module_function_arity,
+            class,
+            reason,
+        } => {
+            if call_trace(module_function_arity)
+                .map_or(false, |call_trace| call_trace.exception_trace)
+            {
+                let exception_from = module_function_arity_to_term(process, *module_function_arity);
+                let class_reason =
+                    process.tuple_from_slice(&[class.as_atom().encode().unwrap(), *reason]);
+
+                send_trace_message(
+                    process,
+                    tracing,
+                    atom!("exception_from"),
+                    &[exception_from, class_reason],
+                );
+            }
+        }
+        TraceEvent::GarbageCollectionStart { major, heap_size } => {
+            let tag = if *major {
+                atom!("gc_major_start")
+            } else {
+                atom!("gc_minor_start")
+            };
+            let info = garbage_collection_info(process, *heap_size);
+
+            send_trace_message(process, tracing, tag, &[info]);
+        }
+        TraceEvent::GarbageCollectionEnd { major, heap_size } => {
+            let tag = if *major {
+                atom!("gc_major_end")
+            } else {
+                atom!("gc_minor_end")
+            };
+            let info = garbage_collection_info(process, *heap_size);
+
+            send_trace_message(process, tracing, tag, &[info]);
+        }
+        TraceEvent::Link(other) => {
+            send_trace_message(process, tracing, atom!("link"), &[other.encode().unwrap()])
+        }
+        TraceEvent::GettingLinked(other) => send_trace_message(
+            process,
+            tracing,
+            atom!("getting_linked"),
+            &[other.encode().unwrap()],
+        ),
+        TraceEvent::Unlink(other) => send_trace_message(
+            process,
+            tracing,
+            atom!("unlink"),
+            &[other.encode().unwrap()],
+        ),
+        TraceEvent::GettingUnlinked(other) => send_trace_message(
+            process,
+            tracing,
+            atom!("getting_unlinked"),
+            &[other.encode().unwrap()],
+        ),
+    }
+}
+
+/// `[{heap_size, HeapSize}]`
+fn garbage_collection_info(process: &Process, heap_size: usize) -> Term {
+    let heap_size_tuple =
+        process.tuple_from_slice(&[atom!("heap_size"), process.integer(heap_size)]);
+
+    process.list_from_slice(&[heap_size_tuple])
+}
+
+fn module_function_arity_to_term(
+    process: &Process,
+    module_function_arity: ModuleFunctionArity,
+) -> Term {
+    process.tuple_from_slice(&[
+        module_function_arity.module.encode().unwrap(),
+        module_function_arity.function.encode().unwrap(),
+        process.integer(module_function_arity.arity),
+    ])
+}
+
+/// Sends `{trace, Pid, Tag, Elements...}` to the tracer of `process`, or
+/// `{trace_ts, Pid, Tag, Elements..., {MegaSecs, Secs, MicroSecs}}` when the `timestamp` flag is
+/// set.
+fn send_trace_message(process: &Process, tracing: Tracing, tag: Term, elements: &[Term]) {
+    let timestamp = tracing.flags.are_set(TraceFlags::Timestamp);
+
+    let mut trace_message_elements = Vec::with_capacity(elements.len() + 4);
+    trace_message_elements.push(if timestamp {
+        atom!("trace_ts")
+    } else {
+        atom!("trace")
+    });
+    trace_message_elements.push(process.pid_term());
+    trace_message_elements.push(tag);
+    trace_message_elements.extend_from_slice(elements);
+
+    if timestamp {
+        trace_message_elements.push(now(process));
+    }
+
+    let trace_message = process.tuple_from_slice(&trace_message_elements);
+
+    if tracing.tracer == process.pid() {
+        process.send_from_self(trace_message);
+    } else if let Some(tracer_arc_process) = pid_to_process(&tracing.tracer) {
+        tracer_arc_process.send_from_other(trace_message);
+        tracer_arc_process
+            .scheduler()
+            .unwrap()
+            .stop_waiting(&tracer_arc_process);
+    }
+}
+
+fn now(process: &Process) -> Term {
+    let milliseconds: Milliseconds = system::time().into();
+    let microseconds = milliseconds.0 * 1_000;
+
+    process.tuple_from_slice(&[
+        process.integer(microseconds / 1_000_000_000_000),
+        process.integer((microseconds / 1_000_000) % 1_000_000),
+        process.integer(microseconds % 1_000_000),
+    ])
+}
@@ -1,17 +1,11 @@
-use std::collections::HashMap;
-use std::ffi::{OsStr, OsString};
-use std::fs;
-use std::io;
-use std::path::Path;
-
 use clap::{App, AppSettings, Arg, SubCommand};
 
-pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
-//TODO: Needs to be HashMap<Atom, HashMap<Atom, Term>>
-pub type AppConfig = HashMap<String, HashMap<String, String>>;
-pub type BootScript = Vec<BootInstruction>;
-//TODO: Needs to be Term
-pub type BootInstruction = String;
+use lumen_rt_core::config::vm_args::{self, VmArgs};
+use lumen_rt_core::config::{self, load_app_config, load_boot_script, load_vm_args};
+
+pub use lumen_rt_core::config::boot_script::BootScript;
+pub use lumen_rt_core::config::sys_config::AppConfig;
+pub use lumen_rt_core::config::{ConfigError, ConfigResult};
 
 pub enum Command {
     Run,
@@ -19,38 +13,12 @@ pub enum Command {
     RemoteShell(String),
 }
 
-#[derive(Debug)]
-pub enum ConfigError {
-    FileError(OsString, io::Error),
-}
-
-impl std::fmt::Display for ConfigError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match *self {
-            ConfigError::FileError(ref path, ref err) => write!(
-                f,
-                "Failed to load {}: {}",
-                path.to_string_lossy(),
-                err.to_string()
-            ),
-        }
-    }
-}
-
-impl std::error::Error for ConfigError {
-    fn cause(&self) -> Option<&dyn std::error::Error> {
-        match *self {
-            ConfigError::FileError(ref _path, ref err) => Some(err),
-        }
-    }
-}
-
 pub struct Config {
     pub config: AppConfig,
     pub boot: Option<BootScript>,
     pub debug: bool,
-    pub name: Option<String>,
-    pub cookie: Option<String>,
+    /// The flags from `--args_file` files, overridden by those given on the command line
+    pub vm_args: VmArgs,
     pub command: Command,
     pub extra: Vec<String>,
 }
@@ -79,6 +47,12 @@ impl Config {
             .arg(Arg::with_name("debug")
                      .long("debug")
                      .help("Enable debug output from the runtime"))
+            .arg(Arg::with_name("schedulers")
+                     .long("schedulers")
+                     .help("The number of scheduler threads to run\n\
+                            If not provided, one scheduler is started per logical CPU")
+                     .takes_value(true)
+                     .validator(is_valid_scheduler_count))
             .arg(Arg::with_name("name")
                      .long("name")
                      .global(true)
@@ -122,20 +96,31 @@ impl Config {
             };
             command = Command::Run;
         }
+        let mut args = load_vm_args(matches.values_of_os("args_file").into_iter().flatten())?;
+        // Flags given on the command line override those in `vm.args`
+        args.merge(VmArgs {
+            name: matches.value_of("name").map(vm_args::node_name),
+            cookie: matches.value_of("cookie").map(|v| v.to_string()),
+            schedulers: matches
+                .value_of("schedulers")
+                .map(|v| v.parse::<usize>().unwrap()),
+            ..Default::default()
+        });
+
         Ok(Config {
-            config: with_file(
-                matches.value_of_os("config"),
-                AppConfig::new(),
-                load_app_config,
-            )?,
-            boot: with_file(matches.value_of_os("boot"), None, load_boot_script)?,
+            config: load_app_config(matches.values_of_os("config").into_iter().flatten())?,
+            boot: load_boot_script(matches.value_of_os("boot"))?,
             debug: matches.is_present("debug"),
-            name: matches.value_of("name").map(|v| v.to_string()),
-            cookie: matches.value_of("cookie").map(|v| v.to_string()),
+            vm_args: args,
             command,
             extra: extra.iter().map(|v| v.to_string()).collect(),
         })
     }
+
+    /// Applies the configuration to the runtime.  Must be called before any process is spawned.
+    pub fn apply(&self) {
+        config::apply(&self.vm_args, &self.config, self.boot.as_ref());
+    }
 }
 
 fn is_valid_node_name(_f: String) -> Result<(), String> {
@@ -143,23 +128,10 @@ fn is_valid_node_name(_f: String) -> Result<(), String> {
     Ok(())
 }
 
-fn with_file<T>(v: Option<&OsStr>, default: T, fun: fn(String) -> T) -> ConfigResult<T> {
-    match v {
-        None => Ok(default),
-        Some(p) => {
-            let path = Path::new(p);
-            match fs::read_to_string(path) {
-                Err(err) => Err(ConfigError::FileError(p.to_os_string(), err)),
-                Ok(contents) => Ok(fun(contents)),
-            }
-        }
+fn is_valid_scheduler_count(v: String) -> Result<(), String> {
+    match v.parse::<usize>() {
+        Ok(0) => Err("there must be at least one scheduler".to_string()),
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
     }
 }
-
-fn load_app_config(_contents: String) -> AppConfig {
-    AppConfig::new()
-}
-
-fn load_boot_script(_contents: String) -> Option<BootScript> {
-    None
-}
@@ -39,7 +39,8 @@ extern crate cfg_if;
 extern crate chrono;
 
 pub use lumen_rt_core::{
-    binary_to_string, context, distribution, proplist, registry, send, test, time, timer,
+    application, binary_to_string, boot, context, distribution, ets, port, proplist, registry,
+    send, seq_trace, test, time, timer, trace,
 };
 
 #[cfg(not(any(test, target_arch = "wasm32")))]
@@ -75,16 +76,24 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
     use self::sys::break_handler::{self, Signal};
     use bus::Bus;
     use log::Level;
-    use std::thread;
 
     // Load system configuration
-    let _config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
+    let config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Config error: {}", err);
             return Err(());
         }
     };
+    config.apply();
+
+    // Named nodes listen for connections from other nodes
+    if config.vm_args.name.is_some() {
+        if let Err(err) = distribution::start() {
+            eprintln!("Distribution error: {}", err);
+            return Err(());
+        }
+    }
 
     // This bus is used to receive signals across threads in the system
     let mut bus: Bus<break_handler::Signal> = Bus::new(1);
@@ -96,8 +105,22 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
     // Start logger
     Logger::init(Level::Info).expect("Unexpected failure initializing logger");
 
+    // The main thread runs the primary scheduler, the rest of the schedulers run on their own
+    // threads and steal work from the primary scheduler when idle
     let scheduler = scheduler::current();
-    loop {
+    let schedulers = config
+        .vm_args
+        .schedulers
+        .unwrap_or_else(|| sys::host::cpus::num_logical().max(1));
+    let pool = match scheduler::Pool::start(schedulers) {
+        Ok(pool) => pool,
+        Err(err) => {
+            eprintln!("Failed to start schedulers: {}", err);
+            return Err(());
+        }
+    };
+
+    let result = loop {
         // Run the scheduler for a cycle
         let scheduled = scheduler.run_once();
         // Check for system signals, and terminate if needed
@@ -108,9 +131,9 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
                     // If an error occurs, report it before shutdown
                     if let Err(err) = scheduler.shutdown() {
                         eprintln!("System error: {}", err);
-                        return Err(());
+                        break Err(());
                     } else {
-                        break;
+                        break Ok(());
                     }
                 }
                 // Technically, we may never see these signals directly,
@@ -118,7 +141,7 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
                 // we handle them explicitly by immediately terminating, so
                 // that we are good citizens of the operating system
                 sig if sig.should_terminate() => {
-                    return Err(());
+                    break Err(());
                 }
                 // All other signals can be surfaced to other parts of the
                 // system for custom use, e.g. SIGCHLD, SIGALRM, SIGUSR1/2
@@ -130,19 +153,12 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
         if scheduled {
             continue;
         }
-        // Otherwise,
-        // In some configurations, it makes more sense for us to spin and use
-        // spin_loop_hint here instead; namely when we're supposed to be the primary
-        // software on a system, and threads are pinned to cores, it makes no sense
-        // to yield to the system scheduler. However on a system with contention for
-        // system resources, or where threads aren't pinned to cores, we're better off
-        // explicitly yielding to the scheduler, rather than waiting to be preempted at
-        // a potentially inopportune time.
-        //
-        // In any case, for now, we always explicitly yield until we've got proper support
-        // for configuring the system
-        thread::yield_now()
-    }
+        // Otherwise, sleep until there is work to steal, a timer deadline, or a signal, instead
+        // of burning a core while idle
+        scheduler::park_current();
+    };
+
+    pool.shutdown();
 
-    Ok(())
+    result
 }
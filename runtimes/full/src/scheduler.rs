@@ -1,19 +1,29 @@
+mod pool;
+#[cfg(test)]
+mod test;
+
+pub use self::pool::Pool;
+
 use std::any::Any;
 use std::convert::TryInto;
 use std::ffi::c_void;
 use std::fmt::{self, Debug};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use liblumen_core::locks::RwLock;
+use liblumen_core::locks::{Condvar, Mutex, RwLock};
 
 use liblumen_alloc::borrow::clone_to_process::CloneToProcess;
 use liblumen_alloc::erts::exception::SystemException;
+use liblumen_alloc::erts::process::gc::GcError;
 use liblumen_alloc::erts::process::{Frame, FrameWithArguments, Native, Priority, Process, Status};
 pub use liblumen_alloc::erts::scheduler::{id, ID};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::{Arity, ModuleFunctionArity, Ran};
 
+use lumen_rt_core::boot;
+use lumen_rt_core::process::spawn::check_system_limit;
 use lumen_rt_core::process::spawn::options::Options;
 use lumen_rt_core::process::{log_exit, propagate_exit, CURRENT_PROCESS};
 use lumen_rt_core::registry::put_pid_to_process;
@@ -21,7 +31,11 @@ pub use lumen_rt_core::scheduler::{
     current, from_id, run_through, Scheduled, SchedulerDependentAlloc, Spawned,
 };
 use lumen_rt_core::scheduler::{run_queue, unregister, Run, Scheduler as SchedulerTrait};
+use lumen_rt_core::statistics;
+use lumen_rt_core::time;
+use lumen_rt_core::time::monotonic;
 use lumen_rt_core::timer::Hierarchy;
+use lumen_rt_core::trace;
 
 use crate::process::out_of_code;
 
@@ -44,9 +58,31 @@ fn unregistered() -> Arc<dyn lumen_rt_core::scheduler::Scheduler> {
         reference_count: AtomicU64::new(0),
         run_queues: Default::default(),
         unique_integer: AtomicU64::new(0),
+        parked: AtomicBool::new(false),
+        woken: Mutex::new(false),
+        wake: Condvar::new(),
     })
 }
 
+/// Parks the current thread's scheduler until it may have work to do.  See `Scheduler::park`.
+pub fn park_current() {
+    current()
+        .as_any()
+        .downcast_ref::<Scheduler>()
+        .unwrap()
+        .park()
+}
+
+/// Wakes all schedulers that are parked, such as when a signal is received that they need to
+/// check for or when the pool is shutting down.
+pub fn wake_all() {
+    for arc_dyn_scheduler in lumen_rt_core::scheduler::all() {
+        if let Some(scheduler) = arc_dyn_scheduler.as_any().downcast_ref::<Scheduler>() {
+            scheduler.wake();
+        }
+    }
+}
+
 pub struct Scheduler {
     pub id: ID,
     pub hierarchy: RwLock<Hierarchy>,
@@ -56,6 +92,13 @@ pub struct Scheduler {
     // Non-monotonic unique integers are scoped to the scheduler ID and then use this per-scheduler
     // `u64`.
     unique_integer: AtomicU64,
+    // Whether the scheduler thread is blocked in `park`, so that schedulers with more work than
+    // they can run know which scheduler to wake to steal it.
+    parked: AtomicBool,
+    // Set when a process is enqueued or the scheduler is otherwise woken, so that a wake up that
+    // happens before the scheduler parks is not lost.
+    woken: Mutex<bool>,
+    wake: Condvar,
 }
 
 impl Scheduler {
@@ -70,8 +113,10 @@ impl Scheduler {
     /// > -- [The Scheduler Loop](https://blog.stenmans.org/theBeamBook/#_the_scheduler_loop)
     pub fn run(&self) {
         loop {
-            // TODO sleep or steal if nothing run
-            let _ = self.run_once();
+            // `run_once` already tried to steal, so sleep until there may be more work
+            if !self.run_once() {
+                self.park();
+            }
         }
     }
 
@@ -79,6 +124,136 @@ impl Scheduler {
         self.run_queues.read().contains(value)
     }
 
+    /// Wakes the scheduler if it is parked, or prevents it from parking on its next idle cycle if
+    /// it is not.
+    pub fn wake(&self) {
+        let mut woken = self.woken.lock();
+        *woken = true;
+        self.wake.notify_one();
+    }
+
+    /// Blocks the scheduler thread when it has nothing to run and nothing to steal, until the next
+    /// timer deadline or until it is woken by a process being enqueued, work to steal, a signal or
+    /// shutdown.
+    pub fn park(&self) {
+        let option_deadline = self.hierarchy.read().next_deadline();
+        let mut woken = self.woken.lock();
+
+        if !*woken {
+            self.parked.store(true, Ordering::SeqCst);
+
+            match option_deadline {
+                Some(deadline) => {
+                    // A deadline that has already passed will time out on the next cycle, so
+                    // there is no need to park
+                    if let Some(milliseconds) = deadline.checked_sub(monotonic::time()) {
+                        let timeout = Duration::from_millis(milliseconds.as_u64());
+                        self.wake.wait_for(&mut woken, timeout);
+                    }
+                }
+                None => self.wake.wait(&mut woken),
+            }
+
+            self.parked.store(false, Ordering::SeqCst);
+        }
+
+        *woken = false;
+    }
+
+    /// Called after a process is enqueued.  Wakes this scheduler in case it is parked, and, when
+    /// this scheduler has more runnable processes than it can run at once, a parked scheduler
+    /// that can steal them.
+    fn enqueued(&self) {
+        self.wake();
+
+        if 1 < self.run_queues.read().runnable_len() {
+            self.wake_parked_other();
+        }
+    }
+
+    fn wake_parked_other(&self) {
+        let option_parked = lumen_rt_core::scheduler::all()
+            .into_iter()
+            .filter(|arc_dyn_scheduler| arc_dyn_scheduler.id() != self.id)
+            .find(|arc_dyn_scheduler| {
+                arc_dyn_scheduler
+                    .as_any()
+                    .downcast_ref::<Scheduler>()
+                    .map(|scheduler| scheduler.parked.load(Ordering::SeqCst))
+                    .unwrap_or(false)
+            });
+
+        if let Some(arc_dyn_scheduler) = option_parked {
+            arc_dyn_scheduler
+                .as_any()
+                .downcast_ref::<Scheduler>()
+                .unwrap()
+                .wake();
+        }
+    }
+
+    /// Migrates a runnable process from the busiest other scheduler to this scheduler.
+    ///
+    /// Returns `true` if a process was stolen.
+    fn steal(&self) -> bool {
+        let victim = lumen_rt_core::scheduler::all()
+            .into_iter()
+            .filter(|arc_dyn_scheduler| arc_dyn_scheduler.id() != self.id)
+            .filter_map(|arc_dyn_scheduler| {
+                let runnable_len = arc_dyn_scheduler
+                    .as_any()
+                    .downcast_ref::<Scheduler>()
+                    .map(|scheduler| scheduler.run_queues.read().runnable_len())
+                    .unwrap_or(0);
+
+                if 0 < runnable_len {
+                    Some((runnable_len, arc_dyn_scheduler))
+                } else {
+                    None
+                }
+            })
+            .max_by_key(|(runnable_len, _)| *runnable_len)
+            .map(|(_, arc_dyn_scheduler)| arc_dyn_scheduler);
+
+        match victim {
+            Some(arc_dyn_scheduler) => {
+                let victim = arc_dyn_scheduler
+                    .as_any()
+                    .downcast_ref::<Scheduler>()
+                    .unwrap();
+
+                self.steal_from(victim)
+            }
+            None => false,
+        }
+    }
+
+    /// Migrates the runnable process that `victim` would run last to this scheduler.
+    ///
+    /// Returns `true` if a process was stolen.
+    fn steal_from(&self, victim: &Scheduler) -> bool {
+        // separate statement so that the victim's `WriteGuard` is released before this
+        // scheduler's run queues are locked, so two schedulers stealing from each other
+        // can't dead lock.
+        let option_stolen = victim.run_queues.write().steal();
+
+        match option_stolen {
+            Some(stolen) => {
+                stolen.arc_process().schedule_with(self.id);
+                self.run_queues.write().enqueue_stolen(stolen);
+
+                // The victim may still have more than it can run, so let another parked
+                // scheduler steal too.
+                if 1 < victim.run_queues.read().runnable_len() {
+                    victim.wake_parked_other();
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+
     fn runnable(process: &Process, frame_with_arguments: FrameWithArguments) {
         process.runnable(|| {
             process.queue_frame_with_arguments(frame_with_arguments);
@@ -182,6 +357,7 @@ impl SchedulerTrait for Scheduler {
 
     fn run_once(&self) -> bool {
         self.hierarchy.write().timeout();
+        time::offset::check();
 
         loop {
             // separate from `match` below so that WriteGuard temporary is not held while process
@@ -198,9 +374,32 @@ impl SchedulerTrait for Scheduler {
                     // Without this check, a process.exit() from outside the process during WAITING
                     // will return to the Frame that called `process.wait()`
                     if !arc_process.is_exiting() {
-                        match arc_process.run() {
-                            Ran::Waiting | Ran::Reduced | Ran::Exited | Ran::RuntimeException => (),
+                        let reductions_before = arc_process.total_reductions.load(Ordering::SeqCst);
+
+                        trace::running(&arc_process, true);
+                        let ran = arc_process.run();
+                        trace::running(&arc_process, false);
+
+                        match ran {
+                            // Collect between runs when forced, such as by
+                            // `erlang:garbage_collect/0`, as all roots are on the stack
+                            Ran::Waiting | Ran::Reduced => {
+                                match arc_process.garbage_collect_if_forced() {
+                                    Ok(reductions) => {
+                                        arc_process.total_reductions.fetch_add(
+                                            reductions.try_into().unwrap(),
+                                            Ordering::SeqCst,
+                                        );
+                                    }
+                                    Err(GcError::MaxHeapSizeExceeded) => arc_process.exit_killed(),
+                                    Err(gc_err) => {
+                                        panic!("fatal garbage collection error: {:?}", gc_err)
+                                    }
+                                }
+                            }
+                            Ran::Exited | Ran::RuntimeException => (),
                             Ran::SystemException => {
+                                let mut killed = false;
                                 let runnable = match &*arc_process.status.read() {
                                     Status::SystemException(system_exception) => {
                                         match system_exception {
@@ -218,6 +417,11 @@ impl SchedulerTrait for Scheduler {
                                                         // successful `garbage_collect`
                                                         true
                                                     }
+                                                    Err(GcError::MaxHeapSizeExceeded) => {
+                                                        killed = true;
+
+                                                        false
+                                                    }
                                                     Err(gc_err) => panic!(
                                                         "fatal garbage collection error: {:?}",
                                                         gc_err
@@ -230,12 +434,19 @@ impl SchedulerTrait for Scheduler {
                                     _ => unreachable!(),
                                 };
 
+                                // Have to set after `match` where `ReadGuard` is held
                                 if runnable {
-                                    // Have to set after `match` where `ReadGuard` is held
                                     *arc_process.status.write() = Status::Runnable;
+                                } else if killed {
+                                    // Exceeded `max_heap_size`, so `requeue` propagates the exit
+                                    arc_process.exit_killed();
                                 }
                             }
                         }
+
+                        statistics::ran(
+                            arc_process.total_reductions.load(Ordering::SeqCst) - reductions_before,
+                        );
                     } else {
                         arc_process.reduce()
                     }
@@ -263,8 +474,15 @@ impl SchedulerTrait for Scheduler {
                     break true;
                 }
                 Run::Delayed => continue,
-                Run::Waiting => break true,
-                // TODO steal processes or sleep if nothing to steal
+                // Nothing is runnable here, so migrate work from a busier scheduler if possible
+                Run::Waiting | Run::None if self.steal() => continue,
+                Run::Waiting => {
+                    // Sleep until the next timer deadline or a wake up instead of spinning, then
+                    // return to the scheduler loop to check for signals and to re-enter from
+                    // `run_once` and time out timers to knock processes out of waiting.
+                    self.park();
+                    break true;
+                }
                 Run::None => break false,
             }
         }
@@ -292,6 +510,7 @@ impl SchedulerTrait for Scheduler {
 
         self.run_queues.write().enqueue(arc_process.clone());
         put_pid_to_process(&arc_process);
+        self.enqueued();
 
         arc_process
     }
@@ -312,13 +531,9 @@ impl SchedulerTrait for Scheduler {
         let mut options: Options = Default::default();
         options.min_heap_size = Some(minimum_heap_size);
 
-        let Spawned { arc_process, .. } = self.spawn_module_function_arguments(
-            None,
-            Atom::from_str("init"),
-            Atom::from_str("start"),
-            vec![],
-            options,
-        )?;
+        let (module, function) = boot::init_module_function();
+        let Spawned { arc_process, .. } =
+            self.spawn_module_function_arguments(None, module, function, vec![], options)?;
 
         Ok(arc_process)
     }
@@ -329,6 +544,7 @@ impl SchedulerTrait for Scheduler {
         closure: Boxed<Closure>,
         options: Options,
     ) -> anyhow::Result<Spawned> {
+        check_system_limit()?;
         let (heap, heap_size) = options.sized_heap()?;
         let priority = options.cascaded_priority(parent);
         let initial_module_function_arity = closure.module_function_arity();
@@ -364,6 +580,7 @@ impl SchedulerTrait for Scheduler {
         arguments: Vec<Term>,
         options: Options,
     ) -> anyhow::Result<Spawned> {
+        check_system_limit()?;
         let (heap, heap_size) = options.sized_heap()?;
         let priority = options.cascaded_priority(parent);
         let initial_module_function_arity = ModuleFunctionArity {
@@ -400,5 +617,6 @@ impl SchedulerTrait for Scheduler {
     fn stop_waiting(&self, process: &Process) {
         process.stop_waiting();
         self.run_queues.write().stop_waiting(process);
+        self.enqueued();
     }
 }
@@ -0,0 +1,69 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use super::{current, park_current};
+
+/// The additional scheduler threads started alongside the primary (main thread) scheduler.
+///
+/// Each thread registers its own `Scheduler` with its own run queues the first time it calls
+/// `current()`.  Processes are spawned on the same scheduler as their parent, and idle
+/// schedulers steal runnable processes from the busiest scheduler, so work spreads across all
+/// threads.  Schedulers with nothing to run or steal park until they are woken by more work, a
+/// timer deadline or shutdown.
+pub struct Pool {
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl Pool {
+    /// Starts `count - 1` scheduler threads, as the calling thread is expected to run the primary
+    /// scheduler.
+    pub fn start(count: usize) -> io::Result<Self> {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut threads = Vec::with_capacity(count.saturating_sub(1));
+
+        for index in 1..count {
+            let thread_shutdown = shutdown.clone();
+            let thread = thread::Builder::new()
+                .name(format!("scheduler_{}", index))
+                .spawn(move || run(thread_shutdown))?;
+
+            threads.push(thread);
+        }
+
+        Ok(Self { shutdown, threads })
+    }
+
+    /// Stops all scheduler threads once they finish their current cycle and waits for them to
+    /// exit.
+    pub fn shutdown(self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Parked schedulers need to be woken to see `shutdown`.  A scheduler that is about to
+        // park won't block, as `woken` stays set until it parks.
+        super::wake_all();
+
+        for thread in self.threads {
+            if let Err(err) = thread.join() {
+                eprintln!("Scheduler thread panicked: {:?}", err);
+            }
+        }
+    }
+}
+
+fn run(shutdown: Arc<AtomicBool>) {
+    let scheduler = current();
+
+    while !shutdown.load(Ordering::SeqCst) {
+        // `run_once` already tried to steal from the other schedulers, so there is nothing to do
+        // but sleep until there may be more work.
+        if !scheduler.run_once() {
+            park_current();
+        }
+    }
+
+    if let Err(err) = scheduler.shutdown() {
+        eprintln!("System error: {}", err);
+    }
+}
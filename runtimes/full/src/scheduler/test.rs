@@ -0,0 +1,142 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::*;
+
+#[test]
+fn steal_from_migrates_runnable_process() {
+    let victim = scheduler_with_process_on_other_thread(Priority::Normal);
+    let victim = downcast(&victim);
+    let thief = current();
+    let thief = downcast(&thief);
+
+    assert!(thief.steal_from(victim));
+
+    assert_eq!(victim.run_queue_len(Priority::Normal), 0);
+    assert_eq!(thief.run_queue_len(Priority::Normal), 1);
+
+    match thief.run_queues.write().dequeue() {
+        Run::Now(arc_process) => assert_eq!(arc_process.scheduler_id(), Some(thief.id)),
+        run => panic!("Expected Run::Now, but got {:?}", run),
+    }
+}
+
+#[test]
+fn steal_from_without_runnable_process_steals_nothing() {
+    let victim = thread::spawn(|| current()).join().unwrap();
+    let thief = current();
+
+    assert!(!downcast(&thief).steal_from(downcast(&victim)));
+}
+
+#[test]
+fn steal_from_keeps_remaining_delay_of_low_priority_process() {
+    let victim = scheduler_with_process_on_other_thread(Priority::Low);
+    let victim = downcast(&victim);
+
+    // Low priority processes start with a delay of 7, so after 1 `dequeue` 6 remain
+    match victim.run_queues.write().dequeue() {
+        Run::Delayed => (),
+        run => panic!("Expected Run::Delayed, but got {:?}", run),
+    }
+
+    let thief = current();
+    let thief = downcast(&thief);
+
+    assert!(thief.steal_from(victim));
+
+    for _ in 0..6 {
+        match thief.run_queues.write().dequeue() {
+            Run::Delayed => (),
+            run => panic!("Expected Run::Delayed, but got {:?}", run),
+        }
+    }
+
+    match thief.run_queues.write().dequeue() {
+        Run::Now(_) => (),
+        run => panic!("Expected Run::Now, but got {:?}", run),
+    }
+}
+
+#[test]
+fn pool_parks_idle_schedulers_and_shuts_down() {
+    let pool = Pool::start(3).unwrap();
+
+    // Idle pool threads have nothing to run or steal, so they park instead of spinning
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while parked_count() < 2 {
+        assert!(Instant::now() < deadline, "pool schedulers never parked");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    thread::spawn(move || {
+        pool.shutdown();
+        sender.send(()).unwrap();
+    });
+
+    // parked schedulers are woken to see the shutdown
+    receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("pool did not shut down");
+}
+
+#[test]
+fn wake_before_park_is_not_lost() {
+    let scheduler = current();
+    let scheduler = downcast(&scheduler);
+
+    scheduler.wake();
+
+    // would block forever without the wake up, as there are no timers
+    scheduler.park();
+
+    assert!(!*scheduler.woken.lock());
+}
+
+fn downcast(arc_dyn_scheduler: &Arc<dyn SchedulerTrait>) -> &Scheduler {
+    arc_dyn_scheduler
+        .as_any()
+        .downcast_ref::<Scheduler>()
+        .unwrap()
+}
+
+fn parked_count() -> usize {
+    lumen_rt_core::scheduler::all()
+        .iter()
+        .filter(|arc_dyn_scheduler| downcast(arc_dyn_scheduler).parked.load(Ordering::SeqCst))
+        .count()
+}
+
+/// Each thread has its own scheduler, so a scheduler for another thread is needed to steal from.
+fn scheduler_with_process_on_other_thread(priority: Priority) -> Arc<dyn SchedulerTrait> {
+    thread::spawn(move || {
+        let scheduler = current();
+        scheduler.schedule(runnable_process(priority));
+
+        scheduler
+    })
+    .join()
+    .unwrap()
+}
+
+fn runnable_process(priority: Priority) -> Process {
+    let (heap, heap_size) = Options::default().sized_heap().unwrap();
+    let frame = out_of_code::frame();
+    let process = Process::new(
+        priority,
+        None,
+        frame.module_function_arity(),
+        heap,
+        heap_size,
+    );
+
+    process.runnable(|| {
+        process.queue_frame_with_arguments(frame.with_arguments(false, &[]));
+        process.stack_queued_frames_with_arguments();
+    });
+
+    process
+}
@@ -39,6 +39,11 @@ pub fn num_total() -> usize {
     get_num_cpus()
 }
 
+#[export_name = "lumen_rt_sys_cpus_num_logical"]
+fn export_num_logical() -> usize {
+    num_logical()
+}
+
 #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
 #[inline]
 fn get_num_physical_cpus() -> usize {
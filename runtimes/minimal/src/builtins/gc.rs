@@ -2,6 +2,7 @@ use core::ptr;
 
 use stackmaps::{FrameInfo, StackMap};
 
+use liblumen_alloc::erts::process::gc::GcError;
 use liblumen_alloc::erts::term::prelude::{Boxed, Encoded, Term};
 use lumen_rt_core::process::current_process;
 
@@ -60,6 +61,11 @@ pub unsafe extern "C" fn builtin_gc_run(
     let roots = iter.collect::<Vec<_>>();
     match current_process().garbage_collect(1, roots) {
         Ok(_) => true,
+        Err(GcError::MaxHeapSizeExceeded) => {
+            current_process().exit_killed();
+
+            false
+        }
         Err(err) => panic!("garbage collection failed: {}", err),
     }
 }
@@ -10,6 +10,7 @@ use liblumen_alloc::erts::timeout::{ReceiveTimeout, Timeout};
 use lumen_rt_core::process::current_process;
 use lumen_rt_core::time::monotonic;
 use lumen_rt_core::timer::{self, SourceEvent};
+use lumen_rt_core::{seq_trace, trace};
 
 extern "C" {
     #[link_name = "__lumen_builtin_yield"]
@@ -101,7 +102,7 @@ pub extern "C" fn builtin_receive_start(timeout: Term) -> *mut ReceiveContext {
     // could keep it on the stack rather than heap allocate here
     let p = current_process();
     let context = Box::new(ReceiveContext::new(p.clone(), to));
-    let mbox = p.mailbox.lock();
+    let mbox = p.acquire_mailbox();
     mbox.borrow().recv_start();
     Box::into_raw(context)
 }
@@ -113,7 +114,7 @@ pub extern "C" fn builtin_receive_wait(ctx: *mut ReceiveContext) -> ReceiveState
     loop {
         {
             let p = current_process();
-            let mbox_lock = p.mailbox.lock();
+            let mbox_lock = p.acquire_mailbox();
             let mut mbox = mbox_lock.borrow_mut();
             if let Some(msg) = mbox.recv_peek() {
                 mbox.recv_increment();
@@ -148,15 +149,17 @@ pub extern "C" fn builtin_receive_message(ctx: *mut ReceiveContext) -> Term {
 pub extern "C" fn builtin_receive_done(ctx: *mut ReceiveContext) -> bool {
     let result = panic::catch_unwind(|| {
         let p = current_process();
-        let mbox_lock = p.mailbox.lock();
-        let mut mbox = mbox_lock.borrow_mut();
 
         let mut context = unsafe { Box::from_raw(ctx) };
         context.cancel_timer();
 
         match context.state {
             ReceiveState::Received => {
-                mbox.recv_received();
+                let message = p.acquire_mailbox().borrow_mut().recv_received();
+
+                // The mailbox lock is released first, as the process may be the system tracer
+                seq_trace::receive(&p, message.seq_trace_token(), context.message);
+                trace::receive(&p, context.message);
             }
             receive_state => {
                 unreachable!(
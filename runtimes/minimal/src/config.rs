@@ -1,17 +1,11 @@
-use std::collections::HashMap;
-use std::ffi::{OsStr, OsString};
-use std::fs;
-use std::io;
-use std::path::Path;
-
 use clap::{App, AppSettings, Arg, SubCommand};
 
-pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
-//TODO: Needs to be HashMap<Atom, HashMap<Atom, Term>>
-pub type AppConfig = HashMap<String, HashMap<String, String>>;
-pub type BootScript = Vec<BootInstruction>;
-//TODO: Needs to be Term
-pub type BootInstruction = String;
+use lumen_rt_core::config::vm_args::{self, VmArgs};
+use lumen_rt_core::config::{self, load_app_config, load_boot_script, load_vm_args};
+
+pub use lumen_rt_core::config::boot_script::BootScript;
+pub use lumen_rt_core::config::sys_config::AppConfig;
+pub use lumen_rt_core::config::{ConfigError, ConfigResult};
 
 pub enum Command {
     Run,
@@ -19,38 +13,12 @@ pub enum Command {
     RemoteShell(String),
 }
 
-#[derive(Debug)]
-pub enum ConfigError {
-    FileError(OsString, io::Error),
-}
-
-impl std::fmt::Display for ConfigError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match *self {
-            ConfigError::FileError(ref path, ref err) => write!(
-                f,
-                "Failed to load {}: {}",
-                path.to_string_lossy(),
-                err.to_string()
-            ),
-        }
-    }
-}
-
-impl std::error::Error for ConfigError {
-    fn cause(&self) -> Option<&dyn std::error::Error> {
-        match *self {
-            ConfigError::FileError(ref _path, ref err) => Some(err),
-        }
-    }
-}
-
 pub struct Config {
     pub config: AppConfig,
     pub boot: Option<BootScript>,
     pub debug: bool,
-    pub name: Option<String>,
-    pub cookie: Option<String>,
+    /// The flags from `--args_file` files, overridden by those given on the command line
+    pub vm_args: VmArgs,
     pub command: Command,
     pub extra: Vec<String>,
 }
@@ -122,44 +90,42 @@ impl Config {
             };
             command = Command::Run;
         }
+        let mut args = load_vm_args(matches.values_of_os("args_file").into_iter().flatten())?;
+        // Flags given on the command line override those in `vm.args`
+        args.merge(VmArgs {
+            name: matches.value_of("name").map(vm_args::node_name),
+            cookie: matches.value_of("cookie").map(|v| v.to_string()),
+            ..Default::default()
+        });
+
+        // There is only ever one scheduler, so more can't be started as asked
+        match args.schedulers {
+            Some(schedulers) if schedulers != 1 => {
+                return Err(ConfigError::Unsupported(format!(
+                    "+S {} as the minimal runtime only runs 1 scheduler",
+                    schedulers
+                )))
+            }
+            _ => (),
+        }
+
         Ok(Config {
-            config: with_file(
-                matches.value_of_os("config"),
-                AppConfig::new(),
-                load_app_config,
-            )?,
-            boot: with_file(matches.value_of_os("boot"), None, load_boot_script)?,
+            config: load_app_config(matches.values_of_os("config").into_iter().flatten())?,
+            boot: load_boot_script(matches.value_of_os("boot"))?,
             debug: matches.is_present("debug"),
-            name: matches.value_of("name").map(|v| v.to_string()),
-            cookie: matches.value_of("cookie").map(|v| v.to_string()),
+            vm_args: args,
             command,
             extra: extra.iter().map(|v| v.to_string()).collect(),
         })
     }
+
+    /// Applies the configuration to the runtime.  Must be called before any process is spawned.
+    pub fn apply(&self) {
+        config::apply(&self.vm_args, &self.config, self.boot.as_ref());
+    }
 }
 
 fn is_valid_node_name(_f: String) -> Result<(), String> {
     //TODO: Validate name
     Ok(())
 }
-
-fn with_file<T>(v: Option<&OsStr>, default: T, fun: fn(String) -> T) -> ConfigResult<T> {
-    match v {
-        None => Ok(default),
-        Some(p) => {
-            let path = Path::new(p);
-            match fs::read_to_string(path) {
-                Err(err) => Err(ConfigError::FileError(p.to_os_string(), err)),
-                Ok(contents) => Ok(fun(contents)),
-            }
-        }
-    }
-}
-
-fn load_app_config(_contents: String) -> AppConfig {
-    AppConfig::new()
-}
-
-fn load_boot_script(_contents: String) -> Option<BootScript> {
-    None
-}
@@ -26,7 +26,8 @@ pub mod sys;
 use liblumen_alloc::erts::process::alloc::default_heap_size;
 
 pub use lumen_rt_core::{
-    binary_to_string, context, distribution, proplist, registry, send, time, timer,
+    application, binary_to_string, boot, context, distribution, ets, port, proplist, registry, send,
+    time, timer,
 };
 
 use bus::Bus;
@@ -45,12 +46,21 @@ fn main() -> impl ::std::process::Termination + 'static {
 fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()> {
     self::env::init_argv_from_slice(std::env::args_os()).unwrap();
     // Load system configuration
-    let _config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
+    let config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
         Ok(config) => config,
         Err(err) => {
             panic!("Config error: {}", err);
         }
     };
+    config.apply();
+
+    // Named nodes listen for connections from other nodes
+    if config.vm_args.name.is_some() {
+        if let Err(err) = distribution::start() {
+            eprintln!("Distribution error: {}", err);
+            return Err(());
+        }
+    }
 
     // This bus is used to receive signals across threads in the system
     let mut bus: Bus<break_handler::Signal> = Bus::new(1);
@@ -93,8 +103,10 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
                 _ => (),
             }
         }
-        // If the scheduler scheduled a process this cycle, then we're busy
-        // and should keep working until we have an idle period
+        // If the scheduler scheduled a process this cycle, or parked until a
+        // waiting process could be woken, then there are still live processes
+        // and we should keep working.  Only when no processes remain do we
+        // shut down.
         if scheduled {
             continue;
         }
@@ -6,10 +6,11 @@ use std::mem;
 use std::ptr::{self, NonNull};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use log::info;
 
-use liblumen_core::locks::RwLock;
+use liblumen_core::locks::{Condvar, Mutex, RwLock};
 use liblumen_core::sys::dynamic_call::DynamicCallee;
 use liblumen_core::util::thread_local::ThreadLocalCell;
 
@@ -20,15 +21,20 @@ use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::ModuleFunctionArity;
 use liblumen_alloc::{Arity, CloneToProcess};
 
+use lumen_rt_core::boot;
+use lumen_rt_core::process::spawn::check_system_limit;
 use lumen_rt_core::process::spawn::options::Options;
 use lumen_rt_core::process::{log_exit, propagate_exit, CURRENT_PROCESS};
 use lumen_rt_core::registry::put_pid_to_process;
 use lumen_rt_core::scheduler::Scheduler as SchedulerTrait;
 use lumen_rt_core::scheduler::{self, run_queue, unregister, Run};
+use lumen_rt_core::statistics;
 pub use lumen_rt_core::scheduler::{
     current, from_id, run_through, Scheduled, SchedulerDependentAlloc, Spawned,
 };
+use lumen_rt_core::time::{self, monotonic};
 use lumen_rt_core::timer::Hierarchy;
+use lumen_rt_core::trace;
 
 // External thread locals owned by the generated code
 extern "C" {
@@ -57,6 +63,16 @@ crate fn stop_waiting(process: &Process) {
     }
 }
 
+/// Wakes all schedulers that are parked waiting for a timer or a message, such as when a signal
+/// is received that they need to check for.
+crate fn wake_all() {
+    for arc_dyn_scheduler in scheduler::all() {
+        if let Some(scheduler) = arc_dyn_scheduler.as_any().downcast_ref::<Scheduler>() {
+            scheduler.wake();
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 struct StackPointer(*mut u64);
 
@@ -147,6 +163,10 @@ pub struct Scheduler {
     root: Arc<Process>,
     init: ThreadLocalCell<Arc<Process>>,
     current: ThreadLocalCell<Arc<Process>>,
+    // Set when a waiting process is made runnable or the scheduler is otherwise woken, so that a
+    // wake up that happens before the scheduler parks is not lost.
+    woken: Mutex<bool>,
+    wake: Condvar,
 }
 // This guarantee holds as long as `init` and `current` are only
 // ever accessed by the scheduler when scheduling
@@ -198,9 +218,43 @@ impl Scheduler {
             hierarchy: Default::default(),
             reference_count: AtomicU64::new(0),
             unique_integer: AtomicU64::new(0),
+            woken: Mutex::new(false),
+            wake: Condvar::new(),
         })
     }
 
+    /// Wakes the scheduler if it is parked, or prevents it from parking on its next idle cycle if
+    /// it is not.
+    pub fn wake(&self) {
+        let mut woken = self.woken.lock();
+        *woken = true;
+        self.wake.notify_one();
+    }
+
+    /// Blocks the scheduler thread when all of its processes are waiting, until the next timer
+    /// deadline or until it is woken by a process being made runnable from another thread or a
+    /// signal.
+    fn park(&self) {
+        let option_deadline = self.hierarchy.read().next_deadline();
+        let mut woken = self.woken.lock();
+
+        if !*woken {
+            match option_deadline {
+                Some(deadline) => {
+                    // A deadline that has already passed will time out on the next cycle, so
+                    // there is no need to park
+                    if let Some(milliseconds) = deadline.checked_sub(monotonic::time()) {
+                        let timeout = Duration::from_millis(milliseconds.as_u64());
+                        self.wake.wait_for(&mut woken, timeout);
+                    }
+                }
+                None => self.wake.wait(&mut woken),
+            }
+        }
+
+        *woken = false;
+    }
+
     /// Returns true if the given process is in the current scheduler's run queue
     #[cfg(test)]
     pub fn is_run_queued(&self, value: &Arc<Process>) -> bool {
@@ -288,13 +342,9 @@ impl SchedulerTrait for Scheduler {
         let mut options: Options = Default::default();
         options.min_heap_size = Some(minimum_heap_size);
 
-        let Spawned { arc_process, .. } = self.spawn_module_function_arguments(
-            None,
-            Atom::from_str("init"),
-            Atom::from_str("start"),
-            vec![],
-            options,
-        )?;
+        let (module, function) = boot::init_module_function();
+        let Spawned { arc_process, .. } =
+            self.spawn_module_function_arguments(None, module, function, vec![], options)?;
 
         unsafe {
             self.init.set(arc_process.clone());
@@ -310,6 +360,7 @@ impl SchedulerTrait for Scheduler {
         closure: Boxed<Closure>,
         options: Options,
     ) -> anyhow::Result<Spawned> {
+        check_system_limit()?;
         let (heap, heap_size) = options.sized_heap()?;
         let priority = options.cascaded_priority(parent);
         let initial_module_function_arity = closure.module_function_arity();
@@ -345,6 +396,7 @@ impl SchedulerTrait for Scheduler {
         arguments: Vec<Term>,
         options: Options,
     ) -> anyhow::Result<Spawned> {
+        check_system_limit()?;
         let (heap, heap_size) = options.sized_heap()?;
         let priority = options.cascaded_priority(parent);
 
@@ -393,6 +445,7 @@ impl SchedulerTrait for Scheduler {
     fn stop_waiting(&self, process: &Process) {
         process.stop_waiting();
         self.run_queues.write().stop_waiting(process);
+        self.wake();
     }
 }
 
@@ -426,6 +479,7 @@ impl Scheduler {
         info!("entering core scheduler loop");
 
         self.hierarchy.write().timeout();
+        time::offset::check();
 
         loop {
             let next = {
@@ -447,6 +501,7 @@ impl Scheduler {
                         // is executed when that process has yielded and we're resetting
                         // the state of the scheduler such that the "current process" is
                         // the scheduler itself
+                        trace::running(&process, true);
                         unsafe {
                             self.swap_process(process);
                         }
@@ -458,11 +513,13 @@ impl Scheduler {
                         // and handling its exit, if exiting
                         let _ = CURRENT_PROCESS.with(|cp| cp.replace(Some(self.root.clone())));
                         let prev = unsafe { self.current.replace(self.root.clone()) };
+                        trace::running(&prev, false);
 
                         // Increment reduction count if not the root process
                         let prev_reductions = reset_reduction_counter();
                         prev.total_reductions
                             .fetch_add(prev_reductions as u64, Ordering::Relaxed);
+                        statistics::ran(prev_reductions as u64);
 
                         // Change the previous process status to Runnable
                         {
@@ -513,9 +570,11 @@ impl Scheduler {
                     continue;
                 }
                 Run::Waiting => {
-                    info!("exiting scheduler loop because waiting");
-                    // Return to main scheduler loop to check for signals and to re-enter from
+                    info!("parking scheduler because all processes are waiting");
+                    // Sleep until the next timer deadline or a wake up instead of spinning, then
+                    // return to main scheduler loop to check for signals and to re-enter from
                     // `run_once` and increment timeouts to knock out of waiting.
+                    self.park();
                     break true;
                 }
                 Run::None if self.current.pid() == self.root.pid() => {
@@ -830,3 +889,96 @@ unsafe extern "C" fn swap_stack(prev: *mut CalleeSavedRegisters, new: *const Cal
     );
     core::intrinsics::unreachable();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+    use std::time::Instant;
+
+    use lumen_rt_core::timer::SourceEvent;
+
+    #[test]
+    fn wake_before_park_is_not_lost() {
+        let scheduler = Scheduler::new().unwrap();
+
+        scheduler.wake();
+
+        // would block forever as there are no timers if the wake up was lost
+        scheduler.park();
+
+        assert!(!*scheduler.woken.lock());
+    }
+
+    #[test]
+    fn park_is_woken_from_another_thread() {
+        let scheduler = Arc::new(Scheduler::new().unwrap());
+        let waker = Arc::clone(&scheduler);
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            waker.wake();
+        });
+
+        scheduler.park();
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn park_returns_at_next_timer_deadline() {
+        let scheduler = Arc::new(Scheduler::new().unwrap());
+        let arc_scheduler: Arc<dyn SchedulerTrait> = scheduler.clone();
+        let milliseconds = 50;
+        let deadline = monotonic::time() + Duration::from_millis(milliseconds);
+
+        scheduler
+            .hierarchy
+            .write()
+            .start(
+                deadline,
+                SourceEvent::StopWaiting,
+                scheduler.root.clone(),
+                arc_scheduler,
+            )
+            .unwrap();
+
+        assert_eq!(scheduler.hierarchy.read().next_deadline(), Some(deadline));
+
+        let start = Instant::now();
+
+        scheduler.park();
+
+        let elapsed = start.elapsed();
+
+        assert!(Duration::from_millis(milliseconds - 1) <= elapsed);
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn park_does_not_block_when_deadline_has_passed() {
+        let scheduler = Arc::new(Scheduler::new().unwrap());
+        let arc_scheduler: Arc<dyn SchedulerTrait> = scheduler.clone();
+        let deadline = monotonic::time();
+
+        scheduler
+            .hierarchy
+            .write()
+            .start(
+                deadline,
+                SourceEvent::StopWaiting,
+                scheduler.root.clone(),
+                arc_scheduler,
+            )
+            .unwrap();
+
+        thread::sleep(Duration::from_millis(2));
+
+        let start = Instant::now();
+
+        scheduler.park();
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
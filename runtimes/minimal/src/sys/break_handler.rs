@@ -61,7 +61,12 @@ pub fn init(mut bus: Bus<Signal>) {
         for signal in signals.forever() {
             match Signal::from(signal as usize) {
                 Signal::Unknown => (),
-                sig => bus.broadcast(sig),
+                sig => {
+                    bus.broadcast(sig);
+                    // Schedulers may be parked with all processes waiting, so they need to be
+                    // woken to see the signal
+                    crate::scheduler::wake_all();
+                }
             }
         }
     });